@@ -1,8 +1,4 @@
-use baby_shark::{
-    io::stl::{StlReader, StlWriter},
-    mesh::polygon_soup::data_structure::PolygonSoup,
-    voxel::prelude::*,
-};
+use baby_shark::prelude::*;
 use nalgebra_glm::Vec3;
 use std::path::Path;
 
@@ -15,10 +11,7 @@ fn main() {
         .expect("Read mesh");
 
     // Convert bunny mesh to volume
-    let bunny_volume = MeshToVolume::default()
-        .with_voxel_size(voxel_size)
-        .convert(&bunny_mesh)
-        .unwrap();
+    let bunny_volume = Volume::from_mesh(&bunny_mesh, voxel_size).unwrap();
 
     // Create a volume of boxes
     let builder = VolumeBuilder::default().with_voxel_size(voxel_size);
@@ -40,10 +33,9 @@ fn main() {
 }
 
 fn write_volume_to_stl(volume: &Volume, path: &str) {
-    let vertices = MarchingCubesMesher::default()
-        .with_voxel_size(volume.voxel_size())
-        .mesh(volume);
-    let mesh = PolygonSoup::from_vertices(vertices);
+    let mesh = volume
+        .to_mesh(MesherKind::MarchingCubes)
+        .expect("Volume should have surface");
 
     StlWriter::new()
         .write_stl_to_file(&mesh, Path::new(path))
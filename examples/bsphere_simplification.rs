@@ -1,10 +1,6 @@
 use std::path::Path;
 
-use baby_shark::{
-    decimation::{edge_decimation::BoundingSphereDecimationCriteria, prelude::EdgeDecimator},
-    io::stl::{StlReader, StlWriter},
-    mesh::corner_table::prelude::CornerTableF,
-};
+use baby_shark::prelude::*;
 use nalgebra::Vector3;
 
 fn main() {
@@ -1,6 +1,4 @@
-use baby_shark::{
-    io::stl::StlWriter, mesh::polygon_soup::data_structure::PolygonSoup, voxel::prelude::*,
-};
+use baby_shark::prelude::*;
 use nalgebra_glm::Vec3;
 use std::path::Path;
 
@@ -14,9 +12,7 @@ fn main() {
     let bunny_volume = cube.subtract(sphere);
 
     // Convert volume to mesh and write to STL
-    let mut mesher = DualContouringMesher::default().with_voxel_size(voxel_size);
-    let vertices = mesher.mesh(&bunny_volume).unwrap();
-    let mesh = PolygonSoup::from_vertices(vertices);
+    let mesh = bunny_volume.to_mesh(MesherKind::DualContouring).unwrap();
 
     StlWriter::new()
         .write_stl_to_file(&mesh, Path::new("sub.stl"))
@@ -1,10 +1,6 @@
 use std::path::Path;
 
-use baby_shark::{
-    io::stl::{StlReader, StlWriter},
-    mesh::corner_table::prelude::CornerTableF,
-    remeshing::incremental::IncrementalRemesher,
-};
+use baby_shark::prelude::*;
 
 fn main() {
     let mut reader = StlReader::new();
@@ -1,8 +1,4 @@
-use baby_shark::{
-    io::stl::{StlReader, StlWriter},
-    mesh::polygon_soup::data_structure::PolygonSoup,
-    voxel::prelude::*,
-};
+use baby_shark::prelude::*;
 use nalgebra_glm::Vec3;
 use std::path::Path;
 
@@ -38,7 +34,7 @@ fn main() {
     // Convert volume to mesh and write to STL
     let vertices = DualContouringMesher::default()
         .with_voxel_size(voxel_size)
-        .mesh(&bunny)
+        .mesh_triangles(&bunny)
         .expect("Should convert volume to mesh");
     let mesh = PolygonSoup::from_vertices(vertices);
 
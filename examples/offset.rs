@@ -1,8 +1,4 @@
-use baby_shark::{
-    io::stl::{StlReader, StlWriter},
-    mesh::polygon_soup::data_structure::PolygonSoup,
-    voxel::prelude::*,
-};
+use baby_shark::prelude::*;
 use std::path::Path;
 
 fn main() {
@@ -28,7 +24,7 @@ fn main() {
 fn write_volume_to_stl(volume: &Volume, path: &str) {
     let vertices = MarchingCubesMesher::default()
         .with_voxel_size(volume.voxel_size())
-        .mesh(volume);
+        .mesh_triangles(volume);
     let mesh = PolygonSoup::from_vertices(vertices);
 
     StlWriter::new()
@@ -1,10 +1,6 @@
 use std::path::Path;
 
-use baby_shark::{
-    decimation::{edge_decimation::ConstantErrorDecimationCriteria, prelude::EdgeDecimator},
-    io::stl::{StlReader, StlWriter},
-    mesh::corner_table::prelude::CornerTableF,
-};
+use baby_shark::prelude::*;
 
 fn main() {
     let mut reader = StlReader::new();
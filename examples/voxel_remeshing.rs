@@ -1,10 +1,6 @@
 use std::path::Path;
 
-use baby_shark::{
-    io::stl::{StlReader, StlWriter},
-    mesh::polygon_soup::data_structure::PolygonSoup,
-    remeshing::voxel::VoxelRemesher,
-};
+use baby_shark::prelude::*;
 
 fn main() {
     type Mesh = PolygonSoup<f32>;
@@ -0,0 +1,169 @@
+use num_traits::{cast, Float, Zero};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{
+    geometry::{
+        primitives::{line_segment3::LineSegment3, triangle3::Triangle3},
+        traits::RealNumber,
+    },
+    helpers::aliases::Vec3,
+    mesh::traits::Mesh,
+    spatial_partitioning::aabb_tree::{AABBTree, MedianCut},
+};
+
+/// Rays start above surface at this fraction of occlusion radius, so they do not hit faces around their vertex
+const RAY_OFFSET_FACTOR: f64 = 1e-3;
+
+///
+/// Bakes per-vertex ambient occlusion. For every vertex `samples` rays of length `radius` are cast over hemisphere
+/// around vertex normal (cosine-weighted), occlusion is fraction of rays hitting mesh: `0` is fully open, `1` is fully occluded.
+/// Ray directions are fixed (Fibonacci spiral), so result is deterministic. Vertices without normal have zero occlusion.
+///
+/// Returns occlusion of every vertex in order of [Mesh::vertices] iterator.
+///
+/// ## Example
+/// ```ignore
+/// let occlusion = ao_bake(&mesh, 64, 0.5);
+/// ```
+///
+pub fn ao_bake<TMesh: Mesh>(mesh: &TMesh, samples: usize, radius: TMesh::ScalarType) -> Vec<TMesh::ScalarType> {
+    let tree = AABBTree::from_mesh(mesh).top_down::<MedianCut>();
+    let directions = hemisphere_directions::<TMesh::ScalarType>(samples);
+    let offset = radius * cast(RAY_OFFSET_FACTOR).unwrap();
+
+    let vertices: Vec<_> = mesh
+        .vertices()
+        .map(|vertex| (*mesh.vertex_position(&vertex), mesh.vertex_normal(&vertex)))
+        .collect();
+
+    #[cfg(feature = "rayon")]
+    let vertices_iter = vertices.par_iter();
+    #[cfg(not(feature = "rayon"))]
+    let vertices_iter = vertices.iter();
+
+    vertices_iter
+        .map(|(position, normal)| match normal {
+            Some(normal) => vertex_occlusion(&tree, position, normal, &directions, radius, offset),
+            None => TMesh::ScalarType::zero(),
+        })
+        .collect()
+}
+
+///
+/// Returns `true` when segment between `from` and `to` does not intersect any triangle of `tree`.
+///
+/// ## Example
+/// ```ignore
+/// let tree = AABBTree::from_mesh(&mesh).top_down::<MedianCut>();
+/// let visible = line_of_sight(&tree, &camera, &target);
+/// ```
+///
+pub fn line_of_sight<TScalar: RealNumber>(tree: &AABBTree<Triangle3<TScalar>>, from: &Vec3<TScalar>, to: &Vec3<TScalar>) -> bool {
+    !tree.intersects_line_segment(&LineSegment3::new(from, to))
+}
+
+/// Returns fraction of hemisphere rays around `normal` hitting mesh
+fn vertex_occlusion<TScalar: RealNumber>(
+    tree: &AABBTree<Triangle3<TScalar>>,
+    position: &Vec3<TScalar>,
+    normal: &Vec3<TScalar>,
+    directions: &[Vec3<TScalar>],
+    radius: TScalar,
+    offset: TScalar,
+) -> TScalar {
+    if directions.is_empty() {
+        return TScalar::zero();
+    }
+
+    let (tangent, bitangent) = tangent_frame(normal);
+    let origin = position + normal * offset;
+
+    let hits = directions
+        .iter()
+        .filter(|local| {
+            let direction = tangent * local.x + bitangent * local.y + normal * local.z;
+            !line_of_sight(tree, &origin, &(origin + direction * radius))
+        })
+        .count();
+
+    cast::<usize, TScalar>(hits).unwrap() / cast(directions.len()).unwrap()
+}
+
+/// Cosine-weighted directions over hemisphere around +Z axis
+fn hemisphere_directions<TScalar: RealNumber>(count: usize) -> Vec<Vec3<TScalar>> {
+    let golden_angle: TScalar = cast(std::f64::consts::PI * (3.0 - 5.0.sqrt())).unwrap();
+    let half: TScalar = cast(0.5).unwrap();
+
+    (0..count)
+        .map(|i| {
+            // Uniform points on disk projected to hemisphere give cosine-weighted distribution
+            let r = Float::sqrt((cast::<usize, TScalar>(i).unwrap() + half) / cast(count).unwrap());
+            let phi = golden_angle * cast(i).unwrap();
+            let z = Float::sqrt(Float::max(TScalar::one() - r * r, TScalar::zero()));
+
+            Vec3::new(r * Float::cos(phi), r * Float::sin(phi), z)
+        })
+        .collect()
+}
+
+/// Returns two unit vectors orthogonal to unit `normal` and to each other
+fn tangent_frame<TScalar: RealNumber>(normal: &Vec3<TScalar>) -> (Vec3<TScalar>, Vec3<TScalar>) {
+    let axis = if Float::abs(normal.x) < cast(0.9).unwrap() {
+        Vec3::x()
+    } else {
+        Vec3::y()
+    };
+
+    let tangent = normal.cross(&axis).normalize();
+    let bitangent = normal.cross(&tangent);
+
+    (tangent, bitangent)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{ao_bake, line_of_sight};
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+        spatial_partitioning::aabb_tree::{AABBTree, MedianCut},
+    };
+
+    #[test]
+    fn test_ao_bake() {
+        let sphere: CornerTableF = primitives::uv_sphere(1.0, 32, 16);
+        assert!(ao_bake(&sphere, 32, 1.0).iter().all(|occlusion| *occlusion == 0.0));
+
+        // Plane covered by another plane above it
+        let plane: CornerTableF = primitives::plane(2.0, 2.0, 8, 8);
+        let mut positions: Vec<_> = plane.vertices().map(|v| *plane.vertex_position(&v)).collect();
+        let mut indices: Vec<_> = plane
+            .faces()
+            .flat_map(|face| {
+                let (v1, v2, v3) = plane.face_vertices(&face);
+                [v1, v2, v3].map(|v| plane.vertices().position(|x| x == v).unwrap())
+            })
+            .collect();
+
+        let count = positions.len();
+        positions.extend_from_within(..);
+        positions[count..].iter_mut().for_each(|p| p.z = 0.2);
+        indices.extend_from_within(..);
+        let faces_len = indices.len() / 2;
+        indices[faces_len..].iter_mut().for_each(|i| *i += count);
+
+        let planes = CornerTableF::from_vertices_and_indices(&positions, &indices);
+        let center = planes.vertices().position(|v| *planes.vertex_position(&v) == Vec3f::zeros()).unwrap();
+
+        let occlusion = ao_bake(&planes, 64, 1.0);
+        assert!(occlusion[center] > 0.9);
+        assert!(occlusion.iter().all(|o| (0.0..=1.0).contains(o)));
+        assert_eq!(ao_bake(&planes, 64, 0.1)[center], 0.0);
+
+        let tree = AABBTree::from_mesh(&planes).top_down::<MedianCut>();
+        assert!(line_of_sight(&tree, &Vec3f::new(0.0, 0.0, 0.1), &Vec3f::new(3.0, 0.0, 0.1)));
+        assert!(!line_of_sight(&tree, &Vec3f::new(0.0, 0.0, 0.1), &Vec3f::new(0.0, 0.0, 1.0)));
+        assert!(!line_of_sight(&tree, &Vec3f::new(0.3, 0.2, 1.0), &Vec3f::new(0.0, 0.1, -1.0)));
+    }
+}
@@ -0,0 +1,96 @@
+use std::collections::{HashMap, HashSet};
+
+use num_traits::{cast, Float, One};
+
+use crate::{helpers::aliases::Vec3, mesh::traits::Mesh};
+
+///
+/// Fast simplification by vertex clustering. Space is split into uniform grid of cubic cells with side `cell_size`,
+/// vertices inside one cell are merged into single vertex placed at their average position.
+/// Faces that collapse to edge or point and duplicated faces are removed.
+///
+/// Runs in linear time, so it is suited for quick extreme reduction of massive meshes before precise decimation.
+/// Details smaller than cell size are lost and topology is not preserved: output may be non-manifold.
+///
+/// ## Example
+/// ```ignore
+/// let coarse: CornerTableF = cluster_decimate(&scan, 0.5);
+/// ```
+///
+pub fn cluster_decimate<TMesh: Mesh>(mesh: &TMesh, cell_size: TMesh::ScalarType) -> TMesh {
+    let inv_cell_size = TMesh::ScalarType::one() / cell_size;
+
+    // Sum of positions and number of vertices of each cluster
+    let mut clusters: Vec<(Vec3<TMesh::ScalarType>, usize)> = Vec::new();
+    let mut cell_cluster = HashMap::new();
+    let mut vertex_cluster = HashMap::new();
+
+    for vertex in mesh.vertices() {
+        let position = *mesh.vertex_position(&vertex);
+        let cell = position.map(|c| cast::<_, isize>(Float::floor(c * inv_cell_size)).unwrap_or(0));
+
+        let cluster = *cell_cluster.entry(cell).or_insert_with(|| {
+            clusters.push((Vec3::zeros(), 0));
+            clusters.len() - 1
+        });
+
+        clusters[cluster].0 += position;
+        clusters[cluster].1 += 1;
+        vertex_cluster.insert(vertex, cluster);
+    }
+
+    let mut faces = HashSet::new();
+    let mut indices = Vec::new();
+
+    for face in mesh.faces() {
+        let (v1, v2, v3) = mesh.face_vertices(&face);
+        let (c1, c2, c3) = (vertex_cluster[&v1], vertex_cluster[&v2], vertex_cluster[&v3]);
+
+        if c1 == c2 || c2 == c3 || c3 == c1 {
+            continue;
+        }
+
+        // Same face may appear with any orientation, first occurrence is kept
+        let mut key = [c1, c2, c3];
+        key.sort_unstable();
+
+        if faces.insert(key) {
+            indices.extend_from_slice(&[c1, c2, c3]);
+        }
+    }
+
+    // Keep only clusters used by remaining faces
+    let mut used = vec![None; clusters.len()];
+    let mut vertices = Vec::new();
+
+    for index in indices.iter_mut() {
+        *index = *used[*index].get_or_insert_with(|| {
+            let (sum, count) = clusters[*index];
+            vertices.push(sum / cast::<usize, TMesh::ScalarType>(count).unwrap());
+            vertices.len() - 1
+        });
+    }
+
+    TMesh::from_vertices_and_indices(&vertices, &indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::cluster_decimate;
+    use crate::mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh};
+
+    #[test]
+    fn test_cluster_decimate() {
+        let sphere: CornerTableF = primitives::uv_sphere(1.0, 128, 64);
+        let decimated = cluster_decimate(&sphere, 0.25);
+
+        assert!(decimated.faces().count() > 0);
+        assert!(decimated.faces().count() * 10 < sphere.faces().count());
+
+        // Vertices are averages of clustered vertices, so they stay close to surface
+        for vertex in decimated.vertices() {
+            let radius = decimated.vertex_position(&vertex).norm();
+            assert!(radius <= 1.0 && radius > 0.9);
+        }
+    }
+}
@@ -0,0 +1,349 @@
+use std::collections::HashSet;
+
+use crate::{
+    algo::{convex_hull::convex_hull, merge_points::IndexedVertices},
+    geometry::primitives::box3::Box3,
+    helpers::aliases::{Vec3f, Vec3i},
+    mesh::traits::Mesh,
+    spatial_partitioning::aabb_tree::winding_numbers::WindingNumbers,
+};
+
+/// Number of splitting planes evaluated along every axis when part is split
+const PLANES_PER_AXIS: isize = 16;
+
+/// Voxel centers closer to hull faces than this fraction of voxel size are on faces
+const COLUMN_TOLERANCE: f32 = 1e-3;
+
+const NEIGHBORS: [Vec3i; 6] = [
+    Vec3i::new(1, 0, 0),
+    Vec3i::new(-1, 0, 0),
+    Vec3i::new(0, 1, 0),
+    Vec3i::new(0, -1, 0),
+    Vec3i::new(0, 0, 1),
+    Vec3i::new(0, 0, -1),
+];
+
+///
+/// Parameters of [convex_decomposition]
+///
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct ConvexDecompositionParams {
+    /// Number of voxels along longest side of mesh bounding box
+    pub resolution: usize,
+    /// Part is not split when volume of its hull not filled by mesh is below this fraction of mesh volume
+    pub max_volume_error: f32,
+    /// Maximal number of produced hulls
+    pub max_hulls: usize,
+}
+
+impl Default for ConvexDecompositionParams {
+    fn default() -> Self {
+        Self {
+            resolution: 32,
+            max_volume_error: 0.01,
+            max_hulls: 32,
+        }
+    }
+}
+
+/// Connected set of voxels approximated by one hull
+struct Part {
+    voxels: Vec<Vec3i>,
+    /// Number of voxels missing in hull of part
+    error: usize,
+}
+
+impl Part {
+    fn new(voxels: Vec<Vec3i>) -> Self {
+        let error = hull_error(&voxels);
+        Self { voxels, error }
+    }
+}
+
+///
+/// Approximates solid bounded by mesh with a set of convex hulls, e.g. collision shapes for physics engines.
+/// Mesh is voxelized, then voxels are split recursively by axis aligned planes which minimize number of voxels
+/// missing in hulls of parts. Part with largest error is split first, until error of every part is below
+/// [ConvexDecompositionParams::max_volume_error] or [ConvexDecompositionParams::max_hulls] is reached.
+///
+/// Hulls cover voxels of their parts, so they may exceed mesh surface by up to one voxel.
+/// Mesh should be closed, parts thinner than voxel may be lost.
+///
+/// ## Example
+/// ```ignore
+/// let params = ConvexDecompositionParams { max_hulls: 16, ..Default::default() };
+/// let hulls: Vec<CornerTableF> = convex_decomposition(&mesh, params);
+/// ```
+///
+pub fn convex_decomposition<TMesh: Mesh<ScalarType = f32>>(mesh: &TMesh, params: ConvexDecompositionParams) -> Vec<TMesh> {
+    let mut bbox = Box3::empty();
+    for vertex in mesh.vertices() {
+        bbox.union_point(mesh.vertex_position(&vertex));
+    }
+
+    if !bbox.is_valid() || params.resolution == 0 {
+        return Vec::new();
+    }
+
+    let origin = *bbox.get_min();
+    let size = bbox.get_max() - bbox.get_min();
+    let voxel_size = size.max() / params.resolution as f32;
+
+    if voxel_size <= 0.0 {
+        return Vec::new();
+    }
+
+    // Voxels with centers inside mesh
+    let winding_numbers = WindingNumbers::from_mesh(mesh);
+    let dimensions = size.map(|extent| (extent / voxel_size).ceil().max(1.0) as isize);
+    let mut voxels = Vec::new();
+
+    for x in 0..dimensions.x {
+        for y in 0..dimensions.y {
+            for z in 0..dimensions.z {
+                let voxel = Vec3i::new(x, y, z);
+                let center = origin + (voxel.cast::<f32>() + Vec3f::repeat(0.5)) * voxel_size;
+
+                if winding_numbers.approximate(&center, 2.0) > 0.5 {
+                    voxels.push(voxel);
+                }
+            }
+        }
+    }
+
+    let max_error = params.max_volume_error * voxels.len() as f32;
+    let mut parts: Vec<_> = connected_components(voxels).into_iter().map(Part::new).collect();
+    let mut finished = Vec::new();
+
+    while parts.len() + finished.len() < params.max_hulls.max(1) {
+        let Some(worst) = (0..parts.len()).max_by_key(|part| parts[*part].error) else {
+            break;
+        };
+
+        if parts[worst].error as f32 <= max_error {
+            break;
+        }
+
+        let part = parts.swap_remove(worst);
+        let pieces: Vec<_> = split(&part.voxels)
+            .map(|(left, right)| [connected_components(left), connected_components(right)].concat())
+            .unwrap_or_default();
+
+        // Split may cut part into several pieces, all of them should fit into budget
+        if pieces.is_empty() || parts.len() + finished.len() + pieces.len() > params.max_hulls {
+            finished.push(part);
+        } else {
+            parts.extend(pieces.into_iter().map(Part::new));
+        }
+    }
+
+    parts
+        .iter()
+        .chain(finished.iter())
+        .filter_map(|part| {
+            let corners: HashSet<_> = boundary_voxels(&part.voxels)
+                .into_iter()
+                .flat_map(|voxel| voxel_corners(&voxel))
+                .collect();
+            let points: Vec<_> = corners.iter().map(|corner| origin + corner.cast::<f32>() * voxel_size).collect();
+            let hull = convex_hull(&points)?;
+
+            Some(TMesh::from_vertices_and_indices(&hull.points, &hull.indices))
+        })
+        .collect()
+}
+
+///
+/// Returns number of voxels missing in part, which have centers inside hull of its voxel centers.
+/// Centers of flat parts have no hull, hull of voxel corners is used for them and only centers strictly inside it are counted.
+///
+fn hull_error(voxels: &[Vec3i]) -> usize {
+    let boundary = boundary_voxels(voxels);
+    let centers: Vec<_> = boundary.iter().map(|voxel| voxel.cast::<f32>()).collect();
+
+    let (hull, tolerance) = match convex_hull(&centers) {
+        Some(hull) => (hull, COLUMN_TOLERANCE),
+        None => {
+            let corners: Vec<_> = boundary
+                .iter()
+                .flat_map(|voxel| voxel_corners(voxel).map(|corner| corner.cast::<f32>() - Vec3f::repeat(0.5)))
+                .collect();
+
+            match convex_hull(&corners) {
+                Some(hull) => (hull, -COLUMN_TOLERANCE),
+                None => return 0,
+            }
+        }
+    };
+
+    let min = voxels.iter().fold(voxels[0], |min, voxel| min.inf(voxel));
+    let max = voxels.iter().fold(voxels[0], |max, voxel| max.sup(voxel));
+
+    voxels_in_hull(&hull, &min, &max, tolerance).saturating_sub(voxels.len())
+}
+
+///
+/// Counts voxels within given bounds having centers inside hull, voxels are counted column by column
+/// along z axis clipped by planes of hull faces. Centers closer to faces than `tolerance` are inside.
+///
+fn voxels_in_hull(hull: &IndexedVertices<3, f32>, min: &Vec3i, max: &Vec3i, tolerance: f32) -> usize {
+    let planes: Vec<_> = hull
+        .indices
+        .chunks_exact(3)
+        .map(|face| {
+            let [a, b, c] = [face[0], face[1], face[2]].map(|vertex| hull.points[vertex]);
+            let normal = (b - a).cross(&(c - a)).normalize();
+            (normal, normal.dot(&a))
+        })
+        .collect();
+
+    let mut inside = 0;
+
+    for x in min.x..=max.x {
+        for y in min.y..=max.y {
+            let (mut low, mut high) = (min.z as f32, max.z as f32);
+
+            for (normal, offset) in &planes {
+                let bound = offset - normal.x * x as f32 - normal.y * y as f32;
+
+                if normal.z.abs() > f32::EPSILON {
+                    if normal.z > 0.0 {
+                        high = high.min((bound + tolerance) / normal.z);
+                    } else {
+                        low = low.max((bound + tolerance) / normal.z);
+                    }
+                } else if bound < -tolerance {
+                    high = low - 1.0;
+                }
+            }
+
+            inside += (high.floor() - low.ceil() + 1.0).max(0.0) as usize;
+        }
+    }
+
+    inside
+}
+
+/// Splits voxels by axis aligned plane minimizing sum of errors of both halves
+fn split(voxels: &[Vec3i]) -> Option<(Vec<Vec3i>, Vec<Vec3i>)> {
+    let min = voxels.iter().fold(voxels[0], |min, voxel| min.inf(voxel));
+    let max = voxels.iter().fold(voxels[0], |max, voxel| max.sup(voxel));
+    let mut best: Option<(usize, usize, isize)> = None;
+
+    for axis in 0..3 {
+        let extent = max[axis] - min[axis];
+
+        if extent == 0 {
+            continue;
+        }
+
+        let step = (extent / PLANES_PER_AXIS).max(1);
+
+        for plane in (min[axis] + 1..=max[axis]).step_by(step as usize) {
+            let (left, right): (Vec<_>, Vec<_>) = voxels.iter().partition(|voxel| voxel[axis] < plane);
+            let error = hull_error(&left) + hull_error(&right);
+
+            if best.is_none_or(|(best_error, _, _)| error < best_error) {
+                best = Some((error, axis, plane));
+            }
+        }
+    }
+
+    let (_, axis, plane) = best?;
+
+    Some(voxels.iter().partition(|voxel| voxel[axis] < plane))
+}
+
+/// Returns grid points at corners of voxel
+#[inline]
+fn voxel_corners(voxel: &Vec3i) -> [Vec3i; 8] {
+    std::array::from_fn(|i| voxel + Vec3i::new(i as isize & 1, (i as isize >> 1) & 1, (i as isize >> 2) & 1))
+}
+
+/// Returns voxels having at least one face not shared with other voxels
+fn boundary_voxels(voxels: &[Vec3i]) -> Vec<Vec3i> {
+    let set: HashSet<_> = voxels.iter().copied().collect();
+
+    voxels
+        .iter()
+        .copied()
+        .filter(|voxel| NEIGHBORS.iter().any(|offset| !set.contains(&(voxel + offset))))
+        .collect()
+}
+
+/// Groups voxels sharing faces
+fn connected_components(voxels: Vec<Vec3i>) -> Vec<Vec<Vec3i>> {
+    let mut remaining: HashSet<_> = voxels.iter().copied().collect();
+    let mut components = Vec::new();
+
+    // Components are started in order of voxels, so result is deterministic
+    for start in voxels {
+        if !remaining.remove(&start) {
+            continue;
+        }
+
+        let mut component = vec![start];
+        let mut i = 0;
+
+        while i < component.len() {
+            let voxel = component[i];
+
+            for offset in &NEIGHBORS {
+                if remaining.remove(&(voxel + offset)) {
+                    component.push(voxel + offset);
+                }
+            }
+
+            i += 1;
+        }
+
+        components.push(component);
+    }
+
+    components
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{convex_decomposition, ConvexDecompositionParams};
+    use crate::{
+        algo::mass_properties::mass_properties,
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::{Mesh, TopologicalMesh}},
+    };
+
+    #[test]
+    fn test_convex_decomposition() {
+        let cube: CornerTableF = primitives::cuboid(Vec3f::new(1.0, 2.0, 1.0), 1);
+        let hulls = convex_decomposition(&cube, ConvexDecompositionParams::default());
+        assert_eq!(hulls.len(), 1);
+        assert!((mass_properties(&hulls[0], 1.0).volume - 2.0).abs() < 1e-4);
+
+        // Hole of torus is not covered by hulls
+        let torus: CornerTableF = primitives::torus(1.0, 0.3, 32, 16);
+        let params = ConvexDecompositionParams {
+            resolution: 24,
+            max_volume_error: 0.05,
+            max_hulls: 16,
+        };
+        let hulls = convex_decomposition(&torus, params);
+        assert!(hulls.len() > 2 && hulls.len() <= 16);
+
+        let torus_volume = mass_properties(&torus, 1.0).volume;
+        let hulls_volume: f32 = hulls.iter().map(|hull| mass_properties(hull, 1.0).volume).sum();
+        assert!(hulls_volume > torus_volume && hulls_volume < 2.0 * torus_volume);
+
+        for hull in &hulls {
+            assert!(hull.edges().all(|edge| !hull.is_edge_on_boundary(&edge)));
+
+            // Center of torus is in front of some face of every hull
+            let outside = hull.faces().any(|face| {
+                let triangle = hull.face_positions(&face);
+                triangle.get_normal().dot(&-triangle.p1()) > 0.0
+            });
+            assert!(outside);
+        }
+    }
+}
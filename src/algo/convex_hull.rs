@@ -0,0 +1,273 @@
+use std::collections::HashMap;
+
+use num_traits::{cast, Float};
+
+use crate::{algo::merge_points::IndexedVertices, geometry::traits::RealNumber, helpers::aliases::Vec3};
+
+/// Points closer to face plane than this fraction of points extent are treated as lying on it
+const PLANE_TOLERANCE: f64 = 1e-5;
+
+struct HullFace<TScalar: RealNumber> {
+    vertices: [usize; 3],
+    normal: Vec3<TScalar>,
+    offset: TScalar,
+    /// Points above face which are not assigned to other faces
+    outside: Vec<usize>,
+    alive: bool,
+}
+
+impl<TScalar: RealNumber> HullFace<TScalar> {
+    fn new(vertices: [usize; 3], points: &[Vec3<TScalar>]) -> Self {
+        let [a, b, c] = vertices.map(|vertex| points[vertex]);
+        let normal = (b - a).cross(&(c - a)).normalize();
+
+        Self {
+            vertices,
+            normal,
+            offset: normal.dot(&a),
+            outside: Vec::new(),
+            alive: true,
+        }
+    }
+
+    #[inline]
+    fn distance(&self, point: &Vec3<TScalar>) -> TScalar {
+        self.normal.dot(point) - self.offset
+    }
+
+    #[inline]
+    fn edges(&self) -> [(usize, usize); 3] {
+        let [a, b, c] = self.vertices;
+        [(a, b), (b, c), (c, a)]
+    }
+}
+
+///
+/// Computes convex hull of points using quickhull algorithm. Faces of hull are oriented outwards,
+/// only points on hull are returned. Returns `None` when points are coplanar (hull has no volume).
+///
+/// ## Example
+/// ```ignore
+/// let hull = convex_hull(&points)?;
+/// let mesh = CornerTableF::from_vertices_and_indices(&hull.points, &hull.indices);
+/// ```
+///
+pub fn convex_hull<TScalar: RealNumber>(points: &[Vec3<TScalar>]) -> Option<IndexedVertices<3, TScalar>> {
+    if points.len() < 4 {
+        return None;
+    }
+
+    let extent = points
+        .iter()
+        .flat_map(|point| point.iter())
+        .fold(TScalar::zero(), |max, coordinate| Float::max(max, Float::abs(*coordinate)));
+    let tolerance = extent * cast(PLANE_TOLERANCE).unwrap();
+
+    let [a, b, c, d] = initial_simplex(points, tolerance)?;
+    let mut faces: Vec<HullFace<TScalar>> = [[a, b, c], [a, d, b], [b, d, c], [c, d, a]]
+        .into_iter()
+        .map(|face| HullFace::new(face, points))
+        .collect();
+
+    // Directed edges of alive faces, used to walk between neighboring faces
+    let mut edge_faces = HashMap::new();
+
+    for (index, face) in faces.iter().enumerate() {
+        for edge in face.edges() {
+            edge_faces.insert(edge, index);
+        }
+    }
+
+    let candidates = (0..points.len()).filter(|point| ![a, b, c, d].contains(point));
+    assign_outside(&mut faces, 0..4, candidates, points, tolerance);
+
+    while let Some(start) = faces.iter().position(|face| face.alive && !face.outside.is_empty()) {
+        let apex = *faces[start]
+            .outside
+            .iter()
+            .max_by(|p1, p2| {
+                let distance = |point: &usize| faces[start].distance(&points[*point]);
+                distance(p1).partial_cmp(&distance(p2)).unwrap()
+            })
+            .unwrap();
+
+        // Faces visible from apex form connected region around start face
+        let mut visible = vec![start];
+        faces[start].alive = false;
+        let mut i = 0;
+
+        while i < visible.len() {
+            for (from, to) in faces[visible[i]].edges() {
+                let neighbor = edge_faces[&(to, from)];
+
+                if faces[neighbor].alive && faces[neighbor].distance(&points[apex]) > tolerance {
+                    faces[neighbor].alive = false;
+                    visible.push(neighbor);
+                }
+            }
+
+            i += 1;
+        }
+
+        let mut horizon = Vec::new();
+        let mut orphans = Vec::new();
+
+        for &face in &visible {
+            for edge in faces[face].edges() {
+                edge_faces.remove(&edge);
+
+                if faces[edge_faces.get(&(edge.1, edge.0)).copied().unwrap_or(face)].alive {
+                    horizon.push(edge);
+                }
+            }
+
+            orphans.append(&mut faces[face].outside);
+        }
+
+        let first_new = faces.len();
+
+        for (from, to) in horizon {
+            let face = HullFace::new([from, to, apex], points);
+
+            for edge in face.edges() {
+                edge_faces.insert(edge, faces.len());
+            }
+
+            faces.push(face);
+        }
+
+        let new_faces = first_new..faces.len();
+        assign_outside(&mut faces, new_faces, orphans.into_iter().filter(|point| *point != apex), points, tolerance);
+    }
+
+    // Keep only points used by hull
+    let mut remap = HashMap::new();
+    let mut hull_points = Vec::new();
+    let mut indices = Vec::new();
+
+    for face in faces.iter().filter(|face| face.alive) {
+        for vertex in face.vertices {
+            let index = *remap.entry(vertex).or_insert_with(|| {
+                hull_points.push(points[vertex]);
+                hull_points.len() - 1
+            });
+            indices.push(index);
+        }
+    }
+
+    Some(IndexedVertices { points: hull_points, indices })
+}
+
+/// Returns volume enclosed by closed mesh with outward oriented faces
+pub fn enclosed_volume<TScalar: RealNumber>(mesh: &IndexedVertices<3, TScalar>) -> TScalar {
+    let volume = mesh.indices.chunks_exact(3).fold(TScalar::zero(), |volume, face| {
+        let [a, b, c] = [face[0], face[1], face[2]].map(|vertex| mesh.points[vertex]);
+        volume + a.dot(&b.cross(&c))
+    });
+
+    volume / cast(6.0).unwrap()
+}
+
+/// Returns four points spanning tetrahedron of maximal size, ordered so faces of [convex_hull] are oriented outwards
+fn initial_simplex<TScalar: RealNumber>(points: &[Vec3<TScalar>], tolerance: TScalar) -> Option<[usize; 4]> {
+    let farthest = |distance: &dyn Fn(&Vec3<TScalar>) -> TScalar| {
+        (0..points.len())
+            .map(|point| (point, distance(&points[point])))
+            .max_by(|a, b| a.1.partial_cmp(&b.1).unwrap())
+    };
+
+    // Most distant pair among extreme points along axes
+    let (a, b) = (0..3)
+        .map(|axis| {
+            let min = farthest(&|point| -point[axis]).unwrap().0;
+            let max = farthest(&|point| point[axis]).unwrap().0;
+            (min, max)
+        })
+        .max_by(|(a1, b1), (a2, b2)| {
+            let length = |a: &usize, b: &usize| (points[*a] - points[*b]).norm_squared();
+            length(a1, b1).partial_cmp(&length(a2, b2)).unwrap()
+        })?;
+
+    let direction = (points[b] - points[a]).try_normalize(TScalar::zero())?;
+    let (c, line_distance) = farthest(&|point| {
+        let offset = point - points[a];
+        (offset - direction * offset.dot(&direction)).norm()
+    })?;
+
+    if line_distance <= tolerance {
+        return None;
+    }
+
+    let normal = (points[b] - points[a]).cross(&(points[c] - points[a])).normalize();
+    let (d, plane_distance) = farthest(&|point| Float::abs(normal.dot(&(point - points[a]))))?;
+
+    if plane_distance <= tolerance {
+        return None;
+    }
+
+    if normal.dot(&(points[d] - points[a])) > TScalar::zero() {
+        Some([a, c, b, d])
+    } else {
+        Some([a, b, c, d])
+    }
+}
+
+/// Assigns every point to first of given faces it is above, points below all of them are inside hull
+fn assign_outside<TScalar: RealNumber>(
+    faces: &mut [HullFace<TScalar>],
+    face_range: std::ops::Range<usize>,
+    candidates: impl Iterator<Item = usize>,
+    points: &[Vec3<TScalar>],
+    tolerance: TScalar,
+) {
+    for point in candidates {
+        if let Some(face) = face_range.clone().find(|face| faces[*face].distance(&points[point]) > tolerance) {
+            faces[face].outside.push(point);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{convex_hull, enclosed_volume};
+    use crate::helpers::aliases::Vec3f;
+
+    #[test]
+    fn test_convex_hull() {
+        let mut points: Vec<_> = (0..8)
+            .map(|i| Vec3f::new((i & 1) as f32, ((i >> 1) & 1) as f32, ((i >> 2) & 1) as f32))
+            .collect();
+
+        // Interior points are not on hull
+        for i in 1..100 {
+            let t = i as f32 / 100.0;
+            points.push(Vec3f::new(t, (t * 7.0).fract(), (t * 13.0).fract()) * 0.8 + Vec3f::repeat(0.1));
+        }
+
+        let hull = convex_hull(&points).unwrap();
+        assert_eq!(hull.points.len(), 8);
+        assert!((enclosed_volume(&hull) - 1.0).abs() < 1e-5);
+
+        // Points on faces may become vertices of hull, but don't change its shape
+        for i in 1..100 {
+            let t = i as f32 / 100.0;
+            points.push(Vec3f::new(t, (t * 3.0).fract(), 1.0));
+        }
+
+        let hull = convex_hull(&points).unwrap();
+        assert!((enclosed_volume(&hull) - 1.0).abs() < 1e-5);
+
+        // Every edge is shared by two faces with opposite directions
+        let mut edges: Vec<_> = hull
+            .indices
+            .chunks_exact(3)
+            .flat_map(|face| [(face[0], face[1]), (face[1], face[2]), (face[2], face[0])])
+            .collect();
+        edges.sort();
+        assert!(edges.iter().all(|(from, to)| edges.binary_search(&(*to, *from)).is_ok()));
+
+        // Coplanar points have no hull
+        let flat: Vec<_> = points.iter().map(|point| Vec3f::new(point.x, point.y, 0.5)).collect();
+        assert!(convex_hull(&flat).is_none());
+    }
+}
@@ -0,0 +1,148 @@
+use std::collections::HashMap;
+
+use num_traits::{cast, Float, Zero};
+
+use crate::{geometry::traits::RealNumber, helpers::aliases::Vec3, mesh::traits::Mesh};
+
+///
+/// Face-varying normals of mesh. Every corner of face references one of `normals`,
+/// corners sharing vertex reference the same normal unless they are separated by crease.
+///
+#[derive(Debug, Clone)]
+pub struct CornerNormals<TScalar: RealNumber> {
+    /// Unique normals, every normal belongs to single vertex
+    pub normals: Vec<Vec3<TScalar>>,
+    /// Index of vertex (position in [Mesh::vertices] iterator) each normal belongs to
+    pub normal_vertices: Vec<usize>,
+    /// Index of normal for every corner, 3 per face in order of [Mesh::faces] iterator
+    pub indices: Vec<usize>,
+}
+
+impl<TScalar: RealNumber> CornerNormals<TScalar> {
+    ///
+    /// Returns vertices split along creases: positions and normals of new vertices and 3 indices per face.
+    /// Intended for formats storing normals per vertex (PLY, GPU buffers).
+    ///
+    pub fn split_vertices<TMesh>(&self, mesh: &TMesh) -> (Vec<Vec3<TScalar>>, Vec<Vec3<TScalar>>, Vec<usize>)
+    where
+        TMesh: Mesh<ScalarType = TScalar>,
+    {
+        let positions: Vec<_> = mesh.vertices().map(|v| *mesh.vertex_position(&v)).collect();
+        let positions = self.normal_vertices.iter().map(|v| positions[*v]).collect();
+
+        (positions, self.normals.clone(), self.indices.clone())
+    }
+}
+
+///
+/// Computes face-varying normals which keep hard edges. Normal of corner is angle-weighted average of normals
+/// of faces around its vertex deviating from corner's face by no more than `crease_angle` (in radians).
+/// Zero angle gives flat shading, `PI` gives smooth shading (same as [Mesh::vertex_normal]).
+///
+/// ## Example
+/// ```ignore
+/// let normals = corner_normals(&mesh, 30.0f32.to_radians());
+/// ```
+///
+pub fn corner_normals<TMesh: Mesh>(mesh: &TMesh, crease_angle: TMesh::ScalarType) -> CornerNormals<TMesh::ScalarType> {
+    let vertex_index: HashMap<_, _> = mesh.vertices().enumerate().map(|(i, v)| (v, i)).collect();
+    let mut vertex_corners = vec![Vec::new(); vertex_index.len()];
+    let mut face_normals = Vec::new();
+    let mut corner_angles = Vec::new();
+
+    for (face_index, face) in mesh.faces().enumerate() {
+        let (v1, v2, v3) = mesh.face_vertices(&face);
+        let triangle = mesh.face_positions(&face);
+        let points = [triangle.p1(), triangle.p2(), triangle.p3()];
+
+        face_normals.push(triangle.try_get_normal());
+
+        for (k, vertex) in [v1, v2, v3].iter().enumerate() {
+            let corner = face_index * 3 + k;
+            vertex_corners[vertex_index[vertex]].push(corner);
+
+            let prev = points[(k + 2) % 3] - points[k];
+            let next = points[(k + 1) % 3] - points[k];
+            let angle = if prev.norm_squared().is_zero() || next.norm_squared().is_zero() {
+                TMesh::ScalarType::zero()
+            } else {
+                next.angle(&prev)
+            };
+            corner_angles.push(angle);
+        }
+    }
+
+    let min_cos = Float::cos(Float::min(crease_angle, cast(std::f64::consts::PI).unwrap()));
+    let mut normals = Vec::new();
+    let mut normal_vertices = Vec::new();
+    let mut indices = vec![0; corner_angles.len()];
+
+    for (vertex, corners) in vertex_corners.iter().enumerate() {
+        // Corners with same set of smoothed faces share normal
+        let mut vertex_normals = HashMap::new();
+
+        for &corner in corners {
+            let face_normal = face_normals[corner / 3];
+
+            let smoothed: Vec<_> = corners
+                .iter()
+                .copied()
+                .filter(|other| match (face_normal, face_normals[other / 3]) {
+                    (Some(normal), Some(other_normal)) => normal.dot(&other_normal) >= min_cos,
+                    _ => *other == corner,
+                })
+                .collect();
+
+            indices[corner] = *vertex_normals.entry(smoothed).or_insert_with_key(|smoothed| {
+                let normal = smoothed
+                    .iter()
+                    .filter_map(|other| face_normals[other / 3].map(|n| n * corner_angles[*other]))
+                    .fold(Vec3::zeros(), |sum, n| sum + n);
+
+                normals.push(normal.try_normalize(TMesh::ScalarType::zero()).or(face_normal).unwrap_or(Vec3::zeros()));
+                normal_vertices.push(vertex);
+                normals.len() - 1
+            });
+        }
+    }
+
+    CornerNormals {
+        normals,
+        normal_vertices,
+        indices,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::corner_normals;
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+    };
+
+    #[test]
+    fn test_corner_normals() {
+        let cube: CornerTableF = primitives::cuboid(Vec3f::new(1.0, 1.0, 1.0), 1);
+
+        let hard = corner_normals(&cube, 30.0f32.to_radians());
+        assert_eq!(hard.normals.len(), 24);
+        assert_eq!(hard.indices.len(), cube.faces().count() * 3);
+
+        // Every corner normal is normal of its face
+        for (face_index, face) in cube.faces().enumerate() {
+            let normal = cube.face_normal(&face);
+            for k in 0..3 {
+                assert!((hard.normals[hard.indices[face_index * 3 + k]] - normal).norm() < 1e-6);
+            }
+        }
+
+        let smooth = corner_normals(&cube, std::f32::consts::PI);
+        assert_eq!(smooth.normals.len(), 8);
+
+        let (positions, normals, indices) = hard.split_vertices(&cube);
+        assert_eq!(positions.len(), 24);
+        assert_eq!(normals.len(), 24);
+        assert_eq!(indices, hard.indices);
+    }
+}
@@ -35,6 +35,91 @@ pub fn is_topologically_safe<TMesh: TopologicalMesh + EditableMesh>(
     common_neighbors_count == 2
 }
 
+///
+/// Returns `true` when edge collapse preserves topology of mesh, `false` otherwise.
+/// Collapse preserves topology when it doesn't change Euler characteristic of mesh
+/// and doesn't close, split or merge boundary loops.
+///
+pub fn is_topology_preserved<TMesh: TopologicalMesh + EditableMesh>(
+    mesh: &TMesh,
+    edge: &TMesh::EdgeDescriptor,
+) -> bool {
+    // Was collapsed?
+    if !mesh.edge_exist(edge) {
+        return false;
+    }
+
+    let (e_start, e_end) = mesh.edge_vertices(edge);
+    let is_boundary_edge = mesh.is_edge_on_boundary(edge);
+
+    // Interior edge connecting two boundary vertices, collapse will pinch boundary
+    if !is_boundary_edge && mesh.is_vertex_on_boundary(&e_start) && mesh.is_vertex_on_boundary(&e_end) {
+        return false;
+    }
+
+    // Link condition: common neighbors of edge vertices should be exactly opposite vertices of edge
+    let mut e_start_neighbors = BTreeSet::new();
+    mesh.vertices_around_vertex(&e_start, |vertex| {
+        e_start_neighbors.insert(*vertex);
+    });
+    let mut common_neighbors = Vec::new();
+    mesh.vertices_around_vertex(&e_end, |vertex| {
+        if e_start_neighbors.contains(vertex) {
+            common_neighbors.push(*vertex);
+        }
+    });
+
+    if is_boundary_edge {
+        if common_neighbors.len() != 1 {
+            return false;
+        }
+
+        // Boundary loop made of 3 edges will be closed
+        let mut e_start_boundary_neighbors = BTreeSet::new();
+        boundary_neighbors(mesh, &e_start, |vertex| {
+            e_start_boundary_neighbors.insert(*vertex);
+        });
+        let mut closes_loop = false;
+        boundary_neighbors(mesh, &e_end, |vertex| {
+            closes_loop |= *vertex != e_start && e_start_boundary_neighbors.contains(vertex);
+        });
+
+        return !closes_loop;
+    }
+
+    if common_neighbors.len() != 2 {
+        return false;
+    }
+
+    // Opposite vertices connected by edge (e.g. tetrahedron), collapse will produce duplicated faces
+    let mut opposite_vertices_connected = false;
+    mesh.vertices_around_vertex(&common_neighbors[0], |vertex| {
+        opposite_vertices_connected |= *vertex == common_neighbors[1];
+    });
+
+    !opposite_vertices_connected
+}
+
+/// Visits vertices connected to `vertex` by boundary edges
+fn boundary_neighbors<TMesh: TopologicalMesh, TVisit: FnMut(&TMesh::VertexDescriptor)>(
+    mesh: &TMesh,
+    vertex: &TMesh::VertexDescriptor,
+    mut visit: TVisit,
+) {
+    mesh.edges_around_vertex(vertex, |edge| {
+        if !mesh.is_edge_on_boundary(edge) {
+            return;
+        }
+
+        let (v1, v2) = mesh.edge_vertices(edge);
+        if v1 == *vertex {
+            visit(&v2);
+        } else {
+            visit(&v1);
+        }
+    });
+}
+
 ///
 /// Returns `true` when edge collapse is geometrically safe, `false` otherwise.
 /// Collapse is not safe when face normals are flipped or quality faces becomes too bad.
@@ -47,8 +132,8 @@ pub fn is_geometrically_safe<TMesh: TopologicalMesh + EditableMesh>(
 ) -> bool {
     // Check new normals (geometrical safety)
     let (e_start, e_end) = mesh.edge_vertices(edge);
-    check_faces_after_collapse(mesh, &e_start, new_position, min_quality)
-        && check_faces_after_collapse(mesh, &e_end, new_position, min_quality)
+    check_faces_after_collapse(mesh, &e_start, &e_end, new_position, min_quality)
+        && check_faces_after_collapse(mesh, &e_end, &e_start, new_position, min_quality)
 }
 
 /// Returns `true` when edge collapse is topologically and geometrically safe, `false` otherwise
@@ -63,9 +148,14 @@ pub fn is_safe<TMesh: TopologicalMesh + EditableMesh>(
         && is_geometrically_safe(mesh, edge, collapse_at, min_quality)
 }
 
+///
+/// Checks faces around `collapsed_vertex` moved to `new_position`.
+/// When `new_position` is position of `other_vertex` faces shared with it are skipped, they are removed by collapse.
+///
 fn check_faces_after_collapse<TMesh: TopologicalMesh + EditableMesh>(
     mesh: &TMesh,
     collapsed_vertex: &TMesh::VertexDescriptor,
+    other_vertex: &TMesh::VertexDescriptor,
     new_position: &Vec3<TMesh::ScalarType>,
     min_quality: TMesh::ScalarType,
 ) -> bool {
@@ -75,8 +165,13 @@ fn check_faces_after_collapse<TMesh: TopologicalMesh + EditableMesh>(
         let mut pos = TMesh::Position::from_vertex_on_face(mesh, face, collapsed_vertex);
 
         let v1 = mesh.vertex_position(&pos.get_vertex());
-        let v2 = mesh.vertex_position(&pos.next().get_vertex());
-        let v3 = mesh.vertex_position(&pos.next().get_vertex());
+        let (vertex2, vertex3) = (pos.next().get_vertex(), pos.next().get_vertex());
+
+        if (vertex2 == *other_vertex || vertex3 == *other_vertex) && mesh.vertex_position(other_vertex) == new_position {
+            return;
+        }
+
+        let (v2, v3) = (mesh.vertex_position(&vertex2), mesh.vertex_position(&vertex3));
 
         let new_quality = Triangle3::quality(new_position, v2, v3);
 
@@ -0,0 +1,678 @@
+use std::{
+    mem,
+    ops::{Add, Mul, Neg, Sub},
+};
+
+use num_traits::cast;
+
+use crate::{
+    algo::merge_points::merge_points,
+    geometry::{primitives::box3::Box3, traits::{HasBBox3, RealNumber}},
+    helpers::aliases::Vec3,
+    mesh::traits::Mesh,
+};
+
+/// Largest number of quantization bits for which determinants of plane coefficients fit into 256 bits
+const MAX_QUANTIZATION_BITS: u32 = 25;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum BooleanOperation {
+    Union,
+    Intersection,
+    /// First mesh minus second one
+    Difference,
+}
+
+///
+/// Exact boolean operations on closed triangle meshes using BSP trees.
+/// Intended as robust fallback for small meshes, when floating point booleans fail on coplanar
+/// or nearly degenerate intersections.
+///
+/// Input vertices are snapped to integer grid covering both meshes, then all geometry is represented by planes
+/// with integer coefficients: polygon is given by its supporting plane and planes bounding its edges,
+/// vertices are intersections of three planes. Clipping never creates new coordinates, so all predicates
+/// (side of implicit vertex relative to plane) are evaluated exactly using determinants in 256-bit integers.
+/// Vertices are converted back to floats only when output is built.
+///
+/// Output is polygon soup triangulated as fans, it may contain T-junctions.
+///
+/// ## Example
+/// ```ignore
+/// let union: CornerTableD = ExactBoolean::new().compute(&a, &b, BooleanOperation::Union);
+/// ```
+///
+pub struct ExactBoolean {
+    quantization_bits: u32,
+}
+
+impl ExactBoolean {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Set number of bits used for grid coordinates along each axis (in addition to sign).
+    /// Default is `20`, values are clamped to `[1, 25]` range.
+    ///
+    #[inline]
+    pub fn with_quantization_bits(mut self, bits: u32) -> Self {
+        self.quantization_bits = bits.clamp(1, MAX_QUANTIZATION_BITS);
+        self
+    }
+
+    /// Computes boolean operation on solids bounded by `a` and `b`
+    pub fn compute<TMesh: Mesh>(&self, a: &TMesh, b: &TMesh, operation: BooleanOperation) -> TMesh {
+        let mut bbox = Box3::empty();
+        for face in a.faces() {
+            bbox.union_box(&a.face_positions(&face).bbox());
+        }
+        for face in b.faces() {
+            bbox.union_box(&b.face_positions(&face).bbox());
+        }
+
+        if !bbox.is_valid() {
+            return TMesh::from_vertices_and_indices(&[], &[]);
+        }
+
+        let quantizer = Quantizer::new(&bbox, self.quantization_bits);
+        let mut a = BspNode::new(mesh_polygons(a, &quantizer));
+        let mut b = BspNode::new(mesh_polygons(b, &quantizer));
+
+        match operation {
+            BooleanOperation::Union => {
+                a.clip_to(&b);
+                b.clip_to(&a);
+                b.invert();
+                b.clip_to(&a);
+                b.invert();
+                a.build(b.all_polygons());
+            }
+            BooleanOperation::Intersection => {
+                a.invert();
+                b.clip_to(&a);
+                b.invert();
+                a.clip_to(&b);
+                b.clip_to(&a);
+                a.build(b.all_polygons());
+                a.invert();
+            }
+            BooleanOperation::Difference => {
+                a.invert();
+                a.clip_to(&b);
+                b.clip_to(&a);
+                b.invert();
+                b.clip_to(&a);
+                b.invert();
+                a.build(b.all_polygons());
+                a.invert();
+            }
+        }
+
+        let mut vertices = Vec::new();
+        for polygon in a.all_polygons() {
+            let points: Vec<_> = (0..polygon.edges.len())
+                .map(|i| quantizer.dequantize(polygon.vertex_position(i)))
+                .collect();
+
+            for i in 1..points.len() - 1 {
+                vertices.extend_from_slice(&[points[0], points[i], points[i + 1]]);
+            }
+        }
+
+        let indexed = merge_points(&vertices);
+        TMesh::from_vertices_and_indices(&indexed.points, &indexed.indices)
+    }
+}
+
+impl Default for ExactBoolean {
+    fn default() -> Self {
+        Self { quantization_bits: 20 }
+    }
+}
+
+fn mesh_polygons<TMesh: Mesh>(mesh: &TMesh, quantizer: &Quantizer) -> Vec<Polygon> {
+    mesh.faces()
+        .filter_map(|face| {
+            let triangle = mesh.face_positions(&face);
+            let [p1, p2, p3] = [triangle.p1(), triangle.p2(), triangle.p3()].map(|p| quantizer.quantize(p));
+
+            Polygon::from_triangle(p1, p2, p3)
+        })
+        .collect()
+}
+
+/// Maps bounding box to integer grid `[-2^bits, 2^bits]` along its largest side
+struct Quantizer {
+    center: Vec3<f64>,
+    scale: f64,
+}
+
+impl Quantizer {
+    fn new<TScalar: RealNumber>(bbox: &Box3<TScalar>, bits: u32) -> Self {
+        let to_f64 = |v: &Vec3<TScalar>| v.map(|c| c.to_f64().unwrap_or(0.0));
+        let (min, max) = (to_f64(bbox.get_min()), to_f64(bbox.get_max()));
+        let half_size = (max - min).max() / 2.0;
+
+        Self {
+            center: (min + max) / 2.0,
+            scale: if half_size > 0.0 { ((1u64 << bits) - 1) as f64 / half_size } else { 1.0 },
+        }
+    }
+
+    #[inline]
+    fn quantize<TScalar: RealNumber>(&self, point: &Vec3<TScalar>) -> [i128; 3] {
+        [0, 1, 2].map(|i| ((point[i].to_f64().unwrap_or(0.0) - self.center[i]) * self.scale).round() as i128)
+    }
+
+    #[inline]
+    fn dequantize<TScalar: RealNumber>(&self, point: [f64; 3]) -> Vec3<TScalar> {
+        Vec3::from_fn(|i, _| cast(point[i] / self.scale + self.center[i]).unwrap())
+    }
+}
+
+///
+/// Plane `normal * x + d = 0` with integer coefficients
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct Plane {
+    normal: [i128; 3],
+    d: i128,
+}
+
+impl Plane {
+    fn new(normal: [i128; 3], point: &[i128; 3]) -> Self {
+        Self { normal, d: -dot(&normal, point) }
+    }
+
+    #[inline]
+    fn flipped(&self) -> Self {
+        Self {
+            normal: self.normal.map(|c| -c),
+            d: -self.d,
+        }
+    }
+
+    #[inline]
+    fn point_side(&self, point: &[i128; 3]) -> i128 {
+        (dot(&self.normal, point) + self.d).signum()
+    }
+}
+
+///
+/// Convex planar polygon. Polygon lies in `support` plane and on negative side of all `edges` planes.
+/// Vertex `i` is intersection of support plane with edge planes `i - 1` and `i`,
+/// vertices are ordered counterclockwise relative to normal of support plane.
+///
+#[derive(Debug, Clone)]
+struct Polygon {
+    support: Plane,
+    edges: Vec<Plane>,
+}
+
+/// Result of splitting polygon by plane
+enum Split {
+    CoplanarFront(Polygon),
+    CoplanarBack(Polygon),
+    Front(Polygon),
+    Back(Polygon),
+    Spanning(Polygon, Polygon),
+}
+
+impl Polygon {
+    /// Creates polygon from counterclockwise triangle, returns `None` for degenerate triangles
+    fn from_triangle(p1: [i128; 3], p2: [i128; 3], p3: [i128; 3]) -> Option<Self> {
+        let normal = cross(&sub(&p2, &p1), &sub(&p3, &p1));
+        if normal == [0; 3] {
+            return None;
+        }
+
+        // Edge planes contain dominant axis of support plane normal, so their coefficients stay small
+        let axis = (0..3).max_by_key(|&i| normal[i].abs()).unwrap();
+        let mut direction = [0; 3];
+        direction[axis] = 1;
+
+        let edges = [(p1, p2, p3), (p2, p3, p1), (p3, p1, p2)].map(|(start, end, opposite)| {
+            let plane = Plane::new(cross(&sub(&end, &start), &direction), &start);
+
+            if plane.point_side(&opposite) > 0 {
+                plane.flipped()
+            } else {
+                plane
+            }
+        });
+
+        Some(Self {
+            support: Plane::new(normal, &p1),
+            edges: edges.to_vec(),
+        })
+    }
+
+    fn flip(&mut self) {
+        self.support = self.support.flipped();
+        self.edges.reverse();
+    }
+
+    #[inline]
+    fn vertex_planes(&self, vertex: usize) -> (&Plane, &Plane) {
+        let count = self.edges.len();
+        (&self.edges[(vertex + count - 1) % count], &self.edges[vertex])
+    }
+
+    /// Position of vertex in grid coordinates
+    fn vertex_position(&self, vertex: usize) -> [f64; 3] {
+        let (e1, e2) = self.vertex_planes(vertex);
+        let planes = [&self.support, e1, e2];
+        let denominator = det3(planes.map(|p| p.normal)).to_f64();
+
+        // Cramer's rule for system `normal * x = -d`
+        [0, 1, 2].map(|axis| {
+            let rows = planes.map(|p| {
+                let mut row = p.normal;
+                row[axis] = -p.d;
+                row
+            });
+
+            det3(rows).to_f64() / denominator
+        })
+    }
+
+    fn split(self, plane: &Plane) -> Split {
+        let sides: Vec<_> = (0..self.edges.len())
+            .map(|vertex| {
+                let (e1, e2) = self.vertex_planes(vertex);
+                vertex_side(&self.support, e1, e2, plane)
+            })
+            .collect();
+
+        let has_front = sides.iter().any(|&s| s > 0);
+        let has_back = sides.iter().any(|&s| s < 0);
+
+        match (has_front, has_back) {
+            (false, false) if dot(&self.support.normal, &plane.normal) > 0 => Split::CoplanarFront(self),
+            (false, false) => Split::CoplanarBack(self),
+            (true, false) => Split::Front(self),
+            (false, true) => Split::Back(self),
+            (true, true) => Split::Spanning(self.piece(&sides, 1, plane.flipped()), self.piece(&sides, -1, *plane)),
+        }
+    }
+
+    /// Returns part of polygon on given side of cutting plane, `cut` is oriented to have piece on its negative side
+    fn piece(&self, sides: &[i32], side: i32, cut: Plane) -> Polygon {
+        let count = self.edges.len();
+        let mut edges = Vec::with_capacity(count + 1);
+
+        // Edge `i` goes from vertex `i` to vertex `i + 1`
+        for i in 0..count {
+            let (start, end) = (sides[i], sides[(i + 1) % count]);
+
+            if start == side || end == side {
+                edges.push(self.edges[i]);
+
+                // Polygon leaves piece at the end of this edge
+                if end != side {
+                    edges.push(cut);
+                }
+            }
+        }
+
+        Polygon {
+            support: self.support,
+            edges,
+        }
+    }
+}
+
+///
+/// BSP tree node, polygons coplanar with splitting plane are stored in node
+///
+#[derive(Default)]
+struct BspNode {
+    plane: Option<Plane>,
+    front: Option<Box<BspNode>>,
+    back: Option<Box<BspNode>>,
+    polygons: Vec<Polygon>,
+}
+
+impl BspNode {
+    fn new(polygons: Vec<Polygon>) -> Self {
+        let mut node = Self::default();
+        node.build(polygons);
+        node
+    }
+
+    fn build(&mut self, polygons: Vec<Polygon>) {
+        if polygons.is_empty() {
+            return;
+        }
+
+        let plane = *self.plane.get_or_insert(polygons[0].support);
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+
+        for polygon in polygons {
+            match polygon.split(&plane) {
+                Split::CoplanarFront(p) | Split::CoplanarBack(p) => self.polygons.push(p),
+                Split::Front(p) => front.push(p),
+                Split::Back(p) => back.push(p),
+                Split::Spanning(f, b) => {
+                    front.push(f);
+                    back.push(b);
+                }
+            }
+        }
+
+        if !front.is_empty() {
+            self.front.get_or_insert_with(Default::default).build(front);
+        }
+
+        if !back.is_empty() {
+            self.back.get_or_insert_with(Default::default).build(back);
+        }
+    }
+
+    /// Swaps solid and empty space
+    fn invert(&mut self) {
+        self.polygons.iter_mut().for_each(Polygon::flip);
+        self.plane = self.plane.map(|p| p.flipped());
+
+        if let Some(front) = &mut self.front {
+            front.invert();
+        }
+
+        if let Some(back) = &mut self.back {
+            back.invert();
+        }
+
+        mem::swap(&mut self.front, &mut self.back);
+    }
+
+    /// Removes parts of polygons inside solid represented by this tree
+    fn clip_polygons(&self, polygons: Vec<Polygon>) -> Vec<Polygon> {
+        let plane = match &self.plane {
+            Some(plane) => plane,
+            None => return polygons,
+        };
+
+        let mut front = Vec::new();
+        let mut back = Vec::new();
+
+        for polygon in polygons {
+            match polygon.split(plane) {
+                Split::CoplanarFront(p) | Split::Front(p) => front.push(p),
+                Split::CoplanarBack(p) | Split::Back(p) => back.push(p),
+                Split::Spanning(f, b) => {
+                    front.push(f);
+                    back.push(b);
+                }
+            }
+        }
+
+        if let Some(node) = &self.front {
+            front = node.clip_polygons(front);
+        }
+
+        // Polygons behind leaf are inside solid
+        if let Some(node) = &self.back {
+            front.extend(node.clip_polygons(back));
+        }
+
+        front
+    }
+
+    /// Removes parts of polygons of this tree inside solid represented by `other` tree
+    fn clip_to(&mut self, other: &BspNode) {
+        self.polygons = other.clip_polygons(mem::take(&mut self.polygons));
+
+        if let Some(front) = &mut self.front {
+            front.clip_to(other);
+        }
+
+        if let Some(back) = &mut self.back {
+            back.clip_to(other);
+        }
+    }
+
+    fn all_polygons(&self) -> Vec<Polygon> {
+        let mut polygons = self.polygons.clone();
+
+        for node in [&self.front, &self.back].into_iter().flatten() {
+            polygons.extend(node.all_polygons());
+        }
+
+        polygons
+    }
+}
+
+///
+/// Returns side of point given by intersection of planes `p`, `q` and `r` relative to plane `h`.
+/// For matrix `M` with rows `(normal, d)` of all four planes `det(M) = det(N) * h(x)`,
+/// where `N` consists of normals of `p`, `q`, `r` and `x` is their intersection point.
+///
+fn vertex_side(p: &Plane, q: &Plane, r: &Plane, h: &Plane) -> i32 {
+    let normals = det3([p.normal, q.normal, r.normal]);
+
+    // Expansion along last column
+    let det = I256::from(h.d) * normals - I256::from(p.d) * det3([q.normal, r.normal, h.normal])
+        + I256::from(q.d) * det3([p.normal, r.normal, h.normal])
+        - I256::from(r.d) * det3([p.normal, q.normal, h.normal]);
+
+    det.signum() * normals.signum()
+}
+
+fn det3(m: [[i128; 3]; 3]) -> I256 {
+    let minor = |a: usize, b: usize| {
+        I256::from(m[1][a]) * I256::from(m[2][b]) - I256::from(m[1][b]) * I256::from(m[2][a])
+    };
+
+    I256::from(m[0][0]) * minor(1, 2) - I256::from(m[0][1]) * minor(0, 2) + I256::from(m[0][2]) * minor(0, 1)
+}
+
+#[inline]
+fn dot(a: &[i128; 3], b: &[i128; 3]) -> i128 {
+    a[0] * b[0] + a[1] * b[1] + a[2] * b[2]
+}
+
+#[inline]
+fn cross(a: &[i128; 3], b: &[i128; 3]) -> [i128; 3] {
+    [
+        a[1] * b[2] - a[2] * b[1],
+        a[2] * b[0] - a[0] * b[2],
+        a[0] * b[1] - a[1] * b[0],
+    ]
+}
+
+#[inline]
+fn sub(a: &[i128; 3], b: &[i128; 3]) -> [i128; 3] {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2]]
+}
+
+///
+/// 256-bit signed integer in two's complement, least significant limb first.
+/// Overflow wraps, callers keep values within range.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct I256([u64; 4]);
+
+impl I256 {
+    const ZERO: Self = Self([0; 4]);
+
+    #[inline]
+    fn is_negative(&self) -> bool {
+        (self.0[3] as i64) < 0
+    }
+
+    #[inline]
+    fn signum(&self) -> i32 {
+        if self.is_negative() {
+            -1
+        } else if *self == Self::ZERO {
+            0
+        } else {
+            1
+        }
+    }
+
+    #[inline]
+    fn magnitude(&self) -> [u64; 4] {
+        if self.is_negative() {
+            (-*self).0
+        } else {
+            self.0
+        }
+    }
+
+    fn to_f64(self) -> f64 {
+        let magnitude = self.magnitude().iter().rev().fold(0.0, |acc, &limb| acc * 18446744073709551616.0 + limb as f64);
+
+        if self.is_negative() {
+            -magnitude
+        } else {
+            magnitude
+        }
+    }
+}
+
+impl From<i128> for I256 {
+    #[inline]
+    fn from(value: i128) -> Self {
+        let low = value as u128;
+        let high = if value < 0 { u64::MAX } else { 0 };
+
+        Self([low as u64, (low >> 64) as u64, high, high])
+    }
+}
+
+impl Add for I256 {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        let mut result = [0; 4];
+        let mut carry = false;
+
+        for (i, limb) in result.iter_mut().enumerate() {
+            let (sum, overflow1) = self.0[i].overflowing_add(other.0[i]);
+            let (sum, overflow2) = sum.overflowing_add(carry as u64);
+            *limb = sum;
+            carry = overflow1 || overflow2;
+        }
+
+        Self(result)
+    }
+}
+
+impl Neg for I256 {
+    type Output = Self;
+
+    #[inline]
+    fn neg(self) -> Self {
+        Self(self.0.map(|limb| !limb)) + Self::from(1)
+    }
+}
+
+impl Sub for I256 {
+    type Output = Self;
+
+    #[inline]
+    fn sub(self, other: Self) -> Self {
+        self + (-other)
+    }
+}
+
+impl Mul for I256 {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self {
+        let (a, b) = (self.magnitude(), other.magnitude());
+        let mut result = [0u64; 4];
+
+        // Schoolbook multiplication of magnitudes, truncated to 256 bits
+        for i in 0..4 {
+            let mut carry = 0u128;
+
+            for j in 0..4 - i {
+                let product = a[i] as u128 * b[j] as u128 + result[i + j] as u128 + carry;
+                result[i + j] = product as u64;
+                carry = product >> 64;
+            }
+        }
+
+        if self.is_negative() != other.is_negative() {
+            -Self(result)
+        } else {
+            Self(result)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BooleanOperation, ExactBoolean, I256};
+    use crate::{
+        algo::mass_properties::mass_properties,
+        helpers::aliases::Vec3,
+        mesh::{polygon_soup::data_structure::PolygonSoup, primitives, traits::Mesh},
+    };
+
+    fn cube(offset: Vec3<f64>) -> PolygonSoup<f64> {
+        let cube: PolygonSoup<f64> = primitives::cuboid(Vec3::new(1.0, 1.0, 1.0), 1);
+        let vertices: Vec<_> = cube
+            .faces()
+            .flat_map(|face| {
+                let triangle = cube.face_positions(&face);
+                [*triangle.p1(), *triangle.p2(), *triangle.p3()]
+            })
+            .map(|p| p + offset)
+            .collect();
+
+        PolygonSoup::from(vertices)
+    }
+
+    #[test]
+    fn test_i256() {
+        let a = I256::from(-(1i128 << 100));
+        let b = I256::from(3i128 << 90);
+
+        assert_eq!((a * b).signum(), -1);
+        assert_eq!((a * b * I256::from(-1)).to_f64(), 3.0 * 2f64.powi(190));
+        assert_eq!(a - a, I256::ZERO);
+        assert_eq!((a + b).to_f64(), -(2f64.powi(100)) + 3.0 * 2f64.powi(90));
+    }
+
+    #[test]
+    fn test_coplanar_cubes() {
+        // Cubes share four coplanar sides, which is hard case for floating point booleans
+        let a = cube(Vec3::zeros());
+        let b = cube(Vec3::new(0.5, 0.0, 0.0));
+        let boolean = ExactBoolean::new();
+
+        let expected = [
+            (BooleanOperation::Union, 1.5),
+            (BooleanOperation::Intersection, 0.5),
+            (BooleanOperation::Difference, 0.5),
+        ];
+
+        for (operation, volume) in expected {
+            let result = boolean.compute(&a, &b, operation);
+            let properties = mass_properties(&result, 1.0);
+
+            assert!((properties.volume - volume).abs() < 1e-9, "{:?}: {}", operation, properties.volume);
+            assert!((properties.area - (2.0 + 4.0 * volume)).abs() < 1e-9, "{:?}: {}", operation, properties.area);
+        }
+    }
+
+    #[test]
+    fn test_corner_overlap() {
+        let a = cube(Vec3::zeros());
+        let b = cube(Vec3::new(0.25, 0.5, 0.75));
+        let result = ExactBoolean::new().compute(&a, &b, BooleanOperation::Intersection);
+
+        // Corners of second cube are not on quantization grid
+        let volume = mass_properties(&result, 1.0).volume;
+        assert!((volume - 0.75 * 0.5 * 0.25).abs() < 1e-5);
+
+        let disjoint = ExactBoolean::new().compute(&a, &cube(Vec3::new(2.0, 0.0, 0.0)), BooleanOperation::Intersection);
+        assert_eq!(disjoint.faces().count(), 0);
+    }
+}
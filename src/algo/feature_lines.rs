@@ -0,0 +1,232 @@
+use std::collections::HashMap;
+
+use num_traits::cast;
+
+use crate::{
+    geometry::traits::RealNumber,
+    helpers::aliases::Vec3,
+    mesh::traits::TopologicalMesh,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureKind {
+    /// Edge with dihedral angle above threshold
+    Crease,
+    /// Edge with single adjacent face
+    Boundary,
+    /// Edge between front and back facing faces
+    Silhouette,
+}
+
+///
+/// Indexed polylines. Closed polyline ends with its first point index.
+///
+#[derive(Debug, Clone)]
+pub struct PolylineSet<TScalar: RealNumber> {
+    /// Points shared by polylines
+    pub points: Vec<Vec3<TScalar>>,
+    /// Point indices of each polyline
+    pub polylines: Vec<Vec<usize>>,
+    /// Kind of feature each polyline belongs to
+    pub kinds: Vec<FeatureKind>,
+}
+
+///
+/// Extracts feature lines of mesh for non-photorealistic rendering, previews or engraving paths:
+/// sharp creases, boundaries and view dependent silhouettes.
+/// Feature edges of each kind are chained into polylines broken at endpoints and junctions.
+///
+/// ## Example
+/// ```ignore
+/// let lines = FeatureLines::new()
+///     .with_crease_angle(Some(40.0f32.to_radians()))
+///     .with_view_point(Some(camera_position))
+///     .extract(&mesh);
+/// ```
+///
+pub struct FeatureLines<TScalar: RealNumber> {
+    crease_angle: Option<TScalar>,
+    boundaries: bool,
+    view_point: Option<Vec3<TScalar>>,
+}
+
+impl<TScalar: RealNumber> FeatureLines<TScalar> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set min angle (in radians) between normals of faces adjacent to crease edge. `None` disables creases. Default is 30 degrees.
+    #[inline]
+    pub fn with_crease_angle(mut self, angle: Option<TScalar>) -> Self {
+        self.crease_angle = angle;
+        self
+    }
+
+    /// Set whether boundaries should be extracted. Default is `true`
+    #[inline]
+    pub fn with_boundaries(mut self, boundaries: bool) -> Self {
+        self.boundaries = boundaries;
+        self
+    }
+
+    /// Set position of viewer used to find silhouettes. `None` (default) disables silhouettes.
+    #[inline]
+    pub fn with_view_point(mut self, view_point: Option<Vec3<TScalar>>) -> Self {
+        self.view_point = view_point;
+        self
+    }
+
+    pub fn extract<TMesh: TopologicalMesh<ScalarType = TScalar>>(&self, mesh: &TMesh) -> PolylineSet<TScalar> {
+        let mut vertex_index = HashMap::new();
+        let mut points = Vec::new();
+        let mut edges = [Vec::new(), Vec::new(), Vec::new()];
+        let kinds = [FeatureKind::Crease, FeatureKind::Boundary, FeatureKind::Silhouette];
+
+        for edge in mesh.edges() {
+            let (face1, face2) = mesh.edge_faces(&edge);
+
+            let is_feature = match face2 {
+                Some(face2) => {
+                    let (n1, n2) = (mesh.face_normal(&face1), mesh.face_normal(&face2));
+                    let is_crease = self.crease_angle.is_some_and(|angle| n1.angle(&n2) > angle);
+                    let is_silhouette = self.view_point.is_some_and(|eye| {
+                        let facing = |face, normal: &Vec3<TScalar>| {
+                            (mesh.face_positions(&face).center() - eye).dot(normal) < TScalar::zero()
+                        };
+
+                        facing(face1, &n1) != facing(face2, &n2)
+                    });
+
+                    [is_crease, false, is_silhouette]
+                }
+                None => [false, self.boundaries, false],
+            };
+
+            if !is_feature.contains(&true) {
+                continue;
+            }
+
+            let (v1, v2) = mesh.edge_vertices(&edge);
+            let [i1, i2] = [v1, v2].map(|v| {
+                *vertex_index.entry(v).or_insert_with(|| {
+                    points.push(*mesh.vertex_position(&v));
+                    points.len() - 1
+                })
+            });
+
+            for (kind_edges, _) in edges.iter_mut().zip(is_feature).filter(|(_, is)| *is) {
+                kind_edges.push((i1, i2));
+            }
+        }
+
+        let mut polylines = Vec::new();
+        let mut polyline_kinds = Vec::new();
+
+        for (kind_edges, kind) in edges.iter().zip(kinds) {
+            let chained = chain_edges(kind_edges, points.len());
+            polyline_kinds.extend(std::iter::repeat_n(kind, chained.len()));
+            polylines.extend(chained);
+        }
+
+        PolylineSet {
+            points,
+            polylines,
+            kinds: polyline_kinds,
+        }
+    }
+}
+
+impl<TScalar: RealNumber> Default for FeatureLines<TScalar> {
+    fn default() -> Self {
+        Self {
+            crease_angle: Some(cast::<f64, TScalar>(30.0).unwrap().to_radians()),
+            boundaries: true,
+            view_point: None,
+        }
+    }
+}
+
+/// Chains edges into polylines. Open polylines start and end at vertices with valence other than 2, the rest are cycles.
+fn chain_edges(edges: &[(usize, usize)], vertices_count: usize) -> Vec<Vec<usize>> {
+    let mut adjacency = vec![Vec::new(); vertices_count];
+    for (index, &(v1, v2)) in edges.iter().enumerate() {
+        adjacency[v1].push(index);
+        adjacency[v2].push(index);
+    }
+
+    let other = |edge: usize, vertex: usize| if edges[edge].0 == vertex { edges[edge].1 } else { edges[edge].0 };
+    let mut used = vec![false; edges.len()];
+    let mut polylines = Vec::new();
+
+    let open_starts = (0..vertices_count).filter(|&v| !adjacency[v].is_empty() && adjacency[v].len() != 2);
+
+    for start in open_starts.chain(0..vertices_count) {
+        while let Some(&first) = adjacency[start].iter().find(|&&e| !used[e]) {
+            let mut polyline = vec![start];
+            let (mut edge, mut vertex) = (first, start);
+
+            loop {
+                used[edge] = true;
+                vertex = other(edge, vertex);
+                polyline.push(vertex);
+
+                if adjacency[vertex].len() != 2 {
+                    break;
+                }
+
+                match adjacency[vertex].iter().find(|&&e| !used[e]) {
+                    Some(&next) => edge = next,
+                    None => break,
+                }
+            }
+
+            polylines.push(polyline);
+        }
+    }
+
+    polylines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FeatureKind, FeatureLines};
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, primitives},
+    };
+
+    #[test]
+    fn test_cube_creases_and_silhouette() {
+        let cube: CornerTableF = primitives::cuboid(Vec3f::new(1.0, 1.0, 1.0), 2);
+        let lines = FeatureLines::new().extract(&cube);
+
+        // Edges of cube, each split in two segments, are broken at corners
+        assert_eq!(lines.polylines.len(), 12);
+        assert!(lines.polylines.iter().all(|polyline| polyline.len() == 3));
+        assert!(lines.kinds.iter().all(|kind| *kind == FeatureKind::Crease));
+
+        // Only top is visible from above, its border is closed silhouette
+        let lines = FeatureLines::new()
+            .with_crease_angle(None)
+            .with_view_point(Some(Vec3f::new(0.0, 0.0, 10.0)))
+            .extract(&cube);
+
+        assert_eq!(lines.polylines.len(), 1);
+        assert_eq!(lines.kinds, vec![FeatureKind::Silhouette]);
+
+        let silhouette = &lines.polylines[0];
+        assert_eq!(silhouette.len(), 9);
+        assert_eq!(silhouette.first(), silhouette.last());
+        assert!(silhouette.iter().all(|&i| lines.points[i].z == 0.5));
+    }
+
+    #[test]
+    fn test_boundary() {
+        let plane: CornerTableF = primitives::plane(1.0, 1.0, 2, 3);
+        let lines = FeatureLines::new().extract(&plane);
+
+        assert_eq!(lines.kinds, vec![FeatureKind::Boundary]);
+        assert_eq!(lines.polylines[0].len(), 11);
+        assert_eq!(lines.points.len(), 10);
+    }
+}
@@ -0,0 +1,551 @@
+use std::{
+    cmp::Ordering,
+    collections::{BinaryHeap, HashMap},
+};
+
+use num_traits::cast;
+
+use crate::{
+    algo::merge_points::merge_points,
+    geometry::traits::RealNumber,
+    helpers::aliases::{Vec2, Vec3},
+    mesh::traits::Mesh,
+};
+
+/// Parameterization is solved until squared norm of residual drops below this value
+const SOLVER_TOLERANCE: f64 = 1e-20;
+
+///
+/// Geometry image: surface of closed genus-0 mesh resampled into square grid of positions (`resolution` x `resolution`),
+/// so mesh can be processed as 3-channel image, e.g. by convolutional networks.
+///
+/// Mesh is cut along path between two most distant vertices, which opens it into disk. Disk is mapped onto unit square
+/// by Tutte embedding (every vertex is at average of its neighbors) with cut path going along square boundary:
+/// one side of cut covers bottom and right sides, other one covers left and top sides. So boundary of image is glued
+/// by mirroring over diagonal: pixel `(i, 0)` is pixel `(0, i)` and pixel `(n - 1, i)` is pixel `(i, n - 1)`,
+/// which is used to stitch seam when image is converted back to mesh.
+///
+/// ## Example
+/// ```ignore
+/// let image = GeometryImage::from_mesh(&mesh, 64)?;
+/// let features: Vec<f32> = image.positions().iter().flat_map(|p| p.iter().copied()).collect();
+///
+/// // Positions predicted by model
+/// let image = GeometryImage::from_positions(64, predicted)?;
+/// let mesh: CornerTableF = image.to_mesh();
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct GeometryImage<TScalar: RealNumber> {
+    resolution: usize,
+    positions: Vec<Vec3<TScalar>>,
+}
+
+impl<TScalar: RealNumber> GeometryImage<TScalar> {
+    ///
+    /// Parameterizes mesh onto square and samples positions at `resolution` x `resolution` grid points.
+    /// Returns `None` when mesh is not closed connected genus-0 surface or resolution is less than 3.
+    ///
+    pub fn from_mesh<TMesh: Mesh<ScalarType = TScalar>>(mesh: &TMesh, resolution: usize) -> Option<Self> {
+        if resolution < 3 {
+            return None;
+        }
+
+        let soup: Vec<_> = mesh
+            .faces()
+            .flat_map(|face| {
+                let triangle = mesh.face_positions(&face);
+                [*triangle.p1(), *triangle.p2(), *triangle.p3()]
+            })
+            .collect();
+        let indexed = merge_points(&soup);
+        let faces: Vec<[usize; 3]> = indexed.indices.chunks_exact(3).map(|face| [face[0], face[1], face[2]]).collect();
+
+        let disk = Disk::cut(&indexed.points, faces)?;
+        let uv = disk.parameterize();
+
+        Some(Self {
+            resolution,
+            positions: disk.resample(&uv, resolution),
+        })
+    }
+
+    /// Creates image from positions stored row by row (pixel `(i, j)` at `j * resolution + i`)
+    pub fn from_positions(resolution: usize, positions: Vec<Vec3<TScalar>>) -> Option<Self> {
+        if resolution < 3 || positions.len() != resolution * resolution {
+            return None;
+        }
+
+        Some(Self { resolution, positions })
+    }
+
+    /// Returns number of pixels along side of image
+    #[inline]
+    pub fn resolution(&self) -> usize {
+        self.resolution
+    }
+
+    /// Returns positions stored row by row (pixel `(i, j)` at `j * resolution + i`)
+    #[inline]
+    pub fn positions(&self) -> &[Vec3<TScalar>] {
+        &self.positions
+    }
+
+    #[inline]
+    pub fn get(&self, i: usize, j: usize) -> &Vec3<TScalar> {
+        &self.positions[j * self.resolution + i]
+    }
+
+    ///
+    /// Converts image back to closed mesh: every grid cell is split into two triangles and pixels glued by seam are merged.
+    /// Seam pixels are taken from bottom and right sides of image.
+    ///
+    pub fn to_mesh<TMesh: Mesh<ScalarType = TScalar>>(&self) -> TMesh {
+        let n = self.resolution;
+
+        // Pixels of left and top sides are replaced by their mirrors
+        let seam = |i: usize, j: usize| {
+            if i == 0 {
+                (j, 0)
+            } else if j == n - 1 {
+                (n - 1, i)
+            } else {
+                (i, j)
+            }
+        };
+
+        // Triangles by sorted pixels, cells next to collapsed corners fold over each other and cancel out
+        let mut triangles = HashMap::new();
+        let mut order = Vec::with_capacity(2 * (n - 1) * (n - 1));
+
+        for j in 0..n - 1 {
+            for i in 0..n - 1 {
+                let [a, b, c, d] = [(i, j), (i + 1, j), (i + 1, j + 1), (i, j + 1)].map(|(i, j)| seam(i, j));
+
+                for triangle in [[a, b, d], [b, c, d]] {
+                    // Cells at corners of image collapse, because their sides are glued
+                    if triangle[0] == triangle[1] || triangle[1] == triangle[2] || triangle[2] == triangle[0] {
+                        continue;
+                    }
+
+                    let mut key = triangle;
+                    key.sort_unstable();
+
+                    if triangles.remove(&key).is_none() {
+                        triangles.insert(key, triangle);
+                        order.push(key);
+                    }
+                }
+            }
+        }
+
+        let mut vertices = Vec::new();
+        let mut indices = Vec::with_capacity(3 * order.len());
+        let mut pixel_vertex = HashMap::new();
+
+        for triangle in order.iter().filter_map(|key| triangles.remove(key)) {
+            for (i, j) in triangle {
+                let vertex = *pixel_vertex.entry((i, j)).or_insert_with(|| {
+                    vertices.push(*self.get(i, j));
+                    vertices.len() - 1
+                });
+                indices.push(vertex);
+            }
+        }
+
+        TMesh::from_vertices_and_indices(&vertices, &indices)
+    }
+}
+
+/// Mesh cut open into topological disk
+struct Disk<'a, TScalar: RealNumber> {
+    points: &'a [Vec3<TScalar>],
+    faces: Vec<[usize; 3]>,
+    /// Original point of every disk vertex, cut vertices are duplicated
+    vertex_points: Vec<usize>,
+    /// Boundary vertices starting from first vertex of cut, in order of boundary edges
+    boundary: Vec<usize>,
+}
+
+impl<'a, TScalar: RealNumber> Disk<'a, TScalar> {
+    fn cut(points: &'a [Vec3<TScalar>], mut faces: Vec<[usize; 3]>) -> Option<Self> {
+        // Face by outgoing directed edge of its vertex
+        let mut outgoing = HashMap::new();
+
+        for (index, face) in faces.iter().enumerate() {
+            for k in 0..3 {
+                if outgoing.insert((face[k], face[(k + 1) % 3]), index).is_some() {
+                    return None;
+                }
+            }
+        }
+
+        // Closed surface with Euler characteristic 2
+        let edges = outgoing.len() / 2;
+
+        if faces.is_empty()
+            || outgoing.keys().any(|(from, to)| !outgoing.contains_key(&(*to, *from)))
+            || points.len() + faces.len() != edges + 2
+        {
+            return None;
+        }
+
+        let mut neighbors = vec![Vec::new(); points.len()];
+        for (from, to) in outgoing.keys() {
+            neighbors[*from].push(*to);
+        }
+
+        // Two most distant vertices along edges, all vertices should be reachable
+        let (_, start) = shortest_paths(points, &neighbors, 0)?;
+        let (parents, end) = shortest_paths(points, &neighbors, start)?;
+
+        let mut path = vec![end];
+        while let Some(parent) = parents[*path.last().unwrap()] {
+            path.push(parent);
+        }
+        path.reverse();
+
+        // Cut should pass through vertex, so single edge is replaced by two edges of its face
+        if path.len() == 2 {
+            let face = faces[outgoing[&(path[0], path[1])]];
+            let third = face.iter().copied().find(|vertex| !path.contains(vertex)).unwrap();
+            path.insert(1, third);
+        }
+
+        // Faces on one side of cut (counterclockwise from next to previous path vertex) are moved to duplicates
+        let mut vertex_points: Vec<_> = (0..points.len()).collect();
+        let mut moved = Vec::new();
+
+        for window in path.windows(3) {
+            let [previous, vertex, next] = [window[0], window[1], window[2]];
+            let duplicate = vertex_points.len();
+            vertex_points.push(vertex);
+
+            let mut current = next;
+
+            while current != previous {
+                let face = outgoing[&(vertex, current)];
+                let k = faces[face].iter().position(|v| *v == vertex).unwrap();
+                current = faces[face][(k + 2) % 3];
+
+                // Faces around non-manifold vertex don't form single fan
+                if current == next {
+                    return None;
+                }
+
+                moved.push((face, k, duplicate));
+            }
+        }
+
+        for (face, k, duplicate) in moved {
+            faces[face][k] = duplicate;
+        }
+
+        // Boundary edges are edges without opposite after cut
+        let mut directed = HashMap::new();
+        for face in &faces {
+            for k in 0..3 {
+                directed.insert((face[k], face[(k + 1) % 3]), ());
+            }
+        }
+
+        let next: HashMap<_, _> = directed
+            .keys()
+            .filter(|(from, to)| !directed.contains_key(&(*to, *from)))
+            .copied()
+            .collect();
+
+        let mut boundary = vec![path[0]];
+        while let Some(vertex) = next.get(boundary.last().unwrap()).filter(|vertex| **vertex != path[0]) {
+            boundary.push(*vertex);
+        }
+
+        Some(Self {
+            points,
+            faces,
+            vertex_points,
+            boundary,
+        })
+    }
+
+    #[inline]
+    fn position(&self, vertex: usize) -> Vec3<TScalar> {
+        self.points[self.vertex_points[vertex]]
+    }
+
+    ///
+    /// Maps boundary onto square by arc length, so both sides of cut are mirrored over diagonal,
+    /// and solves for interior vertices placed at average of their neighbors.
+    ///
+    fn parameterize(&self) -> Vec<Vec2<f64>> {
+        let vertices_count = self.vertex_points.len();
+        let mut uv = vec![Vec2::zeros(); vertices_count];
+        let mut fixed = vec![false; vertices_count];
+
+        let lengths: Vec<f64> = (0..self.boundary.len())
+            .map(|k| {
+                let edge = self.position(self.boundary[(k + 1) % self.boundary.len()]) - self.position(self.boundary[k]);
+                cast(edge.norm()).unwrap()
+            })
+            .collect();
+        let perimeter: f64 = lengths.iter().sum();
+        let mut length = 0.0;
+
+        for (k, vertex) in self.boundary.iter().enumerate() {
+            uv[*vertex] = square_point(4.0 * length / perimeter);
+            fixed[*vertex] = true;
+            length += lengths[k];
+        }
+
+        let mut neighbors = vec![Vec::new(); vertices_count];
+        for face in &self.faces {
+            for k in 0..3 {
+                neighbors[face[k]].push(face[(k + 1) % 3]);
+                neighbors[face[(k + 1) % 3]].push(face[k]);
+            }
+        }
+
+        for ring in &mut neighbors {
+            ring.sort_unstable();
+            ring.dedup();
+        }
+
+        // Laplacian of free vertices is symmetric positive definite, so conjugate gradients are used for both coordinates
+        let free: Vec<_> = (0..vertices_count).filter(|vertex| !fixed[*vertex]).collect();
+        let mut free_index = vec![usize::MAX; vertices_count];
+        for (index, vertex) in free.iter().enumerate() {
+            free_index[*vertex] = index;
+        }
+
+        let multiply = |x: &[f64], result: &mut [f64]| {
+            for (index, vertex) in free.iter().enumerate() {
+                let ring = &neighbors[*vertex];
+                result[index] = ring.len() as f64 * x[index]
+                    - ring.iter().filter(|n| !fixed[**n]).map(|n| x[free_index[*n]]).sum::<f64>();
+            }
+        };
+
+        let [u, v] = [0, 1].map(|axis| {
+            let rhs: Vec<f64> = free
+                .iter()
+                .map(|vertex| neighbors[*vertex].iter().filter(|n| fixed[**n]).map(|n| uv[*n][axis]).sum())
+                .collect();
+
+            conjugate_gradients(multiply, &rhs)
+        });
+
+        for (index, vertex) in free.iter().enumerate() {
+            uv[*vertex] = Vec2::new(u[index], v[index]);
+        }
+
+        uv
+    }
+
+    /// Samples positions at grid points by interpolation in triangle of parameterization containing them
+    fn resample(&self, uv: &[Vec2<f64>], resolution: usize) -> Vec<Vec3<TScalar>> {
+        let scale = (resolution - 1) as f64;
+        let mut positions = vec![Vec3::zeros(); resolution * resolution];
+
+        // Pixels not covered due to rounding take closest triangle, so best (largest) smallest barycentric coordinate is kept
+        let mut best = vec![f64::MIN; resolution * resolution];
+
+        for face in &self.faces {
+            let [a, b, c] = face.map(|vertex| uv[vertex] * scale);
+            let area = (b - a).perp(&(c - a));
+
+            if area <= 0.0 {
+                continue;
+            }
+
+            let min = a.inf(&b).inf(&c).map(|x| (x.floor() as isize - 1).max(0) as usize);
+            let max = a.sup(&b).sup(&c).map(|x| (x.ceil() as usize + 1).min(resolution - 1));
+
+            for j in min.y..=max.y {
+                for i in min.x..=max.x {
+                    let pixel = Vec2::new(i as f64, j as f64);
+                    let weights = [(c - b).perp(&(pixel - b)), (a - c).perp(&(pixel - c)), (b - a).perp(&(pixel - a))]
+                        .map(|weight| weight / area);
+                    let smallest = weights.iter().copied().fold(f64::MAX, f64::min);
+                    let index = j * resolution + i;
+
+                    if smallest <= best[index] {
+                        continue;
+                    }
+
+                    best[index] = smallest;
+
+                    let weights = weights.map(|weight| weight.max(0.0));
+                    let sum: f64 = weights.iter().sum();
+                    positions[index] = face
+                        .iter()
+                        .zip(weights)
+                        .fold(Vec3::zeros(), |position, (vertex, weight)| {
+                            position + self.position(*vertex) * cast::<f64, TScalar>(weight / sum).unwrap()
+                        });
+                }
+            }
+        }
+
+        positions
+    }
+}
+
+/// Returns point on boundary of unit square at given distance (in sides) counterclockwise from origin
+fn square_point(distance: f64) -> Vec2<f64> {
+    match distance {
+        d if d < 1.0 => Vec2::new(d, 0.0),
+        d if d < 2.0 => Vec2::new(1.0, d - 1.0),
+        d if d < 3.0 => Vec2::new(3.0 - d, 1.0),
+        d => Vec2::new(0.0, (4.0 - d).max(0.0)),
+    }
+}
+
+/// Vertex queued by [shortest_paths]
+struct Visit {
+    distance: f64,
+    vertex: usize,
+}
+
+impl PartialEq for Visit {
+    fn eq(&self, other: &Self) -> bool {
+        self.distance == other.distance
+    }
+}
+
+impl Eq for Visit {}
+
+impl PartialOrd for Visit {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Visit {
+    /// Closest vertex has highest priority
+    fn cmp(&self, other: &Self) -> Ordering {
+        other.distance.total_cmp(&self.distance)
+    }
+}
+
+///
+/// Dijkstra along edges. Returns parent of every vertex on shortest path from `source` and most distant vertex,
+/// `None` when some vertex is not reachable.
+///
+fn shortest_paths<TScalar: RealNumber>(
+    points: &[Vec3<TScalar>],
+    neighbors: &[Vec<usize>],
+    source: usize,
+) -> Option<(Vec<Option<usize>>, usize)> {
+    let mut distances = vec![f64::INFINITY; points.len()];
+    let mut parents = vec![None; points.len()];
+    let mut queue = BinaryHeap::new();
+
+    distances[source] = 0.0;
+    queue.push(Visit { distance: 0.0, vertex: source });
+
+    while let Some(Visit { distance, vertex }) = queue.pop() {
+        if distance > distances[vertex] {
+            continue;
+        }
+
+        for neighbor in &neighbors[vertex] {
+            let length: f64 = cast((points[*neighbor] - points[vertex]).norm()).unwrap();
+
+            if distance + length < distances[*neighbor] {
+                distances[*neighbor] = distance + length;
+                parents[*neighbor] = Some(vertex);
+                queue.push(Visit {
+                    distance: distance + length,
+                    vertex: *neighbor,
+                });
+            }
+        }
+    }
+
+    if distances.iter().any(|distance| distance.is_infinite()) {
+        return None;
+    }
+
+    let farthest = (0..points.len()).max_by(|a, b| distances[*a].total_cmp(&distances[*b]))?;
+
+    Some((parents, farthest))
+}
+
+/// Solves `A x = b` for symmetric positive definite `A` given by multiplication
+fn conjugate_gradients(multiply: impl Fn(&[f64], &mut [f64]), rhs: &[f64]) -> Vec<f64> {
+    let mut x = vec![0.0; rhs.len()];
+    let mut residual = rhs.to_vec();
+    let mut direction = residual.clone();
+    let mut product = vec![0.0; rhs.len()];
+    let dot = |a: &[f64], b: &[f64]| a.iter().zip(b).map(|(a, b)| a * b).sum::<f64>();
+    let mut residual_norm = dot(&residual, &residual);
+
+    for _ in 0..rhs.len() {
+        if residual_norm <= SOLVER_TOLERANCE {
+            break;
+        }
+
+        multiply(&direction, &mut product);
+        let step = residual_norm / dot(&direction, &product);
+
+        for ((x, residual), (direction, product)) in x.iter_mut().zip(&mut residual).zip(direction.iter().zip(&product)) {
+            *x += step * direction;
+            *residual -= step * product;
+        }
+
+        let norm = dot(&residual, &residual);
+
+        for (direction, residual) in direction.iter_mut().zip(&residual) {
+            *direction = residual + norm / residual_norm * *direction;
+        }
+
+        residual_norm = norm;
+    }
+
+    x
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GeometryImage;
+    use crate::{
+        algo::mass_properties::mass_properties,
+        helpers::aliases::Vec3f,
+        mesh::{
+            corner_table::prelude::CornerTableF,
+            primitives,
+            traits::{Mesh, TopologicalMesh},
+        },
+    };
+
+    #[test]
+    fn test_geometry_image() {
+        let sphere: CornerTableF = primitives::icosphere(1.0, 3);
+        let image = GeometryImage::from_mesh(&sphere, 48).unwrap();
+        assert_eq!(image.positions().len(), 48 * 48);
+        assert!(image.positions().iter().all(|position| position.norm() > 0.95 && position.norm() < 1.0 + 1e-5));
+
+        // Seam sides of image sample same points
+        for i in 0..48 {
+            assert!((image.get(i, 0) - image.get(0, i)).norm() < 1e-4);
+            assert!((image.get(47, i) - image.get(i, 47)).norm() < 1e-4);
+        }
+
+        let mesh: CornerTableF = image.to_mesh();
+        assert_eq!(mesh.faces().count(), 2 * 47 * 47 - 8);
+        assert!(mesh.validate_topology().is_valid());
+        assert!(mesh.edges().all(|edge| !mesh.is_edge_on_boundary(&edge)));
+
+        let volume = mass_properties(&mesh, 1.0).volume;
+        let expected = mass_properties(&sphere, 1.0).volume;
+        assert!((volume - expected).abs() / expected < 0.05);
+
+        // Only genus-0 surfaces can be mapped onto square
+        let torus: CornerTableF = primitives::torus(1.0, 0.3, 16, 8);
+        assert!(GeometryImage::from_mesh(&torus, 16).is_none());
+
+        let plane: CornerTableF = primitives::plane(1.0, 1.0, 4, 4);
+        assert!(GeometryImage::from_mesh(&plane, 16).is_none());
+        assert!(GeometryImage::from_positions(4, vec![Vec3f::zeros(); 15]).is_none());
+    }
+}
@@ -0,0 +1,231 @@
+use std::collections::{hash_map::Entry, HashMap};
+
+use num_traits::{cast, Float};
+
+use crate::{
+    algo::merge_points::merge_points,
+    geometry::traits::RealNumber,
+    helpers::aliases::Vec3,
+    mesh::{corner_table::table::CornerTable, traits::{Mesh, TopologicalMesh}},
+};
+
+///
+/// Fixes done by [make_manifold] and diagnostics of resulting mesh
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ManifoldReport {
+    /// Distinct points merged into nearby ones
+    pub welded_vertices: usize,
+    /// Faces removed because their vertices were merged
+    pub degenerate_faces: usize,
+    /// Repeated faces removed, faces with reversed orientation cancel each other
+    pub duplicate_faces: usize,
+    /// Edges shared by more than two faces or by inconsistently oriented faces, split in result
+    pub non_manifold_edges: usize,
+    /// Vertices duplicated to split non-manifold edges and vertices
+    pub duplicated_vertices: usize,
+    /// Edges of one face in result, zero when surface is closed
+    pub boundary_edges: usize,
+}
+
+impl ManifoldReport {
+    /// Returns `true` when input was already closed manifold and nothing was fixed
+    #[inline]
+    pub fn is_clean(&self) -> bool {
+        *self == Self::default()
+    }
+
+    /// Returns `true` when result has no boundary
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.boundary_edges == 0
+    }
+}
+
+///
+/// Builds manifold mesh from triangle soup (every three points are face), e.g. output of marching cubes.
+/// Points closer than `weld_tolerance` are welded, faces collapsed by welding are removed, duplicate faces are removed
+/// and remaining non-manifold edges and vertices are split (see [CornerTable::from_faces_split_non_manifold]).
+/// Result can be passed to algorithms requiring manifold input, e.g. decimation.
+///
+/// ## Example
+/// ```ignore
+/// let faces = MarchingCubesMesher::default().with_voxel_size(voxel_size).mesh_triangles(&volume);
+/// let (mesh, report) = make_manifold(&faces, voxel_size * 1e-3);
+/// assert!(report.is_closed());
+/// ```
+///
+pub fn make_manifold<TScalar: RealNumber>(triangles: &[Vec3<TScalar>], weld_tolerance: TScalar) -> (CornerTable<TScalar>, ManifoldReport) {
+    let mut report = ManifoldReport::default();
+
+    let indexed = merge_points(&triangles.to_vec());
+    let (points, welded) = weld_points(&indexed.points, weld_tolerance);
+    report.welded_vertices = indexed.points.len() - points.len();
+
+    // Faces by sorted vertices, reversed faces cancel each other
+    let mut faces: HashMap<[usize; 3], ([usize; 3], usize)> = HashMap::new();
+    let mut order = Vec::new();
+    let mut faces_count = 0;
+
+    for face in indexed.indices.chunks_exact(3) {
+        let face = [welded[face[0]], welded[face[1]], welded[face[2]]];
+
+        if face[0] == face[1] || face[1] == face[2] || face[2] == face[0] {
+            report.degenerate_faces += 1;
+            continue;
+        }
+
+        faces_count += 1;
+        let mut key = face;
+        key.sort_unstable();
+
+        match faces.entry(key) {
+            Entry::Occupied(mut entry) => {
+                let (kept, count) = entry.get_mut();
+
+                if *count == 0 {
+                    *kept = face;
+                    *count = 1;
+                } else if same_orientation(kept, &face) {
+                    *count += 1;
+                } else {
+                    *count -= 1;
+                }
+            }
+            Entry::Vacant(entry) => {
+                entry.insert((face, 1));
+                order.push(key);
+            }
+        }
+    }
+
+    // Every face is kept once unless reversed copies cancelled it
+    let kept: Vec<_> = order
+        .iter()
+        .filter_map(|key| {
+            let (face, count) = faces[key];
+            (count > 0).then_some(face)
+        })
+        .collect();
+    report.duplicate_faces = faces_count - kept.len();
+
+    let (mesh, split) = CornerTable::from_faces_split_non_manifold(&points, kept);
+    report.non_manifold_edges = split.non_manifold_edges.len();
+    report.duplicated_vertices = split.duplicated_vertices.len();
+    report.boundary_edges = mesh.edges().filter(|edge| mesh.is_edge_on_boundary(edge)).count();
+
+    (mesh, report)
+}
+
+/// Returns `true` when `b` is rotation of `a`
+#[inline]
+fn same_orientation(a: &[usize; 3], b: &[usize; 3]) -> bool {
+    (0..3).any(|shift| a[0] == b[shift] && a[1] == b[(shift + 1) % 3])
+}
+
+///
+/// Merges points closer than `tolerance` into first of them. Returns merged points and index of merged point for every input one.
+/// Points are bucketed by cells of size `tolerance`, so only neighboring cells are searched.
+///
+pub(crate) fn weld_points<TScalar: RealNumber>(points: &[Vec3<TScalar>], tolerance: TScalar) -> (Vec<Vec3<TScalar>>, Vec<usize>) {
+    if tolerance <= TScalar::zero() {
+        return (points.to_vec(), (0..points.len()).collect());
+    }
+
+    let cell = |point: &Vec3<TScalar>| point.map(|coordinate| cast::<TScalar, i64>(Float::floor(coordinate / tolerance)).unwrap());
+    let tolerance_squared = tolerance * tolerance;
+
+    let mut cells: HashMap<Vec3<i64>, Vec<usize>> = HashMap::new();
+    let mut welded: Vec<Vec3<TScalar>> = Vec::new();
+    let mut indices = Vec::with_capacity(points.len());
+
+    for point in points {
+        let center = cell(point);
+        let mut found = None;
+
+        'search: for x in -1..=1 {
+            for y in -1..=1 {
+                for z in -1..=1 {
+                    let neighbor = center + Vec3::new(x, y, z);
+
+                    if let Some(candidates) = cells.get(&neighbor) {
+                        found = candidates
+                            .iter()
+                            .copied()
+                            .find(|candidate: &usize| (welded[*candidate] - point).norm_squared() <= tolerance_squared);
+
+                        if found.is_some() {
+                            break 'search;
+                        }
+                    }
+                }
+            }
+        }
+
+        let index = found.unwrap_or_else(|| {
+            welded.push(*point);
+            cells.entry(center).or_default().push(welded.len() - 1);
+            welded.len() - 1
+        });
+
+        indices.push(index);
+    }
+
+    (welded, indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::make_manifold;
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+    };
+
+    fn triangles(mesh: &CornerTableF) -> Vec<Vec3f> {
+        mesh.faces()
+            .flat_map(|face| {
+                let triangle = mesh.face_positions(&face);
+                [*triangle.p1(), *triangle.p2(), *triangle.p3()]
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_make_manifold() {
+        let sphere: CornerTableF = primitives::icosphere(1.0, 2);
+        let faces_count = sphere.faces().count();
+
+        let (mesh, report) = make_manifold(&triangles(&sphere), 1e-4);
+        assert!(report.is_clean());
+        assert_eq!(mesh.faces().count(), faces_count);
+
+        // Jittered copies of points are welded, collapsed sliver and repeated faces are removed
+        let mut soup = triangles(&sphere);
+        soup.iter_mut().step_by(7).for_each(|point| point.x += 1e-5);
+        let (a, b, far) = (soup[6], soup[7], Vec3f::new(2.0, 0.0, 0.0));
+        soup.extend_from_slice(&[soup[0], soup[0] + Vec3f::new(0.0, 1e-5, 0.0), soup[1]]);
+        soup.extend_from_within(3..6);
+        soup.extend_from_slice(&[a, b, far, b, a, far]);
+
+        let (mesh, report) = make_manifold(&soup, 1e-4);
+        assert!(report.welded_vertices > 0);
+        assert_eq!(report.degenerate_faces, 1);
+        assert_eq!(report.duplicate_faces, 3);
+        assert!(report.is_closed());
+        assert_eq!(report.non_manifold_edges, 0);
+        assert_eq!(mesh.faces().count(), faces_count);
+
+        // Two cubes sharing an edge
+        let mut cubes = triangles(&primitives::cuboid(Vec3f::new(1.0, 1.0, 1.0), 1));
+        let shifted: Vec<_> = cubes.iter().map(|point| point + Vec3f::new(1.0, 1.0, 0.0)).collect();
+        cubes.extend(shifted);
+
+        let (mesh, report) = make_manifold(&cubes, 1e-4);
+        assert_eq!(report.non_manifold_edges, 1);
+        assert_eq!(report.duplicated_vertices, 2);
+        assert!(report.is_closed());
+        assert_eq!(mesh.faces().count(), 24);
+    }
+}
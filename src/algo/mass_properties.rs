@@ -0,0 +1,115 @@
+use nalgebra::Matrix3;
+use num_traits::{cast, Zero};
+
+use crate::{geometry::traits::RealNumber, helpers::aliases::Vec3, mesh::traits::Mesh};
+
+///
+/// Mass properties of solid bounded by closed mesh
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MassProperties<TScalar: RealNumber> {
+    /// Total area of faces
+    pub area: TScalar,
+    /// Signed enclosed volume, negative when faces are oriented inwards
+    pub volume: TScalar,
+    /// Volume multiplied by density
+    pub mass: TScalar,
+    /// Center of mass, zero when volume is zero
+    pub center_of_mass: Vec3<TScalar>,
+    /// Inertia tensor relative to center of mass
+    pub inertia: Matrix3<TScalar>,
+}
+
+///
+/// Computes surface area, enclosed volume, center of mass and inertia tensor of solid with uniform `density`.
+/// Solid is decomposed into tetrahedrons formed by faces and origin, so mesh should be closed
+/// and consistently oriented (normals pointing outwards). For open meshes only area is meaningful.
+///
+/// ## Example
+/// ```ignore
+/// let properties = mass_properties(&mesh, 1.0);
+/// assert!(properties.volume > 0.0, "Mesh is inside out");
+/// ```
+///
+pub fn mass_properties<TMesh: Mesh>(mesh: &TMesh, density: TMesh::ScalarType) -> MassProperties<TMesh::ScalarType> {
+    let real = |value: f64| cast::<f64, TMesh::ScalarType>(value).unwrap();
+
+    // Covariance of canonical tetrahedron (0, x, y, z) with unit determinant
+    let canonical = Matrix3::new(2.0, 1.0, 1.0, 1.0, 2.0, 1.0, 1.0, 1.0, 2.0).map(|x| real(x / 120.0));
+
+    let mut area = TMesh::ScalarType::zero();
+    let mut volume = TMesh::ScalarType::zero();
+    let mut first_moment = Vec3::zeros();
+    let mut covariance = Matrix3::zeros();
+
+    for face in mesh.faces() {
+        let triangle = mesh.face_positions(&face);
+        area += triangle.get_area();
+
+        let tetrahedron = Matrix3::from_columns(&[*triangle.p1(), *triangle.p2(), *triangle.p3()]);
+        let determinant = tetrahedron.determinant();
+
+        volume += determinant;
+        first_moment += (triangle.p1() + triangle.p2() + triangle.p3()) * determinant;
+        covariance += tetrahedron * canonical * tetrahedron.transpose() * determinant;
+    }
+
+    volume /= real(6.0);
+    first_moment /= real(24.0);
+
+    let mass = volume * density;
+    let center_of_mass = if volume.is_zero() {
+        Vec3::zeros()
+    } else {
+        first_moment / volume
+    };
+
+    // Move covariance to center of mass and convert it to inertia tensor
+    let covariance = covariance * density - center_of_mass * center_of_mass.transpose() * mass;
+    let inertia = Matrix3::identity() * covariance.trace() - covariance;
+
+    MassProperties {
+        area,
+        volume,
+        mass,
+        center_of_mass,
+        inertia,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::mass_properties;
+    use crate::{
+        helpers::aliases::Vec3,
+        mesh::{corner_table::prelude::CornerTableD, primitives, traits::{EditableMesh, Mesh}},
+    };
+
+    #[test]
+    fn test_box_mass_properties() {
+        let (a, b, c) = (1.0, 2.0, 3.0);
+        let mut mesh: CornerTableD = primitives::cuboid(Vec3::new(a, b, c), 2);
+
+        let offset = Vec3::new(1.0, -2.0, 0.5);
+        let vertices: Vec<_> = mesh.vertices().collect();
+        for vertex in vertices {
+            let position = *mesh.vertex_position(&vertex);
+            mesh.shift_vertex(&vertex, &(position + offset));
+        }
+
+        let density = 2.0;
+        let properties = mass_properties(&mesh, density);
+        let mass = a * b * c * density;
+
+        assert!((properties.area - 2.0 * (a * b + b * c + a * c)).abs() < 1e-9);
+        assert!((properties.volume - a * b * c).abs() < 1e-9);
+        assert!((properties.mass - mass).abs() < 1e-9);
+        assert!((properties.center_of_mass - offset).norm() < 1e-9);
+
+        let expected = Vec3::new(b * b + c * c, a * a + c * c, a * a + b * b) * mass / 12.0;
+        assert!((properties.inertia.diagonal() - expected).norm() < 1e-9);
+        assert!(properties.inertia.m12.abs() < 1e-9);
+        assert!(properties.inertia.m13.abs() < 1e-9);
+        assert!(properties.inertia.m23.abs() < 1e-9);
+    }
+}
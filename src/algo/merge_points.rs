@@ -1,5 +1,5 @@
 use nalgebra::{Scalar, SVector};
-use crate::{data_structures::vertex_index_map::PointIndexMap, geometry::traits::RealNumber};
+use crate::{data_structures::vertex_index_map::PointIndexMap, geometry::traits::RealNumber, mesh::remap::VertexRemap};
 
 pub struct IndexedVertices<const D: usize, TScalar: Scalar> {
     /// Unique points
@@ -8,6 +8,13 @@ pub struct IndexedVertices<const D: usize, TScalar: Scalar> {
     pub indices: Vec<usize>
 }
 
+impl<const D: usize, TScalar: Scalar> IndexedVertices<D, TScalar> {
+    /// Returns table mapping index of input vertex to index of merged one
+    pub fn vertex_remap(&self) -> VertexRemap {
+        VertexRemap::new(self.indices.iter().copied().map(Some).collect())
+    }
+}
+
 ///
 /// Merges exactly coincident points
 /// 
@@ -0,0 +1,245 @@
+use std::collections::HashMap;
+
+use crate::{
+    geometry::traits::RealNumber,
+    helpers::aliases::Vec3,
+    mesh::{
+        remap::{FaceRemap, Remap, VertexRemap},
+        traits::Mesh,
+    },
+};
+
+///
+/// Difference between two meshes, see [mesh_diff].
+/// Vertices and faces are identified by positions in [Mesh::vertices] and [Mesh::faces] iterators.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct MeshPatch<TScalar: RealNumber> {
+    /// Number of vertices of source mesh
+    pub source_vertices: usize,
+    /// Number of faces of source mesh
+    pub source_faces: usize,
+    /// Number of vertices of target mesh
+    pub target_vertices: usize,
+    /// Number of faces of target mesh
+    pub target_faces: usize,
+    /// Maps source vertices to target ones, removed and moved vertices are mapped to `None`
+    pub vertex_remap: VertexRemap,
+    /// Maps source faces to target ones, removed faces are mapped to `None`
+    pub face_remap: FaceRemap,
+    /// Target vertices missing in source: index in target and position
+    pub added_vertices: Vec<(usize, Vec3<TScalar>)>,
+    /// Target faces missing in source: index in target and indices of target vertices
+    pub added_faces: Vec<(usize, [usize; 3])>,
+}
+
+impl<TScalar: RealNumber> MeshPatch<TScalar> {
+    /// Returns `true` when meshes are equal
+    pub fn is_empty(&self) -> bool {
+        self.added_vertices.is_empty()
+            && self.added_faces.is_empty()
+            && self.source_vertices == self.target_vertices
+            && self.source_faces == self.target_faces
+            && self.vertex_remap.iter().all(|(old, new)| old == new)
+            && self.face_remap.iter().all(|(old, new)| old == new)
+    }
+}
+
+///
+/// Computes patch transforming mesh `a` into mesh `b`. Vertices are matched by exact positions,
+/// faces are matched by matched vertices (in same order). Only vertices and faces of `b` which are not found in `a`
+/// are stored in patch, so patch of locally edited mesh (e.g. after remeshing of region) is small.
+///
+/// ## Example
+/// ```ignore
+/// let patch = mesh_diff(&old_version, &new_version);
+/// send(&patch);
+/// let new_version: CornerTableF = apply_patch(&old_version, &patch).unwrap();
+/// ```
+///
+pub fn mesh_diff<TMesh: Mesh>(a: &TMesh, b: &TMesh) -> MeshPatch<TMesh::ScalarType> {
+    let (a_positions, a_faces) = indexed(a);
+    let (b_positions, b_faces) = indexed(b);
+
+    // Target vertices by position, several vertices can share position
+    let mut b_vertices: HashMap<_, Vec<usize>> = HashMap::new();
+    for (index, position) in b_positions.iter().enumerate().rev() {
+        b_vertices.entry(position_key(position)).or_default().push(index);
+    }
+
+    let vertex_map: Vec<_> = a_positions
+        .iter()
+        .map(|position| b_vertices.get_mut(&position_key(position)).and_then(|candidates| candidates.pop()))
+        .collect();
+    let vertex_remap = Remap::new(vertex_map);
+
+    let mut is_vertex_matched = vec![false; b_positions.len()];
+    for (_, new) in vertex_remap.iter() {
+        is_vertex_matched[new] = true;
+    }
+
+    let mut b_face_indices: HashMap<_, Vec<usize>> = HashMap::new();
+    for (index, face) in b_faces.iter().enumerate().rev() {
+        b_face_indices.entry(*face).or_default().push(index);
+    }
+
+    let face_map: Vec<_> = a_faces
+        .iter()
+        .map(|face| {
+            let [v1, v2, v3] = face.map(|v| vertex_remap.get(v));
+            let face = [v1?, v2?, v3?];
+            b_face_indices.get_mut(&face).and_then(|candidates| candidates.pop())
+        })
+        .collect();
+    let face_remap = Remap::new(face_map);
+
+    let mut is_face_matched = vec![false; b_faces.len()];
+    for (_, new) in face_remap.iter() {
+        is_face_matched[new] = true;
+    }
+
+    let added_vertices = b_positions
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !is_vertex_matched[*index])
+        .map(|(index, position)| (index, *position))
+        .collect();
+
+    let added_faces = b_faces
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| !is_face_matched[*index])
+        .map(|(index, face)| (index, *face))
+        .collect();
+
+    MeshPatch {
+        source_vertices: a_positions.len(),
+        source_faces: a_faces.len(),
+        target_vertices: b_positions.len(),
+        target_faces: b_faces.len(),
+        vertex_remap,
+        face_remap,
+        added_vertices,
+        added_faces,
+    }
+}
+
+///
+/// Reconstructs target mesh of patch from its source mesh `a`.
+/// Returns `None` when `a` is not the mesh patch was computed from (sizes mismatch) or patch is inconsistent.
+///
+pub fn apply_patch<TMesh: Mesh>(a: &TMesh, patch: &MeshPatch<TMesh::ScalarType>) -> Option<TMesh> {
+    let (a_positions, a_faces) = indexed(a);
+
+    if a_positions.len() != patch.source_vertices
+        || a_faces.len() != patch.source_faces
+        || patch.vertex_remap.old_len() != patch.source_vertices
+        || patch.face_remap.old_len() != patch.source_faces
+    {
+        return None;
+    }
+
+    let mut positions = vec![None; patch.target_vertices];
+
+    for (old, new) in patch.vertex_remap.iter() {
+        *positions.get_mut(new)? = Some(a_positions[old]);
+    }
+
+    for (index, position) in &patch.added_vertices {
+        *positions.get_mut(*index)? = Some(*position);
+    }
+
+    let mut faces = vec![None; patch.target_faces];
+
+    for (old, new) in patch.face_remap.iter() {
+        let face = a_faces[old].map(|v| patch.vertex_remap.get(v));
+        let [v1, v2, v3] = face;
+        *faces.get_mut(new)? = Some([v1?, v2?, v3?]);
+    }
+
+    for (index, face) in &patch.added_faces {
+        *faces.get_mut(*index)? = Some(*face);
+    }
+
+    let positions: Vec<_> = positions.into_iter().collect::<Option<_>>()?;
+    let indices: Vec<_> = faces.into_iter().collect::<Option<Vec<_>>>()?.concat();
+
+    if indices.iter().any(|index| *index >= positions.len()) {
+        return None;
+    }
+
+    Some(TMesh::from_vertices_and_indices(&positions, &indices))
+}
+
+/// Returns vertex positions and faces as indices of vertices
+fn indexed<TMesh: Mesh>(mesh: &TMesh) -> (Vec<Vec3<TMesh::ScalarType>>, Vec<[usize; 3]>) {
+    let vertex_index: HashMap<_, _> = mesh.vertices().enumerate().map(|(i, v)| (v, i)).collect();
+    let positions = mesh.vertices().map(|v| *mesh.vertex_position(&v)).collect();
+    let faces = mesh
+        .faces()
+        .map(|face| {
+            let (v1, v2, v3) = mesh.face_vertices(&face);
+            [vertex_index[&v1], vertex_index[&v2], vertex_index[&v3]]
+        })
+        .collect();
+
+    (positions, faces)
+}
+
+#[inline]
+fn position_key<TScalar: RealNumber>(position: &Vec3<TScalar>) -> [u64; 3] {
+    [position.x, position.y, position.z].map(|c| c.to_f64().unwrap_or(f64::NAN).to_bits())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_patch, mesh_diff};
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+    };
+
+    fn faces(mesh: &CornerTableF) -> Vec<[Vec3f; 3]> {
+        mesh.faces()
+            .map(|face| {
+                let (v1, v2, v3) = mesh.face_vertices(&face);
+                [v1, v2, v3].map(|v| *mesh.vertex_position(&v))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_mesh_diff() {
+        let a: CornerTableF = primitives::plane(1.0, 1.0, 8, 8);
+        assert!(mesh_diff(&a, &a).is_empty());
+
+        // Move one vertex and split one face
+        let mut positions: Vec<_> = a.vertices().map(|v| *a.vertex_position(&v)).collect();
+        let vertex_index = |v| a.vertices().position(|x| x == v).unwrap();
+        let mut indices: Vec<_> = a
+            .faces()
+            .flat_map(|face| {
+                let (v1, v2, v3) = a.face_vertices(&face);
+                [vertex_index(v1), vertex_index(v2), vertex_index(v3)]
+            })
+            .collect();
+
+        positions[40].z = 0.1;
+        let split: Vec<_> = indices.drain(0..3).collect();
+        let center = (positions[split[0]] + positions[split[1]] + positions[split[2]]) / 3.0;
+        positions.push(center);
+        let c = positions.len() - 1;
+        indices.extend_from_slice(&[split[0], split[1], c, split[1], split[2], c, split[2], split[0], c]);
+
+        let b = CornerTableF::from_vertices_and_indices(&positions, &indices);
+        let patch = mesh_diff(&a, &b);
+
+        assert_eq!(patch.added_vertices.len(), 2);
+        assert!(patch.added_faces.len() <= 3 + 8);
+        assert_eq!(patch.face_remap.iter().count() + patch.added_faces.len(), b.faces().count());
+
+        let restored = apply_patch(&a, &patch).unwrap();
+        assert_eq!(faces(&restored), faces(&b));
+        assert!(apply_patch(&b, &patch).is_none());
+    }
+}
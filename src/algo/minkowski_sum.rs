@@ -0,0 +1,216 @@
+use crate::{
+    algo::{convex_hull::convex_hull, merge_points::merge_points},
+    geometry::primitives::box3::Box3,
+    helpers::aliases::{Vec3f, Vec3i},
+    mesh::traits::Mesh,
+    spatial_partitioning::aabb_tree::winding_numbers::WindingNumbers,
+    voxel::prelude::{MarchingCubesMesher, MeshToVolume, Mesher, Volume},
+};
+
+/// Width (in voxels) of band around swept faces where distances are evaluated
+const SWEEP_BAND: isize = 2;
+
+/// Convex shape added to mesh by [minkowski_sum]
+#[derive(Debug, Clone)]
+pub enum ConvexShape {
+    /// Ball of given radius centered at origin, sum is mesh with rounded edges and corners
+    Sphere(f32),
+    /// Convex hull of given points, e.g. tolerance zone of part placement
+    Polyhedron(Vec<Vec3f>),
+}
+
+///
+/// Computes Minkowski sum of solid bounded by closed mesh and convex shape: union of copies of shape
+/// translated to every point of solid. Sum with sphere is level set of mesh distance field at its radius (rounding),
+/// sum with polyhedron is computed by sweeping polyhedron along mesh faces: every face is replaced by
+/// convex hull of face translated by vertices of polyhedron, distances to these hulls are evaluated by
+/// their support planes on voxel grid.
+///
+/// Result is extracted by marching cubes, so features smaller than `voxel_size` are lost.
+/// Returns `None` when mesh is empty, polyhedron is flat or result has no surface.
+///
+/// ## Example
+/// ```ignore
+/// // Space swept by part shifted by up to 0.1 along every axis
+/// let tolerance = ConvexShape::Polyhedron(vec![Vec3f::repeat(-0.1), Vec3f::repeat(0.1), ...]);
+/// let swept: CornerTableF = minkowski_sum(&part, &tolerance, 0.02)?;
+/// ```
+///
+pub fn minkowski_sum<TMesh: Mesh<ScalarType = f32>>(mesh: &TMesh, convex: &ConvexShape, voxel_size: f32) -> Option<TMesh> {
+    match convex {
+        ConvexShape::Sphere(radius) => sphere_sum(mesh, *radius, voxel_size),
+        ConvexShape::Polyhedron(points) => polyhedron_sum(mesh, points, voxel_size),
+    }
+}
+
+/// Rounds mesh by extracting level set of its distance field at `radius`
+fn sphere_sum<TMesh: Mesh<ScalarType = f32>>(mesh: &TMesh, radius: f32, voxel_size: f32) -> Option<TMesh> {
+    // Narrow band should contain offset surface
+    let band_width = (radius.max(0.0) / voxel_size).ceil() as isize + 1;
+    let volume = MeshToVolume::default()
+        .with_voxel_size(voxel_size)
+        .with_narrow_band_width(band_width)
+        .convert(mesh)?;
+
+    let faces = MarchingCubesMesher::default()
+        .with_voxel_size(voxel_size)
+        .with_iso_value(radius)
+        .mesh_triangles(&volume);
+
+    mesh_from_faces(faces)
+}
+
+fn polyhedron_sum<TMesh: Mesh<ScalarType = f32>>(mesh: &TMesh, points: &[Vec3f], voxel_size: f32) -> Option<TMesh> {
+    let polyhedron = convex_hull(points)?;
+
+    let mut bbox = Box3::empty();
+    for vertex in mesh.vertices() {
+        bbox.union_point(mesh.vertex_position(&vertex));
+    }
+
+    if !bbox.is_valid() {
+        return None;
+    }
+
+    let mut polyhedron_bbox = Box3::empty();
+    for point in &polyhedron.points {
+        polyhedron_bbox.union_point(point);
+    }
+
+    // Grid covers sum with margin, so surface is not cut by grid bounds
+    let margin = Vec3f::repeat(SWEEP_BAND as f32 * voxel_size);
+    let grid_min = ((bbox.get_min() + polyhedron_bbox.get_min() - margin) / voxel_size).map(|x| x.floor() as isize);
+    let grid_max = ((bbox.get_max() + polyhedron_bbox.get_max() + margin) / voxel_size).map(|x| x.ceil() as isize);
+    let dimensions = (grid_max - grid_min).add_scalar(1);
+    let linear = |index: &Vec3i| {
+        let local = index - grid_min;
+        ((local.x * dimensions.y + local.y) * dimensions.z + local.z) as usize
+    };
+
+    let mut distances = vec![f32::MAX; (dimensions.x * dimensions.y * dimensions.z) as usize];
+
+    for face in mesh.faces() {
+        let triangle = mesh.face_positions(&face);
+        let swept: Vec<_> = [triangle.p1(), triangle.p2(), triangle.p3()]
+            .iter()
+            .flat_map(|vertex| polyhedron.points.iter().map(move |point| *vertex + point))
+            .collect();
+
+        let Some(hull) = convex_hull(&swept) else {
+            continue;
+        };
+
+        let planes: Vec<_> = hull
+            .indices
+            .chunks_exact(3)
+            .map(|face| {
+                let [a, b, c] = [face[0], face[1], face[2]].map(|vertex| hull.points[vertex]);
+                let normal = (b - a).cross(&(c - a)).normalize();
+                (normal, normal.dot(&a))
+            })
+            .collect();
+
+        let mut hull_bbox = Box3::empty();
+        for point in &hull.points {
+            hull_bbox.union_point(point);
+        }
+
+        let min = (hull_bbox.get_min() / voxel_size).map(|x| x.floor() as isize).add_scalar(-SWEEP_BAND).sup(&grid_min);
+        let max = (hull_bbox.get_max() / voxel_size).map(|x| x.ceil() as isize).add_scalar(SWEEP_BAND).inf(&grid_max);
+
+        for x in min.x..=max.x {
+            for y in min.y..=max.y {
+                for z in min.z..=max.z {
+                    let index = Vec3i::new(x, y, z);
+                    let point = index.cast() * voxel_size;
+
+                    // Signed distance to support planes, exact inside of hull and lower bound outside
+                    let distance = planes
+                        .iter()
+                        .fold(f32::MIN, |max, (normal, offset)| max.max(normal.dot(&point) - offset));
+
+                    let value = &mut distances[linear(&index)];
+                    *value = value.min(distance);
+                }
+            }
+        }
+    }
+
+    // Interior of solid translated by any point of polyhedron is inside sum, but far from swept faces
+    let winding_numbers = WindingNumbers::from_mesh(mesh);
+    let shift = polyhedron.points[0];
+
+    for x in grid_min.x..=grid_max.x {
+        for y in grid_min.y..=grid_max.y {
+            for z in grid_min.z..=grid_max.z {
+                let index = Vec3i::new(x, y, z);
+                let value = &mut distances[linear(&index)];
+
+                if *value > 0.0 && winding_numbers.approximate(&(index.cast() * voxel_size - shift), 2.0) > 0.5 {
+                    *value = -voxel_size;
+                }
+            }
+        }
+    }
+
+    let volume = Volume::from_fn(
+        voxel_size,
+        grid_min.cast() * voxel_size,
+        grid_max.cast() * voxel_size,
+        SWEEP_BAND as usize,
+        |point| {
+            let index = (point / voxel_size).map(|x| x.round() as isize);
+            distances[linear(&index)]
+        },
+    );
+
+    let faces = MarchingCubesMesher::default().with_voxel_size(voxel_size).mesh_triangles(&volume);
+
+    mesh_from_faces(faces)
+}
+
+fn mesh_from_faces<TMesh: Mesh<ScalarType = f32>>(faces: Vec<Vec3f>) -> Option<TMesh> {
+    if faces.is_empty() {
+        return None;
+    }
+
+    let indexed_faces = merge_points(&faces);
+    Some(TMesh::from_vertices_and_indices(&indexed_faces.points, &indexed_faces.indices))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{minkowski_sum, ConvexShape};
+    use crate::{
+        algo::mass_properties::mass_properties,
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::{Mesh, TopologicalMesh}},
+    };
+
+    #[test]
+    fn test_minkowski_sum() {
+        let cube: CornerTableF = primitives::cuboid(Vec3f::new(1.0, 1.0, 1.0), 1);
+
+        // Cube swept by smaller cube is larger cube
+        let corners = (0..8)
+            .map(|i| Vec3f::new((i & 1) as f32, ((i >> 1) & 1) as f32, ((i >> 2) & 1) as f32) * 0.5 - Vec3f::repeat(0.25))
+            .collect();
+        let sum: CornerTableF = minkowski_sum(&cube, &ConvexShape::Polyhedron(corners), 0.05).unwrap();
+        assert!((mass_properties(&sum, 1.0).volume - 1.5f32.powi(3)).abs() < 0.05);
+        assert!(sum.edges().all(|edge| !sum.is_edge_on_boundary(&edge)));
+
+        for vertex in sum.vertices() {
+            assert!(sum.vertex_position(&vertex).abs().max() < 0.75 + 0.01);
+        }
+
+        // Sum with sphere rounds edges and corners
+        let radius = 0.3;
+        let sum: CornerTableF = minkowski_sum(&cube, &ConvexShape::Sphere(radius), 0.1).unwrap();
+        let expected = 1.0 + 6.0 * radius + 3.0 * std::f32::consts::PI * radius * radius + 4.0 / 3.0 * std::f32::consts::PI * radius.powi(3);
+        assert!((mass_properties(&sum, 1.0).volume - expected).abs() / expected < 0.03);
+
+        // Flat polyhedron has no volume
+        let flat = vec![Vec3f::zeros(), Vec3f::x(), Vec3f::y()];
+        assert!(minkowski_sum(&cube, &ConvexShape::Polyhedron(flat), 0.05).is_none());
+    }
+}
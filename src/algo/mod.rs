@@ -3,3 +3,25 @@ pub mod float_hash;
 pub mod utils;
 pub mod edge_collapse;
 pub mod vertex_shift;
+pub mod remove_slivers;
+pub mod orient_faces;
+pub mod subdivision;
+pub mod smoothing;
+pub mod cluster_decimate;
+pub mod mass_properties;
+pub mod exact_boolean;
+pub mod feature_lines;
+pub mod optimize_vertex_cache;
+pub mod corner_normals;
+pub mod sampling;
+pub mod mesh_diff;
+pub mod ambient_occlusion;
+pub mod watertight;
+pub mod manifold;
+pub mod repair;
+pub mod convex_hull;
+pub mod convex_decomposition;
+pub mod geometry_image;
+pub mod normalize_scale;
+#[cfg(feature = "voxel")]
+pub mod minkowski_sum;
@@ -0,0 +1,197 @@
+use nalgebra::Matrix4;
+use num_traits::{cast, One, Zero};
+
+use crate::{
+    geometry::{primitives::box3::Box3, traits::RealNumber},
+    helpers::aliases::Vec3,
+    mesh::traits::EditableMesh,
+};
+
+///
+/// Uniform scaling followed by translation applied by [normalize_scale]: `normalized = original * scale + translation`.
+/// Keep it to map results computed on normalized mesh (meshes, points, lengths like voxel size) back to original coordinates.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NormalizationTransform<TScalar: RealNumber> {
+    scale: TScalar,
+    translation: Vec3<TScalar>,
+}
+
+impl<TScalar: RealNumber> NormalizationTransform<TScalar> {
+    #[inline]
+    pub fn identity() -> Self {
+        Self {
+            scale: TScalar::one(),
+            translation: Vec3::zeros(),
+        }
+    }
+
+    #[inline]
+    pub fn scale(&self) -> TScalar {
+        self.scale
+    }
+
+    #[inline]
+    pub fn translation(&self) -> &Vec3<TScalar> {
+        &self.translation
+    }
+
+    /// Maps point from original to normalized coordinates
+    #[inline]
+    pub fn apply(&self, point: &Vec3<TScalar>) -> Vec3<TScalar> {
+        point * self.scale + self.translation
+    }
+
+    /// Maps point from normalized to original coordinates
+    #[inline]
+    pub fn inverse(&self, point: &Vec3<TScalar>) -> Vec3<TScalar> {
+        (point - self.translation) / self.scale
+    }
+
+    /// Maps length (e.g. voxel size or offset distance) from original to normalized coordinates
+    #[inline]
+    pub fn apply_length(&self, length: TScalar) -> TScalar {
+        length * self.scale
+    }
+
+    /// Maps length from normalized to original coordinates
+    #[inline]
+    pub fn inverse_length(&self, length: TScalar) -> TScalar {
+        length / self.scale
+    }
+
+    /// Moves vertices of mesh from original to normalized coordinates
+    pub fn apply_to_mesh<TMesh: EditableMesh<ScalarType = TScalar>>(&self, mesh: &mut TMesh) {
+        transform_vertices(mesh, |point| self.apply(point));
+    }
+
+    /// Moves vertices of mesh (e.g. result of processing of normalized mesh) back to original coordinates
+    pub fn inverse_mesh<TMesh: EditableMesh<ScalarType = TScalar>>(&self, mesh: &mut TMesh) {
+        transform_vertices(mesh, |point| self.inverse(point));
+    }
+
+    /// Returns homogeneous matrix of transform
+    pub fn to_matrix(&self) -> Matrix4<TScalar> {
+        Matrix4::new_scaling(self.scale).append_translation(&self.translation)
+    }
+}
+
+///
+/// Uniformly scales and translates mesh in place, so it is centered in unit cube `[0, 1]^3` and its longest side has unit length.
+/// Algorithms with absolute tolerances (e.g. voxel pipeline) lose precision on very large or very small coordinates,
+/// run them on normalized mesh and map results back with returned transform.
+///
+/// ## Example
+/// ```ignore
+/// let transform = normalize_scale(&mut mesh);
+/// let mut remeshed = VoxelRemesher::default()
+///     .with_voxel_size(transform.apply_length(voxel_size))
+///     .remesh(&mesh)?;
+/// transform.inverse_mesh(&mut remeshed);
+/// ```
+///
+pub fn normalize_scale<TMesh: EditableMesh>(mesh: &mut TMesh) -> NormalizationTransform<TMesh::ScalarType> {
+    let unit = Box3::new(Vec3::zeros(), Vec3::repeat(TMesh::ScalarType::one()));
+    normalize_scale_to(mesh, &unit)
+}
+
+///
+/// Uniformly scales and translates mesh in place, so it is centered in `target` box and fits it as tightly as possible.
+/// Mesh without extent (single point) is only translated to center of `target`, empty mesh gets identity transform.
+///
+pub fn normalize_scale_to<TMesh: EditableMesh>(mesh: &mut TMesh, target: &Box3<TMesh::ScalarType>) -> NormalizationTransform<TMesh::ScalarType> {
+    let mut bbox = Box3::empty();
+    for vertex in mesh.vertices() {
+        bbox.union_point(mesh.vertex_position(&vertex));
+    }
+
+    if !bbox.is_valid() {
+        return NormalizationTransform::identity();
+    }
+
+    let size = bbox.get_max() - bbox.get_min();
+    let target_size = target.get_max() - target.get_min();
+
+    // Largest scale keeping every side of mesh inside target
+    let scale = size
+        .iter()
+        .zip(target_size.iter())
+        .filter(|(size, _)| !size.is_zero())
+        .map(|(size, target_size)| *target_size / *size)
+        .reduce(|min, scale| if scale < min { scale } else { min })
+        .unwrap_or(TMesh::ScalarType::one());
+
+    let half: TMesh::ScalarType = cast(0.5).unwrap();
+    let center = (bbox.get_min() + bbox.get_max()) * half;
+    let transform = NormalizationTransform {
+        scale,
+        translation: target.get_center() - center * scale,
+    };
+
+    transform.apply_to_mesh(mesh);
+
+    transform
+}
+
+fn transform_vertices<TMesh: EditableMesh>(mesh: &mut TMesh, transform: impl Fn(&Vec3<TMesh::ScalarType>) -> Vec3<TMesh::ScalarType>) {
+    let vertices: Vec<_> = mesh.vertices().collect();
+
+    for vertex in vertices {
+        let position = transform(mesh.vertex_position(&vertex));
+        mesh.shift_vertex(&vertex, &position);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{normalize_scale, normalize_scale_to};
+    use crate::{
+        geometry::primitives::box3::Box3,
+        helpers::aliases::Vec3,
+        mesh::{corner_table::prelude::CornerTableD, primitives, traits::{EditableMesh, Mesh}},
+    };
+
+    fn bbox(mesh: &CornerTableD) -> Box3<f64> {
+        let mut bbox = Box3::empty();
+        for vertex in mesh.vertices() {
+            bbox.union_point(mesh.vertex_position(&vertex));
+        }
+
+        bbox
+    }
+
+    #[test]
+    fn test_normalize_scale() {
+        let mut mesh: CornerTableD = primitives::cuboid(Vec3::new(4e6, 2e6, 1e6), 2);
+        let vertices: Vec<_> = mesh.vertices().collect();
+        for vertex in vertices {
+            let position = *mesh.vertex_position(&vertex);
+            mesh.shift_vertex(&vertex, &(position + Vec3::new(1e7, 0.0, -3e6)));
+        }
+
+        let original: Vec<_> = mesh.vertices().map(|vertex| *mesh.vertex_position(&vertex)).collect();
+
+        let transform = normalize_scale(&mut mesh);
+        let normalized = bbox(&mesh);
+        assert!((normalized.get_min() - Vec3::new(0.0, 0.25, 0.375)).norm() < 1e-12);
+        assert!((normalized.get_max() - Vec3::new(1.0, 0.75, 0.625)).norm() < 1e-12);
+        assert!((transform.apply_length(4e6) - 1.0).abs() < 1e-12);
+        assert!((transform.inverse_length(1.0) - 4e6).abs() < 1e-6);
+
+        let matrix = transform.to_matrix();
+        let point = Vec3::new(1e7, 0.0, -3e6);
+        assert!((matrix.transform_point(&point.into()).coords - transform.apply(&point)).norm() < 1e-12);
+
+        transform.inverse_mesh(&mut mesh);
+        for (vertex, expected) in mesh.vertices().zip(&original) {
+            assert!((mesh.vertex_position(&vertex) - expected).norm() < 1e-6);
+        }
+
+        // Flat mesh fits target by its non-zero sides
+        let mut plane: CornerTableD = primitives::plane(2.0, 1.0, 2, 2);
+        let target = Box3::new(Vec3::new(-1.0, -1.0, -1.0), Vec3::new(1.0, 3.0, 1.0));
+        let transform = normalize_scale_to(&mut plane, &target);
+        assert_eq!(transform.scale(), 1.0);
+        assert!((bbox(&plane).get_center() - target.get_center()).norm() < 1e-12);
+    }
+}
@@ -0,0 +1,303 @@
+use std::collections::HashMap;
+
+use crate::mesh::{
+    remap::{FaceRemap, Remap, VertexRemap},
+    traits::Mesh,
+};
+
+/// Size of simulated LRU cache used for scoring
+const CACHE_SIZE: usize = 32;
+const CACHE_DECAY_POWER: f32 = 1.5;
+/// Score of vertices of last emitted face, lower than for next cache entries to avoid emitting strips
+const LAST_FACE_SCORE: f32 = 0.75;
+const VALENCE_BOOST_SCALE: f32 = 2.0;
+const VALENCE_BOOST_POWER: f32 = 0.5;
+
+///
+/// Index buffer reordered for vertex cache efficiency
+///
+#[derive(Debug, Clone)]
+pub struct VertexCacheOrder {
+    /// New index buffer, 3 indices per face
+    pub indices: Vec<usize>,
+    /// Maps old vertex indices to new ones
+    pub vertex_remap: VertexRemap,
+    /// Maps old face indices to new ones
+    pub face_remap: FaceRemap,
+}
+
+///
+/// Reorders faces of index buffer so vertices are reused while they are still in post-transform cache of GPU,
+/// then renumbers vertices in order of first use, which improves locality of vertex fetches.
+/// Uses "Linear-Speed Vertex Cache Optimisation" by Tom Forsyth: faces are greedily emitted by score of their vertices,
+/// which is high for vertices recently put to simulated cache and for vertices with few remaining faces.
+///
+/// Vertices not referenced by any face are moved to the end.
+/// Use [Remap::apply] of returned remaps to reorder vertex attributes.
+///
+/// ## Example
+/// ```ignore
+/// let order = optimize_vertex_cache(&indices, positions.len());
+/// let positions = order.vertex_remap.apply(&positions);
+/// let normals = order.vertex_remap.apply(&normals);
+/// upload(&positions, &normals, &order.indices);
+/// ```
+///
+pub fn optimize_vertex_cache(indices: &[usize], vertices_count: usize) -> VertexCacheOrder {
+    assert!(indices.len().is_multiple_of(3), "Invalid number of face indices: {}", indices.len());
+
+    let faces_count = indices.len() / 3;
+
+    // Faces adjacent to vertices in compressed form
+    let mut offsets = vec![0; vertices_count + 1];
+    for &vertex in indices {
+        offsets[vertex + 1] += 1;
+    }
+    for vertex in 0..vertices_count {
+        offsets[vertex + 1] += offsets[vertex];
+    }
+
+    let mut adjacent_faces = vec![0; indices.len()];
+    let mut fill = offsets.clone();
+    for (corner, &vertex) in indices.iter().enumerate() {
+        adjacent_faces[fill[vertex]] = corner / 3;
+        fill[vertex] += 1;
+    }
+
+    let mut remaining_valence: Vec<_> = (0..vertices_count).map(|v| offsets[v + 1] - offsets[v]).collect();
+    let mut cache_position = vec![None; vertices_count];
+    let mut vertex_score: Vec<_> = (0..vertices_count)
+        .map(|v| score(None, remaining_valence[v]))
+        .collect();
+
+    let mut is_emitted = vec![false; faces_count];
+    let mut face_score: Vec<_> = indices
+        .chunks(3)
+        .map(|face| face.iter().map(|&v| vertex_score[v]).sum::<f32>())
+        .collect();
+
+    let mut face_order = Vec::with_capacity(faces_count);
+    let mut cache: Vec<usize> = Vec::with_capacity(CACHE_SIZE + 3);
+    let mut next_face = 0;
+    let mut best_face = None;
+
+    while face_order.len() < faces_count {
+        // Dead end, restart from next face not emitted yet
+        let face = match best_face {
+            Some(face) => face,
+            None => {
+                while is_emitted[next_face] {
+                    next_face += 1;
+                }
+
+                next_face
+            }
+        };
+
+        is_emitted[face] = true;
+        face_order.push(face);
+
+        let face_vertices = &indices[face * 3..face * 3 + 3];
+
+        // Emitted face is no longer adjacent to its vertices
+        for &vertex in face_vertices {
+            let begin = offsets[vertex];
+            let end = begin + remaining_valence[vertex];
+            let position = adjacent_faces[begin..end].iter().position(|f| *f == face).unwrap();
+            adjacent_faces.swap(begin + position, end - 1);
+            remaining_valence[vertex] -= 1;
+        }
+
+        // Vertices of emitted face go to the front of LRU cache
+        let mut new_cache: Vec<usize> = Vec::with_capacity(CACHE_SIZE + 3);
+        for &vertex in face_vertices.iter().chain(&cache) {
+            if !new_cache.contains(&vertex) {
+                new_cache.push(vertex);
+            }
+        }
+
+        for &evicted in new_cache.iter().skip(CACHE_SIZE) {
+            cache_position[evicted] = None;
+        }
+
+        for (position, &vertex) in new_cache.iter().enumerate() {
+            cache_position[vertex] = (position < CACHE_SIZE).then_some(position);
+        }
+
+        // Rescore vertices whose cache position or valence changed, best face is searched among faces adjacent to cache
+        best_face = None;
+        let mut best_score = f32::MIN;
+
+        for &vertex in &new_cache {
+            let new_score = score(cache_position[vertex], remaining_valence[vertex]);
+            let delta = new_score - vertex_score[vertex];
+            vertex_score[vertex] = new_score;
+
+            let begin = offsets[vertex];
+            for &adjacent in &adjacent_faces[begin..begin + remaining_valence[vertex]] {
+                face_score[adjacent] += delta;
+            }
+        }
+
+        for &vertex in new_cache.iter().take(CACHE_SIZE) {
+            let begin = offsets[vertex];
+            for &adjacent in &adjacent_faces[begin..begin + remaining_valence[vertex]] {
+                if face_score[adjacent] > best_score {
+                    best_score = face_score[adjacent];
+                    best_face = Some(adjacent);
+                }
+            }
+        }
+
+        new_cache.truncate(CACHE_SIZE);
+        cache = new_cache;
+    }
+
+    // Vertices are numbered in order of first use
+    let mut vertex_map = vec![None; vertices_count];
+    let mut new_vertices_count = 0;
+    let mut new_indices = Vec::with_capacity(indices.len());
+
+    for &face in &face_order {
+        for &vertex in &indices[face * 3..face * 3 + 3] {
+            let new_vertex = *vertex_map[vertex].get_or_insert_with(|| {
+                new_vertices_count += 1;
+                new_vertices_count - 1
+            });
+            new_indices.push(new_vertex);
+        }
+    }
+
+    for new_vertex in vertex_map.iter_mut().filter(|v| v.is_none()) {
+        *new_vertex = Some(new_vertices_count);
+        new_vertices_count += 1;
+    }
+
+    let mut face_map = vec![None; faces_count];
+    for (new_face, &face) in face_order.iter().enumerate() {
+        face_map[face] = Some(new_face);
+    }
+
+    VertexCacheOrder {
+        indices: new_indices,
+        vertex_remap: Remap::new(vertex_map),
+        face_remap: Remap::new(face_map),
+    }
+}
+
+///
+/// Rebuilds mesh with faces and vertices reordered by [optimize_vertex_cache].
+/// Old indices in returned remaps are positions of elements in [Mesh::vertices] and [Mesh::faces] iterators.
+///
+pub fn optimize_mesh_vertex_cache<TMesh: Mesh>(mesh: &TMesh) -> (TMesh, VertexRemap, FaceRemap) {
+    let vertex_index: HashMap<_, _> = mesh.vertices().enumerate().map(|(i, v)| (v, i)).collect();
+    let positions: Vec<_> = mesh.vertices().map(|v| *mesh.vertex_position(&v)).collect();
+    let indices: Vec<_> = mesh
+        .faces()
+        .flat_map(|face| {
+            let (v1, v2, v3) = mesh.face_vertices(&face);
+            [vertex_index[&v1], vertex_index[&v2], vertex_index[&v3]]
+        })
+        .collect();
+
+    let order = optimize_vertex_cache(&indices, positions.len());
+    let positions = order.vertex_remap.apply(&positions);
+
+    (
+        TMesh::from_vertices_and_indices(&positions, &order.indices),
+        order.vertex_remap,
+        order.face_remap,
+    )
+}
+
+///
+/// Average cache miss ratio: number of vertex shader invocations per face when index buffer is rendered
+/// with FIFO post-transform cache of given size. Lies between 0.5 (best case for large meshes) and 3.
+///
+pub fn average_cache_miss_ratio(indices: &[usize], cache_size: usize) -> f64 {
+    if indices.is_empty() {
+        return 0.0;
+    }
+
+    let mut cache = std::collections::VecDeque::with_capacity(cache_size);
+    let mut misses = 0;
+
+    for vertex in indices {
+        if cache.contains(vertex) {
+            continue;
+        }
+
+        misses += 1;
+        if cache.len() == cache_size {
+            cache.pop_front();
+        }
+        cache.push_back(*vertex);
+    }
+
+    misses as f64 / (indices.len() / 3) as f64
+}
+
+/// Forsyth's score of vertex
+fn score(cache_position: Option<usize>, remaining_valence: usize) -> f32 {
+    if remaining_valence == 0 {
+        return -1.0;
+    }
+
+    let cache_score = match cache_position {
+        Some(position) if position < 3 => LAST_FACE_SCORE,
+        Some(position) => {
+            let scaler = 1.0 / (CACHE_SIZE - 3) as f32;
+            (1.0 - (position - 3) as f32 * scaler).powf(CACHE_DECAY_POWER)
+        }
+        None => 0.0,
+    };
+
+    cache_score + VALENCE_BOOST_SCALE * (remaining_valence as f32).powf(-VALENCE_BOOST_POWER)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{average_cache_miss_ratio, optimize_mesh_vertex_cache, optimize_vertex_cache};
+    use crate::{
+        helpers::random::Rng,
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+    };
+
+    #[test]
+    fn test_optimize_vertex_cache() {
+        let grid: CornerTableF = primitives::plane(1.0, 1.0, 64, 64);
+        let (_, vertex_remap, _) = optimize_mesh_vertex_cache(&grid);
+        assert_eq!(vertex_remap.new_len(), grid.vertices().count());
+
+        // Shuffled faces of grid
+        let vertices: Vec<_> = grid.vertices().collect();
+        let mut faces: Vec<_> = grid
+            .faces()
+            .map(|face| {
+                let (v1, v2, v3) = grid.face_vertices(&face);
+                [v1, v2, v3].map(|v| vertices.iter().position(|x| *x == v).unwrap())
+            })
+            .collect();
+        Rng::new(7).shuffle(&mut faces);
+
+        let mut indices: Vec<_> = faces.concat();
+        // Unreferenced vertex
+        let vertices_count = vertices.len() + 1;
+        indices.extend_from_slice(&[0, 1, 2]);
+
+        let order = optimize_vertex_cache(&indices, vertices_count);
+
+        let before = average_cache_miss_ratio(&indices, 16);
+        let after = average_cache_miss_ratio(&order.indices, 16);
+        assert!(before > 2.0);
+        assert!(after < 0.8, "ACMR after optimization: {}", after);
+
+        assert_eq!(order.vertex_remap.get(vertices_count - 1), Some(vertices_count - 1));
+        for (old_face, new_face) in order.face_remap.iter() {
+            for k in 0..3 {
+                let old_vertex = indices[old_face * 3 + k];
+                assert_eq!(order.vertex_remap.get(old_vertex), Some(order.indices[new_face * 3 + k]));
+            }
+        }
+    }
+}
@@ -0,0 +1,177 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+
+use crate::{algo::utils::triple_product, geometry::traits::RealNumber, helpers::aliases::Vec3};
+
+///
+/// Makes winding of faces coherent. Face adjacency graph is built from shared edges and
+/// orientation of first face in each connected component is propagated to its neighbors.
+/// Orientation is propagated only across manifold edges (edges shared by exactly two faces).
+/// When `positive_volume` is `true` components with negative signed volume are flipped,
+/// so normals of closed components point outwards.
+///
+/// `indices` is a flat list of triangle vertex indices, it is modified in place.
+/// Returns number of flipped faces.
+///
+/// ## Example
+/// ```ignore
+/// let mut indexed = merge_points(&soup);
+/// orient_faces(&indexed.points, &mut indexed.indices, true);
+/// let mesh = CornerTableF::from_vertices_and_indices(&indexed.points, &indexed.indices);
+/// ```
+///
+pub fn orient_faces<TScalar: RealNumber>(vertices: &[Vec3<TScalar>], indices: &mut [usize], positive_volume: bool) -> usize {
+    let faces_count = indices.len() / 3;
+    let edge_faces = edge_faces_map(indices);
+
+    let mut flipped = vec![false; faces_count];
+    let mut visited = vec![false; faces_count];
+    let mut queue = VecDeque::new();
+
+    for seed in 0..faces_count {
+        if visited[seed] {
+            continue;
+        }
+
+        visited[seed] = true;
+        queue.push_back(seed);
+        let mut component = Vec::new();
+
+        while let Some(face) = queue.pop_front() {
+            component.push(face);
+
+            let edges: Vec<_> = face_edges(indices, face).collect();
+
+            for (v1, v2) in edges {
+                let neighbors = &edge_faces[&undirected(v1, v2)];
+
+                if neighbors.len() != 2 {
+                    continue;
+                }
+
+                let neighbor = if neighbors[0] == face { neighbors[1] } else { neighbors[0] };
+
+                if visited[neighbor] {
+                    continue;
+                }
+
+                // Coherent neighbor traverses shared edge in opposite direction
+                if face_edges(indices, neighbor).any(|edge| edge == (v1, v2)) {
+                    flip_face(indices, neighbor);
+                    flipped[neighbor] = !flipped[neighbor];
+                }
+
+                visited[neighbor] = true;
+                queue.push_back(neighbor);
+            }
+        }
+
+        if positive_volume && signed_volume(vertices, indices, &component) < TScalar::zero() {
+            for face in component {
+                flip_face(indices, face);
+                flipped[face] = !flipped[face];
+            }
+        }
+    }
+
+    flipped.into_iter().filter(|f| *f).count()
+}
+
+///
+/// Returns `true` when winding of faces is coherent, i.e. each directed edge is used by at most one face.
+///
+pub fn is_coherently_oriented(indices: &[usize]) -> bool {
+    let mut directed_edges = HashSet::with_capacity(indices.len());
+    (0..indices.len() / 3).all(|face| face_edges(indices, face).all(|edge| directed_edges.insert(edge)))
+}
+
+fn edge_faces_map(indices: &[usize]) -> HashMap<(usize, usize), Vec<usize>> {
+    let mut edge_faces: HashMap<_, Vec<_>> = HashMap::with_capacity(indices.len());
+
+    for face in 0..indices.len() / 3 {
+        for (v1, v2) in face_edges(indices, face) {
+            edge_faces.entry(undirected(v1, v2)).or_default().push(face);
+        }
+    }
+
+    edge_faces
+}
+
+/// Sum of signed volumes of tetrahedrons formed by faces and origin
+fn signed_volume<TScalar: RealNumber>(vertices: &[Vec3<TScalar>], indices: &[usize], faces: &[usize]) -> TScalar {
+    faces.iter().fold(TScalar::zero(), |volume, face| {
+        let v1 = &vertices[indices[face * 3]];
+        let v2 = &vertices[indices[face * 3 + 1]];
+        let v3 = &vertices[indices[face * 3 + 2]];
+
+        volume + triple_product(v1, v2, v3)
+    })
+}
+
+#[inline]
+fn face_edges(indices: &[usize], face: usize) -> impl Iterator<Item = (usize, usize)> + '_ {
+    let first = face * 3;
+    (0..3).map(move |i| (indices[first + i], indices[first + (i + 1) % 3]))
+}
+
+#[inline]
+fn flip_face(indices: &mut [usize], face: usize) {
+    indices.swap(face * 3 + 1, face * 3 + 2);
+}
+
+#[inline]
+fn undirected(v1: usize, v2: usize) -> (usize, usize) {
+    (v1.min(v2), v1.max(v2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_coherently_oriented, orient_faces};
+    use crate::helpers::aliases::Vec3f;
+
+    fn tetrahedron() -> (Vec<Vec3f>, Vec<usize>) {
+        let vertices = vec![
+            Vec3f::new(0.0, 0.0, 0.0),
+            Vec3f::new(1.0, 0.0, 0.0),
+            Vec3f::new(0.0, 1.0, 0.0),
+            Vec3f::new(0.0, 0.0, 1.0),
+        ];
+        let indices = vec![
+            0, 2, 1,
+            0, 1, 3,
+            1, 2, 3,
+            0, 3, 2,
+        ];
+
+        (vertices, indices)
+    }
+
+    #[test]
+    fn test_orient_faces() {
+        let (vertices, expected) = tetrahedron();
+        assert!(is_coherently_oriented(&expected));
+
+        let mut indices = expected.clone();
+        indices.swap(4, 5);
+        indices.swap(10, 11);
+        assert!(!is_coherently_oriented(&indices));
+
+        assert_eq!(orient_faces(&vertices, &mut indices, true), 2);
+        assert!(is_coherently_oriented(&indices));
+        assert_eq!(indices, expected);
+    }
+
+    #[test]
+    fn test_orient_faces_inverted_component() {
+        let (vertices, expected) = tetrahedron();
+        let mut indices = expected.clone();
+
+        for face in indices.chunks_exact_mut(3) {
+            face.swap(1, 2);
+        }
+
+        // Inverted mesh is coherent, so it is flipped only when positive volume is requested
+        assert_eq!(orient_faces(&vertices, &mut indices, false), 0);
+        assert_eq!(orient_faces(&vertices, &mut indices, true), 4);
+        assert_eq!(indices, expected);
+    }
+}
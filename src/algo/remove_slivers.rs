@@ -0,0 +1,252 @@
+use num_traits::{cast, Float};
+
+use crate::{
+    algo::edge_collapse,
+    geometry::primitives::triangle3::Triangle3,
+    mesh::traits::{EditableMesh, Position, TopologicalMesh},
+};
+
+/// Max number of passes over mesh edges
+const MAX_PASSES: usize = 10;
+
+///
+/// Targeted cleanup of degenerate and sliver triangles without global remeshing.
+/// Triangle is considered to be sliver when its [quality](Triangle3::quality) is below `min_quality`
+/// or its area is below `min_area`. Slivers are flipped along their longest edge when it improves quality,
+/// needles (triangles with one very short edge) are collapsed along their shortest edge.
+/// Operations that would change mesh boundary, flip normals or produce non-manifold edges are skipped,
+/// so the overall shape of mesh is preserved.
+///
+/// Returns number of performed collapses and flips.
+///
+/// ## Example
+/// ```ignore
+/// let mut mesh = CornerTableF::from_vertices_and_indices(&vertices, &faces);
+/// remove_slivers(&mut mesh, 0.1, 0.0);
+/// ```
+///
+pub fn remove_slivers<TMesh: TopologicalMesh + EditableMesh>(
+    mesh: &mut TMesh,
+    min_quality: TMesh::ScalarType,
+    min_area: TMesh::ScalarType,
+) -> usize {
+    let mut operations = 0;
+
+    // Face is a needle when its shortest edge is less than half of the longest one (lengths are squared)
+    let needle_ratio = cast::<f64, TMesh::ScalarType>(0.25).unwrap();
+
+    for _ in 0..MAX_PASSES {
+        let edges: Vec<TMesh::EdgeDescriptor> = mesh.edges().collect();
+        let mut changed = false;
+
+        for edge in edges {
+            if !mesh.edge_exist(&edge) {
+                continue;
+            }
+
+            let (f1, f2) = mesh.edge_faces(&edge);
+            let edge_length = mesh.edge_length_squared(&edge);
+
+            for face in std::iter::once(f1).chain(f2) {
+                if !is_sliver(mesh, &face, min_quality, min_area) {
+                    continue;
+                }
+
+                let (shortest, longest) = face_edge_lengths_range(mesh, &face);
+                let is_needle = shortest < longest * needle_ratio;
+
+                let done = if edge_length >= longest {
+                    try_flip(mesh, &edge)
+                } else if edge_length <= shortest && is_needle {
+                    try_collapse(mesh, &edge)
+                } else {
+                    false
+                };
+
+                if done {
+                    operations += 1;
+                    changed = true;
+                    break;
+                }
+            }
+        }
+
+        if !changed {
+            break;
+        }
+    }
+
+    operations
+}
+
+#[inline]
+fn is_sliver<TMesh: TopologicalMesh + EditableMesh>(
+    mesh: &TMesh,
+    face: &TMesh::FaceDescriptor,
+    min_quality: TMesh::ScalarType,
+    min_area: TMesh::ScalarType,
+) -> bool {
+    let triangle = mesh.face_positions(face);
+    triangle.get_quality() < min_quality || triangle.get_area() < min_area
+}
+
+/// Returns squared lengths of shortest and longest edges of face
+fn face_edge_lengths_range<TMesh: TopologicalMesh + EditableMesh>(
+    mesh: &TMesh,
+    face: &TMesh::FaceDescriptor,
+) -> (TMesh::ScalarType, TMesh::ScalarType) {
+    let (e1, e2, e3) = mesh.face_edges(face);
+    let l1 = mesh.edge_length_squared(&e1);
+    let l2 = mesh.edge_length_squared(&e2);
+    let l3 = mesh.edge_length_squared(&e3);
+
+    (l1.min(l2).min(l3), l1.max(l2).max(l3))
+}
+
+fn try_collapse<TMesh: TopologicalMesh + EditableMesh>(
+    mesh: &mut TMesh,
+    edge: &TMesh::EdgeDescriptor,
+) -> bool {
+    if edge_collapse::will_collapse_affect_boundary(mesh, edge) {
+        return false;
+    }
+
+    let (v1, v2) = mesh.edge_positions(edge);
+    let collapse_at = (v1 + v2) * cast::<f64, TMesh::ScalarType>(0.5).unwrap();
+
+    if !edge_collapse::is_safe(mesh, edge, &collapse_at, cast(0.5).unwrap()) {
+        return false;
+    }
+
+    mesh.collapse_edge(edge, &collapse_at);
+
+    true
+}
+
+fn try_flip<TMesh: TopologicalMesh + EditableMesh>(
+    mesh: &mut TMesh,
+    edge: &TMesh::EdgeDescriptor,
+) -> bool {
+    if mesh.is_edge_on_boundary(edge) {
+        return false;
+    }
+
+    let (v0, v1, v2, v3) = {
+        let mut pos = TMesh::Position::from_edge(mesh, edge);
+
+        let v1 = pos.get_vertex();
+        let v2 = pos.next().get_vertex();
+        let v0 = pos.next().get_vertex();
+        let v3 = pos.next().opposite().get_vertex();
+
+        (v0, v1, v2, v3)
+    };
+
+    // Flipped edge already exist?
+    let mut edge_exist = false;
+    mesh.vertices_around_vertex(&v1, |v| edge_exist |= *v == v3);
+
+    if edge_exist {
+        return false;
+    }
+
+    let v0_pos = mesh.vertex_position(&v0);
+    let v1_pos = mesh.vertex_position(&v1);
+    let v2_pos = mesh.vertex_position(&v2);
+    let v3_pos = mesh.vertex_position(&v3);
+
+    let old_quality1 = Triangle3::quality(v0_pos, v1_pos, v2_pos);
+    let old_quality2 = Triangle3::quality(v0_pos, v2_pos, v3_pos);
+    let new_quality1 = Triangle3::quality(v1_pos, v2_pos, v3_pos);
+    let new_quality2 = Triangle3::quality(v0_pos, v1_pos, v3_pos);
+
+    if new_quality1.min(new_quality2) <= old_quality1.min(old_quality2) {
+        return false;
+    }
+
+    // Normal of sliver is not reliable, so new faces are compared with the better one of old faces
+    let reference_normal = if old_quality1 > old_quality2 {
+        Triangle3::normal(v0_pos, v1_pos, v2_pos)
+    } else {
+        Triangle3::normal(v0_pos, v2_pos, v3_pos)
+    };
+    let min_cos = cast::<f64, TMesh::ScalarType>(0.7).unwrap();
+
+    if reference_normal.dot(&Triangle3::normal(v1_pos, v2_pos, v3_pos)) < min_cos
+        || reference_normal.dot(&Triangle3::normal(v0_pos, v1_pos, v3_pos)) < min_cos
+    {
+        return false;
+    }
+
+    mesh.flip_edge(edge);
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::remove_slivers;
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{
+            corner_table::prelude::CornerTableF,
+            primitives,
+            traits::{EditableMesh, Mesh, TopologicalMesh},
+        },
+    };
+
+    fn min_quality(mesh: &CornerTableF) -> f32 {
+        mesh.faces()
+            .map(|face| mesh.face_positions(&face).get_quality())
+            .fold(f32::MAX, f32::min)
+    }
+
+    fn boundary_edges_count(mesh: &CornerTableF) -> usize {
+        mesh.edges()
+            .filter(|edge| mesh.is_edge_on_boundary(edge))
+            .count()
+    }
+
+    /// 4x4 grid on [-2, 2] with interior vertex at (0, 0) moved to `to`
+    fn grid_with_shifted_vertex(to: Vec3f) -> CornerTableF {
+        let mut mesh: CornerTableF = primitives::plane(4.0, 4.0, 4, 4);
+        let vertex = mesh
+            .vertices()
+            .find(|v| mesh.vertex_position(v).norm() < 1e-6)
+            .unwrap();
+        mesh.shift_vertex(&vertex, &to);
+
+        mesh
+    }
+
+    #[test]
+    fn test_remove_needles() {
+        let mut mesh = grid_with_shifted_vertex(Vec3f::new(0.99, 0.0, 0.0));
+        let boundary_edges = boundary_edges_count(&mesh);
+        assert!(min_quality(&mesh) < 0.1);
+
+        assert!(remove_slivers(&mut mesh, 0.1, 0.0) > 0);
+
+        assert!(min_quality(&mesh) >= 0.1);
+        assert_eq!(boundary_edges_count(&mesh), boundary_edges);
+        assert!(mesh.vertices().all(|v| mesh.vertex_position(&v).z == 0.0));
+    }
+
+    #[test]
+    fn test_remove_caps() {
+        let mut mesh = grid_with_shifted_vertex(Vec3f::new(0.99, 0.5, 0.0));
+        let faces_count = mesh.faces().count();
+        assert!(min_quality(&mesh) < 0.1);
+
+        assert!(remove_slivers(&mut mesh, 0.1, 0.0) > 0);
+
+        assert!(min_quality(&mesh) >= 0.1);
+        assert_eq!(mesh.faces().count(), faces_count);
+    }
+
+    #[test]
+    fn test_good_mesh_is_not_changed() {
+        let mut mesh: CornerTableF = primitives::plane(4.0, 4.0, 4, 4);
+        assert_eq!(remove_slivers(&mut mesh, 0.1, 0.0), 0);
+    }
+}
@@ -0,0 +1,393 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::{
+    algo::{
+        manifold::{make_manifold, weld_points, ManifoldReport},
+        merge_points::merge_points,
+        orient_faces::orient_faces,
+    },
+    geometry::{
+        primitives::{line_segment3::LineSegment3, triangle3::Triangle3},
+        traits::HasBBox3,
+    },
+    helpers::aliases::Vec3f,
+    mesh::{corner_table::table::CornerTable, traits::{Mesh, TopologicalMesh}},
+    spatial_partitioning::aabb_tree::{AABBTree, MedianCut},
+};
+#[cfg(feature = "voxel")]
+use crate::voxel::{
+    mesh_to_volume::MeshToVolume,
+    meshing::{MarchingCubesMesher, Mesher},
+};
+
+///
+/// Options of [repair]
+///
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct RepairOptions {
+    /// Vertices closer than this distance are welded
+    pub weld_tolerance: f32,
+    /// Make winding of faces coherent, closed components are oriented outwards
+    pub orient: bool,
+    /// Close boundary loops by triangulating them
+    pub fill_holes: bool,
+    /// Holes with more edges are left open. `None` fills holes of any size.
+    pub max_hole_edges: Option<usize>,
+    /// Size of voxel used to remesh connected components with self-intersections. `None` only reports them.
+    /// Ignored when `voxel` feature is disabled.
+    pub voxel_size: Option<f32>,
+}
+
+impl Default for RepairOptions {
+    fn default() -> Self {
+        Self {
+            weld_tolerance: 1e-5,
+            orient: true,
+            fill_holes: true,
+            max_hole_edges: None,
+            voxel_size: Some(0.1),
+        }
+    }
+}
+
+///
+/// Fixes done by [repair]
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct RepairReport {
+    /// Welding, removal of degenerate and duplicate faces and splitting of non-manifold edges.
+    /// Boundary edges are counted before hole filling.
+    pub cleanup: ManifoldReport,
+    /// Faces flipped to make orientation coherent
+    pub flipped_faces: usize,
+    /// Boundary loops closed by new faces
+    pub filled_holes: usize,
+    /// Boundary loops left open because they are larger than [RepairOptions::max_hole_edges] or pass through non-manifold vertex
+    pub skipped_holes: usize,
+    /// Faces intersecting other faces of mesh
+    pub self_intersecting_faces: usize,
+    /// Faces of components replaced by voxel remesh
+    pub remeshed_faces: usize,
+    /// Edges of one face in result, zero when surface is closed
+    pub boundary_edges: usize,
+}
+
+impl RepairReport {
+    /// Returns `true` when result has no boundary
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.boundary_edges == 0
+    }
+}
+
+///
+/// Makes mesh printable by running sequence of fixes:
+/// 1. Welding of vertices closer than [RepairOptions::weld_tolerance] and removal of faces collapsed by welding.
+/// 2. Coherent orientation of faces (see [orient_faces]).
+/// 3. Removal of duplicate faces and splitting of non-manifold edges (see [make_manifold]).
+/// 4. Hole filling, every boundary loop is triangulated by repeatedly cutting off its sharpest convex corner.
+///    Filling is suitable for holes which are nearly flat, large curved holes may need remeshing afterwards.
+/// 5. Detection of self-intersections. Faces sharing vertex are not tested against each other.
+///    Connected components having self-intersections or intersecting each other are replaced by voxel remesh
+///    (see [RepairOptions::voxel_size]), other components are kept intact.
+///
+/// ## Example
+/// ```ignore
+/// let (repaired, report) = repair(&scan, RepairOptions { voxel_size: Some(0.2), ..Default::default() });
+/// assert!(report.is_closed());
+/// ```
+///
+pub fn repair<TMesh: Mesh<ScalarType = f32>>(mesh: &TMesh, options: RepairOptions) -> (CornerTable<f32>, RepairReport) {
+    let mut report = RepairReport::default();
+
+    let soup: Vec<_> = mesh
+        .faces()
+        .flat_map(|face| {
+            let triangle = mesh.face_positions(&face);
+            [*triangle.p1(), *triangle.p2(), *triangle.p3()]
+        })
+        .collect();
+    let indexed = merge_points(&soup);
+    let (points, welded) = weld_points(&indexed.points, options.weld_tolerance);
+
+    let mut indices = Vec::with_capacity(indexed.indices.len());
+    let mut degenerate_faces = 0;
+
+    for face in indexed.indices.chunks_exact(3) {
+        let face = [welded[face[0]], welded[face[1]], welded[face[2]]];
+
+        if face[0] == face[1] || face[1] == face[2] || face[2] == face[0] {
+            degenerate_faces += 1;
+        } else {
+            indices.extend(face);
+        }
+    }
+
+    if options.orient {
+        report.flipped_faces = orient_faces(&points, &mut indices, true);
+    }
+
+    let oriented: Vec<_> = indices.iter().map(|index| points[*index]).collect();
+    let (cleaned, cleanup) = make_manifold(&oriented, 0.0);
+    report.cleanup = ManifoldReport {
+        welded_vertices: indexed.points.len() - points.len(),
+        degenerate_faces,
+        ..cleanup
+    };
+
+    let points: Vec<_> = cleaned.vertices().map(|vertex| *cleaned.vertex_position(&vertex)).collect();
+    let mut faces: Vec<_> = cleaned
+        .faces()
+        .map(|face| {
+            let (v1, v2, v3) = cleaned.face_vertices(&face);
+            [v1, v2, v3]
+        })
+        .collect();
+
+    if options.fill_holes {
+        for boundary_loop in boundary_loops(&faces) {
+            match boundary_loop {
+                Some(boundary_loop) if options.max_hole_edges.is_none_or(|max| boundary_loop.len() <= max) => {
+                    fill_hole(&points, &boundary_loop, &mut faces);
+                    report.filled_holes += 1;
+                }
+                _ => report.skipped_holes += 1,
+            }
+        }
+    }
+
+    let intersecting = self_intersecting_faces(&points, &faces);
+    report.self_intersecting_faces = intersecting.iter().filter(|face| **face).count();
+
+    // Only voxel remeshing of intersecting regions changes points
+    #[cfg(feature = "voxel")]
+    let mut points = points;
+    #[cfg(feature = "voxel")]
+    if let Some(voxel_size) = options.voxel_size.filter(|_| report.self_intersecting_faces > 0) {
+        report.remeshed_faces = remesh_intersecting(&mut points, &mut faces, &intersecting, voxel_size);
+    }
+
+    let (result, _) = CornerTable::from_faces_split_non_manifold(&points, faces);
+    report.boundary_edges = result.edges().filter(|edge| result.is_edge_on_boundary(edge)).count();
+
+    (result, report)
+}
+
+///
+/// Returns loops of directed boundary edges (edges without reversed twin) in direction of faces.
+/// Loops passing through vertex having several outgoing boundary edges are `None`.
+///
+fn boundary_loops(faces: &[[usize; 3]]) -> Vec<Option<Vec<usize>>> {
+    let edges: HashSet<_> = faces
+        .iter()
+        .flat_map(|face| [(face[0], face[1]), (face[1], face[2]), (face[2], face[0])])
+        .collect();
+
+    let mut next: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+
+    for (from, to) in edges.iter().filter(|(from, to)| !edges.contains(&(*to, *from))) {
+        next.entry(*from).or_default().push(*to);
+    }
+
+    let mut loops = Vec::new();
+
+    while let Some((start, targets)) = next.pop_first() {
+        let mut boundary_loop = vec![start];
+        let mut valid = targets.len() == 1;
+        let mut current = targets[0];
+
+        while current != start {
+            boundary_loop.push(current);
+
+            match next.remove(&current) {
+                Some(targets) => {
+                    valid &= targets.len() == 1;
+                    current = targets[0];
+                }
+                None => {
+                    valid = false;
+                    break;
+                }
+            }
+        }
+
+        loops.push(valid.then_some(boundary_loop));
+    }
+
+    loops
+}
+
+///
+/// Triangulates hole bounded by loop by cutting off ears. Ear at sharpest convex corner is cut first,
+/// convexity is measured against average normal of hole. New faces are oriented coherently with faces around hole.
+///
+fn fill_hole(points: &[Vec3f], boundary_loop: &[usize], faces: &mut Vec<[usize; 3]>) {
+    let mut remaining = boundary_loop.to_vec();
+
+    // Newell normal of filling polygon, it runs against direction of boundary edges
+    let normal: Vec3f = (0..remaining.len())
+        .map(|i| points[remaining[(i + 1) % remaining.len()]].cross(&points[remaining[i]]))
+        .sum();
+
+    while remaining.len() > 3 {
+        let count = remaining.len();
+        let ear = (0..count)
+            .map(|i| {
+                let (previous, current, next) = (remaining[(i + count - 1) % count], remaining[i], remaining[(i + 1) % count]);
+                let (a, b) = (points[previous] - points[current], points[next] - points[current]);
+                let convex = (points[current] - points[next]).cross(&(points[previous] - points[next])).dot(&normal) >= 0.0;
+
+                (i, !convex, a.angle(&b))
+            })
+            .min_by(|(_, reflex1, angle1), (_, reflex2, angle2)| reflex1.cmp(reflex2).then(angle1.total_cmp(angle2)))
+            .map(|(i, _, _)| i)
+            .unwrap();
+
+        let (previous, current, next) = (remaining[(ear + count - 1) % count], remaining[ear], remaining[(ear + 1) % count]);
+        faces.push([next, current, previous]);
+        remaining.remove(ear);
+    }
+
+    faces.push([remaining[2], remaining[1], remaining[0]]);
+}
+
+/// Marks faces crossing other faces, faces sharing vertex are not tested
+fn self_intersecting_faces(points: &[Vec3f], faces: &[[usize; 3]]) -> Vec<bool> {
+    let triangles: Vec<_> = faces
+        .iter()
+        .map(|face| Triangle3::new(points[face[0]], points[face[1]], points[face[2]]))
+        .collect();
+    let tree = AABBTree::new(triangles.clone()).top_down::<MedianCut>();
+    let mut intersecting = vec![false; faces.len()];
+
+    let crosses = |triangle: &Triangle3<f32>, other: &Triangle3<f32>| {
+        let corners = [other.p1(), other.p2(), other.p3()];
+        (0..3).any(|i| triangle.intersects_line_segment3(&LineSegment3::new(corners[i], corners[(i + 1) % 3])))
+    };
+
+    for (face, triangle) in triangles.iter().enumerate() {
+        for other in tree.objects_in_box(&triangle.bbox()) {
+            if other <= face || faces[other].iter().any(|vertex| faces[face].contains(vertex)) {
+                continue;
+            }
+
+            if crosses(triangle, &triangles[other]) || crosses(&triangles[other], triangle) {
+                intersecting[face] = true;
+                intersecting[other] = true;
+            }
+        }
+    }
+
+    intersecting
+}
+
+///
+/// Replaces connected components containing intersecting faces by voxel remesh of their union.
+/// Returns number of replaced faces.
+///
+#[cfg(feature = "voxel")]
+fn remesh_intersecting(points: &mut Vec<Vec3f>, faces: &mut Vec<[usize; 3]>, intersecting: &[bool], voxel_size: f32) -> usize {
+    fn find(parent: &mut [usize], vertex: usize) -> usize {
+        let mut root = vertex;
+
+        while parent[root] != root {
+            root = parent[root];
+        }
+
+        parent[vertex] = root;
+        root
+    }
+
+    let mut parent: Vec<_> = (0..points.len()).collect();
+
+    for face in faces.iter() {
+        for vertex in &face[1..] {
+            let (a, b) = (find(&mut parent, face[0]), find(&mut parent, *vertex));
+            parent[a] = b;
+        }
+    }
+
+    let broken: HashSet<_> = faces
+        .iter()
+        .zip(intersecting)
+        .filter(|(_, intersecting)| **intersecting)
+        .map(|(face, _)| find(&mut parent, face[0]))
+        .collect();
+
+    let (remeshed, kept): (Vec<_>, Vec<_>) = faces.iter().partition(|face| broken.contains(&find(&mut parent, face[0])));
+
+    let indices: Vec<_> = remeshed.iter().flatten().copied().collect();
+    let shells = CornerTable::from_vertices_and_indices(points, &indices);
+
+    let Some(volume) = MeshToVolume::default().with_voxel_size(voxel_size).convert(&shells) else {
+        return 0;
+    };
+
+    let surface = MarchingCubesMesher::default().with_voxel_size(voxel_size).mesh_triangles(&volume);
+    let surface = merge_points(&surface);
+    let offset = points.len();
+
+    points.extend(surface.points);
+    *faces = kept;
+    faces.extend(surface.indices.chunks_exact(3).map(|face| [face[0] + offset, face[1] + offset, face[2] + offset]));
+
+    remeshed.len()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{repair, RepairOptions};
+    use crate::{
+        algo::mass_properties::mass_properties,
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, polygon_soup::data_structure::PolygonSoup, primitives, traits::Mesh},
+    };
+
+    #[test]
+    fn test_repair() {
+        let cube: CornerTableF = primitives::cuboid(Vec3f::new(1.0, 1.0, 1.0), 2);
+        let mut triangles: Vec<_> = cube
+            .faces()
+            .map(|face| cube.face_positions(&face))
+            .map(|triangle| [*triangle.p1(), *triangle.p2(), *triangle.p3()])
+            .collect();
+
+        // Hole, flipped face and crack
+        triangles.remove(0);
+        triangles[5].swap(1, 2);
+        triangles[7][0] += Vec3f::new(1e-6, 0.0, 0.0);
+
+        let soup = PolygonSoup::from_vertices(triangles.concat());
+        let (repaired, report) = repair(&soup, RepairOptions::default());
+
+        assert!(report.is_closed());
+        assert_eq!(report.flipped_faces, 1);
+        assert_eq!(report.filled_holes, 1);
+        assert_eq!(report.skipped_holes, 0);
+        assert_eq!(report.self_intersecting_faces, 0);
+        assert_eq!(report.cleanup.welded_vertices, 1);
+        assert_eq!(repaired.faces().count(), cube.faces().count());
+        assert!((mass_properties(&repaired, 1.0).volume - 1.0).abs() < 1e-4);
+
+        // Overlapping cubes are merged by remesh, separate cube is kept
+        let shifted = |shift: Vec3f| cube.faces().map(|face| cube.face_positions(&face)).flat_map(move |triangle| {
+            [*triangle.p1() + shift, *triangle.p2() + shift, *triangle.p3() + shift]
+        });
+        let points: Vec<_> = shifted(Vec3f::zeros())
+            .chain(shifted(Vec3f::new(0.5, 0.5, 0.5)))
+            .chain(shifted(Vec3f::new(5.0, 0.0, 0.0)))
+            .collect();
+        let (repaired, report) = repair(&PolygonSoup::from_vertices(points), RepairOptions::default());
+
+        assert!(report.is_closed());
+        assert!(report.self_intersecting_faces > 0);
+        assert_eq!(report.remeshed_faces, cube.faces().count() * 2);
+
+        // Union of two cubes and one cube
+        let volume = mass_properties(&repaired, 1.0).volume;
+        assert!((volume - (2.0 - 0.125 + 1.0)).abs() < 0.1);
+    }
+}
@@ -0,0 +1,186 @@
+use std::collections::HashMap;
+
+use num_traits::{cast, Float, One, ToPrimitive, Zero};
+
+use crate::{geometry::traits::RealNumber, helpers::aliases::Vec3, mesh::traits::Mesh};
+
+pub use crate::helpers::random::Rng;
+
+/// Number of candidates per expected Poisson-disk sample
+const POISSON_CANDIDATES_FACTOR: usize = 20;
+
+///
+/// Point sampled on mesh surface
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceSample<TScalar: RealNumber> {
+    pub point: Vec3<TScalar>,
+    /// Index of face (position in [Mesh::faces] iterator) containing point
+    pub face: usize,
+}
+
+///
+/// Random sampling of mesh surface. Samples are generated by crate-local [Rng] seeded with [SurfaceSampler::with_seed],
+/// so same mesh and seed give same samples on every run and platform.
+///
+/// ## Example
+/// ```ignore
+/// let samples = SurfaceSampler::new()
+///     .with_seed(42)
+///     .poisson_disk(&mesh, 0.1);
+/// ```
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SurfaceSampler {
+    seed: u64,
+}
+
+impl SurfaceSampler {
+    pub fn new() -> Self {
+        Self { seed: 0 }
+    }
+
+    /// Set seed of random generator. Default is `0`.
+    #[inline]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Returns `count` points uniformly distributed over surface area
+    pub fn uniform<TMesh: Mesh>(&self, mesh: &TMesh, count: usize) -> Vec<SurfaceSample<TMesh::ScalarType>> {
+        self.uniform_with_rng(mesh, count, &mut Rng::new(self.seed))
+    }
+
+    ///
+    /// Returns points on surface with distance between any two of them not less than `radius`.
+    /// Uses dart throwing: uniform candidates are accepted when they are far enough from already accepted ones.
+    /// Distance is euclidean, so points on opposite sides of thin walls may be closer than `radius` along surface.
+    ///
+    pub fn poisson_disk<TMesh: Mesh>(&self, mesh: &TMesh, radius: TMesh::ScalarType) -> Vec<SurfaceSample<TMesh::ScalarType>> {
+        assert!(radius > TMesh::ScalarType::zero(), "Radius should be positive");
+
+        let area = mesh
+            .faces()
+            .map(|face| mesh.face_positions(&face).get_area())
+            .fold(TMesh::ScalarType::zero(), |sum, area| sum + area);
+        let disk_area = radius * radius * cast(std::f64::consts::PI).unwrap();
+        let expected = (area / disk_area).to_usize().unwrap_or(0) + 1;
+
+        let mut rng = Rng::new(self.seed);
+        let candidates = self.uniform_with_rng(mesh, expected * POISSON_CANDIDATES_FACTOR, &mut rng);
+
+        // Accepted samples hashed by cell of size `radius`, neighbors are searched in adjacent cells
+        let cell = |point: &Vec3<TMesh::ScalarType>| point.map(|c| Float::floor(c / radius).to_i64().unwrap_or(0));
+        let mut cells: HashMap<_, Vec<usize>> = HashMap::new();
+        let mut accepted: Vec<SurfaceSample<TMesh::ScalarType>> = Vec::new();
+        let radius_squared = radius * radius;
+
+        for candidate in candidates {
+            let center = cell(&candidate.point);
+            let mut is_far = true;
+
+            'search: for dx in -1..=1 {
+                for dy in -1..=1 {
+                    for dz in -1..=1 {
+                        let neighbor = center + Vec3::new(dx, dy, dz);
+
+                        for &sample in cells.get(&neighbor).into_iter().flatten() {
+                            if (accepted[sample].point - candidate.point).norm_squared() < radius_squared {
+                                is_far = false;
+                                break 'search;
+                            }
+                        }
+                    }
+                }
+            }
+
+            if is_far {
+                cells.entry(center).or_default().push(accepted.len());
+                accepted.push(candidate);
+            }
+        }
+
+        accepted
+    }
+
+    /// Same as [SurfaceSampler::uniform] but draws numbers from given generator instead of seeded one
+    pub fn uniform_with_rng<TMesh: Mesh>(&self, mesh: &TMesh, count: usize, rng: &mut Rng) -> Vec<SurfaceSample<TMesh::ScalarType>> {
+        let triangles: Vec<_> = mesh.faces().map(|face| mesh.face_positions(&face)).collect();
+
+        // Cumulative areas for choosing face proportionally to its area
+        let mut cumulative = Vec::with_capacity(triangles.len());
+        let mut total = TMesh::ScalarType::zero();
+        for triangle in &triangles {
+            total += triangle.get_area();
+            cumulative.push(total);
+        }
+
+        if total.is_zero() {
+            return Vec::new();
+        }
+
+        (0..count)
+            .map(|_| {
+                let target = rng.next_real::<TMesh::ScalarType>() * total;
+                let face = cumulative.partition_point(|area| *area <= target).min(triangles.len() - 1);
+
+                // Uniform point in triangle
+                let mut u = rng.next_real::<TMesh::ScalarType>();
+                let mut v = rng.next_real::<TMesh::ScalarType>();
+                if u + v > TMesh::ScalarType::one() {
+                    u = TMesh::ScalarType::one() - u;
+                    v = TMesh::ScalarType::one() - v;
+                }
+
+                let triangle = &triangles[face];
+                let point = triangle.p1() + (triangle.p2() - triangle.p1()) * u + (triangle.p3() - triangle.p1()) * v;
+
+                SurfaceSample { point, face }
+            })
+            .collect()
+    }
+}
+
+impl Default for SurfaceSampler {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SurfaceSampler;
+    use crate::mesh::{corner_table::prelude::CornerTableF, primitives};
+
+    #[test]
+    fn test_uniform() {
+        let sphere: CornerTableF = primitives::uv_sphere(1.0, 32, 16);
+
+        let samples = SurfaceSampler::new().with_seed(3).uniform(&sphere, 500);
+        assert_eq!(samples.len(), 500);
+        assert!(samples.iter().all(|s| (s.point.norm() - 1.0).abs() < 0.02));
+        assert_eq!(samples, SurfaceSampler::new().with_seed(3).uniform(&sphere, 500));
+        assert_ne!(samples, SurfaceSampler::new().with_seed(4).uniform(&sphere, 500));
+
+        // Samples are distributed over both hemispheres
+        let upper = samples.iter().filter(|s| s.point.z > 0.0).count();
+        assert!(upper > 200 && upper < 300);
+    }
+
+    #[test]
+    fn test_poisson_disk() {
+        let plane: CornerTableF = primitives::plane(1.0, 1.0, 4, 4);
+
+        let samples = SurfaceSampler::new().poisson_disk(&plane, 0.1);
+        assert!(samples.len() > 40);
+        assert_eq!(samples, SurfaceSampler::new().poisson_disk(&plane, 0.1));
+
+        for (i, a) in samples.iter().enumerate() {
+            for b in &samples[i + 1..] {
+                assert!((a.point - b.point).norm() >= 0.1);
+            }
+        }
+    }
+}
@@ -0,0 +1,210 @@
+use num_traits::{cast, Float, One, Zero};
+
+use crate::{
+    algo::mass_properties::mass_properties,
+    helpers::aliases::Vec3,
+    mesh::traits::{EditableMesh, TopologicalMesh},
+};
+
+///
+/// Smoothing step applied by [Smoothing] on every iteration
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum SmoothingMethod<TScalar> {
+    /// Every vertex is moved towards average of its neighbors, mesh shrinks with every iteration
+    Laplacian,
+    ///
+    /// Laplacian step followed by inflating step with negative factor `mu`, `|mu|` should be slightly greater than smoothing factor
+    /// (e.g. `-0.53` for factor `0.5`). Removes noise with much less shrinkage than plain Laplacian smoothing.
+    ///
+    Taubin { mu: TScalar },
+}
+
+///
+/// Laplacian smoothing of triangle meshes. Plain Laplacian smoothing shrinks models, use [SmoothingMethod::Taubin]
+/// and/or volume correction (see [Smoothing::with_preserve_volume]) to keep dimensions of model, e.g. when cleaning up model for printing.
+///
+/// ## Example
+/// ```ignore
+/// Smoothing::new()
+///     .with_iterations(20)
+///     .with_method(SmoothingMethod::Taubin { mu: -0.53 })
+///     .with_preserve_volume(true)
+///     .smooth(&mut mesh);
+/// ```
+///
+pub struct Smoothing<TMesh: TopologicalMesh + EditableMesh> {
+    iterations: usize,
+    factor: TMesh::ScalarType,
+    method: SmoothingMethod<TMesh::ScalarType>,
+    preserve_volume: bool,
+    keep_boundary: bool,
+}
+
+impl<TMesh: TopologicalMesh + EditableMesh> Smoothing<TMesh> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set number of smoothing iterations. Default is `10`
+    #[inline]
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Set fraction of distance to average of neighbors vertex is moved by on every step. Default is `0.5`
+    #[inline]
+    pub fn with_factor(mut self, factor: TMesh::ScalarType) -> Self {
+        self.factor = factor;
+        self
+    }
+
+    /// Set smoothing method. Default is [SmoothingMethod::Laplacian]
+    #[inline]
+    pub fn with_method(mut self, method: SmoothingMethod<TMesh::ScalarType>) -> Self {
+        self.method = method;
+        self
+    }
+
+    ///
+    /// Set whether mesh is uniformly scaled about its center of mass after every iteration, so its enclosed volume stays unchanged.
+    /// Volume is defined for closed meshes only, so correction is skipped for meshes with boundary. Default is `false`
+    ///
+    #[inline]
+    pub fn with_preserve_volume(mut self, preserve: bool) -> Self {
+        self.preserve_volume = preserve;
+        self
+    }
+
+    /// Set whether boundary vertices are fixed. Default is `true`
+    #[inline]
+    pub fn with_keep_boundary(mut self, keep: bool) -> Self {
+        self.keep_boundary = keep;
+        self
+    }
+
+    /// Smooths `mesh` in place, connectivity is not changed
+    pub fn smooth(&self, mesh: &mut TMesh) {
+        let vertices: Vec<_> = mesh
+            .vertices()
+            .filter(|vertex| !self.keep_boundary || !mesh.is_vertex_on_boundary(vertex))
+            .collect();
+        let closed = mesh.vertices().all(|vertex| !mesh.is_vertex_on_boundary(&vertex));
+        let volume = mass_properties(mesh, TMesh::ScalarType::one()).volume;
+
+        for _ in 0..self.iterations {
+            laplacian_step(mesh, &vertices, self.factor);
+
+            if let SmoothingMethod::Taubin { mu } = self.method {
+                laplacian_step(mesh, &vertices, mu);
+            }
+
+            if self.preserve_volume && closed {
+                restore_volume(mesh, volume);
+            }
+        }
+    }
+}
+
+impl<TMesh: TopologicalMesh + EditableMesh> Default for Smoothing<TMesh> {
+    fn default() -> Self {
+        Self {
+            iterations: 10,
+            factor: cast(0.5).unwrap(),
+            method: SmoothingMethod::Laplacian,
+            preserve_volume: false,
+            keep_boundary: true,
+        }
+    }
+}
+
+/// Moves vertices towards average of their neighbors by `factor`, all vertices are moved at once
+fn laplacian_step<TMesh: TopologicalMesh + EditableMesh>(mesh: &mut TMesh, vertices: &[TMesh::VertexDescriptor], factor: TMesh::ScalarType) {
+    let positions: Vec<_> = vertices
+        .iter()
+        .map(|vertex| {
+            let mut sum = Vec3::zeros();
+            let mut count = 0;
+
+            mesh.vertices_around_vertex(vertex, |neighbor| {
+                sum += mesh.vertex_position(neighbor);
+                count += 1;
+            });
+
+            let position = mesh.vertex_position(vertex);
+
+            if count == 0 {
+                return *position;
+            }
+
+            let average: Vec3<TMesh::ScalarType> = sum / cast::<usize, TMesh::ScalarType>(count).unwrap();
+            position + (average - position) * factor
+        })
+        .collect();
+
+    for (vertex, position) in vertices.iter().zip(positions) {
+        mesh.shift_vertex(vertex, &position);
+    }
+}
+
+/// Scales mesh about its center of mass, so its enclosed volume is equal to `volume`
+fn restore_volume<TMesh: TopologicalMesh + EditableMesh>(mesh: &mut TMesh, volume: TMesh::ScalarType) {
+    let properties = mass_properties(mesh, TMesh::ScalarType::one());
+
+    // Inside out or collapsed mesh can't be fixed by scaling
+    if properties.volume.is_zero() || (properties.volume > TMesh::ScalarType::zero()) != (volume > TMesh::ScalarType::zero()) {
+        return;
+    }
+
+    let scale = Float::cbrt(volume / properties.volume);
+    let center = properties.center_of_mass;
+    let vertices: Vec<_> = mesh.vertices().collect();
+
+    for vertex in vertices {
+        let position = center + (mesh.vertex_position(&vertex) - center) * scale;
+        mesh.shift_vertex(&vertex, &position);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Smoothing, SmoothingMethod};
+    use crate::{
+        algo::mass_properties::mass_properties,
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+    };
+
+    #[test]
+    fn test_volume_preserving_smoothing() {
+        let volume = |mesh: &CornerTableF| mass_properties(mesh, 1.0).volume;
+        let smoothed = |smoothing: Smoothing<CornerTableF>| {
+            let mut mesh: CornerTableF = primitives::uv_sphere(1.0, 32, 16);
+            smoothing.with_iterations(20).smooth(&mut mesh);
+            mesh
+        };
+
+        let original = volume(&primitives::uv_sphere(1.0, 32, 16));
+        let laplacian = volume(&smoothed(Smoothing::new()));
+        let taubin = volume(&smoothed(Smoothing::new().with_method(SmoothingMethod::Taubin { mu: -0.53 })));
+        let preserved = smoothed(Smoothing::new().with_preserve_volume(true));
+
+        // Plain smoothing shrinks sphere, Taubin smoothing much less
+        assert!(laplacian < original * 0.9);
+        assert!((original - taubin).abs() < (original - laplacian) * 0.25);
+        assert!((volume(&preserved) - original).abs() < original * 1e-4);
+
+        // Scaling is done about center, sphere stays centered
+        let center = preserved.vertices().map(|vertex| *preserved.vertex_position(&vertex)).sum::<Vec3f>()
+            / preserved.vertices().count() as f32;
+        assert!(center.norm() < 1e-4);
+
+        // Open mesh keeps boundary and is not rescaled
+        let mut plane: CornerTableF = primitives::plane(1.0, 1.0, 4, 4);
+        let corner = *plane.vertex_position(&0);
+        Smoothing::new().with_preserve_volume(true).smooth(&mut plane);
+        assert_eq!(*plane.vertex_position(&0), corner);
+    }
+}
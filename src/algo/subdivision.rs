@@ -0,0 +1,259 @@
+use std::collections::{HashMap, HashSet};
+
+use num_traits::{cast, Float};
+
+use crate::{geometry::traits::RealNumber, helpers::aliases::Vec3, mesh::traits::Mesh};
+
+///
+/// Loop subdivision of triangle meshes.
+/// Each iteration splits every triangle into four and smooths vertices using Loop's masks,
+/// so low-poly meshes (e.g. results of CSG) converge to smooth surface.
+///
+/// Boundary edges, non-manifold edges and user given crease edges are kept sharp:
+/// vertices on them are smoothed only along the crease, vertices where more than two creases meet stay fixed.
+///
+/// ## Example
+/// ```ignore
+/// let subdivision = LoopSubdivision::new()
+///     .with_iterations(2)
+///     .with_crease_edges(sharp_edges);
+/// let smooth: CornerTableF = subdivision.subdivide(&mesh);
+/// ```
+///
+pub struct LoopSubdivision<TMesh: Mesh> {
+    iterations: usize,
+    crease_edges: HashSet<(TMesh::VertexDescriptor, TMesh::VertexDescriptor)>,
+}
+
+impl<TMesh: Mesh> LoopSubdivision<TMesh> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set number of subdivision iterations. Default is `1`
+    #[inline]
+    pub fn with_iterations(mut self, iterations: usize) -> Self {
+        self.iterations = iterations;
+        self
+    }
+
+    /// Set edges (given by pairs of vertices of input mesh) that should stay sharp
+    #[inline]
+    pub fn with_crease_edges<TIter>(mut self, edges: TIter) -> Self
+    where
+        TIter: IntoIterator<Item = (TMesh::VertexDescriptor, TMesh::VertexDescriptor)>
+    {
+        self.crease_edges.extend(edges.into_iter().map(|(v1, v2)| (v1.min(v2), v1.max(v2))));
+        self
+    }
+
+    /// Returns subdivided copy of `mesh`
+    pub fn subdivide(&self, mesh: &TMesh) -> TMesh {
+        let mut vertex_index = HashMap::new();
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+
+        for face in mesh.faces() {
+            let (v1, v2, v3) = mesh.face_vertices(&face);
+
+            for vertex in [v1, v2, v3] {
+                let index = *vertex_index.entry(vertex).or_insert_with(|| {
+                    vertices.push(*mesh.vertex_position(&vertex));
+                    vertices.len() - 1
+                });
+                indices.push(index);
+            }
+        }
+
+        let mut creases: HashSet<_> = self.crease_edges
+            .iter()
+            .filter_map(|(v1, v2)| Some(undirected(*vertex_index.get(v1)?, *vertex_index.get(v2)?)))
+            .collect();
+
+        for _ in 0..self.iterations {
+            (vertices, indices, creases) = subdivide_once(&vertices, &indices, &creases);
+        }
+
+        TMesh::from_vertices_and_indices(&vertices, &indices)
+    }
+}
+
+impl<TMesh: Mesh> Default for LoopSubdivision<TMesh> {
+    fn default() -> Self {
+        Self {
+            iterations: 1,
+            crease_edges: HashSet::new(),
+        }
+    }
+}
+
+/// Vertices and indices of subdivided mesh together with its crease edges
+type Subdivided<TScalar> = (Vec<Vec3<TScalar>>, Vec<usize>, HashSet<(usize, usize)>);
+
+fn subdivide_once<TScalar: RealNumber>(
+    vertices: &[Vec3<TScalar>],
+    indices: &[usize],
+    creases: &HashSet<(usize, usize)>,
+) -> Subdivided<TScalar> {
+    // Opposite vertices of faces adjacent to each edge
+    let mut edge_opposites: HashMap<(usize, usize), Vec<usize>> = HashMap::new();
+    for face in indices.chunks_exact(3) {
+        for i in 0..3 {
+            edge_opposites
+                .entry(undirected(face[i], face[(i + 1) % 3]))
+                .or_default()
+                .push(face[(i + 2) % 3]);
+        }
+    }
+
+    let is_sharp = |edge: &(usize, usize)| edge_opposites[edge].len() != 2 || creases.contains(edge);
+
+    // One ring and sharp neighbors of each vertex
+    let mut neighbors = vec![Vec::new(); vertices.len()];
+    let mut sharp_neighbors = vec![Vec::new(); vertices.len()];
+    for edge in edge_opposites.keys() {
+        let (v1, v2) = *edge;
+        neighbors[v1].push(v2);
+        neighbors[v2].push(v1);
+
+        if is_sharp(edge) {
+            sharp_neighbors[v1].push(v2);
+            sharp_neighbors[v2].push(v1);
+        }
+    }
+
+    // Even vertices
+    let mut new_vertices: Vec<_> = (0..vertices.len())
+        .map(|v| even_vertex(vertices, v, &neighbors[v], &sharp_neighbors[v]))
+        .collect();
+
+    // Odd vertices, one per edge
+    let three_eighths: TScalar = cast(0.375).unwrap();
+    let one_eighth: TScalar = cast(0.125).unwrap();
+    let half: TScalar = cast(0.5).unwrap();
+    let mut edge_vertex = HashMap::with_capacity(edge_opposites.len());
+
+    for (edge, opposites) in &edge_opposites {
+        let (v1, v2) = (&vertices[edge.0], &vertices[edge.1]);
+
+        let position = if is_sharp(edge) {
+            (v1 + v2) * half
+        } else {
+            (v1 + v2) * three_eighths + (vertices[opposites[0]] + vertices[opposites[1]]) * one_eighth
+        };
+
+        new_vertices.push(position);
+        edge_vertex.insert(*edge, new_vertices.len() - 1);
+    }
+
+    // Every face is split into four
+    let mut new_indices = Vec::with_capacity(indices.len() * 4);
+    for face in indices.chunks_exact(3) {
+        let (a, b, c) = (face[0], face[1], face[2]);
+        let ab = edge_vertex[&undirected(a, b)];
+        let bc = edge_vertex[&undirected(b, c)];
+        let ca = edge_vertex[&undirected(c, a)];
+
+        new_indices.extend_from_slice(&[a, ab, ca, ab, b, bc, ca, bc, c, ab, bc, ca]);
+    }
+
+    // Each crease is split in two
+    let new_creases = creases
+        .iter()
+        .filter_map(|edge| edge_vertex.get(edge).map(|mid| (edge, *mid)))
+        .flat_map(|((v1, v2), mid)| [undirected(*v1, mid), undirected(mid, *v2)])
+        .collect();
+
+    (new_vertices, new_indices, new_creases)
+}
+
+/// Loop's mask for existing vertex
+fn even_vertex<TScalar: RealNumber>(
+    vertices: &[Vec3<TScalar>],
+    vertex: usize,
+    neighbors: &[usize],
+    sharp_neighbors: &[usize],
+) -> Vec3<TScalar> {
+    let position = vertices[vertex];
+
+    match sharp_neighbors.len() {
+        // Smooth vertex (or dart)
+        0 | 1 => {
+            let n = neighbors.len();
+            if n == 0 {
+                return position;
+            }
+
+            let n_scalar: TScalar = cast(n).unwrap();
+            let cos = Float::cos(TScalar::two_pi() / n_scalar);
+            let inner = cast::<f64, TScalar>(0.375).unwrap() + cast::<f64, TScalar>(0.25).unwrap() * cos;
+            let beta = (cast::<f64, TScalar>(0.625).unwrap() - inner * inner) / n_scalar;
+
+            let sum = neighbors.iter().fold(Vec3::zeros(), |sum, v| sum + vertices[*v]);
+
+            position * (TScalar::one() - n_scalar * beta) + sum * beta
+        }
+        // Crease vertex
+        2 => {
+            let crease_sum = vertices[sharp_neighbors[0]] + vertices[sharp_neighbors[1]];
+            position * cast::<f64, TScalar>(0.75).unwrap() + crease_sum * cast::<f64, TScalar>(0.125).unwrap()
+        }
+        // Corner
+        _ => position,
+    }
+}
+
+#[inline]
+fn undirected(v1: usize, v2: usize) -> (usize, usize) {
+    (v1.min(v2), v1.max(v2))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::LoopSubdivision;
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+    };
+
+    #[test]
+    fn test_loop_subdivision() {
+        let cube: CornerTableF = primitives::cuboid(Vec3f::new(2.0, 2.0, 2.0), 1);
+        let subdivided = LoopSubdivision::new().with_iterations(2).subdivide(&cube);
+
+        // Each face is split into four, new vertex is inserted on each edge
+        assert_eq!(subdivided.faces().count(), 12 * 16);
+        assert_eq!(subdivided.vertices().count(), (8 + 18) + (2 * 18 + 3 * 12));
+
+        // Smoothed cube shrinks
+        for vertex in subdivided.vertices() {
+            assert!(subdivided.vertex_position(&vertex).abs().max() < 1.0);
+        }
+    }
+
+    #[test]
+    fn test_loop_subdivision_with_creases() {
+        let cube: CornerTableF = primitives::cuboid(Vec3f::new(2.0, 2.0, 2.0), 1);
+
+        // Crease all edges of box, diagonals of sides stay smooth
+        let creases: Vec<_> = cube.edges()
+            .map(|edge| cube.edge_vertices(&edge))
+            .filter(|(v1, v2)| {
+                let d = cube.vertex_position(v1) - cube.vertex_position(v2);
+                d.iter().filter(|c| c.abs() > 1e-6).count() == 1
+            })
+            .collect();
+        assert_eq!(creases.len(), 12);
+
+        let subdivided = LoopSubdivision::new()
+            .with_iterations(2)
+            .with_crease_edges(creases)
+            .subdivide(&cube);
+
+        // All vertices stay on sides of box
+        for vertex in subdivided.vertices() {
+            let max = subdivided.vertex_position(&vertex).abs().max();
+            assert!((max - 1.0).abs() < 1e-6);
+        }
+    }
+}
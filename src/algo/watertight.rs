@@ -0,0 +1,184 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::{
+    data_structures::vertex_index_map::HashablePoint,
+    geometry::traits::RealNumber,
+    helpers::aliases::Vec3,
+    mesh::traits::Mesh,
+};
+
+///
+/// Leaks of mesh surface found by [watertight_report]. Near leaks mesh does not separate inside from outside,
+/// so signs of distance fields computed from it (e.g. by [MeshToVolume](crate::voxel::prelude::MeshToVolume)) are unreliable there.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct WatertightReport<TScalar: RealNumber> {
+    /// Chains of boundary edges (edges of one face) as positions of their vertices, closed loops are not repeating first vertex.
+    /// Boundaries ending at non-manifold edges are open chains, loops passing through non-manifold vertices may be split.
+    pub boundary_loops: Vec<Vec<Vec3<TScalar>>>,
+    /// Edges shared by more than two faces as positions of their end points
+    pub non_manifold_edges: Vec<(Vec3<TScalar>, Vec3<TScalar>)>,
+}
+
+impl<TScalar: RealNumber> WatertightReport<TScalar> {
+    /// Returns `true` when mesh has no leaks
+    #[inline]
+    pub fn is_watertight(&self) -> bool {
+        self.boundary_loops.is_empty() && self.non_manifold_edges.is_empty()
+    }
+}
+
+///
+/// Returns `true` when every edge of mesh is shared by exactly two faces, so mesh encloses volume.
+/// Vertices are matched by exact positions, so meshes with duplicated vertices (e.g. polygon soups) are handled too.
+///
+pub fn is_watertight<TMesh: Mesh>(mesh: &TMesh) -> bool {
+    watertight_report(mesh).is_watertight()
+}
+
+///
+/// Finds leaks of mesh: boundary loops (holes and cracks) and non-manifold edges.
+/// Vertices are matched by exact positions, so meshes with duplicated vertices (e.g. polygon soups) are handled too.
+///
+/// ## Example
+/// ```ignore
+/// let report = watertight_report(&mesh);
+///
+/// for boundary_loop in &report.boundary_loops {
+///     println!("hole near {:?}", boundary_loop[0]);
+/// }
+/// ```
+///
+pub fn watertight_report<TMesh: Mesh>(mesh: &TMesh) -> WatertightReport<TMesh::ScalarType> {
+    let mut positions = Vec::new();
+    let mut position_ids = HashMap::new();
+    let mut position_id = |position: &Vec3<TMesh::ScalarType>| {
+        let key: HashablePoint<3, TMesh::ScalarType> = (*position).into();
+
+        *position_ids.entry(key).or_insert_with(|| {
+            positions.push(*position);
+            positions.len() - 1
+        })
+    };
+
+    // Directed edges of faces grouped by their undirected key
+    let mut edges: BTreeMap<(usize, usize), Vec<(usize, usize)>> = BTreeMap::new();
+
+    for face in mesh.faces() {
+        let triangle = mesh.face_positions(&face);
+        let ids = [triangle.p1(), triangle.p2(), triangle.p3()].map(&mut position_id);
+
+        for (from, to) in [(ids[0], ids[1]), (ids[1], ids[2]), (ids[2], ids[0])] {
+            // Faces collapsed into edge or point can't close surface
+            if from != to {
+                edges.entry((from.min(to), from.max(to))).or_default().push((from, to));
+            }
+        }
+    }
+
+    let mut boundary_edges: BTreeMap<usize, Vec<usize>> = BTreeMap::new();
+    let mut non_manifold_edges = Vec::new();
+
+    for ((v1, v2), directed) in &edges {
+        match directed.len() {
+            1 => boundary_edges.entry(directed[0].0).or_default().push(directed[0].1),
+            2 => {}
+            _ => non_manifold_edges.push((positions[*v1], positions[*v2])),
+        }
+    }
+
+    let boundary_loops = boundary_chains(boundary_edges)
+        .into_iter()
+        .map(|chain| chain.into_iter().map(|vertex| positions[vertex]).collect())
+        .collect();
+
+    WatertightReport {
+        boundary_loops,
+        non_manifold_edges,
+    }
+}
+
+///
+/// Splits directed boundary edges (outgoing edges of every vertex) into chains following edge directions.
+/// Every edge is in exactly one chain, chain is closed when its last vertex is connected to first one.
+///
+fn boundary_chains(mut outgoing: BTreeMap<usize, Vec<usize>>) -> Vec<Vec<usize>> {
+    let incoming: HashSet<_> = outgoing.values().flatten().copied().collect();
+    let heads: Vec<_> = outgoing.keys().copied().filter(|vertex| !incoming.contains(vertex)).collect();
+
+    let mut chains = Vec::new();
+
+    // Open chains are started at their heads, so they are not split
+    for head in heads {
+        while outgoing.contains_key(&head) {
+            chains.push(boundary_chain(&mut outgoing, head));
+        }
+    }
+
+    while let Some(&start) = outgoing.keys().next() {
+        chains.push(boundary_chain(&mut outgoing, start));
+    }
+
+    chains
+}
+
+fn boundary_chain(outgoing: &mut BTreeMap<usize, Vec<usize>>, start: usize) -> Vec<usize> {
+    let mut chain = vec![start];
+
+    while let Some(next) = pop_outgoing(outgoing, *chain.last().unwrap()) {
+        if next == start {
+            break;
+        }
+
+        chain.push(next);
+    }
+
+    chain
+}
+
+fn pop_outgoing(outgoing: &mut BTreeMap<usize, Vec<usize>>, vertex: usize) -> Option<usize> {
+    let targets = outgoing.get_mut(&vertex)?;
+    let next = targets.pop();
+
+    if targets.is_empty() {
+        outgoing.remove(&vertex);
+    }
+
+    next
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_watertight, watertight_report};
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, polygon_soup::data_structure::PolygonSoup, primitives},
+    };
+
+    #[test]
+    fn test_watertight_report() {
+        let sphere: CornerTableF = primitives::uv_sphere(1.0, 16, 8);
+        assert!(is_watertight(&sphere));
+
+        let plane: CornerTableF = primitives::plane(1.0, 1.0, 8, 8);
+        let report = watertight_report(&plane);
+        assert!(!report.is_watertight());
+        assert_eq!(report.boundary_loops.len(), 1);
+        assert_eq!(report.boundary_loops[0].len(), 32);
+        assert!(report.non_manifold_edges.is_empty());
+
+        // Soup of tetrahedron faces is closed, third face on one of its edges makes it non-manifold
+        let (a, b, c, d) = (Vec3f::zeros(), Vec3f::x(), Vec3f::y(), Vec3f::z());
+        let mut soup = PolygonSoup::new();
+        soup.add_face(a, c, b);
+        soup.add_face(a, b, d);
+        soup.add_face(b, c, d);
+        soup.add_face(c, a, d);
+        assert!(is_watertight(&soup));
+
+        soup.add_face(a, b, Vec3f::new(0.0, -1.0, 1.0));
+        let report = watertight_report(&soup);
+        assert_eq!(report.non_manifold_edges, vec![(a, b)]);
+        assert_eq!(report.boundary_loops, vec![vec![b, Vec3f::new(0.0, -1.0, 1.0), a]]);
+    }
+}
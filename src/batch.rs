@@ -0,0 +1,189 @@
+//!
+//! Processing of many meshes (e.g. one mesh per part) on a thread pool created once and reused by all jobs.
+//! Every worker thread keeps its own [Scratch], so buffers of operations are allocated once per thread instead of once per mesh.
+//! Parallel algorithms called by jobs (e.g. voxel remeshing) run on the same pool.
+//!
+
+use std::{
+    io,
+    sync::{
+        mpsc::{channel, Receiver, Sender},
+        Arc, Mutex,
+    },
+};
+
+use rayon::{ThreadPool, ThreadPoolBuilder};
+
+use crate::{
+    mesh::corner_table::prelude::CornerTableF,
+    ops::{apply_with, Op, Scratch},
+    pipelines::PipelineReport,
+};
+
+///
+/// Mesh processed by [BatchProcessor] together with operations applied to it in order
+///
+pub struct BatchJob {
+    /// Identifier of job returned in its result, e.g. index of part
+    pub id: usize,
+    pub mesh: CornerTableF,
+    pub ops: Vec<Op>,
+}
+
+///
+/// Result of [BatchJob]
+///
+pub struct BatchResult {
+    pub id: usize,
+    /// Processed mesh or error of first failed operation
+    pub mesh: io::Result<CornerTableF>,
+    /// Faces count after every finished operation, validation of result when all operations succeeded
+    pub report: PipelineReport,
+}
+
+///
+/// Runs [BatchJob]s on shared thread pool, results are sent through channel as soon as jobs are finished.
+///
+/// ## Example
+/// ```ignore
+/// let processor = BatchProcessor::new(0)?;
+/// let jobs = parts.into_iter().enumerate().map(|(id, mesh)| BatchJob {
+///     id,
+///     mesh,
+///     ops: vec![Op::Decimate { max_error: None, target_faces: Some(1000), keep_boundary: false }],
+/// });
+///
+/// for result in processor.process(jobs) {
+///     write_part(result.id, result.mesh?);
+/// }
+/// ```
+///
+pub struct BatchProcessor {
+    pool: ThreadPool,
+    scratches: Arc<Vec<Mutex<Scratch<CornerTableF>>>>,
+}
+
+impl BatchProcessor {
+    /// Creates processor with given number of threads, zero selects number of CPUs
+    pub fn new(threads: usize) -> io::Result<Self> {
+        let pool = ThreadPoolBuilder::new().num_threads(threads).build().map_err(io::Error::other)?;
+        let scratches = (0..pool.current_num_threads()).map(|_| Mutex::new(Scratch::default())).collect();
+
+        Ok(Self {
+            pool,
+            scratches: Arc::new(scratches),
+        })
+    }
+
+    #[inline]
+    pub fn threads_count(&self) -> usize {
+        self.pool.current_num_threads()
+    }
+
+    /// Queues job, its result is sent to `results` when job is finished
+    pub fn submit(&self, job: BatchJob, results: Sender<BatchResult>) {
+        let scratches = Arc::clone(&self.scratches);
+
+        self.pool.spawn(move || {
+            let worker = rayon::current_thread_index().unwrap_or(0);
+            let mut scratch = scratches[worker].lock().unwrap_or_else(|poisoned| poisoned.into_inner());
+
+            // Receiver may be dropped when caller is not interested in remaining results
+            let _ = results.send(run_job(job, &mut scratch));
+        });
+    }
+
+    ///
+    /// Queues all jobs and returns receiver of their results in order of completion.
+    /// Iteration over receiver ends when all jobs are finished.
+    ///
+    pub fn process(&self, jobs: impl IntoIterator<Item = BatchJob>) -> Receiver<BatchResult> {
+        let (sender, receiver) = channel();
+
+        for job in jobs {
+            self.submit(job, sender.clone());
+        }
+
+        receiver
+    }
+
+    /// Runs all jobs and waits for them, results are sorted by job id
+    pub fn process_all(&self, jobs: impl IntoIterator<Item = BatchJob>) -> Vec<BatchResult> {
+        let mut results: Vec<_> = self.process(jobs).into_iter().collect();
+        results.sort_by_key(|result| result.id);
+        results
+    }
+}
+
+fn run_job(job: BatchJob, scratch: &mut Scratch<CornerTableF>) -> BatchResult {
+    let mut report = PipelineReport::default();
+    report.add_stage("input", &job.mesh);
+
+    let mut mesh = job.mesh;
+
+    for op in &job.ops {
+        match apply_with(&mesh, op, scratch) {
+            Ok(result) => {
+                report.add_stage(op.name(), &result);
+                mesh = result;
+            }
+            Err(error) => {
+                return BatchResult {
+                    id: job.id,
+                    mesh: Err(error),
+                    report,
+                }
+            }
+        }
+    }
+
+    report.validate(&mesh);
+
+    BatchResult {
+        id: job.id,
+        mesh: Ok(mesh),
+        report,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BatchJob, BatchProcessor};
+    use crate::{
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+        ops::Op,
+    };
+
+    #[test]
+    fn test_batch_processor() {
+        let processor = BatchProcessor::new(2).unwrap();
+        assert_eq!(processor.threads_count(), 2);
+
+        let decimate = Op::Decimate { max_error: None, target_faces: Some(100), keep_boundary: false };
+        let jobs = (0..8).map(|id| BatchJob {
+            id,
+            mesh: primitives::uv_sphere(1.0 + id as f32, 16 + id, 8 + id),
+            ops: if id == 5 {
+                vec![Op::Check, Op::Decimate { max_error: None, target_faces: None, keep_boundary: false }]
+            } else {
+                vec![decimate.clone(), Op::Check]
+            },
+        });
+
+        let results = processor.process_all(jobs);
+        assert_eq!(results.iter().map(|result| result.id).collect::<Vec<_>>(), (0..8).collect::<Vec<_>>());
+
+        for result in &results {
+            if result.id == 5 {
+                assert!(result.mesh.is_err());
+                assert_eq!(result.report.stages.len(), 2);
+                continue;
+            }
+
+            let mesh: &CornerTableF = result.mesh.as_ref().unwrap();
+            assert!(mesh.faces().count() <= 100);
+            assert!(result.report.is_watertight());
+            assert_eq!(result.report.stages.len(), 3);
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//!
+//! Command line driver of `baby_shark::ops`.
+//!
+//! ```text
+//! baby_shark-cli <decimate|remesh|offset|boolean|check> <params.json>
+//! baby_shark-cli run <ops.ndjson|->
+//! ```
+//!
+//! Parameter file of command is JSON object with fields of [OpDesc] without `op` tag, e.g.
+//! `{ "input": "scan.stl", "output": "scan_decimated.stl", "target_faces": 10000 }`.
+//! `run` executes stream of tagged descriptions (one per line or concatenated objects) read from file or stdin.
+//! Result of every operation is printed to stdout as single line of JSON.
+//!
+
+use std::{
+    env, fs,
+    io::{self, Read},
+    process::ExitCode,
+};
+
+use baby_shark::ops::{self, OpDesc};
+use serde_json::{json, Value};
+
+const COMMANDS: [&str; 5] = ["decimate", "remesh", "offset", "boolean", "check"];
+const USAGE: &str = "Usage:
+    baby_shark-cli <decimate|remesh|offset|boolean|check> <params.json>
+    baby_shark-cli run <ops.ndjson|->";
+
+fn main() -> ExitCode {
+    let args: Vec<_> = env::args().skip(1).collect();
+
+    let (command, path) = match args.as_slice() {
+        [command, path] if command == "run" || COMMANDS.contains(&command.as_str()) => (command, path),
+        _ => {
+            eprintln!("{}", USAGE);
+            return ExitCode::from(2);
+        }
+    };
+
+    let text = match read_input(path) {
+        Ok(text) => text,
+        Err(err) => {
+            eprintln!("Failed to read {}: {}", path, err);
+            return ExitCode::from(2);
+        }
+    };
+
+    let mut succeeded = true;
+
+    for value in serde_json::Deserializer::from_str(&text).into_iter::<Value>() {
+        let mut value = match value {
+            Ok(value) => value,
+            Err(err) => {
+                // Nothing can be read after syntax error
+                println!("{}", json!({ "ok": false, "error": format!("Invalid JSON: {}", err) }));
+                succeeded = false;
+                break;
+            }
+        };
+
+        if command != "run" {
+            if let Some(object) = value.as_object_mut() {
+                object.insert("op".to_string(), Value::String(command.clone()));
+            }
+        }
+
+        let line = run(value);
+        succeeded &= line["ok"] == Value::Bool(true);
+        println!("{}", line);
+    }
+
+    if succeeded {
+        ExitCode::SUCCESS
+    } else {
+        ExitCode::FAILURE
+    }
+}
+
+/// Runs operation and returns line of output
+fn run(value: Value) -> Value {
+    let op = value.get("op").cloned().unwrap_or(Value::Null);
+
+    let desc: OpDesc = match serde_json::from_value(value) {
+        Ok(desc) => desc,
+        Err(err) => return json!({ "op": op, "ok": false, "error": format!("Invalid parameters: {}", err) }),
+    };
+
+    match ops::run(&desc) {
+        Ok(report) => json!({
+            "op": op,
+            "input": desc.input,
+            "output": desc.output,
+            "ok": true,
+            "watertight": report.is_watertight(),
+            "report": report,
+        }),
+        Err(err) => json!({ "op": op, "input": desc.input, "ok": false, "error": err.to_string() }),
+    }
+}
+
+fn read_input(path: &str) -> io::Result<String> {
+    if path == "-" {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text)?;
+        Ok(text)
+    } else {
+        fs::read_to_string(path)
+    }
+}
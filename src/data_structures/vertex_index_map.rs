@@ -6,6 +6,7 @@ use crate::{algo::float_hash::{combine_hash, hash_float}, geometry::traits::Real
 ///
 /// Hashable type for nalgebra Point3
 /// 
+#[derive(Clone, Copy)]
 pub struct HashablePoint<const D: usize, TScalar: RealNumber>(SVector<TScalar, D>);
 
 impl<const D: usize, TScalar: RealNumber> From<HashablePoint<D, TScalar>> for SVector<TScalar, D> {
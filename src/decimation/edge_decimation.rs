@@ -1,15 +1,26 @@
 use std::{
     cmp::Ordering,
-    collections::{BinaryHeap, HashMap},
+    collections::{BinaryHeap, HashMap, HashSet},
+    hash::Hash,
 };
 
-use nalgebra::{Matrix4, Vector4};
-use num_traits::{cast, Float, FromPrimitive, One};
+use nalgebra::{Matrix2, Matrix4, Matrix4x2, Matrix6, Vector4, Vector6};
+use num_traits::{cast, Float, FromPrimitive, One, ToPrimitive, Zero};
 
 use crate::{
     algo::edge_collapse,
-    helpers::aliases::Vec3,
-    mesh::traits::{EditableMesh, Marker, Mesh, MeshMarker, TopologicalMesh},
+    data_structures::vertex_index_map::HashablePoint,
+    geometry::{primitives::triangle3::Triangle3, traits::RealNumber},
+    helpers::{
+        aliases::{Vec2, Vec3},
+        trace::{trace_counters, trace_span},
+        utils::quantize,
+    },
+    mesh::{
+        face_uvs::FaceUvs,
+        traits::{EditableMesh, Marker, Mesh, MeshMarker, TopologicalMesh},
+    },
+    spatial_partitioning::grid::Grid,
 };
 
 /// Collapse candidate
@@ -36,7 +47,8 @@ impl<TMesh: Mesh> PartialEq for Contraction<TMesh> {
 impl<TMesh: Mesh> Ord for Contraction<TMesh> {
     #[inline]
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        other.cost.partial_cmp(&self.cost).unwrap()
+        // Equal costs are ordered by edges, so order doesn't depend on order of insertion
+        other.cost.partial_cmp(&self.cost).unwrap().then_with(|| other.edge.cmp(&self.edge))
     }
 }
 
@@ -58,28 +70,238 @@ pub trait CollapseStrategy<TMesh: Mesh>: Default {
     /// Returns point at which `edge` will be collapsed. Ideally it should minimize cost.
     fn get_placement(&self, mesh: &TMesh, edge: &TMesh::EdgeDescriptor) -> Vec3<TMesh::ScalarType>;
 
+    /// Returns `edge` collapsing cost at given point, used when edge is collapsed into vertex of constrained edge.
+    /// Defaults to [get_cost].
+    fn get_cost_at(&self, mesh: &TMesh, edge: &TMesh::EdgeDescriptor, _at: &Vec3<TMesh::ScalarType>) -> TMesh::ScalarType {
+        self.get_cost(mesh, edge)
+    }
+
     /// Called on edge collapse. Can be used to update internal state.
     fn collapse_edge(&mut self, mesh: &TMesh, edge: &TMesh::EdgeDescriptor);
 }
 
+/// Positions and UVs of corners of triangle
+type UvTriangle<TScalar> = ([Vec3<TScalar>; 3], [Vec2<TScalar>; 3]);
+
 ///
 /// Collapsing strategy based on quadric error.
 /// Collapsing cost is approximated using quadric matrices.
-/// Collapsing point is placed on middle of edge.
+/// Collapsing point is placed on middle of edge or, when [QuadricError::with_optimal_placement] is set, at point minimizing quadric error.
 /// Based on article of Heckber and Garland: http://www.cs.cmu.edu/~garland/Papers/quadrics.pdf.
+/// Error of UVs (see [QuadricError::with_uvs]) is measured as in article of Hoppe: https://hhoppe.com/newqem.pdf.
+///
+/// ## Example
+/// ```ignore
+/// // Keep rims of holes smooth
+/// let mut decimator = EdgeDecimator::new()
+///     .collapse_strategy(QuadricError::new().with_boundary_weight(Some(100.0)));
+/// decimator.decimate(&mut mesh);
+/// ```
 ///
 pub struct QuadricError<TMesh: Mesh> {
     vertex_quadric_map: HashMap<TMesh::VertexDescriptor, Matrix4<TMesh::ScalarType>>,
+    vertex_uv_quadric_map: HashMap<TMesh::VertexDescriptor, Matrix6<TMesh::ScalarType>>,
+    boundary_weight: Option<TMesh::ScalarType>,
+    optimal_placement: bool,
+    uv_triangles: Vec<UvTriangle<TMesh::ScalarType>>,
+    uv_weight: TMesh::ScalarType,
+}
+
+impl<TMesh: Mesh> QuadricError<TMesh> {
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    ///
+    /// Add quadrics of planes perpendicular to faces along boundary edges, multiplied by `weight`.
+    /// Such constraints penalize collapses that move boundary away from its original position,
+    /// so boundaries of holes remain smooth while interior is decimated normally.
+    /// Pass `None` to disable constraints (default).
+    ///
+    #[inline]
+    pub fn with_boundary_weight(mut self, weight: Option<TMesh::ScalarType>) -> Self {
+        self.boundary_weight = weight;
+        self
+    }
+
+    ///
+    /// Place collapsed vertex at point minimizing quadric error instead of middle of edge, so decimated mesh follows curved surfaces closer.
+    /// In flat and cylindrical regions, where minimum is not unique, point closest to middle of edge is used.
+    /// Optimal point may be far from edge, limit it with [IncrementalDecimator::max_displacement]. Default is `false`.
+    ///
+    #[inline]
+    pub fn with_optimal_placement(mut self, optimal: bool) -> Self {
+        self.optimal_placement = optimal;
+        self
+    }
+
+    ///
+    /// Measure error in combined 3D+UV space: squared differences between UVs of faces and UVs interpolated over
+    /// collapsed faces, multiplied by `weight`, are added to geometric error. UVs of collapsed vertex are the ones minimizing error,
+    /// so collapses stretching texture are more expensive. Faces are matched by positions, so components decimated separately
+    /// are supported too (see [IncrementalDecimator::component_budget]). Vertex on UV seam has several UVs,
+    /// so seams should be kept as constrained edges (see [FaceUvs::seam_edges]). UVs of decimated mesh are restored by [FaceUvs::transfer].
+    ///
+    /// ## Example
+    /// ```ignore
+    /// let mut decimator = EdgeDecimator::new()
+    ///     .constrained_edges(&mesh, uvs.seam_edges(&mesh))
+    ///     .collapse_strategy(QuadricError::new().with_uvs(&mesh, &uvs, 1.0));
+    /// decimator.decimate(&mut mesh);
+    /// ```
+    ///
+    pub fn with_uvs(mut self, mesh: &TMesh, uvs: &FaceUvs<TMesh>, weight: TMesh::ScalarType) -> Self {
+        self.uv_triangles = mesh
+            .faces()
+            .filter_map(|face| {
+                let triangle = mesh.face_positions(&face);
+                uvs.face_uvs(&face).map(|uvs| ([*triangle.p1(), *triangle.p2(), *triangle.p3()], *uvs))
+            })
+            .collect();
+        self.uv_weight = weight;
+        self
+    }
 }
 
 impl<TMesh: Mesh> Default for QuadricError<TMesh> {
     fn default() -> Self {
         Self {
             vertex_quadric_map: HashMap::new(),
+            vertex_uv_quadric_map: HashMap::new(),
+            boundary_weight: None,
+            optimal_placement: false,
+            uv_triangles: Vec::new(),
+            uv_weight: TMesh::ScalarType::zero(),
+        }
+    }
+}
+
+impl<TMesh: Mesh> Clone for QuadricError<TMesh> {
+    fn clone(&self) -> Self {
+        Self {
+            vertex_quadric_map: self.vertex_quadric_map.clone(),
+            vertex_uv_quadric_map: self.vertex_uv_quadric_map.clone(),
+            boundary_weight: self.boundary_weight,
+            optimal_placement: self.optimal_placement,
+            uv_triangles: self.uv_triangles.clone(),
+            uv_weight: self.uv_weight,
+        }
+    }
+}
+
+impl<TMesh: Mesh + TopologicalMesh> QuadricError<TMesh> {
+    fn add_boundary_quadrics(&mut self, mesh: &TMesh, weight: TMesh::ScalarType) {
+        for edge in mesh.edges() {
+            if !mesh.is_edge_on_boundary(&edge) {
+                continue;
+            }
+
+            let (face, _) = mesh.edge_faces(&edge);
+            let face_normal = mesh.face_positions(&face).get_normal();
+            let (v1, v2) = mesh.edge_vertices(&edge);
+            let (p1, p2) = mesh.edge_positions(&edge);
+
+            // Plane containing edge and perpendicular to face
+            let normal = (p2 - p1).cross(&face_normal);
+            let length = normal.norm();
+            if length <= TMesh::ScalarType::zero() {
+                continue;
+            }
+
+            let n = normal / length;
+            let p = Vector4::new(n.x, n.y, n.z, -n.dot(&p1));
+            let quadric = p * p.transpose() * weight;
+
+            for vertex in [v1, v2] {
+                if let Some(q) = self.vertex_quadric_map.get_mut(&vertex) {
+                    *q += quadric;
+                }
+            }
+        }
+    }
+
+    fn add_uv_quadrics(&mut self, mesh: &TMesh) {
+        let uvs = FaceUvs::from_triangles(mesh, self.uv_triangles.iter().copied());
+
+        for face in mesh.faces() {
+            let Some(face_uvs) = uvs.face_uvs(&face) else {
+                continue;
+            };
+
+            let triangle = mesh.face_positions(&face);
+            let Some(quadric) = uv_quadric(&[*triangle.p1(), *triangle.p2(), *triangle.p3()], face_uvs) else {
+                continue;
+            };
+
+            let (v1, v2, v3) = mesh.face_vertices(&face);
+            for vertex in [v1, v2, v3] {
+                *self.vertex_uv_quadric_map.entry(vertex).or_insert_with(Matrix6::zeros) += quadric * self.uv_weight;
+            }
+        }
+    }
+
+    ///
+    /// Returns quadric of collapsed `edge` over position. UVs are eliminated from quadric in combined 3D+UV space
+    /// by taking UVs minimizing error at every position (Schur complement).
+    ///
+    fn edge_quadric(&self, mesh: &TMesh, edge: &TMesh::EdgeDescriptor) -> Matrix4<TMesh::ScalarType> {
+        let (v1, v2) = mesh.edge_vertices(edge);
+        let quadric = self.vertex_quadric_map[&v1] + self.vertex_quadric_map[&v2];
+
+        let uv_quadric = match (self.vertex_uv_quadric_map.get(&v1), self.vertex_uv_quadric_map.get(&v2)) {
+            (Some(q1), Some(q2)) => q1 + q2,
+            (Some(q), None) | (None, Some(q)) => *q,
+            (None, None) => return quadric,
+        };
+
+        // Position and homogeneous coordinate, UVs are 3 and 4
+        let position = [0, 1, 2, 5];
+        let q_pp = Matrix4::from_fn(|i, j| uv_quadric[(position[i], position[j])]);
+        let q_pt = Matrix4x2::from_fn(|i, j| uv_quadric[(position[i], 3 + j)]);
+        let q_tt = Matrix2::from_fn(|i, j| uv_quadric[(3 + i, 3 + j)]);
+
+        match q_tt.try_inverse() {
+            Some(q_tt_inverse) => quadric + q_pp - q_pt * q_tt_inverse * q_pt.transpose(),
+            None => quadric,
         }
     }
 }
 
+///
+/// Returns quadric over `(x, y, z, u, v, 1)` of squared differences between UVs and UVs interpolated linearly over plane of triangle.
+/// Returns `None` for degenerate triangle.
+///
+fn uv_quadric<TScalar: RealNumber>(triangle: &[Vec3<TScalar>; 3], uvs: &[Vec2<TScalar>; 3]) -> Option<Matrix6<TScalar>> {
+    let [p1, p2, p3] = triangle;
+    let normal = (p2 - p1).cross(&(p3 - p1)).try_normalize(TScalar::zero())?;
+
+    // Gradient `g` of interpolated UV is in plane of triangle: `g * p + d = uv` at corners and `g * normal = 0`
+    let (zero, one) = (TScalar::zero(), TScalar::one());
+    let system = Matrix4::from_rows(&[
+        Vector4::new(p1.x, p1.y, p1.z, one).transpose(),
+        Vector4::new(p2.x, p2.y, p2.z, one).transpose(),
+        Vector4::new(p3.x, p3.y, p3.z, one).transpose(),
+        Vector4::new(normal.x, normal.y, normal.z, zero).transpose(),
+    ]);
+    let inverse = system.try_inverse()?;
+    let mut quadric = Matrix6::zeros();
+
+    for channel in 0..2 {
+        let gradient = inverse * Vector4::new(uvs[0][channel], uvs[1][channel], uvs[2][channel], zero);
+
+        // Error of channel is `uv - g * p - d`
+        let mut error = Vector6::zeros();
+        error.fixed_rows_mut::<3>(0).copy_from(&-gradient.xyz());
+        error[3 + channel] = one;
+        error[5] = -gradient.w;
+
+        quadric += error * error.transpose();
+    }
+
+    Some(quadric)
+}
+
 impl<TMesh: Mesh + TopologicalMesh> CollapseStrategy<TMesh> for QuadricError<TMesh> {
     fn set(&mut self, mesh: &TMesh) {
         // Preallocate memory
@@ -104,6 +326,16 @@ impl<TMesh: Mesh + TopologicalMesh> CollapseStrategy<TMesh> for QuadricError<TMe
 
             self.vertex_quadric_map.insert(vertex, quadric);
         }
+
+        if let Some(weight) = self.boundary_weight {
+            self.add_boundary_quadrics(mesh, weight);
+        }
+
+        self.vertex_uv_quadric_map.clear();
+
+        if !self.uv_triangles.is_empty() {
+            self.add_uv_quadrics(mesh);
+        }
     }
 
     fn get_cost(
@@ -111,16 +343,21 @@ impl<TMesh: Mesh + TopologicalMesh> CollapseStrategy<TMesh> for QuadricError<TMe
         mesh: &TMesh,
         edge: &<TMesh as Mesh>::EdgeDescriptor,
     ) -> <TMesh as Mesh>::ScalarType {
-        let (v1, v2) = mesh.edge_vertices(edge);
+        self.get_cost_at(mesh, edge, &self.get_placement(mesh, edge))
+    }
 
-        let q1 = self.vertex_quadric_map.get(&v1).unwrap();
-        let q2 = self.vertex_quadric_map.get(&v2).unwrap();
+    fn get_cost_at(
+        &self,
+        mesh: &TMesh,
+        edge: &<TMesh as Mesh>::EdgeDescriptor,
+        at: &Vec3<<TMesh as Mesh>::ScalarType>,
+    ) -> <TMesh as Mesh>::ScalarType {
+        let quadric = self.edge_quadric(mesh, edge);
 
-        let new_position = self.get_placement(mesh, edge);
-        let v = Vector4::new(new_position.x, new_position.y, new_position.z, TMesh::ScalarType::one());
+        let v = Vector4::new(at.x, at.y, at.z, TMesh::ScalarType::one());
         let v_t = v.transpose();
 
-        (v_t * (q1 + q2) * v)[0].abs().sqrt()
+        (v_t * quadric * v)[0].abs().sqrt()
     }
 
     #[inline]
@@ -130,7 +367,25 @@ impl<TMesh: Mesh + TopologicalMesh> CollapseStrategy<TMesh> for QuadricError<TMe
         edge: &<TMesh as Mesh>::EdgeDescriptor,
     ) -> Vec3<<TMesh as Mesh>::ScalarType> {
         let (v1_pos, v2_pos) = mesh.edge_positions(edge);
-        (v1_pos + v2_pos) * TMesh::ScalarType::from_f64(0.5).unwrap()
+        let middle = (v1_pos + v2_pos) * TMesh::ScalarType::from_f64(0.5).unwrap();
+
+        if !self.optimal_placement {
+            return middle;
+        }
+
+        let quadric = self.edge_quadric(mesh, edge);
+
+        // Minimize error over offset from middle of edge, small singular values are dropped so degenerate directions keep middle
+        let a = quadric.fixed_view::<3, 3>(0, 0).into_owned();
+        let b = -quadric.fixed_view::<3, 1>(0, 3).into_owned() - a * middle;
+        let svd = a.svd(true, true);
+        let eps = svd.singular_values.max() * TMesh::ScalarType::from_f64(1e-3).unwrap();
+
+        if eps <= TMesh::ScalarType::zero() {
+            return middle;
+        }
+
+        svd.solve(&b, eps).map(|offset| middle + offset).unwrap_or(middle)
     }
 
     fn collapse_edge(&mut self, mesh: &TMesh, edge: &<TMesh as Mesh>::EdgeDescriptor) {
@@ -138,9 +393,42 @@ impl<TMesh: Mesh + TopologicalMesh> CollapseStrategy<TMesh> for QuadricError<TMe
         let new_quadric = self.vertex_quadric_map[&v1] + self.vertex_quadric_map[&v2];
         self.vertex_quadric_map.insert(v1, new_quadric);
         self.vertex_quadric_map.insert(v2, new_quadric);
+
+        let uv_quadrics = [v1, v2].map(|vertex| self.vertex_uv_quadric_map.get(&vertex).copied());
+        if let Some(new_uv_quadric) = uv_quadrics.into_iter().flatten().reduce(|q1, q2| q1 + q2) {
+            self.vertex_uv_quadric_map.insert(v1, new_uv_quadric);
+            self.vertex_uv_quadric_map.insert(v2, new_uv_quadric);
+        }
     }
 }
 
+///
+/// Defines how minimal faces count is distributed between connected components
+/// when they are decimated independently.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum ComponentBudget {
+    /// Budget of component is proportional to its number of faces
+    FacesCount,
+    /// Budget of component is proportional to its area
+    Area,
+}
+
+///
+/// Limit of distance vertices are moved by single edge collapse, see [IncrementalDecimator::max_displacement]
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum MaxDisplacement<TScalar> {
+    /// Max distance in units of mesh
+    Absolute(TScalar),
+    /// Max distance as fraction of average length of edges around collapsed edge
+    Relative(TScalar),
+}
+
 ///
 /// Incremental edge decimator.
 /// This `struct` implements incremental edge collapse algorithm.
@@ -168,6 +456,12 @@ where
     min_faces_count: usize,
     min_face_quality: TMesh::ScalarType,
     keep_boundary: bool,
+    preserve_topology: bool,
+    component_budget: Option<ComponentBudget>,
+    max_displacement: Option<MaxDisplacement<TMesh::ScalarType>>,
+    deterministic: bool,
+    pinned_points: HashSet<HashablePoint<3, TMesh::ScalarType>>,
+    vertex_errors: HashMap<HashablePoint<3, TMesh::ScalarType>, TMesh::ScalarType>,
     priority_queue: BinaryHeap<Contraction<TMesh>>,
     not_safe_collapses: Vec<Contraction<TMesh>>,
     collapse_strategy: TCollapseStrategy,
@@ -214,6 +508,15 @@ where
         self
     }
 
+    ///
+    /// Set strategy used to compute collapse costs and placements.
+    ///
+    #[inline]
+    pub fn collapse_strategy(mut self, strategy: TCollapseStrategy) -> Self {
+        self.collapse_strategy = strategy;
+        self
+    }
+
     ///
     /// Keep boundary on decimation.
     ///
@@ -224,7 +527,73 @@ where
     }
 
     ///
-    /// Decimated given `mesh`.
+    /// Set edges (given by pairs of vertices of `mesh`) that are kept by decimation, e.g. borders of face groups.
+    /// Vertices of constrained edges are not moved: edges between two of them are not collapsed, other edges touching
+    /// them are collapsed into them. Vertices are remembered by positions, so constraints also apply to components
+    /// decimated separately (see [Self::component_budget]).
+    ///
+    pub fn constrained_edges<TIter>(mut self, mesh: &TMesh, edges: TIter) -> Self
+    where
+        TIter: IntoIterator<Item = (TMesh::VertexDescriptor, TMesh::VertexDescriptor)>
+    {
+        for (v1, v2) in edges {
+            self.pinned_points.insert((*mesh.vertex_position(&v1)).into());
+            self.pinned_points.insert((*mesh.vertex_position(&v2)).into());
+        }
+
+        self
+    }
+
+    ///
+    /// Reject collapses that change topology of mesh: its genus or number of boundary loops.
+    /// Useful when decimated mesh is used for simulations. Default is `false`.
+    ///
+    #[inline]
+    pub fn preserve_topology(mut self, preserve_topology: bool) -> Self {
+        self.preserve_topology = preserve_topology;
+        self
+    }
+
+    ///
+    /// Decimate each connected component independently. Minimal faces count (see [Self::min_faces_count])
+    /// is distributed between components according to `budget`, so small parts are not decimated to nothing.
+    /// Each component keeps at least one face. Pass `None` to decimate mesh as a whole (default).
+    ///
+    /// Mesh of several components is rebuilt from decimated components, so its vertex and face descriptors are invalidated
+    /// (vertices and faces are renumbered). Mesh of single component is decimated in place.
+    ///
+    #[inline]
+    pub fn component_budget(mut self, budget: Option<ComponentBudget>) -> Self {
+        self.component_budget = budget;
+        self
+    }
+
+    ///
+    /// Limit distance from original positions of edge vertices to collapse point. Collapses exceeding the limit are deferred
+    /// and retried when neighborhood changes, e.g. relative limit grows as surrounding edges get longer.
+    /// Prevents long skinny triangles and shape drift in low error regions. Pass `None` to disable the limit (default).
+    ///
+    #[inline]
+    pub fn max_displacement(mut self, limit: Option<MaxDisplacement<TMesh::ScalarType>>) -> Self {
+        self.max_displacement = limit;
+        self
+    }
+
+    ///
+    /// Round collapse costs to [QUANTIZE_BITS](crate::helpers::utils::QUANTIZE_BITS) significant bits before they are compared
+    /// and passed to decimation criteria, so costs differing by rounding errors tie and ties are broken by edges.
+    /// Same input gives the same mesh on every platform (e.g. x86_64 and wasm32), unless rounding errors move cost across rounding step,
+    /// which is rare. Collapses of almost equal cost may be done in different order than without rounding. Default is `false`.
+    ///
+    #[inline]
+    pub fn deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    ///
+    /// Decimated given `mesh`. When [Self::component_budget] is set and mesh has several components
+    /// it is rebuilt, so descriptors of its vertices and faces are invalidated.
     ///
     /// ## Example
     /// ```ignore
@@ -235,6 +604,112 @@ where
     /// ```
     ///
     pub fn decimate(&mut self, mesh: &mut TMesh) {
+        self.vertex_errors.clear();
+
+        let budget = match self.component_budget {
+            Some(budget) => budget,
+            None => return self.decimate_mesh(mesh),
+        };
+
+        let mut components = split_components(mesh, budget);
+
+        if components.len() <= 1 {
+            return self.decimate_mesh(mesh);
+        }
+
+        let min_faces_count = self.min_faces_count;
+
+        for component in &mut components {
+            self.min_faces_count = component.min_faces_count(min_faces_count);
+            self.decimate_mesh(&mut component.mesh);
+        }
+
+        self.min_faces_count = min_faces_count;
+        *mesh = merge_components(&components);
+    }
+
+    ///
+    /// Returns error introduced by decimation at every vertex of decimated `mesh` in order of [Mesh::vertices] iterator,
+    /// e.g. to color-map simplification error and decide where to refine. Error of vertex is the largest cost of collapses
+    /// that produced it (for [QuadricError] it is accumulated quadric error), vertices not touched by collapses have zero error.
+    /// Errors are recorded by last call to [Self::decimate], vertices are matched by positions, so mesh should not be modified in between.
+    ///
+    /// ## Example
+    /// ```ignore
+    /// decimator.decimate(&mut mesh);
+    /// let errors = decimator.vertex_errors(&mesh);
+    /// let max_error = errors.iter().copied().fold(0.0, f32::max);
+    /// let colors: Vec<_> = errors.iter().map(|error| heat_color(error / max_error)).collect();
+    /// ```
+    ///
+    pub fn vertex_errors(&self, mesh: &TMesh) -> Vec<TMesh::ScalarType> {
+        mesh.vertices()
+            .map(|vertex| {
+                self.vertex_errors
+                    .get(&(*mesh.vertex_position(&vertex)).into())
+                    .copied()
+                    .unwrap_or_else(TMesh::ScalarType::zero)
+            })
+            .collect()
+    }
+
+    ///
+    /// Decimates connected components of `mesh` independently and in parallel.
+    /// Minimal faces count is distributed according to [Self::component_budget] ([ComponentBudget::FacesCount] when not set).
+    /// Vertex errors are not recorded, see [Self::vertex_errors]. Mesh of several components is rebuilt,
+    /// so descriptors of its vertices and faces are invalidated.
+    ///
+    #[cfg(feature = "rayon")]
+    pub fn decimate_par(&self, mesh: &mut TMesh)
+    where
+        TMesh: Send,
+        TCollapseStrategy: Clone + Send + Sync,
+        TEdgeDecimationCriteria: Clone + Send + Sync,
+    {
+        use rayon::prelude::*;
+
+        let budget = self.component_budget.unwrap_or(ComponentBudget::FacesCount);
+        let mut components = split_components(mesh, budget);
+
+        // Capture settings only, decimator itself is not shared between threads
+        let criteria = &self.decimation_criteria;
+        let strategy = &self.collapse_strategy;
+        let (min_faces_count, min_face_quality) = (self.min_faces_count, self.min_face_quality);
+        let (keep_boundary, preserve_topology) = (self.keep_boundary, self.preserve_topology);
+        let (max_displacement, deterministic) = (self.max_displacement, self.deterministic);
+        let pinned_points = &self.pinned_points;
+
+        let new_decimator = |min_faces_count| Self {
+            decimation_criteria: criteria.clone(),
+            min_faces_count,
+            min_face_quality,
+            keep_boundary,
+            preserve_topology,
+            component_budget: None,
+            max_displacement,
+            deterministic,
+            pinned_points: pinned_points.clone(),
+            vertex_errors: HashMap::new(),
+            priority_queue: BinaryHeap::new(),
+            not_safe_collapses: Vec::new(),
+            collapse_strategy: strategy.clone(),
+        };
+
+        if components.len() <= 1 {
+            return new_decimator(min_faces_count).decimate_mesh(mesh);
+        }
+
+        components.par_iter_mut().for_each(|component| {
+            let mut decimator = new_decimator(component.min_faces_count(min_faces_count));
+            decimator.decimate_mesh(&mut component.mesh);
+        });
+
+        *mesh = merge_components(&components);
+    }
+
+    fn decimate_mesh(&mut self, mesh: &mut TMesh) {
+        trace_span!("decimate");
+
         // Clear internals data structures
         self.priority_queue.clear();
         self.not_safe_collapses.clear();
@@ -248,7 +723,9 @@ where
     fn collapse_edges(&mut self, mesh: &mut TMesh) {
         let mut marker = mesh.marker();
 
-        let mut remaining_faces_count = mesh.faces().count();
+        let faces_count = mesh.faces().count();
+        let mut remaining_faces_count = faces_count;
+        let mut collapses_count = 0usize;
 
         while !self.priority_queue.is_empty() || !self.not_safe_collapses.is_empty() {
             // Collapse edges one by one taking them from priority queue
@@ -259,10 +736,10 @@ where
                 }
 
                 let (v1, v2) = mesh.edge_vertices(&best.edge);
-                let collapse_at = self.collapse_strategy.get_placement(mesh, &best.edge);
+                let collapse_at = self.get_placement(mesh, &best.edge);
 
                 // Skip not safe collapses
-                if !edge_collapse::is_safe(mesh, &best.edge, &collapse_at, self.min_face_quality) {
+                if !self.is_collapse_safe(mesh, &best.edge, &collapse_at) {
                     self.not_safe_collapses.push(best);
                     continue;
                 }
@@ -271,7 +748,7 @@ where
                 if marker.is_edge_marked(&best.edge) {
                     marker.mark_edge(&best.edge, false);
 
-                    best.cost = self.collapse_strategy.get_cost(mesh, &best.edge);
+                    best.cost = self.get_cost(mesh, &best.edge);
                     if self
                         .decimation_criteria
                         .should_decimate(best.cost, mesh, &best.edge)
@@ -282,6 +759,14 @@ where
                     continue;
                 }
 
+                // Criteria may depend on geometry around edge that was changed by neighboring collapses
+                if !self
+                    .decimation_criteria
+                    .should_collapse(best.cost, mesh, &best.edge, &collapse_at)
+                {
+                    continue;
+                }
+
                 // Find edges affected by collapse
                 mesh.edges_around_vertex(&v1, |edge| marker.mark_edge(edge, true));
                 mesh.edges_around_vertex(&v2, |edge| marker.mark_edge(edge, true));
@@ -298,8 +783,17 @@ where
                     remaining_faces_count -= 2;
                 }
 
+                // Collapsed vertex inherits largest error of edge vertices
+                let (p1, p2) = mesh.edge_positions(&best.edge);
+                let error = [p1, p2]
+                    .into_iter()
+                    .filter_map(|position| self.vertex_errors.remove(&position.into()))
+                    .fold(best.cost, Float::max);
+                self.vertex_errors.insert(collapse_at.into(), error);
+
                 // Collapse edge
                 mesh.collapse_edge(&best.edge, &collapse_at);
+                collapses_count += 1;
 
                 // Stop when number of remaining faces smaller than minimal
                 if remaining_faces_count <= self.min_faces_count {
@@ -315,21 +809,19 @@ where
             if !self.not_safe_collapses.is_empty() {
                 // Reinsert unsafe collapses (mb they are safe now)
                 for collapse in self.not_safe_collapses.iter() {
-                    let new_cost = self.collapse_strategy.get_cost(mesh, &collapse.edge);
-                    let (v1_pos, v2_pos) = mesh.edge_positions(&collapse.edge);
-                    let new_position =
-                        (v1_pos + v2_pos) * TMesh::ScalarType::from_f64(0.5).unwrap();
+                    // Edge was collapsed?
+                    if !mesh.edge_exist(&collapse.edge) {
+                        continue;
+                    }
+
+                    let new_cost = self.get_cost(mesh, &collapse.edge);
+                    let new_position = self.get_placement(mesh, &collapse.edge);
 
                     // Safe to collapse and have low error
                     if self
                         .decimation_criteria
                         .should_decimate(new_cost, mesh, &collapse.edge)
-                        && edge_collapse::is_safe(
-                            mesh,
-                            &collapse.edge,
-                            &new_position,
-                            self.min_face_quality,
-                        )
+                        && self.is_collapse_safe(mesh, &collapse.edge, &new_position)
                     {
                         self.priority_queue
                             .push(Contraction::new(collapse.edge, new_cost));
@@ -339,18 +831,144 @@ where
                 self.not_safe_collapses.clear();
             }
         }
+
+        trace_counters!(
+            faces_processed = faces_count,
+            collapses_done = collapses_count,
+            faces_remaining = remaining_faces_count,
+        );
+    }
+
+    #[inline]
+    fn is_collapse_safe(
+        &self,
+        mesh: &TMesh,
+        edge: &TMesh::EdgeDescriptor,
+        collapse_at: &Vec3<TMesh::ScalarType>,
+    ) -> bool {
+        edge_collapse::is_safe(mesh, edge, collapse_at, self.min_face_quality)
+            && (!self.preserve_topology || edge_collapse::is_topology_preserved(mesh, edge))
+            && !self.is_edge_pinned(mesh, edge)
+            && !self.exceeds_max_displacement(mesh, edge, collapse_at)
+    }
+
+    /// Returns collapse cost of edge, rounded in deterministic mode
+    #[inline]
+    fn get_cost(&self, mesh: &TMesh, edge: &TMesh::EdgeDescriptor) -> TMesh::ScalarType {
+        let cost = match self.pinned_placement(mesh, edge) {
+            Some(at) => self.collapse_strategy.get_cost_at(mesh, edge, &at),
+            None => self.collapse_strategy.get_cost(mesh, edge),
+        };
+
+        if self.deterministic {
+            quantize(cost)
+        } else {
+            cost
+        }
+    }
+
+    /// Returns `true` if collapse moves one of edge vertices farther than [Self::max_displacement]
+    fn exceeds_max_displacement(
+        &self,
+        mesh: &TMesh,
+        edge: &TMesh::EdgeDescriptor,
+        collapse_at: &Vec3<TMesh::ScalarType>,
+    ) -> bool {
+        let limit = match self.max_displacement {
+            Some(MaxDisplacement::Absolute(distance)) => distance,
+            Some(MaxDisplacement::Relative(ratio)) => {
+                let (v1, v2) = mesh.edge_vertices(edge);
+                let mut length = TMesh::ScalarType::zero();
+                let mut count = 0;
+
+                for vertex in [v1, v2] {
+                    mesh.edges_around_vertex(&vertex, |edge| {
+                        length += mesh.edge_length(edge);
+                        count += 1;
+                    });
+                }
+
+                ratio * length / TMesh::ScalarType::from_usize(count).unwrap()
+            }
+            None => return false,
+        };
+
+        let (p1, p2) = mesh.edge_positions(edge);
+        (p1 - collapse_at).norm() > limit || (p2 - collapse_at).norm() > limit
+    }
+
+    ///
+    /// Returns `true` if collapse of edge breaks constrained edges: both its vertices belong to constrained edges,
+    /// so one of them would be moved, or collapse removes face whose other edges are on boundary and one of them is constrained.
+    ///
+    fn is_edge_pinned(&self, mesh: &TMesh, edge: &TMesh::EdgeDescriptor) -> bool {
+        if self.pinned_points.is_empty() {
+            return false;
+        }
+
+        let is_pinned = |position: Vec3<TMesh::ScalarType>| self.pinned_points.contains(&position.into());
+        let (p1, p2) = mesh.edge_positions(edge);
+
+        match (is_pinned(p1), is_pinned(p2)) {
+            (true, true) => return true,
+            (false, false) => return false,
+            _ => {}
+        }
+
+        let (v1, v2) = mesh.edge_vertices(edge);
+        let (face1, face2) = mesh.edge_faces(edge);
+
+        [Some(face1), face2].into_iter().flatten().any(|face| {
+            let (e1, e2, e3) = mesh.face_edges(&face);
+            let other_edges: Vec<_> = [e1, e2, e3]
+                .into_iter()
+                .filter(|other| {
+                    let (u1, u2) = mesh.edge_vertices(other);
+                    !(u1 == v1 && u2 == v2 || u1 == v2 && u2 == v1)
+                })
+                .collect();
+
+            other_edges.iter().all(|other| mesh.is_edge_on_boundary(other))
+                && other_edges.iter().any(|other| {
+                    let (q1, q2) = mesh.edge_positions(other);
+                    is_pinned(q1) && is_pinned(q2)
+                })
+        })
+    }
+
+    /// Returns collapse point of edge
+    #[inline]
+    fn get_placement(&self, mesh: &TMesh, edge: &TMesh::EdgeDescriptor) -> Vec3<TMesh::ScalarType> {
+        self.pinned_placement(mesh, edge)
+            .unwrap_or_else(|| self.collapse_strategy.get_placement(mesh, edge))
+    }
+
+    /// Returns position of vertex of constrained edge touched by edge, edge is collapsed into that vertex
+    #[inline]
+    fn pinned_placement(&self, mesh: &TMesh, edge: &TMesh::EdgeDescriptor) -> Option<Vec3<TMesh::ScalarType>> {
+        if self.pinned_points.is_empty() {
+            return None;
+        }
+
+        let (v1, v2) = mesh.edge_positions(edge);
+        [v1, v2].into_iter().find(|position| self.pinned_points.contains(&(*position).into()))
     }
 
     /// Fill priority queue with edges of original mesh that have low collapse cost and can be collapsed
     fn fill_queue(&mut self, mesh: &mut TMesh) {
         for edge in mesh.edges() {
-            let cost = self.collapse_strategy.get_cost(mesh, &edge);
-            let is_collapse_topologically_safe = edge_collapse::is_topologically_safe(mesh, &edge);
+            let cost = self.get_cost(mesh, &edge);
+            let is_collapse_topologically_safe = edge_collapse::is_topologically_safe(mesh, &edge)
+                && (!self.preserve_topology || edge_collapse::is_topology_preserved(mesh, &edge));
 
             if self.keep_boundary && edge_collapse::will_collapse_affect_boundary(mesh, &edge) {
                 continue;
             }
 
+            if self.is_edge_pinned(mesh, &edge) {
+                continue;
+            }
+
             // Collapsable and low cost?
             if self.decimation_criteria.should_decimate(cost, mesh, &edge)
                 && is_collapse_topologically_safe
@@ -374,6 +992,12 @@ where
             min_faces_count: 0,
             min_face_quality: cast(0.1).unwrap(),
             keep_boundary: false,
+            preserve_topology: false,
+            component_budget: None,
+            max_displacement: None,
+            deterministic: false,
+            pinned_points: HashSet::new(),
+            vertex_errors: HashMap::new(),
             priority_queue: BinaryHeap::new(),
             not_safe_collapses: Vec::new(),
             collapse_strategy: TCollapseStrategy::default(),
@@ -381,6 +1005,115 @@ where
     }
 }
 
+/// Connected component of mesh and its share of faces budget
+pub(crate) struct Component<TMesh: Mesh> {
+    pub(crate) mesh: TMesh,
+    pub(crate) share: TMesh::ScalarType,
+}
+
+impl<TMesh: Mesh> Component<TMesh> {
+    #[inline]
+    fn min_faces_count(&self, total: usize) -> usize {
+        if total == 0 {
+            return 0;
+        }
+
+        let count = TMesh::ScalarType::from_usize(total).unwrap() * self.share;
+        Float::ceil(count).to_usize().unwrap_or(1).max(1)
+    }
+}
+
+/// Splits `mesh` into connected components, shares of components are computed according to `budget`
+pub(crate) fn split_components<TMesh: Mesh>(mesh: &TMesh, budget: ComponentBudget) -> Vec<Component<TMesh>> {
+    fn find<T: Copy + Eq + Hash>(parent: &mut HashMap<T, T>, v: T) -> T {
+        let mut root = v;
+        while let Some(p) = parent.get(&root).copied().filter(|p| *p != root) {
+            root = p;
+        }
+
+        parent.insert(v, root);
+        root
+    }
+
+    // Union-find over vertices of faces
+    let mut parent = HashMap::new();
+
+    for face in mesh.faces() {
+        let (v1, v2, v3) = mesh.face_vertices(&face);
+        let r1 = find(&mut parent, v1);
+        let r2 = find(&mut parent, v2);
+        let r3 = find(&mut parent, v3);
+        parent.insert(r2, r1);
+        parent.insert(r3, r1);
+    }
+
+    let mut component_index = HashMap::new();
+    let mut components = Vec::new();
+    let mut vertex_index = HashMap::new();
+
+    for face in mesh.faces() {
+        let (v1, v2, v3) = mesh.face_vertices(&face);
+        let root = find(&mut parent, v1);
+        let index = *component_index.entry(root).or_insert_with(|| {
+            components.push((Vec::new(), Vec::new(), TMesh::ScalarType::zero()));
+            components.len() - 1
+        });
+
+        let (vertices, indices, weight) = &mut components[index];
+
+        for vertex in [v1, v2, v3] {
+            let vertex_index = *vertex_index.entry(vertex).or_insert_with(|| {
+                vertices.push(*mesh.vertex_position(&vertex));
+                vertices.len() - 1
+            });
+            indices.push(vertex_index);
+        }
+
+        *weight += match budget {
+            ComponentBudget::FacesCount => TMesh::ScalarType::one(),
+            ComponentBudget::Area => {
+                let (p1, p2, p3) = (mesh.vertex_position(&v1), mesh.vertex_position(&v2), mesh.vertex_position(&v3));
+                Triangle3::area(p1, p2, p3)
+            }
+        };
+    }
+
+    let total_weight = components.iter().fold(TMesh::ScalarType::zero(), |total, (_, _, weight)| total + *weight);
+
+    components
+        .into_iter()
+        .map(|(vertices, indices, weight)| Component {
+            mesh: TMesh::from_vertices_and_indices(&vertices, &indices),
+            share: if total_weight > TMesh::ScalarType::zero() { weight / total_weight } else { TMesh::ScalarType::zero() },
+        })
+        .collect()
+}
+
+/// Merges components back into single mesh
+pub(crate) fn merge_components<TMesh: Mesh>(components: &[Component<TMesh>]) -> TMesh {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+
+    for component in components {
+        let mesh = &component.mesh;
+        let mut vertex_index = HashMap::new();
+
+        for face in mesh.faces() {
+            let (v1, v2, v3) = mesh.face_vertices(&face);
+
+            for vertex in [v1, v2, v3] {
+                let index = *vertex_index.entry(vertex).or_insert_with(|| {
+                    vertices.push(*mesh.vertex_position(&vertex));
+                    vertices.len() - 1
+                });
+                indices.push(index);
+            }
+        }
+    }
+
+    TMesh::from_vertices_and_indices(&vertices, &indices)
+}
+
 ///
 /// Trait used to decide whether to decimate an edge
 ///
@@ -391,12 +1124,25 @@ pub trait EdgeDecimationCriteria<TMesh: Mesh>: Default {
         mesh: &TMesh,
         edge: &TMesh::EdgeDescriptor,
     ) -> bool;
+
+    /// Checked right before `edge` is collapsed to `collapse_at` (see [CollapseStrategy::get_placement]),
+    /// criteria that depend on resulting geometry should check it here. Defaults to [Self::should_decimate].
+    #[inline]
+    fn should_collapse(
+        &self,
+        error: TMesh::ScalarType,
+        mesh: &TMesh,
+        edge: &TMesh::EdgeDescriptor,
+        _collapse_at: &Vec3<TMesh::ScalarType>,
+    ) -> bool {
+        self.should_decimate(error, mesh, edge)
+    }
 }
 
 ///
 /// Always decimate edges
 ///
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy)]
 pub struct AlwaysDecimate;
 
 impl<TMesh: Mesh> EdgeDecimationCriteria<TMesh> for AlwaysDecimate {
@@ -414,7 +1160,7 @@ impl<TMesh: Mesh> EdgeDecimationCriteria<TMesh> for AlwaysDecimate {
 ///
 /// Never decimate edges
 ///
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone, Copy)]
 pub struct NeverDecimate;
 
 impl<TMesh: Mesh> EdgeDecimationCriteria<TMesh> for NeverDecimate {
@@ -462,6 +1208,12 @@ where
     }
 }
 
+impl<TMesh: Mesh> Clone for ConstantErrorDecimationCriteria<TMesh> {
+    fn clone(&self) -> Self {
+        Self::new(self.max_error)
+    }
+}
+
 impl<TMesh> Default for ConstantErrorDecimationCriteria<TMesh>
 where
     TMesh: Mesh,
@@ -517,6 +1269,15 @@ impl<TMesh: Mesh> EdgeDecimationCriteria<TMesh> for BoundingSphereDecimationCrit
     }
 }
 
+impl<TMesh: Mesh> Clone for BoundingSphereDecimationCriteria<TMesh> {
+    fn clone(&self) -> Self {
+        Self {
+            origin: self.origin,
+            radii_sq_error_map: self.radii_sq_error_map.clone(),
+        }
+    }
+}
+
 impl<TMesh> Default for BoundingSphereDecimationCriteria<TMesh>
 where
     TMesh: Mesh,
@@ -528,3 +1289,647 @@ where
         Self::new(origin, radii_error)
     }
 }
+
+///
+/// Bounds the deviation of decimated mesh from the original surface.
+/// Edge is collapsed only when the faces created by collapse stay within `max_distance` from the original mesh.
+/// Deviation is an approximation of one-sided Hausdorff distance from the decimated mesh to the original one,
+/// it is measured at collapse point (see [CollapseStrategy::get_placement]), centroids and edge midpoints of the new faces.
+/// Deviation is checked only right before collapse (see [EdgeDecimationCriteria::should_collapse]),
+/// [EdgeDecimationCriteria::should_decimate] accepts every edge as collapse point is not known there.
+///
+/// ## Example
+/// ```ignore
+/// let criteria = HausdorffDistanceDecimationCriteria::new(&mesh, 0.01);
+/// let mut decimator = EdgeDecimator::new().decimation_criteria(criteria);
+/// decimator.decimate(&mut mesh);
+/// ```
+///
+pub struct HausdorffDistanceDecimationCriteria<TMesh: Mesh> {
+    original: Grid<Triangle3<TMesh::ScalarType>>,
+    max_distance: TMesh::ScalarType,
+}
+
+impl<TMesh: Mesh> HausdorffDistanceDecimationCriteria<TMesh> {
+    /// Creates criteria from `original` surface and max allowed deviation from it
+    pub fn new(original: &TMesh, max_distance: TMesh::ScalarType) -> Self {
+        let original = if original.faces().next().is_some() {
+            Grid::from_mesh(original)
+        } else {
+            Grid::empty()
+        };
+
+        Self {
+            original,
+            max_distance,
+        }
+    }
+
+    #[inline]
+    fn is_within_tolerance(&self, point: &Vec3<TMesh::ScalarType>) -> bool {
+        match self.original.closest_point(point, self.max_distance) {
+            Some(closest) => (closest - point).norm_squared() <= self.max_distance * self.max_distance,
+            None => false,
+        }
+    }
+}
+
+impl<TMesh> EdgeDecimationCriteria<TMesh> for HausdorffDistanceDecimationCriteria<TMesh>
+where
+    TMesh: Mesh + TopologicalMesh,
+{
+    #[inline]
+    fn should_decimate(
+        &self,
+        _error: <TMesh as Mesh>::ScalarType,
+        _mesh: &TMesh,
+        _edge: &<TMesh as Mesh>::EdgeDescriptor,
+    ) -> bool {
+        // Nothing to measure deviation against
+        !self.original.cells.is_empty()
+    }
+
+    fn should_collapse(
+        &self,
+        error: <TMesh as Mesh>::ScalarType,
+        mesh: &TMesh,
+        edge: &<TMesh as Mesh>::EdgeDescriptor,
+        collapse_at: &Vec3<TMesh::ScalarType>,
+    ) -> bool {
+        if !self.should_decimate(error, mesh, edge) || !self.is_within_tolerance(collapse_at) {
+            return false;
+        }
+
+        let (v1, v2) = mesh.edge_vertices(edge);
+
+        let half = TMesh::ScalarType::from_f64(0.5).unwrap();
+        let third = TMesh::ScalarType::from_f64(1.0 / 3.0).unwrap();
+        let mut within_tolerance = true;
+
+        for vertex in [v1, v2] {
+            mesh.faces_around_vertex(&vertex, |face| {
+                if !within_tolerance {
+                    return;
+                }
+
+                let (a, b, c) = mesh.face_vertices(face);
+                let face_vertices = [a, b, c];
+
+                // Faces incident to collapsed edge are removed
+                if face_vertices.contains(&v1) && face_vertices.contains(&v2) {
+                    return;
+                }
+
+                // Wing vertices of new face
+                let mut wing = face_vertices
+                    .iter()
+                    .filter(|v| **v != vertex)
+                    .map(|v| *mesh.vertex_position(v));
+                let p1 = wing.next().unwrap();
+                let p2 = wing.next().unwrap();
+
+                within_tolerance = self.is_within_tolerance(&((collapse_at + p1) * half))
+                    && self.is_within_tolerance(&((collapse_at + p2) * half))
+                    && self.is_within_tolerance(&((collapse_at + p1 + p2) * third));
+            });
+        }
+
+        within_tolerance
+    }
+}
+
+impl<TMesh: Mesh> Default for HausdorffDistanceDecimationCriteria<TMesh> {
+    fn default() -> Self {
+        Self {
+            original: Grid::empty(),
+            max_distance: cast(0.001).unwrap(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{
+        split_components, AlwaysDecimate, CollapseStrategy, ComponentBudget, ConstantErrorDecimationCriteria, EdgeDecimationCriteria,
+        HausdorffDistanceDecimationCriteria, IncrementalDecimator, MaxDisplacement, QuadricError,
+    };
+    use crate::{
+        decimation::prelude::EdgeDecimator,
+        helpers::aliases::{Vec2, Vec3f},
+        mesh::{
+            corner_table::prelude::CornerTableF,
+            face_uvs::FaceUvs,
+            primitives,
+            traits::{EditableMesh, Mesh, TopologicalMesh},
+        },
+    };
+
+    fn create_grid_mesh(size: usize, height: impl Fn(f32, f32) -> f32) -> CornerTableF {
+        let step = 1.0 / size as f32;
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+
+        for i in 0..=size {
+            for j in 0..=size {
+                let x = i as f32 * step;
+                let y = j as f32 * step;
+                vertices.push(Vec3f::new(x, y, height(x, y)));
+            }
+        }
+
+        for i in 0..size {
+            for j in 0..size {
+                let v0 = i * (size + 1) + j;
+                let v1 = v0 + size + 1;
+                indices.extend_from_slice(&[v0, v1, v1 + 1, v0, v1 + 1, v0 + 1]);
+            }
+        }
+
+        CornerTableF::from_vertices_and_indices(&vertices, &indices)
+    }
+
+    #[test]
+    fn test_hausdorff_criteria_decimates_flat_surface() {
+        let mut mesh = create_grid_mesh(10, |_, _| 0.0);
+        let faces_before = mesh.faces().count();
+
+        let criteria = HausdorffDistanceDecimationCriteria::new(&mesh, 0.001);
+        let mut decimator = EdgeDecimator::new().decimation_criteria(criteria);
+        decimator.decimate(&mut mesh);
+
+        assert!(mesh.faces().count() < faces_before / 2);
+    }
+
+    #[test]
+    fn test_hausdorff_criteria_bounds_deviation() {
+        let bump = |x: f32, y: f32| 0.2 * (x * std::f32::consts::PI).sin() * (y * std::f32::consts::PI).sin();
+        let original = create_grid_mesh(20, bump);
+        let mut mesh = create_grid_mesh(20, bump);
+
+        let max_distance = 0.005;
+        let criteria = HausdorffDistanceDecimationCriteria::new(&original, max_distance);
+        let mut decimator = EdgeDecimator::new().decimation_criteria(criteria);
+        decimator.decimate(&mut mesh);
+
+        assert!(mesh.faces().count() < original.faces().count());
+
+        let check = HausdorffDistanceDecimationCriteria::new(&original, max_distance * 1.01);
+        for vertex in mesh.vertices() {
+            assert!(check.is_within_tolerance(mesh.vertex_position(&vertex)));
+        }
+
+        for face in mesh.faces() {
+            let center = mesh.face_positions(&face).center();
+            assert!(check.is_within_tolerance(&center));
+        }
+    }
+
+    #[test]
+    fn test_hausdorff_criteria_with_optimal_placement() {
+        let bump = |x: f32, y: f32| 0.2 * (x * std::f32::consts::PI).sin() * (y * std::f32::consts::PI).sin();
+        let original = create_grid_mesh(20, bump);
+        let mut mesh = create_grid_mesh(20, bump);
+
+        let max_distance = 0.005;
+        let criteria = HausdorffDistanceDecimationCriteria::new(&original, max_distance);
+
+        // Deviation is measured at given collapse point, not at middle of edge
+        let edge = original.edges().next().unwrap();
+        let (p1, p2) = original.edge_positions(&edge);
+        let middle = (p1 + p2) * 0.5;
+        let off_surface = middle + Vec3f::new(0.0, 0.0, max_distance * 2.0);
+        assert!(criteria.should_collapse(0.0, &original, &edge, &middle));
+        assert!(!criteria.should_collapse(0.0, &original, &edge, &off_surface));
+
+        let mut decimator = EdgeDecimator::new()
+            .decimation_criteria(criteria)
+            .collapse_strategy(QuadricError::new().with_optimal_placement(true));
+        decimator.decimate(&mut mesh);
+
+        assert!(mesh.faces().count() < original.faces().count());
+
+        let check = HausdorffDistanceDecimationCriteria::new(&original, max_distance * 1.01);
+        for vertex in mesh.vertices() {
+            assert!(check.is_within_tolerance(mesh.vertex_position(&vertex)));
+        }
+
+        for face in mesh.faces() {
+            let center = mesh.face_positions(&face).center();
+            assert!(check.is_within_tolerance(&center));
+        }
+    }
+
+    #[test]
+    fn test_default_hausdorff_criteria_never_decimates() {
+        let mesh = create_grid_mesh(2, |_, _| 0.0);
+        let criteria = HausdorffDistanceDecimationCriteria::<CornerTableF>::default();
+
+        for edge in mesh.edges() {
+            assert!(!criteria.should_decimate(0.0, &mesh, &edge));
+        }
+    }
+
+    fn euler_characteristic(mesh: &CornerTableF) -> isize {
+        mesh.vertices().count() as isize - mesh.edges().count() as isize + mesh.faces().count() as isize
+    }
+
+    fn boundary_edges_count(mesh: &CornerTableF) -> usize {
+        mesh.edges().filter(|edge| mesh.is_edge_on_boundary(edge)).count()
+    }
+
+    /// Number of boundary loops. Panics when boundary is not manifold.
+    fn boundary_loops_count(mesh: &CornerTableF) -> usize {
+        let mut parent = HashMap::new();
+        let mut boundary_valence = HashMap::new();
+
+        fn find(parent: &mut HashMap<usize, usize>, v: usize) -> usize {
+            let p = *parent.entry(v).or_insert(v);
+            if p == v {
+                return v;
+            }
+
+            let root = find(parent, p);
+            parent.insert(v, root);
+            root
+        }
+
+        for edge in mesh.edges().filter(|edge| mesh.is_edge_on_boundary(edge)) {
+            let (v1, v2) = mesh.edge_vertices(&edge);
+            *boundary_valence.entry(v1).or_insert(0) += 1;
+            *boundary_valence.entry(v2).or_insert(0) += 1;
+
+            let r1 = find(&mut parent, v1);
+            let r2 = find(&mut parent, v2);
+            parent.insert(r1, r2);
+        }
+
+        assert!(boundary_valence.values().all(|valence| *valence == 2), "Boundary is not manifold");
+
+        let vertices: Vec<_> = parent.keys().copied().collect();
+        vertices
+            .into_iter()
+            .filter(|v| find(&mut parent, *v) == *v)
+            .count()
+    }
+
+    #[test]
+    fn test_preserve_topology_of_closed_mesh() {
+        let mut mesh: CornerTableF = primitives::cylinder(1.0, 2.0, 8);
+        let faces_count = mesh.faces().count();
+
+        let mut decimator = EdgeDecimator::<_, AlwaysDecimate>::new().preserve_topology(true);
+        decimator.decimate(&mut mesh);
+
+        // Smallest closed manifold is tetrahedron
+        assert!(mesh.faces().count() < faces_count);
+        assert!(mesh.faces().count() >= 4);
+        assert_eq!(euler_characteristic(&mesh), 2);
+        assert_eq!(boundary_edges_count(&mesh), 0);
+    }
+
+    #[test]
+    fn test_preserve_topology_of_mesh_with_boundary() {
+        let mut mesh: CornerTableF = primitives::plane(1.0, 1.0, 6, 6);
+
+        let mut decimator = EdgeDecimator::<_, AlwaysDecimate>::new().preserve_topology(true);
+        decimator.decimate(&mut mesh);
+
+        assert!(mesh.faces().count() < 72);
+        assert_eq!(euler_characteristic(&mesh), 1);
+        assert_eq!(boundary_loops_count(&mesh), 1);
+    }
+
+    /// Large plane and small plane next to it
+    fn two_planes() -> CornerTableF {
+        let large: CornerTableF = primitives::plane(1.0, 1.0, 10, 10);
+        let small: CornerTableF = primitives::plane(0.2, 0.2, 2, 2);
+
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+
+        for (mesh, offset) in [(&large, Vec3f::zeros()), (&small, Vec3f::new(2.0, 0.0, 0.0))] {
+            let mut index = HashMap::new();
+            for face in mesh.faces() {
+                let (v1, v2, v3) = mesh.face_vertices(&face);
+                for vertex in [v1, v2, v3] {
+                    let i = *index.entry(vertex).or_insert_with(|| {
+                        vertices.push(mesh.vertex_position(&vertex) + offset);
+                        vertices.len() - 1
+                    });
+                    indices.push(i);
+                }
+            }
+        }
+
+        CornerTableF::from_vertices_and_indices(&vertices, &indices)
+    }
+
+    #[test]
+    fn test_decimate_components_with_budget() {
+        let mut mesh = two_planes();
+        let faces_count = mesh.faces().count();
+        let min_faces_count = faces_count / 2;
+
+        let mut decimator = EdgeDecimator::<_, AlwaysDecimate>::new()
+            .min_faces_count(Some(min_faces_count))
+            .component_budget(Some(ComponentBudget::FacesCount));
+        decimator.decimate(&mut mesh);
+
+        assert!(mesh.faces().count() < faces_count);
+
+        // Each component keeps its share of faces
+        let components = split_components(&mesh, ComponentBudget::FacesCount);
+        assert_eq!(components.len(), 2);
+
+        let small = components.iter().map(|c| c.mesh.faces().count()).min().unwrap();
+        assert!(small >= (min_faces_count * 8).div_ceil(faces_count));
+    }
+
+    #[test]
+    fn test_decimate_single_component_in_place() {
+        let criteria = || ConstantErrorDecimationCriteria::new(0.01);
+        let bump = |x: f32, y: f32| 0.05 * (x * 7.0).sin() * (y * 5.0).cos();
+        let mut mesh = create_grid_mesh(16, bump);
+        let mut expected = create_grid_mesh(16, bump);
+
+        EdgeDecimator::new().decimation_criteria(criteria()).decimate(&mut expected);
+        EdgeDecimator::new()
+            .decimation_criteria(criteria())
+            .component_budget(Some(ComponentBudget::Area))
+            .decimate(&mut mesh);
+
+        // Mesh is not rebuilt, so surviving vertices keep their descriptors
+        assert!(mesh.faces().count() < 16 * 16 * 2);
+        assert!(mesh.vertices().eq(expected.vertices()));
+        assert!(mesh.vertices().all(|v| mesh.vertex_position(&v) == expected.vertex_position(&v)));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_decimate_components_in_parallel() {
+        let mut mesh = two_planes();
+        let faces_count = mesh.faces().count();
+
+        let decimator = EdgeDecimator::<_, AlwaysDecimate>::new()
+            .min_faces_count(Some(faces_count / 2))
+            .component_budget(Some(ComponentBudget::Area));
+        decimator.decimate_par(&mut mesh);
+
+        assert!(mesh.faces().count() < faces_count);
+        assert_eq!(split_components(&mesh, ComponentBudget::Area).len(), 2);
+    }
+
+    /// Max distance from boundary vertices of mesh to sides of unit square centered at origin
+    fn boundary_deviation(mesh: &CornerTableF) -> f32 {
+        mesh.edges()
+            .filter(|edge| mesh.is_edge_on_boundary(edge))
+            .flat_map(|edge| {
+                let (p1, p2) = mesh.edge_positions(&edge);
+                [p1, p2]
+            })
+            .map(|p| 0.5 - p.x.abs().max(p.y.abs()))
+            .fold(0.0, f32::max)
+    }
+
+    #[test]
+    fn test_uv_quadrics() {
+        let decimate = |uv: fn(&Vec3f) -> Vec2<f32>| {
+            let mut mesh = create_grid_mesh(16, |_, _| 0.0);
+            let mut uvs = FaceUvs::new();
+
+            for face in mesh.faces() {
+                let (v1, v2, v3) = mesh.face_vertices(&face);
+                uvs.set_face_uvs(face, [v1, v2, v3].map(|v| uv(mesh.vertex_position(&v))));
+            }
+
+            let mut decimator = EdgeDecimator::new()
+                .decimation_criteria(ConstantErrorDecimationCriteria::new(0.01))
+                .collapse_strategy(QuadricError::new().with_uvs(&mesh, &uvs, 1.0));
+            decimator.decimate(&mut mesh);
+            mesh.faces().count()
+        };
+
+        // UVs interpolated linearly over plane have no error, so plane is decimated as without UVs
+        let mut plane = create_grid_mesh(16, |_, _| 0.0);
+        EdgeDecimator::new()
+            .decimation_criteria(ConstantErrorDecimationCriteria::new(0.01))
+            .decimate(&mut plane);
+
+        let linear = decimate(|p| Vec2::new(p.x * 0.5, p.y));
+        assert_eq!(linear, plane.faces().count());
+
+        // Collapses stretching texture are rejected
+        let stretched = decimate(|p| Vec2::new(p.x * p.x, p.y));
+        assert!(stretched > linear);
+        assert!(stretched < 16 * 16 * 2);
+    }
+
+    #[test]
+    fn test_boundary_quadrics() {
+        let bump = |x: f32, y: f32| 0.05 * (x * 7.0).sin() * (y * 5.0).cos();
+        let mut mesh = create_grid_mesh(16, bump);
+        let mut constrained = create_grid_mesh(16, bump);
+
+        // Move grid so that it is centered at origin
+        for m in [&mut mesh, &mut constrained] {
+            let vertices: Vec<_> = m.vertices().collect();
+            for v in vertices {
+                let p = *m.vertex_position(&v);
+                m.shift_vertex(&v, &(p - Vec3f::new(0.5, 0.5, 0.0)));
+            }
+        }
+
+        let criteria = || ConstantErrorDecimationCriteria::new(0.01);
+        let mut decimator = EdgeDecimator::new().decimation_criteria(criteria());
+        decimator.decimate(&mut mesh);
+
+        let mut decimator = EdgeDecimator::new()
+            .decimation_criteria(criteria())
+            .collapse_strategy(QuadricError::new().with_boundary_weight(Some(100.0)));
+        decimator.decimate(&mut constrained);
+
+        // Interior is still decimated, but boundary stays on sides of square
+        assert!(constrained.faces().count() < 16 * 16 * 2);
+        assert!(boundary_deviation(&constrained) < 1e-5);
+        assert!(boundary_deviation(&mesh) > boundary_deviation(&constrained));
+    }
+
+    #[test]
+    fn test_constrained_edges() {
+        let mesh = create_grid_mesh(16, |_, _| 0.0);
+
+        // Vertical line x = 0.5 in the middle of grid
+        let borders: Vec<_> = mesh
+            .edges()
+            .map(|edge| mesh.edge_vertices(&edge))
+            .filter(|(v1, v2)| mesh.vertex_position(v1).x == 0.5 && mesh.vertex_position(v2).x == 0.5)
+            .collect();
+        assert_eq!(borders.len(), 16);
+
+        for budget in [None, Some(ComponentBudget::FacesCount)] {
+            let mut decimated = create_grid_mesh(16, |_, _| 0.0);
+            let mut decimator = EdgeDecimator::new()
+                .decimation_criteria(ConstantErrorDecimationCriteria::new(0.01))
+                .component_budget(budget)
+                .constrained_edges(&mesh, borders.iter().copied());
+            decimator.decimate(&mut decimated);
+
+            // Line is kept as is, rest of mesh is decimated
+            let line_edges = decimated
+                .edges()
+                .filter(|edge| {
+                    let (p1, p2) = decimated.edge_positions(edge);
+                    p1.x == 0.5 && p2.x == 0.5
+                })
+                .count();
+            assert_eq!(line_edges, 16);
+            assert!(decimated.faces().count() < 16 * 16);
+        }
+    }
+
+    #[test]
+    fn test_edges_touching_constrained_edges_are_decimated() {
+        let mesh = create_grid_mesh(16, |_, _| 0.0);
+        let center = Vec3f::new(0.5, 0.5, 0.0);
+
+        // Loop of constrained edges around vertex in the middle of grid
+        let center_vertex = mesh.vertices().find(|vertex| *mesh.vertex_position(vertex) == center).unwrap();
+        let mut ring = Vec::new();
+        mesh.vertices_around_vertex(&center_vertex, |vertex| ring.push(*vertex));
+        let borders: Vec<_> = mesh
+            .edges()
+            .map(|edge| mesh.edge_vertices(&edge))
+            .filter(|(v1, v2)| ring.contains(v1) && ring.contains(v2))
+            .collect();
+        assert_eq!(borders.len(), 6);
+
+        let mut decimated = create_grid_mesh(16, |_, _| 0.0);
+        let mut decimator = EdgeDecimator::new()
+            .decimation_criteria(ConstantErrorDecimationCriteria::new(0.01))
+            .constrained_edges(&mesh, borders.iter().copied());
+        decimator.decimate(&mut decimated);
+
+        // Vertex inside of loop is collapsed into it, loop itself is kept
+        let positions: Vec<_> = decimated.vertices().map(|vertex| *decimated.vertex_position(&vertex)).collect();
+        assert!(!positions.contains(&center));
+
+        for (v1, v2) in &borders {
+            let (p1, p2) = (mesh.vertex_position(v1), mesh.vertex_position(v2));
+            assert!(decimated.edges().any(|edge| {
+                let (q1, q2) = decimated.edge_positions(&edge);
+                (q1 == *p1 && q2 == *p2) || (q1 == *p2 && q2 == *p1)
+            }));
+        }
+    }
+
+    #[test]
+    fn test_max_displacement() {
+        let bump = |x: f32, y: f32| 0.1 * (x * 3.0).sin() * (y * 2.0).cos();
+        let max_edge_length = |mesh: &CornerTableF| mesh.edges().map(|edge| mesh.edge_length(&edge)).fold(0.0, f32::max);
+        let max_deviation = |mesh: &CornerTableF| {
+            mesh.vertices()
+                .map(|vertex| {
+                    let p = mesh.vertex_position(&vertex);
+                    (p.z - bump(p.x, p.y)).abs()
+                })
+                .fold(0.0, f32::max)
+        };
+        let decimated = |strategy: QuadricError<CornerTableF>, limit: Option<MaxDisplacement<f32>>| {
+            let mut mesh = create_grid_mesh(16, bump);
+            let mut decimator = EdgeDecimator::new()
+                .decimation_criteria(ConstantErrorDecimationCriteria::new(0.01))
+                .collapse_strategy(strategy)
+                .max_displacement(limit);
+            decimator.decimate(&mut mesh);
+            mesh
+        };
+
+        let free = decimated(QuadricError::new(), None);
+        let optimal = decimated(QuadricError::new().with_optimal_placement(true), None);
+        assert!(optimal.faces().count() <= free.faces().count());
+        assert!(max_deviation(&optimal) < max_deviation(&free));
+
+        // Capped collapses keep triangles shorter and closer to surface
+        for limit in [MaxDisplacement::Absolute(0.05), MaxDisplacement::Relative(0.5)] {
+            let capped = decimated(QuadricError::new(), Some(limit));
+            assert!(capped.faces().count() < 16 * 16 * 2);
+            assert!(max_edge_length(&capped) < max_edge_length(&free));
+            assert!(max_deviation(&capped) < max_deviation(&free));
+        }
+
+        // Limit below half of shortest edge rejects every collapse
+        let rejected = decimated(QuadricError::new(), Some(MaxDisplacement::Absolute(0.01)));
+        assert_eq!(rejected.faces().count(), 16 * 16 * 2);
+    }
+
+    type EdgeF = <CornerTableF as Mesh>::EdgeDescriptor;
+
+    /// Quadric error with costs of some edges off by rounding error, like costs computed on other platform
+    #[derive(Default)]
+    struct RoundedQuadricError(QuadricError<CornerTableF>);
+
+    impl CollapseStrategy<CornerTableF> for RoundedQuadricError {
+        fn set(&mut self, mesh: &CornerTableF) {
+            self.0.set(mesh);
+        }
+
+        fn get_cost(&self, mesh: &CornerTableF, edge: &EdgeF) -> f32 {
+            let cost = self.0.get_cost(mesh, edge);
+            let (v1, v2) = mesh.edge_vertices(edge);
+            if v1.min(v2) % 3 == 0 { cost * (1.0 + 4.0 * f32::EPSILON) } else { cost }
+        }
+
+        fn get_placement(&self, mesh: &CornerTableF, edge: &EdgeF) -> Vec3f {
+            self.0.get_placement(mesh, edge)
+        }
+
+        fn collapse_edge(&mut self, mesh: &CornerTableF, edge: &EdgeF) {
+            self.0.collapse_edge(mesh, edge);
+        }
+    }
+
+    #[test]
+    fn test_deterministic_decimation() {
+        fn decimated<TStrategy: CollapseStrategy<CornerTableF>>(deterministic: bool) -> Vec<(usize, usize, usize)> {
+            let mut mesh = create_grid_mesh(16, |x, _| x * x);
+            IncrementalDecimator::<CornerTableF, TStrategy, AlwaysDecimate>::new()
+                .min_faces_count(Some(200))
+                .deterministic(deterministic)
+                .decimate(&mut mesh);
+
+            mesh.faces().map(|face| mesh.face_vertices(&face)).collect()
+        }
+
+        // Surface curved along one axis has many ties, rounding errors break them differently
+        assert_ne!(decimated::<QuadricError<CornerTableF>>(false), decimated::<RoundedQuadricError>(false));
+        assert_eq!(decimated::<QuadricError<CornerTableF>>(true), decimated::<RoundedQuadricError>(true));
+    }
+
+    #[test]
+    fn test_vertex_errors() {
+        // Left half is flat, right half is bumpy
+        let bump = |x: f32, y: f32| 0.05 * ((x - 0.5) * 20.0).sin() * (y * 10.0).cos();
+        let mut mesh = create_grid_mesh(16, |x, y| if x > 0.5 { bump(x, y) } else { 0.0 });
+        let mut decimator = EdgeDecimator::<_, AlwaysDecimate>::new()
+            .min_faces_count(Some(100))
+            .keep_boundary(true);
+        decimator.decimate(&mut mesh);
+
+        let errors = decimator.vertex_errors(&mesh);
+        assert_eq!(errors.len(), mesh.vertices().count());
+        assert!(errors.iter().all(|error| *error >= 0.0));
+
+        let max_error = |flat: bool| {
+            mesh.vertices()
+                .zip(&errors)
+                .filter(|(vertex, _)| (mesh.vertex_position(vertex).x < 0.45) == flat)
+                .map(|(_, error)| *error)
+                .fold(0.0, f32::max)
+        };
+
+        assert!(max_error(false) > 0.0);
+        assert!(max_error(true) < max_error(false) * 1e-2);
+    }
+}
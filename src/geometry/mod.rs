@@ -2,3 +2,4 @@ pub mod traits;
 pub mod primitives;
 pub mod basis2d;
 pub mod orientation;
+pub mod polygon2;
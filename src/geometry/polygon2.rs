@@ -0,0 +1,519 @@
+use std::collections::{HashMap, HashSet};
+
+use num_traits::{cast, Float};
+
+use crate::{
+    algo::exact_boolean::BooleanOperation,
+    data_structures::vertex_index_map::HashablePoint,
+    geometry::traits::RealNumber,
+    helpers::aliases::Vec2,
+};
+
+///
+/// Rule defining which points are inside of polygon with overlapping or self-intersecting contours,
+/// based on winding number of point (see [Polygon2::winding_number])
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum FillRule {
+    /// Point is inside when winding number is odd, e.g. nested contours alternate between solid and hole regardless of orientation
+    EvenOdd,
+    /// Point is inside when winding number is not zero
+    NonZero,
+    /// Point is inside when winding number is positive
+    Positive,
+}
+
+impl FillRule {
+    #[inline]
+    pub fn is_inside(&self, winding_number: isize) -> bool {
+        match self {
+            FillRule::EvenOdd => winding_number % 2 != 0,
+            FillRule::NonZero => winding_number != 0,
+            FillRule::Positive => winding_number > 0,
+        }
+    }
+}
+
+///
+/// Shape of offset contour around convex corners, see [Polygon2::offset]
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum JoinType<TScalar> {
+    /// Sharp corner, corners longer than `limit` times offset distance are cut off
+    Miter { limit: TScalar },
+    /// Circular arc approximated by segments deviating from it by at most 1% of offset distance
+    Round,
+}
+
+///
+/// 2D polygon given by closed contours, e.g. slice of mesh. Last point of contour is connected to the first one.
+/// Results of boolean operations and offsetting are normalized: contours don't intersect, outer contours are
+/// counterclockwise and holes are clockwise, so they are filled correctly by any fill rule.
+///
+/// Operations split all edges at intersections and classify pieces by winding numbers of points on both sides of them,
+/// so they handle touching and overlapping contours. Complexity is quadratic in number of edges.
+///
+/// ## Example
+/// ```ignore
+/// let slice = Polygon2::new(contours, FillRule::EvenOdd);
+/// let compensated = slice.offset(-tool_radius, JoinType::Round);
+/// let walls = slice.boolean(&compensated, BooleanOperation::Difference);
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polygon2<TScalar: RealNumber> {
+    contours: Vec<Vec<Vec2<TScalar>>>,
+    fill_rule: FillRule,
+}
+
+impl<TScalar: RealNumber> Polygon2<TScalar> {
+    pub fn new(contours: Vec<Vec<Vec2<TScalar>>>, fill_rule: FillRule) -> Self {
+        Self { contours, fill_rule }
+    }
+
+    #[inline]
+    pub fn contours(&self) -> &[Vec<Vec2<TScalar>>] {
+        &self.contours
+    }
+
+    #[inline]
+    pub fn fill_rule(&self) -> FillRule {
+        self.fill_rule
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.contours.iter().all(|contour| contour.len() < 3)
+    }
+
+    /// Returns edges of all contours including closing ones
+    pub fn edges(&self) -> impl Iterator<Item = (Vec2<TScalar>, Vec2<TScalar>)> + '_ {
+        self.contours
+            .iter()
+            .flat_map(|contour| (0..contour.len()).map(move |i| (contour[i], contour[(i + 1) % contour.len()])))
+    }
+
+    /// Returns sum of signed areas of contours, positive for counterclockwise ones. Equals to area of normalized polygon.
+    pub fn area(&self) -> TScalar {
+        let double_area = self.edges().fold(TScalar::zero(), |area, (p, q)| area + cross(&p, &q));
+        double_area * cast(0.5).unwrap()
+    }
+
+    /// Returns number of times contours wind around point counterclockwise
+    pub fn winding_number(&self, point: &Vec2<TScalar>) -> isize {
+        let mut winding_number = 0;
+
+        for (p, q) in self.edges() {
+            let side = cross(&(q - p), &(point - p));
+
+            if p.y <= point.y {
+                if q.y > point.y && side > TScalar::zero() {
+                    winding_number += 1;
+                }
+            } else if q.y <= point.y && side < TScalar::zero() {
+                winding_number -= 1;
+            }
+        }
+
+        winding_number
+    }
+
+    /// Returns `true` when point is inside of polygon according to its fill rule
+    #[inline]
+    pub fn contains(&self, point: &Vec2<TScalar>) -> bool {
+        self.fill_rule.is_inside(self.winding_number(point))
+    }
+
+    /// Returns distance from point to closest edge, negative inside of polygon
+    pub fn signed_distance(&self, point: &Vec2<TScalar>) -> TScalar {
+        let distance = self
+            .edges()
+            .map(|(p, q)| {
+                let edge = q - p;
+                let t = Float::max(TScalar::zero(), Float::min(TScalar::one(), (point - p).dot(&edge) / edge.norm_squared()));
+                let t = if Float::is_nan(t) { TScalar::zero() } else { t };
+                (p + edge * t - point).norm()
+            })
+            .fold(TScalar::infinity(), Float::min);
+
+        if self.contains(point) { -distance } else { distance }
+    }
+
+    /// Returns result of boolean operation, every polygon is filled according to its own fill rule
+    pub fn boolean(&self, other: &Self, operation: BooleanOperation) -> Self {
+        match operation {
+            BooleanOperation::Union => clip(self, other, |a, b| a || b),
+            BooleanOperation::Intersection => clip(self, other, |a, b| a && b),
+            BooleanOperation::Difference => clip(self, other, |a, b| a && !b),
+        }
+    }
+
+    /// Returns normalized polygon covering same region, self-intersections and overlaps are resolved according to fill rule
+    pub fn simplify(&self) -> Self {
+        clip(self, &Self::new(Vec::new(), FillRule::NonZero), |a, _| a)
+    }
+
+    ///
+    /// Returns polygon grown by `delta` (shrunk when `delta` is negative), e.g. for tool compensation of slice.
+    /// Holes shrink when polygon grows and vice versa, parts thinner than `2 * |delta|` vanish when polygon shrinks.
+    ///
+    pub fn offset(&self, delta: TScalar, join: JoinType<TScalar>) -> Self {
+        let normalized = self.simplify();
+
+        if delta.is_zero() {
+            return normalized;
+        }
+
+        let raw = normalized
+            .contours
+            .iter()
+            .map(|contour| offset_contour(contour, delta, join))
+            .collect();
+
+        // Loops created at concave corners wind negatively, only positively wound region is offset polygon
+        Self::new(raw, FillRule::Positive).simplify()
+    }
+}
+
+#[inline]
+fn cross<TScalar: RealNumber>(a: &Vec2<TScalar>, b: &Vec2<TScalar>) -> TScalar {
+    a.x * b.y - a.y * b.x
+}
+
+/// Builds contours bounding points where `inside` holds for containment in `a` and `b`
+fn clip<TScalar: RealNumber>(a: &Polygon2<TScalar>, b: &Polygon2<TScalar>, inside: impl Fn(bool, bool) -> bool) -> Polygon2<TScalar> {
+    let segments: Vec<_> = a.edges().chain(b.edges()).filter(|(p, q)| p != q).collect();
+    let pieces = split_segments(&segments);
+    let quarter: TScalar = cast(0.25).unwrap();
+    let half: TScalar = cast(0.5).unwrap();
+    let mut boundary = Vec::new();
+
+    for (i, (p, q)) in pieces.iter().enumerate() {
+        let middle = (p + q) * half;
+        let direction = q - p;
+        let length = direction.norm();
+
+        // Points closer to piece than any other piece are in faces of arrangement adjacent to it
+        let clearance = pieces
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .map(|(_, (r, s))| distance_to_segment(&middle, r, s))
+            .fold(length, Float::min);
+
+        if clearance <= TScalar::zero() {
+            continue;
+        }
+
+        let normal = Vec2::new(-direction.y, direction.x) * (clearance * quarter / length);
+        let (left, right) = (middle + normal, middle - normal);
+        let left_inside = inside(a.contains(&left), b.contains(&left));
+        let right_inside = inside(a.contains(&right), b.contains(&right));
+
+        // Result is on the left of its edges
+        match (left_inside, right_inside) {
+            (true, false) => boundary.push((*p, *q)),
+            (false, true) => boundary.push((*q, *p)),
+            _ => {}
+        }
+    }
+
+    Polygon2::new(link_contours(&boundary), FillRule::NonZero)
+}
+
+/// Splits segments at intersections with each other, returns pieces without duplicates
+fn split_segments<TScalar: RealNumber>(segments: &[(Vec2<TScalar>, Vec2<TScalar>)]) -> Vec<(Vec2<TScalar>, Vec2<TScalar>)> {
+    let eps = TScalar::epsilon() * cast(16).unwrap();
+    let mut splits: Vec<Vec<Vec2<TScalar>>> = segments.iter().map(|(p, q)| vec![*p, *q]).collect();
+
+    for i in 0..segments.len() {
+        let (p, q) = segments[i];
+        let d1 = q - p;
+
+        for j in i + 1..segments.len() {
+            let (r, s) = segments[j];
+
+            let separated = |a: TScalar, b: TScalar, c: TScalar, d: TScalar| Float::max(a, b) < Float::min(c, d) || Float::max(c, d) < Float::min(a, b);
+
+            if separated(p.x, q.x, r.x, s.x) || separated(p.y, q.y, r.y, s.y) {
+                continue;
+            }
+
+            let d2 = s - r;
+            let denominator = cross(&d1, &d2);
+            let scale = d1.norm() * d2.norm();
+
+            if Float::abs(denominator) <= eps * scale {
+                // Collinear segments overlap, endpoints of each split the other one
+                if Float::abs(cross(&(r - p), &d1)) <= eps * d1.norm() * (r - p).norm() {
+                    splits[i].extend([r, s].into_iter().filter(|point| is_inside_segment(point, &p, &q)));
+                    let split = [p, q].into_iter().filter(|point| is_inside_segment(point, &r, &s));
+                    splits[j].extend(split);
+                }
+
+                continue;
+            }
+
+            let t = cross(&(r - p), &d2) / denominator;
+            let u = cross(&(r - p), &d1) / denominator;
+
+            if t < -eps || t > TScalar::one() + eps || u < -eps || u > TScalar::one() + eps {
+                continue;
+            }
+
+            // Touching endpoints are used as is, so pieces meet exactly
+            let point = if u <= eps {
+                r
+            } else if u >= TScalar::one() - eps {
+                s
+            } else if t <= eps {
+                p
+            } else if t >= TScalar::one() - eps {
+                q
+            } else {
+                p + d1 * t
+            };
+
+            splits[i].push(point);
+            splits[j].push(point);
+        }
+    }
+
+    let mut known = HashSet::new();
+    let mut pieces = Vec::new();
+
+    for ((p, q), mut points) in segments.iter().zip(splits) {
+        let direction = q - p;
+        points.sort_by(|a, b| direction.dot(&(a - p)).partial_cmp(&direction.dot(&(b - p))).unwrap());
+        points.dedup();
+
+        for piece in points.windows(2) {
+            let (start, end) = (piece[0], piece[1]);
+            let key = if (start.x, start.y) < (end.x, end.y) { (start, end) } else { (end, start) };
+
+            if known.insert((HashablePoint::from(key.0), HashablePoint::from(key.1))) {
+                pieces.push((start, end));
+            }
+        }
+    }
+
+    pieces
+}
+
+/// Returns `true` when collinear point lies strictly between ends of segment
+#[inline]
+fn is_inside_segment<TScalar: RealNumber>(point: &Vec2<TScalar>, p: &Vec2<TScalar>, q: &Vec2<TScalar>) -> bool {
+    let t = (point - p).dot(&(q - p));
+    t > TScalar::zero() && t < (q - p).norm_squared()
+}
+
+fn distance_to_segment<TScalar: RealNumber>(point: &Vec2<TScalar>, p: &Vec2<TScalar>, q: &Vec2<TScalar>) -> TScalar {
+    let direction = q - p;
+    let t = Float::min(Float::max((point - p).dot(&direction) / direction.norm_squared(), TScalar::zero()), TScalar::one());
+    (p + direction * t - point).norm()
+}
+
+/// Links directed edges into closed contours, at shared vertices contour turns left as much as possible so touching contours stay separate
+fn link_contours<TScalar: RealNumber>(edges: &[(Vec2<TScalar>, Vec2<TScalar>)]) -> Vec<Vec<Vec2<TScalar>>> {
+    let mut outgoing: HashMap<HashablePoint<2, TScalar>, Vec<usize>> = HashMap::new();
+    for (i, (start, _)) in edges.iter().enumerate() {
+        outgoing.entry((*start).into()).or_default().push(i);
+    }
+
+    let mut used = vec![false; edges.len()];
+    let mut contours = Vec::new();
+
+    for first in 0..edges.len() {
+        if used[first] {
+            continue;
+        }
+
+        let mut contour = Vec::new();
+        let mut current = first;
+
+        loop {
+            used[current] = true;
+            let (start, end) = edges[current];
+            contour.push(start);
+
+            if end == edges[first].0 {
+                break;
+            }
+
+            let direction = end - start;
+            let next = outgoing.get(&end.into()).and_then(|candidates| {
+                candidates
+                    .iter()
+                    .filter(|candidate| !used[**candidate])
+                    .map(|candidate| {
+                        let turn = edges[*candidate].1 - edges[*candidate].0;
+                        (*candidate, Float::atan2(cross(&direction, &turn), direction.dot(&turn)))
+                    })
+                    .max_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap())
+            });
+
+            match next {
+                Some((next, _)) => current = next,
+                None => break,
+            }
+        }
+
+        let contour = remove_collinear(contour);
+
+        if contour.len() >= 3 {
+            contours.push(contour);
+        }
+    }
+
+    contours
+}
+
+/// Removes points lying on straight line between their neighbors, e.g. left by splitting of edges
+fn remove_collinear<TScalar: RealNumber>(mut contour: Vec<Vec2<TScalar>>) -> Vec<Vec2<TScalar>> {
+    let eps = TScalar::epsilon() * cast(16).unwrap();
+    let mut i = 0;
+
+    while i < contour.len() && contour.len() >= 3 {
+        let previous = contour[(i + contour.len() - 1) % contour.len()];
+        let next = contour[(i + 1) % contour.len()];
+        let (a, b) = (contour[i] - previous, next - contour[i]);
+
+        if Float::abs(cross(&a, &b)) <= eps * a.norm() * b.norm() && a.dot(&b) > TScalar::zero() {
+            contour.remove(i);
+            i = i.saturating_sub(1);
+        } else {
+            i += 1;
+        }
+    }
+
+    contour
+}
+
+/// Moves edges of contour to their right side by `delta` and joins them, result may self-intersect
+fn offset_contour<TScalar: RealNumber>(contour: &[Vec2<TScalar>], delta: TScalar, join: JoinType<TScalar>) -> Vec<Vec2<TScalar>> {
+    let count = contour.len();
+    let normal = |i: usize| {
+        let direction = contour[(i + 1) % count] - contour[i];
+        Vec2::new(direction.y, -direction.x) / direction.norm()
+    };
+
+    let mut offset = Vec::new();
+
+    for (i, point) in contour.iter().copied().enumerate() {
+        let (n1, n2) = (normal((i + count - 1) % count), normal(i));
+        let turn = cross(&n1, &n2);
+
+        // Concave corner relative to offset direction, edges overlap and loop is removed later
+        if turn * delta < TScalar::zero() {
+            offset.extend([point + n1 * delta, point, point + n2 * delta]);
+            continue;
+        }
+
+        let cos = n1.dot(&n2);
+
+        match join {
+            JoinType::Miter { limit } => {
+                let one = TScalar::one();
+
+                // Length of miter relative to delta is `1 / cos(angle / 2)`
+                if (one + cos) * limit * limit >= cast(2).unwrap() {
+                    offset.push(point + (n1 + n2) * (delta / (one + cos)));
+                } else {
+                    offset.extend([point + n1 * delta, point + n2 * delta]);
+                }
+            }
+            JoinType::Round => {
+                let angle = Float::atan2(turn, cos);
+                let max_step = Float::acos(TScalar::one() - cast(0.01).unwrap()) * cast(2).unwrap();
+                let steps = Float::ceil(Float::abs(angle) / max_step).to_usize().unwrap_or(1).max(1);
+
+                for step in 0..=steps {
+                    let (sin, cos) = Float::sin_cos(angle * cast(step).unwrap() / cast(steps).unwrap());
+                    let rotated = Vec2::new(n1.x * cos - n1.y * sin, n1.x * sin + n1.y * cos);
+                    offset.push(point + rotated * delta);
+                }
+            }
+        }
+    }
+
+    offset
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{FillRule, JoinType, Polygon2};
+    use crate::{algo::exact_boolean::BooleanOperation, helpers::aliases::Vec2};
+
+    fn square(min: f64, max: f64) -> Vec<Vec2<f64>> {
+        vec![Vec2::new(min, min), Vec2::new(max, min), Vec2::new(max, max), Vec2::new(min, max)]
+    }
+
+    fn shifted(mut contour: Vec<Vec2<f64>>, shift: Vec2<f64>) -> Vec<Vec2<f64>> {
+        contour.iter_mut().for_each(|point| *point += shift);
+        contour
+    }
+
+    #[test]
+    fn test_polygon2_boolean() {
+        let a = Polygon2::new(vec![square(0.0, 2.0)], FillRule::NonZero);
+        let b = Polygon2::new(vec![square(1.0, 3.0)], FillRule::NonZero);
+
+        let union = a.boolean(&b, BooleanOperation::Union);
+        assert_eq!(union.contours().len(), 1);
+        assert_eq!(union.contours()[0].len(), 8);
+        assert!((union.area() - 7.0).abs() < 1e-12);
+
+        let intersection = a.boolean(&b, BooleanOperation::Intersection);
+        assert!((intersection.area() - 1.0).abs() < 1e-12);
+        assert!(intersection.contains(&Vec2::new(1.5, 1.5)));
+
+        let difference = a.boolean(&b, BooleanOperation::Difference);
+        assert!((difference.area() - 3.0).abs() < 1e-12);
+        assert!(!difference.contains(&Vec2::new(1.5, 1.5)));
+
+        // Nested contours of the same orientation
+        let nested = vec![square(0.0, 4.0), square(1.0, 3.0)];
+        let even_odd = Polygon2::new(nested.clone(), FillRule::EvenOdd).simplify();
+        assert_eq!(even_odd.contours().len(), 2);
+        assert!((even_odd.area() - 12.0).abs() < 1e-12);
+        assert!((Polygon2::new(nested, FillRule::NonZero).simplify().area() - 16.0).abs() < 1e-12);
+
+        // Squares touching by corner and overlapping by edge
+        let corner = Polygon2::new(vec![square(0.0, 1.0), square(1.0, 2.0)], FillRule::NonZero).simplify();
+        assert_eq!(corner.contours().len(), 2);
+        assert!((corner.area() - 2.0).abs() < 1e-12);
+
+        let side = Polygon2::new(vec![shifted(square(1.0, 2.0), Vec2::new(0.0, -1.0))], FillRule::NonZero);
+        let merged = Polygon2::new(vec![square(0.0, 1.0)], FillRule::NonZero).boolean(&side, BooleanOperation::Union);
+        assert_eq!(merged.contours().len(), 1);
+        assert_eq!(merged.contours()[0].len(), 4);
+        assert!((merged.area() - 2.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_polygon2_offset() {
+        let framed = Polygon2::new(vec![square(0.0, 4.0), square(1.0, 3.0)], FillRule::EvenOdd);
+
+        // Hole shrinks when polygon grows
+        let grown = framed.offset(0.5, JoinType::Miter { limit: 2.0 });
+        assert_eq!(grown.contours().len(), 2);
+        assert!((grown.area() - (25.0 - 1.0)).abs() < 1e-9);
+
+        // Square corners are cut by small miter limit, rounded ones are close to arcs
+        let square = Polygon2::new(vec![square(0.0, 2.0)], FillRule::NonZero);
+        let beveled = square.offset(0.5, JoinType::Miter { limit: 1.2 });
+        assert!((beveled.area() - (9.0 - 4.0 * 0.125)).abs() < 1e-9);
+
+        let rounded = square.offset(0.5, JoinType::Round);
+        let expected = 4.0 + 4.0 + std::f64::consts::PI * 0.25;
+        assert!(rounded.area() < expected && rounded.area() > expected * 0.995);
+
+        // Shrinking removes thin frame entirely
+        assert!((square.offset(-0.5, JoinType::Round).area() - 1.0).abs() < 1e-9);
+        assert!(framed.offset(-0.6, JoinType::Miter { limit: 2.0 }).is_empty());
+    }
+}
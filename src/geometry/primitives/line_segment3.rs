@@ -1,4 +1,6 @@
-use crate::{geometry::traits::{RealNumber, HasScalarType, ClosestPoint3}, helpers::aliases::Vec3};
+use num_traits::Float;
+
+use crate::{geometry::traits::{RealNumber, HasScalarType, ClosestPoint3, ClosestPoints3}, helpers::aliases::Vec3};
 
 use super::{line3::Line3, plane3::Plane3, box3::Box3};
 
@@ -73,6 +75,75 @@ impl<TScalar: RealNumber> LineSegment3<TScalar> {
     pub fn is_on_segment(&self, t: TScalar) -> bool {
         t >= TScalar::zero() && t <= self.length
     }
+
+    ///
+    /// Returns pair of closest points between `self` and `other` segments.
+    /// First point lies on `self`, second one on `other`.
+    /// Based on "Real-Time Collision Detection" by Christer Ericson.
+    ///
+    pub fn closest_point_to_segment(&self, other: &LineSegment3<TScalar>) -> (Vec3<TScalar>, Vec3<TScalar>) {
+        let zero = TScalar::zero();
+        let one = TScalar::one();
+        let clamp = |x: TScalar| Float::min(Float::max(x, zero), one);
+
+        let p1 = self.get_start();
+        let p2 = other.get_start();
+        let d1 = self.vector();
+        let d2 = other.vector();
+        let r = p1 - p2;
+
+        let a = d1.norm_squared();
+        let e = d2.norm_squared();
+        let f = d2.dot(&r);
+
+        let (s, t) = if a <= TScalar::epsilon() && e <= TScalar::epsilon() {
+            // Both segments degenerate into points
+            (zero, zero)
+        } else if a <= TScalar::epsilon() {
+            // First segment degenerates into point
+            (zero, clamp(f / e))
+        } else {
+            let c = d1.dot(&r);
+
+            if e <= TScalar::epsilon() {
+                // Second segment degenerates into point
+                (clamp(-c / a), zero)
+            } else {
+                let b = d1.dot(&d2);
+                let denom = a * e - b * b;
+
+                // Pick arbitrary point on first segment when segments are parallel
+                let s = if denom != zero {
+                    clamp((b * f - c * e) / denom)
+                } else {
+                    zero
+                };
+
+                let t = (b * s + f) / e;
+
+                if t < zero {
+                    (clamp(-c / a), zero)
+                } else if t > one {
+                    (clamp((b - c) / a), one)
+                } else {
+                    (s, t)
+                }
+            }
+        };
+
+        (p1 + d1 * s, p2 + d2 * t)
+    }
+
+    /// Returns vector from start to end of segment
+    #[inline]
+    fn vector(&self) -> Vec3<TScalar> {
+        // Direction of degenerate segment is not defined
+        if self.length <= TScalar::epsilon() {
+            return Vec3::zeros();
+        }
+
+        self.line.get_direction() * self.length
+    }
 }
 
 impl<TScalar: RealNumber> HasScalarType for LineSegment3<TScalar> {
@@ -93,3 +164,43 @@ impl<TScalar: RealNumber> ClosestPoint3 for LineSegment3<TScalar> {
         self.line.point_at(t)
     }
 }
+
+impl<TScalar: RealNumber> ClosestPoints3<LineSegment3<TScalar>> for LineSegment3<TScalar> {
+    #[inline]
+    fn closest_points(&self, segment: &LineSegment3<TScalar>) -> (Vec3<TScalar>, Vec3<TScalar>) {
+        self.closest_point_to_segment(segment)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{geometry::primitives::line_segment3::LineSegment3, helpers::aliases::Vec3f};
+
+    #[test]
+    fn segment_closest_point_to_segment() {
+        let s1 = LineSegment3::new(&Vec3f::new(-1.0, 0.0, 0.0), &Vec3f::new(1.0, 0.0, 0.0));
+
+        // Skew segments
+        let s2 = LineSegment3::new(&Vec3f::new(0.5, -1.0, 1.0), &Vec3f::new(0.5, 1.0, 1.0));
+        let (p1, p2) = s1.closest_point_to_segment(&s2);
+        assert!((p1 - Vec3f::new(0.5, 0.0, 0.0)).norm() < 1e-6);
+        assert!((p2 - Vec3f::new(0.5, 0.0, 1.0)).norm() < 1e-6);
+
+        // Closest points at endpoints
+        let s3 = LineSegment3::new(&Vec3f::new(2.0, 1.0, 0.0), &Vec3f::new(3.0, 2.0, 0.0));
+        let (p1, p2) = s1.closest_point_to_segment(&s3);
+        assert!((p1 - Vec3f::new(1.0, 0.0, 0.0)).norm() < 1e-6);
+        assert!((p2 - Vec3f::new(2.0, 1.0, 0.0)).norm() < 1e-6);
+
+        // Parallel segments
+        let s4 = LineSegment3::new(&Vec3f::new(0.0, 1.0, 0.0), &Vec3f::new(3.0, 1.0, 0.0));
+        let (p1, p2) = s1.closest_point_to_segment(&s4);
+        assert!(((p1 - p2).norm() - 1.0).abs() < 1e-6);
+
+        // Degenerate segment
+        let point = LineSegment3::new(&Vec3f::new(0.25, 2.0, 0.0), &Vec3f::new(0.25, 2.0, 0.0));
+        let (p1, p2) = s1.closest_point_to_segment(&point);
+        assert!((p1 - Vec3f::new(0.25, 0.0, 0.0)).norm() < 1e-6);
+        assert!((p2 - Vec3f::new(0.25, 2.0, 0.0)).norm() < 1e-6);
+    }
+}
@@ -0,0 +1,382 @@
+use std::collections::HashSet;
+
+use num_traits::{cast, Float};
+
+use crate::{
+    geometry::traits::RealNumber,
+    helpers::aliases::Vec3,
+    mesh::traits::TopologicalMesh,
+    spatial_partitioning::aabb_tree::{AABBTree, MedianCut},
+};
+
+use super::line_segment3::LineSegment3;
+
+///
+/// 3D polyline, open or closed. Closed polyline has implicit segment from last point to first one.
+/// Parameterized by arc length `s` measured from first point.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct Polyline3<TScalar: RealNumber> {
+    points: Vec<Vec3<TScalar>>,
+    closed: bool,
+}
+
+impl<TScalar: RealNumber> Polyline3<TScalar> {
+    pub fn new(points: Vec<Vec3<TScalar>>, closed: bool) -> Self {
+        Self { points, closed }
+    }
+
+    #[inline]
+    pub fn points(&self) -> &[Vec3<TScalar>] {
+        &self.points
+    }
+
+    #[inline]
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Returns segments of polyline including closing one
+    pub fn segments(&self) -> impl Iterator<Item = LineSegment3<TScalar>> + '_ {
+        let count = self.segments_count();
+        (0..count).map(move |i| LineSegment3::new(&self.points[i], &self.points[(i + 1) % self.points.len()]))
+    }
+
+    /// Total length of polyline
+    pub fn length(&self) -> TScalar {
+        self.arc_lengths().last().copied().unwrap_or(TScalar::zero())
+    }
+
+    ///
+    /// Returns cumulative arc length at every point. For closed polyline last element is total length
+    /// (arc length of first point reached again), so it has one element more than points.
+    ///
+    pub fn arc_lengths(&self) -> Vec<TScalar> {
+        let mut lengths = Vec::with_capacity(self.points.len() + 1);
+
+        if self.points.is_empty() {
+            return lengths;
+        }
+
+        let mut length = TScalar::zero();
+        lengths.push(length);
+
+        for i in 0..self.segments_count() {
+            length += (self.points[(i + 1) % self.points.len()] - self.points[i]).norm();
+            lengths.push(length);
+        }
+
+        lengths
+    }
+
+    ///
+    /// Returns point at arc length `s`. Arc length is clamped to polyline for open polylines and wrapped for closed ones.
+    ///
+    pub fn point_at(&self, s: TScalar) -> Vec3<TScalar> {
+        assert!(!self.points.is_empty(), "Empty polyline");
+        self.point_at_with_lengths(s, &self.arc_lengths())
+    }
+
+    ///
+    /// Returns polyline with points evenly distributed by arc length. Distance between consecutive points is
+    /// the closest to `spacing` that divides polyline into whole number of segments.
+    /// End points of open polyline are preserved.
+    ///
+    pub fn resampled(&self, spacing: TScalar) -> Self {
+        assert!(spacing > TScalar::zero(), "Spacing should be positive");
+
+        let lengths = self.arc_lengths();
+        let length = lengths.last().copied().unwrap_or(TScalar::zero());
+
+        if length == TScalar::zero() {
+            return self.clone();
+        }
+
+        let segments = Float::max(Float::round(length / spacing), TScalar::one());
+        let step = length / segments;
+        let segments = segments.to_usize().unwrap();
+        let count = if self.closed { segments } else { segments + 1 };
+
+        let points = (0..count)
+            .map(|i| self.point_at_with_lengths(step * cast::<usize, TScalar>(i).unwrap(), &lengths))
+            .collect();
+
+        Self::new(points, self.closed)
+    }
+
+    ///
+    /// Returns polyline smoothed by Laplacian smoothing: every point is moved by `factor`
+    /// towards the middle of its neighbors on each of `iterations`. End points of open polyline are fixed.
+    ///
+    pub fn smoothed(&self, iterations: usize, factor: TScalar) -> Self {
+        let mut points = self.points.clone();
+        let count = points.len();
+
+        if count < 3 {
+            return self.clone();
+        }
+
+        let half: TScalar = cast(0.5).unwrap();
+        let (first, last) = if self.closed { (0, count) } else { (1, count - 1) };
+
+        for _ in 0..iterations {
+            let previous = points.clone();
+
+            for (i, point) in points.iter_mut().enumerate().take(last).skip(first) {
+                let prev = previous[(i + count - 1) % count];
+                let next = previous[(i + 1) % count];
+                let middle = (prev + next) * half;
+                *point += (middle - *point) * factor;
+            }
+        }
+
+        Self::new(points, self.closed)
+    }
+
+    ///
+    /// Projects polyline onto mesh surface. Every point is moved to the closest point on mesh,
+    /// then path between consecutive projections is traced across faces by intersecting them with plane
+    /// through both projections, so resulting polyline lies on surface and contains its crossings of mesh edges.
+    /// Tracing stops at mesh boundary, in that case consecutive projections are connected directly.
+    ///
+    /// ## Example
+    /// ```ignore
+    /// let path = Polyline3::new(points, false).resampled(0.5).project_to_mesh(&mesh);
+    /// ```
+    ///
+    pub fn project_to_mesh<TMesh>(&self, mesh: &TMesh) -> Self
+    where
+        TMesh: TopologicalMesh<ScalarType = TScalar>,
+    {
+        let faces: Vec<_> = mesh.faces().collect();
+
+        if faces.is_empty() || self.points.is_empty() {
+            return self.clone();
+        }
+
+        let tree = AABBTree::from_mesh(mesh).top_down::<MedianCut>();
+
+        let projected: Vec<_> = self
+            .points
+            .iter()
+            .map(|point| {
+                let (index, closest) = tree.closest_object(point, Float::infinity()).unwrap();
+                (faces[index], closest)
+            })
+            .collect();
+
+        let mut points = vec![projected[0].1];
+
+        for i in 0..self.segments_count() {
+            let (start_face, start) = projected[i];
+            let (end_face, end) = projected[(i + 1) % projected.len()];
+
+            walk(mesh, start_face, &start, end_face, &end, &mut points);
+
+            points.push(end);
+        }
+
+        // Closing segment ends at first point
+        if self.closed {
+            points.pop();
+        }
+
+        Self::new(points, self.closed)
+    }
+
+    fn segments_count(&self) -> usize {
+        match (self.points.len(), self.closed) {
+            (0, _) => 0,
+            (count, true) => count,
+            (count, false) => count - 1,
+        }
+    }
+
+    fn point_at_with_lengths(&self, s: TScalar, lengths: &[TScalar]) -> Vec3<TScalar> {
+        let length = *lengths.last().unwrap();
+
+        if length == TScalar::zero() {
+            return self.points[0];
+        }
+
+        let s = if self.closed {
+            s - Float::floor(s / length) * length
+        } else {
+            Float::min(Float::max(s, TScalar::zero()), length)
+        };
+
+        // Segment containing `s`
+        let segment = lengths.partition_point(|l| *l <= s).clamp(1, lengths.len() - 1) - 1;
+        let start = self.points[segment];
+        let end = self.points[(segment + 1) % self.points.len()];
+        let segment_length = lengths[segment + 1] - lengths[segment];
+
+        if segment_length == TScalar::zero() {
+            return start;
+        }
+
+        start + (end - start) * ((s - lengths[segment]) / segment_length)
+    }
+}
+
+///
+/// Traces path on surface from `start` on `start_face` towards `end` on `end_face`, pushing crossings of mesh edges.
+/// Path follows intersection of surface with plane through both points which contains average of faces normals.
+///
+fn walk<TScalar, TMesh>(
+    mesh: &TMesh,
+    start_face: TMesh::FaceDescriptor,
+    start: &Vec3<TScalar>,
+    end_face: TMesh::FaceDescriptor,
+    end: &Vec3<TScalar>,
+    points: &mut Vec<Vec3<TScalar>>,
+) where
+    TScalar: RealNumber,
+    TMesh: TopologicalMesh<ScalarType = TScalar>,
+{
+    if start_face == end_face {
+        return;
+    }
+
+    let direction = end - start;
+    let normal = mesh.face_positions(&start_face).try_get_normal().unwrap_or(Vec3::zeros())
+        + mesh.face_positions(&end_face).try_get_normal().unwrap_or(Vec3::zeros());
+    let plane_normal = direction.cross(&normal);
+
+    if plane_normal.norm_squared() == TScalar::zero() {
+        return;
+    }
+
+    let total_progress = direction.norm_squared();
+    let mut progress = TScalar::zero();
+    let mut face = start_face;
+    let mut entry_edge = None;
+    let mut visited = HashSet::new();
+
+    while face != end_face && visited.insert(face) {
+        let (e1, e2, e3) = mesh.face_edges(&face);
+        let mut best = None;
+
+        // Crossing of face boundary with cutting plane furthest along path
+        for edge in [e1, e2, e3] {
+            let (v1, v2) = mesh.edge_vertices(&edge);
+            let key = (v1.min(v2), v1.max(v2));
+
+            if entry_edge == Some(key) {
+                continue;
+            }
+
+            let p1 = mesh.vertex_position(&v1);
+            let p2 = mesh.vertex_position(&v2);
+            let d1 = plane_normal.dot(&(p1 - start));
+            let d2 = plane_normal.dot(&(p2 - start));
+
+            if d1 * d2 > TScalar::zero() || d1 == d2 {
+                continue;
+            }
+
+            let crossing = p1 + (p2 - p1) * (d1 / (d1 - d2));
+            let crossing_progress = direction.dot(&(crossing - start));
+
+            if crossing_progress > progress && best.is_none_or(|(_, _, _, best_progress)| crossing_progress > best_progress) {
+                best = Some((edge, key, crossing, crossing_progress));
+            }
+        }
+
+        let Some((edge, key, crossing, crossing_progress)) = best else {
+            break;
+        };
+
+        if crossing_progress >= total_progress {
+            break;
+        }
+
+        points.push(crossing);
+        progress = crossing_progress;
+
+        let next_face = match mesh.edge_faces(&edge) {
+            (f1, Some(f2)) if f1 == face => f2,
+            (f1, Some(_)) => f1,
+            (_, None) => break,
+        };
+
+        entry_edge = Some(key);
+        face = next_face;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, primitives},
+    };
+
+    use super::Polyline3;
+
+    #[test]
+    fn test_arc_length() {
+        let square = Polyline3::new(
+            vec![
+                Vec3f::new(0.0, 0.0, 0.0),
+                Vec3f::new(1.0, 0.0, 0.0),
+                Vec3f::new(1.0, 1.0, 0.0),
+                Vec3f::new(0.0, 1.0, 0.0),
+            ],
+            true,
+        );
+
+        assert_eq!(square.length(), 4.0);
+        assert_eq!(square.segments().count(), 4);
+        assert_eq!(square.point_at(1.5), Vec3f::new(1.0, 0.5, 0.0));
+        assert_eq!(square.point_at(5.5), Vec3f::new(1.0, 0.5, 0.0));
+        assert_eq!(square.point_at(3.5), Vec3f::new(0.0, 0.5, 0.0));
+
+        let resampled = square.resampled(0.5);
+        assert_eq!(resampled.points().len(), 8);
+        assert!((resampled.length() - 4.0).abs() < 1e-6);
+
+        let open = Polyline3::new(square.points().to_vec(), false);
+        assert_eq!(open.length(), 3.0);
+        assert_eq!(open.point_at(10.0), Vec3f::new(0.0, 1.0, 0.0));
+        assert_eq!(open.resampled(0.4).points().len(), 9);
+    }
+
+    #[test]
+    fn test_smoothed() {
+        let zigzag = Polyline3::new((0..10).map(|i| Vec3f::new(i as f32, (i % 2) as f32, 0.0)).collect(), false);
+        let smoothed = zigzag.smoothed(5, 0.5);
+
+        assert!(smoothed.length() < zigzag.length());
+        assert_eq!(smoothed.points()[0], zigzag.points()[0]);
+        assert_eq!(smoothed.points()[9], zigzag.points()[9]);
+    }
+
+    #[test]
+    fn test_project_to_mesh() {
+        let sphere: CornerTableF = primitives::uv_sphere(1.0, 32, 16);
+        let circle = Polyline3::new(
+            (0..12)
+                .map(|i| {
+                    let angle = i as f32 * std::f32::consts::TAU / 12.0;
+                    Vec3f::new(1.2 * angle.cos(), 0.3, 1.2 * angle.sin())
+                })
+                .collect(),
+            true,
+        );
+
+        let projected = circle.project_to_mesh(&sphere);
+
+        assert!(projected.points().len() > circle.points().len());
+        assert!(projected.is_closed());
+
+        for point in projected.points() {
+            assert!((point.norm() - 1.0).abs() < 0.02, "Point is not on sphere: {}", point);
+        }
+
+        // Consecutive points are close, path does not jump across sphere
+        for segment in projected.segments() {
+            assert!(segment.get_line().get_point().metric_distance(&segment.get_end()) < 0.6);
+        }
+    }
+}
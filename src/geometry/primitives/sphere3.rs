@@ -15,6 +15,16 @@ impl<TScalar: RealNumber> Sphere3<TScalar> {
         Self { center, radius }
     }
 
+    #[inline]
+    pub fn get_center(&self) -> &Vec3<TScalar> {
+        &self.center
+    }
+
+    #[inline]
+    pub fn get_radius(&self) -> TScalar {
+        self.radius
+    }
+
     #[inline]
     pub fn intersects_box3(&self, bbox: &Box3<TScalar>) -> bool {
         bbox.squared_distance(&self.center) <= self.radius * self.radius
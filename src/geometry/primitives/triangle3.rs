@@ -1,4 +1,4 @@
-use std::mem::swap;
+use std::{cmp::Ordering, mem::swap};
 
 use nalgebra::Vector3;
 use nalgebra_glm::{max2, min2};
@@ -9,8 +9,8 @@ use crate::{
     geometry::{
         basis2d::Basis2,
         traits::{
-            ClosestPoint3, HasBBox3, HasScalarType, IntersectsPlane3, IntersectsTriangle3, Number,
-            RealNumber,
+            ClosestPoint3, ClosestPoints3, HasBBox3, HasScalarType, IntersectsPlane3, IntersectsTriangle3,
+            Number, RealNumber,
         },
     },
     helpers::aliases::Vec3,
@@ -264,6 +264,73 @@ impl<TScalar: RealNumber> Triangle3<TScalar> {
         self.intersects_ray3_at(ray).is_some()
     }
 
+    ///
+    /// Returns distance between triangle and line segment together with closest points on triangle and on segment.
+    /// When segment intersects triangle distance is zero and both points are equal to intersection point.
+    ///
+    pub fn distance_to_segment(
+        &self,
+        segment: &LineSegment3<TScalar>,
+    ) -> (TScalar, Vec3<TScalar>, Vec3<TScalar>) {
+        if let Some((_, t)) = self.intersects_line_segment3_at(segment) {
+            let point = segment.get_line().point_at(t);
+            return (TScalar::zero(), point, point);
+        }
+
+        let start = *segment.get_start();
+        let end = segment.get_end();
+
+        // Closest points lie either on segment endpoint or on one of triangle edges
+        let candidates = [
+            (self.closest_point(&start), start),
+            (self.closest_point(&end), end),
+        ];
+
+        let closest = self
+            .edges()
+            .iter()
+            .map(|edge| edge.closest_point_to_segment(segment))
+            .chain(candidates)
+            .map(|(on_triangle, on_segment)| ((on_triangle - on_segment).norm_squared(), on_triangle, on_segment))
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal))
+            .unwrap();
+
+        (Float::sqrt(closest.0), closest.1, closest.2)
+    }
+
+    ///
+    /// Returns distance between triangles together with closest points on `self` and on `other`.
+    /// When triangles intersect distance is zero and both points are equal to one of intersection points.
+    ///
+    pub fn distance_to_triangle(
+        &self,
+        other: &Triangle3<TScalar>,
+    ) -> (TScalar, Vec3<TScalar>, Vec3<TScalar>) {
+        // Closest points of non-intersecting triangles lie on edge of one of them
+        let from_self = self.edges().into_iter().map(|edge| {
+            let (distance, on_other, on_self) = other.distance_to_segment(&edge);
+            (distance, on_self, on_other)
+        });
+        let from_other = other
+            .edges()
+            .into_iter()
+            .map(|edge| self.distance_to_segment(&edge));
+
+        from_self
+            .chain(from_other)
+            .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap_or(Ordering::Equal))
+            .unwrap()
+    }
+
+    #[inline]
+    fn edges(&self) -> [LineSegment3<TScalar>; 3] {
+        [
+            LineSegment3::new(&self.a, &self.b),
+            LineSegment3::new(&self.b, &self.c),
+            LineSegment3::new(&self.c, &self.a),
+        ]
+    }
+
     #[inline]
     pub fn normal(a: &Vec3<TScalar>, b: &Vec3<TScalar>, c: &Vec3<TScalar>) -> Vec3<TScalar> {
         let cross = (b - a).cross(&(c - a));
@@ -390,6 +457,30 @@ impl<TScalar: RealNumber> ClosestPoint3 for Triangle3<TScalar> {
     }
 }
 
+impl<TScalar: RealNumber> ClosestPoints3<LineSegment3<TScalar>> for Triangle3<TScalar> {
+    #[inline]
+    fn closest_points(&self, segment: &LineSegment3<TScalar>) -> (Vec3<TScalar>, Vec3<TScalar>) {
+        let (_, on_triangle, on_segment) = self.distance_to_segment(segment);
+        (on_triangle, on_segment)
+    }
+}
+
+impl<TScalar: RealNumber> ClosestPoints3<Triangle3<TScalar>> for LineSegment3<TScalar> {
+    #[inline]
+    fn closest_points(&self, triangle: &Triangle3<TScalar>) -> (Vec3<TScalar>, Vec3<TScalar>) {
+        let (_, on_triangle, on_segment) = triangle.distance_to_segment(self);
+        (on_segment, on_triangle)
+    }
+}
+
+impl<TScalar: RealNumber> ClosestPoints3<Triangle3<TScalar>> for Triangle3<TScalar> {
+    #[inline]
+    fn closest_points(&self, triangle: &Triangle3<TScalar>) -> (Vec3<TScalar>, Vec3<TScalar>) {
+        let (_, on_self, on_other) = self.distance_to_triangle(triangle);
+        (on_self, on_other)
+    }
+}
+
 #[derive(PartialEq, Debug)]
 pub enum Triangle3Triangle3Intersection<TScalar: RealNumber> {
     LineSegment(LineSegment3<TScalar>),
@@ -825,4 +916,77 @@ mod tests {
         assert!(t1t7_actual.is_some());
         assert_eq!(t1t7_expected, t1t7_actual.unwrap());
     }
+
+    #[test]
+    fn triangle_distance_to_segment() {
+        let triangle = Triangle3::new(
+            Vec3f::new(0.0, 0.0, 0.0),
+            Vec3f::new(1.0, 0.0, 0.0),
+            Vec3f::new(0.0, 1.0, 0.0),
+        );
+
+        // Segment above triangle
+        let above = LineSegment3::new(&Vec3f::new(0.25, 0.25, 1.0), &Vec3f::new(0.25, 0.25, 2.0));
+        let (distance, on_triangle, on_segment) = triangle.distance_to_segment(&above);
+        assert!((distance - 1.0).abs() < 1e-6);
+        assert!((on_triangle - Vec3f::new(0.25, 0.25, 0.0)).norm() < 1e-6);
+        assert!((on_segment - Vec3f::new(0.25, 0.25, 1.0)).norm() < 1e-6);
+
+        // Segment crossing edge region
+        let beside = LineSegment3::new(&Vec3f::new(0.5, -1.0, -1.0), &Vec3f::new(0.5, -1.0, 1.0));
+        let (distance, on_triangle, on_segment) = triangle.distance_to_segment(&beside);
+        assert!((distance - 1.0).abs() < 1e-6);
+        assert!((on_triangle - Vec3f::new(0.5, 0.0, 0.0)).norm() < 1e-6);
+        assert!((on_segment - Vec3f::new(0.5, -1.0, 0.0)).norm() < 1e-6);
+
+        // Segment piercing triangle
+        let piercing = LineSegment3::new(&Vec3f::new(0.25, 0.25, -1.0), &Vec3f::new(0.25, 0.25, 1.0));
+        let (distance, on_triangle, on_segment) = triangle.distance_to_segment(&piercing);
+        assert!(distance.abs() < 1e-6);
+        assert!((on_triangle - Vec3f::new(0.25, 0.25, 0.0)).norm() < 1e-6);
+        assert_eq!(on_triangle, on_segment);
+    }
+
+    #[test]
+    fn triangle_distance_to_triangle() {
+        let t1 = Triangle3::new(
+            Vec3f::new(0.0, 0.0, 0.0),
+            Vec3f::new(1.0, 0.0, 0.0),
+            Vec3f::new(0.0, 1.0, 0.0),
+        );
+
+        // Vertex of second triangle points to the face of first one
+        let t2 = Triangle3::new(
+            Vec3f::new(0.25, 0.25, 0.5),
+            Vec3f::new(-1.0, -1.0, 2.0),
+            Vec3f::new(1.0, -1.0, 2.0),
+        );
+        let (distance, on_t1, on_t2) = t1.distance_to_triangle(&t2);
+        assert!((distance - 0.5).abs() < 1e-6);
+        assert!((on_t1 - Vec3f::new(0.25, 0.25, 0.0)).norm() < 1e-6);
+        assert!((on_t2 - Vec3f::new(0.25, 0.25, 0.5)).norm() < 1e-6);
+
+        let (distance_swapped, on_t2_swapped, on_t1_swapped) = t2.distance_to_triangle(&t1);
+        assert!((distance - distance_swapped).abs() < 1e-6);
+        assert!((on_t1 - on_t1_swapped).norm() < 1e-6);
+        assert!((on_t2 - on_t2_swapped).norm() < 1e-6);
+
+        // Intersecting triangles
+        let t3 = Triangle3::new(
+            Vec3f::new(0.25, 0.25, -1.0),
+            Vec3f::new(0.25, 0.25, 1.0),
+            Vec3f::new(2.0, 2.0, 0.0),
+        );
+        let (distance, on_t1, on_t3) = t1.distance_to_triangle(&t3);
+        assert!(distance.abs() < 1e-6);
+        assert_eq!(on_t1, on_t3);
+
+        // NaN coordinates don't panic
+        let nan = Triangle3::new(
+            Vec3f::new(f32::NAN, 0.0, 0.0),
+            Vec3f::new(1.0, 0.0, 1.0),
+            Vec3f::new(0.0, 1.0, 1.0),
+        );
+        t1.distance_to_triangle(&nan);
+    }
 }
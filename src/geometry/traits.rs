@@ -27,6 +27,12 @@ pub trait ClosestPoint3: HasScalarType {
     fn closest_point(&self, point: &Vec3<Self::ScalarType>) -> Vec3<Self::ScalarType>;
 }
 
+/// Closest points between two primitives query
+pub trait ClosestPoints3<TPrimitive: HasScalarType>: HasScalarType {
+    /// Returns pair of closest points, first one lies on `self`, second one on `primitive`
+    fn closest_points(&self, primitive: &TPrimitive) -> (Vec3<Self::ScalarType>, Vec3<Self::ScalarType>);
+}
+
 pub trait IntersectsTriangle3: HasScalarType {
     type Output;
 
@@ -1,8 +1,9 @@
-use nalgebra::{Vector3, Matrix3};
+use nalgebra::{Vector2, Vector3, Matrix3};
 
 pub type Vec3i = Vector3<isize>;
 pub type Vec3u = Vector3<usize>;
 pub type Vec3f = Vector3<f32>;
 pub type Vec3<T> = Vector3<T>;
+pub type Vec2<T> = Vector2<T>;
 
 pub type Mat3f = Matrix3<f32>;
@@ -1,4 +1,7 @@
+#[cfg(feature = "tabled")]
 pub mod display;
 pub mod utils;
 pub mod aliases;
 pub mod one_of;
+pub mod random;
+pub(crate) mod trace;
@@ -0,0 +1,111 @@
+use num_traits::cast;
+
+use crate::geometry::traits::RealNumber;
+
+///
+/// Small seedable pseudo-random generator (xoshiro256**) used by randomized algorithms of crate.
+/// Sequence depends only on seed, so results are reproducible across runs, platforms and dependency versions.
+///
+/// ## Example
+/// ```ignore
+/// let mut rng = Rng::new(42);
+/// let t: f32 = rng.next_real();
+/// rng.shuffle(&mut faces);
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rng {
+    state: [u64; 4],
+}
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        // State is expanded from seed with SplitMix64 as recommended by authors of xoshiro
+        let mut seed = seed;
+        let state = [(); 4].map(|_| {
+            seed = seed.wrapping_add(0x9e3779b97f4a7c15);
+            let mut z = seed;
+            z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+            z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+            z ^ (z >> 31)
+        });
+
+        Self { state }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        let result = self.state[1].wrapping_mul(5).rotate_left(7).wrapping_mul(9);
+        let t = self.state[1] << 17;
+
+        self.state[2] ^= self.state[0];
+        self.state[3] ^= self.state[1];
+        self.state[1] ^= self.state[2];
+        self.state[0] ^= self.state[3];
+        self.state[2] ^= t;
+        self.state[3] = self.state[3].rotate_left(45);
+
+        result
+    }
+
+    /// Returns uniformly distributed number in `[0, 1)`
+    #[inline]
+    pub fn next_f64(&mut self) -> f64 {
+        (self.next_u64() >> 11) as f64 * (1.0 / (1u64 << 53) as f64)
+    }
+
+    /// Returns uniformly distributed number in `[0, 1)`
+    #[inline]
+    pub fn next_real<TScalar: RealNumber>(&mut self) -> TScalar {
+        cast(self.next_f64()).unwrap()
+    }
+
+    /// Returns uniformly distributed index in `[0, len)`
+    #[inline]
+    pub fn next_index(&mut self, len: usize) -> usize {
+        debug_assert!(len > 0);
+        ((self.next_u64() as u128 * len as u128) >> 64) as usize
+    }
+
+    /// Shuffles slice in place (Fisher-Yates)
+    pub fn shuffle<T>(&mut self, slice: &mut [T]) {
+        for i in (1..slice.len()).rev() {
+            let j = self.next_index(i + 1);
+            slice.swap(i, j);
+        }
+    }
+}
+
+impl Default for Rng {
+    /// Generator with zero seed
+    #[inline]
+    fn default() -> Self {
+        Self::new(0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Rng;
+
+    #[test]
+    fn test_rng() {
+        // Reference values of SplitMix64 seeded xoshiro256**, must never change
+        let mut rng = Rng::new(0);
+        assert_eq!(rng.next_u64(), 11091344671253066420);
+        assert_eq!(rng.next_u64(), 13793997310169335082);
+        assert_ne!(Rng::new(1).next_u64(), 11091344671253066420);
+
+        let mut rng = Rng::new(7);
+        for _ in 0..1000 {
+            let value = rng.next_f64();
+            assert!((0.0..1.0).contains(&value));
+            assert!(rng.next_index(10) < 10);
+        }
+
+        let mut values: Vec<_> = (0..100).collect();
+        rng.shuffle(&mut values);
+        assert_ne!(values, (0..100).collect::<Vec<_>>());
+        values.sort();
+        assert_eq!(values, (0..100).collect::<Vec<_>>());
+    }
+}
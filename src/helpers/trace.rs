@@ -0,0 +1,64 @@
+//!
+//! Instrumentation of algorithms with [tracing](https://docs.rs/tracing) spans and counters.
+//! Macros expand to nothing unless `tracing` feature is enabled, so instrumented code costs nothing by default.
+//! Expressions of counters are evaluated lazily, only when some subscriber is interested in event.
+//!
+
+///
+/// Enters span with given name and fields, span is exited at the end of enclosing block.
+///
+/// ## Example
+/// ```ignore
+/// trace_span!("decimate", faces = mesh.faces().count());
+/// ```
+///
+macro_rules! trace_span {
+    ($name:literal $(, $field:ident = $value:expr)* $(,)?) => {
+        #[cfg(feature = "tracing")]
+        let _span = tracing::info_span!($name $(, $field = $value)*).entered();
+        #[cfg(not(feature = "tracing"))]
+        let _ = || { $(let _ = &$value;)* };
+    };
+}
+
+///
+/// Records event with counters of current span.
+///
+/// ## Example
+/// ```ignore
+/// trace_counters!(faces_processed = faces_count, collapses_done = collapses);
+/// ```
+///
+macro_rules! trace_counters {
+    ($($field:ident = $value:expr),+ $(,)?) => {
+        #[cfg(feature = "tracing")]
+        tracing::info!($($field = $value),+);
+        #[cfg(not(feature = "tracing"))]
+        let _ = || { $(let _ = &$value;)+ };
+    };
+}
+
+///
+/// Returns `true` when some subscriber is interested in events of current span, always `false` without `tracing` feature.
+/// Guards counters which can't be evaluated lazily, e.g. captured before operation consumes its inputs.
+///
+/// ## Example
+/// ```ignore
+/// let faces_before = trace_enabled!().then(|| mesh.faces().count());
+/// ```
+///
+#[cfg(feature = "voxel")]
+macro_rules! trace_enabled {
+    () => {{
+        #[cfg(feature = "tracing")]
+        let enabled = tracing::enabled!(tracing::Level::INFO);
+        #[cfg(not(feature = "tracing"))]
+        let enabled = false;
+        enabled
+    }};
+}
+
+pub(crate) use trace_counters;
+#[cfg(feature = "voxel")]
+pub(crate) use trace_enabled;
+pub(crate) use trace_span;
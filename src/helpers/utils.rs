@@ -1,5 +1,12 @@
 use std::mem::swap;
 
+use num_traits::{cast, Float};
+
+use crate::geometry::traits::RealNumber;
+
+/// Number of significant bits kept by [quantize]
+pub const QUANTIZE_BITS: u32 = 16;
+
 /// Sorts three values in ascending order
 pub fn sort3<TValue: PartialOrd>(a: &mut TValue, b: &mut TValue, c: &mut TValue) {
     if a > c {
@@ -15,6 +22,32 @@ pub fn sort3<TValue: PartialOrd>(a: &mut TValue, b: &mut TValue, c: &mut TValue)
     }
 }
 
+///
+/// Rounds value to [QUANTIZE_BITS] significant bits. Values differing only by rounding errors (e.g. computed in `f32` and `f64`,
+/// with and without fused multiply-add or by different `libm`) usually become equal, so ties between them can be broken by ids
+/// and comparisons give the same result on every platform. Rounding is done on bits of value, so it is exact everywhere.
+/// Values too small to be scaled back (below `2^-100` for `f32`) are rounded to zero.
+///
+pub fn quantize<T: RealNumber>(value: T) -> T {
+    if value.is_zero() || !value.is_finite() {
+        return value;
+    }
+
+    let (mantissa, exponent, sign) = Float::integer_decode(value);
+    let bits = u64::BITS - mantissa.leading_zeros();
+
+    if bits <= QUANTIZE_BITS {
+        return value;
+    }
+
+    let shift = bits - QUANTIZE_BITS;
+    let rounded = (mantissa + (1 << (shift - 1))) >> shift;
+    let exponent = exponent as i32 + shift as i32;
+    let two: T = cast(2.0).unwrap();
+
+    cast::<i64, T>(sign as i64 * rounded as i64).unwrap() * Float::powi(two, exponent)
+}
+
 #[macro_export]
 macro_rules! const_map_fn {
     ($name:ident, $src:ty, $dest:ty, $map:path) => {
@@ -33,3 +66,22 @@ macro_rules! const_map_fn {
         }
     };
 }
+
+#[cfg(test)]
+mod tests {
+    use super::quantize;
+
+    #[test]
+    fn test_quantize() {
+        // Same expression evaluated with different precision
+        let single = (0.1f32 + 0.2f32) * 3.0f32 / 7.0f32;
+        let double = (0.1f64 + 0.2f64) * 3.0f64 / 7.0f64;
+        assert_ne!(single as f64, double);
+        assert_eq!(quantize(single) as f64, quantize(double));
+
+        assert_eq!(quantize(-1.5f64), -1.5);
+        assert_eq!(quantize(0.0f32), 0.0);
+        assert!(quantize(f64::NAN).is_nan());
+        assert!((quantize(1234.5678f64) - 1234.5678).abs() < 1234.5678 / 65536.0);
+    }
+}
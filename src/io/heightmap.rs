@@ -0,0 +1,248 @@
+use nalgebra::Vector3;
+use num_traits::Float;
+
+use crate::{
+    geometry::{
+        primitives::{box3::Box3, ray3::Ray3},
+        traits::{HasBBox3, RealNumber},
+    },
+    helpers::aliases::Vec3,
+    mesh::traits::Mesh,
+};
+
+///
+/// Regular grid of heights, e.g. terrain elevation. Heights are stored row by row,
+/// sample `(x, y)` is placed at `origin + (x * scale.x, y * scale.y, height * scale.z)`.
+///
+/// ## Example
+/// ```ignore
+/// let heightmap = Heightmap::new(256, 256, heights).with_scale(Vec3::new(0.5, 0.5, 10.0));
+/// let terrain: CornerTableF = heightmap.to_mesh();
+///
+/// // Decimate/remesh terrain, then sample it back
+/// let resampled = Heightmap::from_mesh(&terrain, 256, 256);
+/// ```
+///
+pub struct Heightmap<TScalar: RealNumber> {
+    width: usize,
+    height: usize,
+    heights: Vec<TScalar>,
+    origin: Vec3<TScalar>,
+    scale: Vec3<TScalar>,
+}
+
+impl<TScalar: RealNumber> Heightmap<TScalar> {
+    ///
+    /// Creates heightmap with `width` samples along X axis and `height` samples along Y axis.
+    /// Number of heights should be equal to `width * height`.
+    ///
+    pub fn new(width: usize, height: usize, heights: Vec<TScalar>) -> Self {
+        assert_eq!(heights.len(), width * height, "Number of heights should match grid size");
+
+        Self {
+            width,
+            height,
+            heights,
+            origin: Vec3::zeros(),
+            scale: Vec3::repeat(TScalar::one()),
+        }
+    }
+
+    /// Set position of sample `(0, 0)` with zero height
+    #[inline]
+    pub fn with_origin(mut self, origin: Vec3<TScalar>) -> Self {
+        self.origin = origin;
+        self
+    }
+
+    /// Set distance between samples along X and Y axes (`scale.x`, `scale.y`) and height multiplier (`scale.z`)
+    #[inline]
+    pub fn with_scale(mut self, scale: Vec3<TScalar>) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    ///
+    /// Rasterizes mesh into heightmap by casting vertical rays from above, so top surface is sampled.
+    /// Grid covers bounding box of mesh in XY plane. Samples missing the mesh get lowest height of mesh.
+    ///
+    pub fn from_mesh<TMesh: Mesh<ScalarType = TScalar>>(mesh: &TMesh, width: usize, height: usize) -> Self {
+        assert!(width > 1 && height > 1, "Heightmap should have at least 2 samples along each axis");
+
+        let mut bbox = Box3::empty();
+        for face in mesh.faces() {
+            bbox.union_box(&mesh.face_positions(&face).bbox());
+        }
+
+        if !bbox.is_valid() {
+            return Self::new(width, height, vec![TScalar::zero(); width * height]);
+        }
+
+        let origin = Vec3::new(bbox.get_min().x, bbox.get_min().y, TScalar::zero());
+        let scale = Vec3::new(
+            bbox.size_x() / TScalar::from(width - 1).unwrap(),
+            bbox.size_y() / TScalar::from(height - 1).unwrap(),
+            TScalar::one(),
+        );
+
+        let mut heights = vec![TScalar::neg_infinity(); width * height];
+        let ray_start = bbox.get_max().z + TScalar::one();
+        let down = Vector3::new(TScalar::zero(), TScalar::zero(), -TScalar::one());
+
+        // Only samples under bounding box of triangle are tested against it
+        for face in mesh.faces() {
+            let triangle = mesh.face_positions(&face);
+            let triangle_bbox = triangle.bbox();
+            let (x_min, x_max) = sample_range(triangle_bbox.get_min().x, triangle_bbox.get_max().x, origin.x, scale.x, width);
+            let (y_min, y_max) = sample_range(triangle_bbox.get_min().y, triangle_bbox.get_max().y, origin.y, scale.y, height);
+
+            for y in y_min..y_max {
+                for x in x_min..x_max {
+                    let point = Vec3::new(
+                        origin.x + TScalar::from(x).unwrap() * scale.x,
+                        origin.y + TScalar::from(y).unwrap() * scale.y,
+                        ray_start,
+                    );
+
+                    if let Some((_, t)) = triangle.intersects_ray3_at(&Ray3::new(point, down)) {
+                        let sample = &mut heights[y * width + x];
+                        *sample = Float::max(*sample, ray_start - t);
+                    }
+                }
+            }
+        }
+
+        let lowest = bbox.get_min().z;
+        for sample in heights.iter_mut().filter(|h| h.is_infinite()) {
+            *sample = lowest;
+        }
+
+        Self::new(width, height, heights).with_origin(origin).with_scale(scale)
+    }
+
+    /// Builds grid mesh facing +Z direction, every grid cell is split into two triangles
+    pub fn to_mesh<TMesh: Mesh<ScalarType = TScalar>>(&self) -> TMesh {
+        let mut vertices = Vec::with_capacity(self.heights.len());
+
+        for y in 0..self.height {
+            for x in 0..self.width {
+                vertices.push(self.sample_position(x, y));
+            }
+        }
+
+        let index = |x: usize, y: usize| y * self.width + x;
+        let mut faces = Vec::with_capacity(self.width.saturating_sub(1) * self.height.saturating_sub(1) * 6);
+
+        for y in 1..self.height {
+            for x in 1..self.width {
+                let (v1, v2, v3, v4) = (index(x - 1, y - 1), index(x, y - 1), index(x, y), index(x - 1, y));
+                faces.extend([v1, v2, v3, v1, v3, v4]);
+            }
+        }
+
+        TMesh::from_vertices_and_indices(&vertices, &faces)
+    }
+
+    /// Returns position of sample in world space
+    #[inline]
+    pub fn sample_position(&self, x: usize, y: usize) -> Vec3<TScalar> {
+        self.origin
+            + Vec3::new(
+                TScalar::from(x).unwrap() * self.scale.x,
+                TScalar::from(y).unwrap() * self.scale.y,
+                self.get(x, y) * self.scale.z,
+            )
+    }
+
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> TScalar {
+        self.heights[y * self.width + x]
+    }
+
+    #[inline]
+    pub fn set(&mut self, x: usize, y: usize, value: TScalar) {
+        self.heights[y * self.width + x] = value;
+    }
+
+    /// Number of samples along X axis
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Number of samples along Y axis
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Heights stored row by row
+    #[inline]
+    pub fn heights(&self) -> &[TScalar] {
+        &self.heights
+    }
+
+    #[inline]
+    pub fn get_origin(&self) -> &Vec3<TScalar> {
+        &self.origin
+    }
+
+    #[inline]
+    pub fn get_scale(&self) -> &Vec3<TScalar> {
+        &self.scale
+    }
+}
+
+/// Returns range of sample indices lying within `[min, max]` along one axis
+#[inline]
+fn sample_range<TScalar: RealNumber>(min: TScalar, max: TScalar, origin: TScalar, spacing: TScalar, count: usize) -> (usize, usize) {
+    let first = Float::ceil((min - origin) / spacing).to_isize().unwrap_or(0).max(0) as usize;
+    let last = Float::floor((max - origin) / spacing).to_isize().unwrap_or(-1) + 1;
+
+    (first.min(count), (last.max(0) as usize).min(count))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Heightmap;
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, traits::{Mesh, TopologicalMesh}},
+    };
+
+    #[test]
+    fn test_heightmap_to_mesh() {
+        let heights = (0..12).map(|i| i as f32).collect();
+        let heightmap = Heightmap::new(4, 3, heights).with_scale(Vec3f::new(0.5, 2.0, 10.0));
+        let mesh: CornerTableF = heightmap.to_mesh();
+
+        assert_eq!(mesh.vertices().count(), 12);
+        assert_eq!(mesh.faces().count(), 12);
+        assert_eq!(heightmap.sample_position(3, 2), Vec3f::new(1.5, 4.0, 110.0));
+
+        // Faces look up
+        for face in mesh.faces() {
+            assert!(mesh.face_normal(&face).z > 0.0);
+        }
+
+        let boundary_edges = mesh.edges().filter(|e| mesh.is_edge_on_boundary(e)).count();
+        assert_eq!(boundary_edges, 10);
+    }
+
+    #[test]
+    fn test_mesh_to_heightmap() {
+        // Tilted plane z = x + 2y sampled on its own grid
+        let heights = (0..25).map(|i| ((i % 5) + 2 * (i / 5)) as f32).collect();
+        let terrain: CornerTableF = Heightmap::new(5, 5, heights).to_mesh();
+
+        let heightmap = Heightmap::from_mesh(&terrain, 9, 9);
+        assert_eq!(heightmap.get_scale(), &Vec3f::new(0.5, 0.5, 1.0));
+
+        for y in 0..9 {
+            for x in 0..9 {
+                let expected = 0.5 * x as f32 + y as f32;
+                assert!((heightmap.get(x, y) - expected).abs() < 1e-4);
+            }
+        }
+    }
+}
@@ -1 +1,242 @@
 pub mod stl;
+pub mod ply;
+pub mod obj;
+pub mod heightmap;
+
+use std::{
+    collections::HashMap,
+    fs,
+    io::{self, BufReader, Error, ErrorKind},
+    path::Path,
+};
+
+use simba::scalar::SupersetOf;
+
+use crate::mesh::traits::Mesh;
+
+use self::{obj::ObjReader, ply::PlyReader, stl::StlReader};
+
+/// Size of binary STL header and triangles count
+const STL_PREAMBLE_SIZE: usize = 84;
+/// Size of one triangle record in binary STL
+const STL_TRIANGLE_SIZE: usize = 50;
+/// Number of bytes inspected by text heuristics
+const SNIFF_SIZE: usize = 4096;
+
+///
+/// Rounding of vertex coordinates applied by writers. Reduces size of text files
+/// and improves compression of binary ones, e.g. for web delivery.
+///
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Quantization {
+    /// Round to given number of digits after decimal point
+    DecimalDigits(u32),
+    /// Round to multiples of `1 / scale`, e.g. scale `1024.0` snaps coordinates to 1/1024 grid
+    FixedPoint(f64),
+}
+
+impl Quantization {
+    /// Rounds value according to quantization
+    #[inline]
+    pub fn quantize(&self, value: f64) -> f64 {
+        let scale = match *self {
+            Quantization::DecimalDigits(digits) => 10f64.powi(digits as i32),
+            Quantization::FixedPoint(scale) => scale,
+        };
+
+        // Adding zero turns negative zero into positive one
+        (value * scale).round() / scale + 0.0
+    }
+
+    /// Formats quantized value without redundant digits
+    pub(crate) fn format(&self, value: f64) -> String {
+        match *self {
+            Quantization::DecimalDigits(digits) => {
+                let text = format!("{:.*}", digits as usize, value);
+
+                if text.contains('.') {
+                    text.trim_end_matches('0').trim_end_matches('.').to_string()
+                } else {
+                    text
+                }
+            }
+            Quantization::FixedPoint(_) => value.to_string(),
+        }
+    }
+}
+
+/// Mesh file formats recognized by [detect_format]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MeshFormat {
+    BinaryStl,
+    AsciiStl,
+    Ply,
+    Obj,
+}
+
+impl MeshFormat {
+    /// Guesses format from file extension
+    pub fn from_extension(path: &Path) -> Option<Self> {
+        let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+
+        match extension.as_str() {
+            "stl" => Some(Self::BinaryStl),
+            "ply" => Some(Self::Ply),
+            "obj" => Some(Self::Obj),
+            _ => None,
+        }
+    }
+}
+
+///
+/// Detects format of mesh file by its content:
+/// * binary STL - size of file matches number of triangles stored in header
+/// * ASCII STL - starts with `solid` keyword
+/// * PLY - starts with `ply` magic
+/// * OBJ - text with vertex (`v`) or face (`f`) statements
+///
+pub fn detect_format(bytes: &[u8]) -> Option<MeshFormat> {
+    if bytes.len() >= STL_PREAMBLE_SIZE {
+        let count = u32::from_le_bytes([bytes[80], bytes[81], bytes[82], bytes[83]]) as usize;
+
+        // Header of binary STL may start with `solid` too, so size check goes first
+        if STL_PREAMBLE_SIZE + count * STL_TRIANGLE_SIZE == bytes.len() {
+            return Some(MeshFormat::BinaryStl);
+        }
+    }
+
+    if bytes.starts_with(b"ply\n") || bytes.starts_with(b"ply\r\n") {
+        return Some(MeshFormat::Ply);
+    }
+
+    let head = &bytes[..bytes.len().min(SNIFF_SIZE)];
+    let text = match std::str::from_utf8(head) {
+        Ok(text) => text,
+        // Head may end in the middle of multibyte character
+        Err(error) if error.error_len().is_none() => std::str::from_utf8(&head[..error.valid_up_to()]).ok()?,
+        Err(_) => return None,
+    };
+
+    if text.trim_start().starts_with("solid") && text.contains("facet") {
+        return Some(MeshFormat::AsciiStl);
+    }
+
+    let is_obj = text.lines().any(|line| {
+        let keyword = line.split_whitespace().next();
+        matches!(keyword, Some("v") | Some("f") | Some("vn") | Some("vt") | Some("o") | Some("g"))
+    });
+
+    if is_obj {
+        return Some(MeshFormat::Obj);
+    }
+
+    None
+}
+
+///
+/// Reads mesh from file. Format is detected by content (see [detect_format]),
+/// extension is used only when content is not recognized.
+/// All objects of OBJ file are merged into single mesh.
+///
+pub fn read_from_file<TMesh>(path: &Path) -> io::Result<TMesh>
+where
+    TMesh: Mesh,
+    TMesh::ScalarType: SupersetOf<f32> + SupersetOf<f64>
+{
+    let bytes = fs::read(path)?;
+    let format = detect_format(&bytes)
+        .or_else(|| MeshFormat::from_extension(path))
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, "Unknown mesh format"))?;
+
+    read_from_buffer(&bytes, format)
+}
+
+/// Reads mesh from buffer, format is detected by content (see [detect_format])
+pub fn read_from_buffer_any<TMesh>(bytes: &[u8]) -> io::Result<TMesh>
+where
+    TMesh: Mesh,
+    TMesh::ScalarType: SupersetOf<f32> + SupersetOf<f64>
+{
+    let format = detect_format(bytes).ok_or_else(|| Error::new(ErrorKind::InvalidData, "Unknown mesh format"))?;
+    read_from_buffer(bytes, format)
+}
+
+/// Reads mesh of given format from buffer
+pub fn read_from_buffer<TMesh>(bytes: &[u8], format: MeshFormat) -> io::Result<TMesh>
+where
+    TMesh: Mesh,
+    TMesh::ScalarType: SupersetOf<f32> + SupersetOf<f64>
+{
+    let mut reader = BufReader::new(bytes);
+
+    match format {
+        MeshFormat::BinaryStl => StlReader::new().read_stl(&mut reader),
+        MeshFormat::Obj => {
+            let objects: Vec<(String, TMesh)> = ObjReader::new().read_obj(&mut reader)?;
+            Ok(merge_meshes(objects.iter().map(|(_, mesh)| mesh)))
+        }
+        MeshFormat::Ply => PlyReader::new().read_ply(&mut reader),
+        MeshFormat::AsciiStl => Err(Error::new(
+            ErrorKind::Unsupported,
+            format!("Reading of {:?} is not supported", format),
+        )),
+    }
+}
+
+fn merge_meshes<'a, TMesh: Mesh + 'a>(meshes: impl Iterator<Item = &'a TMesh>) -> TMesh {
+    let mut vertices = Vec::new();
+    let mut indices = Vec::new();
+
+    for mesh in meshes {
+        let offset = vertices.len();
+        let vertex_index: HashMap<_, _> = mesh
+            .vertices()
+            .enumerate()
+            .map(|(i, v)| {
+                vertices.push(*mesh.vertex_position(&v));
+                (v, offset + i)
+            })
+            .collect();
+
+        for face in mesh.faces() {
+            let (v1, v2, v3) = mesh.face_vertices(&face);
+            indices.extend_from_slice(&[vertex_index[&v1], vertex_index[&v2], vertex_index[&v3]]);
+        }
+    }
+
+    TMesh::from_vertices_and_indices(&vertices, &indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::BufWriter;
+
+    use super::{detect_format, read_from_buffer_any, stl::StlWriter, MeshFormat};
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+    };
+
+    #[test]
+    fn test_detect_format() {
+        let cube: CornerTableF = primitives::cuboid(Vec3f::new(1.0, 1.0, 1.0), 1);
+        let mut stl = Vec::new();
+        StlWriter::new().write_stl(&cube, &mut BufWriter::new(&mut stl)).unwrap();
+
+        // Binary STL with header starting with `solid`
+        stl[..5].copy_from_slice(b"solid");
+        assert_eq!(detect_format(&stl), Some(MeshFormat::BinaryStl));
+
+        let mesh: CornerTableF = read_from_buffer_any(&stl).unwrap();
+        assert_eq!(mesh.faces().count(), 12);
+
+        assert_eq!(detect_format(b"solid cube\n facet normal 0 0 1\n"), Some(MeshFormat::AsciiStl));
+        assert_eq!(detect_format(b"ply\nformat ascii 1.0\n"), Some(MeshFormat::Ply));
+        assert_eq!(detect_format(b"# comment\nv 0 0 0\nv 1 0 0\nv 0 1 0\nf 1 2 3\n"), Some(MeshFormat::Obj));
+        assert_eq!(detect_format(b"\x00\x01\x02"), None);
+
+        let obj = b"v 0 0 0\nv 1 0 0\nv 0 1 0\nv 0 0 1\no a\nf 1 2 3\no b\nf 1 2 4\n";
+        let mesh: CornerTableF = read_from_buffer_any(obj).unwrap();
+        assert_eq!(mesh.faces().count(), 2);
+    }
+}
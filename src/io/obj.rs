@@ -0,0 +1,842 @@
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, Error, ErrorKind, Write},
+    path::Path,
+};
+
+use num_traits::{cast, ToPrimitive};
+use simba::scalar::SupersetOf;
+
+use super::Quantization;
+use crate::{
+    algo::corner_normals::corner_normals,
+    geometry::traits::RealNumber,
+    helpers::aliases::{Vec2, Vec3},
+    mesh::{builder::IndexedBuilder, face_groups::FaceGroups, face_uvs::FaceUvs, traits::Mesh},
+};
+
+/// Name of object containing faces defined before first `o`/`g` statement
+const DEFAULT_OBJECT_NAME: &str = "default";
+
+///
+/// Wavefront OBJ reader. Only vertex positions, texture coordinates and faces are read, polygons are triangulated as fans.
+/// Each object (`o`) or group (`g`) is read as separate mesh, or whole file can be read as single mesh
+/// with face groups (see [ObjReader::read_obj_with_groups]) or UVs (see [ObjReader::read_obj_with_uvs]).
+///
+pub struct ObjReader;
+
+impl ObjReader {
+    pub fn new() -> Self {
+        Self {}
+    }
+
+    /// Reads named meshes from file
+    pub fn read_obj_from_file<TMesh>(&self, filepath: &Path) -> io::Result<Vec<(String, TMesh)>>
+    where
+        TMesh: Mesh,
+        TMesh::ScalarType: SupersetOf<f64>
+    {
+        let file = OpenOptions::new().read(true).open(filepath)?;
+        let mut reader = BufReader::new(file);
+
+        self.read_obj::<File, TMesh>(&mut reader)
+    }
+
+    /// Reads named meshes from buffer. Objects without faces are skipped.
+    pub fn read_obj<TBuffer, TMesh>(&self, reader: &mut BufReader<TBuffer>) -> io::Result<Vec<(String, TMesh)>>
+    where
+        TBuffer: io::Read,
+        TMesh: Mesh,
+        TMesh::ScalarType: SupersetOf<f64>
+    {
+        let mut objects = vec![ObjObject::new(DEFAULT_OBJECT_NAME.to_string())];
+
+        parse_obj(reader, |positions, statement| {
+            match statement {
+                ObjStatement::Group(name) => objects.push(ObjObject::new(name)),
+                ObjStatement::Material(_) => {}
+                ObjStatement::Face(polygon, _) => {
+                    let object = objects.last_mut().unwrap();
+                    for i in 1..polygon.len() - 1 {
+                        object.add_face(positions, [polygon[0], polygon[i], polygon[i + 1]]);
+                    }
+                }
+            }
+        })?;
+
+        let meshes = objects
+            .into_iter()
+            .filter(|object| !object.indices.is_empty())
+            .map(|object| {
+                let vertices: Vec<_> = object.vertices.iter().map(|v| v.cast::<TMesh::ScalarType>()).collect();
+                (object.name, TMesh::from_vertices_and_indices(&vertices, &object.indices))
+            })
+            .collect();
+
+        Ok(meshes)
+    }
+
+    /// Reads file as single mesh together with its face groups, see [Self::read_obj_with_groups]
+    pub fn read_obj_with_groups_from_file<TMesh>(&self, filepath: &Path) -> io::Result<(TMesh, FaceGroups<TMesh>)>
+    where
+        TMesh: Mesh,
+        TMesh::ScalarType: SupersetOf<f64>
+    {
+        let file = OpenOptions::new().read(true).open(filepath)?;
+        let mut reader = BufReader::new(file);
+
+        self.read_obj_with_groups::<File, TMesh>(&mut reader)
+    }
+
+    ///
+    /// Reads all objects as single mesh. Faces are assigned to groups by preceding `o`/`g` and `usemtl` statements,
+    /// each distinct pair of group and material makes separate face group named `group/material`
+    /// (or just `group` when no material is used).
+    ///
+    pub fn read_obj_with_groups<TBuffer, TMesh>(&self, reader: &mut BufReader<TBuffer>) -> io::Result<(TMesh, FaceGroups<TMesh>)>
+    where
+        TBuffer: io::Read,
+        TMesh: Mesh,
+        TMesh::ScalarType: SupersetOf<f64>
+    {
+        let mut object = ObjObject::new(String::new());
+        let mut group_ids = HashMap::new();
+        let mut names = Vec::new();
+        let mut triangle_groups = Vec::new();
+        let mut current = (DEFAULT_OBJECT_NAME.to_string(), None);
+
+        parse_obj(reader, |positions, statement| {
+            match statement {
+                ObjStatement::Group(name) => current.0 = name,
+                ObjStatement::Material(name) => current.1 = Some(name),
+                ObjStatement::Face(polygon, _) => {
+                    let group = *group_ids.entry(current.clone()).or_insert_with(|| {
+                        names.push(match &current.1 {
+                            Some(material) => format!("{}/{}", current.0, material),
+                            None => current.0.clone(),
+                        });
+                        names.len() - 1
+                    });
+
+                    for i in 1..polygon.len() - 1 {
+                        object.add_face(positions, [polygon[0], polygon[i], polygon[i + 1]]);
+                        triangle_groups.push(group);
+                    }
+                }
+            }
+        })?;
+
+        let vertices: Vec<_> = object.vertices.iter().map(|v| v.cast::<TMesh::ScalarType>()).collect();
+        let mesh = TMesh::from_vertices_and_indices(&vertices, &object.indices);
+
+        let triangles = object
+            .indices
+            .chunks_exact(3)
+            .zip(triangle_groups)
+            .map(|(face, group)| ([vertices[face[0]], vertices[face[1]], vertices[face[2]]], group));
+        let groups = FaceGroups::from_triangles(&mesh, triangles, names);
+
+        Ok((mesh, groups))
+    }
+
+    /// Reads file as single mesh together with its texture coordinates, see [Self::read_obj_with_uvs]
+    pub fn read_obj_with_uvs_from_file<TMesh>(&self, filepath: &Path) -> io::Result<(TMesh, FaceUvs<TMesh>)>
+    where
+        TMesh: Mesh,
+        TMesh::ScalarType: SupersetOf<f64>
+    {
+        let file = OpenOptions::new().read(true).open(filepath)?;
+        let mut reader = BufReader::new(file);
+
+        self.read_obj_with_uvs::<File, TMesh>(&mut reader)
+    }
+
+    ///
+    /// Reads all objects as single mesh with texture coordinates (`vt`) of face corners.
+    /// Faces where some of vertices have no (or invalid) texture coordinates are left without UVs.
+    ///
+    pub fn read_obj_with_uvs<TBuffer, TMesh>(&self, reader: &mut BufReader<TBuffer>) -> io::Result<(TMesh, FaceUvs<TMesh>)>
+    where
+        TBuffer: io::Read,
+        TMesh: Mesh,
+        TMesh::ScalarType: SupersetOf<f64>
+    {
+        let mut object = ObjObject::new(String::new());
+        let mut triangle_uvs = Vec::new();
+
+        parse_obj(reader, |positions, statement| {
+            if let ObjStatement::Face(polygon, uvs) = statement {
+                for i in 1..polygon.len() - 1 {
+                    object.add_face(positions, [polygon[0], polygon[i], polygon[i + 1]]);
+                    triangle_uvs.push(uvs.as_ref().map(|uvs| [uvs[0], uvs[i], uvs[i + 1]]));
+                }
+            }
+        })?;
+
+        let vertices: Vec<_> = object.vertices.iter().map(|v| v.cast::<TMesh::ScalarType>()).collect();
+        let mesh = TMesh::from_vertices_and_indices(&vertices, &object.indices);
+
+        let triangles = object
+            .indices
+            .chunks_exact(3)
+            .zip(triangle_uvs)
+            .filter_map(|(face, uvs)| {
+                let uvs = uvs?.map(|uv| uv.cast::<TMesh::ScalarType>());
+                Some(([vertices[face[0]], vertices[face[1]], vertices[face[2]]], uvs))
+            });
+        let uvs = FaceUvs::from_triangles(&mesh, triangles);
+
+        Ok((mesh, uvs))
+    }
+
+    ///
+    /// Streams vertices and faces of OBJ file from byte buffer (e.g. memory-mapped file) directly to `builder`,
+    /// see [Self::stream_obj_with_remap]. Objects, groups and texture coordinates are ignored.
+    ///
+    #[inline]
+    pub fn stream_obj<TScalar: RealNumber>(&self, bytes: &[u8], builder: &mut IndexedBuilder<TScalar>) -> io::Result<()> {
+        self.stream_obj_with_remap(bytes, builder, |_, _| None)
+    }
+
+    ///
+    /// Streams vertices and faces of OBJ file from byte buffer (e.g. memory-mapped file) directly to `builder`.
+    /// Lines are parsed in place without intermediate buffers, polygons of any size are triangulated as fans.
+    ///
+    /// `remap` is called for every vertex with its index in file (starting at `0`) and position,
+    /// it returns index of already added builder vertex to weld vertex into, or `None` to add vertex to builder.
+    /// Faces collapsed by welding are passed to builder as is, so they are culled or reported by its validation.
+    ///
+    /// ## Example
+    /// ```ignore
+    /// // Weld vertices lying on the same grid cell
+    /// let mut cells = HashMap::new();
+    /// let mut builder = IndexedBuilder::new();
+    /// let mut next = 0;
+    /// ObjReader::new().stream_obj_with_remap(&mmap, &mut builder, |_, position| {
+    ///     let cell = (position / 1e-4).map(|c| c.round() as i64);
+    ///     let existing = cells.get(&cell).copied();
+    ///     if existing.is_none() {
+    ///         cells.insert(cell, next);
+    ///         next += 1;
+    ///     }
+    ///     existing
+    /// })?;
+    /// let (mesh, report): (CornerTableF, _) = builder.finish();
+    /// ```
+    ///
+    pub fn stream_obj_with_remap<TScalar, TRemap>(&self, bytes: &[u8], builder: &mut IndexedBuilder<TScalar>, mut remap: TRemap) -> io::Result<()>
+    where
+        TScalar: RealNumber,
+        TRemap: FnMut(usize, &Vec3<TScalar>) -> Option<usize>,
+    {
+        // Builder index of every vertex of file
+        let mut vertex_map = Vec::new();
+
+        for line in bytes.split(|byte| *byte == b'\n') {
+            let mut tokens = line
+                .split(|byte| byte.is_ascii_whitespace())
+                .filter(|token| !token.is_empty())
+                .map(std::str::from_utf8);
+
+            match tokens.next() {
+                Some(Ok("v")) => {
+                    let mut coordinate = || -> io::Result<f64> {
+                        tokens
+                            .next()
+                            .and_then(|t| t.ok()?.parse().ok())
+                            .ok_or_else(|| invalid_data("Invalid vertex"))
+                    };
+
+                    let position = Vec3::new(coordinate()?, coordinate()?, coordinate()?).cast::<TScalar>();
+                    let index = match remap(vertex_map.len(), &position) {
+                        Some(index) => index,
+                        None => builder.add_vertex(position),
+                    };
+                    vertex_map.push(index);
+                }
+                Some(Ok("f")) => {
+                    let mut vertex = || -> Option<io::Result<usize>> {
+                        let token = tokens.next()?;
+                        Some(
+                            token
+                                .map_err(|_| invalid_data("Invalid face"))
+                                .and_then(|token| parse_vertex_index(token, vertex_map.len()))
+                                .map(|index| vertex_map[index]),
+                        )
+                    };
+
+                    let (Some(first), Some(previous)) = (vertex(), vertex()) else {
+                        return Err(invalid_data("Face has less than 3 vertices"));
+                    };
+                    let (first, mut previous) = (first?, previous?);
+                    let mut triangles = 0;
+
+                    while let Some(current) = vertex() {
+                        let current = current?;
+                        builder.add_face(first, previous, current);
+                        previous = current;
+                        triangles += 1;
+                    }
+
+                    if triangles == 0 {
+                        return Err(invalid_data("Face has less than 3 vertices"));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for ObjReader {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Object being read, vertices are local to object
+struct ObjObject {
+    name: String,
+    vertices: Vec<Vec3<f64>>,
+    indices: Vec<usize>,
+    vertex_map: HashMap<usize, usize>,
+}
+
+impl ObjObject {
+    fn new(name: String) -> Self {
+        Self {
+            name,
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            vertex_map: HashMap::new(),
+        }
+    }
+
+    fn add_face(&mut self, positions: &[Vec3<f64>], face: [usize; 3]) {
+        for global in face {
+            let local = *self.vertex_map.entry(global).or_insert_with(|| {
+                self.vertices.push(positions[global]);
+                self.vertices.len() - 1
+            });
+            self.indices.push(local);
+        }
+    }
+}
+
+/// Statements of OBJ file relevant for reading of faces
+enum ObjStatement {
+    /// Object (`o`) or group (`g`)
+    Group(String),
+    /// Material (`usemtl`)
+    Material(String),
+    /// Polygon given by position indices, has at least 3 vertices.
+    /// Texture coordinates of polygon vertices are given when all of them have ones.
+    Face(Vec<usize>, Option<Vec<Vec2<f64>>>),
+}
+
+/// Reads vertex positions and passes them together with other statements to `visit`
+fn parse_obj<TBuffer, TVisit>(reader: &mut BufReader<TBuffer>, mut visit: TVisit) -> io::Result<()>
+where
+    TBuffer: io::Read,
+    TVisit: FnMut(&[Vec3<f64>], ObjStatement),
+{
+    let mut positions = Vec::new();
+    let mut uvs = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let mut tokens = line.split_whitespace();
+
+        match tokens.next() {
+            Some("v") => {
+                let mut coordinate = || -> io::Result<f64> {
+                    tokens
+                        .next()
+                        .and_then(|t| t.parse().ok())
+                        .ok_or_else(|| invalid_data("Invalid vertex"))
+                };
+
+                positions.push(Vec3::new(coordinate()?, coordinate()?, coordinate()?));
+            }
+            Some("vt") => {
+                let mut coordinate = || -> io::Result<f64> {
+                    tokens
+                        .next()
+                        .and_then(|t| t.parse().ok())
+                        .ok_or_else(|| invalid_data("Invalid texture coordinates"))
+                };
+
+                uvs.push(Vec2::new(coordinate()?, coordinate()?));
+            }
+            Some("o") | Some("g") => {
+                let name = tokens.collect::<Vec<_>>().join(" ");
+                visit(&positions, ObjStatement::Group(name));
+            }
+            Some("usemtl") => {
+                let name = tokens.collect::<Vec<_>>().join(" ");
+                visit(&positions, ObjStatement::Material(name));
+            }
+            Some("f") => {
+                let tokens: Vec<_> = tokens.collect();
+                let polygon = tokens
+                    .iter()
+                    .map(|t| parse_vertex_index(t, positions.len()))
+                    .collect::<io::Result<Vec<_>>>()?;
+
+                if polygon.len() < 3 {
+                    return Err(invalid_data("Face has less than 3 vertices"));
+                }
+
+                let polygon_uvs = tokens
+                    .iter()
+                    .map(|t| parse_uv_index(t, uvs.len()).map(|index| uvs[index]))
+                    .collect::<Option<Vec<_>>>();
+
+                visit(&positions, ObjStatement::Face(polygon, polygon_uvs));
+            }
+            _ => {}
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses position index of face vertex (`v`, `v/vt`, `v//vn` or `v/vt/vn`), negative indices are relative
+fn parse_vertex_index(token: &str, vertices_count: usize) -> io::Result<usize> {
+    let index: isize = token
+        .split('/')
+        .next()
+        .and_then(|t| t.parse().ok())
+        .ok_or_else(|| invalid_data("Invalid face"))?;
+
+    let index = if index < 0 {
+        vertices_count as isize + index
+    } else {
+        index - 1
+    };
+
+    if index < 0 || index as usize >= vertices_count {
+        return Err(invalid_data("Face vertex index is out of bounds"));
+    }
+
+    Ok(index as usize)
+}
+
+///
+/// Parses texture coordinates index of face vertex (`v/vt` or `v/vt/vn`). Returns `None` when vertex has no UV
+/// or its index is invalid, UVs are optional so such faces are read without them.
+///
+fn parse_uv_index(token: &str, uvs_count: usize) -> Option<usize> {
+    let index: isize = token.split('/').nth(1)?.parse().ok()?;
+    let index = if index < 0 {
+        uvs_count as isize + index
+    } else {
+        index - 1
+    };
+
+    (index >= 0 && (index as usize) < uvs_count).then_some(index as usize)
+}
+
+#[inline]
+fn invalid_data(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+///
+/// Wavefront OBJ writer. Each mesh is written as separate object.
+///
+/// Coordinates can be quantized to reduce file size, optionally merging vertices
+/// that become coincident after quantization (faces collapsed by merge are skipped).
+/// Face-varying normals can be written to keep hard edges of mesh in viewers.
+///
+/// ## Example
+/// ```ignore
+/// ObjWriter::new()
+///     .with_quantization(Some(Quantization::DecimalDigits(4)))
+///     .with_weld(true)
+///     .with_normals(Some(30.0f64.to_radians()))
+///     .write_obj_to_file([("model", &mesh)], Path::new("model.obj"))?;
+/// ```
+///
+pub struct ObjWriter {
+    quantization: Option<Quantization>,
+    weld: bool,
+    crease_angle: Option<f64>,
+}
+
+impl ObjWriter {
+    pub fn new() -> Self {
+        Self {
+            quantization: None,
+            weld: false,
+            crease_angle: None,
+        }
+    }
+
+    /// Set rounding of vertex coordinates. `None` (default) writes full precision.
+    #[inline]
+    pub fn with_quantization(mut self, quantization: Option<Quantization>) -> Self {
+        self.quantization = quantization;
+        self
+    }
+
+    /// Merge vertices of object having same coordinates after quantization. Default is `false`.
+    #[inline]
+    pub fn with_weld(mut self, weld: bool) -> Self {
+        self.weld = weld;
+        self
+    }
+
+    ///
+    /// Write face-varying normals (see [corner_normals]) with given crease angle in radians.
+    /// Edges with dihedral angle above crease angle are shaded as hard. `None` (default) writes no normals.
+    ///
+    #[inline]
+    pub fn with_normals(mut self, crease_angle: Option<f64>) -> Self {
+        self.crease_angle = crease_angle;
+        self
+    }
+
+    /// Writes named meshes to file
+    pub fn write_obj_to_file<'a, TMesh, TObjects>(&self, objects: TObjects, path: &Path) -> io::Result<()>
+    where
+        TMesh: Mesh + 'a,
+        TObjects: IntoIterator<Item = (&'a str, &'a TMesh)>
+    {
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(path)?;
+        let mut writer = BufWriter::new(file);
+
+        self.write_obj(objects, &mut writer)
+    }
+
+    /// Writes named meshes to buffer
+    pub fn write_obj<'a, TBuffer, TMesh, TObjects>(&self, objects: TObjects, writer: &mut BufWriter<TBuffer>) -> io::Result<()>
+    where
+        TBuffer: Write,
+        TMesh: Mesh + 'a,
+        TObjects: IntoIterator<Item = (&'a str, &'a TMesh)>
+    {
+        // OBJ indices are global and start from 1
+        let mut index_offset = 1;
+        let mut normal_offset = 1;
+
+        for (name, mesh) in objects {
+            writeln!(writer, "o {}", name)?;
+
+            let mut vertex_index = HashMap::new();
+            let mut welded = HashMap::new();
+            let mut written = 0;
+
+            for vertex in mesh.vertices() {
+                let position = mesh.vertex_position(&vertex);
+                let mut coordinates = [position.x, position.y, position.z].map(|c| c.to_f64().unwrap_or(0.0));
+
+                if let Some(quantization) = &self.quantization {
+                    coordinates = coordinates.map(|c| quantization.quantize(c));
+                }
+
+                if self.weld {
+                    if let Some(index) = welded.get(&coordinates.map(f64::to_bits)) {
+                        vertex_index.insert(vertex, *index);
+                        continue;
+                    }
+                }
+
+                let [x, y, z] = coordinates.map(|c| match &self.quantization {
+                    Some(quantization) => quantization.format(c),
+                    None => c.to_string(),
+                });
+                writeln!(writer, "v {} {} {}", x, y, z)?;
+
+                let index = index_offset + written;
+                written += 1;
+                vertex_index.insert(vertex, index);
+
+                if self.weld {
+                    welded.insert(coordinates.map(f64::to_bits), index);
+                }
+            }
+
+            let normals = self
+                .crease_angle
+                .map(|angle| corner_normals(mesh, cast(angle).unwrap()));
+
+            if let Some(normals) = &normals {
+                for normal in &normals.normals {
+                    let [x, y, z] = [normal.x, normal.y, normal.z].map(|c| c.to_f64().unwrap_or(0.0));
+                    writeln!(writer, "vn {} {} {}", x, y, z)?;
+                }
+            }
+
+            for (face_index, face) in mesh.faces().enumerate() {
+                let (v1, v2, v3) = mesh.face_vertices(&face);
+                let (i1, i2, i3) = (vertex_index[&v1], vertex_index[&v2], vertex_index[&v3]);
+
+                if i1 == i2 || i2 == i3 || i3 == i1 {
+                    continue;
+                }
+
+                match &normals {
+                    Some(normals) => {
+                        let [n1, n2, n3] = [0, 1, 2].map(|k| normal_offset + normals.indices[face_index * 3 + k]);
+                        writeln!(writer, "f {}//{} {}//{} {}//{}", i1, n1, i2, n2, i3, n3)?;
+                    }
+                    None => writeln!(writer, "f {} {} {}", i1, i2, i3)?,
+                }
+            }
+
+            index_offset += written;
+            normal_offset += normals.map_or(0, |normals| normals.normals.len());
+        }
+
+        writer.flush()
+    }
+}
+
+impl Default for ObjWriter {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, BufWriter};
+
+    use super::{ObjReader, ObjWriter};
+    use crate::{
+        io::Quantization,
+        helpers::aliases::{Vec2, Vec3f},
+        mesh::{
+            builder::IndexedBuilder,
+            corner_table::prelude::CornerTableF,
+            primitives,
+            traits::{Mesh, TopologicalMesh},
+        },
+    };
+
+    #[test]
+    fn test_read_obj_groups() {
+        let obj = "\
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+o quad
+f 1/1 2/2 3/3 4/4
+g triangle
+f -4//1 -3//1 -1//1
+";
+        let objects: Vec<(String, CornerTableF)> = ObjReader::new()
+            .read_obj(&mut BufReader::new(obj.as_bytes()))
+            .unwrap();
+
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0].0, "quad");
+        assert_eq!(objects[0].1.faces().count(), 2);
+        assert_eq!(objects[0].1.vertices().count(), 4);
+        assert_eq!(objects[1].0, "triangle");
+        assert_eq!(objects[1].1.faces().count(), 1);
+        assert_eq!(objects[1].1.vertices().count(), 3);
+    }
+
+    #[test]
+    fn test_read_obj_with_groups() {
+        let obj = "\
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 2 0 0
+v 2 1 0
+f 1 2 3
+g side
+usemtl red
+f 1 3 4
+usemtl blue
+f 2 5 6 3
+";
+        let (mesh, groups): (CornerTableF, _) = ObjReader::new()
+            .read_obj_with_groups(&mut BufReader::new(obj.as_bytes()))
+            .unwrap();
+
+        assert_eq!(mesh.faces().count(), 4);
+        assert_eq!(mesh.vertices().count(), 6);
+        assert_eq!(groups.groups_count(), 3);
+        assert_eq!(groups.group_name(0), Some("default"));
+        assert_eq!(groups.group_name(1), Some("side/red"));
+        assert_eq!(groups.group_name(2), Some("side/blue"));
+        assert_eq!(groups.group_faces(2).count(), 2);
+        assert!(mesh.faces().all(|face| groups.face_group(&face).is_some()));
+
+        // Diagonal 1-3 and edge 2-3 separate groups
+        assert_eq!(groups.boundary_edges(&mesh).len(), 2);
+    }
+
+    #[test]
+    fn test_read_obj_with_uvs() {
+        // Second quad is mapped to separate chart, last triangle has no UVs
+        let obj = "\
+v 0 0 0
+v 1 0 0
+v 1 1 0
+v 0 1 0
+v 2 0 0
+v 2 1 0
+v 0 -1 0
+vt 0 0
+vt 0.5 0
+vt 0.5 0.5
+vt 0 0.5
+vt 1 1
+vt 1 0.5
+f 1/1 2/2 3/3 4/4
+f 2/6/1 5/2 6/3 3/5
+f 1 7 2
+";
+        let (mesh, uvs): (CornerTableF, _) = ObjReader::new()
+            .read_obj_with_uvs(&mut BufReader::new(obj.as_bytes()))
+            .unwrap();
+
+        assert_eq!(mesh.faces().count(), 5);
+        assert_eq!(mesh.faces().filter(|face| uvs.face_uvs(face).is_some()).count(), 4);
+
+        let vertex = mesh.vertices().find(|v| *mesh.vertex_position(v) == Vec3f::new(1.0, 1.0, 0.0)).unwrap();
+        let face = mesh.faces().find(|f| mesh.face_positions(f).center().x < 1.0 && uvs.face_uvs(f).is_some()).unwrap();
+        assert_eq!(uvs.vertex_uv(&mesh, &face, &vertex), Some(Vec2::new(0.5, 0.5)));
+
+        // Edge 2-3 is seam, edge 1-2 is between faces with and without UVs
+        assert_eq!(uvs.seam_edges(&mesh).len(), 2);
+    }
+
+    #[test]
+    fn test_stream_obj() {
+        // Two quads and triangle, vertices 5 and 6 duplicate vertices 2 and 3
+        let obj = b"\
+v 0 0 0\r
+v 1 0 0\r
+v 1 1 0\r
+v 0 1 0
+v 1 0 0
+v 1 1 0
+v 2 0 0
+v 2 1 0
+v 3 0.5 0
+o ignored
+vt 0 0
+f 1/1 2/1 3/1 4/1
+f 5//1 7 8 6
+f -3 -1 -2
+";
+        let mut builder = IndexedBuilder::new();
+        ObjReader::new().stream_obj(obj, &mut builder).unwrap();
+        let (mesh, report): (CornerTableF, _) = builder.finish();
+        assert!(report.is_clean());
+        assert_eq!(mesh.vertices().count(), 9);
+        assert_eq!(mesh.faces().count(), 5);
+        assert_eq!(mesh.edges().filter(|edge| mesh.is_edge_on_boundary(edge)).count(), 9);
+
+        // Weld duplicates on the fly
+        let mut builder = IndexedBuilder::new();
+        let mut welded = Vec::new();
+        ObjReader::new()
+            .stream_obj_with_remap(obj, &mut builder, |index, position: &Vec3f| {
+                let existing = welded.iter().position(|p| p == position);
+                if existing.is_none() {
+                    welded.push(*position);
+                }
+                assert!(index < 9);
+                existing
+            })
+            .unwrap();
+        let (mesh, _): (CornerTableF, _) = builder.finish();
+        assert_eq!(mesh.vertices().count(), 7);
+        assert_eq!(mesh.faces().count(), 5);
+        assert_eq!(mesh.edges().filter(|edge| mesh.is_edge_on_boundary(edge)).count(), 7);
+
+        let mut builder = IndexedBuilder::<f32>::new();
+        assert!(ObjReader::new().stream_obj(b"v 0 0 0\nv 1 0 0\nf 1 2\n", &mut builder).is_err());
+        assert!(ObjReader::new().stream_obj(b"v 0 0 0\nf 1 2 4\n", &mut builder).is_err());
+        assert!(ObjReader::new().stream_obj(b"v 0 0\n", &mut builder).is_err());
+    }
+
+    #[test]
+    fn test_write_read_obj() {
+        let cube: CornerTableF = primitives::cuboid(Vec3f::new(1.0, 1.0, 1.0), 2);
+        let plane: CornerTableF = primitives::plane(1.0, 1.0, 3, 3);
+
+        let mut buffer = Vec::new();
+        ObjWriter::new()
+            .write_obj([("cube", &cube), ("plane", &plane)], &mut BufWriter::new(&mut buffer))
+            .unwrap();
+
+        let objects: Vec<(String, CornerTableF)> = ObjReader::new()
+            .read_obj(&mut BufReader::new(buffer.as_slice()))
+            .unwrap();
+
+        assert_eq!(objects.len(), 2);
+        assert_eq!(objects[0].0, "cube");
+        assert_eq!(objects[0].1.faces().count(), cube.faces().count());
+        assert_eq!(objects[1].0, "plane");
+        assert_eq!(objects[1].1.vertices().count(), plane.vertices().count());
+    }
+
+    #[test]
+    fn test_write_quantized_obj() {
+        let vertices = [
+            Vec3f::new(0.0, 0.0, 0.0),
+            Vec3f::new(1.0, 0.0, 0.0),
+            Vec3f::new(0.0, 1.0, 0.0),
+            Vec3f::new(1.0, 1.0, 0.0),
+            Vec3f::new(1.0, 1.001, 0.0),
+            Vec3f::new(-0.0001, 0.251, 0.0),
+        ];
+        let mesh = CornerTableF::from_vertices_and_indices(&vertices, &[0, 1, 2, 1, 3, 2, 1, 4, 3, 0, 2, 5]);
+
+        let mut buffer = Vec::new();
+        ObjWriter::new()
+            .with_quantization(Some(Quantization::DecimalDigits(2)))
+            .with_weld(true)
+            .write_obj([("quantized", &mesh)], &mut BufWriter::new(&mut buffer))
+            .unwrap();
+
+        let text = String::from_utf8(buffer).unwrap();
+        let lines: Vec<_> = text.lines().collect();
+        assert!(lines.contains(&"v 1 1 0"));
+        assert!(lines.contains(&"v 0 0.25 0"));
+
+        // Vertex 4 is welded with vertex 3 and face between them is skipped
+        assert_eq!(lines.iter().filter(|line| line.starts_with("v ")).count(), 5);
+        assert_eq!(lines.iter().filter(|line| line.starts_with("f ")).count(), 3);
+    }
+
+    #[test]
+    fn test_write_obj_normals() {
+        let cube: CornerTableF = primitives::cuboid(Vec3f::new(1.0, 1.0, 1.0), 1);
+
+        let mut buffer = Vec::new();
+        ObjWriter::new()
+            .with_normals(Some(30.0f64.to_radians()))
+            .write_obj([("first", &cube), ("second", &cube)], &mut BufWriter::new(&mut buffer))
+            .unwrap();
+
+        let text = String::from_utf8(buffer.clone()).unwrap();
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines.iter().filter(|line| line.starts_with("vn ")).count(), 48);
+        assert!(lines.iter().any(|line| line.starts_with("f ") && line.contains("//48")));
+
+        let objects: Vec<(String, CornerTableF)> = ObjReader::new()
+            .read_obj(&mut BufReader::new(buffer.as_slice()))
+            .unwrap();
+        assert_eq!(objects[1].1.faces().count(), cube.faces().count());
+    }
+}
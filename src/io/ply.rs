@@ -0,0 +1,1154 @@
+use std::{
+    collections::HashMap,
+    fs::{File, OpenOptions},
+    io::{self, BufRead, BufReader, BufWriter, Error, ErrorKind, Read, Write},
+    path::Path,
+};
+
+use num_traits::cast;
+
+use super::Quantization;
+use crate::{
+    algo::corner_normals::corner_normals,
+    data_structures::vertex_index_map::HashablePoint,
+    geometry::traits::RealNumber,
+    helpers::aliases::Vec3,
+    mesh::{
+        face_groups::face_key,
+        traits::{Mesh, PropertyMap},
+        vertex_groups::VertexGroups,
+    },
+};
+
+///
+/// Named scalar attribute of points written to PLY as `float` vertex property.
+///
+pub struct ScalarProperty<'a> {
+    name: &'a str,
+    values: &'a [f32],
+}
+
+impl<'a> ScalarProperty<'a> {
+    pub fn new(name: &'a str, values: &'a [f32]) -> Self {
+        Self { name, values }
+    }
+}
+
+///
+/// Writes point clouds to binary little-endian PLY files.
+/// Points can be written together with normals and arbitrary scalar properties,
+/// so sampling results and SDF gradient probes can be inspected in external viewers.
+/// Meshes can be written with normals split along hard edges.
+/// Point coordinates can be quantized to improve compression of files.
+///
+/// ## Example
+/// ```ignore
+/// let distances = ScalarProperty::new("distance", &distances);
+/// PlyWriter::new().write_point_cloud_to_file(&points, Some(&normals), &[distances], Path::new("points.ply"))?;
+/// ```
+///
+pub struct PlyWriter {
+    quantization: Option<Quantization>,
+}
+
+impl PlyWriter {
+    pub fn new() -> Self {
+        PlyWriter { quantization: None }
+    }
+
+    /// Set rounding of point coordinates (normals are not quantized). `None` (default) writes full precision.
+    #[inline]
+    pub fn with_quantization(mut self, quantization: Option<Quantization>) -> Self {
+        self.quantization = quantization;
+        self
+    }
+
+    pub fn write_point_cloud_to_file<TScalar: RealNumber>(
+        &self,
+        points: &[Vec3<TScalar>],
+        normals: Option<&[Vec3<TScalar>]>,
+        properties: &[ScalarProperty],
+        path: &Path,
+    ) -> io::Result<()> {
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(path)?;
+        let mut writer = BufWriter::new(file);
+
+        self.write_point_cloud(points, normals, properties, &mut writer)
+    }
+
+    ///
+    /// Writes points without faces. `normals` and `values` of each property should have same length as `points`.
+    ///
+    pub fn write_point_cloud<TBuffer: Write, TScalar: RealNumber>(
+        &self,
+        points: &[Vec3<TScalar>],
+        normals: Option<&[Vec3<TScalar>]>,
+        properties: &[ScalarProperty],
+        writer: &mut BufWriter<TBuffer>,
+    ) -> io::Result<()> {
+        if normals.is_some_and(|normals| normals.len() != points.len()) {
+            return Err(Error::new(ErrorKind::InvalidInput, "Number of normals does not match number of points"));
+        }
+
+        if let Some(property) = properties.iter().find(|p| p.values.len() != points.len()) {
+            return Err(Error::new(
+                ErrorKind::InvalidInput,
+                format!("Number of values of property '{}' does not match number of points", property.name),
+            ));
+        }
+
+        self.write_header(writer, points.len(), normals.is_some(), properties, 0)?;
+
+        for (i, point) in points.iter().enumerate() {
+            self.write_vector(writer, point, self.quantization)?;
+
+            if let Some(normals) = normals {
+                self.write_vector(writer, &normals[i], None)?;
+            }
+
+            for property in properties {
+                writer.write_all(&property.values[i].to_le_bytes())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    pub fn write_vertices_to_file<TMesh, TPropertyMap>(
+        &self,
+        mesh: &TMesh,
+        write_normals: bool,
+        properties: &[(&str, &TPropertyMap)],
+        path: &Path,
+    ) -> io::Result<()>
+    where
+        TMesh: Mesh,
+        TPropertyMap: PropertyMap<TMesh::VertexDescriptor, f32>,
+    {
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(path)?;
+        let mut writer = BufWriter::new(file);
+
+        self.write_vertices(mesh, write_normals, properties, &mut writer)
+    }
+
+    ///
+    /// Writes vertices of mesh as point cloud. Optionally writes vertex normals and scalar properties
+    /// from vertex property maps. Vertices without normal or property value are written with zeros.
+    ///
+    pub fn write_vertices<TBuffer, TMesh, TPropertyMap>(
+        &self,
+        mesh: &TMesh,
+        write_normals: bool,
+        properties: &[(&str, &TPropertyMap)],
+        writer: &mut BufWriter<TBuffer>,
+    ) -> io::Result<()>
+    where
+        TBuffer: Write,
+        TMesh: Mesh,
+        TPropertyMap: PropertyMap<TMesh::VertexDescriptor, f32>,
+    {
+        let vertices: Vec<_> = mesh.vertices().collect();
+        let points: Vec<_> = vertices.iter().map(|v| *mesh.vertex_position(v)).collect();
+        let normals: Option<Vec<_>> = write_normals.then(|| {
+            vertices
+                .iter()
+                .map(|v| mesh.vertex_normal(v).unwrap_or_else(Vec3::zeros))
+                .collect()
+        });
+        let values: Vec<Vec<f32>> = properties
+            .iter()
+            .map(|(_, map)| {
+                vertices
+                    .iter()
+                    .map(|v| map.get(v).copied().unwrap_or(0.0))
+                    .collect()
+            })
+            .collect();
+        let properties: Vec<_> = properties
+            .iter()
+            .zip(values.iter())
+            .map(|((name, _), values)| ScalarProperty::new(name, values))
+            .collect();
+
+        self.write_point_cloud(&points, normals.as_deref(), &properties, writer)
+    }
+
+    pub fn write_mesh_to_file<TMesh: Mesh>(&self, mesh: &TMesh, crease_angle: Option<f64>, path: &Path) -> io::Result<()> {
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(path)?;
+        let mut writer = BufWriter::new(file);
+
+        self.write_mesh(mesh, crease_angle, &mut writer)
+    }
+
+    ///
+    /// Writes mesh with faces. When `crease_angle` (in radians) is set, face-varying normals are computed
+    /// (see [corner_normals]) and vertices are split along edges with dihedral angle above it,
+    /// so hard edges are preserved. Without crease angle vertices are written without normals.
+    ///
+    pub fn write_mesh<TBuffer: Write, TMesh: Mesh>(
+        &self,
+        mesh: &TMesh,
+        crease_angle: Option<f64>,
+        writer: &mut BufWriter<TBuffer>,
+    ) -> io::Result<()> {
+        let (points, normals, indices) = match crease_angle {
+            Some(angle) => {
+                let (points, normals, indices) = corner_normals(mesh, cast(angle).unwrap()).split_vertices(mesh);
+                (points, Some(normals), indices)
+            }
+            None => {
+                let (points, indices) = indexed(mesh);
+                (points, None, indices)
+            }
+        };
+
+        self.write_header(writer, points.len(), normals.is_some(), &[], indices.len() / 3)?;
+
+        for (i, point) in points.iter().enumerate() {
+            self.write_vector(writer, point, self.quantization)?;
+
+            if let Some(normals) = &normals {
+                self.write_vector(writer, &normals[i], None)?;
+            }
+        }
+
+        self.write_faces(writer, &indices)
+    }
+
+    pub fn write_mesh_with_vertex_groups_to_file<TMesh: Mesh>(
+        &self,
+        mesh: &TMesh,
+        groups: &VertexGroups<TMesh>,
+        path: &Path,
+    ) -> io::Result<()> {
+        let file = OpenOptions::new()
+            .write(true)
+            .truncate(true)
+            .create(true)
+            .open(path)?;
+        let mut writer = BufWriter::new(file);
+
+        self.write_mesh_with_vertex_groups(mesh, groups, &mut writer)
+    }
+
+    ///
+    /// Writes mesh with faces and vertex groups stored as `float` vertex properties named after groups.
+    /// Vertices missing in group are written with zero weight. Vertices are written without normals.
+    ///
+    pub fn write_mesh_with_vertex_groups<TBuffer: Write, TMesh: Mesh>(
+        &self,
+        mesh: &TMesh,
+        groups: &VertexGroups<TMesh>,
+        writer: &mut BufWriter<TBuffer>,
+    ) -> io::Result<()> {
+        let (points, indices) = indexed(mesh);
+        let values: Vec<Vec<f32>> = (0..groups.groups_count())
+            .map(|group| mesh.vertices().map(|v| groups.weight(group, &v).unwrap_or(0.0)).collect())
+            .collect();
+        let properties: Vec<_> = values
+            .iter()
+            .enumerate()
+            .map(|(group, values)| ScalarProperty::new(groups.group_name(group).unwrap_or_default(), values))
+            .collect();
+
+        self.write_header(writer, points.len(), false, &properties, indices.len() / 3)?;
+
+        for (i, point) in points.iter().enumerate() {
+            self.write_vector(writer, point, self.quantization)?;
+
+            for property in &properties {
+                writer.write_all(&property.values[i].to_le_bytes())?;
+            }
+        }
+
+        self.write_faces(writer, &indices)
+    }
+
+    fn write_header<TBuffer: Write>(
+        &self,
+        writer: &mut BufWriter<TBuffer>,
+        points_count: usize,
+        normals: bool,
+        properties: &[ScalarProperty],
+        faces_count: usize,
+    ) -> io::Result<()> {
+        writeln!(writer, "ply")?;
+        writeln!(writer, "format binary_little_endian 1.0")?;
+        writeln!(writer, "element vertex {}", points_count)?;
+        writeln!(writer, "property float x")?;
+        writeln!(writer, "property float y")?;
+        writeln!(writer, "property float z")?;
+
+        if normals {
+            writeln!(writer, "property float nx")?;
+            writeln!(writer, "property float ny")?;
+            writeln!(writer, "property float nz")?;
+        }
+
+        for property in properties {
+            if property.name.is_empty() || property.name.contains(char::is_whitespace) {
+                return Err(Error::new(ErrorKind::InvalidInput, format!("Invalid property name '{}'", property.name)));
+            }
+
+            writeln!(writer, "property float {}", property.name)?;
+        }
+
+        if faces_count > 0 {
+            writeln!(writer, "element face {}", faces_count)?;
+            writeln!(writer, "property list uchar int vertex_indices")?;
+        }
+
+        writeln!(writer, "end_header")
+    }
+
+    fn write_faces<TBuffer: Write>(&self, writer: &mut BufWriter<TBuffer>, indices: &[usize]) -> io::Result<()> {
+        for face in indices.chunks_exact(3) {
+            writer.write_all(&[3])?;
+
+            for index in face {
+                writer.write_all(&(*index as i32).to_le_bytes())?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_vector<TBuffer: Write, TScalar: RealNumber>(
+        &self,
+        writer: &mut BufWriter<TBuffer>,
+        vector: &Vec3<TScalar>,
+        quantization: Option<Quantization>,
+    ) -> io::Result<()> {
+        for coordinate in vector.iter() {
+            let coordinate = match (quantization, coordinate.to_f64()) {
+                (Some(quantization), Some(coordinate)) => quantization.quantize(coordinate) as f32,
+                _ => coordinate.to_f32().unwrap_or(f32::NAN),
+            };
+            writer.write_all(&coordinate.to_le_bytes())?;
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for PlyWriter {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+///
+/// Reads meshes from binary little-endian PLY files. Polygonal faces are triangulated as fans.
+/// Scalar vertex properties other than position and normal are read as vertex groups (weight maps),
+/// see [PlyReader::read_ply_with_vertex_groups], all vertex and face properties can be read with [PlyReader::read_ply_with_attributes].
+/// Unknown elements are skipped.
+///
+/// ## Example
+/// ```ignore
+/// let (mesh, groups) = PlyReader::new().read_ply_with_vertex_groups_from_file::<CornerTableF>(Path::new("skin.ply"))?;
+/// ```
+///
+pub struct PlyReader;
+
+impl PlyReader {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Reads mesh from file
+    pub fn read_ply_from_file<TMesh: Mesh>(&self, path: &Path) -> io::Result<TMesh> {
+        let file = OpenOptions::new().read(true).open(path)?;
+        let mut reader = BufReader::new(file);
+
+        self.read_ply::<File, TMesh>(&mut reader)
+    }
+
+    /// Reads mesh from buffer, custom vertex properties are ignored
+    pub fn read_ply<TBuffer: Read, TMesh: Mesh>(&self, reader: &mut BufReader<TBuffer>) -> io::Result<TMesh> {
+        self.read_ply_with_vertex_groups(reader).map(|(mesh, _)| mesh)
+    }
+
+    /// Reads file as mesh together with its vertex groups, see [Self::read_ply_with_vertex_groups]
+    pub fn read_ply_with_vertex_groups_from_file<TMesh: Mesh>(&self, path: &Path) -> io::Result<(TMesh, VertexGroups<TMesh>)> {
+        let file = OpenOptions::new().read(true).open(path)?;
+        let mut reader = BufReader::new(file);
+
+        self.read_ply_with_vertex_groups::<File, TMesh>(&mut reader)
+    }
+
+    ///
+    /// Reads mesh and its vertex groups. Every scalar vertex property except `x`, `y`, `z`, `nx`, `ny`, `nz`
+    /// becomes group with name of property and weight of every vertex.
+    ///
+    pub fn read_ply_with_vertex_groups<TBuffer: Read, TMesh: Mesh>(
+        &self,
+        reader: &mut BufReader<TBuffer>,
+    ) -> io::Result<(TMesh, VertexGroups<TMesh>)> {
+        let data = self.read_elements(reader)?;
+        let mesh = TMesh::from_vertices_and_indices(&data.points, &data.indices);
+
+        let (names, values) = data
+            .vertex
+            .into_iter()
+            .filter(|(name, _)| !VERTEX_PROPERTIES.contains(&name.as_str()))
+            .filter_map(|(name, attribute)| {
+                let values = (0..attribute.len()).map(|row| attribute.scalar(row).map(|value| value as f32)).collect::<Option<_>>()?;
+                Some((name, values))
+            })
+            .unzip();
+        let groups = VertexGroups::from_points(&mesh, &data.points, names, values);
+
+        Ok((mesh, groups))
+    }
+
+    /// Reads file as mesh together with all properties of its vertices and faces, see [Self::read_ply_with_attributes]
+    pub fn read_ply_with_attributes_from_file<TMesh: Mesh>(&self, path: &Path) -> io::Result<(TMesh, PlyAttributes<TMesh>)> {
+        let file = OpenOptions::new().read(true).open(path)?;
+        let mut reader = BufReader::new(file);
+
+        self.read_ply_with_attributes::<File, TMesh>(&mut reader)
+    }
+
+    ///
+    /// Reads mesh and all properties (scalars and lists) of `vertex` and `face` elements, including positions and
+    /// vertex indices, e.g. confidence or intensity of scanned points. Properties are matched to vertices and faces of mesh
+    /// by positions, all triangles of polygonal face share its properties.
+    ///
+    /// ## Example
+    /// ```ignore
+    /// let (mesh, attributes) = PlyReader::new().read_ply_with_attributes_from_file::<CornerTableF>(Path::new("scan.ply"))?;
+    /// let confidence = attributes.vertex_value("confidence", &vertex).unwrap_or(0.0);
+    /// ```
+    ///
+    pub fn read_ply_with_attributes<TBuffer: Read, TMesh: Mesh>(
+        &self,
+        reader: &mut BufReader<TBuffer>,
+    ) -> io::Result<(TMesh, PlyAttributes<TMesh>)> {
+        let data = self.read_elements(reader)?;
+        let mesh = TMesh::from_vertices_and_indices(&data.points, &data.indices);
+        let triangles = data.indices.chunks_exact(3).zip(data.face_rows).map(|(face, row)| {
+            ([data.points[face[0]], data.points[face[1]], data.points[face[2]]], row)
+        });
+        let attributes = PlyAttributes::new(&mesh, &data.points, triangles, data.vertex, data.face);
+
+        Ok((mesh, attributes))
+    }
+
+    /// Reads body of file, polygons are triangulated as fans
+    fn read_elements<TBuffer: Read, TScalar: RealNumber>(&self, reader: &mut BufReader<TBuffer>) -> io::Result<PlyData<TScalar>> {
+        let elements = self.read_header(reader)?;
+        let mut data = PlyData {
+            points: Vec::new(),
+            indices: Vec::new(),
+            face_rows: Vec::new(),
+            vertex: Vec::new(),
+            face: Vec::new(),
+        };
+
+        for element in &elements {
+            let mut attributes: Vec<_> = element
+                .properties
+                .iter()
+                .map(|property| (property.name.clone(), PlyAttribute::with_capacity(&property.kind, element.count)))
+                .collect();
+
+            match element.name.as_str() {
+                "vertex" => {
+                    let coordinates = ["x", "y", "z"].map(|name| element.properties.iter().position(|p| p.name == name));
+                    let [Some(x), Some(y), Some(z)] = coordinates else {
+                        return Err(Error::new(ErrorKind::InvalidData, "Vertex element has no position properties"));
+                    };
+
+                    data.points.reserve(element.count);
+
+                    for _ in 0..element.count {
+                        let mut point = Vec3::zeros();
+
+                        for (i, property) in element.properties.iter().enumerate() {
+                            let value = attributes[i].1.read(reader, &property.kind)?;
+
+                            if i == x {
+                                point.x = cast(value).unwrap();
+                            } else if i == y {
+                                point.y = cast(value).unwrap();
+                            } else if i == z {
+                                point.z = cast(value).unwrap();
+                            }
+                        }
+
+                        data.points.push(point);
+                    }
+
+                    data.vertex = attributes;
+                }
+                "face" => {
+                    let faces = element
+                        .properties
+                        .iter()
+                        .position(|p| p.name == "vertex_indices" || p.name == "vertex_index");
+
+                    for row in 0..element.count {
+                        for (i, property) in element.properties.iter().enumerate() {
+                            attributes[i].1.read(reader, &property.kind)?;
+                        }
+
+                        let Some(PlyAttribute::List(polygons)) = faces.map(|i| &attributes[i].1) else {
+                            continue;
+                        };
+
+                        // Triangulate polygon as fan
+                        let polygon = &polygons[row];
+                        for j in 2..polygon.len() {
+                            data.indices.extend_from_slice(&[polygon[0] as usize, polygon[j - 1] as usize, polygon[j] as usize]);
+                            data.face_rows.push(row);
+                        }
+                    }
+
+                    data.face = attributes;
+                }
+                _ => {
+                    for _ in 0..element.count {
+                        for property in &element.properties {
+                            match property.kind {
+                                PlyPropertyKind::Scalar(scalar) => {
+                                    scalar.read(reader)?;
+                                }
+                                PlyPropertyKind::List(..) => self.skip_list(reader, &property.kind)?,
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        if data.indices.iter().any(|index| *index >= data.points.len()) {
+            return Err(Error::new(ErrorKind::InvalidData, "Face references vertex out of range"));
+        }
+
+        Ok(data)
+    }
+
+    fn read_header<TBuffer: Read>(&self, reader: &mut BufReader<TBuffer>) -> io::Result<Vec<PlyElement>> {
+        let invalid = |message: &str| Error::new(ErrorKind::InvalidData, message.to_string());
+        let mut line = String::new();
+        let mut elements: Vec<PlyElement> = Vec::new();
+
+        reader.read_line(&mut line)?;
+        if line.trim_end() != "ply" {
+            return Err(invalid("Missing PLY magic"));
+        }
+
+        loop {
+            line.clear();
+            if reader.read_line(&mut line)? == 0 {
+                return Err(invalid("Unexpected end of PLY header"));
+            }
+
+            let tokens: Vec<_> = line.split_whitespace().collect();
+
+            match tokens.as_slice() {
+                ["format", "binary_little_endian", _] => {}
+                ["format", format, _] => {
+                    return Err(Error::new(ErrorKind::Unsupported, format!("PLY format '{}' is not supported", format)));
+                }
+                ["element", name, count] => elements.push(PlyElement {
+                    name: name.to_string(),
+                    count: count.parse().map_err(|_| invalid("Invalid element count"))?,
+                    properties: Vec::new(),
+                }),
+                ["property", "list", count, item, name] => {
+                    let kind = match (PlyType::parse(count), PlyType::parse(item)) {
+                        (Some(count), Some(item)) => PlyPropertyKind::List(count, item),
+                        _ => return Err(invalid("Invalid type of list property")),
+                    };
+                    let element = elements.last_mut().ok_or_else(|| invalid("Property outside of element"))?;
+                    element.properties.push(PlyProperty { name: name.to_string(), kind });
+                }
+                ["property", scalar, name] => {
+                    let kind = PlyPropertyKind::Scalar(PlyType::parse(scalar).ok_or_else(|| invalid("Invalid type of property"))?);
+                    let element = elements.last_mut().ok_or_else(|| invalid("Property outside of element"))?;
+                    element.properties.push(PlyProperty { name: name.to_string(), kind });
+                }
+                ["end_header"] => return Ok(elements),
+                ["comment", ..] | ["obj_info", ..] | [] => {}
+                _ => return Err(invalid("Invalid PLY header")),
+            }
+        }
+    }
+
+    fn skip_list<TBuffer: Read>(&self, reader: &mut BufReader<TBuffer>, kind: &PlyPropertyKind) -> io::Result<()> {
+        if let PlyPropertyKind::List(count, item) = kind {
+            let count = count.read(reader)? as usize;
+
+            for _ in 0..count {
+                item.read(reader)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Default for PlyReader {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Vertex properties which are not read as vertex groups
+const VERTEX_PROPERTIES: [&str; 6] = ["x", "y", "z", "nx", "ny", "nz"];
+
+///
+/// Values of PLY property for every element in order of file
+///
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlyAttribute {
+    /// Values of `float` and `double` properties
+    Float(Vec<f64>),
+    /// Values of integer properties
+    Int(Vec<i64>),
+    /// Items of list properties
+    List(Vec<Vec<f64>>),
+}
+
+impl PlyAttribute {
+    fn with_capacity(kind: &PlyPropertyKind, capacity: usize) -> Self {
+        match kind {
+            PlyPropertyKind::Scalar(PlyType::Float | PlyType::Double) => Self::Float(Vec::with_capacity(capacity)),
+            PlyPropertyKind::Scalar(_) => Self::Int(Vec::with_capacity(capacity)),
+            PlyPropertyKind::List(..) => Self::List(Vec::with_capacity(capacity)),
+        }
+    }
+
+    /// Reads value of next element and returns it, items count is returned for lists
+    fn read<TBuffer: Read>(&mut self, reader: &mut BufReader<TBuffer>, kind: &PlyPropertyKind) -> io::Result<f64> {
+        match (self, kind) {
+            (Self::Float(values), PlyPropertyKind::Scalar(scalar)) => {
+                values.push(scalar.read(reader)?);
+                Ok(values[values.len() - 1])
+            }
+            (Self::Int(values), PlyPropertyKind::Scalar(scalar)) => {
+                let value = scalar.read(reader)?;
+                values.push(value as i64);
+                Ok(value)
+            }
+            (Self::List(lists), PlyPropertyKind::List(count, item)) => {
+                let count = count.read(reader)? as usize;
+                let list = (0..count).map(|_| item.read(reader)).collect::<io::Result<Vec<_>>>()?;
+                lists.push(list);
+                Ok(count as f64)
+            }
+            _ => unreachable!("Attribute is created from property kind"),
+        }
+    }
+
+    /// Number of elements
+    #[inline]
+    pub fn len(&self) -> usize {
+        match self {
+            Self::Float(values) => values.len(),
+            Self::Int(values) => values.len(),
+            Self::List(lists) => lists.len(),
+        }
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns value of scalar property of element `row`, `None` for list properties
+    #[inline]
+    pub fn scalar(&self, row: usize) -> Option<f64> {
+        match self {
+            Self::Float(values) => values.get(row).copied(),
+            Self::Int(values) => values.get(row).map(|value| *value as f64),
+            Self::List(_) => None,
+        }
+    }
+
+    /// Returns items of list property of element `row`, `None` for scalar properties
+    #[inline]
+    pub fn list(&self, row: usize) -> Option<&[f64]> {
+        match self {
+            Self::List(lists) => lists.get(row).map(Vec::as_slice),
+            _ => None,
+        }
+    }
+}
+
+///
+/// All properties of `vertex` and `face` elements of PLY file, see [PlyReader::read_ply_with_attributes].
+/// Properties are stored by name in order of file elements (rows), vertices and faces of mesh are mapped to rows they were read from.
+///
+pub struct PlyAttributes<TMesh: Mesh> {
+    vertex: Vec<(String, PlyAttribute)>,
+    face: Vec<(String, PlyAttribute)>,
+    vertex_rows: HashMap<TMesh::VertexDescriptor, usize>,
+    face_rows: HashMap<TMesh::FaceDescriptor, usize>,
+}
+
+impl<TMesh: Mesh> PlyAttributes<TMesh> {
+    /// Maps vertices and faces of `mesh` to rows by matching their positions with given points and triangles
+    fn new<TIter>(
+        mesh: &TMesh,
+        points: &[Vec3<TMesh::ScalarType>],
+        triangles: TIter,
+        vertex: Vec<(String, PlyAttribute)>,
+        face: Vec<(String, PlyAttribute)>,
+    ) -> Self
+    where
+        TIter: IntoIterator<Item = ([Vec3<TMesh::ScalarType>; 3], usize)>,
+    {
+        let point_rows: HashMap<HashablePoint<3, TMesh::ScalarType>, usize> =
+            points.iter().enumerate().map(|(row, point)| ((*point).into(), row)).collect();
+        let triangle_rows: HashMap<_, _> = triangles.into_iter().map(|(triangle, row)| (face_key(triangle), row)).collect();
+
+        let vertex_rows = mesh
+            .vertices()
+            .filter_map(|vertex| Some((vertex, *point_rows.get(&(*mesh.vertex_position(&vertex)).into())?)))
+            .collect();
+        let face_rows = mesh
+            .faces()
+            .filter_map(|face| {
+                let triangle = mesh.face_positions(&face);
+                Some((face, *triangle_rows.get(&face_key([*triangle.p1(), *triangle.p2(), *triangle.p3()]))?))
+            })
+            .collect();
+
+        Self { vertex, face, vertex_rows, face_rows }
+    }
+
+    /// Returns vertex property with given name
+    #[inline]
+    pub fn vertex_attribute(&self, name: &str) -> Option<&PlyAttribute> {
+        self.vertex.iter().find(|(n, _)| n == name).map(|(_, attribute)| attribute)
+    }
+
+    /// Returns face property with given name
+    #[inline]
+    pub fn face_attribute(&self, name: &str) -> Option<&PlyAttribute> {
+        self.face.iter().find(|(n, _)| n == name).map(|(_, attribute)| attribute)
+    }
+
+    /// Returns names and values of vertex properties in order of file header
+    #[inline]
+    pub fn vertex_attributes(&self) -> impl Iterator<Item = (&str, &PlyAttribute)> {
+        self.vertex.iter().map(|(name, attribute)| (name.as_str(), attribute))
+    }
+
+    /// Returns names and values of face properties in order of file header
+    #[inline]
+    pub fn face_attributes(&self) -> impl Iterator<Item = (&str, &PlyAttribute)> {
+        self.face.iter().map(|(name, attribute)| (name.as_str(), attribute))
+    }
+
+    /// Returns index of file element `vertex` was read from
+    #[inline]
+    pub fn vertex_row(&self, vertex: &TMesh::VertexDescriptor) -> Option<usize> {
+        self.vertex_rows.get(vertex).copied()
+    }
+
+    /// Returns index of file element `face` was read from, triangles of polygon share its row
+    #[inline]
+    pub fn face_row(&self, face: &TMesh::FaceDescriptor) -> Option<usize> {
+        self.face_rows.get(face).copied()
+    }
+
+    /// Returns value of scalar vertex property
+    #[inline]
+    pub fn vertex_value(&self, name: &str, vertex: &TMesh::VertexDescriptor) -> Option<f64> {
+        self.vertex_attribute(name)?.scalar(self.vertex_row(vertex)?)
+    }
+
+    /// Returns value of scalar face property
+    #[inline]
+    pub fn face_value(&self, name: &str, face: &TMesh::FaceDescriptor) -> Option<f64> {
+        self.face_attribute(name)?.scalar(self.face_row(face)?)
+    }
+}
+
+/// Vertices and triangles of PLY file with properties of their elements
+struct PlyData<TScalar: RealNumber> {
+    points: Vec<Vec3<TScalar>>,
+    indices: Vec<usize>,
+    /// Row of face element every triangle is made from
+    face_rows: Vec<usize>,
+    vertex: Vec<(String, PlyAttribute)>,
+    face: Vec<(String, PlyAttribute)>,
+}
+
+struct PlyElement {
+    name: String,
+    count: usize,
+    properties: Vec<PlyProperty>,
+}
+
+struct PlyProperty {
+    name: String,
+    kind: PlyPropertyKind,
+}
+
+enum PlyPropertyKind {
+    Scalar(PlyType),
+    /// Type of items count and type of items
+    List(PlyType, PlyType),
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlyType {
+    Char,
+    UChar,
+    Short,
+    UShort,
+    Int,
+    UInt,
+    Float,
+    Double,
+}
+
+impl PlyType {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "char" | "int8" => Some(Self::Char),
+            "uchar" | "uint8" => Some(Self::UChar),
+            "short" | "int16" => Some(Self::Short),
+            "ushort" | "uint16" => Some(Self::UShort),
+            "int" | "int32" => Some(Self::Int),
+            "uint" | "uint32" => Some(Self::UInt),
+            "float" | "float32" => Some(Self::Float),
+            "double" | "float64" => Some(Self::Double),
+            _ => None,
+        }
+    }
+
+    /// Reads little-endian value of this type
+    fn read<TBuffer: Read>(&self, reader: &mut BufReader<TBuffer>) -> io::Result<f64> {
+        let mut buf = [0u8; 8];
+
+        let value = match self {
+            Self::Char => {
+                reader.read_exact(&mut buf[..1])?;
+                i8::from_le_bytes([buf[0]]) as f64
+            }
+            Self::UChar => {
+                reader.read_exact(&mut buf[..1])?;
+                buf[0] as f64
+            }
+            Self::Short => {
+                reader.read_exact(&mut buf[..2])?;
+                i16::from_le_bytes([buf[0], buf[1]]) as f64
+            }
+            Self::UShort => {
+                reader.read_exact(&mut buf[..2])?;
+                u16::from_le_bytes([buf[0], buf[1]]) as f64
+            }
+            Self::Int => {
+                reader.read_exact(&mut buf[..4])?;
+                i32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as f64
+            }
+            Self::UInt => {
+                reader.read_exact(&mut buf[..4])?;
+                u32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as f64
+            }
+            Self::Float => {
+                reader.read_exact(&mut buf[..4])?;
+                f32::from_le_bytes([buf[0], buf[1], buf[2], buf[3]]) as f64
+            }
+            Self::Double => {
+                reader.read_exact(&mut buf)?;
+                f64::from_le_bytes(buf)
+            }
+        };
+
+        Ok(value)
+    }
+}
+
+/// Returns vertex positions and faces as indices of vertices (in order of [Mesh::vertices] iterator)
+fn indexed<TMesh: Mesh>(mesh: &TMesh) -> (Vec<Vec3<TMesh::ScalarType>>, Vec<usize>) {
+    let vertex_index: HashMap<_, _> = mesh.vertices().enumerate().map(|(i, v)| (v, i)).collect();
+    let points = mesh.vertices().map(|v| *mesh.vertex_position(&v)).collect();
+    let indices = mesh
+        .faces()
+        .flat_map(|face| {
+            let (v1, v2, v3) = mesh.face_vertices(&face);
+            [vertex_index[&v1], vertex_index[&v2], vertex_index[&v3]]
+        })
+        .collect();
+
+    (points, indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, BufWriter};
+
+    use super::{PlyAttribute, PlyReader, PlyWriter, ScalarProperty};
+    use crate::{
+        helpers::aliases::Vec3f,
+        io::{read_from_buffer_any, Quantization},
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh, vertex_groups::VertexGroups},
+    };
+
+    #[test]
+    fn test_write_point_cloud() {
+        let points = [Vec3f::new(1.0, 2.0, 3.0), Vec3f::new(4.0, 5.0, 6.0)];
+        let normals = [Vec3f::x(), Vec3f::y()];
+        let values = [0.5, 1.5];
+        let properties = [ScalarProperty::new("distance", &values)];
+
+        let mut writer = BufWriter::new(Vec::new());
+        PlyWriter::new()
+            .write_point_cloud(&points, Some(&normals), &properties, &mut writer)
+            .unwrap();
+        let bytes = writer.into_inner().unwrap();
+
+        let header = "ply\n\
+            format binary_little_endian 1.0\n\
+            element vertex 2\n\
+            property float x\n\
+            property float y\n\
+            property float z\n\
+            property float nx\n\
+            property float ny\n\
+            property float nz\n\
+            property float distance\n\
+            end_header\n";
+        assert!(bytes.starts_with(header.as_bytes()));
+
+        let body: Vec<f32> = bytes[header.len()..]
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+        assert_eq!(body, vec![1.0, 2.0, 3.0, 1.0, 0.0, 0.0, 0.5, 4.0, 5.0, 6.0, 0.0, 1.0, 0.0, 1.5]);
+    }
+
+    #[test]
+    fn test_write_quantized_point_cloud() {
+        let points = [Vec3f::new(0.1, -0.3, 2.55)];
+
+        let mut writer = BufWriter::new(Vec::new());
+        PlyWriter::new()
+            .with_quantization(Some(Quantization::FixedPoint(4.0)))
+            .write_point_cloud(&points, None, &[], &mut writer)
+            .unwrap();
+        let bytes = writer.into_inner().unwrap();
+
+        let body: Vec<f32> = bytes[bytes.len() - 12..]
+            .chunks_exact(4)
+            .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect();
+        assert_eq!(body, vec![0.0, -0.25, 2.5]);
+    }
+
+    #[test]
+    fn test_property_length_mismatch() {
+        let points = [Vec3f::zeros()];
+        let values = [0.5, 1.5];
+        let properties = [ScalarProperty::new("distance", &values)];
+
+        let mut writer = BufWriter::new(Vec::new());
+        let result = PlyWriter::new().write_point_cloud(&points, None, &properties, &mut writer);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_write_mesh_with_hard_edges() {
+        let cube: CornerTableF = primitives::cuboid(Vec3f::new(1.0, 1.0, 1.0), 1);
+
+        let mut writer = BufWriter::new(Vec::new());
+        PlyWriter::new()
+            .write_mesh(&cube, Some(30.0f64.to_radians()), &mut writer)
+            .unwrap();
+        let bytes = writer.into_inner().unwrap();
+
+        let header = "ply\n\
+            format binary_little_endian 1.0\n\
+            element vertex 24\n\
+            property float x\n\
+            property float y\n\
+            property float z\n\
+            property float nx\n\
+            property float ny\n\
+            property float nz\n\
+            element face 12\n\
+            property list uchar int vertex_indices\n\
+            end_header\n";
+        assert!(bytes.starts_with(header.as_bytes()));
+        assert_eq!(bytes.len(), header.len() + 24 * 24 + 12 * 13);
+    }
+
+    #[test]
+    fn test_vertex_groups_round_trip() {
+        let plane: CornerTableF = primitives::plane(1.0, 1.0, 4, 4);
+        let mut groups = VertexGroups::new();
+        let left = groups.add_group("left".to_string());
+        let height = groups.add_group("height".to_string());
+
+        for vertex in plane.vertices() {
+            let position = plane.vertex_position(&vertex);
+            groups.set_weight(height, vertex, position.y + 0.5);
+
+            if position.x < 0.0 {
+                groups.set_weight(left, vertex, 1.0);
+            }
+        }
+
+        let mut writer = BufWriter::new(Vec::new());
+        PlyWriter::new()
+            .write_mesh_with_vertex_groups(&plane, &groups, &mut writer)
+            .unwrap();
+        let bytes = writer.into_inner().unwrap();
+
+        let (mesh, read): (CornerTableF, _) = PlyReader::new()
+            .read_ply_with_vertex_groups(&mut BufReader::new(bytes.as_slice()))
+            .unwrap();
+        assert_eq!(mesh.faces().count(), plane.faces().count());
+        assert_eq!(read.groups_count(), 2);
+        assert_eq!(read.group_name(1), Some("height"));
+
+        let left = read.group_id("left").unwrap();
+        for vertex in mesh.vertices() {
+            let position = mesh.vertex_position(&vertex);
+            let expected = if position.x < 0.0 { 1.0 } else { 0.0 };
+            assert_eq!(read.weight(left, &vertex), Some(expected));
+            assert_eq!(read.weight(1, &vertex), Some(position.y + 0.5));
+        }
+    }
+
+    #[test]
+    fn test_read_ply() {
+        // Quad and unknown element with list property
+        let mut bytes = b"ply\n\
+            format binary_little_endian 1.0\n\
+            comment test\n\
+            element vertex 4\n\
+            property double x\n\
+            property double y\n\
+            property double z\n\
+            property uchar mask\n\
+            element face 1\n\
+            property list uchar uint vertex_indices\n\
+            element edge 1\n\
+            property list uchar int vertex_pair\n\
+            end_header\n"
+            .to_vec();
+
+        for (x, y, mask) in [(0.0f64, 0.0f64, 0u8), (1.0, 0.0, 1), (1.0, 1.0, 2), (0.0, 1.0, 3)] {
+            bytes.extend_from_slice(&x.to_le_bytes());
+            bytes.extend_from_slice(&y.to_le_bytes());
+            bytes.extend_from_slice(&0.0f64.to_le_bytes());
+            bytes.push(mask);
+        }
+
+        bytes.push(4);
+        for index in 0u32..4 {
+            bytes.extend_from_slice(&index.to_le_bytes());
+        }
+
+        bytes.push(2);
+        bytes.extend_from_slice(&0i32.to_le_bytes());
+        bytes.extend_from_slice(&2i32.to_le_bytes());
+
+        let (mesh, groups): (CornerTableF, _) = PlyReader::new()
+            .read_ply_with_vertex_groups(&mut BufReader::new(bytes.as_slice()))
+            .unwrap();
+        assert_eq!(mesh.faces().count(), 2);
+        assert_eq!(groups.group_name(0), Some("mask"));
+
+        let corner = mesh.vertices().find(|v| *mesh.vertex_position(v) == Vec3f::new(1.0, 1.0, 0.0)).unwrap();
+        assert_eq!(groups.weight(0, &corner), Some(2.0));
+
+        let mesh: CornerTableF = read_from_buffer_any(&bytes).unwrap();
+        assert_eq!(mesh.faces().count(), 2);
+
+        let ascii = b"ply\nformat ascii 1.0\nend_header\n";
+        let result: std::io::Result<CornerTableF> = PlyReader::new().read_ply(&mut BufReader::new(ascii.as_slice()));
+        assert!(result.is_err());
+
+        // Truncated body
+        let result: std::io::Result<CornerTableF> = PlyReader::new().read_ply(&mut BufReader::new(&bytes[..bytes.len() - 4]));
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_read_ply_with_attributes() {
+        // Quad and triangle with confidence of vertices, color and neighbors of faces
+        let mut bytes = b"ply\n\
+            format binary_little_endian 1.0\n\
+            element vertex 5\n\
+            property float x\n\
+            property float y\n\
+            property float z\n\
+            property float confidence\n\
+            property ushort intensity\n\
+            element face 2\n\
+            property uchar red\n\
+            property list uchar int vertex_indices\n\
+            property list uchar float weights\n\
+            end_header\n"
+            .to_vec();
+
+        for (i, (x, y)) in [(0.0f32, 0.0f32), (1.0, 0.0), (1.0, 1.0), (0.0, 1.0), (2.0, 0.5)].into_iter().enumerate() {
+            for value in [x, y, 0.0, i as f32 * 0.25] {
+                bytes.extend_from_slice(&value.to_le_bytes());
+            }
+            bytes.extend_from_slice(&(i as u16 * 100).to_le_bytes());
+        }
+
+        for (red, polygon, weights) in [(255u8, vec![0i32, 1, 2, 3], vec![0.5f32]), (7, vec![1, 4, 2], vec![])] {
+            bytes.push(red);
+            bytes.push(polygon.len() as u8);
+            polygon.iter().for_each(|index| bytes.extend_from_slice(&index.to_le_bytes()));
+            bytes.push(weights.len() as u8);
+            weights.iter().for_each(|weight| bytes.extend_from_slice(&weight.to_le_bytes()));
+        }
+
+        let (mesh, attributes): (CornerTableF, _) = PlyReader::new()
+            .read_ply_with_attributes(&mut BufReader::new(bytes.as_slice()))
+            .unwrap();
+        assert_eq!(mesh.faces().count(), 3);
+
+        let names: Vec<_> = attributes.vertex_attributes().map(|(name, _)| name).collect();
+        assert_eq!(names, ["x", "y", "z", "confidence", "intensity"]);
+        assert_eq!(attributes.vertex_attribute("intensity"), Some(&PlyAttribute::Int(vec![0, 100, 200, 300, 400])));
+        assert_eq!(attributes.face_attribute("weights").unwrap().list(0), Some([0.5].as_slice()));
+        assert_eq!(attributes.face_attribute("vertex_indices").unwrap().list(1), Some([1.0, 4.0, 2.0].as_slice()));
+
+        for vertex in mesh.vertices() {
+            let position = mesh.vertex_position(&vertex);
+            let row = attributes.vertex_row(&vertex).unwrap();
+            assert_eq!(attributes.vertex_value("x", &vertex), Some(position.x as f64));
+            assert_eq!(attributes.vertex_value("confidence", &vertex), Some(row as f64 * 0.25));
+        }
+
+        // Both triangles of quad share its color
+        let red: Vec<_> = mesh.faces().map(|face| attributes.face_value("red", &face).unwrap()).collect();
+        assert_eq!(red.iter().filter(|red| **red == 255.0).count(), 2);
+        assert_eq!(red.iter().filter(|red| **red == 7.0).count(), 1);
+        assert!(mesh.faces().all(|face| attributes.face_value("weights", &face).is_none()));
+
+        // Vertex groups are read from the same properties
+        let (_, groups): (CornerTableF, _) = PlyReader::new()
+            .read_ply_with_vertex_groups(&mut BufReader::new(bytes.as_slice()))
+            .unwrap();
+        assert_eq!(groups.groups_count(), 2);
+        assert_eq!(groups.group_name(1), Some("intensity"));
+    }
+}
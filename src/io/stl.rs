@@ -1,17 +1,60 @@
 use std::{
     mem::size_of, 
     io::{ErrorKind, Read, Error, BufReader, self, Write, BufWriter}, 
-    fs::{OpenOptions, File}, path::Path, ops::Index
+    fs::{OpenOptions, File}, path::Path, ops::Index, collections::HashSet, fmt::Display
 };
 use nalgebra::{Point3, Vector3};
 use simba::scalar::SupersetOf;
 
-use crate::{algo::{merge_points::merge_points, utils::cast}, mesh::traits::Mesh, helpers::aliases::Vec3f};
+use crate::{algo::{merge_points::merge_points, utils::cast}, geometry::primitives::triangle3::Triangle3, mesh::traits::Mesh, helpers::aliases::Vec3f};
 
 const STL_HEADER_SIZE: usize = 80;
 
+///
+/// Cleanup done by [StlReader] while reading file, see [StlReader::read_stl_with_report]
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StlReport {
+    /// Number of triangles declared in header
+    pub declared_triangles: usize,
+    /// Number of triangles read, less than declared when file is truncated
+    pub read_triangles: usize,
+    /// Triangles skipped because of NaN or infinite coordinates
+    pub non_finite_triangles: usize,
+    /// Triangles skipped because their vertices coincide or, when enabled, their area is zero
+    pub degenerate_triangles: usize,
+    /// Repeated triangles skipped when removal of degenerate faces is enabled
+    pub duplicate_triangles: usize,
+}
+
+impl StlReport {
+    /// Returns `true` when file is shorter than declared in its header
+    #[inline]
+    pub fn is_truncated(&self) -> bool {
+        self.read_triangles < self.declared_triangles
+    }
+
+    /// Returns `true` when file was read without any cleanup
+    #[inline]
+    pub fn is_clean(&self) -> bool {
+        !self.is_truncated() && self.non_finite_triangles == 0 && self.degenerate_triangles == 0 && self.duplicate_triangles == 0
+    }
+}
+
+impl Display for StlReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "declared triangles: {}, read triangles: {}, non-finite triangles: {}, degenerate triangles: {}, duplicate triangles: {}",
+            self.declared_triangles, self.read_triangles, self.non_finite_triangles, self.degenerate_triangles, self.duplicate_triangles
+        )
+    }
+}
+
 pub struct StlReader {
     vertices: Vec<Vec3f>,
+    remove_degenerate_faces: bool,
 
     // Buffers for reading
     buf32: [u8; size_of::<u32>()],
@@ -19,17 +62,31 @@ pub struct StlReader {
 }
 
 ///
-/// Binary STL reader
+/// Binary STL reader. Tolerates malformed files found in the wild:
+/// * triangles with NaN or infinite coordinates are skipped, normals stored in file are ignored
+/// * triangles collapsed by merging of coincident vertices are skipped
+/// * zero-area and repeated triangles are skipped (configurable, see [StlReader::with_remove_degenerate_faces])
+/// * file shorter than declared in header is read up to its end
+///
+/// Use [StlReader::read_stl_with_report] to get statistics of cleanup.
 /// 
 impl StlReader {
     pub fn new() -> Self {
         Self {
             vertices: Vec::new(),
+            remove_degenerate_faces: true,
             buf16: [0; size_of::<u16>()],
             buf32: [0; size_of::<u32>()]
         }
     }
 
+    /// Set whether zero-area and repeated triangles should be skipped. Default is `true`.
+    #[inline]
+    pub fn with_remove_degenerate_faces(mut self, remove: bool) -> Self {
+        self.remove_degenerate_faces = remove;
+        self
+    }
+
     /// Reads mesh from file
     pub fn read_stl_from_file<TMesh>(&mut self, filepath: &Path) -> std::io::Result<TMesh> 
     where 
@@ -49,35 +106,146 @@ impl StlReader {
         TMesh: Mesh,
         TMesh::ScalarType: SupersetOf<f32>
     {
-        self.vertices.clear();
+        self.read_stl_with_report(reader).map(|(mesh, _)| mesh)
+    }
 
-        // Read header
-        let mut header = [0u8; STL_HEADER_SIZE];
-        reader.read_exact(&mut header)?;
+    /// Reads mesh from file together with statistics of cleanup
+    pub fn read_stl_with_report_from_file<TMesh>(&mut self, filepath: &Path) -> std::io::Result<(TMesh, StlReport)> 
+    where 
+        TMesh: Mesh,
+        TMesh::ScalarType: SupersetOf<f32>
+    {
+        let file = OpenOptions::new().read(true).open(filepath)?;
+        let mut reader = BufReader::new(file);
 
-        // Read number of triangle
-        reader.read_exact(&mut self.buf32)?;
-        let number_of_triangles: u32 = u32::from_le_bytes(self.buf32);
+        self.read_stl_with_report::<File, TMesh>(&mut reader)
+    }
+
+    ///
+    /// Reads mesh from buffer together with statistics of cleanup.
+    /// Fails only when header can't be read or on IO errors other than unexpected end of file.
+    ///
+    pub fn read_stl_with_report<TBuffer, TMesh>(&mut self, reader: &mut BufReader<TBuffer>) -> std::io::Result<(TMesh, StlReport)> 
+    where 
+        TBuffer: Read, 
+        TMesh: Mesh,
+        TMesh::ScalarType: SupersetOf<f32>
+    {
+        self.vertices.clear();
+
+        // Read header and number of triangles
+        let number_of_triangles = self.read_header(reader)? as usize;
+        let mut report = StlReport { declared_triangles: number_of_triangles, ..Default::default() };
 
         // Faces
         for _ in 0..number_of_triangles {
-            self.read_face(reader)?;
+            let (v1, v2, v3) = match self.read_triangle(reader) {
+                Ok(triangle) => triangle,
+                // Count in header is bogus or file is truncated, partially read triangle is dropped
+                Err(err) if err.kind() == ErrorKind::UnexpectedEof => break,
+                Err(err) => return Err(err),
+            };
+
+            report.read_triangles += 1;
+
+            if [v1, v2, v3].iter().any(|v| v.iter().any(|c| !c.is_finite())) {
+                report.non_finite_triangles += 1;
+                continue;
+            }
+
+            self.vertices.push(v1);
+            self.vertices.push(v2);
+            self.vertices.push(v3);
         }
 
         // Merge face vertices
         let merged_vertices = merge_points(&self.vertices);
-        
-        // Case points to scalar type used by mesh
-        let vertices: Vec<_> = merged_vertices.points
-                .iter()
-                .map(|point| point.cast::<TMesh::ScalarType>())
-                .collect();
-        
+
+        // Skip degenerate faces and vertices referenced only by them
+        let mut vertex_map = vec![None; merged_vertices.points.len()];
+        let mut vertices = Vec::new();
+        let mut indices = Vec::with_capacity(merged_vertices.indices.len());
+        let mut faces = HashSet::new();
+
+        for face in merged_vertices.indices.chunks_exact(3) {
+            let (i1, i2, i3) = (face[0], face[1], face[2]);
+
+            if i1 == i2 || i2 == i3 || i3 == i1 {
+                report.degenerate_triangles += 1;
+                continue;
+            }
+
+            if self.remove_degenerate_faces {
+                let (p1, p2, p3) = (&merged_vertices.points[i1], &merged_vertices.points[i2], &merged_vertices.points[i3]);
+
+                if (p2 - p1).cross(&(p3 - p1)).norm_squared() == 0.0 {
+                    report.degenerate_triangles += 1;
+                    continue;
+                }
+
+                let mut key = [i1, i2, i3];
+                key.sort_unstable();
+
+                if !faces.insert(key) {
+                    report.duplicate_triangles += 1;
+                    continue;
+                }
+            }
+
+            for index in face {
+                let new_index = *vertex_map[*index].get_or_insert_with(|| {
+                    // Cast points to scalar type used by mesh
+                    vertices.push(merged_vertices.points[*index].cast::<TMesh::ScalarType>());
+                    vertices.len() - 1
+                });
+                indices.push(new_index);
+            }
+        }
+
         // Create mesh
-        Ok(TMesh::from_vertices_and_indices(&vertices, &merged_vertices.indices))
+        Ok((TMesh::from_vertices_and_indices(&vertices, &indices), report))
+    }
+
+    ///
+    /// Streams triangles from file one by one without building mesh.
+    /// Useful for files that are too large to be loaded at once.
+    ///
+    pub fn visit_triangles_from_file<TVisit>(&mut self, filepath: &Path, visit: TVisit) -> io::Result<()>
+    where
+        TVisit: FnMut(Triangle3<f32>)
+    {
+        let file = OpenOptions::new().read(true).open(filepath)?;
+        let mut reader = BufReader::new(file);
+
+        self.visit_triangles(&mut reader, visit)
+    }
+
+    /// Streams triangles from buffer one by one without building mesh
+    pub fn visit_triangles<TBuffer, TVisit>(&mut self, reader: &mut BufReader<TBuffer>, mut visit: TVisit) -> io::Result<()>
+    where
+        TBuffer: Read,
+        TVisit: FnMut(Triangle3<f32>)
+    {
+        let number_of_triangles = self.read_header(reader)?;
+
+        for _ in 0..number_of_triangles {
+            let (v1, v2, v3) = self.read_triangle(reader)?;
+            visit(Triangle3::new(v1, v2, v3));
+        }
+
+        Ok(())
     }
 
-    fn read_face<TBuffer: Read>(&mut self, reader: &mut BufReader<TBuffer>) -> io::Result<()> {
+    /// Reads header and returns number of triangles
+    fn read_header<TBuffer: Read>(&mut self, reader: &mut BufReader<TBuffer>) -> io::Result<u32> {
+        let mut header = [0u8; STL_HEADER_SIZE];
+        reader.read_exact(&mut header)?;
+
+        reader.read_exact(&mut self.buf32)?;
+        Ok(u32::from_le_bytes(self.buf32))
+    }
+
+    fn read_triangle<TBuffer: Read>(&mut self, reader: &mut BufReader<TBuffer>) -> io::Result<(Vec3f, Vec3f, Vec3f)> {
         // Normal
         self.read_vec3(reader)?;
 
@@ -86,14 +254,10 @@ impl StlReader {
         let v2 = self.read_vec3(reader)?;
         let v3 = self.read_vec3(reader)?;
 
-        self.vertices.push(v1);
-        self.vertices.push(v2);
-        self.vertices.push(v3);
-
         // Attribute
         reader.read_exact(&mut self.buf16)?;
 
-        Ok(())
+        Ok((v1, v2, v3))
     }
 
     fn read_vec3<TBuffer: Read>(&mut self, reader: &mut BufReader<TBuffer>) -> io::Result<Vec3f> {
@@ -191,3 +355,63 @@ impl Default for StlWriter {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::io::{BufReader, BufWriter};
+
+    use super::{StlReader, StlWriter};
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+    };
+
+    fn push_triangle(bytes: &mut Vec<u8>, vertices: [[f32; 3]; 3]) {
+        bytes.extend_from_slice(&[0; 12]);
+        vertices.iter().flatten().for_each(|c| bytes.extend_from_slice(&c.to_le_bytes()));
+        bytes.extend_from_slice(&[0; 2]);
+    }
+
+    #[test]
+    fn test_read_malformed_stl() {
+        let cube: CornerTableF = primitives::cuboid(Vec3f::new(1.0, 1.0, 1.0), 1);
+        let mut bytes = Vec::new();
+        StlWriter::new().write_stl(&cube, &mut BufWriter::new(&mut bytes)).unwrap();
+
+        // Copy of first triangle, triangle with NaN, collinear and collapsed triangles
+        let first = bytes[84..134].to_vec();
+        bytes.extend_from_slice(&first);
+        push_triangle(&mut bytes, [[f32::NAN, 0.0, 0.0], [1.0, 0.0, 0.0], [0.0, 1.0, 0.0]]);
+        push_triangle(&mut bytes, [[0.0, 0.0, 0.0], [1.0, 0.0, 0.0], [2.0, 0.0, 0.0]]);
+        push_triangle(&mut bytes, [[0.0, 0.0, 0.0], [0.0, 0.0, 0.0], [2.0, 0.0, 0.0]]);
+
+        // Header declares more triangles than stored, last one is cut
+        bytes[80..84].copy_from_slice(&20u32.to_le_bytes());
+        push_triangle(&mut bytes, [[0.0, 0.0, 0.0]; 3]);
+        bytes.truncate(bytes.len() - 10);
+
+        let (mesh, report): (CornerTableF, _) = StlReader::new()
+            .read_stl_with_report(&mut BufReader::new(bytes.as_slice()))
+            .unwrap();
+        assert_eq!(mesh.faces().count(), 12);
+        assert_eq!(mesh.vertices().count(), 8);
+        assert_eq!(report.declared_triangles, 20);
+        assert_eq!(report.read_triangles, 16);
+        assert_eq!(report.non_finite_triangles, 1);
+        assert_eq!(report.degenerate_triangles, 2);
+        assert_eq!(report.duplicate_triangles, 1);
+        assert!(report.is_truncated() && !report.is_clean());
+
+        let (mesh, report): (CornerTableF, _) = StlReader::new()
+            .with_remove_degenerate_faces(false)
+            .read_stl_with_report(&mut BufReader::new(bytes.as_slice()))
+            .unwrap();
+        assert!(mesh.faces().count() > 12);
+        assert_eq!(report.degenerate_triangles, 1);
+        assert_eq!(report.duplicate_triangles, 0);
+
+        // Missing header
+        let result: std::io::Result<CornerTableF> = StlReader::new().read_stl(&mut BufReader::new(&bytes[..40]));
+        assert!(result.is_err());
+    }
+}
@@ -1,12 +1,21 @@
 pub mod mesh;
 pub mod algo;
 pub mod data_structures;
+#[cfg(feature = "io")]
 pub mod io;
 pub mod remeshing;
 pub mod spatial_partitioning;
 pub mod geometry;
 pub mod decimation;
+pub mod scene;
+pub mod pipelines;
+#[cfg(all(feature = "io", feature = "voxel"))]
+pub mod ops;
+#[cfg(all(feature = "io", feature = "voxel", feature = "rayon"))]
+pub mod batch;
+#[cfg(feature = "voxel")]
 pub mod voxel;
+pub mod prelude;
 
 pub mod exports {
     pub use nalgebra as nalgebra;
@@ -0,0 +1,198 @@
+//!
+//! Conversions between meshes and flat `f32`/`u32` buffers used by graphics APIs and FFI consumers.
+//! Interleaved positions (`x, y, z, x, y, z, ...`) share memory layout with `[Vec3f]`, so they are borrowed without copying.
+//!
+
+use crate::{
+    helpers::aliases::Vec3f,
+    mesh::{corner_table::table::CornerTable, polygon_soup::data_structure::PolygonSoup, traits::Mesh},
+};
+
+// Borrowed views below rely on `Vec3f` being three packed `f32`
+const _: () = assert!(std::mem::size_of::<Vec3f>() == 3 * std::mem::size_of::<f32>());
+const _: () = assert!(std::mem::align_of::<Vec3f>() == std::mem::align_of::<f32>());
+
+///
+/// Layout of vertex positions in flat buffer
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum VertexLayout {
+    /// Coordinates of every vertex are stored together: `x0, y0, z0, x1, y1, z1, ...`
+    Interleaved,
+    /// All `x` coordinates, then all `y`, then all `z`: `x0, x1, ..., y0, y1, ..., z0, z1, ...`
+    Planar,
+}
+
+///
+/// Returns positions as interleaved flat buffer without copying, e.g. to upload them to vertex buffer.
+///
+pub fn positions_as_flat(positions: &[Vec3f]) -> &[f32] {
+    // SAFETY: `Vec3f` is `repr(C)` array of three `f32` with alignment of `f32` (checked above),
+    // so slice of `n` vectors is valid slice of `3 * n` floats borrowed for the same lifetime
+    unsafe { std::slice::from_raw_parts(positions.as_ptr().cast::<f32>(), positions.len() * 3) }
+}
+
+///
+/// Returns interleaved flat buffer as positions without copying.
+/// Returns `None` when length of buffer is not multiple of 3.
+///
+pub fn flat_as_positions(buffer: &[f32]) -> Option<&[Vec3f]> {
+    if !buffer.len().is_multiple_of(3) {
+        return None;
+    }
+
+    // SAFETY: every `f32` bit pattern is valid, size and alignment of `Vec3f` match three `f32` (checked above)
+    Some(unsafe { std::slice::from_raw_parts(buffer.as_ptr().cast::<Vec3f>(), buffer.len() / 3) })
+}
+
+///
+/// Copies positions from flat buffer with given layout.
+/// Returns `None` when length of buffer is not multiple of 3.
+///
+pub fn positions_from_flat(buffer: &[f32], layout: VertexLayout) -> Option<Vec<Vec3f>> {
+    match layout {
+        VertexLayout::Interleaved => flat_as_positions(buffer).map(|positions| positions.to_vec()),
+        VertexLayout::Planar => {
+            if !buffer.len().is_multiple_of(3) {
+                return None;
+            }
+
+            let count = buffer.len() / 3;
+            let (xs, rest) = buffer.split_at(count);
+            let (ys, zs) = rest.split_at(count);
+
+            Some((0..count).map(|i| Vec3f::new(xs[i], ys[i], zs[i])).collect())
+        }
+    }
+}
+
+/// Writes positions to flat buffer with given layout
+pub fn positions_to_flat(positions: &[Vec3f], layout: VertexLayout) -> Vec<f32> {
+    match layout {
+        VertexLayout::Interleaved => positions_as_flat(positions).to_vec(),
+        VertexLayout::Planar => (0..3).flat_map(|axis| positions.iter().map(move |position| position[axis])).collect(),
+    }
+}
+
+impl CornerTable<f32> {
+    ///
+    /// Creates mesh from flat buffer of positions and buffer of triangle indices.
+    /// Interleaved positions are read in place, only mesh itself is allocated.
+    /// Returns `None` when length of buffers is not multiple of 3 or index is out of bounds.
+    ///
+    /// ## Example
+    /// ```ignore
+    /// let mesh = CornerTableF::from_buffers(&gltf_positions, VertexLayout::Interleaved, &gltf_indices)?;
+    /// ```
+    ///
+    pub fn from_buffers(positions: &[f32], layout: VertexLayout, indices: &[u32]) -> Option<Self> {
+        let vertices_count = positions.len() / 3;
+
+        if !indices.len().is_multiple_of(3) || indices.iter().any(|index| *index as usize >= vertices_count) {
+            return None;
+        }
+
+        let indices: Vec<_> = indices.iter().map(|index| *index as usize).collect();
+
+        match layout {
+            VertexLayout::Interleaved => Some(Self::from_vertices_and_indices(flat_as_positions(positions)?, &indices)),
+            VertexLayout::Planar => Some(Self::from_vertices_and_indices(&positions_from_flat(positions, layout)?, &indices)),
+        }
+    }
+
+    ///
+    /// Returns flat buffers of positions and triangle indices. Deleted vertices and faces are skipped,
+    /// other vertices and faces keep their order.
+    ///
+    pub fn to_buffers(&self, layout: VertexLayout) -> (Vec<f32>, Vec<u32>) {
+        let mut positions = Vec::new();
+        let mut vertex_index = vec![0; self.vertices().map(|vertex| vertex + 1).max().unwrap_or(0)];
+
+        for vertex in self.vertices() {
+            vertex_index[vertex] = positions.len() as u32;
+            positions.push(*self.vertex_position(&vertex));
+        }
+
+        let indices = self
+            .faces()
+            .flat_map(|face| {
+                let (v1, v2, v3) = self.face_vertices(&face);
+                [v1, v2, v3].map(|vertex| vertex_index[vertex])
+            })
+            .collect();
+
+        (positions_to_flat(&positions, layout), indices)
+    }
+}
+
+impl PolygonSoup<f32> {
+    ///
+    /// Creates soup from flat buffer of positions, every 3 consecutive vertices form triangle.
+    /// Returns `None` when length of buffer is not multiple of 9.
+    ///
+    pub fn from_buffer(positions: &[f32], layout: VertexLayout) -> Option<Self> {
+        if !positions.len().is_multiple_of(9) {
+            return None;
+        }
+
+        positions_from_flat(positions, layout).map(Self::from_vertices)
+    }
+
+    ///
+    /// Returns interleaved positions of triangle vertices without copying, e.g. to draw soup as non-indexed triangle list
+    ///
+    #[inline]
+    pub fn as_flat(&self) -> &[f32] {
+        positions_as_flat(&self.vertices)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{flat_as_positions, positions_from_flat, VertexLayout};
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{
+            corner_table::prelude::CornerTableF, polygon_soup::data_structure::PolygonSoup, primitives,
+            traits::{EditableMesh, Mesh},
+        },
+    };
+
+    #[test]
+    fn test_flat_buffers() {
+        let mut mesh: CornerTableF = primitives::cuboid(Vec3f::new(1.0, 2.0, 3.0), 2);
+        let edge = mesh.edges().next().unwrap();
+        mesh.collapse_edge(&edge, &mesh.edge_positions(&edge).0);
+
+        let positions: Vec<_> = mesh.vertices().map(|vertex| *mesh.vertex_position(&vertex)).collect();
+
+        for layout in [VertexLayout::Interleaved, VertexLayout::Planar] {
+            let (flat, indices) = mesh.to_buffers(layout);
+            assert_eq!(flat.len(), positions.len() * 3);
+            assert_eq!(indices.len(), mesh.faces().count() * 3);
+            assert_eq!(positions_from_flat(&flat, layout).unwrap(), positions);
+
+            let restored = CornerTableF::from_buffers(&flat, layout, &indices).unwrap();
+            assert!(restored.validate_topology().is_valid());
+            assert_eq!(restored.to_buffers(layout), (flat, indices));
+        }
+
+        let (flat, _) = mesh.to_buffers(VertexLayout::Interleaved);
+        assert_eq!(flat_as_positions(&flat).unwrap(), positions.as_slice());
+        assert_eq!(&flat[3..6], positions[1].as_slice());
+
+        // Invalid buffers
+        assert!(flat_as_positions(&flat[1..]).is_none());
+        assert!(CornerTableF::from_buffers(&flat, VertexLayout::Interleaved, &[0, 1]).is_none());
+        assert!(CornerTableF::from_buffers(&flat, VertexLayout::Interleaved, &[0, 1, positions.len() as u32]).is_none());
+
+        // Soup shares memory with flat view
+        let soup = PolygonSoup::from(&mesh);
+        let view = soup.as_flat();
+        assert_eq!(view.len(), mesh.faces().count() * 9);
+        assert_eq!(PolygonSoup::from_buffer(view, VertexLayout::Interleaved).unwrap().as_flat(), view);
+        assert!(PolygonSoup::from_buffer(&view[..6], VertexLayout::Interleaved).is_none());
+    }
+}
@@ -1,6 +1,14 @@
-use crate::helpers::aliases::Vec3;
+use std::fmt::Display;
 
-use super::traits::Mesh;
+use crate::{
+    geometry::{primitives::triangle3::Triangle3, traits::RealNumber},
+    helpers::aliases::Vec3,
+};
+
+use super::{
+    remap::{FaceRemap, Remap, VertexRemap},
+    traits::Mesh,
+};
 
 pub fn cube<T: Mesh>(origin: Vec3<T::ScalarType>, x_size: T::ScalarType, y_size: T::ScalarType, z_size: T::ScalarType) -> T {
     let vertices = [
@@ -26,3 +34,266 @@ pub fn cube<T: Mesh>(origin: Vec3<T::ScalarType>, x_size: T::ScalarType, y_size:
 
     T::from_vertices_and_indices(&vertices, &faces)
 }
+
+/// Validation performed by [IndexedBuilder::finish]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Validation {
+    /// Data is passed to mesh as is, report is empty
+    Off,
+    /// Problems are counted, only faces referencing missing vertices are removed
+    Report,
+    /// Problems are counted, invalid and degenerate faces and unreferenced vertices are removed
+    Cull,
+}
+
+///
+/// Problems found in indexed data by [IndexedBuilder]. Every face is counted once,
+/// under the first problem in order of fields.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct BuildReport {
+    /// Faces referencing missing vertices
+    pub invalid_indices: usize,
+    /// Faces referencing the same vertex more than once
+    pub repeated_indices: usize,
+    /// Faces with zero area
+    pub degenerate_faces: usize,
+    /// Vertices not referenced by any face
+    pub unreferenced_vertices: usize,
+    /// Maps indices of added vertices to vertices of mesh
+    pub vertex_remap: VertexRemap,
+    /// Maps indices of added faces to faces passed to mesh
+    pub face_remap: FaceRemap,
+}
+
+impl BuildReport {
+    /// Returns `true` when no problems were found
+    #[inline]
+    pub fn is_clean(&self) -> bool {
+        self.invalid_indices == 0 && self.repeated_indices == 0 && self.degenerate_faces == 0 && self.unreferenced_vertices == 0
+    }
+}
+
+impl Display for BuildReport {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "invalid indices: {}, repeated indices: {}, degenerate faces: {}, unreferenced vertices: {}",
+            self.invalid_indices, self.repeated_indices, self.degenerate_faces, self.unreferenced_vertices
+        )
+    }
+}
+
+///
+/// Collects vertices and faces and builds mesh from them, validating the data first.
+/// Report describes what was found (and removed) so importers can log problems of input files.
+///
+/// ## Example
+/// ```ignore
+/// let mut builder = IndexedBuilder::new();
+/// let v1 = builder.add_vertex(Vec3f::new(0.0, 0.0, 0.0));
+/// let v2 = builder.add_vertex(Vec3f::new(1.0, 0.0, 0.0));
+/// let v3 = builder.add_vertex(Vec3f::new(0.0, 1.0, 0.0));
+/// builder.add_face(v1, v2, v3);
+///
+/// let (mesh, report): (CornerTableF, _) = builder.finish();
+/// assert!(report.is_clean());
+/// ```
+///
+pub struct IndexedBuilder<TScalar: RealNumber> {
+    vertices: Vec<Vec3<TScalar>>,
+    indices: Vec<usize>,
+    validation: Validation,
+}
+
+impl<TScalar: RealNumber> IndexedBuilder<TScalar> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Creates builder from vertex positions and flat array of face indices
+    pub fn from_vertices_and_indices(vertices: Vec<Vec3<TScalar>>, indices: Vec<usize>) -> Self {
+        assert!(indices.len().is_multiple_of(3), "Invalid number of face indices: {}", indices.len());
+
+        Self {
+            vertices,
+            indices,
+            ..Default::default()
+        }
+    }
+
+    /// Set validation of data. Default is [Validation::Cull].
+    #[inline]
+    pub fn with_validation(mut self, validation: Validation) -> Self {
+        self.validation = validation;
+        self
+    }
+
+    /// Adds vertex and returns its index
+    #[inline]
+    pub fn add_vertex(&mut self, position: Vec3<TScalar>) -> usize {
+        self.vertices.push(position);
+        self.vertices.len() - 1
+    }
+
+    /// Adds face and returns its index
+    #[inline]
+    pub fn add_face(&mut self, v1: usize, v2: usize, v3: usize) -> usize {
+        self.indices.extend_from_slice(&[v1, v2, v3]);
+        self.indices.len() / 3 - 1
+    }
+
+    /// Validates data and builds mesh
+    pub fn finish<TMesh: Mesh<ScalarType = TScalar>>(self) -> (TMesh, BuildReport) {
+        let faces_count = self.indices.len() / 3;
+        let mut report = BuildReport {
+            invalid_indices: 0,
+            repeated_indices: 0,
+            degenerate_faces: 0,
+            unreferenced_vertices: 0,
+            vertex_remap: Remap::identity(self.vertices.len()),
+            face_remap: Remap::identity(faces_count),
+        };
+
+        if self.validation == Validation::Off {
+            return (TMesh::from_vertices_and_indices(&self.vertices, &self.indices), report);
+        }
+
+        let mut face_map = Vec::with_capacity(faces_count);
+        let mut kept_faces = 0;
+        let mut is_referenced = vec![false; self.vertices.len()];
+
+        for face in self.indices.chunks(3) {
+            let (v1, v2, v3) = (face[0], face[1], face[2]);
+            let vertices_count = self.vertices.len();
+
+            let keep = if v1 >= vertices_count || v2 >= vertices_count || v3 >= vertices_count {
+                report.invalid_indices += 1;
+                false
+            } else if v1 == v2 || v2 == v3 || v3 == v1 {
+                report.repeated_indices += 1;
+                self.validation == Validation::Report
+            } else if Triangle3::is_degenerate(&self.vertices[v1], &self.vertices[v2], &self.vertices[v3]) {
+                report.degenerate_faces += 1;
+                self.validation == Validation::Report
+            } else {
+                true
+            };
+
+            if keep {
+                face.iter().for_each(|&v| is_referenced[v] = true);
+                face_map.push(Some(kept_faces));
+                kept_faces += 1;
+            } else {
+                face_map.push(None);
+            }
+        }
+
+        report.unreferenced_vertices = is_referenced.iter().filter(|referenced| !**referenced).count();
+
+        if self.validation == Validation::Cull {
+            let mut kept_vertices = 0;
+            let vertex_map = is_referenced
+                .iter()
+                .map(|referenced| {
+                    referenced.then(|| {
+                        kept_vertices += 1;
+                        kept_vertices - 1
+                    })
+                })
+                .collect();
+            report.vertex_remap = Remap::new(vertex_map);
+        }
+
+        report.face_remap = Remap::new(face_map);
+
+        let vertices = report.vertex_remap.apply(&self.vertices);
+        let indices: Vec<_> = self
+            .indices
+            .chunks(3)
+            .enumerate()
+            .filter(|(face, _)| !report.face_remap.is_removed(*face))
+            .flat_map(|(_, face)| face.iter().map(|v| report.vertex_remap.get(*v).unwrap()))
+            .collect();
+
+        (TMesh::from_vertices_and_indices(&vertices, &indices), report)
+    }
+}
+
+impl<TScalar: RealNumber> Default for IndexedBuilder<TScalar> {
+    fn default() -> Self {
+        Self {
+            vertices: Vec::new(),
+            indices: Vec::new(),
+            validation: Validation::Cull,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{IndexedBuilder, Validation};
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, traits::Mesh},
+    };
+
+    fn builder() -> IndexedBuilder<f32> {
+        let vertices = vec![
+            Vec3f::new(0.0, 0.0, 0.0),
+            Vec3f::new(1.0, 0.0, 0.0),
+            Vec3f::new(1.0, 1.0, 0.0),
+            Vec3f::new(0.0, 1.0, 0.0),
+            Vec3f::new(2.0, 2.0, 0.0),
+            Vec3f::new(5.0, 5.0, 5.0),
+        ];
+        let indices = vec![
+            0, 1, 2, // valid
+            0, 2, 3, // valid
+            0, 2, 4, // collinear
+            1, 1, 2, // repeated index
+            0, 1, 9, // missing vertex
+        ];
+
+        IndexedBuilder::from_vertices_and_indices(vertices, indices)
+    }
+
+    #[test]
+    fn test_cull() {
+        let (mesh, report): (CornerTableF, _) = builder().finish();
+
+        assert!(!report.is_clean());
+        assert_eq!(report.invalid_indices, 1);
+        assert_eq!(report.repeated_indices, 1);
+        assert_eq!(report.degenerate_faces, 1);
+        assert_eq!(report.unreferenced_vertices, 2);
+        assert_eq!(report.to_string(), "invalid indices: 1, repeated indices: 1, degenerate faces: 1, unreferenced vertices: 2");
+        assert_eq!(report.face_remap.iter().collect::<Vec<_>>(), vec![(0, 0), (1, 1)]);
+        assert!(report.vertex_remap.is_removed(4));
+        assert_eq!(mesh.faces().count(), 2);
+        assert_eq!(mesh.vertices().count(), 4);
+    }
+
+    #[test]
+    fn test_report() {
+        let (mesh, report): (CornerTableF, _) = builder().with_validation(Validation::Report).finish();
+
+        assert_eq!(report.invalid_indices, 1);
+        assert_eq!(report.degenerate_faces, 1);
+        assert_eq!(report.unreferenced_vertices, 1);
+        assert_eq!(report.face_remap.new_len(), 4);
+        assert_eq!(report.vertex_remap.new_len(), 6);
+        assert_eq!(mesh.vertices().count(), 6);
+
+        let mut builder = IndexedBuilder::new();
+        let v1 = builder.add_vertex(Vec3f::new(0.0, 0.0, 0.0));
+        let v2 = builder.add_vertex(Vec3f::new(1.0, 0.0, 0.0));
+        let v3 = builder.add_vertex(Vec3f::new(0.0, 1.0, 0.0));
+        assert_eq!(builder.add_face(v1, v2, v3), 0);
+
+        let (mesh, report): (CornerTableF, _) = builder.finish();
+        assert!(report.is_clean());
+        assert_eq!(mesh.faces().count(), 1);
+    }
+}
@@ -0,0 +1,78 @@
+use std::collections::HashMap;
+
+use super::traits::Mesh;
+
+///
+/// Rebuilds mesh in another representation. Vertices and faces are added in order of [Mesh::vertices]
+/// and [Mesh::faces] iterators of source mesh, deleted elements are dropped.
+/// Vertices are not merged, so faces of [PolygonSoup](super::polygon_soup::data_structure::PolygonSoup) stay disconnected,
+/// use its [From] impls to weld them.
+///
+/// ## Example
+/// ```ignore
+/// let soup: PolygonSoup<f32> = convert_mesh(&corner_table);
+/// ```
+///
+pub fn convert_mesh<TFrom: Mesh, TTo: Mesh<ScalarType = TFrom::ScalarType>>(mesh: &TFrom) -> TTo {
+    let mut vertices = Vec::new();
+    let vertex_index: HashMap<_, _> = mesh
+        .vertices()
+        .enumerate()
+        .map(|(i, vertex)| {
+            vertices.push(*mesh.vertex_position(&vertex));
+            (vertex, i)
+        })
+        .collect();
+
+    let mut indices = Vec::new();
+
+    for face in mesh.faces() {
+        let (v1, v2, v3) = mesh.face_vertices(&face);
+        indices.extend_from_slice(&[vertex_index[&v1], vertex_index[&v2], vertex_index[&v3]]);
+    }
+
+    TTo::from_vertices_and_indices(&vertices, &indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::convert_mesh;
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{
+            corner_table::prelude::CornerTableF,
+            polygon_soup::data_structure::PolygonSoup,
+            primitives,
+            traits::{Mesh, TopologicalMesh},
+        },
+    };
+
+    #[test]
+    fn test_corner_table_to_soup_and_back() {
+        let cube: CornerTableF = primitives::cuboid(Vec3f::new(1.0, 1.0, 1.0), 2);
+
+        let soup = PolygonSoup::from(&cube);
+        assert_eq!(soup.faces().count(), cube.faces().count());
+        assert_eq!(soup.vertices().count(), cube.faces().count() * 3);
+
+        let faces = |mesh: &CornerTableF| -> Vec<_> {
+            mesh.faces()
+                .map(|face| {
+                    let triangle = mesh.face_positions(&face);
+                    [*triangle.p1(), *triangle.p2(), *triangle.p3()]
+                })
+                .collect()
+        };
+
+        // Faces are welded back into closed mesh
+        let welded = CornerTableF::from(&soup);
+        assert_eq!(welded.vertices().count(), cube.vertices().count());
+        assert_eq!(faces(&welded), faces(&cube));
+        assert!(welded.edges().all(|edge| !welded.is_edge_on_boundary(&edge)));
+
+        // Generic conversion keeps faces disconnected
+        let disconnected: CornerTableF = convert_mesh(&soup);
+        assert_eq!(disconnected.vertices().count(), soup.vertices().count());
+        assert!(disconnected.edges().all(|edge| disconnected.is_edge_on_boundary(&edge)));
+    }
+}
@@ -1,19 +1,22 @@
 use std::cell::UnsafeCell;
 
+#[cfg(feature = "tabled")]
 use tabled::Tabled;
+#[cfg(feature = "tabled")]
 use crate::helpers::display::{display_option, display_unsafecell};
 use super::{traits::Flags, flags};
 
 ///
 /// Default implementation for Corner trait
 /// 
-#[derive(Debug, Tabled)]
+#[derive(Debug)]
+#[cfg_attr(feature = "tabled", derive(Tabled))]
 pub struct Corner {
-    #[tabled(display_with = "display_option")]
+    #[cfg_attr(feature = "tabled", tabled(display_with = "display_option"))]
     opposite_corner_index: Option<usize>,
     vertex_index: usize,
 
-    #[tabled(display_with = "display_unsafecell")]
+    #[cfg_attr(feature = "tabled", tabled(display_with = "display_unsafecell"))]
     flags: UnsafeCell<flags::Flags>
 }
 
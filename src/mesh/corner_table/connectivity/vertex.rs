@@ -1,17 +1,21 @@
 use std::cell::UnsafeCell;
+#[cfg(feature = "tabled")]
 use tabled::Tabled;
-use crate::{helpers::{display::display_unsafecell, aliases::Vec3}, geometry::traits::RealNumber};
+#[cfg(feature = "tabled")]
+use crate::helpers::display::display_unsafecell;
+use crate::{helpers::aliases::Vec3, geometry::traits::RealNumber};
 use super::{traits::Flags, flags};
 
 ///
 /// Default implementation for Vertex trait
 /// 
-#[derive(Debug, Tabled)]
+#[derive(Debug)]
+#[cfg_attr(feature = "tabled", derive(Tabled))]
 pub struct Vertex<TScalarType: RealNumber> {
     corner_index: usize,
     position: Vec3<TScalarType>,
 
-    #[tabled(display_with = "display_unsafecell")]
+    #[cfg_attr(feature = "tabled", tabled(display_with = "display_unsafecell"))]
     flags: UnsafeCell<flags::Flags>
 }
 
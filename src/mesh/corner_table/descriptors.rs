@@ -1,9 +1,9 @@
 
 use std::{hash::Hash, fmt::{Display, Debug}};
 
-use crate::geometry::traits::RealNumber;
+use crate::{geometry::traits::RealNumber, mesh::remap::{FaceRemap, VertexRemap}};
 
-use super::table::CornerTable;
+use super::{connectivity::traits::Flags, table::CornerTable};
 
 /// 
 /// Edge descriptor for corner table.
@@ -40,3 +40,87 @@ impl Debug for EdgeRef {
         write!(f, "corner_index: {}", &self.corner_index)
     }
 }
+
+///
+/// Stable identifier of corner table face which can be stored outside of mesh, e.g. as key of per-face data.
+///
+/// Edits never move faces: removed faces are only marked as deleted and new faces are appended,
+/// so identifier stays valid until its face is removed. [CornerTable::compact] renumbers faces and
+/// invalidates identifiers created before it, they can be translated by [FaceId::remap] with face remap returned by compaction.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct FaceId {
+    face: usize,
+    generation: u32,
+}
+
+impl FaceId {
+    #[inline]
+    pub(super) fn new(face: usize, generation: u32) -> Self {
+        Self { face, generation }
+    }
+
+    /// Returns face descriptor (index of first corner of face)
+    #[inline]
+    pub fn face(&self) -> usize {
+        self.face
+    }
+
+    /// Returns `true` when face still exists in mesh and was not renumbered by compaction
+    #[inline]
+    pub fn is_valid<TScalar: RealNumber>(&self, mesh: &CornerTable<TScalar>) -> bool {
+        self.generation == mesh.generation && mesh.get_corner(self.face).is_some_and(|corner| !corner.is_deleted())
+    }
+
+    ///
+    /// Translates identifier created before last [CornerTable::compact] using face remap returned by it.
+    /// Identifiers which are already up to date are returned as is. Returns `None` when face was removed
+    /// or identifier is older than last compaction.
+    ///
+    pub fn remap<TScalar: RealNumber>(&self, remap: &FaceRemap, mesh: &CornerTable<TScalar>) -> Option<Self> {
+        let id = if self.generation == mesh.generation {
+            *self
+        } else if self.generation + 1 == mesh.generation {
+            Self::new(remap.get(self.face / 3)? * 3, mesh.generation)
+        } else {
+            return None;
+        };
+
+        id.is_valid(mesh).then_some(id)
+    }
+}
+
+///
+/// Canonical identifier of undirected corner table edge: pair of its vertex indices, smaller first.
+/// Both corners of edge (and [EdgeRef] built from any of them) give the same identifier.
+///
+/// Unlike [EdgeRef], identifier does not depend on corners, so it survives most edits of other edges.
+/// It is invalidated by edits of its edge and by edits moving its vertices (split of edge moves one of its vertices
+/// to split point), see [EdgeAttribute](super::edge_attribute::EdgeAttribute) for data kept consistent through edits.
+/// Vertices renumbered by [CornerTable::compact] are translated by [EdgeId::remap].
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EdgeId {
+    v1: usize,
+    v2: usize,
+}
+
+impl EdgeId {
+    /// Creates identifier of edge between given vertices, order of vertices doesn't matter
+    #[inline]
+    pub fn new(v1: usize, v2: usize) -> Self {
+        Self { v1: v1.min(v2), v2: v1.max(v2) }
+    }
+
+    /// Returns vertices of edge, smaller index first
+    #[inline]
+    pub fn vertices(&self) -> (usize, usize) {
+        (self.v1, self.v2)
+    }
+
+    /// Translates identifier using vertex remap returned by [CornerTable::compact], returns `None` when any vertex was removed
+    #[inline]
+    pub fn remap(&self, remap: &VertexRemap) -> Option<Self> {
+        Some(Self::new(remap.get(self.v1)?, remap.get(self.v2)?))
+    }
+}
@@ -0,0 +1,239 @@
+use std::collections::HashMap;
+
+use crate::{
+    geometry::traits::RealNumber,
+    helpers::aliases::Vec3,
+    mesh::{
+        remap::VertexRemap,
+        traits::{EditableMesh, Mesh},
+    },
+};
+
+use super::{
+    connectivity::traits::Flags,
+    descriptors::{EdgeId, EdgeRef},
+    table::CornerTable,
+};
+
+///
+/// Values attached to edges of corner table, e.g. crease flags or edge weights. Values are keyed by [EdgeId],
+/// so they are kept by edits which don't touch their edges. Edits of edges should be done by methods of attribute,
+/// which perform edit on mesh and update values with following semantics:
+/// * [flip_edge](EdgeAttribute::flip_edge) moves value of flipped edge to new diagonal
+/// * [split_edge](EdgeAttribute::split_edge) copies value to both halves of split edge, edges connecting split point with opposite vertices have no value
+/// * [collapse_edge](EdgeAttribute::collapse_edge) drops value of collapsed edge, edges of removed vertex are merged into edges of kept one,
+///   value of kept edge wins when both have one
+/// * [remap](EdgeAttribute::remap) translates identifiers after [CornerTable::compact]
+///
+/// ## Example
+/// ```ignore
+/// let mut creases = EdgeAttribute::new();
+/// creases.set(mesh.edge_id(&edge), true);
+///
+/// creases.split_edge(&mut mesh, &edge, &midpoint);
+/// let (vertex_remap, _) = mesh.compact();
+/// creases.remap(&vertex_remap);
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct EdgeAttribute<T> {
+    values: HashMap<EdgeId, T>,
+}
+
+impl<T> EdgeAttribute<T> {
+    pub fn new() -> Self {
+        Self { values: HashMap::new() }
+    }
+
+    /// Sets value of edge, returns previous one
+    #[inline]
+    pub fn set(&mut self, edge: EdgeId, value: T) -> Option<T> {
+        self.values.insert(edge, value)
+    }
+
+    #[inline]
+    pub fn get(&self, edge: &EdgeId) -> Option<&T> {
+        self.values.get(edge)
+    }
+
+    #[inline]
+    pub fn get_mut(&mut self, edge: &EdgeId) -> Option<&mut T> {
+        self.values.get_mut(edge)
+    }
+
+    #[inline]
+    pub fn remove(&mut self, edge: &EdgeId) -> Option<T> {
+        self.values.remove(edge)
+    }
+
+    /// Returns number of edges with value
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Returns edges with values in arbitrary order
+    #[inline]
+    pub fn iter(&self) -> impl Iterator<Item = (&EdgeId, &T)> {
+        self.values.iter()
+    }
+
+    /// Flips inner edge of mesh, value of flipped edge is moved to new diagonal
+    pub fn flip_edge<TScalar: RealNumber>(&mut self, mesh: &mut CornerTable<TScalar>, edge: &EdgeRef) {
+        let old_id = mesh.edge_id(edge);
+        let corner = edge.get_corner_index();
+        let opposite = mesh.corners[corner]
+            .get_opposite_corner_index()
+            .expect("Boundary edge can't be flipped");
+        let new_id = EdgeId::new(mesh.corners[corner].get_vertex_index(), mesh.corners[opposite].get_vertex_index());
+
+        mesh.flip_edge(edge);
+
+        if let Some(value) = self.values.remove(&old_id) {
+            self.values.insert(new_id, value);
+        }
+    }
+
+    ///
+    /// Splits edge of mesh at given point, both halves of split edge get its value.
+    /// Values of other edges are kept, including edges of vertex moved to split point by corner table.
+    ///
+    pub fn split_edge<TScalar: RealNumber>(&mut self, mesh: &mut CornerTable<TScalar>, edge: &EdgeRef, at: &Vec3<TScalar>)
+    where
+        T: Clone,
+    {
+        let (v1, v2) = mesh.edge_vertices(edge);
+        let rings = [v1, v2].map(|vertex| mesh.vertex_one_ring(vertex).collect::<Vec<_>>());
+        let new_vertex = mesh.vertices.len();
+
+        mesh.split_edge(edge, at);
+
+        // Split moves one end of edge to split point, new vertex takes its place
+        let (fixed, moved, ring) = if mesh.vertex_one_ring(v2).any(|vertex| vertex == new_vertex) {
+            (v1, v2, &rings[1])
+        } else {
+            (v2, v1, &rings[0])
+        };
+
+        for &neighbor in ring.iter().filter(|neighbor| **neighbor != fixed) {
+            if let Some(value) = self.values.remove(&EdgeId::new(moved, neighbor)) {
+                self.values.insert(EdgeId::new(new_vertex, neighbor), value);
+            }
+        }
+
+        if let Some(value) = self.values.get(&EdgeId::new(fixed, moved)) {
+            self.values.insert(EdgeId::new(moved, new_vertex), value.clone());
+        }
+    }
+
+    ///
+    /// Collapses edge of mesh at given point. Value of collapsed edge is dropped, values of edges of removed vertex
+    /// are moved to corresponding edges of kept vertex unless they already have values.
+    ///
+    pub fn collapse_edge<TScalar: RealNumber>(&mut self, mesh: &mut CornerTable<TScalar>, edge: &EdgeRef, at: &Vec3<TScalar>) {
+        let (v1, v2) = mesh.edge_vertices(edge);
+        let rings = [v1, v2].map(|vertex| mesh.vertex_one_ring(vertex).collect::<Vec<_>>());
+
+        mesh.collapse_edge(edge, at);
+
+        let (kept, removed, ring) = if mesh.vertices[v2].is_deleted() {
+            (v1, v2, &rings[1])
+        } else {
+            (v2, v1, &rings[0])
+        };
+
+        self.values.remove(&EdgeId::new(kept, removed));
+
+        for &neighbor in ring.iter().filter(|neighbor| **neighbor != kept) {
+            if let Some(value) = self.values.remove(&EdgeId::new(removed, neighbor)) {
+                self.values.entry(EdgeId::new(kept, neighbor)).or_insert(value);
+            }
+        }
+    }
+
+    /// Translates identifiers using vertex remap returned by [CornerTable::compact], values of removed edges are dropped
+    pub fn remap(&mut self, remap: &VertexRemap) {
+        self.values = self
+            .values
+            .drain()
+            .filter_map(|(edge, value)| Some((edge.remap(remap)?, value)))
+            .collect();
+    }
+}
+
+impl<T> Default for EdgeAttribute<T> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::EdgeAttribute;
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{
+            corner_table::prelude::CornerTableF,
+            primitives,
+            traits::{Mesh, TopologicalMesh},
+        },
+    };
+
+    #[test]
+    fn test_edge_attribute() {
+        let mut mesh: CornerTableF = primitives::plane(1.0, 1.0, 4, 4);
+        let ids: Vec<_> = mesh.unique_edges().collect();
+        assert_eq!(ids.len(), mesh.edges().count());
+        assert!(ids.iter().all(|id| mesh.edge_id(&mesh.edge_by_id(id).unwrap()) == *id));
+
+        let inner = mesh.edges().find(|edge| !mesh.is_edge_on_boundary(edge)).unwrap();
+        let inner_id = mesh.edge_id(&inner);
+        let mut weights = EdgeAttribute::new();
+        weights.set(inner_id, 1.0);
+
+        // Value moves to new diagonal
+        weights.flip_edge(&mut mesh, &inner);
+        assert!(mesh.edge_by_id(&inner_id).is_none());
+        let (flipped, _) = weights.iter().next().map(|(id, value)| (*id, *value)).unwrap();
+        assert!(mesh.edge_by_id(&flipped).is_some());
+
+        // Both halves get value
+        let edge = mesh.edge_by_id(&flipped).unwrap();
+        let (v1, v2) = mesh.edge_positions(&edge);
+        let midpoint = (v1 + v2) * 0.5;
+        let boundary_id = mesh.edge_id(&mesh.edges().find(|edge| mesh.is_edge_on_boundary(edge)).unwrap());
+        weights.set(boundary_id, 2.0);
+        weights.split_edge(&mut mesh, &edge, &midpoint);
+        assert_eq!(weights.len(), 3);
+
+        let mut halves: Vec<_> = weights
+            .iter()
+            .filter(|(_, value)| **value == 1.0)
+            .map(|(id, _)| mesh.edge_positions(&mesh.edge_by_id(id).unwrap()))
+            .collect();
+        halves.iter_mut().for_each(|(a, b)| if *a == midpoint { std::mem::swap(a, b) });
+        halves.sort_by(|a, b| a.0.x.total_cmp(&b.0.x).then(a.0.y.total_cmp(&b.0.y)));
+        let mut expected = vec![(v1, midpoint), (v2, midpoint)];
+        expected.sort_by(|a, b| a.0.x.total_cmp(&b.0.x).then(a.0.y.total_cmp(&b.0.y)));
+        assert_eq!(halves, expected);
+
+        // Collapsing one half merges other half into edge of kept vertex
+        let half = weights.iter().find(|(_, value)| **value == 1.0).map(|(id, _)| *id).unwrap();
+        let half = mesh.edge_by_id(&half).unwrap();
+        weights.collapse_edge(&mut mesh, &half, &Vec3f::from(midpoint));
+        assert_eq!(weights.len(), 2);
+        assert!(weights.iter().all(|(id, _)| mesh.edge_by_id(id).is_some()));
+
+        let (vertex_remap, _) = mesh.compact();
+        weights.remap(&vertex_remap);
+        assert!(weights.iter().all(|(id, _)| mesh.edge_id(&mesh.edge_by_id(id).unwrap()) == *id));
+        let boundary = weights.iter().find(|(_, value)| **value == 2.0).unwrap().0;
+        assert!(mesh.is_edge_on_boundary(&mesh.edge_by_id(boundary).unwrap()));
+    }
+}
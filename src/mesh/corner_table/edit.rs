@@ -0,0 +1,376 @@
+//!
+//! Low level Euler operators on corner table complementing [EditableMesh](crate::mesh::traits::EditableMesh).
+//! Together with edge collapse/split they allow to implement progressive meshes and custom remeshers:
+//! * [split_vertex] is inverse of edge collapse
+//! * [join_edges] is inverse of edge split
+//!
+//! Operators keep opposite corners and vertex-to-corner references consistent.
+//!
+
+use crate::{
+    geometry::traits::RealNumber,
+    helpers::aliases::Vec3,
+    mesh::traits::{EditableMesh, Mesh, TopologicalMesh},
+};
+
+use super::{
+    connectivity::{
+        corner::{next, previous},
+        traits::Flags,
+    },
+    descriptors::EdgeRef,
+    editable::make_corners_opposite,
+    table::CornerTable,
+    traversal::{collect_corners_around_vertex, vertices_around_vertex},
+};
+
+///
+/// Splits `vertex` into two vertices connected by new edge (inverse of edge collapse).
+///
+/// Faces around `vertex` lying counterclockwise between edges to `right` and `left` wing vertices are moved
+/// to new vertex. New faces `(vertex, new, left)` and `(new, vertex, right)` are created to fill the gap.
+/// For boundary vertex one of the wings can be `None`, then fan between boundary and other wing is moved
+/// and only one face is created.
+///
+/// Returns index of new vertex or `None` (mesh is left untouched) when wings are not neighbors of `vertex`
+/// or don't split its fan.
+///
+/// ## Example
+/// ```ignore
+/// // Undo collapse of edge (v, u) with wing vertices l and r
+/// let u = split_vertex(&mut mesh, v, Some(l), Some(r), v_position, u_position).unwrap();
+/// ```
+///
+pub fn split_vertex<TScalar: RealNumber>(
+    corner_table: &mut CornerTable<TScalar>,
+    vertex: usize,
+    left: Option<usize>,
+    right: Option<usize>,
+    vertex_position: Vec3<TScalar>,
+    new_vertex_position: Vec3<TScalar>,
+) -> Option<usize> {
+    if corner_table.vertices.get(vertex)?.is_deleted() || left == right {
+        return None;
+    }
+
+    // Corner of first moved face: face after edge to right wing or first face after boundary
+    let start = collect_corners_around_vertex(corner_table, vertex)
+        .into_iter()
+        .find(|&corner| match right {
+            Some(right) => corner_table.corners[next(corner)].get_vertex_index() == right,
+            None => corner_table.corners[previous(corner)].get_opposite_corner_index().is_none(),
+        })?;
+
+    // Walk counterclockwise until edge to left wing or boundary
+    let mut moved = vec![start];
+    let mut corner = start;
+
+    loop {
+        if Some(corner_table.corners[previous(corner)].get_vertex_index()) == left {
+            break;
+        }
+
+        match corner_table.corners[next(corner)].get_opposite_corner_index() {
+            Some(opposite) => corner = next(opposite),
+            None if left.is_none() => break,
+            None => return None,
+        }
+
+        // Went around interior vertex without reaching left wing
+        if corner == start {
+            return None;
+        }
+
+        moved.push(corner);
+    }
+
+    // Edges bounding moved fan and their opposites in remaining fan
+    let right_edge = previous(start);
+    let right_edge_opposite = corner_table.corners[right_edge].get_opposite_corner_index();
+    let left_edge = next(*moved.last().unwrap());
+    let left_edge_opposite = corner_table.corners[left_edge].get_opposite_corner_index();
+
+    let new_vertex = corner_table.vertices.len();
+    corner_table
+        .create_vertex()
+        .set_position(new_vertex_position)
+        .set_corner_index(start);
+    corner_table.vertices[vertex].set_position(vertex_position);
+
+    for corner in moved {
+        corner_table.corners[corner].set_vertex_index(new_vertex);
+    }
+
+    let left_face = left.map(|left| corner_table.create_face_from_vertices(vertex, new_vertex, left));
+    let right_face = right.map(|right| corner_table.create_face_from_vertices(new_vertex, vertex, right));
+
+    if let Some(face) = left_face {
+        corner_table.set_opposite_relationship(face, left_edge);
+        make_corners_opposite(corner_table, Some(face + 1), left_edge_opposite);
+        corner_table.vertices[vertex].set_corner_index(face);
+    }
+
+    if let Some(face) = right_face {
+        corner_table.set_opposite_relationship(face + 1, right_edge);
+        make_corners_opposite(corner_table, Some(face), right_edge_opposite);
+        corner_table.vertices[vertex].set_corner_index(face + 1);
+    }
+
+    // New edge between split vertices
+    if let (Some(left_face), Some(right_face)) = (left_face, right_face) {
+        corner_table.set_opposite_relationship(left_face + 2, right_face + 2);
+    }
+
+    Some(new_vertex)
+}
+
+///
+/// Removes `vertex` created by edge split joining two edges passing through it into one (inverse of edge split).
+/// Edge from `vertex` to `towards` is merged with opposite edge, `towards` keeps its index and position.
+///
+/// `vertex` should have valence 4 (or be on boundary and have 2 faces, then `towards` should be on boundary too).
+/// Returns `false` (mesh is left untouched) when `vertex` can't be removed or joined edge already exists.
+///
+pub fn join_edges<TScalar: RealNumber>(corner_table: &mut CornerTable<TScalar>, vertex: usize, towards: usize) -> bool {
+    if corner_table.vertices.get(vertex).is_none_or(|v| v.is_deleted()) {
+        return false;
+    }
+
+    // One-ring of vertex in counterclockwise order
+    let corners = collect_corners_around_vertex(corner_table, vertex);
+    let first = corners
+        .iter()
+        .copied()
+        .find(|&corner| corner_table.corners[previous(corner)].get_opposite_corner_index().is_none())
+        .unwrap_or(corners[0]);
+
+    let mut ring = vec![corner_table.corners[next(first)].get_vertex_index()];
+    let mut corner = first;
+
+    loop {
+        ring.push(corner_table.corners[previous(corner)].get_vertex_index());
+
+        match corner_table.corners[next(corner)].get_opposite_corner_index() {
+            Some(opposite) if next(opposite) != first => corner = next(opposite),
+            _ => break,
+        }
+    }
+
+    let is_boundary = corner_table.is_vertex_on_boundary(&vertex);
+
+    // Interior ring is closed (last vertex repeats first one), boundary ring is open
+    let ring = match (is_boundary, ring.len()) {
+        (false, 5) => &ring[..4],
+        (true, 3) => &ring[..],
+        _ => return false,
+    };
+
+    // Vertex on other side of joined edge
+    let other = match (is_boundary, ring.iter().position(|v| *v == towards)) {
+        (false, Some(index)) => ring[(index + 2) % 4],
+        (true, Some(0)) => ring[2],
+        (true, Some(2)) => ring[0],
+        _ => return false,
+    };
+
+    let mut already_connected = false;
+    vertices_around_vertex(corner_table, towards, |neighbor| already_connected |= *neighbor == other);
+
+    if already_connected {
+        return false;
+    }
+
+    // Edge between vertex and towards is opposite to corner following or preceding towards
+    let edge_corner = corners
+        .iter()
+        .find_map(|&corner| {
+            if corner_table.corners[next(corner)].get_vertex_index() == towards {
+                Some(previous(corner))
+            } else if corner_table.corners[previous(corner)].get_vertex_index() == towards {
+                Some(next(corner))
+            } else {
+                None
+            }
+        })
+        .unwrap();
+
+    let position = *corner_table.vertex_position(&towards);
+    corner_table.collapse_edge(&EdgeRef::new(edge_corner, corner_table), &position);
+
+    // Collapse may keep either end of edge, make sure `towards` survives
+    if !corner_table.vertices[vertex].is_deleted() {
+        corner_table.vertices.swap(vertex, towards);
+
+        for corner in collect_corners_around_vertex(corner_table, towards) {
+            corner_table.corners[corner].set_vertex_index(towards);
+        }
+    }
+
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::{join_edges, split_vertex};
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{
+            corner_table::{
+                connectivity::{corner::{next, previous}, traits::Flags},
+                prelude::CornerTableF,
+            },
+            primitives,
+            traits::{EditableMesh, Mesh, TopologicalMesh},
+        },
+    };
+
+    /// Faces as triples of positions, rotated to start from smallest one to keep orientation
+    fn face_set(mesh: &CornerTableF) -> HashSet<[[u32; 3]; 3]> {
+        mesh.faces()
+            .map(|face| {
+                let (v1, v2, v3) = mesh.face_vertices(&face);
+                let key = |v| mesh.vertex_position(&v).map(|c| c.to_bits()).into();
+                let mut face: [[u32; 3]; 3] = [key(v1), key(v2), key(v3)];
+                let min = (0..3).min_by_key(|&i| face[i]).unwrap();
+                face.rotate_left(min);
+                face
+            })
+            .collect()
+    }
+
+    fn assert_connectivity_valid(mesh: &CornerTableF) {
+        for (corner_index, corner) in mesh.corners.iter().enumerate() {
+            if corner.is_deleted() {
+                continue;
+            }
+
+            if let Some(opposite) = corner.get_opposite_corner_index() {
+                assert!(!mesh.corners[opposite].is_deleted());
+                assert_eq!(mesh.corners[opposite].get_opposite_corner_index(), Some(corner_index));
+                assert_eq!(
+                    mesh.corners[next(corner_index)].get_vertex_index(),
+                    mesh.corners[previous(opposite)].get_vertex_index()
+                );
+                assert_eq!(
+                    mesh.corners[previous(corner_index)].get_vertex_index(),
+                    mesh.corners[next(opposite)].get_vertex_index()
+                );
+            }
+        }
+
+        for (vertex_index, vertex) in mesh.vertices.iter().enumerate() {
+            if vertex.is_deleted() {
+                continue;
+            }
+
+            let corner = &mesh.corners[vertex.get_corner_index()];
+            assert!(!corner.is_deleted());
+            assert_eq!(corner.get_vertex_index(), vertex_index);
+        }
+    }
+
+    /// Returns vertex of plane at given position
+    fn vertex_at(mesh: &CornerTableF, position: Vec3f) -> usize {
+        mesh.vertices().find(|v| (mesh.vertex_position(v) - position).norm() < 1e-5).unwrap()
+    }
+
+    #[test]
+    fn split_vertex_is_inverse_of_collapse() {
+        let mut mesh: CornerTableF = primitives::plane(4.0, 4.0, 4, 4);
+        let original = face_set(&mesh);
+
+        let center = Vec3f::new(0.0, 0.0, 0.0);
+        let v = vertex_at(&mesh, center);
+
+        let mut ring = Vec::new();
+        mesh.vertices_around_vertex(&v, |n| ring.push(*n));
+        let (left, right) = (ring[0], ring[3]);
+
+        let u = split_vertex(&mut mesh, v, Some(left), Some(right), center, Vec3f::new(0.25, 0.25, 0.0)).unwrap();
+
+        assert_connectivity_valid(&mesh);
+        assert_eq!(mesh.faces().count(), 32 + 2);
+        assert_eq!(mesh.vertices().count(), 25 + 1);
+
+        let mut edge = None;
+        mesh.edges_around_vertex(&u, |e| {
+            let (v1, v2) = mesh.edge_vertices(e);
+            if v1 == v || v2 == v {
+                edge = Some(*e);
+            }
+        });
+
+        mesh.collapse_edge(&edge.unwrap(), &center);
+
+        assert_connectivity_valid(&mesh);
+        assert_eq!(face_set(&mesh), original);
+    }
+
+    #[test]
+    fn split_boundary_vertex() {
+        let mut mesh: CornerTableF = primitives::plane(4.0, 4.0, 4, 4);
+        let v = vertex_at(&mesh, Vec3f::new(0.0, 2.0, 0.0));
+
+        let mut ring = Vec::new();
+        mesh.vertices_around_vertex(&v, |n| ring.push(*n));
+        let interior = *ring.iter().find(|n| !mesh.is_vertex_on_boundary(n)).unwrap();
+
+        let split = split_vertex(&mut mesh, v, Some(interior), None, Vec3f::new(-0.25, 2.0, 0.0), Vec3f::new(0.25, 2.0, 0.0))
+            .or_else(|| split_vertex(&mut mesh, v, None, Some(interior), Vec3f::new(-0.25, 2.0, 0.0), Vec3f::new(0.25, 2.0, 0.0)));
+
+        assert!(split.is_some());
+        assert_connectivity_valid(&mesh);
+        assert_eq!(mesh.faces().count(), 32 + 1);
+
+        // Interior vertex needs both wings
+        let center = vertex_at(&mesh, Vec3f::new(0.0, 0.0, 0.0));
+        let mut center_ring = Vec::new();
+        mesh.vertices_around_vertex(&center, |n| center_ring.push(*n));
+        assert!(split_vertex(&mut mesh, center, Some(center_ring[0]), None, Vec3f::zeros(), Vec3f::zeros()).is_none());
+    }
+
+    #[test]
+    fn join_edges_is_inverse_of_split() {
+        let mut mesh: CornerTableF = primitives::plane(4.0, 4.0, 4, 4);
+        let original = face_set(&mesh);
+
+        let mut inner_edge = None;
+        let mut boundary_edge = None;
+
+        for edge in mesh.edges() {
+            let (v1, v2) = mesh.edge_vertices(&edge);
+
+            if mesh.is_edge_on_boundary(&edge) {
+                boundary_edge.get_or_insert(edge);
+            } else if !mesh.is_vertex_on_boundary(&v1) && !mesh.is_vertex_on_boundary(&v2) {
+                inner_edge.get_or_insert(edge);
+            }
+        }
+
+        for edge in [inner_edge.unwrap(), boundary_edge.unwrap()] {
+            let (start, end) = mesh.edge_positions(&edge);
+            let middle = (start + end) * 0.5;
+
+            mesh.split_edge(&edge, &middle);
+            assert_connectivity_valid(&mesh);
+
+            let vertex = vertex_at(&mesh, middle);
+            let towards = vertex_at(&mesh, start);
+
+            assert!(join_edges(&mut mesh, vertex, towards));
+            assert!(mesh.vertices[vertex].is_deleted());
+            assert_eq!(*mesh.vertex_position(&towards), start);
+
+            assert_connectivity_valid(&mesh);
+            assert_eq!(face_set(&mesh), original);
+        }
+
+        // Vertex of original plane can't be joined
+        let center = vertex_at(&mesh, Vec3f::new(0.0, 0.0, 0.0));
+        let corner = mesh.vertices[center].get_corner_index();
+        let neighbor = mesh.corners[next(corner)].get_vertex_index();
+        assert!(!join_edges(&mut mesh, center, neighbor));
+    }
+}
@@ -26,7 +26,7 @@ fn set_corner_for_wing_vertex<TScalar: RealNumber>(
 
 /// Make corners opposite to each other
 #[inline]
-fn make_corners_opposite<TScalar: RealNumber>(
+pub(super) fn make_corners_opposite<TScalar: RealNumber>(
     corner_table: &mut CornerTable<TScalar>, 
     c1: Option<usize>,
     c2: Option<usize>
@@ -0,0 +1,259 @@
+use num_traits::Float;
+
+use crate::{
+    geometry::{
+        primitives::line_segment3::LineSegment3,
+        traits::{ClosestPoint3, RealNumber},
+    },
+    helpers::aliases::Vec3,
+    mesh::traits::{EditableMesh, Mesh, SplitFaceAtPoint},
+};
+
+use super::{
+    connectivity::corner::{next, previous},
+    descriptors::{EdgeId, EdgeRef},
+    table::CornerTable,
+    traversal::collect_corners_around_vertex,
+};
+
+/// Step of walk along curve segment
+enum Step<TScalar: RealNumber> {
+    /// Move along existing edge to vertex
+    Vertex(usize),
+    /// Split edge opposite to corner at point and move to new vertex
+    Edge(usize, Vec3<TScalar>),
+}
+
+impl<TScalar: RealNumber> CornerTable<TScalar> {
+    ///
+    /// Inserts polyline lying on surface (e.g. curve drawn by user and projected on mesh) into connectivity of mesh,
+    /// so it is represented by edges. Points of polyline are projected to closest face and inserted as vertices,
+    /// triangles between them are split along intersection of surface with plane through segment and surface normal.
+    /// Points closer than `tolerance` to existing vertex or edge are snapped to it, which avoids slivers.
+    ///
+    /// Returns vertices of embedded curve in order, consecutive vertices are connected by edge (see [EdgeId]).
+    /// Existing vertices keep their indices, inserted vertices are appended.
+    /// Returns `None` when curve can't be embedded, e.g. segment leaves mesh through boundary, mesh may be partially split in that case.
+    /// Polyline should be sampled densely enough that every segment is nearly flat on surface.
+    ///
+    /// ## Example
+    /// ```ignore
+    /// let path = mesh.embed_curve(&stroke, 1e-4).unwrap();
+    /// let cut: Vec<_> = path.windows(2).map(|edge| EdgeId::new(edge[0], edge[1])).collect();
+    /// ```
+    ///
+    pub fn embed_curve(&mut self, points: &[Vec3<TScalar>], tolerance: TScalar) -> Option<Vec<usize>> {
+        let mut controls = Vec::with_capacity(points.len());
+
+        for point in points {
+            let vertex = self.insert_point(point, tolerance)?;
+
+            if controls.last() != Some(&vertex) {
+                controls.push(vertex);
+            }
+        }
+
+        let mut path = vec![*controls.first()?];
+
+        for segment in controls.windows(2) {
+            self.embed_segment(segment[0], segment[1], tolerance, &mut path)?;
+        }
+
+        Some(path)
+    }
+
+    /// Inserts closest point on surface as vertex, returns existing vertex when point is snapped to it
+    fn insert_point(&mut self, point: &Vec3<TScalar>, tolerance: TScalar) -> Option<usize> {
+        let (face, closest) = self
+            .faces()
+            .map(|face| (face, self.face_positions(&face).closest_point(point)))
+            .min_by(|(_, a), (_, b)| (a - point).norm_squared().partial_cmp(&(b - point).norm_squared()).unwrap())?;
+
+        for corner in face..face + 3 {
+            let vertex = self.corners[corner].get_vertex_index();
+
+            if (self.vertices[vertex].get_position() - closest).norm() <= tolerance {
+                return Some(vertex);
+            }
+        }
+
+        for corner in face..face + 3 {
+            let (v1, v2) = (self.corner_vertex(next(corner)), self.corner_vertex(previous(corner)));
+            let on_edge = LineSegment3::new(&v1, &v2).closest_point(&closest);
+
+            if (on_edge - closest).norm() <= tolerance {
+                return Some(self.split_edge_at(corner, &on_edge));
+            }
+        }
+
+        let vertex = self.vertices.len();
+        self.split_face(&face, closest);
+
+        Some(vertex)
+    }
+
+    /// Connects two vertices by edges, visited vertices except `from` are appended to path
+    fn embed_segment(&mut self, from: usize, to: usize, tolerance: TScalar, path: &mut Vec<usize>) -> Option<()> {
+        let start = *self.vertices[from].get_position();
+        let end = *self.vertices[to].get_position();
+        let normal = self.vertex_normal(&from)? + self.vertex_normal(&to)?;
+        let plane_normal = (end - start).cross(&normal).try_normalize(Float::epsilon())?;
+
+        let mut previous_vertex = None;
+        let mut current = from;
+
+        // Every step crosses one of faces existing before walk
+        for _ in 0..=self.corners.len() / 3 {
+            if self.edge_by_id(&EdgeId::new(current, to)).is_some() {
+                path.push(to);
+                return Some(());
+            }
+
+            let position = *self.vertices[current].get_position();
+            let direction = end - position;
+            let distance = |vertex: usize| plane_normal.dot(&(self.vertices[vertex].get_position() - start));
+            let mut best: Option<(TScalar, Step<TScalar>)> = None;
+
+            for corner in collect_corners_around_vertex(self, current) {
+                let (v1, v2) = (self.corners[next(corner)].get_vertex_index(), self.corners[previous(corner)].get_vertex_index());
+                let (d1, d2) = (distance(v1), distance(v2));
+                let (p1, p2) = (*self.vertices[v1].get_position(), *self.vertices[v2].get_position());
+
+                let step = if Float::abs(d1) <= tolerance {
+                    (p1, Step::Vertex(v1))
+                } else if Float::abs(d2) <= tolerance {
+                    (p2, Step::Vertex(v2))
+                } else if d1 * d2 < TScalar::zero() {
+                    let at = p1 + (p2 - p1) * (d1 / (d1 - d2));
+
+                    if (at - p1).norm() <= tolerance {
+                        (p1, Step::Vertex(v1))
+                    } else if (at - p2).norm() <= tolerance {
+                        (p2, Step::Vertex(v2))
+                    } else {
+                        (at, Step::Edge(corner, at))
+                    }
+                } else {
+                    continue;
+                };
+
+                if let Step::Vertex(vertex) = step.1 {
+                    if Some(vertex) == previous_vertex {
+                        continue;
+                    }
+                }
+
+                // Plane crosses fan of vertex twice, keep crossing heading to end of segment
+                let Some(heading) = (step.0 - position).try_normalize(Float::epsilon()) else {
+                    continue;
+                };
+                let score = heading.dot(&direction);
+
+                if score > TScalar::zero() && best.as_ref().is_none_or(|(best, _)| score > *best) {
+                    best = Some((score, step.1));
+                }
+            }
+
+            let next_vertex = match best?.1 {
+                Step::Vertex(vertex) => vertex,
+                Step::Edge(corner, at) => self.split_edge_at(corner, &at),
+            };
+
+            path.push(next_vertex);
+
+            if next_vertex == to {
+                return Some(());
+            }
+
+            previous_vertex = Some(current);
+            current = next_vertex;
+        }
+
+        None
+    }
+
+    ///
+    /// Splits edge opposite to corner and returns new vertex at split point.
+    /// [EditableMesh::split_edge] moves one of edge vertices to split point, here vertices are swapped back,
+    /// so indices of existing vertices tracked by curve stay valid.
+    ///
+    fn split_edge_at(&mut self, corner: usize, at: &Vec3<TScalar>) -> usize {
+        let edge = EdgeRef::new(corner, self);
+        let shifted = self.corners[next(edge.get_corner_index())].get_vertex_index();
+        let created = self.vertices.len();
+
+        self.split_edge(&edge, at);
+
+        let shifted_corners = collect_corners_around_vertex(self, shifted);
+        let created_corners = collect_corners_around_vertex(self, created);
+
+        for corner in shifted_corners {
+            self.corners[corner].set_vertex_index(created);
+        }
+
+        for corner in created_corners {
+            self.corners[corner].set_vertex_index(shifted);
+        }
+
+        self.vertices.swap(shifted, created);
+
+        created
+    }
+
+    #[inline]
+    fn corner_vertex(&self, corner: usize) -> Vec3<TScalar> {
+        *self.vertices[self.corners[corner].get_vertex_index()].get_position()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{
+            corner_table::prelude::{CornerTableF, EdgeId},
+            primitives,
+            traits::Mesh,
+        },
+    };
+
+    #[test]
+    fn test_embed_curve() {
+        let mut mesh: CornerTableF = primitives::plane(1.0, 1.0, 4, 4);
+        let vertices_count = mesh.vertices().count();
+        let positions: Vec<_> = mesh.vertices().map(|vertex| *mesh.vertex_position(&vertex)).collect();
+
+        let stroke = [
+            Vec3f::new(-0.4, -0.3, 0.01),
+            Vec3f::new(0.05, 0.1, -0.01),
+            Vec3f::new(0.4, 0.35, 0.0),
+            Vec3f::new(0.4, 0.35, 0.0),
+        ];
+        let path = mesh.embed_curve(&stroke, 1e-4).unwrap();
+
+        assert!(mesh.validate_topology().is_valid());
+        assert!(path.len() > stroke.len());
+        assert_eq!(path.iter().filter(|vertex| **vertex < vertices_count).count(), 0);
+
+        // Existing vertices are not moved, curve vertices lie on polyline
+        for (vertex, position) in positions.iter().enumerate() {
+            assert_eq!(mesh.vertex_position(&vertex), position);
+        }
+
+        let polyline: Vec<_> = stroke.iter().map(|point| Vec3f::new(point.x, point.y, 0.0)).collect();
+
+        for edge in path.windows(2) {
+            assert!(mesh.edge_by_id(&EdgeId::new(edge[0], edge[1])).is_some());
+
+            let position = mesh.vertex_position(&edge[1]);
+            let on_polyline = polyline.windows(2).any(|segment| {
+                let (a, b) = (segment[0], segment[1]);
+                let t = (position - a).dot(&(b - a)) / (b - a).norm_squared();
+                (a + (b - a) * t.clamp(0.0, 1.0) - position).norm() < 1e-5
+            });
+            assert!(on_polyline);
+        }
+
+        assert!((mesh.vertex_position(path.last().unwrap()) - polyline[2]).norm() < 1e-6);
+    }
+}
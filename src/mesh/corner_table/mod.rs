@@ -2,11 +2,17 @@ pub mod table;
 pub mod prelude;
 pub mod traversal;
 pub mod connectivity;
+pub mod edit;
+pub mod edge_attribute;
+pub mod validation;
+pub mod positions;
+pub mod embed_curve;
+pub mod snapshot;
 
 mod marker;
 mod editable;
 mod descriptors;
-mod property_maps;
+pub(crate) mod property_maps;
 
 #[cfg(test)]
 mod test_helpers;
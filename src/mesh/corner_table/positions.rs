@@ -0,0 +1,198 @@
+use std::collections::HashSet;
+
+use crate::{geometry::traits::RealNumber, helpers::aliases::Vec3, mesh::traits::Mesh};
+
+use super::{
+    connectivity::{corner::first_corner_from_corner, traits::Flags},
+    table::CornerTable,
+    traversal::{faces_around_vertex, vertices_around_vertex},
+};
+
+///
+/// Vertices moved by [CornerTable::set_positions]. Pass it to caches depending on geometry,
+/// so they refresh only affected parts: [VertexNormals::invalidate], [AABBTree::refit_faces](crate::spatial_partitioning::aabb_tree::AABBTree::refit_faces).
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PositionsUpdate {
+    vertices: Vec<usize>,
+}
+
+impl PositionsUpdate {
+    /// Returns moved vertices in order of update, vertex moved several times is listed once
+    #[inline]
+    pub fn vertices(&self) -> &[usize] {
+        &self.vertices
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.vertices.is_empty()
+    }
+
+    /// Merges vertices of other update, e.g. to refresh caches once for several updates
+    pub fn merge(&mut self, other: &PositionsUpdate) {
+        let known: HashSet<_> = self.vertices.iter().copied().collect();
+        self.vertices.extend(other.vertices.iter().filter(|vertex| !known.contains(vertex)));
+    }
+
+    /// Returns faces (first corners) having moved vertex
+    pub fn faces<TScalar: RealNumber>(&self, mesh: &CornerTable<TScalar>) -> HashSet<usize> {
+        let mut faces = HashSet::new();
+
+        for vertex in &self.vertices {
+            faces_around_vertex(mesh, *vertex, |corner| {
+                faces.insert(first_corner_from_corner(*corner));
+            });
+        }
+
+        faces
+    }
+}
+
+impl<TScalar: RealNumber> CornerTable<TScalar> {
+    ///
+    /// Moves vertices to new positions given as pairs of vertex and position, connectivity is not changed.
+    /// Deleted and not existing vertices are skipped. Returns moved vertices, which are used to refresh caches
+    /// of normals and bounding volumes without rebuilding them, e.g. in simulation loop updating mesh every frame.
+    ///
+    /// ## Example
+    /// ```ignore
+    /// let mut normals = VertexNormals::new(&mesh);
+    /// let mut tree = AABBTree::from_mesh(&mesh).top_down::<MedianCut>();
+    ///
+    /// loop {
+    ///     let update = mesh.set_positions(simulation.step());
+    ///     normals.invalidate(&mesh, &update);
+    ///     tree.refit_faces(&mesh, &update.faces(&mesh));
+    /// }
+    /// ```
+    ///
+    pub fn set_positions<TIter>(&mut self, positions: TIter) -> PositionsUpdate
+    where
+        TIter: IntoIterator<Item = (usize, Vec3<TScalar>)>,
+    {
+        let mut moved = HashSet::new();
+        let mut update = PositionsUpdate::default();
+
+        for (vertex, position) in positions {
+            let Some(v) = self.vertices.get_mut(vertex).filter(|v| !v.is_deleted()) else {
+                continue;
+            };
+
+            v.set_position(position);
+
+            if moved.insert(vertex) {
+                update.vertices.push(vertex);
+            }
+        }
+
+        update
+    }
+}
+
+///
+/// Vertex normals of corner table (see [Mesh::vertex_normal]) computed lazily: normal is computed on first access
+/// and reused until its vertex or one of neighbors is moved, see [VertexNormals::invalidate].
+///
+#[derive(Debug, Clone)]
+pub struct VertexNormals<TScalar: RealNumber> {
+    normals: Vec<Option<Vec3<TScalar>>>,
+    valid: Vec<bool>,
+}
+
+impl<TScalar: RealNumber> VertexNormals<TScalar> {
+    /// Creates cache for vertices of mesh, nothing is computed until normals are requested
+    pub fn new(mesh: &CornerTable<TScalar>) -> Self {
+        Self {
+            normals: vec![None; mesh.vertices.len()],
+            valid: vec![false; mesh.vertices.len()],
+        }
+    }
+
+    /// Returns normal of vertex, `None` when vertex has no faces or all of them are degenerate
+    pub fn get(&mut self, mesh: &CornerTable<TScalar>, vertex: usize) -> Option<Vec3<TScalar>> {
+        self.resize(mesh);
+
+        if !self.valid[vertex] {
+            self.normals[vertex] = mesh.vertex_normal(&vertex);
+            self.valid[vertex] = true;
+        }
+
+        self.normals[vertex]
+    }
+
+    /// Computes all outdated normals, e.g. before normals are read by other threads
+    pub fn refresh(&mut self, mesh: &CornerTable<TScalar>) {
+        for vertex in mesh.vertices() {
+            self.get(mesh, vertex);
+        }
+    }
+
+    /// Marks normals of moved vertices and their neighbors outdated
+    pub fn invalidate(&mut self, mesh: &CornerTable<TScalar>, update: &PositionsUpdate) {
+        self.resize(mesh);
+
+        for vertex in update.vertices() {
+            self.valid[*vertex] = false;
+            vertices_around_vertex(mesh, *vertex, |neighbor| self.valid[*neighbor] = false);
+        }
+    }
+
+    /// Vertices added to mesh after cache was created have no normals yet
+    fn resize(&mut self, mesh: &CornerTable<TScalar>) {
+        if self.normals.len() < mesh.vertices.len() {
+            self.normals.resize(mesh.vertices.len(), None);
+            self.valid.resize(mesh.vertices.len(), false);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::VertexNormals;
+    use crate::{
+        geometry::primitives::box3::Box3,
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+        spatial_partitioning::aabb_tree::{AABBTree, MedianCut},
+    };
+
+    #[test]
+    fn test_set_positions() {
+        let mut mesh: CornerTableF = primitives::plane(1.0, 1.0, 8, 8);
+        let mut normals = VertexNormals::new(&mesh);
+        let mut tree = AABBTree::from_mesh(&mesh).with_min_objects_per_leaf(2).top_down::<MedianCut>();
+        normals.refresh(&mesh);
+
+        // Raise central vertex
+        let center = mesh.vertices().find(|vertex| mesh.vertex_position(vertex).norm() < 1e-6).unwrap();
+        let update = mesh.set_positions([
+            (center, Vec3f::new(0.0, 0.0, 0.5)),
+            (center, Vec3f::new(0.0, 0.0, 0.25)),
+            (usize::MAX, Vec3f::zeros()),
+        ]);
+        assert_eq!(update.vertices(), &[center]);
+        assert_eq!(update.faces(&mesh).len(), 6);
+
+        let neighbor = mesh.vertex_one_ring(center).next().unwrap();
+        let stale = normals.get(&mesh, neighbor).unwrap();
+
+        normals.invalidate(&mesh, &update);
+        tree.refit_faces(&mesh, &update.faces(&mesh));
+
+        for vertex in mesh.vertices() {
+            assert_eq!(normals.get(&mesh, vertex), mesh.vertex_normal(&vertex));
+        }
+
+        assert_ne!(normals.get(&mesh, neighbor).unwrap(), stale);
+
+        // Refitted tree matches rebuilt one
+        let rebuilt = AABBTree::from_mesh(&mesh).with_min_objects_per_leaf(2).top_down::<MedianCut>();
+        let query = Box3::new(Vec3f::new(-0.1, -0.1, 0.2), Vec3f::new(0.1, 0.1, 0.3));
+        let (mut found, mut expected) = (tree.triangles_in_box(&query), rebuilt.triangles_in_box(&query));
+        found.sort_unstable();
+        expected.sort_unstable();
+        assert_eq!(found, expected);
+        assert_eq!(found.len(), 6);
+    }
+}
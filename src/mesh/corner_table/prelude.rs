@@ -1,4 +1,9 @@
 use super::{table::CornerTable};
+pub use super::descriptors::{EdgeId, FaceId};
+pub use super::edge_attribute::EdgeAttribute;
+pub use super::validation::{TopologyReport, TopologyViolation};
+pub use super::positions::{PositionsUpdate, VertexNormals};
+pub use super::snapshot::{SnapshotAttribute, SnapshotAttributes, SNAPSHOT_VERSION};
 
 pub type CornerTableF = CornerTable<f32>;
 pub type CornerTableD = CornerTable<f64>;
@@ -0,0 +1,383 @@
+use std::{
+    io::{self, Error, ErrorKind, Read, Write},
+    mem::size_of,
+};
+
+use num_traits::cast;
+
+use crate::{geometry::traits::RealNumber, helpers::aliases::Vec3};
+
+use super::{
+    connectivity::{corner::Corner, flags, traits::Flags, vertex::Vertex},
+    table::CornerTable,
+};
+
+/// Magic bytes snapshot starts with
+const MAGIC: &[u8; 8] = b"BSCTSNAP";
+/// Index stored in place of missing one (`None` opposite corner, `usize::MAX` corner of isolated vertex)
+const NO_INDEX: u64 = u64::MAX;
+
+/// Version of snapshot format written by [CornerTable::write_snapshot]
+pub const SNAPSHOT_VERSION: u32 = 1;
+
+///
+/// Values attached to elements (vertices or faces) of snapshot. Every element has `components` values,
+/// values of `i`-th element are `values[i * components..(i + 1) * components]`.
+/// Elements are indexed as in corner table, deleted ones included.
+///
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotAttribute {
+    pub name: String,
+    pub components: usize,
+    pub values: Vec<f64>,
+}
+
+/// Attributes stored along with corner table in snapshot
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct SnapshotAttributes {
+    pub vertex: Vec<SnapshotAttribute>,
+    pub face: Vec<SnapshotAttribute>,
+}
+
+impl SnapshotAttributes {
+    #[inline]
+    pub fn vertex_attribute(&self, name: &str) -> Option<&SnapshotAttribute> {
+        self.vertex.iter().find(|attribute| attribute.name == name)
+    }
+
+    #[inline]
+    pub fn face_attribute(&self, name: &str) -> Option<&SnapshotAttribute> {
+        self.face.iter().find(|attribute| attribute.name == name)
+    }
+}
+
+impl<TScalar: RealNumber> CornerTable<TScalar> {
+    ///
+    /// Writes binary snapshot of corner table: positions, connectivity and deleted flags of vertices and corners
+    /// (deleted elements are kept, so indices stay valid) and `attributes`. Other flags are not stored.
+    /// Format starts with magic bytes and [version](SNAPSHOT_VERSION), all numbers are little-endian.
+    ///
+    /// ## Example
+    /// ```ignore
+    /// let mut checkpoint = Vec::new();
+    /// mesh.write_snapshot(&attributes, &mut checkpoint)?;
+    ///
+    /// // Undo
+    /// let (mesh, attributes) = CornerTableF::read_snapshot(&mut checkpoint.as_slice())?;
+    /// ```
+    ///
+    pub fn write_snapshot<TWriter: Write>(&self, attributes: &SnapshotAttributes, writer: &mut TWriter) -> io::Result<()> {
+        let faces_count = self.corners.len() / 3;
+
+        for (attributes, count) in [(&attributes.vertex, self.vertices.len()), (&attributes.face, faces_count)] {
+            if let Some(attribute) = attributes.iter().find(|a| a.values.len() != a.components * count) {
+                return Err(Error::new(
+                    ErrorKind::InvalidInput,
+                    format!("Attribute {} has {} values, expected {}", attribute.name, attribute.values.len(), attribute.components * count),
+                ));
+            }
+        }
+
+        let scalar_size = size_of::<TScalar>().min(size_of::<f64>());
+        let mut bytes = Vec::with_capacity(
+            self.vertices.len() * vertex_record_size(scalar_size as u8) + self.corners.len() * CORNER_RECORD_SIZE,
+        );
+
+        bytes.extend_from_slice(MAGIC);
+        bytes.extend_from_slice(&SNAPSHOT_VERSION.to_le_bytes());
+        bytes.push(scalar_size as u8);
+        bytes.extend_from_slice(&self.generation.to_le_bytes());
+        bytes.extend_from_slice(&(self.vertices.len() as u64).to_le_bytes());
+        bytes.extend_from_slice(&(self.corners.len() as u64).to_le_bytes());
+
+        for vertex in &self.vertices {
+            for coordinate in vertex.get_position().iter() {
+                let coordinate = coordinate.to_f64().unwrap_or(f64::NAN);
+
+                if scalar_size == size_of::<f32>() {
+                    bytes.extend_from_slice(&(coordinate as f32).to_le_bytes());
+                } else {
+                    bytes.extend_from_slice(&coordinate.to_le_bytes());
+                }
+            }
+
+            bytes.extend_from_slice(&encode_index(Some(vertex.get_corner_index())).to_le_bytes());
+            bytes.push(vertex.is_deleted() as u8);
+        }
+
+        for corner in &self.corners {
+            bytes.extend_from_slice(&encode_index(corner.get_opposite_corner_index()).to_le_bytes());
+            bytes.extend_from_slice(&(corner.get_vertex_index() as u64).to_le_bytes());
+            bytes.push(corner.is_deleted() as u8);
+        }
+
+        for attributes in [&attributes.vertex, &attributes.face] {
+            bytes.extend_from_slice(&(attributes.len() as u32).to_le_bytes());
+
+            for attribute in attributes {
+                bytes.extend_from_slice(&(attribute.name.len() as u32).to_le_bytes());
+                bytes.extend_from_slice(attribute.name.as_bytes());
+                bytes.extend_from_slice(&(attribute.components as u32).to_le_bytes());
+                attribute.values.iter().for_each(|value| bytes.extend_from_slice(&value.to_le_bytes()));
+            }
+        }
+
+        writer.write_all(&bytes)
+    }
+
+    ///
+    /// Reads snapshot written by [CornerTable::write_snapshot]. Snapshots of older versions are supported,
+    /// positions are converted when snapshot was written with other scalar type.
+    /// Returns error with [ErrorKind::InvalidData] when data is not valid snapshot or it is newer than [SNAPSHOT_VERSION].
+    ///
+    pub fn read_snapshot<TReader: Read>(reader: &mut TReader) -> io::Result<(Self, SnapshotAttributes)> {
+        let header = read_bytes(reader, MAGIC.len() + 4 + 1 + 4 + 8 + 8)?;
+        let mut header = Bytes(&header);
+
+        if header.take(MAGIC.len()) != MAGIC {
+            return Err(invalid_data("Not a corner table snapshot"));
+        }
+
+        let version = header.u32();
+        if version == 0 || version > SNAPSHOT_VERSION {
+            return Err(invalid_data(&format!("Unsupported snapshot version {}", version)));
+        }
+
+        let scalar_size = header.take(1)[0];
+        if scalar_size as usize != size_of::<f32>() && scalar_size as usize != size_of::<f64>() {
+            return Err(invalid_data(&format!("Unsupported scalar size {}", scalar_size)));
+        }
+
+        let generation = header.u32();
+        let vertices_count = header.index()?;
+        let corners_count = header.index()?;
+
+        if corners_count % 3 != 0 {
+            return Err(invalid_data("Number of corners is not multiple of 3"));
+        }
+
+        let records = read_bytes(reader, vertices_count.saturating_mul(vertex_record_size(scalar_size)))?;
+        let mut vertices = Vec::with_capacity(vertices_count);
+
+        for mut record in records.chunks_exact(vertex_record_size(scalar_size)).map(Bytes) {
+            let mut position = Vec3::zeros();
+
+            for coordinate in position.iter_mut() {
+                let value = if scalar_size as usize == size_of::<f32>() { record.f32() as f64 } else { record.f64() };
+                *coordinate = cast(value).ok_or_else(|| invalid_data("Position can't be represented by scalar type"))?;
+            }
+
+            let corner = record.optional_index(corners_count)?.unwrap_or(usize::MAX);
+            vertices.push(Vertex::new(corner, position, deleted_flags(record.take(1)[0])));
+        }
+
+        let records = read_bytes(reader, corners_count.saturating_mul(CORNER_RECORD_SIZE))?;
+        let mut corners = Vec::with_capacity(corners_count);
+
+        for mut record in records.chunks_exact(CORNER_RECORD_SIZE).map(Bytes) {
+            let opposite = record.optional_index(corners_count)?;
+            let vertex = record.index()?;
+
+            if vertex >= vertices_count {
+                return Err(invalid_data("Vertex index is out of bounds"));
+            }
+
+            corners.push(Corner::new(opposite, vertex, deleted_flags(record.take(1)[0])));
+        }
+
+        let vertex = read_attributes(reader, vertices_count)?;
+        let face = read_attributes(reader, corners_count / 3)?;
+
+        let mesh = Self {
+            vertices,
+            corners,
+            generation,
+        };
+
+        Ok((mesh, SnapshotAttributes { vertex, face }))
+    }
+}
+
+/// Size of corner record: opposite corner, vertex and flags
+const CORNER_RECORD_SIZE: usize = 8 + 8 + 1;
+
+/// Size of vertex record: position, corner and flags
+#[inline]
+fn vertex_record_size(scalar_size: u8) -> usize {
+    3 * scalar_size as usize + 8 + 1
+}
+
+#[inline]
+fn encode_index(index: Option<usize>) -> u64 {
+    match index {
+        Some(index) if index != usize::MAX => index as u64,
+        _ => NO_INDEX,
+    }
+}
+
+#[inline]
+fn deleted_flags(deleted: u8) -> flags::Flags {
+    if deleted != 0 {
+        flags::Flags::IS_DELETED
+    } else {
+        flags::Flags::empty()
+    }
+}
+
+#[inline]
+fn invalid_data(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+///
+/// Reads exactly `len` bytes. Buffer grows while data is read, so corrupted lengths
+/// fail on end of data instead of allocating huge buffer upfront.
+///
+fn read_bytes<TReader: Read>(reader: &mut TReader, len: usize) -> io::Result<Vec<u8>> {
+    let mut bytes = Vec::new();
+    reader.take(len as u64).read_to_end(&mut bytes)?;
+
+    if bytes.len() != len {
+        return Err(Error::new(ErrorKind::UnexpectedEof, "Snapshot is truncated"));
+    }
+
+    Ok(bytes)
+}
+
+fn read_attributes<TReader: Read>(reader: &mut TReader, elements_count: usize) -> io::Result<Vec<SnapshotAttribute>> {
+    let count = Bytes(&read_bytes(reader, 4)?).u32();
+    let mut attributes = Vec::new();
+
+    for _ in 0..count {
+        let name_len = Bytes(&read_bytes(reader, 4)?).u32() as usize;
+        let name = String::from_utf8(read_bytes(reader, name_len)?).map_err(|_| invalid_data("Attribute name is not valid UTF-8"))?;
+        let components = Bytes(&read_bytes(reader, 4)?).u32() as usize;
+        let values = read_bytes(reader, components.saturating_mul(elements_count).saturating_mul(8))?
+            .chunks_exact(8)
+            .map(|value| Bytes(value).f64())
+            .collect();
+
+        attributes.push(SnapshotAttribute { name, components, values });
+    }
+
+    Ok(attributes)
+}
+
+/// Cursor over little-endian record, length of record is checked before it is parsed
+struct Bytes<'a>(&'a [u8]);
+
+impl<'a> Bytes<'a> {
+    #[inline]
+    fn take(&mut self, len: usize) -> &'a [u8] {
+        let (taken, rest) = self.0.split_at(len);
+        self.0 = rest;
+        taken
+    }
+
+    #[inline]
+    fn u32(&mut self) -> u32 {
+        u32::from_le_bytes(self.take(4).try_into().unwrap())
+    }
+
+    #[inline]
+    fn u64(&mut self) -> u64 {
+        u64::from_le_bytes(self.take(8).try_into().unwrap())
+    }
+
+    #[inline]
+    fn f32(&mut self) -> f32 {
+        f32::from_le_bytes(self.take(4).try_into().unwrap())
+    }
+
+    #[inline]
+    fn f64(&mut self) -> f64 {
+        f64::from_le_bytes(self.take(8).try_into().unwrap())
+    }
+
+    #[inline]
+    fn index(&mut self) -> io::Result<usize> {
+        usize::try_from(self.u64()).map_err(|_| invalid_data("Index is too large"))
+    }
+
+    /// Reads index which is either missing or less than `bound`
+    #[inline]
+    fn optional_index(&mut self, bound: usize) -> io::Result<Option<usize>> {
+        match self.u64() {
+            NO_INDEX => Ok(None),
+            index if index < bound as u64 => Ok(Some(index as usize)),
+            _ => Err(invalid_data("Index is out of bounds")),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::ErrorKind;
+
+    use super::{SnapshotAttribute, SnapshotAttributes, SNAPSHOT_VERSION};
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{
+            corner_table::{
+                connectivity::traits::Flags,
+                prelude::{CornerTableD, CornerTableF},
+            },
+            primitives,
+            traits::{EditableMesh, Mesh},
+        },
+    };
+
+    #[test]
+    fn test_snapshot_round_trip() {
+        let mut mesh: CornerTableF = primitives::cuboid(Vec3f::new(1.0, 2.0, 3.0), 2);
+        let edge = mesh.edges().next().unwrap();
+        let (v1, v2) = mesh.edge_positions(&edge);
+        mesh.collapse_edge(&edge, &((v1 + v2) * 0.5));
+
+        let attributes = SnapshotAttributes {
+            vertex: vec![SnapshotAttribute {
+                name: "color".into(),
+                components: 3,
+                values: (0..mesh.vertices.len() * 3).map(|v| v as f64).collect(),
+            }],
+            face: vec![SnapshotAttribute {
+                name: "group".into(),
+                components: 1,
+                values: (0..mesh.corners.len() / 3).map(|f| (f % 4) as f64).collect(),
+            }],
+        };
+
+        let mut bytes = Vec::new();
+        mesh.write_snapshot(&attributes, &mut bytes).unwrap();
+        assert_eq!(&bytes[8..12], &SNAPSHOT_VERSION.to_le_bytes());
+
+        let (restored, restored_attributes) = CornerTableF::read_snapshot(&mut bytes.as_slice()).unwrap();
+        assert_eq!(restored.vertices, mesh.vertices);
+        assert_eq!(restored.corners, mesh.corners);
+        assert_eq!(restored.generation, mesh.generation);
+        assert_eq!(restored_attributes, attributes);
+        assert!(restored.corners.iter().any(|corner| corner.is_deleted()));
+        assert!(restored.corners.iter().zip(&mesh.corners).all(|(c1, c2)| c1.is_deleted() == c2.is_deleted()));
+        assert!(restored.vertices.iter().zip(&mesh.vertices).all(|(v1, v2)| v1.is_deleted() == v2.is_deleted()));
+        assert_eq!(restored.faces().count(), mesh.faces().count());
+
+        // Positions are converted to other scalar type
+        let (converted, _) = CornerTableD::read_snapshot(&mut bytes.as_slice()).unwrap();
+        assert_eq!(converted.corners, mesh.corners);
+        assert_eq!(converted.vertices().count(), mesh.vertices().count());
+
+        // Invalid snapshots are rejected
+        let mut future = bytes.clone();
+        future[8..12].copy_from_slice(&(SNAPSHOT_VERSION + 1).to_le_bytes());
+        let result = CornerTableF::read_snapshot(&mut future.as_slice());
+        assert!(result.is_err_and(|error| error.kind() == ErrorKind::InvalidData));
+
+        let result = CornerTableF::read_snapshot(&mut &bytes[..bytes.len() - 1]);
+        assert!(result.is_err_and(|error| error.kind() == ErrorKind::UnexpectedEof));
+
+        let mut wrong = attributes.clone();
+        wrong.face[0].values.pop();
+        let error = mesh.write_snapshot(&wrong, &mut Vec::new()).unwrap_err();
+        assert_eq!(error.kind(), ErrorKind::InvalidInput);
+    }
+}
@@ -1,6 +1,13 @@
-use std::{collections::HashMap, fmt::Display};
+use std::collections::{HashMap, HashSet};
+#[cfg(feature = "tabled")]
+use std::fmt::Display;
+#[cfg(feature = "tabled")]
 use tabled::Table;
-use crate::{mesh::traits::{Mesh, TopologicalMesh, MeshMarker}, geometry::traits::RealNumber, helpers::aliases::Vec3};
+use crate::{
+    mesh::{traits::{Mesh, TopologicalMesh, MeshMarker}, remap::{VertexRemap, FaceRemap}}, 
+    geometry::traits::RealNumber, 
+    helpers::aliases::Vec3
+};
 use self::helpers::Edge;
 use super::{
     traversal::{
@@ -8,27 +15,55 @@ use super::{
         CornerTableVerticesIter, 
         CornerTableEdgesIter, 
         CornerWalker, 
+        VertexCornersIter,
         faces_around_vertex, 
         vertices_around_vertex, 
-        edges_around_vertex
+        edges_around_vertex,
+        corners_around_vertex
     }, 
     connectivity::{
-        corner::{Corner, first_corner_from_corner}, 
-        vertex::Vertex
+        corner::{Corner, first_corner_from_corner, face, first_corner, next, previous}, 
+        vertex::Vertex,
+        traits::Flags
     }, 
-    marker::CornerTableMarker, descriptors::EdgeRef
+    marker::CornerTableMarker, descriptors::{EdgeId, EdgeRef, FaceId}
 };
 
+///
+/// Non-manifold elements resolved by [CornerTable::from_faces_split_non_manifold]
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct NonManifoldSplit {
+    /// Edges (pairs of input vertex indices) shared by more than two faces or by two inconsistently oriented faces
+    pub non_manifold_edges: Vec<(usize, usize)>,
+    /// Input vertex of every created duplicate, `i`-th duplicate is appended after input vertices
+    pub duplicated_vertices: Vec<usize>,
+    /// Indices of skipped faces referencing same vertex more than once
+    pub degenerate_faces: Vec<usize>,
+}
+
+impl NonManifoldSplit {
+    /// Returns `true` when nothing was split or skipped, i.e. input was manifold
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.non_manifold_edges.is_empty() && self.duplicated_vertices.is_empty() && self.degenerate_faces.is_empty()
+    }
+}
+
 pub struct CornerTable<TScalar: RealNumber> {
     pub(super) vertices: Vec<Vertex<TScalar>>,
-    pub(super) corners: Vec<Corner>
+    pub(super) corners: Vec<Corner>,
+    /// Incremented each time faces are renumbered, see [FaceId]
+    pub(super) generation: u32
 }
 
 impl<TScalar: RealNumber> Default for CornerTable<TScalar> {
     fn default() -> Self {
         Self { 
             vertices: Vec::new(), 
-            corners: Vec::new() 
+            corners: Vec::new(),
+            generation: 0
         }
     }
 }
@@ -90,6 +125,140 @@ impl<TScalar: RealNumber> CornerTable<TScalar> {
         self.corners.len() - 3
     }
 
+    ///
+    /// Creates corner table from vertices and faces given as triplets of vertex indices.
+    /// Unlike [Mesh::from_vertices_and_indices], which skips faces introducing non-manifold edges,
+    /// non-manifold edges and vertices are split by duplicating vertices, so all non-degenerate faces are kept
+    /// and resulting mesh is always manifold. Returns mesh together with report of what was split.
+    ///
+    /// ## Example
+    /// ```ignore
+    /// let (mesh, split) = CornerTableF::from_faces_split_non_manifold(&positions, indices.chunks(3).map(|f| [f[0], f[1], f[2]]));
+    /// ```
+    ///
+    pub fn from_faces_split_non_manifold(
+        vertices: &[Vec3<TScalar>],
+        faces: impl IntoIterator<Item = [usize; 3]>
+    ) -> (Self, NonManifoldSplit) {
+        let mut split = NonManifoldSplit::default();
+        let mut corner_table = Self::new();
+
+        for position in vertices {
+            corner_table.create_vertex().set_position(*position);
+        }
+
+        // Corners without opposite by directed edge opposite to them
+        let mut unpaired = HashMap::<Edge, usize>::new();
+        // Number of faces and number of faces going from smaller vertex index to larger one for every undirected edge
+        let mut edge_faces = HashMap::<(usize, usize), (usize, usize)>::new();
+
+        for (face_index, [v1, v2, v3]) in faces.into_iter().enumerate() {
+            if v1 == v2 || v2 == v3 || v3 == v1 {
+                split.degenerate_faces.push(face_index);
+                continue;
+            }
+
+            let first = corner_table.create_face_from_vertices(v1, v2, v3);
+
+            for corner in first..first + 3 {
+                let vertex = corner_table.corners[corner].get_vertex_index();
+                corner_table.vertices[vertex].set_corner_index(corner);
+
+                let start = corner_table.corners[next(corner)].get_vertex_index();
+                let end = corner_table.corners[previous(corner)].get_vertex_index();
+
+                let (count, forward) = edge_faces.entry((start.min(end), start.max(end))).or_default();
+                *count += 1;
+                *forward += (start < end) as usize;
+
+                // Edges already used by face of same orientation are left on boundary and split below
+                if let Some(opposite) = unpaired.remove(&Edge::new(end, start)) {
+                    corner_table.set_opposite_relationship(corner, opposite);
+                } else {
+                    unpaired.entry(Edge::new(start, end)).or_insert(corner);
+                }
+            }
+        }
+
+        split.non_manifold_edges = edge_faces
+            .into_iter()
+            .filter(|(_, (count, forward))| *count > 2 || (*count == 2 && *forward != 1))
+            .map(|(edge, _)| edge)
+            .collect();
+        split.non_manifold_edges.sort_unstable();
+
+        // Every fan of corners around vertex except the first one gets its own copy of vertex
+        let mut vertex_corners = vec![Vec::new(); vertices.len()];
+        for (corner_index, corner) in corner_table.corners.iter().enumerate() {
+            vertex_corners[corner.get_vertex_index()].push(corner_index);
+        }
+
+        let mut visited = vec![false; corner_table.corners.len()];
+        let mut fan = Vec::new();
+
+        for (vertex, corners) in vertex_corners.iter().enumerate() {
+            for &corner in corners {
+                if visited[corner] {
+                    continue;
+                }
+
+                let fan_vertex = if corner == corners[0] {
+                    vertex
+                } else {
+                    corner_table.create_vertex().set_position(vertices[vertex]);
+                    split.duplicated_vertices.push(vertex);
+                    corner_table.vertices.len() - 1
+                };
+
+                corner_table.vertices[fan_vertex].set_corner_index(corner);
+
+                fan.clear();
+                corners_around_vertex(&corner_table, fan_vertex, |fan_corner| fan.push(*fan_corner));
+
+                for fan_corner in &fan {
+                    visited[*fan_corner] = true;
+                    corner_table.corners[*fan_corner].set_vertex_index(fan_vertex);
+                }
+            }
+        }
+
+        (corner_table, split)
+    }
+
+    /// Returns stable identifier of face
+    #[inline]
+    pub fn face_id(&self, face: usize) -> FaceId {
+        FaceId::new(face, self.generation)
+    }
+
+    /// Returns identifiers of all faces, deleted faces are skipped
+    pub fn face_ids(&self) -> impl Iterator<Item = FaceId> + '_ {
+        self.faces().map(|face| self.face_id(face))
+    }
+
+    /// Returns canonical identifier of edge
+    #[inline]
+    pub fn edge_id(&self, edge: &EdgeRef) -> EdgeId {
+        let (v1, v2) = self.edge_vertices(edge);
+        EdgeId::new(v1, v2)
+    }
+
+    /// Returns identifiers of all edges, every edge shared by two faces is returned once
+    pub fn unique_edges(&self) -> impl Iterator<Item = EdgeId> + '_ {
+        self.edges().map(|edge| self.edge_id(&edge))
+    }
+
+    /// Returns edge with given identifier, `None` when its vertices are not connected
+    pub fn edge_by_id(&self, id: &EdgeId) -> Option<EdgeRef> {
+        let (v1, _) = id.vertices();
+
+        if self.vertices.get(v1)?.is_deleted() {
+            return None;
+        }
+
+        self.vertex_edge_star(v1).find(|edge| self.edge_id(edge) == *id)
+    }
+
     /// Makes give corners opposite to each other
     #[inline]
     pub fn set_opposite_relationship(&mut self, corner1_index: usize, corner2_index: usize) {
@@ -97,6 +266,269 @@ impl<TScalar: RealNumber> CornerTable<TScalar> {
         self.get_corner_mut(corner2_index).unwrap().set_opposite_corner_index(Some(corner1_index));
     }
 
+    ///
+    /// Removes deleted vertices and faces (e.g. left after edge collapses) from storage.
+    /// Returns tables mapping old vertex and face indices to new ones. Face index is index of its first corner divided by 3.
+    /// Order of remaining vertices and faces is preserved.
+    /// [FaceId]s are invalidated when faces are renumbered, see [FaceId::remap].
+    ///
+    pub fn compact(&mut self) -> (VertexRemap, FaceRemap) {
+        let mut vertex_map = Vec::with_capacity(self.vertices.len());
+        let mut vertices_count = 0;
+
+        for vertex in &self.vertices {
+            if vertex.is_deleted() {
+                vertex_map.push(None);
+            } else {
+                vertex_map.push(Some(vertices_count));
+                vertices_count += 1;
+            }
+        }
+
+        let mut face_map = Vec::with_capacity(self.corners.len() / 3);
+        let mut faces_count = 0;
+
+        for first_corner in (0..self.corners.len()).step_by(3) {
+            if self.corners[first_corner].is_deleted() {
+                face_map.push(None);
+            } else {
+                face_map.push(Some(faces_count));
+                faces_count += 1;
+            }
+        }
+
+        let vertex_remap = VertexRemap::new(vertex_map);
+        let face_remap = FaceRemap::new(face_map);
+        let remap_corner = |corner: usize| {
+            face_remap
+                .get(face(corner))
+                .map(|new_face| new_face * 3 + corner % 3)
+        };
+
+        let mut corners = Vec::with_capacity(faces_count * 3);
+
+        for (corner_index, corner) in self.corners.iter().enumerate() {
+            if face_remap.is_removed(face(corner_index)) {
+                continue;
+            }
+
+            let opposite = corner.get_opposite_corner_index().and_then(remap_corner);
+            let vertex = vertex_remap
+                .get(corner.get_vertex_index())
+                .expect("Face should not reference deleted vertex");
+
+            corners.push(Corner::new(opposite, vertex, Default::default()));
+        }
+
+        let mut vertices = Vec::with_capacity(vertices_count);
+
+        for vertex in &self.vertices {
+            if vertex.is_deleted() {
+                continue;
+            }
+
+            let corner_index = remap_corner(vertex.get_corner_index()).unwrap_or(usize::MAX);
+            vertices.push(Vertex::new(corner_index, *vertex.get_position(), Default::default()));
+        }
+
+        if face_remap.new_len() != face_remap.old_len() {
+            self.generation += 1;
+        }
+
+        self.vertices = vertices;
+        self.corners = corners;
+
+        (vertex_remap, face_remap)
+    }
+
+    ///
+    /// Removes face and returns boundary loops passing through resulting hole.
+    /// Each loop is a list of vertices ordered along boundary, it can be passed to hole filling.
+    /// Vertices left without faces are removed too.
+    ///
+    /// Returns `None` and leaves mesh untouched when face is already removed
+    /// or its removal would produce non-manifold vertex (vertex joining two fans).
+    ///
+    pub fn remove_face(&mut self, face_id: usize) -> Option<Vec<Vec<usize>>> {
+        self.remove_faces(&[face(face_id)])
+    }
+
+    ///
+    /// Removes vertex together with all faces around it and returns boundary loops passing through resulting hole.
+    /// See [remove_face](Self::remove_face).
+    ///
+    pub fn remove_vertex_with_fan(&mut self, vertex_id: usize) -> Option<Vec<Vec<usize>>> {
+        if self.vertices.get(vertex_id)?.is_deleted() {
+            return None;
+        }
+
+        let mut fan = Vec::new();
+        faces_around_vertex(self, vertex_id, |face_corner| fan.push(face(*face_corner)));
+
+        self.remove_faces(&fan)
+    }
+
+    /// Removes faces (given by face indices). See [remove_face](Self::remove_face).
+    fn remove_faces(&mut self, faces: &[usize]) -> Option<Vec<Vec<usize>>> {
+        let removed: HashSet<usize> = faces.iter().copied().collect();
+
+        if removed.iter().any(|face| self.corners.get(first_corner(*face)).is_none_or(|c| c.is_deleted())) {
+            return None;
+        }
+
+        // Find corner of remaining face for each affected vertex, `None` if vertex will be isolated
+        let mut affected_vertices = HashMap::new();
+
+        for removed_face in &removed {
+            for corner in first_corner(*removed_face)..first_corner(*removed_face) + 3 {
+                let vertex = self.corners[corner].get_vertex_index();
+
+                if affected_vertices.contains_key(&vertex) {
+                    continue;
+                }
+
+                let mut remaining_corner = None;
+                let mut boundary_edges = 0;
+
+                edges_around_vertex(self, vertex, |edge| {
+                    let (f1, f2) = self.edge_faces(edge);
+                    let remaining: Vec<_> = std::iter::once(f1)
+                        .chain(f2)
+                        .filter(|f| !removed.contains(&face(*f)))
+                        .collect();
+
+                    if remaining.len() == 1 {
+                        boundary_edges += 1;
+                    }
+
+                    if let Some(f) = remaining.first() {
+                        let first = first_corner_from_corner(*f);
+                        remaining_corner = (first..first + 3).find(|c| self.corners[*c].get_vertex_index() == vertex);
+                    }
+                });
+
+                // Remaining faces around vertex should form single fan
+                if boundary_edges > 2 {
+                    return None;
+                }
+
+                affected_vertices.insert(vertex, remaining_corner);
+            }
+        }
+
+        // Unlink removed faces from their neighbors
+        let mut new_boundary_corners = Vec::new();
+
+        for removed_face in &removed {
+            for corner in first_corner(*removed_face)..first_corner(*removed_face) + 3 {
+                if let Some(opposite) = self.corners[corner].get_opposite_corner_index() {
+                    if !removed.contains(&face(opposite)) {
+                        self.corners[opposite].set_opposite_corner_index(None);
+                        new_boundary_corners.push(opposite);
+                    }
+                }
+
+                self.corners[corner].set_opposite_corner_index(None);
+                self.corners[corner].set_deleted(true);
+            }
+        }
+
+        for (vertex, remaining_corner) in affected_vertices {
+            match remaining_corner {
+                Some(corner) => self.vertices[vertex].set_corner_index(corner),
+                None => self.vertices[vertex].set_deleted(true),
+            };
+        }
+
+        // Collect boundary loops passing through new boundary edges
+        let mut visited = HashSet::new();
+        let mut loops = Vec::new();
+
+        for start in new_boundary_corners {
+            if visited.contains(&start) {
+                continue;
+            }
+
+            let mut boundary_loop = Vec::new();
+            let mut corner = start;
+
+            while visited.insert(corner) {
+                boundary_loop.push(self.corners[next(corner)].get_vertex_index());
+                corner = self.next_boundary_corner(corner);
+            }
+
+            loops.push(boundary_loop);
+        }
+
+        Some(loops)
+    }
+
+    ///
+    /// Returns iterator over vertices adjacent to given vertex in counterclockwise order.
+    /// For boundary vertex first and last vertices are on boundary. Deleted vertex has no neighbors.
+    ///
+    pub fn vertex_one_ring(&self, vertex_index: usize) -> impl Iterator<Item = usize> + '_ {
+        let mut corners = VertexCornersIter::new(self, vertex_index);
+        let mut last_corner = None;
+
+        std::iter::from_fn(move || match corners.next() {
+            Some(corner) => {
+                last_corner = Some(corner);
+                Some(self.corners[next(corner)].get_vertex_index())
+            }
+            // Vertex closing boundary fan
+            None => {
+                let corner = last_corner.take()?;
+                self.corners[next(corner)]
+                    .get_opposite_corner_index()
+                    .is_none()
+                    .then(|| self.corners[previous(corner)].get_vertex_index())
+            }
+        })
+    }
+
+    ///
+    /// Returns iterator over edges incident to given vertex in counterclockwise order.
+    /// Same as [edges_around_vertex](crate::mesh::traits::TopologicalMesh::edges_around_vertex) but lazy.
+    ///
+    pub fn vertex_edge_star(&self, vertex_index: usize) -> impl Iterator<Item = EdgeRef> + '_ {
+        let mut corners = VertexCornersIter::new(self, vertex_index);
+        let mut last_corner = None;
+
+        std::iter::from_fn(move || match corners.next() {
+            Some(corner) => {
+                last_corner = Some(corner);
+                Some(EdgeRef::new(previous(corner), self))
+            }
+            // Edge closing boundary fan
+            None => {
+                let corner = last_corner.take()?;
+                self.corners[next(corner)]
+                    .get_opposite_corner_index()
+                    .is_none()
+                    .then(|| EdgeRef::new(next(corner), self))
+            }
+        })
+    }
+
+    /// Returns iterator over faces sharing edge with given face. Faces are returned as corner indices.
+    pub fn face_neighbors(&self, face_index: usize) -> impl Iterator<Item = usize> + '_ {
+        let first = first_corner_from_corner(face_index);
+        (first..first + 3).filter_map(|corner| self.corners[corner].get_opposite_corner_index())
+    }
+
+    /// Returns corner opposite to next boundary edge along the boundary loop
+    fn next_boundary_corner(&self, boundary_corner: usize) -> usize {
+        // Rotate around end vertex of boundary edge until boundary is reached
+        let mut corner = next(boundary_corner);
+
+        while let Some(opposite) = self.corners[corner].get_opposite_corner_index() {
+            corner = next(opposite);
+        }
+
+        corner
+    }
+
     fn corner_from(
         &mut self,
         edge_opposite_corner_map: &mut HashMap<Edge, usize>,
@@ -164,7 +596,7 @@ impl<TScalar: RealNumber> Mesh for CornerTable<TScalar> {
             vertex.set_position(*v_position);
         }
 
-        for face_idx in (0..faces.len() - 1).step_by(3) {
+        for face_idx in (0..faces.len()).step_by(3) {
             let v1_index = faces[face_idx];
             let v2_index = faces[face_idx + 1];
             let v3_index = faces[face_idx + 2];
@@ -298,10 +730,11 @@ impl<TScalar: RealNumber> TopologicalMesh for CornerTable<TScalar> {
 
     #[inline]
     fn edge_faces(&self, edge: &Self::EdgeDescriptor) -> (Self::FaceDescriptor, Option<Self::FaceDescriptor>) {
-        let f1 = edge.get_corner_index();
+        // Faces are identified by their first corners, same as in faces iterator
+        let corner = edge.get_corner_index();
         (
-            f1,
-            self.corners[f1].get_opposite_corner_index()
+            first_corner_from_corner(corner),
+            self.corners[corner].get_opposite_corner_index().map(first_corner_from_corner)
         )
     }
 
@@ -325,6 +758,7 @@ impl<TScalar: RealNumber> MeshMarker for CornerTable<TScalar> {
     }
 }
 
+#[cfg(feature = "tabled")]
 impl<TScalar: RealNumber> Display for CornerTable<TScalar> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         let vertices = Table::new(self.vertices.iter());
@@ -368,11 +802,13 @@ pub mod helpers {
 mod tests {
     use crate::{mesh::{
         corner_table::{
-            test_helpers::{create_unit_square_mesh, assert_mesh_eq}, 
+            test_helpers::{create_unit_square_mesh, assert_mesh_eq, create_collapse_edge_sample_mesh1}, 
             connectivity::{vertex::VertexF, corner::Corner}, 
-            prelude::CornerTableF
+            prelude::CornerTableF,
+            descriptors::EdgeRef
         }, 
-        traits::Mesh
+        primitives,
+        traits::{Mesh, EditableMesh, TopologicalMesh}
     }, helpers::aliases::{Vec3f, Vec3}};
 
     #[test]
@@ -418,4 +854,206 @@ mod tests {
 
         assert!(mesh.faces().count() == 4);
     }
+
+    #[test]
+    fn from_faces_split_non_manifold() {
+        let plane: CornerTableF = primitives::plane(1.0, 1.0, 2, 2);
+        let positions: Vec<_> = plane.vertices().map(|v| *plane.vertex_position(&v)).collect();
+        let faces: Vec<_> = plane.faces().map(|face| {
+            let (v1, v2, v3) = plane.face_vertices(&face);
+            [v1, v2, v3]
+        }).collect();
+
+        let (mesh, split) = CornerTableF::from_faces_split_non_manifold(&positions, faces);
+        assert!(split.is_empty());
+        assert_eq!(mesh.faces().count(), 8);
+        assert_eq!(mesh.vertices().count(), 9);
+
+        // Fin: three faces sharing edge (0, 1), and bowtie touching vertex 0
+        let positions = [
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(1.0, 0.0, 0.0),
+            Vec3::new(0.5, 1.0, 0.0),
+            Vec3::new(0.5, -1.0, 0.0),
+            Vec3::new(0.5, 0.0, 1.0),
+            Vec3::new(-1.0, 0.5, 0.0),
+            Vec3::new(-1.0, -0.5, 0.0),
+        ];
+        let faces = [[0, 1, 2], [1, 0, 3], [0, 1, 4], [0, 5, 6], [2, 2, 3]];
+
+        let (mesh, split) = CornerTableF::from_faces_split_non_manifold(&positions, faces);
+        assert_eq!(split.non_manifold_edges, vec![(0, 1)]);
+        assert_eq!(split.degenerate_faces, vec![4]);
+        assert_eq!(mesh.faces().count(), 4);
+
+        let mut duplicated = split.duplicated_vertices.clone();
+        duplicated.sort();
+        assert_eq!(duplicated, vec![0, 0, 1]);
+
+        // Every vertex has single fan
+        for vertex in mesh.vertices() {
+            let mut fan_size = 0;
+            mesh.faces_around_vertex(&vertex, |_| fan_size += 1);
+            let corners = mesh.corners.iter().filter(|c| c.get_vertex_index() == vertex).count();
+            assert_eq!(fan_size, corners);
+        }
+    }
+
+    #[test]
+    fn compact() {
+        let mut mesh = create_collapse_edge_sample_mesh1();
+        mesh.collapse_edge(&EdgeRef::new(9, &mesh), &Vec3f::new(0.5, 0.5, 0.0));
+
+        let faces_before: Vec<_> = mesh.faces()
+            .map(|face| (face, mesh.face_vertices(&face)))
+            .collect();
+        let vertices_before: Vec<_> = mesh.vertices()
+            .map(|vertex| (vertex, *mesh.vertex_position(&vertex)))
+            .collect();
+
+        let (vertex_remap, face_remap) = mesh.compact();
+
+        assert_eq!(vertex_remap.old_len(), 10);
+        assert_eq!(vertex_remap.new_len(), 9);
+        assert_eq!(face_remap.old_len(), 10);
+        assert_eq!(face_remap.new_len(), 8);
+        assert_eq!(mesh.vertices().count(), 9);
+        assert_eq!(mesh.faces().count(), 8);
+
+        for (vertex, position) in vertices_before {
+            let new_vertex = vertex_remap.get(vertex).unwrap();
+            assert_eq!(*mesh.vertex_position(&new_vertex), position);
+
+            let corner = mesh.get_vertex(new_vertex).unwrap().get_corner_index();
+            assert_eq!(mesh.get_corner(corner).unwrap().get_vertex_index(), new_vertex);
+        }
+
+        for (face, vertices) in faces_before {
+            let new_face = face_remap.get(face / 3).unwrap() * 3;
+            let (v1, v2, v3) = vertices;
+            let expected = (
+                vertex_remap.get(v1).unwrap(),
+                vertex_remap.get(v2).unwrap(),
+                vertex_remap.get(v3).unwrap()
+            );
+            assert_eq!(mesh.face_vertices(&new_face), expected);
+        }
+
+        for (corner_index, corner) in mesh.corners.iter().enumerate() {
+            if let Some(opposite) = corner.get_opposite_corner_index() {
+                assert_eq!(mesh.corners[opposite].get_opposite_corner_index(), Some(corner_index));
+            }
+        }
+    }
+
+    #[test]
+    fn face_ids() {
+        let mut mesh = create_collapse_edge_sample_mesh1();
+        let ids: Vec<_> = mesh.face_ids().collect();
+
+        mesh.collapse_edge(&EdgeRef::new(9, &mesh), &Vec3f::new(0.5, 0.5, 0.0));
+
+        let valid: Vec<_> = ids.iter().copied().filter(|id| id.is_valid(&mesh)).collect();
+        assert_eq!(valid.len(), 8);
+        assert!(mesh.face_ids().all(|id| id.is_valid(&mesh)));
+
+        let positions = |mesh: &CornerTableF, face: usize| {
+            let (v1, v2, v3) = mesh.face_vertices(&face);
+            [v1, v2, v3].map(|v| *mesh.vertex_position(&v))
+        };
+        let valid_positions: Vec<_> = valid.iter().map(|id| positions(&mesh, id.face())).collect();
+
+        let (_, face_remap) = mesh.compact();
+        assert!(ids.iter().all(|id| !id.is_valid(&mesh)));
+        assert_eq!(ids.iter().filter_map(|id| id.remap(&face_remap, &mesh)).count(), 8);
+
+        for (id, expected) in valid.iter().zip(valid_positions) {
+            let new_id = id.remap(&face_remap, &mesh).unwrap();
+            assert!(new_id.is_valid(&mesh));
+            assert_eq!(new_id.remap(&face_remap, &mesh), Some(new_id));
+            assert_eq!(positions(&mesh, new_id.face()), expected);
+        }
+    }
+
+    fn boundary_edges_count(mesh: &CornerTableF) -> usize {
+        mesh.edges().filter(|edge| mesh.is_edge_on_boundary(edge)).count()
+    }
+
+    #[test]
+    fn remove_face() {
+        let mut mesh: CornerTableF = primitives::plane(4.0, 4.0, 4, 4);
+        let face = mesh.faces()
+            .find(|face| {
+                let (v1, v2, v3) = mesh.face_vertices(face);
+                [v1, v2, v3].iter().all(|v| !mesh.is_vertex_on_boundary(v))
+            })
+            .unwrap();
+        let (v1, v2, v3) = mesh.face_vertices(&face);
+
+        let loops = mesh.remove_face(face).unwrap();
+
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].len(), 3);
+        assert!([v1, v2, v3].iter().all(|v| loops[0].contains(v)));
+        assert_eq!(mesh.faces().count(), 31);
+        assert_eq!(boundary_edges_count(&mesh), 16 + 3);
+
+        // Already removed
+        assert!(mesh.remove_face(face).is_none());
+    }
+
+    #[test]
+    fn remove_face_that_introduces_non_manifold_vertex() {
+        let mut mesh: CornerTableF = primitives::plane(4.0, 4.0, 4, 4);
+        let face = mesh.faces()
+            .find(|face| {
+                let (e1, e2, e3) = mesh.face_edges(face);
+                let (v1, v2, v3) = mesh.face_vertices(face);
+                [e1, e2, e3].iter().all(|e| !mesh.is_edge_on_boundary(e)) &&
+                    [v1, v2, v3].iter().any(|v| mesh.is_vertex_on_boundary(v))
+            })
+            .unwrap();
+
+        assert!(mesh.remove_face(face).is_none());
+        assert_eq!(mesh.faces().count(), 32);
+    }
+
+    #[test]
+    fn remove_vertex_with_fan() {
+        let mut mesh: CornerTableF = primitives::plane(4.0, 4.0, 4, 4);
+        let vertex = mesh.vertices()
+            .find(|v| mesh.vertex_position(v).norm() < 1e-6)
+            .unwrap();
+        let mut neighbors = Vec::new();
+        mesh.vertices_around_vertex(&vertex, |v| neighbors.push(*v));
+        let faces_count = mesh.faces().count();
+
+        let loops = mesh.remove_vertex_with_fan(vertex).unwrap();
+
+        assert_eq!(loops.len(), 1);
+        assert_eq!(loops[0].len(), neighbors.len());
+        assert!(neighbors.iter().all(|v| loops[0].contains(v)));
+        assert!(mesh.vertices().all(|v| v != vertex));
+        assert_eq!(mesh.faces().count(), faces_count - neighbors.len());
+        assert_eq!(boundary_edges_count(&mesh), 16 + neighbors.len());
+
+        // Loop is ordered along boundary
+        for i in 0..loops[0].len() {
+            let v1 = loops[0][i];
+            let v2 = loops[0][(i + 1) % loops[0].len()];
+            let mut connected = false;
+            mesh.vertices_around_vertex(&v1, |v| connected |= *v == v2);
+            assert!(connected);
+        }
+    }
+
+    #[test]
+    fn remove_vertex_with_fan_isolates_vertex() {
+        let mut mesh = create_unit_square_mesh();
+        let loops = mesh.remove_vertex_with_fan(0).unwrap();
+
+        assert!(loops.is_empty());
+        assert_eq!(mesh.faces().count(), 0);
+        assert_eq!(mesh.vertices().count(), 0);
+    }
 }
@@ -413,6 +413,66 @@ impl<'a, TScalar: RealNumber> Iterator for CornerTableEdgesIter<'a, TScalar> {
     }
 }
 
+///
+/// Iterator over corners of vertex (one corner per incident face) in counterclockwise order.
+/// For boundary vertex iteration starts at boundary, so faces are returned as single fan.
+/// Number of steps is limited by number of corners in table, so iteration ends even on broken topology.
+///
+pub struct VertexCornersIter<'a, TScalar: RealNumber> {
+    table: &'a CornerTable<TScalar>,
+    start: usize,
+    current: Option<usize>,
+    steps_left: usize
+}
+
+impl<'a, TScalar: RealNumber> VertexCornersIter<'a, TScalar> {
+    pub fn new(table: &'a CornerTable<TScalar>, vertex_index: usize) -> Self {
+        let steps_left = table.corners.len();
+        let start = match table.get_vertex(vertex_index) {
+            Some(vertex) if !vertex.is_deleted() => vertex.get_corner_index(),
+            _ => return Self { table, start: 0, current: None, steps_left: 0 }
+        };
+
+        // Rewind clockwise to boundary
+        let mut corner = start;
+
+        for _ in 0..steps_left {
+            match table.corners[previous(corner)].get_opposite_corner_index() {
+                Some(opposite) if previous(opposite) != start => corner = previous(opposite),
+                _ => break,
+            }
+        }
+
+        Self {
+            table,
+            start: corner,
+            current: Some(corner),
+            steps_left
+        }
+    }
+}
+
+impl<'a, TScalar: RealNumber> Iterator for VertexCornersIter<'a, TScalar> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current?;
+
+        if self.steps_left == 0 {
+            self.current = None;
+            return None;
+        }
+
+        self.steps_left -= 1;
+        self.current = self.table.corners[next(current)]
+            .get_opposite_corner_index()
+            .map(next)
+            .filter(|corner| *corner != self.start);
+
+        Some(current)
+    }
+}
+
 /// Iterates over corners that are adjacent to given vertex
 pub fn corners_around_vertex<TScalar: RealNumber, TFunc: FnMut(&usize)>(corner_table: &CornerTable<TScalar>, vertex_index: usize, mut visit: TFunc) {
     let mut walker = CornerWalker::from_vertex(corner_table, vertex_index);
@@ -591,9 +651,10 @@ mod tests {
     use crate::mesh::{
         corner_table::{
             test_helpers::{create_unit_square_mesh, create_unit_cross_square_mesh}, 
-            traversal::{vertices_around_vertex, faces_around_vertex, corners_around_vertex}, descriptors::EdgeRef
+            traversal::{vertices_around_vertex, faces_around_vertex, corners_around_vertex}, descriptors::EdgeRef,
+            connectivity::corner::face
         }, 
-        traits::Mesh
+        traits::{Mesh, TopologicalMesh}
     };
     
     #[test]
@@ -684,4 +745,42 @@ mod tests {
         assert_eq!(faces, expected_faces);
     }
 
+    // Adjacency iterators
+
+    #[test]
+    fn one_ring_iterators() {
+        let mesh = create_unit_cross_square_mesh();
+
+        let mut ring: Vec<usize> = mesh.vertex_one_ring(4).collect();
+        ring.sort();
+        assert_eq!(ring, vec![0, 1, 2, 3]);
+        assert_eq!(mesh.vertex_edge_star(4).count(), 4);
+
+        // Boundary fan is returned from boundary to boundary
+        let ring: Vec<usize> = mesh.vertex_one_ring(0).collect();
+        assert_eq!(ring.len(), 3);
+        assert_eq!(ring[1], 4);
+
+        let star: Vec<EdgeRef> = mesh.vertex_edge_star(0).collect();
+        assert_eq!(star.len(), 3);
+        assert!(mesh.is_edge_on_boundary(&star[0]));
+        assert!(!mesh.is_edge_on_boundary(&star[1]));
+        assert!(mesh.is_edge_on_boundary(&star[2]));
+
+        let mut neighbors: Vec<usize> = mesh.face_neighbors(0).map(face).collect();
+        neighbors.sort();
+        assert_eq!(neighbors, vec![1, 3]);
+    }
+
+    #[test]
+    fn one_ring_iterators_stop_on_broken_topology() {
+        let mut mesh = create_unit_cross_square_mesh();
+
+        // Corner referencing itself as opposite creates cycle that never returns to start
+        mesh.corners[9].set_opposite_corner_index(Some(9));
+
+        assert!(mesh.vertex_one_ring(4).count() <= mesh.corners.len() + 1);
+        assert!(mesh.vertex_edge_star(4).count() <= mesh.corners.len() + 1);
+    }
+
 }
@@ -0,0 +1,300 @@
+use crate::geometry::traits::RealNumber;
+
+use super::{
+    connectivity::{
+        corner::{first_corner, next, previous},
+        traits::Flags,
+    },
+    table::CornerTable,
+};
+
+///
+/// Broken invariant of corner table found by [CornerTable::validate_topology]
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TopologyViolation {
+    /// Only some corners of face are deleted
+    PartiallyDeletedFace { face: usize },
+    /// Corner references vertex which doesn't exist or is deleted
+    InvalidVertex { corner: usize, vertex: usize },
+    /// Face references same vertex more than once
+    DegenerateFace { face: usize },
+    /// Opposite corner doesn't exist or is deleted
+    InvalidOpposite { corner: usize, opposite: usize },
+    /// Opposite corner doesn't point back to corner
+    AsymmetricOpposite { corner: usize, opposite: usize },
+    /// Opposite corners don't share edge (or share it with same orientation)
+    MismatchedOpposite { corner: usize, opposite: usize },
+    /// Vertex references corner which doesn't exist, is deleted or belongs to other vertex
+    InvalidVertexCorner { vertex: usize, corner: usize },
+    /// Vertex is not referenced by any face
+    IsolatedVertex { vertex: usize },
+    /// Faces around vertex form several fans, so some of them can't be reached by traversal
+    NonManifoldVertex { vertex: usize },
+}
+
+impl TopologyViolation {
+    ///
+    /// Returns `true` for violations fixed by [CornerTable::repair_topology]: broken opposite corners are unlinked (edge becomes boundary),
+    /// vertex is pointed to one of its corners, isolated vertices are deleted.
+    ///
+    #[inline]
+    pub fn is_trivial(&self) -> bool {
+        matches!(
+            self,
+            Self::InvalidOpposite { .. }
+                | Self::AsymmetricOpposite { .. }
+                | Self::MismatchedOpposite { .. }
+                | Self::InvalidVertexCorner { .. }
+                | Self::IsolatedVertex { .. }
+        )
+    }
+}
+
+///
+/// Result of [CornerTable::validate_topology] and [CornerTable::repair_topology]
+///
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TopologyReport {
+    /// Violations present in mesh
+    pub violations: Vec<TopologyViolation>,
+    /// Violations fixed by repair, always empty after validation
+    pub repaired: Vec<TopologyViolation>,
+}
+
+impl TopologyReport {
+    /// Returns `true` when all invariants hold
+    #[inline]
+    pub fn is_valid(&self) -> bool {
+        self.violations.is_empty()
+    }
+}
+
+impl<TScalar: RealNumber> CornerTable<TScalar> {
+    ///
+    /// Checks invariants of corner table and returns every violation with ids of involved corners, vertices and faces.
+    /// Useful for debugging of edits and of meshes built from broken input, e.g. non-manifold triangle soups.
+    /// Faces are given by index of their first corner, as face descriptors.
+    ///
+    /// ## Example
+    /// ```ignore
+    /// let report = mesh.validate_topology();
+    /// assert!(report.is_valid(), "{:?}", report.violations);
+    /// ```
+    ///
+    pub fn validate_topology(&self) -> TopologyReport {
+        let mut violations = Vec::new();
+        let mut vertex_corners = vec![Vec::new(); self.vertices.len()];
+
+        for face in (0..self.corners.len() / 3).map(first_corner) {
+            let deleted = (face..face + 3).filter(|corner| self.corners[*corner].is_deleted()).count();
+
+            if deleted == 3 {
+                continue;
+            }
+
+            if deleted > 0 {
+                violations.push(TopologyViolation::PartiallyDeletedFace { face });
+                continue;
+            }
+
+            let vertices = [face, face + 1, face + 2].map(|corner| self.corners[corner].get_vertex_index());
+
+            for (corner, vertex) in (face..face + 3).zip(vertices) {
+                match self.vertices.get(vertex) {
+                    Some(v) if !v.is_deleted() => vertex_corners[vertex].push(corner),
+                    _ => violations.push(TopologyViolation::InvalidVertex { corner, vertex }),
+                }
+            }
+
+            if vertices[0] == vertices[1] || vertices[1] == vertices[2] || vertices[2] == vertices[0] {
+                violations.push(TopologyViolation::DegenerateFace { face });
+            }
+        }
+
+        for (corner, c) in self.corners.iter().enumerate() {
+            if c.is_deleted() {
+                continue;
+            }
+
+            let Some(opposite) = c.get_opposite_corner_index() else {
+                continue;
+            };
+
+            if self.corners.get(opposite).is_none_or(|o| o.is_deleted()) {
+                violations.push(TopologyViolation::InvalidOpposite { corner, opposite });
+            } else if self.corners[opposite].get_opposite_corner_index() != Some(corner) {
+                violations.push(TopologyViolation::AsymmetricOpposite { corner, opposite });
+            } else if self.corners[next(corner)].get_vertex_index() != self.corners[previous(opposite)].get_vertex_index()
+                || self.corners[previous(corner)].get_vertex_index() != self.corners[next(opposite)].get_vertex_index()
+            {
+                violations.push(TopologyViolation::MismatchedOpposite { corner, opposite });
+            }
+        }
+
+        let links_valid = violations.is_empty();
+
+        for (vertex, v) in self.vertices.iter().enumerate() {
+            if v.is_deleted() {
+                continue;
+            }
+
+            if vertex_corners[vertex].is_empty() {
+                violations.push(TopologyViolation::IsolatedVertex { vertex });
+                continue;
+            }
+
+            let corner = v.get_corner_index();
+
+            if !vertex_corners[vertex].contains(&corner) {
+                violations.push(TopologyViolation::InvalidVertexCorner { vertex, corner });
+            } else if links_valid && self.fan_size(corner) != vertex_corners[vertex].len() {
+                // Fans can be walked only when opposite corners are consistent
+                violations.push(TopologyViolation::NonManifoldVertex { vertex });
+            }
+        }
+
+        TopologyReport {
+            violations,
+            repaired: Vec::new(),
+        }
+    }
+
+    ///
+    /// Validates topology and fixes trivial violations (see [TopologyViolation::is_trivial]).
+    /// Returned report contains fixed violations and violations left in mesh after repair.
+    /// Remaining violations require rebuilding of mesh, e.g. by [CornerTable::from_faces_split_non_manifold].
+    ///
+    pub fn repair_topology(&mut self) -> TopologyReport {
+        let mut repaired = Vec::new();
+
+        // Fixing opposite corners may reveal vertex violations, so repeat until nothing trivial is left
+        loop {
+            let (trivial, _): (Vec<_>, Vec<_>) = self.validate_topology().violations.into_iter().partition(|v| v.is_trivial());
+
+            if trivial.is_empty() {
+                break;
+            }
+
+            for violation in &trivial {
+                self.repair(violation);
+            }
+
+            repaired.extend(trivial);
+        }
+
+        TopologyReport {
+            violations: self.validate_topology().violations,
+            repaired,
+        }
+    }
+
+    fn repair(&mut self, violation: &TopologyViolation) {
+        match *violation {
+            TopologyViolation::InvalidOpposite { corner, .. } => {
+                self.corners[corner].set_opposite_corner_index(None);
+            }
+            TopologyViolation::AsymmetricOpposite { corner, opposite } | TopologyViolation::MismatchedOpposite { corner, opposite } => {
+                self.corners[corner].set_opposite_corner_index(None);
+
+                if let Some(o) = self.corners.get_mut(opposite).filter(|o| o.get_opposite_corner_index() == Some(corner)) {
+                    o.set_opposite_corner_index(None);
+                }
+            }
+            TopologyViolation::InvalidVertexCorner { vertex, .. } => {
+                let corner = self
+                    .corners
+                    .iter()
+                    .position(|c| !c.is_deleted() && c.get_vertex_index() == vertex)
+                    .unwrap();
+                self.vertices[vertex].set_corner_index(corner);
+            }
+            TopologyViolation::IsolatedVertex { vertex } => {
+                self.vertices[vertex].set_deleted(true);
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns number of corners reachable by walking around vertex of given corner in both directions
+    fn fan_size(&self, start: usize) -> usize {
+        let mut size = 1;
+        let mut corner = start;
+
+        // Counterclockwise until boundary or back to start
+        while let Some(opposite) = self.corners[next(corner)].get_opposite_corner_index() {
+            corner = next(opposite);
+
+            if corner == start {
+                return size;
+            }
+
+            size += 1;
+        }
+
+        corner = start;
+
+        while let Some(opposite) = self.corners[previous(corner)].get_opposite_corner_index() {
+            corner = previous(opposite);
+            size += 1;
+        }
+
+        size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::TopologyViolation;
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{
+            corner_table::{connectivity::traits::Flags, prelude::CornerTableF},
+            primitives,
+            traits::Mesh,
+        },
+    };
+
+    #[test]
+    fn test_validate_topology() {
+        let mut mesh: CornerTableF = primitives::icosphere(1.0, 1);
+        assert!(mesh.validate_topology().is_valid());
+
+        // Broken opposite and vertex references are repaired, unlinked edge becomes boundary
+        let corner = 0;
+        let opposite = mesh.corners[corner].get_opposite_corner_index().unwrap();
+        mesh.corners[opposite].set_opposite_corner_index(None);
+        let vertex = mesh.corners[0].get_vertex_index();
+        mesh.vertices[vertex].set_corner_index(usize::MAX);
+
+        let report = mesh.validate_topology();
+        assert!(report.violations.contains(&TopologyViolation::AsymmetricOpposite { corner, opposite }));
+        assert!(report.violations.contains(&TopologyViolation::InvalidVertexCorner { vertex, corner: usize::MAX }));
+
+        let report = mesh.repair_topology();
+        assert!(report.is_valid());
+        assert_eq!(report.repaired.len(), 2);
+        assert!(mesh.validate_topology().is_valid());
+
+        // Non-manifold vertex and deleted corner are reported, but not repaired
+        let vertices = [
+            Vec3f::new(0.0, 0.0, 0.0),
+            Vec3f::new(1.0, 0.0, 0.0),
+            Vec3f::new(0.0, 1.0, 0.0),
+            Vec3f::new(-1.0, 0.0, 0.0),
+            Vec3f::new(0.0, -1.0, 0.0),
+            Vec3f::new(5.0, 5.0, 5.0),
+        ];
+        let mut bowtie = CornerTableF::from_vertices_and_indices(&vertices, &[0, 1, 2, 0, 3, 4]);
+
+        let report = bowtie.repair_topology();
+        assert_eq!(report.repaired, vec![TopologyViolation::IsolatedVertex { vertex: 5 }]);
+        assert_eq!(report.violations, vec![TopologyViolation::NonManifoldVertex { vertex: 0 }]);
+        assert_eq!(bowtie.vertices().count(), 5);
+
+        bowtie.corners[4].set_deleted(true);
+        assert!(bowtie.validate_topology().violations.contains(&TopologyViolation::PartiallyDeletedFace { face: 3 }));
+    }
+}
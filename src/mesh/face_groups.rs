@@ -0,0 +1,173 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "io")]
+use crate::{data_structures::vertex_index_map::HashablePoint, geometry::traits::RealNumber, helpers::aliases::Vec3};
+
+use super::traits::{Mesh, TopologicalMesh};
+
+///
+/// Face groups (polygroups) of mesh, e.g. material regions read from OBJ file.
+/// Each face may be assigned to one named group.
+///
+/// Borders between groups can be kept during remeshing and decimation by passing
+/// [boundary edges](FaceGroups::boundary_edges) as constrained edges.
+///
+/// ## Example
+/// ```ignore
+/// let (mut mesh, groups) = ObjReader::new().read_obj_with_groups_from_file::<CornerTableF>(path)?;
+/// let borders = groups.boundary_edges(&mesh);
+///
+/// let mut decimator = EdgeDecimator::new().constrained_edges(&mesh, borders.iter().copied());
+/// decimator.decimate(&mut mesh);
+/// ```
+///
+pub struct FaceGroups<TMesh: Mesh> {
+    face_groups: HashMap<TMesh::FaceDescriptor, usize>,
+    names: Vec<String>,
+}
+
+impl<TMesh: Mesh> FaceGroups<TMesh> {
+    pub fn new() -> Self {
+        Self {
+            face_groups: HashMap::new(),
+            names: Vec::new(),
+        }
+    }
+
+    ///
+    /// Assigns groups to faces of `mesh` by matching their positions with given triangles.
+    /// Used when mesh is built from indexed triangles and some of them may be skipped or reordered.
+    ///
+    #[cfg(feature = "io")]
+    pub(crate) fn from_triangles<TIter>(mesh: &TMesh, triangles: TIter, names: Vec<String>) -> Self
+    where
+        TIter: IntoIterator<Item = ([Vec3<TMesh::ScalarType>; 3], usize)>,
+    {
+        let triangle_groups: HashMap<_, _> = triangles
+            .into_iter()
+            .map(|(triangle, group)| (face_key(triangle), group))
+            .collect();
+
+        let mut groups = Self { face_groups: HashMap::new(), names };
+
+        for face in mesh.faces() {
+            let triangle = mesh.face_positions(&face);
+            let key = face_key([*triangle.p1(), *triangle.p2(), *triangle.p3()]);
+
+            if let Some(group) = triangle_groups.get(&key) {
+                groups.face_groups.insert(face, *group);
+            }
+        }
+
+        groups
+    }
+
+    /// Adds new group and returns its id
+    pub fn add_group(&mut self, name: String) -> usize {
+        self.names.push(name);
+        self.names.len() - 1
+    }
+
+    #[inline]
+    pub fn set_face_group(&mut self, face: TMesh::FaceDescriptor, group: usize) {
+        debug_assert!(group < self.names.len(), "Group {} does not exist", group);
+        self.face_groups.insert(face, group);
+    }
+
+    #[inline]
+    pub fn face_group(&self, face: &TMesh::FaceDescriptor) -> Option<usize> {
+        self.face_groups.get(face).copied()
+    }
+
+    #[inline]
+    pub fn group_name(&self, group: usize) -> Option<&str> {
+        self.names.get(group).map(String::as_str)
+    }
+
+    #[inline]
+    pub fn groups_count(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Returns faces of given group
+    pub fn group_faces(&self, group: usize) -> impl Iterator<Item = TMesh::FaceDescriptor> + '_ {
+        self.face_groups
+            .iter()
+            .filter(move |(_, g)| **g == group)
+            .map(|(face, _)| *face)
+    }
+
+    /// Returns edges (as pairs of vertices) shared by faces of different groups
+    pub fn boundary_edges(&self, mesh: &TMesh) -> Vec<(TMesh::VertexDescriptor, TMesh::VertexDescriptor)>
+    where
+        TMesh: TopologicalMesh,
+    {
+        mesh.edges()
+            .filter(|edge| match mesh.edge_faces(edge) {
+                (face1, Some(face2)) => self.face_group(&face1) != self.face_group(&face2),
+                (_, None) => false,
+            })
+            .map(|edge| mesh.edge_vertices(&edge))
+            .collect()
+    }
+}
+
+impl<TMesh: Mesh> Default for FaceGroups<TMesh> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Key of triangle independent of its starting vertex, rotated to start at lexicographically smallest point
+#[cfg(feature = "io")]
+pub(crate) fn face_key<TScalar: RealNumber>(triangle: [Vec3<TScalar>; 3]) -> [HashablePoint<3, TScalar>; 3] {
+    let lexicographic = |p: &Vec3<TScalar>| (p.x, p.y, p.z);
+    let start = (1..3).fold(0, |min, i| {
+        if lexicographic(&triangle[i]) < lexicographic(&triangle[min]) { i } else { min }
+    });
+
+    [0, 1, 2].map(|i| triangle[(start + i) % 3].into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FaceGroups;
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+        remeshing::incremental::IncrementalRemesher,
+    };
+
+    #[test]
+    fn test_group_boundary_is_kept_on_remeshing() {
+        let mut mesh: CornerTableF = primitives::plane(2.0, 2.0, 8, 8);
+        let mut groups = FaceGroups::new();
+        let left = groups.add_group("left".to_string());
+        let right = groups.add_group("right".to_string());
+
+        for face in mesh.faces() {
+            let center = mesh.face_positions(&face).center();
+            groups.set_face_group(face, if center.x < 0.0 { left } else { right });
+        }
+
+        // Border is a line x = 0 crossing the plane
+        let borders = groups.boundary_edges(&mesh);
+        assert_eq!(borders.len(), 8);
+
+        let border_positions: Vec<Vec3f> = borders
+            .iter()
+            .flat_map(|(v1, v2)| [*mesh.vertex_position(v1), *mesh.vertex_position(v2)])
+            .collect();
+        assert!(border_positions.iter().all(|p| p.x.abs() < 1e-6));
+
+        let remesher = IncrementalRemesher::new()
+            .with_iterations_count(3)
+            .with_constrained_edges(borders.iter().copied());
+        remesher.remesh(&mut mesh, 0.1);
+
+        // Border vertices are not moved
+        let positions: Vec<_> = mesh.vertices().map(|v| *mesh.vertex_position(&v)).collect();
+        assert!(border_positions.iter().all(|p| positions.contains(p)));
+    }
+}
@@ -0,0 +1,293 @@
+use std::collections::{HashMap, HashSet};
+
+use num_traits::Float;
+
+use crate::{
+    data_structures::vertex_index_map::HashablePoint,
+    geometry::traits::RealNumber,
+    helpers::aliases::{Vec2, Vec3},
+    spatial_partitioning::aabb_tree::{AABBTree, MedianCut},
+};
+
+use super::traits::{Mesh, TopologicalMesh};
+
+///
+/// Texture coordinates of mesh, e.g. read from OBJ file. UVs are stored per face corner,
+/// so vertex on UV seam has different coordinates in faces of different charts.
+///
+/// Decimation does not carry UVs, so seams should be kept by passing [seam edges](FaceUvs::seam_edges)
+/// as constrained edges, then UVs of simplified mesh are restored by [FaceUvs::transfer].
+/// Texture stretching is limited by measuring decimation error in combined 3D+UV space, see [QuadricError::with_uvs](crate::decimation::edge_decimation::QuadricError::with_uvs).
+///
+/// ## Example
+/// ```ignore
+/// let (mut mesh, uvs) = ObjReader::new().read_obj_with_uvs_from_file::<CornerTableF>(path)?;
+/// let original: CornerTableF = convert_mesh(&mesh);
+/// let seams = uvs.seam_edges(&mesh);
+///
+/// let mut decimator = EdgeDecimator::new()
+///     .constrained_edges(&mesh, seams.iter().copied())
+///     .collapse_strategy(QuadricError::new().with_uvs(&mesh, &uvs, 1.0));
+/// decimator.decimate(&mut mesh);
+/// let uvs = uvs.transfer(&original, &mesh);
+/// ```
+///
+pub struct FaceUvs<TMesh: Mesh> {
+    face_uvs: HashMap<TMesh::FaceDescriptor, [Vec2<TMesh::ScalarType>; 3]>,
+}
+
+impl<TMesh: Mesh> FaceUvs<TMesh> {
+    pub fn new() -> Self {
+        Self { face_uvs: HashMap::new() }
+    }
+
+    ///
+    /// Assigns UVs to faces of `mesh` by matching their positions with given triangles.
+    /// Used when mesh is built from indexed triangles and some of them may be skipped or reordered.
+    ///
+    pub(crate) fn from_triangles<TIter>(mesh: &TMesh, triangles: TIter) -> Self
+    where
+        TIter: IntoIterator<Item = ([Vec3<TMesh::ScalarType>; 3], [Vec2<TMesh::ScalarType>; 3])>,
+    {
+        // UVs are rotated the same way as key of triangle
+        let triangle_uvs: HashMap<_, _> = triangles
+            .into_iter()
+            .map(|(triangle, uvs)| {
+                let start = lexicographic_start(&triangle);
+                (face_key(&triangle, start), [0, 1, 2].map(|i| uvs[(start + i) % 3]))
+            })
+            .collect();
+
+        let mut face_uvs = HashMap::new();
+
+        for face in mesh.faces() {
+            let triangle = mesh.face_positions(&face);
+            let triangle = [*triangle.p1(), *triangle.p2(), *triangle.p3()];
+            let start = lexicographic_start(&triangle);
+
+            if let Some(uvs) = triangle_uvs.get(&face_key(&triangle, start)) {
+                face_uvs.insert(face, [0, 1, 2].map(|i| uvs[(i + 3 - start) % 3]));
+            }
+        }
+
+        Self { face_uvs }
+    }
+
+    /// Set UVs of face corners, in order of [Mesh::face_vertices]
+    #[inline]
+    pub fn set_face_uvs(&mut self, face: TMesh::FaceDescriptor, uvs: [Vec2<TMesh::ScalarType>; 3]) {
+        self.face_uvs.insert(face, uvs);
+    }
+
+    /// Returns UVs of face corners, in order of [Mesh::face_vertices]
+    #[inline]
+    pub fn face_uvs(&self, face: &TMesh::FaceDescriptor) -> Option<&[Vec2<TMesh::ScalarType>; 3]> {
+        self.face_uvs.get(face)
+    }
+
+    /// Returns UV of `vertex` in `face`
+    pub fn vertex_uv(&self, mesh: &TMesh, face: &TMesh::FaceDescriptor, vertex: &TMesh::VertexDescriptor) -> Option<Vec2<TMesh::ScalarType>> {
+        let uvs = self.face_uvs.get(face)?;
+        let (v1, v2, v3) = mesh.face_vertices(face);
+
+        [v1, v2, v3].iter().position(|v| v == vertex).map(|corner| uvs[corner])
+    }
+
+    ///
+    /// Returns edges (as pairs of vertices) where UVs are discontinuous: faces of edge have different UVs
+    /// at any of its vertices or only one of them has UVs. Boundary edges are not seams.
+    ///
+    pub fn seam_edges(&self, mesh: &TMesh) -> Vec<(TMesh::VertexDescriptor, TMesh::VertexDescriptor)>
+    where
+        TMesh: TopologicalMesh,
+    {
+        mesh.edges()
+            .filter(|edge| self.is_seam(mesh, edge))
+            .map(|edge| mesh.edge_vertices(&edge))
+            .collect()
+    }
+
+    ///
+    /// Computes UVs of `target` mesh approximating surface of `original` one, e.g. decimated or remeshed copy of it.
+    /// Faces of `original` are split into charts along UV seams. Chart of every target face is the one closest
+    /// to face center, then corners of face are projected onto that chart and UVs are interpolated there.
+    /// Faces of `original` without UVs are ignored. Seams of `target` should follow seams of `original`
+    /// (e.g. be constrained during decimation), otherwise faces crossing seams get stretched UVs.
+    ///
+    pub fn transfer(&self, original: &TMesh, target: &TMesh) -> Self
+    where
+        TMesh: TopologicalMesh,
+    {
+        let charts = self.charts(original);
+
+        let trees: Vec<_> = charts
+            .iter()
+            .map(|chart| {
+                let triangles = chart.iter().map(|face| original.face_positions(face)).collect();
+                AABBTree::new(triangles).top_down::<MedianCut>()
+            })
+            .collect();
+
+        // Faces of all charts together, used to find chart of target face
+        let all_faces: Vec<_> = charts
+            .iter()
+            .enumerate()
+            .flat_map(|(chart, faces)| faces.iter().map(move |face| (chart, *face)))
+            .collect();
+        let all_tree = AABBTree::new(all_faces.iter().map(|(_, face)| original.face_positions(face)).collect()).top_down::<MedianCut>();
+
+        let far = Float::max_value();
+        let mut face_uvs = HashMap::new();
+
+        for face in target.faces() {
+            let triangle = target.face_positions(&face);
+
+            let Some((closest, _)) = all_tree.closest_object(&triangle.center(), far) else {
+                continue;
+            };
+            let chart = all_faces[closest].0;
+
+            let uvs = [*triangle.p1(), *triangle.p2(), *triangle.p3()].map(|corner| {
+                let (index, point) = trees[chart].closest_object(&corner, far).unwrap();
+                let original_face = &charts[chart][index];
+                let barycentric = original.face_positions(original_face).barycentric(&point);
+                let uvs = &self.face_uvs[original_face];
+
+                uvs[0] * barycentric.u() + uvs[1] * barycentric.v() + uvs[2] * barycentric.w()
+            });
+
+            face_uvs.insert(face, uvs);
+        }
+
+        Self { face_uvs }
+    }
+
+    /// Groups faces with UVs into connected components not crossing seams
+    fn charts(&self, mesh: &TMesh) -> Vec<Vec<TMesh::FaceDescriptor>>
+    where
+        TMesh: TopologicalMesh,
+    {
+        let mut visited = HashSet::new();
+        let mut charts = Vec::new();
+
+        for start in mesh.faces() {
+            if !self.face_uvs.contains_key(&start) || !visited.insert(start) {
+                continue;
+            }
+
+            let mut chart = Vec::new();
+            let mut stack = vec![start];
+
+            while let Some(face) = stack.pop() {
+                chart.push(face);
+
+                let (e1, e2, e3) = mesh.face_edges(&face);
+
+                for edge in [e1, e2, e3] {
+                    if let (f1, Some(f2)) = mesh.edge_faces(&edge) {
+                        let neighbor = if f1 == face { f2 } else { f1 };
+
+                        if !self.is_seam(mesh, &edge) && visited.insert(neighbor) {
+                            stack.push(neighbor);
+                        }
+                    }
+                }
+            }
+
+            charts.push(chart);
+        }
+
+        charts
+    }
+
+    fn is_seam(&self, mesh: &TMesh, edge: &TMesh::EdgeDescriptor) -> bool
+    where
+        TMesh: TopologicalMesh,
+    {
+        let (face1, Some(face2)) = mesh.edge_faces(edge) else {
+            return false;
+        };
+        let (v1, v2) = mesh.edge_vertices(edge);
+
+        [v1, v2]
+            .iter()
+            .any(|vertex| self.vertex_uv(mesh, &face1, vertex) != self.vertex_uv(mesh, &face2, vertex))
+    }
+}
+
+impl<TMesh: Mesh> Default for FaceUvs<TMesh> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Returns index of lexicographically smallest point of triangle
+fn lexicographic_start<TScalar: RealNumber>(triangle: &[Vec3<TScalar>; 3]) -> usize {
+    let lexicographic = |p: &Vec3<TScalar>| (p.x, p.y, p.z);
+
+    (1..3).fold(0, |min, i| {
+        if lexicographic(&triangle[i]) < lexicographic(&triangle[min]) { i } else { min }
+    })
+}
+
+/// Key of triangle independent of its starting vertex, rotated to start at `start`
+fn face_key<TScalar: RealNumber>(triangle: &[Vec3<TScalar>; 3], start: usize) -> [HashablePoint<3, TScalar>; 3] {
+    [0, 1, 2].map(|i| triangle[(start + i) % 3].into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FaceUvs;
+    use crate::{
+        decimation::{edge_decimation::ConstantErrorDecimationCriteria, prelude::EdgeDecimator},
+        helpers::aliases::{Vec2, Vec3f},
+        mesh::{convert::convert_mesh, corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+    };
+
+    /// Left half of plane is mapped to left half of texture, right half is mirrored into right one
+    fn chart_uv(face_center: &Vec3f, point: &Vec3f) -> Vec2<f32> {
+        if face_center.x < 0.0 {
+            Vec2::new(point.x + 1.0, point.y + 1.0) * 0.25
+        } else {
+            Vec2::new(3.0 - point.x, point.y + 1.0) * 0.25
+        }
+    }
+
+    #[test]
+    fn test_seams_are_kept_on_decimation() {
+        let original: CornerTableF = primitives::plane(2.0, 2.0, 16, 16);
+        let mut uvs = FaceUvs::new();
+
+        for face in original.faces() {
+            let center = original.face_positions(&face).center();
+            let (v1, v2, v3) = original.face_vertices(&face);
+            uvs.set_face_uvs(face, [v1, v2, v3].map(|v| chart_uv(&center, original.vertex_position(&v))));
+        }
+
+        // Seam is a line x = 0 crossing the plane
+        let seams = uvs.seam_edges(&original);
+        assert_eq!(seams.len(), 16);
+
+        let mut mesh: CornerTableF = convert_mesh(&original);
+        // Flat plane has zero collapse cost everywhere, keep its outline so seam stays inside of mesh
+        let mut decimator = EdgeDecimator::new()
+            .decimation_criteria(ConstantErrorDecimationCriteria::new(0.01))
+            .keep_boundary(true)
+            .constrained_edges(&mesh, seams.iter().copied());
+        decimator.decimate(&mut mesh);
+        assert!(mesh.faces().count() < original.faces().count());
+
+        let transferred = uvs.transfer(&original, &mesh);
+        assert_eq!(transferred.seam_edges(&mesh).len(), 16);
+
+        for face in mesh.faces() {
+            let center = mesh.face_positions(&face).center();
+            let (v1, v2, v3) = mesh.face_vertices(&face);
+
+            for (vertex, uv) in [v1, v2, v3].iter().zip(transferred.face_uvs(&face).unwrap()) {
+                assert!((uv - chart_uv(&center, mesh.vertex_position(vertex))).norm() < 1e-4);
+            }
+        }
+    }
+}
@@ -0,0 +1,172 @@
+use std::cell::UnsafeCell;
+
+use crate::{
+    geometry::traits::RealNumber,
+    helpers::aliases::Vec3,
+    mesh::corner_table::connectivity::{flags, traits::Flags},
+};
+
+///
+/// Directed edge of face. Half-edges of face form cycle linked by `next`,
+/// half-edges of neighbor faces sharing same edge are twins.
+///
+#[derive(Debug)]
+pub struct HalfEdge {
+    origin_index: usize,
+    next_index: usize,
+    twin_index: Option<usize>,
+    face_index: usize,
+    flags: UnsafeCell<flags::Flags>,
+}
+
+impl HalfEdge {
+    pub fn new(origin_index: usize, next_index: usize, twin_index: Option<usize>, face_index: usize) -> Self {
+        Self {
+            origin_index,
+            next_index,
+            twin_index,
+            face_index,
+            flags: Default::default(),
+        }
+    }
+
+    /// Returns index of vertex half-edge starts at
+    #[inline]
+    pub fn get_origin_index(&self) -> usize {
+        self.origin_index
+    }
+
+    #[inline]
+    pub fn set_origin_index(&mut self, index: usize) -> &mut Self {
+        self.origin_index = index;
+        self
+    }
+
+    /// Returns index of next half-edge of same face
+    #[inline]
+    pub fn get_next_index(&self) -> usize {
+        self.next_index
+    }
+
+    #[inline]
+    pub fn set_next_index(&mut self, index: usize) -> &mut Self {
+        self.next_index = index;
+        self
+    }
+
+    /// Returns index of oppositely directed half-edge of neighbor face, `None` on boundary
+    #[inline]
+    pub fn get_twin_index(&self) -> Option<usize> {
+        self.twin_index
+    }
+
+    #[inline]
+    pub fn set_twin_index(&mut self, index: Option<usize>) -> &mut Self {
+        self.twin_index = index;
+        self
+    }
+
+    #[inline]
+    pub fn get_face_index(&self) -> usize {
+        self.face_index
+    }
+
+    #[inline]
+    pub fn set_face_index(&mut self, index: usize) -> &mut Self {
+        self.face_index = index;
+        self
+    }
+}
+
+impl Flags for HalfEdge {
+    #[inline]
+    fn get_flags(&self) -> &UnsafeCell<flags::Flags> {
+        &self.flags
+    }
+}
+
+///
+/// Vertex of half-edge mesh, references one of outgoing half-edges
+///
+#[derive(Debug)]
+pub struct Vertex<TScalar: RealNumber> {
+    half_edge_index: usize,
+    position: Vec3<TScalar>,
+    flags: UnsafeCell<flags::Flags>,
+}
+
+impl<TScalar: RealNumber> Vertex<TScalar> {
+    pub fn new(half_edge_index: usize, position: Vec3<TScalar>) -> Self {
+        Self {
+            half_edge_index,
+            position,
+            flags: Default::default(),
+        }
+    }
+
+    #[inline]
+    pub fn get_position(&self) -> &Vec3<TScalar> {
+        &self.position
+    }
+
+    #[inline]
+    pub fn set_position(&mut self, point: Vec3<TScalar>) -> &mut Self {
+        self.position = point;
+        self
+    }
+
+    /// Returns index of one of half-edges starting at vertex
+    #[inline]
+    pub fn get_half_edge_index(&self) -> usize {
+        self.half_edge_index
+    }
+
+    #[inline]
+    pub fn set_half_edge_index(&mut self, index: usize) -> &mut Self {
+        self.half_edge_index = index;
+        self
+    }
+}
+
+impl<TScalar: RealNumber> Flags for Vertex<TScalar> {
+    #[inline]
+    fn get_flags(&self) -> &UnsafeCell<flags::Flags> {
+        &self.flags
+    }
+}
+
+///
+/// Face of half-edge mesh, references one of its half-edges
+///
+#[derive(Debug)]
+pub struct Face {
+    half_edge_index: usize,
+    flags: UnsafeCell<flags::Flags>,
+}
+
+impl Face {
+    pub fn new(half_edge_index: usize) -> Self {
+        Self {
+            half_edge_index,
+            flags: Default::default(),
+        }
+    }
+
+    #[inline]
+    pub fn get_half_edge_index(&self) -> usize {
+        self.half_edge_index
+    }
+
+    #[inline]
+    pub fn set_half_edge_index(&mut self, index: usize) -> &mut Self {
+        self.half_edge_index = index;
+        self
+    }
+}
+
+impl Flags for Face {
+    #[inline]
+    fn get_flags(&self) -> &UnsafeCell<flags::Flags> {
+        &self.flags
+    }
+}
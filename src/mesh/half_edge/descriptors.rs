@@ -0,0 +1,41 @@
+use std::fmt::{Debug, Display};
+
+use crate::geometry::traits::RealNumber;
+
+use super::mesh::HalfEdgeMesh;
+
+///
+/// Edge descriptor for half-edge mesh.
+/// Edge is saved as one of its half-edges that has smaller index.
+///
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EdgeRef {
+    half_edge_index: usize,
+}
+
+impl EdgeRef {
+    pub fn new<TScalar: RealNumber>(half_edge_index: usize, mesh: &HalfEdgeMesh<TScalar>) -> Self {
+        let twin = mesh.half_edges[half_edge_index].get_twin_index();
+        Self {
+            half_edge_index: half_edge_index.min(twin.unwrap_or(usize::MAX)),
+        }
+    }
+
+    /// Returns half-edge index of `this` edge reference
+    pub fn get_half_edge_index(&self) -> usize {
+        self.half_edge_index
+    }
+}
+
+impl Display for EdgeRef {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.half_edge_index)
+    }
+}
+
+impl Debug for EdgeRef {
+    #[inline]
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "half_edge_index: {}", &self.half_edge_index)
+    }
+}
@@ -0,0 +1,394 @@
+use crate::{
+    geometry::traits::RealNumber,
+    helpers::aliases::Vec3,
+    mesh::{
+        corner_table::{connectivity::traits::Flags, property_maps::VertexPropertyMap},
+        traits::{EditableMesh, SplitFaceAtPoint, VertexProperties},
+    },
+};
+
+use super::mesh::HalfEdgeMesh;
+
+impl<TScalar: RealNumber> HalfEdgeMesh<TScalar> {
+    /// Set outgoing half-edge of wing vertex of collapsed edge, `incoming` and `outgoing` are half-edges that survive collapse
+    #[inline]
+    fn set_half_edge_for_wing_vertex(&mut self, vertex_index: usize, incoming: Option<usize>, outgoing: Option<usize>) {
+        if let Some(half_edge) = outgoing {
+            self.vertices[vertex_index].set_half_edge_index(half_edge);
+        } else if let Some(half_edge) = incoming {
+            let next = self.next(half_edge);
+            self.vertices[vertex_index].set_half_edge_index(next);
+        } else {
+            self.vertices[vertex_index].set_deleted(true);
+        }
+    }
+
+    /// Marks face and its half-edges as deleted
+    #[inline]
+    fn delete_face(&mut self, half_edge_index: usize) {
+        let face_index = self.half_edges[half_edge_index].get_face_index();
+        self.faces[face_index].set_deleted(true);
+
+        let mut half_edge = half_edge_index;
+        for _ in 0..3 {
+            self.half_edges[half_edge].set_deleted(true);
+            half_edge = self.next(half_edge);
+        }
+    }
+}
+
+impl<TScalar: RealNumber> EditableMesh for HalfEdgeMesh<TScalar> {
+    fn collapse_edge(&mut self, edge: &Self::EdgeDescriptor, at: &Vec3<Self::ScalarType>) {
+        // Edge goes from `v_keep` to `v_remove`
+        let h_edge = edge.get_half_edge_index();
+        let v_keep = self.origin(h_edge);
+        let v_remove = self.target(h_edge);
+        let removed_outgoing: Vec<_> = self.outgoing_half_edges(v_remove).collect();
+
+        // Left face (v_keep, v_remove, v_left), its outer half-edges are glued together after collapse
+        let h_left_next = self.next(h_edge);
+        let h_left_prev = self.next(h_left_next);
+        let v_left = self.origin(h_left_prev);
+        let left_wing_out = self.twin(h_left_next);
+        let left_keep_out = self.twin(h_left_prev);
+
+        // Right face (v_remove, v_keep, v_right)
+        let mut right_wing_out = None;
+        let mut right_keep_out = None;
+
+        if let Some(h_twin) = self.twin(h_edge) {
+            let h_right_next = self.next(h_twin);
+            let h_right_prev = self.next(h_right_next);
+            let v_right = self.origin(h_right_prev);
+            right_wing_out = self.twin(h_right_next);
+            right_keep_out = self.twin(h_right_prev);
+
+            // Make sure vertices are not referencing deleted half-edges
+            self.set_half_edge_for_wing_vertex(v_right, right_keep_out, right_wing_out);
+            self.delete_face(h_twin);
+        }
+
+        self.set_half_edge_for_wing_vertex(v_left, left_keep_out, left_wing_out);
+        self.delete_face(h_edge);
+
+        // Half-edges starting at removed vertex now start at kept one
+        for half_edge in removed_outgoing {
+            self.half_edges[half_edge].set_origin_index(v_keep);
+        }
+
+        self.vertices[v_remove].set_deleted(true);
+
+        let keep_out = left_keep_out
+            .or(right_keep_out)
+            .or_else(|| left_wing_out.map(|half_edge| self.next(half_edge)))
+            .or_else(|| right_wing_out.map(|half_edge| self.next(half_edge)));
+
+        match keep_out {
+            Some(half_edge) => { self.vertices[v_keep].set_half_edge_index(half_edge); }
+            None => { self.vertices[v_keep].set_deleted(true); }
+        }
+
+        self.vertices[v_keep].set_position(*at);
+
+        // Glue outer edges of removed faces
+        self.make_twins(left_wing_out, left_keep_out);
+        self.make_twins(right_wing_out, right_keep_out);
+    }
+
+    fn flip_edge(&mut self, edge: &Self::EdgeDescriptor) {
+        // Edge goes from v1 to v2, it is replaced by edge from v3 to v4
+        let h_edge = edge.get_half_edge_index();
+        let h_twin = self.twin(h_edge).expect("Boundary edge can't be flipped");
+
+        let f1 = self.half_edges[h_edge].get_face_index();
+        let f2 = self.half_edges[h_twin].get_face_index();
+
+        let h23 = self.next(h_edge);
+        let h31 = self.next(h23);
+        let h14 = self.next(h_twin);
+        let h42 = self.next(h14);
+
+        let v1 = self.origin(h_edge);
+        let v2 = self.origin(h_twin);
+        let v3 = self.origin(h31);
+        let v4 = self.origin(h42);
+
+        // Faces (v3, v4, v2) and (v4, v3, v1)
+        self.half_edges[h_edge].set_origin_index(v3);
+        self.half_edges[h_twin].set_origin_index(v4);
+        self.link_face(f1, h_edge, h42, h23);
+        self.link_face(f2, h_twin, h31, h14);
+
+        // Make sure vertices are referencing correct half-edges
+        self.vertices[v1].set_half_edge_index(h14);
+        self.vertices[v2].set_half_edge_index(h23);
+        self.vertices[v3].set_half_edge_index(h31);
+        self.vertices[v4].set_half_edge_index(h42);
+    }
+
+    fn split_edge(&mut self, edge: &Self::EdgeDescriptor, at: &Vec3<Self::ScalarType>) {
+        // Edge goes from v1 to v2, new vertex is inserted between them
+        let h_edge = edge.get_half_edge_index();
+        let v2 = self.target(h_edge);
+        let v_new = self.create_vertex(*at);
+
+        // Left face (v1, v2, v3) is split into (v1, v_new, v3) and (v_new, v2, v3)
+        let f1 = self.half_edges[h_edge].get_face_index();
+        let h23 = self.next(h_edge);
+        let h31 = self.next(h23);
+        let v3 = self.origin(h31);
+
+        let h_new3 = self.create_half_edge(v_new);
+        let h_3new = self.create_half_edge(v3);
+        let h_new2 = self.create_half_edge(v_new);
+
+        self.link_face(f1, h_edge, h_new3, h31);
+        self.create_face_from_half_edges(h_new2, h23, h_3new);
+        self.make_twins(Some(h_new3), Some(h_3new));
+
+        self.vertices[v_new].set_half_edge_index(h_new2);
+        self.vertices[v2].set_half_edge_index(h23);
+
+        // Right face (v2, v1, v4) is split into (v_new, v1, v4) and (v2, v_new, v4)
+        if let Some(h_twin) = self.twin(h_edge) {
+            let f2 = self.half_edges[h_twin].get_face_index();
+            let h14 = self.next(h_twin);
+            let h42 = self.next(h14);
+            let v4 = self.origin(h42);
+
+            let h_4new = self.create_half_edge(v4);
+            let h_2new = self.create_half_edge(v2);
+            let h_new4 = self.create_half_edge(v_new);
+
+            self.half_edges[h_twin].set_origin_index(v_new);
+            self.link_face(f2, h_twin, h14, h_4new);
+            self.create_face_from_half_edges(h_2new, h_new4, h42);
+            self.make_twins(Some(h_new4), Some(h_4new));
+            self.make_twins(Some(h_new2), Some(h_2new));
+        }
+    }
+
+    #[inline]
+    fn shift_vertex(&mut self, vertex: &Self::VertexDescriptor, to: &Vec3<Self::ScalarType>) {
+        self.vertices[*vertex].set_position(*to);
+    }
+
+    #[inline]
+    fn edge_exist(&self, edge: &Self::EdgeDescriptor) -> bool {
+        !self.half_edges[edge.get_half_edge_index()].is_deleted()
+    }
+}
+
+impl<TScalar: RealNumber> SplitFaceAtPoint for HalfEdgeMesh<TScalar> {
+    fn split_face(&mut self, face: &Self::FaceDescriptor, point: Vec3<Self::ScalarType>) {
+        // Face (v0, v1, v2) is split into (v0, v1, new), (v1, v2, new) and (v2, v0, new)
+        let h01 = self.faces[*face].get_half_edge_index();
+        let h12 = self.next(h01);
+        let h20 = self.next(h12);
+        let v0 = self.origin(h01);
+        let v1 = self.origin(h12);
+        let v2 = self.origin(h20);
+        let v_new = self.create_vertex(point);
+
+        let h1new = self.create_half_edge(v1);
+        let h_new0 = self.create_half_edge(v_new);
+        let h2new = self.create_half_edge(v2);
+        let h_new1 = self.create_half_edge(v_new);
+        let h0new = self.create_half_edge(v0);
+        let h_new2 = self.create_half_edge(v_new);
+
+        self.link_face(*face, h01, h1new, h_new0);
+        self.create_face_from_half_edges(h12, h2new, h_new1);
+        self.create_face_from_half_edges(h20, h0new, h_new2);
+
+        self.make_twins(Some(h1new), Some(h_new1));
+        self.make_twins(Some(h2new), Some(h_new2));
+        self.make_twins(Some(h0new), Some(h_new0));
+
+        self.vertices[v_new].set_half_edge_index(h_new0);
+    }
+}
+
+/// Implementation of vertex property maps for half-edge mesh
+impl<TScalar: RealNumber> VertexProperties for HalfEdgeMesh<TScalar> {
+    type VertexPropertyMap<TProperty: Default> = VertexPropertyMap<TProperty>;
+
+    #[inline]
+    fn create_vertex_properties_map<TProperty: Default>(&self) -> Self::VertexPropertyMap<TProperty> {
+        VertexPropertyMap::new(self.vertices.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        decimation::{edge_decimation::AlwaysDecimate, prelude::EdgeDecimator},
+        helpers::aliases::Vec3f,
+        mesh::{
+            corner_table::connectivity::traits::Flags,
+            half_edge::{descriptors::EdgeRef, prelude::HalfEdgeMeshF},
+            primitives,
+            traits::{EditableMesh, Mesh, SplitFaceAtPoint, TopologicalMesh},
+        },
+        remeshing::incremental::IncrementalRemesher,
+    };
+
+    fn assert_connectivity_valid(mesh: &HalfEdgeMeshF) {
+        for (index, half_edge) in mesh.half_edges.iter().enumerate() {
+            if half_edge.is_deleted() {
+                continue;
+            }
+
+            let face = half_edge.get_face_index();
+            assert!(!mesh.faces[face].is_deleted());
+            assert_eq!(mesh.next(mesh.previous(index)), index);
+            assert_eq!(mesh.half_edges[mesh.next(index)].get_face_index(), face);
+            assert!(!mesh.vertices[half_edge.get_origin_index()].is_deleted());
+
+            if let Some(twin) = half_edge.get_twin_index() {
+                assert!(!mesh.half_edges[twin].is_deleted());
+                assert_eq!(mesh.twin(twin), Some(index));
+                assert_eq!(mesh.origin(twin), mesh.target(index));
+            }
+        }
+
+        for vertex in mesh.vertices() {
+            let half_edge = mesh.vertices[vertex].get_half_edge_index();
+            assert!(!mesh.half_edges[half_edge].is_deleted());
+            assert_eq!(mesh.origin(half_edge), vertex);
+        }
+    }
+
+    fn boundary_edges_count(mesh: &HalfEdgeMeshF) -> usize {
+        mesh.edges().filter(|edge| mesh.is_edge_on_boundary(edge)).count()
+    }
+
+    #[test]
+    fn split_inner_edge() {
+        let mut mesh: HalfEdgeMeshF = primitives::plane(1.0, 1.0, 1, 1);
+        let edge = mesh.edges().find(|edge| !mesh.is_edge_on_boundary(edge)).unwrap();
+        let (v1, v2) = mesh.edge_positions(&edge);
+
+        mesh.split_edge(&edge, &((v1 + v2) * 0.5));
+
+        assert_connectivity_valid(&mesh);
+        assert_eq!(mesh.faces().count(), 4);
+        assert_eq!(mesh.edges().count(), 8);
+        assert_eq!(boundary_edges_count(&mesh), 4);
+
+        let mut valence = 0;
+        mesh.vertices_around_vertex(&4, |_| valence += 1);
+        assert_eq!(valence, 4);
+        assert!(!mesh.is_vertex_on_boundary(&4));
+    }
+
+    #[test]
+    fn split_boundary_edge() {
+        let mut mesh: HalfEdgeMeshF = primitives::plane(1.0, 1.0, 1, 1);
+        let edge = mesh.edges().find(|edge| mesh.is_edge_on_boundary(edge)).unwrap();
+        let (v1, v2) = mesh.edge_positions(&edge);
+
+        mesh.split_edge(&edge, &((v1 + v2) * 0.5));
+
+        assert_connectivity_valid(&mesh);
+        assert_eq!(mesh.faces().count(), 3);
+        assert_eq!(boundary_edges_count(&mesh), 5);
+        assert!(mesh.is_vertex_on_boundary(&4));
+    }
+
+    #[test]
+    fn flip_edge() {
+        let mut mesh: HalfEdgeMeshF = primitives::plane(1.0, 1.0, 1, 1);
+        let edge = mesh.edges().find(|edge| !mesh.is_edge_on_boundary(edge)).unwrap();
+        let (v1, v2) = mesh.edge_vertices(&edge);
+
+        mesh.flip_edge(&edge);
+
+        assert_connectivity_valid(&mesh);
+        assert_eq!(mesh.faces().count(), 2);
+
+        let (v3, v4) = mesh.edge_vertices(&edge);
+        let mut flipped = [v1, v2, v3, v4];
+        flipped.sort_unstable();
+        assert_eq!(flipped, [0, 1, 2, 3]);
+
+        // Faces keep orientation
+        for face in mesh.faces() {
+            assert!(mesh.face_normal(&face).z > 0.0);
+        }
+    }
+
+    #[test]
+    fn collapse_edge() {
+        let mut mesh: HalfEdgeMeshF = primitives::plane(4.0, 4.0, 4, 4);
+        let faces_count = mesh.faces().count();
+        let edge = mesh
+            .edges()
+            .find(|edge| {
+                let (v1, v2) = mesh.edge_vertices(edge);
+                !mesh.is_vertex_on_boundary(&v1) && !mesh.is_vertex_on_boundary(&v2)
+            })
+            .unwrap();
+        let (v_keep, v_remove) = mesh.edge_vertices(&edge);
+        let at = Vec3f::new(0.1, 0.1, 0.0);
+
+        mesh.collapse_edge(&edge, &at);
+
+        assert_connectivity_valid(&mesh);
+        assert!(!mesh.edge_exist(&edge));
+        assert_eq!(mesh.faces().count(), faces_count - 2);
+        assert!(mesh.vertices().all(|vertex| vertex != v_remove));
+        assert_eq!(*mesh.vertex_position(&v_keep), at);
+        assert_eq!(boundary_edges_count(&mesh), 16);
+    }
+
+    #[test]
+    fn collapse_boundary_edge() {
+        let mut mesh: HalfEdgeMeshF = primitives::plane(4.0, 4.0, 4, 4);
+        let edge = EdgeRef::new(mesh.find_half_edge(1, 2).unwrap(), &mesh);
+        assert!(mesh.is_edge_on_boundary(&edge));
+
+        mesh.collapse_edge(&edge, &Vec3f::new(-1.5, -2.0, 0.0));
+
+        assert_connectivity_valid(&mesh);
+        assert_eq!(mesh.faces().count(), 31);
+        assert_eq!(boundary_edges_count(&mesh), 15);
+        assert!(mesh.is_vertex_on_boundary(&1));
+    }
+
+    #[test]
+    fn split_face() {
+        let mut mesh: HalfEdgeMeshF = primitives::plane(1.0, 1.0, 1, 1);
+        mesh.split_face(&0, mesh.face_positions(&0).center());
+
+        assert_connectivity_valid(&mesh);
+        assert_eq!(mesh.faces().count(), 4);
+        assert_eq!(mesh.edges().count(), 8);
+
+        let mut valence = 0;
+        mesh.vertices_around_vertex(&4, |_| valence += 1);
+        assert_eq!(valence, 3);
+
+        for face in mesh.faces() {
+            assert!(mesh.face_normal(&face).z > 0.0);
+        }
+    }
+
+    #[test]
+    fn remesh_and_decimate() {
+        let mut mesh: HalfEdgeMeshF = primitives::uv_sphere(1.0, 16, 8);
+        assert_eq!(boundary_edges_count(&mesh), 0);
+
+        IncrementalRemesher::new().with_iterations_count(3).remesh(&mut mesh, 0.2);
+        assert_connectivity_valid(&mesh);
+        assert_eq!(boundary_edges_count(&mesh), 0);
+
+        let faces_count = mesh.faces().count();
+        let mut decimator = EdgeDecimator::<HalfEdgeMeshF, AlwaysDecimate>::new().min_faces_count(Some(faces_count / 4));
+        decimator.decimate(&mut mesh);
+
+        assert_connectivity_valid(&mesh);
+        assert_eq!(boundary_edges_count(&mesh), 0);
+        assert!(mesh.faces().count() < faces_count / 2);
+    }
+}
@@ -0,0 +1,74 @@
+use crate::{
+    geometry::traits::RealNumber,
+    mesh::{corner_table::connectivity::traits::Flags, traits::{Marker, Mesh}},
+};
+
+use super::mesh::HalfEdgeMesh;
+
+/// Implementation of [Marker] API for [HalfEdgeMesh]
+pub struct HalfEdgeMarker<TScalar: RealNumber> {
+    mesh: *const HalfEdgeMesh<TScalar>,
+}
+
+impl<TScalar: RealNumber> HalfEdgeMarker<TScalar> {
+    pub fn new(mesh: &HalfEdgeMesh<TScalar>) -> Self {
+        Self { mesh }
+    }
+
+    /// Flags are interior mutable, so marking needs only shared reference to mesh
+    #[inline]
+    fn mesh(&self) -> &HalfEdgeMesh<TScalar> {
+        unsafe { &*self.mesh }
+    }
+}
+
+impl<TScalar: RealNumber> Marker<HalfEdgeMesh<TScalar>> for HalfEdgeMarker<TScalar> {
+
+    //
+    // Face
+    //
+
+    #[inline]
+    fn mark_face(&mut self, face: &<HalfEdgeMesh<TScalar> as Mesh>::FaceDescriptor, marked: bool) {
+        self.mesh().faces[*face].set_marked_1(marked);
+    }
+
+    #[inline]
+    fn is_face_marked(&self, face: &<HalfEdgeMesh<TScalar> as Mesh>::FaceDescriptor) -> bool {
+        self.mesh().faces[*face].is_marked_1()
+    }
+
+    //
+    // Vertex
+    //
+
+    #[inline]
+    fn mark_vertex(&mut self, vertex: &<HalfEdgeMesh<TScalar> as Mesh>::VertexDescriptor, marked: bool) {
+        self.mesh().vertices[*vertex].set_marked_1(marked);
+    }
+
+    #[inline]
+    fn is_vertex_marked(&self, vertex: &<HalfEdgeMesh<TScalar> as Mesh>::VertexDescriptor) -> bool {
+        self.mesh().vertices[*vertex].is_marked_1()
+    }
+
+    //
+    // Edge
+    //
+
+    #[inline]
+    fn mark_edge(&mut self, edge: &<HalfEdgeMesh<TScalar> as Mesh>::EdgeDescriptor, marked: bool) {
+        let mesh = self.mesh();
+        let half_edge = &mesh.half_edges[edge.get_half_edge_index()];
+        half_edge.set_marked_2(marked);
+
+        if let Some(twin) = half_edge.get_twin_index() {
+            mesh.half_edges[twin].set_marked_2(marked);
+        }
+    }
+
+    #[inline]
+    fn is_edge_marked(&self, edge: &<HalfEdgeMesh<TScalar> as Mesh>::EdgeDescriptor) -> bool {
+        self.mesh().half_edges[edge.get_half_edge_index()].is_marked_2()
+    }
+}
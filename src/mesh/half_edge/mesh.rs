@@ -0,0 +1,445 @@
+use std::collections::HashMap;
+
+use crate::{
+    geometry::traits::RealNumber,
+    helpers::aliases::Vec3,
+    mesh::{
+        convert::convert_mesh,
+        corner_table::table::CornerTable,
+        traits::{Mesh, MeshMarker, TopologicalMesh},
+    },
+};
+
+use super::{
+    connectivity::{Face, HalfEdge, Vertex},
+    descriptors::EdgeRef,
+    marker::HalfEdgeMarker,
+    traversal::{HalfEdgeEdgesIter, HalfEdgeFacesIter, HalfEdgeVerticesIter, HalfEdgeWalker, OutgoingHalfEdgesIter},
+};
+
+///
+/// Half-edge mesh. Every face is cycle of three half-edges, half-edges of adjacent faces
+/// running along the same edge in opposite directions are twins. Boundary half-edges have no twin.
+///
+/// Unlike [CornerTable], where face is three consecutive corners, half-edges are linked explicitly,
+/// so edits can relink existing half-edges into new faces.
+/// Both structures implement the same mesh traits and can be converted into each other with [From].
+///
+pub struct HalfEdgeMesh<TScalar: RealNumber> {
+    pub(super) vertices: Vec<Vertex<TScalar>>,
+    pub(super) half_edges: Vec<HalfEdge>,
+    pub(super) faces: Vec<Face>,
+}
+
+impl<TScalar: RealNumber> Default for HalfEdgeMesh<TScalar> {
+    fn default() -> Self {
+        Self {
+            vertices: Vec::new(),
+            half_edges: Vec::new(),
+            faces: Vec::new(),
+        }
+    }
+}
+
+impl<TScalar: RealNumber> HalfEdgeMesh<TScalar> {
+    #[inline]
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    #[inline]
+    pub fn get_vertex(&self, vertex_index: usize) -> Option<&Vertex<TScalar>> {
+        self.vertices.get(vertex_index)
+    }
+
+    #[inline]
+    pub fn get_half_edge(&self, half_edge_index: usize) -> Option<&HalfEdge> {
+        self.half_edges.get(half_edge_index)
+    }
+
+    #[inline]
+    pub fn get_face(&self, face_index: usize) -> Option<&Face> {
+        self.faces.get(face_index)
+    }
+
+    /// Returns half-edge going from `from` to `to` vertex if exist
+    pub fn find_half_edge(&self, from: usize, to: usize) -> Option<usize> {
+        OutgoingHalfEdgesIter::new(self, from).find(|&half_edge| self.target(half_edge) == to)
+    }
+
+    /// Returns outgoing half-edges of vertex in counterclockwise order
+    #[inline]
+    pub fn outgoing_half_edges(&self, vertex_index: usize) -> OutgoingHalfEdgesIter<'_, TScalar> {
+        OutgoingHalfEdgesIter::new(self, vertex_index)
+    }
+
+    #[inline]
+    pub(super) fn next(&self, half_edge_index: usize) -> usize {
+        self.half_edges[half_edge_index].get_next_index()
+    }
+
+    #[inline]
+    pub(super) fn previous(&self, half_edge_index: usize) -> usize {
+        self.next(self.next(half_edge_index))
+    }
+
+    #[inline]
+    pub(super) fn twin(&self, half_edge_index: usize) -> Option<usize> {
+        self.half_edges[half_edge_index].get_twin_index()
+    }
+
+    #[inline]
+    pub(super) fn origin(&self, half_edge_index: usize) -> usize {
+        self.half_edges[half_edge_index].get_origin_index()
+    }
+
+    #[inline]
+    pub(super) fn target(&self, half_edge_index: usize) -> usize {
+        self.origin(self.next(half_edge_index))
+    }
+
+    /// Creates vertex without incident faces
+    pub(super) fn create_vertex(&mut self, position: Vec3<TScalar>) -> usize {
+        self.vertices.push(Vertex::new(usize::MAX, position));
+        self.vertices.len() - 1
+    }
+
+    /// Creates half-edge not linked to any face
+    pub(super) fn create_half_edge(&mut self, origin: usize) -> usize {
+        self.half_edges.push(HalfEdge::new(origin, usize::MAX, None, usize::MAX));
+        self.half_edges.len() - 1
+    }
+
+    /// Creates face from existing vertices without twins. Returns index of new face.
+    pub(super) fn create_face(&mut self, v1: usize, v2: usize, v3: usize) -> usize {
+        let h1 = self.create_half_edge(v1);
+        let h2 = self.create_half_edge(v2);
+        let h3 = self.create_half_edge(v3);
+
+        self.create_face_from_half_edges(h1, h2, h3)
+    }
+
+    /// Creates face from existing half-edges. Returns index of new face.
+    pub(super) fn create_face_from_half_edges(&mut self, h1: usize, h2: usize, h3: usize) -> usize {
+        self.faces.push(Face::new(h1));
+        let face_index = self.faces.len() - 1;
+        self.link_face(face_index, h1, h2, h3);
+
+        face_index
+    }
+
+    /// Links half-edges into cycle of given face
+    #[inline]
+    pub(super) fn link_face(&mut self, face_index: usize, h1: usize, h2: usize, h3: usize) {
+        self.half_edges[h1].set_next_index(h2).set_face_index(face_index);
+        self.half_edges[h2].set_next_index(h3).set_face_index(face_index);
+        self.half_edges[h3].set_next_index(h1).set_face_index(face_index);
+        self.faces[face_index].set_half_edge_index(h1);
+    }
+
+    /// Makes half-edges twins of each other
+    #[inline]
+    pub(super) fn make_twins(&mut self, h1: Option<usize>, h2: Option<usize>) {
+        if let Some(h1_index) = h1 {
+            self.half_edges[h1_index].set_twin_index(h2);
+        }
+
+        if let Some(h2_index) = h2 {
+            self.half_edges[h2_index].set_twin_index(h1);
+        }
+    }
+}
+
+///
+/// Implementation of mesh trait for half-edge mesh.
+///
+/// Edge is represented by one of its half-edges.
+/// Vertex is represented by it`s index in vertices vector.
+/// Face is represented by it`s index in faces vector.
+///
+impl<TScalar: RealNumber> Mesh for HalfEdgeMesh<TScalar> {
+    type ScalarType = TScalar;
+
+    /// Half-edge with smaller index
+    type EdgeDescriptor = EdgeRef;
+    /// Vertex index
+    type VertexDescriptor = usize;
+    /// Face index
+    type FaceDescriptor = usize;
+
+    type FacesIter<'iter> = HalfEdgeFacesIter<'iter, TScalar>;
+    type VerticesIter<'iter> = HalfEdgeVerticesIter<'iter, TScalar>;
+    type EdgesIter<'iter> = HalfEdgeEdgesIter<'iter, TScalar>;
+
+    fn from_vertices_and_indices(vertices: &[Vec3<Self::ScalarType>], faces: &[usize]) -> Self {
+        assert!(faces.len().is_multiple_of(3), "Invalid number of face indices: {}", faces.len());
+
+        let mut directed_edges = HashMap::<(usize, usize), usize>::new();
+        let mut mesh = Self::new();
+
+        for position in vertices {
+            mesh.create_vertex(*position);
+        }
+
+        for face in faces.chunks_exact(3) {
+            let edges = [(face[0], face[1]), (face[1], face[2]), (face[2], face[0])];
+
+            // Half-edge already exist, so edge is non manifold. Same as corner table we skip such faces.
+            if edges.iter().any(|edge| directed_edges.contains_key(edge)) {
+                continue;
+            }
+
+            let face_index = mesh.create_face(face[0], face[1], face[2]);
+
+            for (i, (start, end)) in edges.into_iter().enumerate() {
+                let half_edge = mesh.faces[face_index].get_half_edge_index() + i;
+                let twin = directed_edges.get(&(end, start)).copied();
+
+                mesh.make_twins(Some(half_edge), twin);
+                mesh.vertices[start].set_half_edge_index(half_edge);
+                directed_edges.insert((start, end), half_edge);
+            }
+        }
+
+        mesh
+    }
+
+    #[inline]
+    fn faces(&self) -> Self::FacesIter<'_> {
+        Self::FacesIter::new(self)
+    }
+
+    #[inline]
+    fn vertices(&self) -> Self::VerticesIter<'_> {
+        Self::VerticesIter::new(self)
+    }
+
+    #[inline]
+    fn edges(&self) -> Self::EdgesIter<'_> {
+        Self::EdgesIter::new(self)
+    }
+
+    #[inline]
+    fn face_vertices(&self, face: &Self::FaceDescriptor) -> (Self::VertexDescriptor, Self::VertexDescriptor, Self::VertexDescriptor) {
+        let half_edge = self.faces[*face].get_half_edge_index();
+        let next = self.next(half_edge);
+
+        (self.origin(half_edge), self.origin(next), self.origin(self.next(next)))
+    }
+
+    #[inline]
+    fn edge_positions(&self, edge: &Self::EdgeDescriptor) -> (Vec3<Self::ScalarType>, Vec3<Self::ScalarType>) {
+        let (start, end) = self.edge_vertices(edge);
+        (*self.vertex_position(&start), *self.vertex_position(&end))
+    }
+
+    #[inline]
+    fn edge_vertices(&self, edge: &Self::EdgeDescriptor) -> (Self::VertexDescriptor, Self::VertexDescriptor) {
+        let half_edge = edge.get_half_edge_index();
+        (self.origin(half_edge), self.target(half_edge))
+    }
+
+    #[inline]
+    fn vertex_position(&self, vertex: &Self::VertexDescriptor) -> &Vec3<Self::ScalarType> {
+        self.vertices[*vertex].get_position()
+    }
+
+    fn vertex_normal(&self, vertex: &Self::VertexDescriptor) -> Option<Vec3<Self::ScalarType>> {
+        let mut sum = Vec3::zeros();
+
+        self.faces_around_vertex(vertex, |face| {
+            sum += self.face_normal(face);
+        });
+
+        if sum.iter().all(|i| i.is_zero()) {
+            return None;
+        }
+
+        Some(sum.normalize())
+    }
+}
+
+impl<TScalar: RealNumber> TopologicalMesh for HalfEdgeMesh<TScalar> {
+    type Position<'a> = HalfEdgeWalker<'a, TScalar>;
+
+    fn vertices_around_vertex<TVisit: FnMut(&Self::VertexDescriptor)>(&self, vertex: &Self::VertexDescriptor, mut visit: TVisit) {
+        let mut last = None;
+
+        for half_edge in self.outgoing_half_edges(*vertex) {
+            visit(&self.target(half_edge));
+            last = Some(half_edge);
+        }
+
+        // Last neighbor of boundary vertex is not target of any outgoing half-edge
+        if let Some(last) = last {
+            let previous = self.previous(last);
+
+            if self.twin(previous).is_none() {
+                visit(&self.origin(previous));
+            }
+        }
+    }
+
+    #[inline]
+    fn faces_around_vertex<TVisit: FnMut(&Self::FaceDescriptor)>(&self, vertex: &Self::VertexDescriptor, mut visit: TVisit) {
+        for half_edge in self.outgoing_half_edges(*vertex) {
+            visit(&self.half_edges[half_edge].get_face_index());
+        }
+    }
+
+    fn edges_around_vertex<TVisit: FnMut(&Self::EdgeDescriptor)>(&self, vertex: &Self::VertexDescriptor, mut visit: TVisit) {
+        let mut last = None;
+
+        for half_edge in self.outgoing_half_edges(*vertex) {
+            visit(&EdgeRef::new(half_edge, self));
+            last = Some(half_edge);
+        }
+
+        if let Some(last) = last {
+            let previous = self.previous(last);
+
+            if self.twin(previous).is_none() {
+                visit(&EdgeRef::new(previous, self));
+            }
+        }
+    }
+
+    fn is_vertex_on_boundary(&self, vertex: &Self::VertexDescriptor) -> bool {
+        // Iteration of boundary vertex starts and ends at boundary edges
+        match self.outgoing_half_edges(*vertex).next() {
+            Some(first) => self.twin(first).is_none(),
+            None => false,
+        }
+    }
+
+    #[inline]
+    fn is_edge_on_boundary(&self, edge: &Self::EdgeDescriptor) -> bool {
+        self.twin(edge.get_half_edge_index()).is_none()
+    }
+
+    #[inline]
+    fn edge_faces(&self, edge: &Self::EdgeDescriptor) -> (Self::FaceDescriptor, Option<Self::FaceDescriptor>) {
+        let half_edge = edge.get_half_edge_index();
+        (
+            self.half_edges[half_edge].get_face_index(),
+            self.twin(half_edge).map(|twin| self.half_edges[twin].get_face_index()),
+        )
+    }
+
+    #[inline]
+    fn face_edges(&self, face: &Self::FaceDescriptor) -> (Self::EdgeDescriptor, Self::EdgeDescriptor, Self::EdgeDescriptor) {
+        let half_edge = self.faces[*face].get_half_edge_index();
+        let next = self.next(half_edge);
+
+        (
+            EdgeRef::new(half_edge, self),
+            EdgeRef::new(next, self),
+            EdgeRef::new(self.next(next), self),
+        )
+    }
+}
+
+impl<TScalar: RealNumber> MeshMarker for HalfEdgeMesh<TScalar> {
+    type Marker = HalfEdgeMarker<TScalar>;
+
+    #[inline]
+    fn marker(&self) -> Self::Marker {
+        HalfEdgeMarker::new(self)
+    }
+}
+
+impl<TScalar: RealNumber> From<&CornerTable<TScalar>> for HalfEdgeMesh<TScalar> {
+    /// Converts corner table to half-edge mesh. Deleted elements are dropped, order of remaining ones is preserved.
+    #[inline]
+    fn from(corner_table: &CornerTable<TScalar>) -> Self {
+        convert_mesh(corner_table)
+    }
+}
+
+impl<TScalar: RealNumber> From<&HalfEdgeMesh<TScalar>> for CornerTable<TScalar> {
+    /// Converts half-edge mesh to corner table. Deleted elements are dropped, order of remaining ones is preserved.
+    #[inline]
+    fn from(mesh: &HalfEdgeMesh<TScalar>) -> Self {
+        convert_mesh(mesh)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{
+            corner_table::prelude::CornerTableF,
+            half_edge::prelude::HalfEdgeMeshF,
+            primitives,
+            traits::{Mesh, TopologicalMesh},
+        },
+    };
+
+    #[test]
+    fn from_vertices_and_indices() {
+        let vertices = vec![
+            Vec3f::new(0.0, 0.0, 0.0),
+            Vec3f::new(1.0, 0.0, 0.0),
+            Vec3f::new(1.0, 1.0, 0.0),
+            Vec3f::new(0.0, 1.0, 0.0),
+        ];
+        let mesh = HalfEdgeMeshF::from_vertices_and_indices(&vertices, &[0, 1, 2, 0, 2, 3]);
+
+        assert_eq!(mesh.faces().count(), 2);
+        assert_eq!(mesh.vertices().count(), 4);
+        assert_eq!(mesh.edges().count(), 5);
+        assert_eq!(mesh.edges().filter(|edge| mesh.is_edge_on_boundary(edge)).count(), 4);
+        assert_eq!(mesh.face_vertices(&1), (0, 2, 3));
+
+        let diagonal = mesh.find_half_edge(0, 2).unwrap();
+        let twin = mesh.find_half_edge(2, 0).unwrap();
+        assert_eq!(mesh.get_half_edge(diagonal).unwrap().get_twin_index(), Some(twin));
+        assert!(mesh.vertices().all(|vertex| mesh.is_vertex_on_boundary(&vertex)));
+    }
+
+    #[test]
+    fn topology_matches_corner_table() {
+        let table: CornerTableF = primitives::plane(1.0, 1.0, 4, 4);
+        let mesh = HalfEdgeMeshF::from(&table);
+
+        assert_eq!(mesh.faces().count(), table.faces().count());
+        assert_eq!(mesh.edges().count(), table.edges().count());
+
+        for vertex in table.vertices() {
+            assert_eq!(mesh.vertex_position(&vertex), table.vertex_position(&vertex));
+            assert_eq!(mesh.is_vertex_on_boundary(&vertex), table.is_vertex_on_boundary(&vertex));
+
+            let mut expected = Vec::new();
+            table.vertices_around_vertex(&vertex, |v| expected.push(*v));
+            let mut actual = Vec::new();
+            mesh.vertices_around_vertex(&vertex, |v| actual.push(*v));
+
+            expected.sort_unstable();
+            actual.sort_unstable();
+            assert_eq!(actual, expected);
+
+            let mut faces_count = 0;
+            mesh.faces_around_vertex(&vertex, |_| faces_count += 1);
+            let mut edges_count = 0;
+            mesh.edges_around_vertex(&vertex, |_| edges_count += 1);
+            assert_eq!(edges_count, expected.len());
+            assert!(faces_count == edges_count || faces_count + 1 == edges_count);
+        }
+    }
+
+    #[test]
+    fn convert_to_corner_table_and_back() {
+        let sphere: CornerTableF = primitives::uv_sphere(1.0, 8, 6);
+        let mesh = HalfEdgeMeshF::from(&sphere);
+        let table = CornerTableF::from(&mesh);
+
+        assert_eq!(table.faces().count(), sphere.faces().count());
+
+        for (expected, actual) in sphere.faces().zip(table.faces()) {
+            assert_eq!(sphere.face_vertices(&expected), table.face_vertices(&actual));
+        }
+
+        assert!(mesh.edges().all(|edge| !mesh.is_edge_on_boundary(&edge)));
+    }
+}
@@ -0,0 +1,8 @@
+pub mod mesh;
+pub mod prelude;
+pub mod traversal;
+pub mod connectivity;
+
+mod marker;
+mod editable;
+mod descriptors;
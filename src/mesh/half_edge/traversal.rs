@@ -0,0 +1,320 @@
+use crate::{
+    geometry::traits::RealNumber,
+    mesh::{corner_table::connectivity::traits::Flags, traits::{Mesh, Position}},
+};
+
+use super::{connectivity::{HalfEdge, Vertex}, descriptors::EdgeRef, mesh::HalfEdgeMesh};
+
+///
+/// Can be used to traverse half-edge mesh topology
+///
+pub struct HalfEdgeWalker<'a, TScalar: RealNumber> {
+    mesh: &'a HalfEdgeMesh<TScalar>,
+    half_edge_index: usize,
+}
+
+impl<'a, TScalar: RealNumber> HalfEdgeWalker<'a, TScalar> {
+    /// Creates walker starting at given half-edge
+    pub fn from_half_edge(mesh: &'a HalfEdgeMesh<TScalar>, half_edge_index: usize) -> Self {
+        Self { mesh, half_edge_index }
+    }
+
+    /// Creates walker starting at one of outgoing half-edges of given vertex
+    pub fn from_vertex(mesh: &'a HalfEdgeMesh<TScalar>, vertex_index: usize) -> Self {
+        Self {
+            mesh,
+            half_edge_index: mesh.vertices[vertex_index].get_half_edge_index(),
+        }
+    }
+
+    /// Jumps to given half-edge
+    #[inline]
+    pub fn set_current_half_edge(&mut self, half_edge_index: usize) -> &mut Self {
+        self.half_edge_index = half_edge_index;
+        self
+    }
+
+    /// Moves to next half-edge of face
+    #[allow(clippy::should_implement_trait)]
+    #[inline]
+    pub fn next(&mut self) -> &mut Self {
+        self.half_edge_index = self.mesh.next(self.half_edge_index);
+        self
+    }
+
+    /// Moves to previous half-edge of face
+    #[inline]
+    pub fn previous(&mut self) -> &mut Self {
+        self.half_edge_index = self.mesh.previous(self.half_edge_index);
+        self
+    }
+
+    /// Moves to twin half-edge if exist, otherwise walker stays still
+    #[inline]
+    pub fn twin(&mut self) -> &mut Self {
+        if let Some(twin) = self.mesh.twin(self.half_edge_index) {
+            self.half_edge_index = twin;
+        } else {
+            debug_assert!(false, "Moving to not existing half-edge");
+        }
+
+        self
+    }
+
+    #[inline]
+    pub fn get_half_edge_index(&self) -> usize {
+        self.half_edge_index
+    }
+
+    #[inline]
+    pub fn get_half_edge(&self) -> &HalfEdge {
+        &self.mesh.half_edges[self.half_edge_index]
+    }
+
+    /// Returns vertex current half-edge starts at
+    #[inline]
+    pub fn get_origin(&self) -> &Vertex<TScalar> {
+        &self.mesh.vertices[self.get_half_edge().get_origin_index()]
+    }
+}
+
+///
+/// Implementation of [Position] for half-edge mesh.
+/// Corner of face is represented by half-edge starting at corner vertex,
+/// so edge opposite to corner is next half-edge.
+///
+impl<'a, TScalar: RealNumber> Position<'a, HalfEdgeMesh<TScalar>> for HalfEdgeWalker<'a, TScalar> {
+    fn from_vertex_on_face(
+        mesh: &'a HalfEdgeMesh<TScalar>,
+        face: &<HalfEdgeMesh<TScalar> as Mesh>::FaceDescriptor,
+        vertex: &<HalfEdgeMesh<TScalar> as Mesh>::VertexDescriptor,
+    ) -> Self {
+        let mut walker = HalfEdgeWalker::from_half_edge(mesh, mesh.faces[*face].get_half_edge_index());
+        walker.set_from_vertex_on_face(face, vertex);
+        walker
+    }
+
+    #[inline]
+    fn from_edge_on_face(
+        mesh: &'a HalfEdgeMesh<TScalar>,
+        face: &<HalfEdgeMesh<TScalar> as Mesh>::FaceDescriptor,
+        edge: &<HalfEdgeMesh<TScalar> as Mesh>::EdgeDescriptor,
+    ) -> Self {
+        let mut walker = HalfEdgeWalker::from_half_edge(mesh, edge.get_half_edge_index());
+        walker.set_from_edge_on_face(face, edge);
+        walker
+    }
+
+    #[inline]
+    fn from_edge(mesh: &'a HalfEdgeMesh<TScalar>, edge: &<HalfEdgeMesh<TScalar> as Mesh>::EdgeDescriptor) -> Self {
+        HalfEdgeWalker::from_half_edge(mesh, mesh.previous(edge.get_half_edge_index()))
+    }
+
+    fn set_from_vertex_on_face(
+        &mut self,
+        face: &<HalfEdgeMesh<TScalar> as Mesh>::FaceDescriptor,
+        vertex: &<HalfEdgeMesh<TScalar> as Mesh>::VertexDescriptor,
+    ) -> &mut Self {
+        self.set_current_half_edge(self.mesh.faces[*face].get_half_edge_index());
+
+        for _ in 0..3 {
+            if self.get_half_edge().get_origin_index() == *vertex {
+                return self;
+            }
+
+            self.next();
+        }
+
+        unreachable!("Input must be invalid or non-manifold");
+    }
+
+    #[inline]
+    fn set_from_edge_on_face(
+        &mut self,
+        face: &<HalfEdgeMesh<TScalar> as Mesh>::FaceDescriptor,
+        edge: &<HalfEdgeMesh<TScalar> as Mesh>::EdgeDescriptor,
+    ) -> &mut Self {
+        let half_edge = edge.get_half_edge_index();
+
+        if self.mesh.half_edges[half_edge].get_face_index() == *face {
+            self.set_current_half_edge(half_edge);
+        } else {
+            self.set_current_half_edge(self.mesh.twin(half_edge).unwrap());
+        }
+
+        self.previous()
+    }
+
+    #[inline]
+    fn next(&mut self) -> &mut Self {
+        self.next()
+    }
+
+    /// Moves to corner opposite to current one across edge opposite to current corner
+    #[inline]
+    fn opposite(&mut self) -> &mut Self {
+        self.next().twin().previous()
+    }
+
+    #[inline]
+    fn get_vertex(&self) -> <HalfEdgeMesh<TScalar> as Mesh>::VertexDescriptor {
+        self.get_half_edge().get_origin_index()
+    }
+}
+
+///
+/// Iterator over outgoing half-edges of vertex (one per incident face) in counterclockwise order.
+/// For boundary vertex iteration starts at boundary, so faces are returned as single fan.
+/// Number of steps is limited by number of half-edges in mesh, so iteration ends even on broken topology.
+///
+pub struct OutgoingHalfEdgesIter<'a, TScalar: RealNumber> {
+    mesh: &'a HalfEdgeMesh<TScalar>,
+    start: usize,
+    current: Option<usize>,
+    steps: usize,
+}
+
+impl<'a, TScalar: RealNumber> OutgoingHalfEdgesIter<'a, TScalar> {
+    pub fn new(mesh: &'a HalfEdgeMesh<TScalar>, vertex_index: usize) -> Self {
+        let vertex = &mesh.vertices[vertex_index];
+        let first = vertex.get_half_edge_index();
+
+        // Deleted or isolated vertex
+        if vertex.is_deleted() || first >= mesh.half_edges.len() {
+            return Self { mesh, start: 0, current: None, steps: 0 };
+        }
+
+        // Rewind clockwise to boundary, if any
+        let mut start = first;
+
+        for _ in 0..mesh.half_edges.len() {
+            match mesh.twin(start) {
+                Some(twin) => {
+                    start = mesh.next(twin);
+
+                    if start == first {
+                        break;
+                    }
+                }
+                None => break,
+            }
+        }
+
+        Self {
+            mesh,
+            start,
+            current: Some(start),
+            steps: 0,
+        }
+    }
+}
+
+impl<'a, TScalar: RealNumber> Iterator for OutgoingHalfEdgesIter<'a, TScalar> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current?;
+        self.steps += 1;
+
+        self.current = self
+            .mesh
+            .twin(self.mesh.previous(current))
+            .filter(|&next| next != self.start && self.steps < self.mesh.half_edges.len());
+
+        Some(current)
+    }
+}
+
+///
+/// Iterator over faces of half-edge mesh
+///
+pub struct HalfEdgeFacesIter<'a, TScalar: RealNumber> {
+    mesh: &'a HalfEdgeMesh<TScalar>,
+    face_index: usize,
+}
+
+impl<'a, TScalar: RealNumber> HalfEdgeFacesIter<'a, TScalar> {
+    pub fn new(mesh: &'a HalfEdgeMesh<TScalar>) -> Self {
+        Self { mesh, face_index: 0 }
+    }
+}
+
+impl<'a, TScalar: RealNumber> Iterator for HalfEdgeFacesIter<'a, TScalar> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(face) = self.mesh.faces.get(self.face_index) {
+            self.face_index += 1;
+
+            if !face.is_deleted() {
+                return Some(self.face_index - 1);
+            }
+        }
+
+        None
+    }
+}
+
+///
+/// Iterator over vertices of half-edge mesh
+///
+pub struct HalfEdgeVerticesIter<'a, TScalar: RealNumber> {
+    mesh: &'a HalfEdgeMesh<TScalar>,
+    vertex_index: usize,
+}
+
+impl<'a, TScalar: RealNumber> HalfEdgeVerticesIter<'a, TScalar> {
+    pub fn new(mesh: &'a HalfEdgeMesh<TScalar>) -> Self {
+        Self { mesh, vertex_index: 0 }
+    }
+}
+
+impl<'a, TScalar: RealNumber> Iterator for HalfEdgeVerticesIter<'a, TScalar> {
+    type Item = usize;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(vertex) = self.mesh.vertices.get(self.vertex_index) {
+            self.vertex_index += 1;
+
+            if !vertex.is_deleted() {
+                return Some(self.vertex_index - 1);
+            }
+        }
+
+        None
+    }
+}
+
+///
+/// Iterator over edges of half-edge mesh. Edge is returned as its half-edge with smaller index.
+///
+pub struct HalfEdgeEdgesIter<'a, TScalar: RealNumber> {
+    mesh: &'a HalfEdgeMesh<TScalar>,
+    half_edge_index: usize,
+}
+
+impl<'a, TScalar: RealNumber> HalfEdgeEdgesIter<'a, TScalar> {
+    pub fn new(mesh: &'a HalfEdgeMesh<TScalar>) -> Self {
+        Self { mesh, half_edge_index: 0 }
+    }
+}
+
+impl<'a, TScalar: RealNumber> Iterator for HalfEdgeEdgesIter<'a, TScalar> {
+    type Item = EdgeRef;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while let Some(half_edge) = self.mesh.half_edges.get(self.half_edge_index) {
+            let current = self.half_edge_index;
+            self.half_edge_index += 1;
+
+            // Interior edge is reported by half-edge with smaller index
+            let is_representative = half_edge.get_twin_index().is_none_or(|twin| current < twin);
+
+            if !half_edge.is_deleted() && is_representative {
+                return Some(EdgeRef::new(current, self.mesh));
+            }
+        }
+
+        None
+    }
+}
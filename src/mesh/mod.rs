@@ -1,4 +1,15 @@
 pub mod corner_table;
+pub mod half_edge;
 pub mod polygon_soup;
 pub mod traits;
 pub mod builder;
+pub mod remap;
+pub mod primitives;
+pub mod face_groups;
+pub mod face_uvs;
+pub mod vertex_groups;
+pub mod convert;
+pub mod submesh;
+pub mod buffers;
+#[cfg(feature = "serde")]
+mod serialization;
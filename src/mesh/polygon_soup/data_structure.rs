@@ -1,4 +1,9 @@
-use crate::{mesh::traits::Mesh, geometry::{traits::RealNumber, primitives::triangle3::Triangle3}, helpers::aliases::Vec3};
+use crate::{
+    algo::merge_points::merge_points,
+    geometry::{primitives::triangle3::Triangle3, traits::RealNumber},
+    helpers::aliases::Vec3,
+    mesh::{convert::convert_mesh, corner_table::table::CornerTable, traits::Mesh},
+};
 use super::traversal::{FacesIter, VerticesIter, EdgesIter};
 
 ///
@@ -6,7 +11,7 @@ use super::traversal::{FacesIter, VerticesIter, EdgesIter};
 /// 
 #[derive(Debug)]
 pub struct PolygonSoup<TScalar: RealNumber> {
-   pub(super) vertices: Vec<Vec3<TScalar>>
+   pub(crate) vertices: Vec<Vec3<TScalar>>
 }
 
 impl<TScalar: RealNumber> PolygonSoup<TScalar> {
@@ -88,8 +93,9 @@ impl<TScalar: RealNumber> Mesh for PolygonSoup<TScalar> {
     }
 
     #[inline]
-    fn edge_vertices(&self, _edge: &Self::EdgeDescriptor) -> (Self::VertexDescriptor, Self::VertexDescriptor) {
-        todo!()
+    fn edge_vertices(&self, edge: &Self::EdgeDescriptor) -> (Self::VertexDescriptor, Self::VertexDescriptor) {
+        let v2 = if edge % 3 == 2 { edge - 2 } else { edge + 1 };
+        (*edge, v2)
     }
 
     #[inline]
@@ -102,8 +108,9 @@ impl<TScalar: RealNumber> Mesh for PolygonSoup<TScalar> {
         todo!()
     }
 
-    fn face_vertices(&self, _face: &Self::FaceDescriptor) -> (Self::VertexDescriptor, Self::VertexDescriptor, Self::VertexDescriptor) {
-        todo!()
+    #[inline]
+    fn face_vertices(&self, face: &Self::FaceDescriptor) -> (Self::VertexDescriptor, Self::VertexDescriptor, Self::VertexDescriptor) {
+        (*face, face + 1, face + 2)
     }
 }
 
@@ -113,3 +120,19 @@ impl<TScalar: RealNumber> From<Vec<Vec3<TScalar>>> for PolygonSoup<TScalar> {
         Self::from_vertices(value)
     }
 }
+
+impl<TScalar: RealNumber> From<&CornerTable<TScalar>> for PolygonSoup<TScalar> {
+    /// Converts corner table to polygon soup, faces keep their order
+    #[inline]
+    fn from(corner_table: &CornerTable<TScalar>) -> Self {
+        convert_mesh(corner_table)
+    }
+}
+
+impl<TScalar: RealNumber> From<&PolygonSoup<TScalar>> for CornerTable<TScalar> {
+    /// Converts polygon soup to corner table, exactly coincident vertices of faces are merged
+    fn from(soup: &PolygonSoup<TScalar>) -> Self {
+        let merged = merge_points(&soup.vertices);
+        Self::from_vertices_and_indices(&merged.points, &merged.indices)
+    }
+}
@@ -0,0 +1,449 @@
+//!
+//! Generators of parametric meshes. All meshes are centered at origin, use Z axis as up direction
+//! and have faces oriented outwards (counter-clockwise when viewed from outside).
+//!
+
+use std::collections::HashMap;
+
+use num_traits::{Float, Zero};
+
+use crate::{geometry::traits::RealNumber, helpers::aliases::Vec3};
+
+use super::traits::Mesh;
+
+#[inline]
+fn real<TScalar: RealNumber>(value: f64) -> TScalar {
+    TScalar::from(value).unwrap()
+}
+
+#[inline]
+fn ratio<TScalar: RealNumber>(numerator: usize, denominator: usize) -> TScalar {
+    TScalar::from(numerator).unwrap() / TScalar::from(denominator).unwrap()
+}
+
+#[inline]
+fn angle<TScalar: RealNumber>(step: usize, steps: usize) -> TScalar {
+    real::<TScalar>(std::f64::consts::TAU) * ratio(step % steps, steps)
+}
+
+///
+/// Rectangular grid in XY plane facing +Z direction.
+///
+/// * `width` - size along X axis
+/// * `height` - size along Y axis
+/// * `width_segments`, `height_segments` - number of grid cells along each axis
+///
+pub fn plane<T: Mesh>(
+    width: T::ScalarType,
+    height: T::ScalarType,
+    width_segments: usize,
+    height_segments: usize,
+) -> T {
+    assert!(width_segments > 0 && height_segments > 0, "Number of segments should be positive");
+
+    let half: T::ScalarType = real(0.5);
+    let mut vertices = Vec::with_capacity((width_segments + 1) * (height_segments + 1));
+
+    for j in 0..=height_segments {
+        for i in 0..=width_segments {
+            vertices.push(Vec3::new(
+                width * (ratio::<T::ScalarType>(i, width_segments) - half),
+                height * (ratio::<T::ScalarType>(j, height_segments) - half),
+                T::ScalarType::zero(),
+            ));
+        }
+    }
+
+    let index = |i: usize, j: usize| j * (width_segments + 1) + i;
+    let mut faces = Vec::with_capacity(width_segments * height_segments * 6);
+
+    for j in 0..height_segments {
+        for i in 0..width_segments {
+            push_quad(
+                &mut faces,
+                index(i, j),
+                index(i + 1, j),
+                index(i + 1, j + 1),
+                index(i, j + 1),
+            );
+        }
+    }
+
+    T::from_vertices_and_indices(&vertices, &faces)
+}
+
+///
+/// Box with given size along each axis. Every side is split into `segments` x `segments` grid.
+///
+pub fn cuboid<T: Mesh>(size: Vec3<T::ScalarType>, segments: usize) -> T {
+    assert!(segments > 0, "Number of segments should be positive");
+
+    let n = segments;
+
+    // Sides are defined on integer lattice as (origin, u axis, v axis) where u x v is outward normal
+    let sides: [([usize; 3], [usize; 3], [usize; 3]); 6] = [
+        ([0, 0, 0], [0, 1, 0], [1, 0, 0]), // -Z
+        ([0, 0, n], [1, 0, 0], [0, 1, 0]), // +Z
+        ([0, 0, 0], [0, 0, 1], [0, 1, 0]), // -X
+        ([n, 0, 0], [0, 1, 0], [0, 0, 1]), // +X
+        ([0, 0, 0], [1, 0, 0], [0, 0, 1]), // -Y
+        ([0, n, 0], [0, 0, 1], [1, 0, 0]), // +Y
+    ];
+
+    let half: T::ScalarType = real(0.5);
+    let mut lattice_index = HashMap::new();
+    let mut vertices = Vec::new();
+    let mut faces = Vec::with_capacity(n * n * 36);
+
+    let mut vertex = |point: [usize; 3]| {
+        *lattice_index.entry(point).or_insert_with(|| {
+            vertices.push(Vec3::new(
+                size.x * (ratio::<T::ScalarType>(point[0], n) - half),
+                size.y * (ratio::<T::ScalarType>(point[1], n) - half),
+                size.z * (ratio::<T::ScalarType>(point[2], n) - half),
+            ));
+            vertices.len() - 1
+        })
+    };
+
+    for (origin, u, v) in sides {
+        let point = |i: usize, j: usize| {
+            [
+                origin[0] + u[0] * i + v[0] * j,
+                origin[1] + u[1] * i + v[1] * j,
+                origin[2] + u[2] * i + v[2] * j,
+            ]
+        };
+
+        for i in 0..n {
+            for j in 0..n {
+                let v1 = vertex(point(i, j));
+                let v2 = vertex(point(i + 1, j));
+                let v3 = vertex(point(i + 1, j + 1));
+                let v4 = vertex(point(i, j + 1));
+
+                push_quad(&mut faces, v1, v2, v3, v4);
+            }
+        }
+    }
+
+    T::from_vertices_and_indices(&vertices, &faces)
+}
+
+///
+/// UV sphere made of `rings` latitude bands and `segments` longitude slices.
+///
+pub fn uv_sphere<T: Mesh>(radius: T::ScalarType, segments: usize, rings: usize) -> T {
+    assert!(segments > 2, "UV sphere should have at least 3 segments");
+    assert!(rings > 1, "UV sphere should have at least 2 rings");
+
+    let pi: T::ScalarType = real(std::f64::consts::PI);
+    let mut vertices = Vec::with_capacity(segments * (rings - 1) + 2);
+
+    // North pole
+    vertices.push(Vec3::new(T::ScalarType::zero(), T::ScalarType::zero(), radius));
+
+    for ring in 1..rings {
+        let theta = pi * ratio(ring, rings);
+        let z = radius * Float::cos(theta);
+        let rho = radius * Float::sin(theta);
+
+        for segment in 0..segments {
+            let phi = angle::<T::ScalarType>(segment, segments);
+            vertices.push(Vec3::new(rho * Float::cos(phi), rho * Float::sin(phi), z));
+        }
+    }
+
+    // South pole
+    vertices.push(Vec3::new(T::ScalarType::zero(), T::ScalarType::zero(), -radius));
+
+    let north = 0;
+    let south = vertices.len() - 1;
+    let index = |ring: usize, segment: usize| 1 + (ring - 1) * segments + segment % segments;
+    let mut faces = Vec::with_capacity(segments * (rings - 1) * 6);
+
+    for segment in 0..segments {
+        faces.extend([north, index(1, segment), index(1, segment + 1)]);
+        faces.extend([south, index(rings - 1, segment + 1), index(rings - 1, segment)]);
+    }
+
+    for ring in 1..rings - 1 {
+        for segment in 0..segments {
+            push_quad(
+                &mut faces,
+                index(ring, segment),
+                index(ring + 1, segment),
+                index(ring + 1, segment + 1),
+                index(ring, segment + 1),
+            );
+        }
+    }
+
+    T::from_vertices_and_indices(&vertices, &faces)
+}
+
+///
+/// Sphere obtained by subdividing icosahedron `subdivisions` times.
+/// Unlike UV sphere, its triangles have nearly equal size.
+///
+pub fn icosphere<T: Mesh>(radius: T::ScalarType, subdivisions: usize) -> T {
+    let t = (1.0 + 5.0_f64.sqrt()) / 2.0;
+
+    let mut vertices: Vec<Vec3<T::ScalarType>> = [
+        (-1.0, t, 0.0),
+        (1.0, t, 0.0),
+        (-1.0, -t, 0.0),
+        (1.0, -t, 0.0),
+        (0.0, -1.0, t),
+        (0.0, 1.0, t),
+        (0.0, -1.0, -t),
+        (0.0, 1.0, -t),
+        (t, 0.0, -1.0),
+        (t, 0.0, 1.0),
+        (-t, 0.0, -1.0),
+        (-t, 0.0, 1.0),
+    ]
+    .into_iter()
+    .map(|(x, y, z)| Vec3::new(real(x), real(y), real(z)).normalize())
+    .collect();
+
+    let mut faces = vec![
+        0, 11, 5, 0, 5, 1, 0, 1, 7, 0, 7, 10, 0, 10, 11,
+        1, 5, 9, 5, 11, 4, 11, 10, 2, 10, 7, 6, 7, 1, 8,
+        3, 9, 4, 3, 4, 2, 3, 2, 6, 3, 6, 8, 3, 8, 9,
+        4, 9, 5, 2, 4, 11, 6, 2, 10, 8, 6, 7, 9, 8, 1,
+    ];
+
+    for _ in 0..subdivisions {
+        let mut midpoints = HashMap::new();
+        let mut midpoint = |v1: usize, v2: usize| {
+            let key = (v1.min(v2), v1.max(v2));
+            *midpoints.entry(key).or_insert_with(|| {
+                let mid = (vertices[v1] + vertices[v2]).normalize();
+                vertices.push(mid);
+                vertices.len() - 1
+            })
+        };
+
+        let mut subdivided = Vec::with_capacity(faces.len() * 4);
+
+        for face in faces.chunks_exact(3) {
+            let (v1, v2, v3) = (face[0], face[1], face[2]);
+            let v12 = midpoint(v1, v2);
+            let v23 = midpoint(v2, v3);
+            let v31 = midpoint(v3, v1);
+
+            subdivided.extend([v1, v12, v31]);
+            subdivided.extend([v2, v23, v12]);
+            subdivided.extend([v3, v31, v23]);
+            subdivided.extend([v12, v23, v31]);
+        }
+
+        faces = subdivided;
+    }
+
+    for vertex in &mut vertices {
+        *vertex *= radius;
+    }
+
+    T::from_vertices_and_indices(&vertices, &faces)
+}
+
+///
+/// Closed cylinder with axis along Z. `segments` is number of sides approximating its circular base.
+///
+pub fn cylinder<T: Mesh>(radius: T::ScalarType, height: T::ScalarType, segments: usize) -> T {
+    assert!(segments > 2, "Cylinder should have at least 3 segments");
+
+    let half_height = height * real(0.5);
+    let mut vertices = Vec::with_capacity(segments * 2 + 2);
+
+    for z in [-half_height, half_height] {
+        for segment in 0..segments {
+            let phi = angle::<T::ScalarType>(segment, segments);
+            vertices.push(Vec3::new(radius * Float::cos(phi), radius * Float::sin(phi), z));
+        }
+    }
+
+    let bottom_center = vertices.len();
+    vertices.push(Vec3::new(T::ScalarType::zero(), T::ScalarType::zero(), -half_height));
+    let top_center = vertices.len();
+    vertices.push(Vec3::new(T::ScalarType::zero(), T::ScalarType::zero(), half_height));
+
+    let bottom = |segment: usize| segment % segments;
+    let top = |segment: usize| segments + segment % segments;
+    let mut faces = Vec::with_capacity(segments * 12);
+
+    for segment in 0..segments {
+        push_quad(&mut faces, top(segment), bottom(segment), bottom(segment + 1), top(segment + 1));
+        faces.extend([top_center, top(segment), top(segment + 1)]);
+        faces.extend([bottom_center, bottom(segment + 1), bottom(segment)]);
+    }
+
+    T::from_vertices_and_indices(&vertices, &faces)
+}
+
+///
+/// Closed cone with axis along Z. Base is at `-height / 2`, apex is at `height / 2`.
+///
+pub fn cone<T: Mesh>(radius: T::ScalarType, height: T::ScalarType, segments: usize) -> T {
+    assert!(segments > 2, "Cone should have at least 3 segments");
+
+    let half_height = height * real(0.5);
+    let mut vertices = Vec::with_capacity(segments + 2);
+
+    for segment in 0..segments {
+        let phi = angle::<T::ScalarType>(segment, segments);
+        vertices.push(Vec3::new(radius * Float::cos(phi), radius * Float::sin(phi), -half_height));
+    }
+
+    let base_center = vertices.len();
+    vertices.push(Vec3::new(T::ScalarType::zero(), T::ScalarType::zero(), -half_height));
+    let apex = vertices.len();
+    vertices.push(Vec3::new(T::ScalarType::zero(), T::ScalarType::zero(), half_height));
+
+    let base = |segment: usize| segment % segments;
+    let mut faces = Vec::with_capacity(segments * 6);
+
+    for segment in 0..segments {
+        faces.extend([apex, base(segment), base(segment + 1)]);
+        faces.extend([base_center, base(segment + 1), base(segment)]);
+    }
+
+    T::from_vertices_and_indices(&vertices, &faces)
+}
+
+///
+/// Torus lying in XY plane.
+///
+/// * `major_radius` - distance from center of torus to center of tube
+/// * `minor_radius` - radius of tube
+/// * `major_segments` - number of segments along the ring
+/// * `minor_segments` - number of segments around the tube
+///
+pub fn torus<T: Mesh>(
+    major_radius: T::ScalarType,
+    minor_radius: T::ScalarType,
+    major_segments: usize,
+    minor_segments: usize,
+) -> T {
+    assert!(major_segments > 2 && minor_segments > 2, "Torus should have at least 3 segments in each direction");
+
+    let mut vertices = Vec::with_capacity(major_segments * minor_segments);
+
+    for i in 0..major_segments {
+        let phi = angle::<T::ScalarType>(i, major_segments);
+
+        for j in 0..minor_segments {
+            let theta = angle::<T::ScalarType>(j, minor_segments);
+            let rho = major_radius + minor_radius * Float::cos(theta);
+
+            vertices.push(Vec3::new(
+                rho * Float::cos(phi),
+                rho * Float::sin(phi),
+                minor_radius * Float::sin(theta),
+            ));
+        }
+    }
+
+    let index = |i: usize, j: usize| (i % major_segments) * minor_segments + j % minor_segments;
+    let mut faces = Vec::with_capacity(major_segments * minor_segments * 6);
+
+    for i in 0..major_segments {
+        for j in 0..minor_segments {
+            push_quad(&mut faces, index(i, j), index(i + 1, j), index(i + 1, j + 1), index(i, j + 1));
+        }
+    }
+
+    T::from_vertices_and_indices(&vertices, &faces)
+}
+
+/// Splits counter-clockwise quad into two triangles
+#[inline]
+fn push_quad(faces: &mut Vec<usize>, v1: usize, v2: usize, v3: usize, v4: usize) {
+    faces.extend([v1, v2, v3, v1, v3, v4]);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mesh::{corner_table::prelude::CornerTableD, traits::TopologicalMesh};
+
+    fn assert_closed(mesh: &CornerTableD, faces: usize, euler_characteristic: isize) {
+        assert_eq!(mesh.faces().count(), faces);
+        assert!(mesh.edges().all(|edge| !mesh.is_edge_on_boundary(&edge)));
+
+        let v = mesh.vertices().count() as isize;
+        let e = mesh.edges().count() as isize;
+        let f = mesh.faces().count() as isize;
+        assert_eq!(v - e + f, euler_characteristic);
+    }
+
+    fn signed_volume(mesh: &CornerTableD) -> f64 {
+        mesh.faces()
+            .map(|face| {
+                let triangle = mesh.face_positions(&face);
+                triangle.p1().dot(&triangle.p2().cross(triangle.p3())) / 6.0
+            })
+            .sum()
+    }
+
+    #[test]
+    fn test_plane() {
+        let mesh: CornerTableD = plane(2.0, 1.0, 4, 2);
+
+        assert_eq!(mesh.vertices().count(), 15);
+        assert_eq!(mesh.faces().count(), 16);
+        assert!(mesh.faces().all(|face| mesh.face_normal(&face).z > 0.0));
+    }
+
+    #[test]
+    fn test_cuboid() {
+        let mesh: CornerTableD = cuboid(Vec3::new(1.0, 2.0, 3.0), 3);
+
+        assert_closed(&mesh, 6 * 9 * 2, 2);
+        assert!((signed_volume(&mesh) - 6.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_uv_sphere() {
+        let mesh: CornerTableD = uv_sphere(1.0, 32, 16);
+
+        assert_closed(&mesh, 32 * 2 + 32 * 14 * 2, 2);
+        assert!(mesh.vertices().all(|v| (mesh.vertex_position(&v).norm() - 1.0).abs() < 1e-9));
+        assert!(signed_volume(&mesh) > 0.0);
+    }
+
+    #[test]
+    fn test_icosphere() {
+        let mesh: CornerTableD = icosphere(2.0, 2);
+
+        assert_closed(&mesh, 20 * 16, 2);
+        assert!(mesh.vertices().all(|v| (mesh.vertex_position(&v).norm() - 2.0).abs() < 1e-9));
+        assert!(signed_volume(&mesh) > 0.0);
+    }
+
+    #[test]
+    fn test_cylinder() {
+        let mesh: CornerTableD = cylinder(1.0, 2.0, 16);
+
+        assert_closed(&mesh, 16 * 4, 2);
+        assert!(signed_volume(&mesh) > 0.0);
+    }
+
+    #[test]
+    fn test_cone() {
+        let mesh: CornerTableD = cone(1.0, 2.0, 16);
+
+        assert_closed(&mesh, 16 * 2, 2);
+        assert!(signed_volume(&mesh) > 0.0);
+    }
+
+    #[test]
+    fn test_torus() {
+        let mesh: CornerTableD = torus(2.0, 0.5, 24, 12);
+
+        assert_closed(&mesh, 24 * 12 * 2, 0);
+        assert!(signed_volume(&mesh) > 0.0);
+    }
+}
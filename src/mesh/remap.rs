@@ -0,0 +1,119 @@
+///
+/// Table mapping old element indices to new ones.
+/// Returned by algorithms that rebuild mesh and so can change indices of its elements.
+/// Can be used to update user-side attribute arrays and external references.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Remap {
+    map: Vec<Option<usize>>,
+    new_len: usize,
+}
+
+/// Maps old vertex indices to new ones
+pub type VertexRemap = Remap;
+/// Maps old face indices to new ones
+pub type FaceRemap = Remap;
+
+impl Remap {
+    ///
+    /// Creates remap from table of new indices. `None` means that element was removed.
+    /// New indices are expected to be dense, i.e. cover `0..new_len` range.
+    ///
+    pub fn new(map: Vec<Option<usize>>) -> Self {
+        let new_len = map.iter().flatten().map(|idx| idx + 1).max().unwrap_or(0);
+        Self { map, new_len }
+    }
+
+    /// Creates remap that maps each index to itself
+    pub fn identity(len: usize) -> Self {
+        Self {
+            map: (0..len).map(Some).collect(),
+            new_len: len,
+        }
+    }
+
+    /// Returns new index of element or `None` if element was removed
+    #[inline]
+    pub fn get(&self, old: usize) -> Option<usize> {
+        self.map.get(old).copied().flatten()
+    }
+
+    /// Number of elements before remapping
+    #[inline]
+    pub fn old_len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Number of elements after remapping
+    #[inline]
+    pub fn new_len(&self) -> usize {
+        self.new_len
+    }
+
+    /// Returns `true` if element was removed
+    #[inline]
+    pub fn is_removed(&self, old: usize) -> bool {
+        self.get(old).is_none()
+    }
+
+    /// Returns iterator over `(old, new)` pairs of elements that were kept
+    pub fn iter(&self) -> impl Iterator<Item = (usize, usize)> + '_ {
+        self.map
+            .iter()
+            .enumerate()
+            .filter_map(|(old, new)| new.map(|new| (old, new)))
+    }
+
+    ///
+    /// Applies remap to attribute array indexed by old indices.
+    /// Attributes of removed elements are dropped. When several old elements are mapped to same new element
+    /// attribute of the first one is used.
+    ///
+    pub fn apply<T: Clone>(&self, attributes: &[T]) -> Vec<T> {
+        assert!(
+            attributes.len() == self.old_len(),
+            "Invalid number of attributes: {}, expected {}",
+            attributes.len(),
+            self.old_len()
+        );
+
+        let mut remapped: Vec<Option<T>> = vec![None; self.new_len];
+
+        for (old, new) in self.iter() {
+            if remapped[new].is_none() {
+                remapped[new] = Some(attributes[old].clone());
+            }
+        }
+
+        remapped
+            .into_iter()
+            .map(|attr| attr.expect("Every new element should be mapped from old one"))
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Remap;
+
+    #[test]
+    fn test_remap() {
+        let remap = Remap::new(vec![Some(0), None, Some(1), Some(0)]);
+
+        assert_eq!(remap.old_len(), 4);
+        assert_eq!(remap.new_len(), 2);
+        assert_eq!(remap.get(2), Some(1));
+        assert!(remap.is_removed(1));
+        assert!(remap.is_removed(10));
+        assert_eq!(remap.apply(&['a', 'b', 'c', 'd']), vec!['a', 'c']);
+    }
+
+    #[test]
+    fn test_identity_remap() {
+        let remap = Remap::identity(3);
+
+        assert_eq!(remap.iter().collect::<Vec<_>>(), vec![(0, 0), (1, 1), (2, 2)]);
+        assert_eq!(remap.apply(&[1, 2, 3]), vec![1, 2, 3]);
+    }
+}
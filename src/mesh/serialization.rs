@@ -0,0 +1,110 @@
+//!
+//! Serialization of meshes in indexed form: `{ "positions": [[x, y, z], ...], "indices": [[v1, v2, v3], ...] }`.
+//! Vertices and faces are stored in order of [Mesh::vertices] and [Mesh::faces] iterators.
+//!
+
+use std::collections::HashMap;
+
+use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+use super::{corner_table::table::CornerTable, polygon_soup::data_structure::PolygonSoup, traits::Mesh};
+use crate::{geometry::traits::RealNumber, helpers::aliases::Vec3};
+
+#[derive(Serialize, Deserialize)]
+struct IndexedMesh<TScalar> {
+    positions: Vec<[TScalar; 3]>,
+    indices: Vec<[usize; 3]>,
+}
+
+impl<TScalar: RealNumber> IndexedMesh<TScalar> {
+    fn from_mesh<TMesh: Mesh<ScalarType = TScalar>>(mesh: &TMesh) -> Self {
+        let vertex_index: HashMap<_, _> = mesh.vertices().enumerate().map(|(i, v)| (v, i)).collect();
+        let positions = mesh
+            .vertices()
+            .map(|v| {
+                let position = mesh.vertex_position(&v);
+                [position.x, position.y, position.z]
+            })
+            .collect();
+        let indices = mesh
+            .faces()
+            .map(|face| {
+                let (v1, v2, v3) = mesh.face_vertices(&face);
+                [vertex_index[&v1], vertex_index[&v2], vertex_index[&v3]]
+            })
+            .collect();
+
+        Self { positions, indices }
+    }
+
+    fn into_mesh<TMesh: Mesh<ScalarType = TScalar>, TError: Error>(self) -> Result<TMesh, TError> {
+        if let Some(index) = self.indices.iter().flatten().find(|index| **index >= self.positions.len()) {
+            return Err(TError::custom(format!(
+                "vertex index {} is out of range, mesh has {} vertices",
+                index,
+                self.positions.len()
+            )));
+        }
+
+        let positions: Vec<_> = self.positions.iter().map(|p| Vec3::new(p[0], p[1], p[2])).collect();
+        let indices = self.indices.concat();
+
+        Ok(TMesh::from_vertices_and_indices(&positions, &indices))
+    }
+}
+
+impl<TScalar: RealNumber + Serialize> Serialize for CornerTable<TScalar> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        IndexedMesh::from_mesh(self).serialize(serializer)
+    }
+}
+
+impl<'de, TScalar: RealNumber + Deserialize<'de>> Deserialize<'de> for CornerTable<TScalar> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        IndexedMesh::deserialize(deserializer)?.into_mesh()
+    }
+}
+
+impl<TScalar: RealNumber + Serialize> Serialize for PolygonSoup<TScalar> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        IndexedMesh::from_mesh(self).serialize(serializer)
+    }
+}
+
+impl<'de, TScalar: RealNumber + Deserialize<'de>> Deserialize<'de> for PolygonSoup<TScalar> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        IndexedMesh::deserialize(deserializer)?.into_mesh()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, polygon_soup::data_structure::PolygonSoup, primitives, traits::Mesh},
+    };
+
+    fn faces<TMesh: Mesh<ScalarType = f32>>(mesh: &TMesh) -> Vec<[Vec3f; 3]> {
+        mesh.faces()
+            .map(|face| {
+                let (v1, v2, v3) = mesh.face_vertices(&face);
+                [v1, v2, v3].map(|v| *mesh.vertex_position(&v))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_serialize_mesh() {
+        let cube: CornerTableF = primitives::cuboid(Vec3f::new(1.0, 2.0, 3.0), 2);
+        let json = serde_json::to_string(&cube).unwrap();
+        let restored: CornerTableF = serde_json::from_str(&json).unwrap();
+        assert_eq!(faces(&restored), faces(&cube));
+
+        let soup: PolygonSoup<f32> = serde_json::from_str(&json).unwrap();
+        assert_eq!(faces(&soup), faces(&cube));
+        assert!(serde_json::to_string(&soup).unwrap().len() > json.len());
+
+        let invalid = r#"{ "positions": [[0, 0, 0], [1, 0, 0], [0, 1, 0]], "indices": [[0, 1, 3]] }"#;
+        assert!(serde_json::from_str::<CornerTableF>(invalid).is_err());
+    }
+}
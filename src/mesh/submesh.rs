@@ -0,0 +1,366 @@
+use crate::{
+    geometry::traits::RealNumber,
+    mesh::{
+        corner_table::{prelude::EdgeId, table::CornerTable},
+        remap::{FaceRemap, VertexRemap},
+        traits::{Mesh, TopologicalMesh},
+    },
+};
+
+///
+/// Edge on boundary of [Submesh]
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SubmeshBoundaryEdge {
+    /// Edge of submesh
+    pub edge: EdgeId,
+    /// Same edge in parent mesh
+    pub parent_edge: EdgeId,
+    /// `true` when edge is on boundary of parent too, `false` when submesh is cut from neighboring faces along it
+    pub parent_boundary: bool,
+}
+
+///
+/// Part of mesh extracted by [extract_submesh] together with mapping of its elements to parent mesh
+///
+pub struct Submesh<TScalar: RealNumber> {
+    pub mesh: CornerTable<TScalar>,
+    /// Maps vertices of parent to vertices of submesh. Vertex duplicated in submesh (see [Submesh::parent_vertices]) is mapped to its first copy.
+    pub vertex_remap: VertexRemap,
+    /// Maps faces of parent to faces of submesh. Face index is index of its first corner divided by 3, see [CornerTable::compact].
+    pub face_remap: FaceRemap,
+    ///
+    /// Parent vertex of every vertex of submesh. Vertex of parent is duplicated when selected faces around it
+    /// form several fans (e.g. two faces touching by vertex only), so submesh stays manifold.
+    ///
+    pub parent_vertices: Vec<usize>,
+    /// Boundary edges of submesh
+    pub boundary: Vec<SubmeshBoundaryEdge>,
+}
+
+impl<TScalar: RealNumber> Submesh<TScalar> {
+    /// Returns boundary edges of submesh along which it was cut from parent, e.g. seam to be kept when part is reinserted
+    pub fn cut_edges(&self) -> impl Iterator<Item = &SubmeshBoundaryEdge> {
+        self.boundary.iter().filter(|edge| !edge.parent_boundary)
+    }
+}
+
+///
+/// Copies given faces (face descriptors) of mesh into new mesh. Deleted and repeated faces are skipped.
+/// Vertices and faces of submesh are ordered as in parent. Useful for region-based workflows:
+/// extract region, remesh or decimate it, and put it back with [stitch_submesh] using boundary edges mapped to parent.
+///
+/// ## Example
+/// ```ignore
+/// let submesh = extract_submesh(&mesh, selected_faces);
+/// let seam: Vec<_> = submesh.cut_edges().map(|edge| edge.edge).collect();
+/// let local = IncrementalRemesher::new().with_constrained_edges(seam.iter().map(|edge| edge.vertices()));
+/// ```
+///
+pub fn extract_submesh<TScalar: RealNumber>(mesh: &CornerTable<TScalar>, faces: impl IntoIterator<Item = usize>) -> Submesh<TScalar> {
+    let parent_faces_count = mesh.faces().map(|face| face / 3 + 1).max().unwrap_or(0);
+    let mut selected = vec![false; parent_faces_count];
+
+    for face in faces {
+        if let Some(selected) = selected.get_mut(face / 3) {
+            *selected = true;
+        }
+    }
+
+    // Deleted faces are not listed by face iterator
+    let mut parent_faces: Vec<_> = mesh.faces().filter(|face| selected[face / 3]).collect();
+    parent_faces.sort_unstable();
+
+    let parent_vertices_count = mesh.vertices().map(|vertex| vertex + 1).max().unwrap_or(0);
+    let mut vertex_map = vec![None; parent_vertices_count];
+    for face in &parent_faces {
+        let (v1, v2, v3) = mesh.face_vertices(face);
+        for vertex in [v1, v2, v3] {
+            vertex_map[vertex] = Some(0);
+        }
+    }
+
+    let mut parent_vertices = Vec::new();
+    for (vertex, new) in vertex_map.iter_mut().enumerate() {
+        if new.is_some() {
+            *new = Some(parent_vertices.len());
+            parent_vertices.push(vertex);
+        }
+    }
+
+    let positions: Vec<_> = parent_vertices.iter().map(|vertex| *mesh.vertex_position(vertex)).collect();
+    let indices = parent_faces.iter().map(|face| {
+        let (v1, v2, v3) = mesh.face_vertices(face);
+        [v1, v2, v3].map(|vertex| vertex_map[vertex].unwrap())
+    });
+
+    let (submesh, split) = CornerTable::from_faces_split_non_manifold(&positions, indices);
+
+    let mut face_map = vec![None; parent_faces_count];
+    let mut degenerate = split.degenerate_faces.iter().peekable();
+    let mut faces_count = 0;
+    for (index, face) in parent_faces.iter().enumerate() {
+        if degenerate.next_if_eq(&&index).is_none() {
+            face_map[face / 3] = Some(faces_count);
+            faces_count += 1;
+        }
+    }
+
+    let duplicates: Vec<_> = split.duplicated_vertices.iter().map(|vertex| parent_vertices[*vertex]).collect();
+    parent_vertices.extend(duplicates);
+
+    let mut boundary: Vec<_> = submesh
+        .edges()
+        .filter(|edge| submesh.is_edge_on_boundary(edge))
+        .map(|edge| {
+            let edge = submesh.edge_id(&edge);
+            let (v1, v2) = edge.vertices();
+            let parent_edge = EdgeId::new(parent_vertices[v1], parent_vertices[v2]);
+            let parent_boundary = mesh.edge_by_id(&parent_edge).is_none_or(|edge| mesh.is_edge_on_boundary(&edge));
+
+            SubmeshBoundaryEdge {
+                edge,
+                parent_edge,
+                parent_boundary,
+            }
+        })
+        .collect();
+    boundary.sort_unstable_by_key(|edge| edge.edge);
+
+    Submesh {
+        mesh: submesh,
+        vertex_remap: VertexRemap::new(vertex_map),
+        face_remap: FaceRemap::new(face_map),
+        parent_vertices,
+        boundary,
+    }
+}
+
+///
+/// Replaces faces of parent extracted into `submesh` by faces of `patch` (e.g. result of processing of submesh),
+/// patch is connected to parent along cut edges of submesh (see [Submesh::cut_edges]).
+/// Boundary vertices of patch are matched to vertices of cut by position: exactly when `tolerance` is zero,
+/// otherwise they are snapped to closest vertex of cut within `tolerance`. Vertices of cut are not moved.
+///
+/// Returns tables mapping old vertices and faces of parent to new ones, vertices and faces of patch are appended.
+/// Returns `None` and leaves parent unchanged when some edge of cut is not matched by boundary edge of patch
+/// (e.g. processing split or moved boundary) or when stitched mesh would be non-manifold.
+///
+/// ## Example
+/// ```ignore
+/// let mut submesh = extract_submesh(&mesh, selected_faces);
+/// let seam: Vec<_> = submesh.cut_edges().map(|edge| edge.edge.vertices()).collect();
+/// IncrementalRemesher::new().with_constrained_edges(seam).remesh(&mut submesh.mesh, 0.01);
+/// let (vertex_remap, face_remap) = stitch_submesh(&mut mesh, &submesh.mesh, &submesh, 0.0)?;
+/// ```
+///
+pub fn stitch_submesh<TScalar: RealNumber>(
+    parent: &mut CornerTable<TScalar>,
+    patch: &CornerTable<TScalar>,
+    submesh: &Submesh<TScalar>,
+    tolerance: TScalar,
+) -> Option<(VertexRemap, FaceRemap)> {
+    let mut cut_vertices: Vec<_> = submesh
+        .cut_edges()
+        .flat_map(|edge| {
+            let (v1, v2) = edge.parent_edge.vertices();
+            [v1, v2]
+        })
+        .collect();
+    cut_vertices.sort_unstable();
+    cut_vertices.dedup();
+
+    // Parent vertices of patch vertices, `None` for vertices added by patch
+    let tolerance_squared = tolerance * tolerance;
+    let patch_vertices_count = patch.vertices().map(|vertex| vertex + 1).max().unwrap_or(0);
+    let mut patch_to_parent = vec![None; patch_vertices_count];
+
+    for vertex in patch.vertices().filter(|vertex| patch.is_vertex_on_boundary(vertex)) {
+        let position = patch.vertex_position(&vertex);
+
+        patch_to_parent[vertex] = cut_vertices
+            .iter()
+            .map(|cut_vertex| (*cut_vertex, (parent.vertex_position(cut_vertex) - position).norm_squared()))
+            .filter(|(_, distance)| *distance <= tolerance_squared)
+            .min_by(|(_, d1), (_, d2)| d1.partial_cmp(d2).unwrap())
+            .map(|(cut_vertex, _)| cut_vertex);
+    }
+
+    let parent_faces_count = parent.faces().map(|face| face / 3 + 1).max().unwrap_or(0);
+    let kept_faces: Vec<_> = parent.faces().filter(|face| submesh.face_remap.is_removed(face / 3)).collect();
+
+    // Parent vertices used by kept faces or by patch keep their order, then vertices added by patch
+    let parent_vertices_count = parent.vertices().map(|vertex| vertex + 1).max().unwrap_or(0);
+    let mut vertex_map = vec![None; parent_vertices_count];
+    for face in &kept_faces {
+        let (v1, v2, v3) = parent.face_vertices(face);
+        for vertex in [v1, v2, v3] {
+            vertex_map[vertex] = Some(0);
+        }
+    }
+
+    for vertex in patch_to_parent.iter().flatten() {
+        vertex_map[*vertex] = Some(0);
+    }
+
+    let mut positions = Vec::new();
+    for (vertex, new) in vertex_map.iter_mut().enumerate() {
+        if new.is_some() {
+            *new = Some(positions.len());
+            positions.push(*parent.vertex_position(&vertex));
+        }
+    }
+
+    let mut patch_map = vec![0; patch_vertices_count];
+    for vertex in patch.vertices() {
+        patch_map[vertex] = match patch_to_parent[vertex] {
+            Some(parent_vertex) => vertex_map[parent_vertex].unwrap(),
+            None => {
+                positions.push(*patch.vertex_position(&vertex));
+                positions.len() - 1
+            }
+        };
+    }
+
+    let faces = kept_faces
+        .iter()
+        .map(|face| {
+            let (v1, v2, v3) = parent.face_vertices(face);
+            [v1, v2, v3].map(|vertex| vertex_map[vertex].unwrap())
+        })
+        .chain(patch.faces().map(|face| {
+            let (v1, v2, v3) = patch.face_vertices(&face);
+            [v1, v2, v3].map(|vertex| patch_map[vertex])
+        }));
+
+    let (stitched, split) = CornerTable::from_faces_split_non_manifold(&positions, faces);
+
+    if !split.is_empty() {
+        return None;
+    }
+
+    // Every edge of cut should become inner edge
+    let stitched_cut = submesh.cut_edges().all(|edge| {
+        let (v1, v2) = edge.parent_edge.vertices();
+        stitched
+            .edge_by_id(&EdgeId::new(vertex_map[v1].unwrap(), vertex_map[v2].unwrap()))
+            .is_some_and(|edge| !stitched.is_edge_on_boundary(&edge))
+    });
+
+    if !stitched_cut {
+        return None;
+    }
+
+    let mut face_map = vec![None; parent_faces_count];
+    for (new, face) in kept_faces.iter().enumerate() {
+        face_map[face / 3] = Some(new);
+    }
+
+    *parent = stitched;
+
+    Some((VertexRemap::new(vertex_map), FaceRemap::new(face_map)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{extract_submesh, stitch_submesh};
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{
+            corner_table::prelude::{CornerTableF, EdgeId},
+            primitives,
+            traits::{EditableMesh, Mesh, SplitFaceAtPoint, TopologicalMesh},
+        },
+    };
+
+    #[test]
+    fn test_extract_submesh() {
+        let mesh: CornerTableF = primitives::plane(1.0, 1.0, 4, 4);
+        let on_cut = |face: &usize| {
+            let (v1, v2, v3) = mesh.face_vertices(face);
+            [v1, v2, v3].iter().filter(|vertex| mesh.vertex_position(vertex).x.abs() < 1e-6).count()
+        };
+
+        // Faces of left half and face of right half touching them by vertex only
+        let selected: Vec<_> = mesh.faces().filter(|face| mesh.face_positions(face).center().x < 0.0).collect();
+        let touching = mesh
+            .faces()
+            .find(|face| mesh.face_positions(face).center().x > 0.0 && mesh.face_positions(face).center().y.abs() < 0.25 && on_cut(face) == 1)
+            .unwrap();
+
+        let submesh = extract_submesh(&mesh, selected.iter().copied().chain([touching, touching]));
+        assert_eq!(submesh.mesh.faces().count(), selected.len() + 1);
+        assert!(submesh.mesh.validate_topology().is_valid());
+
+        // Shared vertex is duplicated, so submesh stays manifold
+        assert_eq!(submesh.parent_vertices.len(), 15 + 2 + 1);
+
+        for (vertex, parent) in submesh.parent_vertices.iter().enumerate() {
+            assert_eq!(submesh.mesh.vertex_position(&vertex), mesh.vertex_position(parent));
+        }
+
+        for face in selected.iter().chain([&touching]) {
+            let sub_face = submesh.face_remap.get(face / 3).unwrap() * 3;
+            assert_eq!(submesh.mesh.face_positions(&sub_face).center(), mesh.face_positions(face).center());
+        }
+
+        // Left half is cut from parent along x = 0, separate face along all its edges
+        assert_eq!(submesh.cut_edges().count(), 4 + 3);
+        assert_eq!(submesh.boundary.len(), 4 + 3 + 8);
+
+        for edge in submesh.cut_edges() {
+            let (v1, v2) = edge.edge.vertices();
+            let (p1, p2) = (submesh.parent_vertices[v1], submesh.parent_vertices[v2]);
+            assert_eq!(edge.parent_edge, EdgeId::new(p1, p2));
+            assert!(mesh.edge_by_id(&edge.parent_edge).is_some());
+        }
+
+        let empty = extract_submesh(&mesh, std::iter::empty());
+        assert_eq!(empty.mesh.faces().count(), 0);
+        assert!(empty.vertex_remap.is_removed(0));
+    }
+
+    #[test]
+    fn test_stitch_submesh() {
+        let mut mesh: CornerTableF = primitives::plane(1.0, 1.0, 4, 4);
+        let boundary_count = |mesh: &CornerTableF| mesh.edges().filter(|edge| mesh.is_edge_on_boundary(edge)).count();
+        let faces_count = mesh.faces().count();
+        let boundary = boundary_count(&mesh);
+
+        let selected: Vec<_> = mesh.faces().filter(|face| mesh.face_positions(face).center().x < 0.0).collect();
+        let submesh = extract_submesh(&mesh, selected.iter().copied());
+
+        // Refine patch, its boundary is not changed
+        let mut patch = extract_submesh(&mesh, selected.iter().copied()).mesh;
+        let patch_faces: Vec<_> = patch.faces().collect();
+        for face in patch_faces {
+            let center = patch.face_positions(&face).center();
+            patch.split_face(&face, center);
+        }
+
+        // Seam vertex moved by processing is snapped back only within tolerance
+        let seam_vertex = submesh.cut_edges().next().unwrap().edge.vertices().0;
+        let seam_position = *patch.vertex_position(&seam_vertex);
+        patch.shift_vertex(&seam_vertex, &(seam_position + Vec3f::new(1e-5, 0.0, 0.0)));
+
+        assert!(stitch_submesh(&mut mesh, &patch, &submesh, 0.0).is_none());
+        assert_eq!(mesh.faces().count(), faces_count);
+
+        let (vertex_remap, face_remap) = stitch_submesh(&mut mesh, &patch, &submesh, 1e-4).unwrap();
+        assert!(mesh.validate_topology().is_valid());
+        assert_eq!(mesh.faces().count(), faces_count - selected.len() + selected.len() * 3);
+        assert_eq!(boundary_count(&mesh), boundary);
+
+        // Vertices used only by region are replaced by vertices of patch, seam vertices are kept
+        assert_eq!(vertex_remap.old_len() - vertex_remap.iter().count(), 15 - 5);
+        assert_eq!(face_remap.iter().count(), faces_count - selected.len());
+
+        for edge in submesh.cut_edges() {
+            let (v1, v2) = edge.parent_edge.vertices();
+            let stitched = EdgeId::new(vertex_remap.get(v1).unwrap(), vertex_remap.get(v2).unwrap());
+            let edge = mesh.edge_by_id(&stitched).unwrap();
+            assert!(!mesh.is_edge_on_boundary(&edge));
+            assert_eq!(mesh.edge_positions(&edge).0.x, 0.0);
+        }
+    }
+}
@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "io")]
+use crate::{data_structures::vertex_index_map::HashablePoint, helpers::aliases::Vec3};
+
+use super::traits::Mesh;
+
+///
+/// Vertex groups (weight maps) of mesh. Each group is named scalar map assigning weights to some vertices,
+/// e.g. influence of bone or mask painted in modeling tool. Vertices missing in group have no weight.
+///
+/// Groups are stored in PLY files as custom `float` vertex properties,
+/// see [PlyReader](crate::io::ply::PlyReader) and [PlyWriter](crate::io::ply::PlyWriter).
+///
+/// ## Example
+/// ```ignore
+/// let (mesh, groups) = PlyReader::new().read_ply_with_vertex_groups_from_file::<CornerTableF>(path)?;
+/// let head = groups.group_id("head").unwrap();
+/// let influence = groups.weight(head, &vertex).unwrap_or(0.0);
+/// ```
+///
+pub struct VertexGroups<TMesh: Mesh> {
+    weights: Vec<HashMap<TMesh::VertexDescriptor, f32>>,
+    names: Vec<String>,
+}
+
+impl<TMesh: Mesh> VertexGroups<TMesh> {
+    pub fn new() -> Self {
+        Self {
+            weights: Vec::new(),
+            names: Vec::new(),
+        }
+    }
+
+    ///
+    /// Assigns weights to vertices of `mesh` by matching their positions with given points.
+    /// `values` contains weights of every point for each group in `names`.
+    /// Used when mesh is built from indexed points and some of them may be skipped or reordered.
+    ///
+    #[cfg(feature = "io")]
+    pub(crate) fn from_points(mesh: &TMesh, points: &[Vec3<TMesh::ScalarType>], names: Vec<String>, values: Vec<Vec<f32>>) -> Self {
+        debug_assert_eq!(names.len(), values.len());
+
+        let point_index: HashMap<HashablePoint<3, TMesh::ScalarType>, usize> = points
+            .iter()
+            .enumerate()
+            .map(|(index, point)| ((*point).into(), index))
+            .collect();
+
+        let weights = values
+            .iter()
+            .map(|values| {
+                mesh.vertices()
+                    .filter_map(|vertex| {
+                        let index = point_index.get(&(*mesh.vertex_position(&vertex)).into())?;
+                        Some((vertex, values[*index]))
+                    })
+                    .collect()
+            })
+            .collect();
+
+        Self { weights, names }
+    }
+
+    /// Adds new empty group and returns its id
+    pub fn add_group(&mut self, name: String) -> usize {
+        self.names.push(name);
+        self.weights.push(HashMap::new());
+        self.names.len() - 1
+    }
+
+    /// Returns id of first group with given name
+    #[inline]
+    pub fn group_id(&self, name: &str) -> Option<usize> {
+        self.names.iter().position(|n| n == name)
+    }
+
+    #[inline]
+    pub fn set_weight(&mut self, group: usize, vertex: TMesh::VertexDescriptor, weight: f32) {
+        debug_assert!(group < self.names.len(), "Group {} does not exist", group);
+        self.weights[group].insert(vertex, weight);
+    }
+
+    #[inline]
+    pub fn weight(&self, group: usize, vertex: &TMesh::VertexDescriptor) -> Option<f32> {
+        self.weights.get(group)?.get(vertex).copied()
+    }
+
+    #[inline]
+    pub fn group_name(&self, group: usize) -> Option<&str> {
+        self.names.get(group).map(String::as_str)
+    }
+
+    #[inline]
+    pub fn groups_count(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Returns vertices of given group together with their weights
+    pub fn group_weights(&self, group: usize) -> impl Iterator<Item = (TMesh::VertexDescriptor, f32)> + '_ {
+        self.weights
+            .get(group)
+            .into_iter()
+            .flat_map(|weights| weights.iter().map(|(vertex, weight)| (*vertex, *weight)))
+    }
+}
+
+impl<TMesh: Mesh> Default for VertexGroups<TMesh> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,290 @@
+//!
+//! Uniform entry point to common mesh operations, described by plain data ([OpDesc]) instead of builders.
+//! Intended for tools and bindings driving the crate, e.g. `baby_shark-cli` binary (`cli` feature)
+//! which reads descriptions from JSON parameter files.
+//!
+
+use std::{
+    io::{self, Error, ErrorKind},
+    path::{Path, PathBuf},
+};
+
+use crate::{
+    algo::exact_boolean::{BooleanOperation, ExactBoolean},
+    decimation::{
+        edge_decimation::{AlwaysDecimate, ConstantErrorDecimationCriteria},
+        prelude::EdgeDecimator,
+    },
+    io::{obj::ObjWriter, read_from_file, stl::StlWriter, MeshFormat},
+    mesh::{
+        corner_table::prelude::CornerTableF,
+        traits::{EditableMesh, Mesh, MeshMarker, TopologicalMesh},
+    },
+    pipelines::{copy_mesh, PipelineReport},
+    remeshing::{incremental::IncrementalRemesher, voxel::VoxelRemesher},
+    voxel::offset::MeshOffset,
+};
+
+///
+/// Operation applied to mesh.
+///
+/// With `serde` feature it is deserialized from object tagged by `op` field, e.g.
+/// `{ "op": "decimate", "target_faces": 1000 }`.
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "op", rename_all = "snake_case"))]
+pub enum Op {
+    /// Edge collapse decimation until `max_error` is reached or mesh has `target_faces` faces, at least one of them is required
+    Decimate {
+        #[cfg_attr(feature = "serde", serde(default))]
+        max_error: Option<f32>,
+        #[cfg_attr(feature = "serde", serde(default))]
+        target_faces: Option<usize>,
+        #[cfg_attr(feature = "serde", serde(default))]
+        keep_boundary: bool,
+    },
+    /// Isotropic remeshing to given edge length, voxel remeshing with voxel size equal to edge length when `voxel` is set
+    Remesh {
+        edge_length: f32,
+        #[cfg_attr(feature = "serde", serde(default))]
+        iterations: Option<u16>,
+        #[cfg_attr(feature = "serde", serde(default))]
+        voxel: bool,
+    },
+    /// Offset of closed mesh, see [MeshOffset]
+    Offset {
+        distance: f32,
+        voxel_size: f32,
+        #[cfg_attr(feature = "serde", serde(default))]
+        separation: Option<f32>,
+    },
+    /// Exact boolean with mesh read from `other` file, see [ExactBoolean]
+    Boolean {
+        operation: BooleanOperation,
+        other: PathBuf,
+    },
+    /// Leaves mesh untouched, only validation is reported
+    Check,
+}
+
+impl Op {
+    /// Name of operation, same as `op` tag
+    pub fn name(&self) -> &'static str {
+        match self {
+            Op::Decimate { .. } => "decimate",
+            Op::Remesh { .. } => "remesh",
+            Op::Offset { .. } => "offset",
+            Op::Boolean { .. } => "boolean",
+            Op::Check => "check",
+        }
+    }
+}
+
+///
+/// Operation on mesh file: mesh is read from `input`, processed and written to `output`.
+/// Input format is detected by content, output format by extension (STL or OBJ).
+///
+/// ## Example
+/// ```ignore
+/// let report = ops::run(&OpDesc {
+///     input: "scan.stl".into(),
+///     output: Some("scan_decimated.stl".into()),
+///     op: Op::Decimate { max_error: None, target_faces: Some(10_000), keep_boundary: true },
+/// })?;
+/// ```
+///
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OpDesc {
+    pub input: PathBuf,
+    /// Result is not written when `None`
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub output: Option<PathBuf>,
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub op: Op,
+}
+
+///
+/// Runs operation on mesh file. Report lists faces count of input and result, and validation of result.
+///
+pub fn run(desc: &OpDesc) -> io::Result<PipelineReport> {
+    let mesh: CornerTableF = read_from_file(&desc.input)?;
+    let mut report = PipelineReport::default();
+    report.add_stage("input", &mesh);
+
+    let result = apply(&mesh, &desc.op)?;
+    report.add_stage(desc.op.name(), &result);
+    report.validate(&result);
+
+    if let Some(output) = &desc.output {
+        write_to_file(&result, output)?;
+    }
+
+    Ok(report)
+}
+
+///
+/// State of operations reused between [apply_with] calls, so buffers (e.g. decimation queue) are not reallocated for every mesh
+///
+pub(crate) struct Scratch<TMesh>
+where
+    TMesh: Mesh<ScalarType = f32> + EditableMesh + TopologicalMesh + MeshMarker,
+{
+    error_decimator: EdgeDecimator<TMesh, ConstantErrorDecimationCriteria<TMesh>>,
+    count_decimator: EdgeDecimator<TMesh, AlwaysDecimate>,
+}
+
+impl<TMesh> Default for Scratch<TMesh>
+where
+    TMesh: Mesh<ScalarType = f32> + EditableMesh + TopologicalMesh + MeshMarker,
+{
+    fn default() -> Self {
+        Self {
+            error_decimator: EdgeDecimator::new(),
+            count_decimator: EdgeDecimator::new(),
+        }
+    }
+}
+
+///
+/// Applies operation to mesh in memory. Fails when parameters are invalid or operation produced no mesh.
+///
+pub fn apply<TMesh>(mesh: &TMesh, op: &Op) -> io::Result<TMesh>
+where
+    TMesh: Mesh<ScalarType = f32> + EditableMesh + TopologicalMesh + MeshMarker,
+{
+    apply_with(mesh, op, &mut Scratch::default())
+}
+
+/// Same as [apply], but reuses state of previous operations
+pub(crate) fn apply_with<TMesh>(mesh: &TMesh, op: &Op, scratch: &mut Scratch<TMesh>) -> io::Result<TMesh>
+where
+    TMesh: Mesh<ScalarType = f32> + EditableMesh + TopologicalMesh + MeshMarker,
+{
+    let result = match op {
+        Op::Decimate { max_error, target_faces, keep_boundary } => {
+            let mut result = copy_mesh(mesh);
+
+            match (max_error, target_faces) {
+                (_, Some(0)) => return Err(invalid_input("`target_faces` should be positive")),
+                (Some(max_error), _) => {
+                    let mut decimator = std::mem::take(&mut scratch.error_decimator)
+                        .decimation_criteria(ConstantErrorDecimationCriteria::new(*max_error))
+                        .min_faces_count(*target_faces)
+                        .keep_boundary(*keep_boundary);
+                    decimator.decimate(&mut result);
+                    scratch.error_decimator = decimator;
+                }
+                (None, Some(_)) => {
+                    let mut decimator = std::mem::take(&mut scratch.count_decimator)
+                        .min_faces_count(*target_faces)
+                        .keep_boundary(*keep_boundary);
+                    decimator.decimate(&mut result);
+                    scratch.count_decimator = decimator;
+                }
+                (None, None) => return Err(invalid_input("decimate requires `max_error` or `target_faces`")),
+            }
+
+            Some(result)
+        }
+        Op::Remesh { edge_length, iterations, voxel } => {
+            if *edge_length <= 0.0 {
+                return Err(invalid_input("`edge_length` should be positive"));
+            }
+
+            if *voxel {
+                VoxelRemesher::default().with_voxel_size(*edge_length).remesh(mesh)
+            } else {
+                let mut result = copy_mesh(mesh);
+                let mut remesher = IncrementalRemesher::new();
+
+                if let Some(iterations) = iterations {
+                    remesher = remesher.with_iterations_count(*iterations);
+                }
+
+                remesher.remesh(&mut result, *edge_length);
+                Some(result)
+            }
+        }
+        Op::Offset { distance, voxel_size, separation } => {
+            if *voxel_size <= 0.0 {
+                return Err(invalid_input("`voxel_size` should be positive"));
+            }
+
+            MeshOffset::default()
+                .with_voxel_size(*voxel_size)
+                .with_separation(*separation)
+                .offset(mesh, *distance)
+        }
+        Op::Boolean { operation, other } => {
+            let other: TMesh = read_from_file(other)?;
+            Some(ExactBoolean::new().compute(mesh, &other, *operation))
+        }
+        Op::Check => Some(copy_mesh(mesh)),
+    };
+
+    result.ok_or_else(|| Error::other(format!("{} produced empty mesh", op.name())))
+}
+
+/// Writes mesh to STL or OBJ file depending on extension
+fn write_to_file<TMesh: Mesh>(mesh: &TMesh, path: &Path) -> io::Result<()> {
+    match MeshFormat::from_extension(path) {
+        Some(MeshFormat::BinaryStl) => StlWriter::new().write_stl_to_file(mesh, path),
+        Some(MeshFormat::Obj) => ObjWriter::new().write_obj_to_file([("mesh", mesh)], path),
+        _ => Err(invalid_input(&format!("Can't write mesh to {}, expected .stl or .obj file", path.display()))),
+    }
+}
+
+#[inline]
+fn invalid_input(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidInput, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply, Op};
+    use crate::{
+        algo::exact_boolean::BooleanOperation,
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+    };
+
+    #[test]
+    fn test_apply() {
+        let sphere: CornerTableF = primitives::uv_sphere(1.0, 32, 16);
+        let faces_count = sphere.faces().count();
+
+        let decimate = Op::Decimate { max_error: None, target_faces: Some(200), keep_boundary: false };
+        let decimated = apply(&sphere, &decimate).unwrap();
+        assert!(decimated.faces().count() <= 200);
+        assert_eq!(sphere.faces().count(), faces_count);
+
+        let invalid = Op::Decimate { max_error: None, target_faces: None, keep_boundary: false };
+        assert!(apply(&sphere, &invalid).is_err());
+
+        let invalid = Op::Decimate { max_error: Some(0.01), target_faces: Some(0), keep_boundary: false };
+        assert!(apply(&sphere, &invalid).is_err());
+
+        let check = apply(&sphere, &Op::Check).unwrap();
+        assert_eq!(check.faces().count(), faces_count);
+
+        let missing = Op::Boolean { operation: BooleanOperation::Union, other: "missing.stl".into() };
+        assert!(apply(&sphere, &missing).is_err());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_deserialize() {
+        use super::OpDesc;
+
+        let desc: OpDesc = serde_json::from_str(r#"{ "op": "offset", "input": "a.stl", "distance": 0.5, "voxel_size": 0.1 }"#).unwrap();
+        assert_eq!(desc.output, None);
+        assert_eq!(desc.op, Op::Offset { distance: 0.5, voxel_size: 0.1, separation: None });
+
+        let desc: OpDesc = serde_json::from_str(r#"{ "op": "boolean", "input": "a.stl", "operation": "difference", "other": "b.stl" }"#).unwrap();
+        assert_eq!(desc.op, Op::Boolean { operation: BooleanOperation::Difference, other: "b.stl".into() });
+
+        let json = serde_json::to_string(&desc).unwrap();
+        assert_eq!(serde_json::from_str::<OpDesc>(&json).unwrap(), desc);
+    }
+}
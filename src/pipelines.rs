@@ -0,0 +1,372 @@
+//!
+//! Curated processing pipelines chaining remeshing, cleanup, decimation and validation
+//! with sensible defaults. Each pipeline returns report describing what was done to the mesh.
+//!
+
+use std::{collections::HashMap, fmt::Display};
+
+use num_traits::{One, ToPrimitive};
+
+#[cfg(feature = "voxel")]
+use crate::{
+    algo::merge_points::merge_points,
+    decimation::edge_decimation::{merge_components, split_components},
+    voxel::{
+        mesh_to_volume::MeshToVolume,
+        meshing::{MarchingCubesMesher, Mesher},
+    },
+};
+use crate::{
+    algo::mass_properties::mass_properties,
+    decimation::{
+        edge_decimation::{AlwaysDecimate, ComponentBudget},
+        prelude::EdgeDecimator,
+    },
+    mesh::traits::{EditableMesh, Mesh, MeshMarker, TopologicalMesh},
+};
+
+/// Number of faces after pipeline stage
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct StageReport {
+    pub name: String,
+    pub faces_count: usize,
+}
+
+///
+/// Summary of pipeline run: faces count after each stage and validation of resulting mesh.
+///
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PipelineReport {
+    pub stages: Vec<StageReport>,
+    /// Number of small connected components removed
+    pub removed_components: usize,
+    /// Number of boundary (open) edges in result
+    pub boundary_edges: usize,
+    /// Euler characteristic (V - E + F) of result, 2 for each closed component of genus 0
+    pub euler_characteristic: isize,
+    /// Signed volume enclosed by result
+    pub volume: f64,
+}
+
+impl PipelineReport {
+    /// Returns `true` when resulting mesh has no holes
+    #[inline]
+    pub fn is_watertight(&self) -> bool {
+        self.boundary_edges == 0
+    }
+
+    /// Records faces count of `mesh` after stage `name`, shared with [crate::ops]
+    pub(crate) fn add_stage<TMesh: Mesh>(&mut self, name: &str, mesh: &TMesh) {
+        self.stages.push(StageReport {
+            name: name.to_string(),
+            faces_count: mesh.faces().count(),
+        });
+    }
+
+    /// Fills boundary edges, Euler characteristic and volume of resulting mesh, shared with [crate::ops]
+    pub(crate) fn validate<TMesh: TopologicalMesh>(&mut self, mesh: &TMesh) {
+        let vertices = mesh.vertices().count() as isize;
+        let edges = mesh.edges().count() as isize;
+        let faces = mesh.faces().count() as isize;
+
+        self.boundary_edges = mesh.edges().filter(|edge| mesh.is_edge_on_boundary(edge)).count();
+        self.euler_characteristic = vertices - edges + faces;
+        self.volume = mass_properties(mesh, TMesh::ScalarType::one()).volume.to_f64().unwrap_or(0.0);
+    }
+}
+
+impl Display for PipelineReport {
+    /// One line per stage followed by validation summary
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for stage in &self.stages {
+            writeln!(f, "{}: {} faces", stage.name, stage.faces_count)?;
+        }
+
+        write!(
+            f,
+            "removed components: {}, boundary edges: {}, euler characteristic: {}, volume: {}",
+            self.removed_components, self.boundary_edges, self.euler_characteristic, self.volume
+        )
+    }
+}
+
+///
+/// Options of [print_ready] pipeline
+///
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct PrintOptions {
+    /// Size of voxel used for remeshing, average edge length of result before decimation
+    pub voxel_size: f32,
+    /// Surface is offset outwards by half of this value, so walls and features are at least that thick.
+    /// Zero disables thickening.
+    pub min_thickness: f32,
+    /// Decimate result down to this number of faces. `None` disables decimation.
+    pub target_faces: Option<usize>,
+    /// Connected components having smaller share of faces are removed
+    pub min_component_share: f32,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        Self {
+            voxel_size: 0.1,
+            min_thickness: 0.0,
+            target_faces: None,
+            min_component_share: 0.01,
+        }
+    }
+}
+
+///
+/// Prepares mesh for 3D printing:
+/// 1. Voxel remeshing, which closes small holes, resolves self-intersections and produces manifold surface.
+///    Surface is thickened according to [PrintOptions::min_thickness].
+/// 2. Removal of small floating components (debris).
+/// 3. Topology preserving decimation to [PrintOptions::target_faces].
+/// 4. Validation: open edges, Euler characteristic and volume.
+///
+/// Returns `None` when mesh can't be voxelized or nothing is left after remeshing.
+///
+/// ## Example
+/// ```ignore
+/// let options = PrintOptions { voxel_size: 0.2, min_thickness: 0.8, target_faces: Some(50_000), ..Default::default() };
+/// let (printable, report) = print_ready(&scan, options).unwrap();
+/// assert!(report.is_watertight());
+/// ```
+///
+#[cfg(feature = "voxel")]
+pub fn print_ready<TMesh>(mesh: &TMesh, options: PrintOptions) -> Option<(TMesh, PipelineReport)>
+where
+    TMesh: Mesh<ScalarType = f32> + EditableMesh + TopologicalMesh + MeshMarker,
+{
+    let mut report = PipelineReport::default();
+    report.add_stage("input", mesh);
+
+    // Narrow band should contain offset surface
+    let shell = options.min_thickness.max(0.0) * 0.5;
+    let band_width = (shell / options.voxel_size).ceil() as isize + 1;
+
+    let mut mesh_to_volume = MeshToVolume::default()
+        .with_voxel_size(options.voxel_size)
+        .with_narrow_band_width(band_width);
+    let volume = mesh_to_volume.convert(mesh)?;
+
+    let mut mesher = MarchingCubesMesher::default()
+        .with_voxel_size(options.voxel_size)
+        .with_iso_value(shell);
+    let faces = mesher.mesh_triangles(&volume);
+
+    if faces.is_empty() {
+        return None;
+    }
+
+    let indexed_faces = merge_points(&faces);
+    let mut result = TMesh::from_vertices_and_indices(&indexed_faces.points, &indexed_faces.indices);
+    report.add_stage("voxel remesh", &result);
+
+    let components = split_components(&result, ComponentBudget::FacesCount);
+    let components_count = components.len();
+    let kept: Vec<_> = components
+        .into_iter()
+        .filter(|component| component.share >= options.min_component_share)
+        .collect();
+
+    report.removed_components = components_count - kept.len();
+    result = merge_components(&kept);
+    report.add_stage("remove small components", &result);
+
+    if let Some(target_faces) = options.target_faces.filter(|target| *target > 0) {
+        let mut decimator = EdgeDecimator::<TMesh, AlwaysDecimate>::new()
+            .min_faces_count(Some(target_faces))
+            .preserve_topology(true);
+        decimator.decimate(&mut result);
+        report.add_stage("decimate", &result);
+    }
+
+    report.validate(&result);
+
+    Some((result, report))
+}
+
+///
+/// Options of [game_lod] pipeline
+///
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(default))]
+pub struct LodOptions {
+    /// Number of generated levels of detail, not counting original mesh
+    pub levels: usize,
+    /// Ratio of faces count of each level to previous one
+    pub reduction: f64,
+    /// Keep open boundaries (e.g. seams between parts) intact
+    pub keep_boundary: bool,
+}
+
+impl Default for LodOptions {
+    fn default() -> Self {
+        Self {
+            levels: 3,
+            reduction: 0.5,
+            keep_boundary: true,
+        }
+    }
+}
+
+///
+/// Generates chain of levels of detail for real-time rendering. Each level is decimated from previous one
+/// down to [LodOptions::reduction] of its faces, connected components are decimated independently
+/// so small parts don't disappear. Levels with the same faces count as previous one are not emitted.
+///
+/// Returns levels ordered from finest to coarsest (original mesh is not included).
+/// Report lists faces count of each level and validation of the coarsest one.
+///
+/// ## Example
+/// ```ignore
+/// let (lods, report) = game_lod(&character, LodOptions::default());
+/// ```
+///
+pub fn game_lod<TMesh>(mesh: &TMesh, options: LodOptions) -> (Vec<TMesh>, PipelineReport)
+where
+    TMesh: Mesh + EditableMesh + TopologicalMesh + MeshMarker,
+{
+    let mut report = PipelineReport::default();
+    report.add_stage("input", mesh);
+
+    let mut levels: Vec<TMesh> = Vec::with_capacity(options.levels);
+    let mut faces_count = mesh.faces().count();
+
+    for level in 0..options.levels {
+        let target = (faces_count as f64 * options.reduction).round() as usize;
+
+        if target == 0 || target >= faces_count {
+            break;
+        }
+
+        let mut decimated = copy_mesh(levels.last().unwrap_or(mesh));
+        let mut decimator = EdgeDecimator::<TMesh, AlwaysDecimate>::new()
+            .min_faces_count(Some(target))
+            .keep_boundary(options.keep_boundary)
+            .component_budget(Some(ComponentBudget::FacesCount));
+        decimator.decimate(&mut decimated);
+
+        let decimated_count = decimated.faces().count();
+
+        if decimated_count >= faces_count {
+            break;
+        }
+
+        faces_count = decimated_count;
+        report.add_stage(&format!("lod {}", level + 1), &decimated);
+        levels.push(decimated);
+    }
+
+    report.validate(levels.last().unwrap_or(mesh));
+
+    (levels, report)
+}
+
+/// Returns copy of mesh without deleted elements
+pub(crate) fn copy_mesh<TMesh: Mesh>(mesh: &TMesh) -> TMesh {
+    let mut vertices = Vec::new();
+    let mut vertex_index = HashMap::new();
+    let mut indices = Vec::new();
+
+    for face in mesh.faces() {
+        let (v1, v2, v3) = mesh.face_vertices(&face);
+
+        for vertex in [v1, v2, v3] {
+            let index = *vertex_index.entry(vertex).or_insert_with(|| {
+                vertices.push(*mesh.vertex_position(&vertex));
+                vertices.len() - 1
+            });
+            indices.push(index);
+        }
+    }
+
+    TMesh::from_vertices_and_indices(&vertices, &indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{game_lod, LodOptions};
+    use crate::mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh};
+
+    #[cfg(feature = "voxel")]
+    #[test]
+    fn test_print_ready() {
+        use super::{print_ready, PrintOptions};
+        use crate::{helpers::aliases::Vec3f, mesh::traits::TopologicalMesh};
+
+        // Open box with small floating part
+        let mut part: CornerTableF = primitives::cuboid(Vec3f::new(1.0, 1.0, 1.0), 4);
+        let top = part
+            .faces()
+            .find(|face| part.face_positions(face).center().z > 0.49)
+            .unwrap();
+        part.remove_face(top).unwrap();
+
+        let debris: CornerTableF = primitives::cuboid(Vec3f::new(0.1, 0.1, 0.1), 1);
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+
+        for mesh in [&part, &debris] {
+            let offset = vertices.len();
+            let shift = if vertices.is_empty() { Vec3f::zeros() } else { Vec3f::new(2.0, 0.0, 0.0) };
+            let mesh_vertices: Vec<_> = mesh.vertices().collect();
+
+            for vertex in &mesh_vertices {
+                vertices.push(mesh.vertex_position(vertex) + shift);
+            }
+
+            for face in mesh.faces() {
+                let (v1, v2, v3) = mesh.face_vertices(&face);
+                for v in [v1, v2, v3] {
+                    indices.push(offset + mesh_vertices.iter().position(|x| *x == v).unwrap());
+                }
+            }
+        }
+
+        let mesh = CornerTableF::from_vertices_and_indices(&vertices, &indices);
+        assert!(mesh.edges().any(|edge| mesh.is_edge_on_boundary(&edge)));
+
+        let options = PrintOptions {
+            voxel_size: 0.05,
+            min_thickness: 0.1,
+            target_faces: Some(2000),
+            min_component_share: 0.05,
+        };
+        let (result, report) = print_ready(&mesh, options).unwrap();
+
+        assert_eq!(report.removed_components, 1);
+        assert!(report.is_watertight());
+        assert_eq!(report.euler_characteristic, 2);
+        assert!(report.volume > 1.0);
+        assert_eq!(report.stages.len(), 4);
+        assert!(result.faces().count() <= 2000);
+    }
+
+    #[test]
+    fn test_game_lod() {
+        let sphere: CornerTableF = primitives::uv_sphere(1.0, 32, 16);
+        let faces_count = sphere.faces().count();
+        let (levels, report) = game_lod(&sphere, LodOptions::default());
+
+        assert_eq!(levels.len(), 3);
+        assert_eq!(report.stages.len(), 4);
+
+        let mut previous = faces_count;
+        for level in &levels {
+            let count = level.faces().count();
+            assert!(count < previous);
+            assert!(count * 3 > previous);
+            previous = count;
+        }
+
+        assert!(report.is_watertight());
+    }
+}
@@ -0,0 +1,32 @@
+//!
+//! Commonly used types and traits. Items re-exported here are part of stable public API,
+//! so `use baby_shark::prelude::*;` is enough for typical mesh processing code.
+//!
+
+pub use crate::decimation::{
+    edge_decimation::{
+        AlwaysDecimate, BoundingSphereDecimationCriteria, ComponentBudget, ConstantErrorDecimationCriteria,
+        EdgeDecimationCriteria, HausdorffDistanceDecimationCriteria, NeverDecimate,
+    },
+    prelude::EdgeDecimator,
+};
+pub use crate::mesh::{
+    convert::convert_mesh,
+    corner_table::prelude::{CornerTableD, CornerTableF},
+    polygon_soup::data_structure::PolygonSoup,
+    traits::{EditableMesh, Mesh, TopologicalMesh},
+};
+pub use crate::remeshing::incremental::IncrementalRemesher;
+
+#[cfg(feature = "io")]
+pub use crate::io::{
+    obj::{ObjReader, ObjWriter},
+    ply::{PlyReader, PlyWriter},
+    read_from_file,
+    stl::{StlReader, StlWriter},
+};
+
+#[cfg(feature = "voxel")]
+pub use crate::remeshing::voxel::{MeshingMethod, VoxelRemesher};
+#[cfg(feature = "voxel")]
+pub use crate::voxel::prelude::*;
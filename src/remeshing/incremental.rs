@@ -1,10 +1,16 @@
-use std::marker::PhantomData;
+use std::{collections::HashSet, hash::Hash, marker::PhantomData, time::{Duration, Instant}};
 use num_traits::{cast, Float};
 use crate::{
-    mesh::traits::{TopologicalMesh, EditableMesh, Position, mesh_stats }, 
+    mesh::{
+        corner_table::table::CornerTable,
+        remap::{FaceRemap, VertexRemap},
+        traits::{TopologicalMesh, EditableMesh, Position, mesh_stats },
+    },
     algo::{utils::tangential_relaxation, edge_collapse, vertex_shift},
     spatial_partitioning::grid::Grid, 
-    geometry::primitives::triangle3::Triangle3
+    geometry::primitives::triangle3::Triangle3,
+    helpers::{aliases::Vec3, trace::{trace_counters, trace_span}, utils::quantize},
+    geometry::traits::RealNumber,
 };
 
 ///
@@ -14,6 +20,10 @@ use crate::{
 /// All the vertices of the remeshed patch are reprojected to 
 /// the original surface to keep a good approximation of the input.
 /// 
+/// First iteration processes whole mesh, next ones revisit only neighborhoods of vertices changed by previous iteration,
+/// so cost of iteration is proportional to part of mesh that is not converged yet.
+/// Remeshing stops early when iteration changes nothing.
+/// 
 /// ## Example
 /// ```ignore
 /// let remesher = IncrementalRemesher::new()
@@ -34,6 +44,14 @@ pub struct IncrementalRemesher<TMesh: TopologicalMesh + EditableMesh> {
     project_vertices: bool,
     iterations: u16,
     keep_boundary: bool,
+    min_edge_length: Option<TMesh::ScalarType>,
+    max_edge_length: Option<TMesh::ScalarType>,
+    max_valence: Option<usize>,
+    feature_angle: Option<TMesh::ScalarType>,
+    time_budget: Option<Duration>,
+    deterministic: bool,
+    pinned_vertices: HashSet<TMesh::VertexDescriptor>,
+    constrained_edges: HashSet<(TMesh::VertexDescriptor, TMesh::VertexDescriptor)>,
 
     mesh_type: PhantomData<TMesh>
 }
@@ -92,6 +110,94 @@ impl<TMesh: TopologicalMesh + EditableMesh> IncrementalRemesher<TMesh> {
         self
     }
 
+    ///
+    /// Set length below which edges are collapsed. `None` (default) uses `4/5` of target edge length.
+    /// Useful for meshes with details of different scale, where fixed ratio to target length collapses small features.
+    ///
+    #[inline]
+    pub fn with_min_edge_length(mut self, length: Option<TMesh::ScalarType>) -> Self {
+        self.min_edge_length = length;
+        self
+    }
+
+    ///
+    /// Set length above which edges are split. `None` (default) uses `4/3` of target edge length.
+    /// Collapses creating edges longer than this are rejected.
+    ///
+    #[inline]
+    pub fn with_max_edge_length(mut self, length: Option<TMesh::ScalarType>) -> Self {
+        self.max_edge_length = length;
+        self
+    }
+
+    /// Set max valence of vertex, collapses and flips exceeding it are rejected. `None` (default) disables the check.
+    #[inline]
+    pub fn with_max_valence(mut self, valence: Option<usize>) -> Self {
+        self.max_valence = valence;
+        self
+    }
+
+    ///
+    /// Set min dihedral angle (in radians) of feature edges. Feature edges are not flipped and their vertices
+    /// are not moved by collapses or smoothing, so sharp creases survive remeshing. `None` (default) disables features.
+    ///
+    #[inline]
+    pub fn with_feature_angle(mut self, angle: Option<TMesh::ScalarType>) -> Self {
+        self.feature_angle = angle;
+        self
+    }
+
+    ///
+    /// Set max time of remeshing. Iterations are stopped when budget is exceeded, iteration in progress is finished,
+    /// so at least one iteration is done. `None` (default) disables the limit.
+    ///
+    #[inline]
+    pub fn with_time_budget(mut self, budget: Option<Duration>) -> Self {
+        self.time_budget = budget;
+        self
+    }
+
+    ///
+    /// Set whether face qualities and normal angles deciding edge flips are rounded (see [quantize]) before they are compared,
+    /// so values differing by rounding errors tie and same input gives the same mesh on every platform (e.g. x86_64 and wasm32).
+    /// Time budget stops remeshing after different number of iterations on different machines, so it should be disabled too. Default is `false`.
+    ///
+    #[inline]
+    pub fn with_deterministic(mut self, deterministic: bool) -> Self {
+        self.deterministic = deterministic;
+        self
+    }
+
+    ///
+    /// Set vertices that are not moved or removed during remeshing.
+    /// Useful for remeshing only part of a model, e.g. vertices on border of selected region.
+    /// Note that edge split may assign new descriptor to existing vertex (depends on mesh implementation),
+    /// so positions of pinned vertices are preserved but their descriptors may change.
+    ///
+    #[inline]
+    pub fn with_pinned_vertices<TIter: IntoIterator<Item = TMesh::VertexDescriptor>>(mut self, vertices: TIter) -> Self {
+        self.pinned_vertices.extend(vertices);
+        self
+    }
+
+    ///
+    /// Set edges (given by pairs of vertices) that are not split, collapsed or flipped during remeshing.
+    /// Vertices of constrained edges are pinned, so seams between remeshed and untouched regions stay matched.
+    ///
+    #[inline]
+    pub fn with_constrained_edges<TIter>(mut self, edges: TIter) -> Self
+    where
+        TIter: IntoIterator<Item = (TMesh::VertexDescriptor, TMesh::VertexDescriptor)>
+    {
+        for (v1, v2) in edges {
+            self.pinned_vertices.insert(v1);
+            self.pinned_vertices.insert(v2);
+            self.constrained_edges.insert((v1.min(v2), v1.max(v2)));
+        }
+
+        self
+    }
+
     ///
     /// Remesh given `mesh`
     /// ## Arguments
@@ -99,56 +205,140 @@ impl<TMesh: TopologicalMesh + EditableMesh> IncrementalRemesher<TMesh> {
     /// * `target_edge_length` - desired length of edge
     /// 
     pub fn remesh(&self, mesh: &mut TMesh, target_edge_length: TMesh::ScalarType) {
-        let max_edge_length = cast::<f64, TMesh::ScalarType>(4.0 / 3.0).unwrap() * target_edge_length;
-        let min_edge_length = cast::<f64, TMesh::ScalarType>(4.0 / 5.0).unwrap() * target_edge_length;
+        let max_edge_length = self
+            .max_edge_length
+            .unwrap_or(cast::<f64, TMesh::ScalarType>(4.0 / 3.0).unwrap() * target_edge_length);
+        let min_edge_length = self
+            .min_edge_length
+            .unwrap_or(cast::<f64, TMesh::ScalarType>(4.0 / 5.0).unwrap() * target_edge_length);
         
         let mut reference_mesh = Grid::empty();
         if self.project_vertices {
             reference_mesh = Grid::from_mesh(mesh);
         }
 
-        for _ in 0..self.iterations {
-            if self.split_edges {
-                self.split_edges(mesh, max_edge_length);
-            }
-
-            if self.collapse_edges {
-                self.collapse_edges(mesh, min_edge_length);
+        // Descriptors of pinned vertices are tracked across edge splits
+        let mut constraints = Constraints {
+            pinned_vertices: self.pinned_vertices.clone(),
+            constrained_edges: self.constrained_edges.clone(),
+        };
+
+        // Vertices moved by less than this don't activate their neighborhood
+        let min_shift = target_edge_length * cast(MIN_SHIFT_RATIO).unwrap();
+        let mut region = ActiveRegion {
+            current: mesh.vertices().collect(),
+            changed: HashSet::new(),
+            min_shift_squared: min_shift * min_shift,
+        };
+        let mut features = self.feature_vertices(mesh);
+        let started_at = Instant::now();
+
+        trace_span!("incremental_remesh", iterations = self.iterations);
+
+        for iteration in 0..self.iterations {
+            // Nothing changed by previous iteration
+            if region.current.is_empty() {
+                break;
             }
 
-            if self.flip_edges {
-                self.flip_edges(mesh);
-            }
+            trace_span!("remesh_iteration", iteration = iteration, active_vertices = region.current.len());
+
+            let splits_count = if self.split_edges {
+                self.split_edges(mesh, max_edge_length, &mut constraints, &mut region)
+            } else {
+                0
+            };
+            self.update_features(mesh, &mut region, &mut features);
+
+            let collapses_count = if self.collapse_edges {
+                self.collapse_edges(mesh, min_edge_length, max_edge_length, &constraints, &features, &mut region)
+            } else {
+                0
+            };
+            self.update_features(mesh, &mut region, &mut features);
+
+            let flips_count = if self.flip_edges {
+                self.flip_edges(mesh, &constraints, &mut region)
+            } else {
+                0
+            };
+            self.update_features(mesh, &mut region, &mut features);
 
             if self.shift_vertices {
-                self.shift_vertices(mesh, target_edge_length * target_edge_length);
+                self.shift_vertices(mesh, target_edge_length * target_edge_length, &constraints, &features, &mut region);
             }
 
             if self.project_vertices {
-                self.project_vertices(mesh, &reference_mesh, target_edge_length);
+                self.project_vertices(mesh, &reference_mesh, target_edge_length, &constraints, &mut region);
+            }
+
+            self.update_features(mesh, &mut region, &mut features);
+            region.next_iteration();
+
+            trace_counters!(
+                splits_done = splits_count,
+                collapses_done = collapses_count,
+                flips_done = flips_count,
+                faces_count = mesh.faces().count(),
+            );
+
+            if self.time_budget.is_some_and(|budget| started_at.elapsed() >= budget) {
+                break;
             }
         }
     }
 
-    fn split_edges(&self, mesh: &mut TMesh, max_edge_length: TMesh::ScalarType) {
-        // Cache all edges, in the case when split edge affects edges iterator
-        let edges: Vec<TMesh::EdgeDescriptor> = mesh.edges().collect();
+    /// Returns number of split edges
+    fn split_edges(
+        &self,
+        mesh: &mut TMesh,
+        max_edge_length: TMesh::ScalarType,
+        constraints: &mut Constraints<TMesh::VertexDescriptor>,
+        region: &mut ActiveRegion<TMesh>
+    ) -> usize {
+        // Cache edges, in the case when split edge affects edges iterator
+        let edges = region.edges(mesh);
         let max_edge_length_squared = max_edge_length * max_edge_length;
+        let mut splits_count = 0;
 
         for edge in edges {
+            if constraints.is_edge_constrained(mesh, &edge) {
+                continue;
+            }
+
             let edge_length_squared = mesh.edge_length_squared(&edge);
 
             // Split long edges at the middle
             if edge_length_squared > max_edge_length_squared {
-                let (v1, v2) = mesh.edge_positions(&edge);
-                let split_at = v1 + (v2 - v1).scale(cast(0.5).unwrap());
+                let (v1, v2) = mesh.edge_vertices(&edge);
+                let v1_pos = *mesh.vertex_position(&v1);
+                let v2_pos = *mesh.vertex_position(&v2);
+                let split_at = v1_pos + (v2_pos - v1_pos).scale(cast(0.5).unwrap());
                 mesh.split_edge(&edge, &split_at);
+                splits_count += 1;
+
+                // Split may move existing vertex and give its old position to new one
+                constraints.track_vertex(mesh, &v1, &v1_pos);
+                constraints.track_vertex(mesh, &v2, &v2_pos);
+
+                // One-rings of edge vertices contain new vertex and both moved by split
+                region.touch(mesh, &v1);
+                region.touch(mesh, &v2);
             }
         }
+
+        splits_count
     }
 
-    fn shift_vertices(&self, mesh: &mut TMesh, target_edge_length_squared: TMesh::ScalarType) {
-        let vertices: Vec<TMesh::VertexDescriptor> = mesh.vertices().collect();
+    fn shift_vertices(
+        &self,
+        mesh: &mut TMesh,
+        target_edge_length_squared: TMesh::ScalarType,
+        constraints: &Constraints<TMesh::VertexDescriptor>,
+        feature_vertices: &HashSet<TMesh::VertexDescriptor>,
+        region: &mut ActiveRegion<TMesh>
+    ) {
+        let vertices = region.vertices();
         let mut one_ring = Vec::with_capacity(mesh_stats::MAX_VERTEX_VALENCE);
 
         // Perform laplacian smoothing for each vertex
@@ -165,18 +355,30 @@ impl<TMesh: TopologicalMesh + EditableMesh> IncrementalRemesher<TMesh> {
             let new_position = tangential_relaxation(one_ring.iter(), vertex_position, &vertex_normal.unwrap()); 
 
             let shift_vertex = 
-                !(self.keep_boundary && mesh.is_vertex_on_boundary(&vertex)) &&
+                !self.is_vertex_pinned(mesh, &vertex, constraints) &&
+                !feature_vertices.contains(&vertex) &&
                 vertex_shift::is_vertex_shift_safe(&vertex, vertex_position, &new_position, target_edge_length_squared,  mesh);
 
             if shift_vertex {
-                mesh.shift_vertex(&vertex, &new_position); 
+                region.shift(mesh, &vertex, &new_position);
             }
         }
     }
 
-    fn collapse_edges(&self, mesh: &mut TMesh, min_edge_length: TMesh::ScalarType) {
-        let edges: Vec<TMesh::EdgeDescriptor> = mesh.edges().collect();
+    /// Returns number of collapsed edges
+    fn collapse_edges(
+        &self,
+        mesh: &mut TMesh,
+        min_edge_length: TMesh::ScalarType,
+        max_edge_length: TMesh::ScalarType,
+        constraints: &Constraints<TMesh::VertexDescriptor>,
+        feature_vertices: &HashSet<TMesh::VertexDescriptor>,
+        region: &mut ActiveRegion<TMesh>
+    ) -> usize {
+        let edges = region.edges(mesh);
+        let mut collapses_count = 0;
         let min_edge_length_squared = min_edge_length * min_edge_length;
+        let max_edge_length_squared = max_edge_length * max_edge_length;
 
         // Collapse long edges
         for edge in edges {
@@ -184,9 +386,14 @@ impl<TMesh: TopologicalMesh + EditableMesh> IncrementalRemesher<TMesh> {
                 continue;
             }
 
-            // Keep boundary
+            // Keep boundary and pinned vertices
             let (v1, v2) = mesh.edge_vertices(&edge);
-            if self.keep_boundary && (mesh.is_vertex_on_boundary(&v1) || mesh.is_vertex_on_boundary(&v2)) {
+            if self.is_vertex_pinned(mesh, &v1, constraints) || self.is_vertex_pinned(mesh, &v2, constraints) {
+                continue;
+            }
+
+            // Moving vertex of feature edge would cut the feature
+            if feature_vertices.contains(&v1) || feature_vertices.contains(&v2) {
                 continue;
             }
 
@@ -199,32 +406,82 @@ impl<TMesh: TopologicalMesh + EditableMesh> IncrementalRemesher<TMesh> {
             let v2_pos = mesh.vertex_position(&v2);
             let collapse_at = (v1_pos + v2_pos) * cast::<f32, TMesh::ScalarType>(0.5).unwrap();
 
+            // Collapse should not create edges that would be split again
+            let mut creates_long_edge = false;
+            for vertex in [v1, v2] {
+                mesh.vertices_around_vertex(&vertex, |v| {
+                    creates_long_edge |= (mesh.vertex_position(v) - collapse_at).norm_squared() > max_edge_length_squared;
+                });
+            }
+
+            if creates_long_edge {
+                continue;
+            }
+
+            if let Some(max_valence) = self.max_valence {
+                // Collapsed vertex is connected to neighbors of both vertices, except the shared ones
+                let shared = if mesh.is_edge_on_boundary(&edge) { 3 } else { 4 };
+                let valence = self.valence(mesh, &v1) + self.valence(mesh, &v2) - shared;
+
+                if valence > max_valence as isize {
+                    continue;
+                }
+            }
+
             if edge_collapse::is_safe(mesh, &edge, &collapse_at, cast(0.5).unwrap()) {
-                mesh.collapse_edge(&edge, &collapse_at);
+                region.collapse(mesh, &edge, &collapse_at);
+                collapses_count += 1;
             }
         }
+
+        collapses_count
     }
 
-    fn flip_edges(&self, mesh: &mut TMesh) {
-        let edges: Vec<TMesh::EdgeDescriptor> = mesh.edges().collect();
+    /// Returns number of flipped edges
+    fn flip_edges(&self, mesh: &mut TMesh, constraints: &Constraints<TMesh::VertexDescriptor>, region: &mut ActiveRegion<TMesh>) -> usize {
+        let edges = region.edges(mesh);
+        let mut flips_count = 0;
 
         // Flip edges to improve valence
         for edge in edges {
+            if constraints.is_edge_constrained(mesh, &edge) || self.is_feature_edge(mesh, &edge) {
+                continue;
+            }
+
             if self.is_flip_safe(mesh, &edge) && self.will_flip_improve_quality(mesh, &edge) {
+                let (v1, v2) = mesh.edge_vertices(&edge);
                 mesh.flip_edge(&edge);
+                flips_count += 1;
+
+                // Vertices of flipped edge stay adjacent to both vertices of new edge
+                region.touch(mesh, &v1);
+                region.touch(mesh, &v2);
             }
         }
+
+        flips_count
     }
 
-    fn project_vertices(&self, mesh: &mut TMesh, grid: &Grid<Triangle3<TMesh::ScalarType>>, target_edge_length: TMesh::ScalarType) {
-        let vertices: Vec<TMesh::VertexDescriptor> = mesh.vertices().collect();
+    fn project_vertices(
+        &self,
+        mesh: &mut TMesh,
+        grid: &Grid<Triangle3<TMesh::ScalarType>>,
+        target_edge_length: TMesh::ScalarType,
+        constraints: &Constraints<TMesh::VertexDescriptor>,
+        region: &mut ActiveRegion<TMesh>
+    ) {
+        let vertices = region.vertices();
 
         // Project vertices back on original mesh
         for vertex in vertices {
+            if constraints.pinned_vertices.contains(&vertex) {
+                continue;
+            }
+
             let vertex_position = mesh.vertex_position(&vertex);
             
             if let Some(closest_point) = grid.closest_point(vertex_position, target_edge_length) {
-                mesh.shift_vertex(&vertex, &closest_point);
+                region.shift(mesh, &vertex, &closest_point);
             }
         }
     }
@@ -238,10 +495,22 @@ impl<TMesh: TopologicalMesh + EditableMesh> IncrementalRemesher<TMesh> {
         // Check normals after flip (geometrical safety)
         let mut pos = TMesh::Position::from_edge(mesh, edge);
         
-        let v1 = mesh.vertex_position(&pos.get_vertex());
-        let v2 = mesh.vertex_position(&pos.next().get_vertex());
-        let v0 = mesh.vertex_position(&pos.next().get_vertex());
-        let v3 = mesh.vertex_position(&pos.next().opposite().get_vertex());
+        let v1 = pos.get_vertex();
+        let v2 = pos.next().get_vertex();
+        let v0 = pos.next().get_vertex();
+        let v3 = pos.next().opposite().get_vertex();
+
+        // Flipped edge connects `v1` and `v3`
+        if self.max_valence.is_some_and(|max| {
+            self.valence(mesh, &v1) >= max as isize || self.valence(mesh, &v3) >= max as isize
+        }) {
+            return false;
+        }
+
+        let v1 = mesh.vertex_position(&v1);
+        let v2 = mesh.vertex_position(&v2);
+        let v0 = mesh.vertex_position(&v0);
+        let v3 = mesh.vertex_position(&v3);
 
         if Triangle3::is_degenerate(v1, v2, v3) ||
            Triangle3::is_degenerate(v0, v1, v3) {
@@ -250,18 +519,18 @@ impl<TMesh: TopologicalMesh + EditableMesh> IncrementalRemesher<TMesh> {
 
         let old_normal1 = Triangle3::normal(v0, v1, v2);
         let new_normal1 = Triangle3::normal(v1, v2, v3);
-        let threshold = cast::<f64, TMesh::ScalarType>(5.0).unwrap().to_radians();
+        let threshold = self.round(cast::<f64, TMesh::ScalarType>(5.0).unwrap().to_radians());
 
-        if old_normal1.angle(&new_normal1) > threshold {
+        if self.round(old_normal1.angle(&new_normal1)) > threshold {
             return false;
         }
 
         let old_normal2 = Triangle3::normal(v0, v2, v3);
         let new_normal2 = Triangle3::normal(v0, v1, v3);
 
-        if old_normal2.angle(&new_normal2) > threshold || 
-           old_normal2.angle(&new_normal1) > threshold || 
-           old_normal1.angle(&new_normal2) > threshold 
+        if self.round(old_normal2.angle(&new_normal2)) > threshold || 
+           self.round(old_normal2.angle(&new_normal1)) > threshold || 
+           self.round(old_normal1.angle(&new_normal2)) > threshold 
         {
             return false;
         }
@@ -304,14 +573,81 @@ impl<TMesh: TopologicalMesh + EditableMesh> IncrementalRemesher<TMesh> {
         let v2_pos = mesh.vertex_position(&v2);
         let v3_pos = mesh.vertex_position(&v3);
 
-        let old_face_quality = Triangle3::quality(v0_pos, v1_pos, v2_pos).min(Triangle3::quality(v0_pos, v2_pos, v3_pos));
-        let new_face_quality = Triangle3::quality(v1_pos, v2_pos, v3_pos).min(Triangle3::quality(v0_pos, v1_pos, v3_pos));
+        let old_face_quality = self.round(Triangle3::quality(v0_pos, v1_pos, v2_pos).min(Triangle3::quality(v0_pos, v2_pos, v3_pos)));
+        let new_face_quality = self.round(Triangle3::quality(v1_pos, v2_pos, v3_pos).min(Triangle3::quality(v0_pos, v1_pos, v3_pos)));
 
         (new_deviation < old_deviation && new_face_quality >= old_face_quality * cast(0.5).unwrap()) ||
                (new_deviation == old_deviation && new_face_quality > old_face_quality) || // Same valence but better quality
                (new_face_quality > old_face_quality * cast(1.5).unwrap())// Hurt valence but improve quality by much
     }
 
+    /// Rounds value compared by flip checks in deterministic mode
+    #[inline]
+    fn round(&self, value: TMesh::ScalarType) -> TMesh::ScalarType {
+        if self.deterministic {
+            quantize(value)
+        } else {
+            value
+        }
+    }
+
+    /// Returns `true` when dihedral angle of edge exceeds feature angle
+    fn is_feature_edge(&self, mesh: &TMesh, edge: &TMesh::EdgeDescriptor) -> bool {
+        let Some(feature_angle) = self.feature_angle else {
+            return false;
+        };
+
+        let (face1, Some(face2)) = mesh.edge_faces(edge) else {
+            return false;
+        };
+
+        match (mesh.face_positions(&face1).try_get_normal(), mesh.face_positions(&face2).try_get_normal()) {
+            (Some(n1), Some(n2)) => n1.angle(&n2) > feature_angle,
+            _ => false,
+        }
+    }
+
+    /// Returns vertices of feature edges
+    fn feature_vertices(&self, mesh: &TMesh) -> HashSet<TMesh::VertexDescriptor> {
+        if self.feature_angle.is_none() {
+            return HashSet::new();
+        }
+
+        mesh.edges()
+            .filter(|edge| self.is_feature_edge(mesh, edge))
+            .flat_map(|edge| {
+                let (v1, v2) = mesh.edge_vertices(&edge);
+                [v1, v2]
+            })
+            .collect()
+    }
+
+    /// Updates feature vertices around vertices changed by last pass and adds changed vertices to active region
+    fn update_features(&self, mesh: &TMesh, region: &mut ActiveRegion<TMesh>, features: &mut HashSet<TMesh::VertexDescriptor>) {
+        if self.feature_angle.is_some() {
+            for vertex in &region.changed {
+                features.remove(vertex);
+            }
+
+            for vertex in &region.changed {
+                mesh.edges_around_vertex(vertex, |edge| {
+                    if self.is_feature_edge(mesh, edge) {
+                        let (v1, v2) = mesh.edge_vertices(edge);
+                        features.insert(v1);
+                        features.insert(v2);
+                    }
+                });
+            }
+        }
+
+        region.current.extend(region.changed.iter().copied());
+    }
+
+    #[inline]
+    fn is_vertex_pinned(&self, mesh: &TMesh, vertex: &TMesh::VertexDescriptor, constraints: &Constraints<TMesh::VertexDescriptor>) -> bool {
+        constraints.pinned_vertices.contains(vertex) || (self.keep_boundary && mesh.is_vertex_on_boundary(vertex))
+    }
+
     #[inline]
     fn valence(&self, mesh: &TMesh, vertex: &TMesh::VertexDescriptor) -> isize {
         let mut valence = 0;
@@ -330,6 +666,25 @@ impl<TMesh: TopologicalMesh + EditableMesh> IncrementalRemesher<TMesh> {
     }
 }
 
+impl<TScalar: RealNumber> IncrementalRemesher<CornerTable<TScalar>> {
+    ///
+    /// Same as [Self::remesh], but also removes vertices and faces deleted by remeshing from storage (see [CornerTable::compact]).
+    /// Returns tables mapping indices of remeshed mesh before compaction to new ones.
+    /// Vertices and faces created by remeshing are appended to storage, so their old indices are past length of input mesh.
+    /// Note that edge split may give new index to one end of split edge, use pinned vertices to track input vertices.
+    ///
+    /// ## Example
+    /// ```ignore
+    /// let (vertex_remap, face_remap) = remesher.remesh_compact(&mut mesh, 0.01);
+    /// let colors = vertex_remap.apply(&colors);
+    /// ```
+    ///
+    pub fn remesh_compact(&self, mesh: &mut CornerTable<TScalar>, target_edge_length: TScalar) -> (VertexRemap, FaceRemap) {
+        self.remesh(mesh, target_edge_length);
+        mesh.compact()
+    }
+}
+
 impl<TMesh: TopologicalMesh + EditableMesh> Default for IncrementalRemesher<TMesh> {
     fn default() -> Self {
         Self {
@@ -340,7 +695,302 @@ impl<TMesh: TopologicalMesh + EditableMesh> Default for IncrementalRemesher<TMes
             project_vertices: true,
             iterations: 10,
             keep_boundary: true,
+            min_edge_length: None,
+            max_edge_length: None,
+            max_valence: None,
+            feature_angle: None,
+            time_budget: None,
+            deterministic: false,
+            pinned_vertices: HashSet::new(),
+            constrained_edges: HashSet::new(),
             mesh_type: PhantomData
         }
     }
 }
+
+/// Shift (relative to target edge length) below which vertex is treated as converged
+const MIN_SHIFT_RATIO: f64 = 0.01;
+
+///
+/// Vertices processed by remeshing passes. Operations mark vertices around changed part of mesh,
+/// they are processed by remaining passes of current iteration and by next iteration.
+///
+struct ActiveRegion<TMesh: TopologicalMesh + EditableMesh> {
+    /// Vertices processed by current iteration
+    current: HashSet<TMesh::VertexDescriptor>,
+    /// Vertices changed by current iteration
+    changed: HashSet<TMesh::VertexDescriptor>,
+    min_shift_squared: TMesh::ScalarType,
+}
+
+impl<TMesh: TopologicalMesh + EditableMesh> ActiveRegion<TMesh> {
+    /// Returns edges around processed vertices, sorted to keep remeshing deterministic
+    fn edges(&self, mesh: &TMesh) -> Vec<TMesh::EdgeDescriptor> {
+        let mut edges = HashSet::new();
+        for vertex in &self.current {
+            mesh.edges_around_vertex(vertex, |edge| {
+                edges.insert(*edge);
+            });
+        }
+
+        let mut edges: Vec<_> = edges.into_iter().collect();
+        edges.sort_unstable();
+        edges
+    }
+
+    /// Returns processed vertices, sorted to keep remeshing deterministic
+    fn vertices(&self) -> Vec<TMesh::VertexDescriptor> {
+        let mut vertices: Vec<_> = self.current.iter().copied().collect();
+        vertices.sort_unstable();
+        vertices
+    }
+
+    /// Marks vertex and its one-ring changed
+    fn touch(&mut self, mesh: &TMesh, vertex: &TMesh::VertexDescriptor) {
+        self.changed.insert(*vertex);
+        mesh.vertices_around_vertex(vertex, |v| {
+            self.changed.insert(*v);
+        });
+    }
+
+    /// Moves vertex, small shifts don't mark it changed
+    fn shift(&mut self, mesh: &mut TMesh, vertex: &TMesh::VertexDescriptor, to: &Vec3<TMesh::ScalarType>) {
+        if (mesh.vertex_position(vertex) - to).norm_squared() > self.min_shift_squared {
+            self.touch(mesh, vertex);
+        }
+
+        mesh.shift_vertex(vertex, to);
+    }
+
+    /// Collapses edge and forgets removed vertex
+    fn collapse(&mut self, mesh: &mut TMesh, edge: &TMesh::EdgeDescriptor, at: &Vec3<TMesh::ScalarType>) {
+        let (v1, v2) = mesh.edge_vertices(edge);
+
+        let mut neighbor = None;
+        mesh.vertices_around_vertex(&v1, |v| {
+            if *v != v2 {
+                neighbor = Some(*v);
+            }
+        });
+
+        mesh.collapse_edge(edge, at);
+
+        // Neighbor of removed vertex is connected to kept one after collapse
+        let mut kept = v1;
+        if let Some(neighbor) = neighbor {
+            mesh.vertices_around_vertex(&neighbor, |v| {
+                if *v == v2 {
+                    kept = v2;
+                }
+            });
+        }
+
+        let removed = if kept == v1 { v2 } else { v1 };
+        self.current.remove(&removed);
+        self.changed.remove(&removed);
+        self.touch(mesh, &kept);
+    }
+
+    /// Vertices changed by current iteration are processed by next one
+    fn next_iteration(&mut self) {
+        self.current = std::mem::take(&mut self.changed);
+    }
+}
+
+/// Pinned vertices and constrained edges of single remeshing run
+struct Constraints<TVertex> {
+    pinned_vertices: HashSet<TVertex>,
+    constrained_edges: HashSet<(TVertex, TVertex)>,
+}
+
+impl<TVertex: Copy + Ord + Hash> Constraints<TVertex> {
+    #[inline]
+    fn is_edge_constrained<TMesh>(&self, mesh: &TMesh, edge: &TMesh::EdgeDescriptor) -> bool
+    where
+        TMesh: TopologicalMesh<VertexDescriptor = TVertex>
+    {
+        if self.constrained_edges.is_empty() {
+            return false;
+        }
+
+        let (v1, v2) = mesh.edge_vertices(edge);
+        self.constrained_edges.contains(&(v1.min(v2), v1.max(v2)))
+    }
+
+    ///
+    /// Updates descriptor of pinned `vertex` if it was moved away from `position` by edge split.
+    /// Vertex that took its place is one of neighbors of `vertex`.
+    ///
+    fn track_vertex<TMesh>(&mut self, mesh: &TMesh, vertex: &TVertex, position: &Vec3<TMesh::ScalarType>)
+    where
+        TMesh: TopologicalMesh<VertexDescriptor = TVertex>
+    {
+        if !self.pinned_vertices.contains(vertex) || mesh.vertex_position(vertex) == position {
+            return;
+        }
+
+        let mut moved_to = None;
+        mesh.vertices_around_vertex(vertex, |v| {
+            if mesh.vertex_position(v) == position {
+                moved_to = Some(*v);
+            }
+        });
+
+        let Some(moved_to) = moved_to else {
+            return;
+        };
+
+        self.pinned_vertices.remove(vertex);
+        self.pinned_vertices.insert(moved_to);
+
+        let edges: Vec<_> = self.constrained_edges
+            .iter()
+            .filter(|(v1, v2)| v1 == vertex || v2 == vertex)
+            .copied()
+            .collect();
+
+        for (v1, v2) in edges {
+            self.constrained_edges.remove(&(v1, v2));
+
+            let v1 = if v1 == *vertex { moved_to } else { v1 };
+            let v2 = if v2 == *vertex { moved_to } else { v2 };
+            self.constrained_edges.insert((v1.min(v2), v1.max(v2)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashMap, time::Duration};
+
+    use super::IncrementalRemesher;
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{
+            corner_table::prelude::CornerTableF,
+            primitives,
+            traits::{Mesh, TopologicalMesh},
+        },
+    };
+
+    #[test]
+    fn test_pinned_vertices_and_constrained_edges() {
+        let mut mesh: CornerTableF = primitives::plane(4.0, 4.0, 8, 8);
+
+        // Pin left half of plane, constrain edges on its border
+        let pinned: Vec<_> = mesh.vertices()
+            .filter(|v| mesh.vertex_position(v).x < 1e-6)
+            .map(|v| (v, *mesh.vertex_position(&v)))
+            .collect();
+        let constrained: Vec<_> = mesh.edges()
+            .map(|e| mesh.edge_vertices(&e))
+            .filter(|(v1, v2)| mesh.vertex_position(v1).x.abs() < 1e-6 && mesh.vertex_position(v2).x.abs() < 1e-6)
+            .collect();
+        assert_eq!(constrained.len(), 8);
+
+        let initial_positions: HashMap<_, _> = mesh.vertices().map(|v| (v, *mesh.vertex_position(&v))).collect();
+
+        let remesher = IncrementalRemesher::new()
+            .with_pinned_vertices(pinned.iter().map(|(v, _)| *v))
+            .with_constrained_edges(constrained.iter().copied());
+        remesher.remesh(&mut mesh, 0.2);
+
+        // Descriptors may change on edge split, so pinned vertices are found by position
+        let find_vertex = |position: &Vec3f| mesh.vertices().find(|v| mesh.vertex_position(v) == position);
+
+        for (_, position) in &pinned {
+            assert!(find_vertex(position).is_some());
+        }
+
+        for (v1, v2) in &constrained {
+            let v1 = find_vertex(&initial_positions[v1]).unwrap();
+            let v2 = find_vertex(&initial_positions[v2]).unwrap();
+
+            let mut connected = false;
+            mesh.vertices_around_vertex(&v1, |v| connected |= *v == v2);
+            assert!(connected);
+        }
+    }
+
+    #[test]
+    fn test_remesh_compact() {
+        let remesher = IncrementalRemesher::new().with_deterministic(true);
+        let mut mesh: CornerTableF = primitives::plane(4.0, 4.0, 8, 8);
+        remesher.remesh(&mut mesh, 0.6);
+
+        let positions: Vec<_> = (0..)
+            .map_while(|vertex| mesh.get_vertex(vertex).map(|v| *v.get_position()))
+            .collect();
+        let mut compacted: CornerTableF = primitives::plane(4.0, 4.0, 8, 8);
+        let (vertex_remap, face_remap) = remesher.remesh_compact(&mut compacted, 0.6);
+
+        assert_eq!(vertex_remap.old_len(), positions.len());
+        assert_eq!(vertex_remap.new_len(), compacted.vertices().count());
+        assert_eq!(face_remap.new_len(), compacted.faces().count());
+
+        for (old, new) in vertex_remap.iter() {
+            assert_eq!(positions[old], *compacted.vertex_position(&new));
+        }
+    }
+
+    #[test]
+    fn test_quality_constraints() {
+        let remesh_cube = |remesher: IncrementalRemesher<CornerTableF>| {
+            let mut cube: CornerTableF = primitives::cuboid(Vec3f::new(1.0, 1.0, 1.0), 4);
+            remesher.remesh(&mut cube, 0.1);
+            cube
+        };
+        let corners = |mesh: &CornerTableF| {
+            mesh.vertices()
+                .filter(|v| mesh.vertex_position(v).iter().all(|c| (c.abs() - 0.5).abs() < 1e-6))
+                .count()
+        };
+        let valences = |mesh: &CornerTableF| {
+            mesh.vertices()
+                .map(|v| {
+                    let mut valence = 0;
+                    mesh.vertices_around_vertex(&v, |_| valence += 1);
+                    valence
+                })
+                .collect::<Vec<_>>()
+        };
+
+        // Corners are smoothed out without features
+        let smoothed = remesh_cube(IncrementalRemesher::new());
+        assert_eq!(corners(&smoothed), 0);
+
+        let preserved = remesh_cube(
+            IncrementalRemesher::new()
+                .with_feature_angle(Some(30.0f32.to_radians()))
+                .with_max_edge_length(Some(0.15)),
+        );
+        assert_eq!(corners(&preserved), 8);
+        assert!(preserved.edges().all(|edge| preserved.edge_length(&edge) < 0.15));
+
+        let constrained = remesh_cube(IncrementalRemesher::new().with_max_valence(Some(7)));
+        assert!(valences(&smoothed).iter().any(|valence| *valence > 7));
+        assert!(valences(&constrained).iter().all(|valence| *valence <= 7));
+    }
+
+    #[test]
+    fn test_time_budget() {
+        let positions = |mesh: &CornerTableF| mesh.vertices().map(|v| *mesh.vertex_position(&v)).collect::<Vec<_>>();
+        let remesh = |remesher: IncrementalRemesher<CornerTableF>| {
+            let mut sphere: CornerTableF = primitives::uv_sphere(1.0, 32, 16);
+            remesher.remesh(&mut sphere, 0.1);
+            sphere
+        };
+
+        // Exceeded budget stops remeshing after first iteration
+        let budgeted = remesh(IncrementalRemesher::new().with_iterations_count(20).with_time_budget(Some(Duration::ZERO)));
+        let single = remesh(IncrementalRemesher::new().with_iterations_count(1));
+        assert_eq!(positions(&budgeted), positions(&single));
+
+        // Later iterations process only changed regions, but reach same quality
+        let remeshed = remesh(IncrementalRemesher::new().with_iterations_count(20));
+        let lengths: Vec<_> = remeshed.edges().map(|edge| remeshed.edge_length(&edge)).collect();
+        let mean = lengths.iter().sum::<f32>() / lengths.len() as f32;
+        assert!((mean - 0.1).abs() < 0.02);
+        assert!(lengths.iter().all(|length| *length < 0.1 * 4.0 / 3.0 + 1e-4));
+    }
+}
@@ -1,2 +1,3 @@
 pub mod incremental;
+#[cfg(feature = "voxel")]
 pub mod voxel;
@@ -1,14 +1,58 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+#[cfg(feature = "io")]
+use std::{io, path::Path};
+
+#[cfg(feature = "io")]
+use crate::io::stl::StlReader;
 use crate::{
-    algo::merge_points::merge_points,
-    mesh::traits::Mesh,
-    voxel::{mesh_to_volume::MeshToVolume, meshing::{DualContouringMesher, MarchingCubesMesher}},
+    algo::{manifold::{make_manifold, ManifoldReport}, merge_points::merge_points},
+    geometry::{primitives::{box3::Box3, triangle3::Triangle3}, traits::HasBBox3},
+    helpers::aliases::{Vec3f, Vec3i},
+    mesh::{convert::convert_mesh, polygon_soup::data_structure::PolygonSoup, traits::Mesh},
+    voxel::{
+        mesh_to_volume::MeshToVolume,
+        meshing::{AdaptiveMarchingCubesMesher, DualContouringMesher, MarchingCubesMesher, Mesher},
+        prelude::Volume,
+    },
 };
 
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
 pub enum MeshingMethod {
     /// Feature preserving meshing, which tries to preserve sharp features but may produce non-manifold/self-intersecting meshes.
     FeaturePreserving,
     /// Meshing which provides strong guarantees about topology (no self-intersections, no non-manifold edges/vertices) of the output mesh, but may smooth sharp features.
     Manifold,
+    /// Same as [MeshingMethod::Manifold], but flat regions are meshed by larger triangles
+    Adaptive,
+}
+
+/// Part of space where [VoxelRemesher::remesh_region] rebuilds mesh
+#[derive(Debug, Clone, Copy)]
+pub enum RegionOfInterest {
+    Box(Box3<f32>),
+    Sphere { center: Vec3f, radius: f32 },
+}
+
+impl RegionOfInterest {
+    #[inline]
+    pub fn contains(&self, point: &Vec3f) -> bool {
+        match self {
+            RegionOfInterest::Box(bbox) => bbox.contains_point(point),
+            RegionOfInterest::Sphere { center, radius } => (point - center).norm_squared() <= radius * radius,
+        }
+    }
+
+    #[inline]
+    pub fn bbox(&self) -> Box3<f32> {
+        match self {
+            RegionOfInterest::Box(bbox) => *bbox,
+            RegionOfInterest::Sphere { center, radius } => {
+                Box3::new(center.add_scalar(-radius), center.add_scalar(*radius))
+            }
+        }
+    }
 }
 
 ///
@@ -42,8 +86,14 @@ pub struct VoxelRemesher {
     mesh_to_sdf: MeshToVolume,
     meshing_method: MeshingMethod,
     voxel_size: f32,
+    gpu: bool,
+    manifold_post_pass: bool,
+    manifold_report: Option<ManifoldReport>,
 }
 
+/// Points of meshed volume closer than this fraction of voxel size are welded by manifold post-pass
+const WELD_TOLERANCE: f32 = 1e-3;
+
 impl VoxelRemesher {
     #[inline]
     pub fn with_voxel_size(mut self, size: f32) -> Self {
@@ -58,25 +108,160 @@ impl VoxelRemesher {
         self
     }
 
+    ///
+    /// Set whether mesh to volume conversion and marching cubes should run on GPU.
+    /// Has effect only when `gpu` feature is enabled, falls back to CPU when no GPU is available. Default is `false`.
+    ///
+    #[inline]
+    pub fn with_gpu(mut self, gpu: bool) -> Self {
+        self.mesh_to_sdf.set_gpu(gpu);
+        self.gpu = gpu;
+        self
+    }
+
+    ///
+    /// Set whether output of [remesh](VoxelRemesher::remesh) should be fixed by [make_manifold] post-pass.
+    /// Post-pass welds nearly coincident vertices, removes degenerate and duplicate faces and splits non-manifold edges,
+    /// so output can be passed to decimation directly. Its diagnostics are returned by [manifold_report](VoxelRemesher::manifold_report).
+    /// Default is `false`.
+    ///
+    #[inline]
+    pub fn with_manifold_post_pass(mut self, post_pass: bool) -> Self {
+        self.manifold_post_pass = post_pass;
+        self
+    }
+
+    /// Returns diagnostics of manifold post-pass done by last [remesh](VoxelRemesher::remesh)
+    #[inline]
+    pub fn manifold_report(&self) -> Option<&ManifoldReport> {
+        self.manifold_report.as_ref()
+    }
+
     pub fn remesh<T: Mesh<ScalarType = f32>>(&mut self, mesh: &T) -> Option<T> {
+        self.manifold_report = None;
+
         let distance_field = self.mesh_to_sdf.convert(mesh)?;
+        let faces = mesh_volume(&distance_field, self.meshing_method, self.voxel_size, self.gpu)?;
 
-        let faces = match self.meshing_method {
-            MeshingMethod::FeaturePreserving => {
-                let mut dc = DualContouringMesher::default().with_voxel_size(self.voxel_size);
-                dc.mesh(&distance_field)?
-            }
-            MeshingMethod::Manifold => {
-                let mut mc = MarchingCubesMesher::default().with_voxel_size(self.voxel_size);
-                mc.mesh(&distance_field)
-            }
-        };
+        if self.manifold_post_pass {
+            let (mesh, report) = make_manifold(&faces, self.voxel_size * WELD_TOLERANCE);
+            self.manifold_report = Some(report);
+
+            return Some(convert_mesh(&mesh));
+        }
 
         let indexed_faces = merge_points(&faces);
         let mesh = T::from_vertices_and_indices(&indexed_faces.points, &indexed_faces.indices);
 
         Some(mesh)
     }
+
+    ///
+    /// Voxel remeshes only part of the mesh inside given region, the rest of the mesh is left untouched.
+    /// Faces with center inside region are replaced by voxel remeshed patch, then the gap between
+    /// patch and untouched part is closed by strip of triangles connecting their boundary loops.
+    ///
+    /// Returns `None` when no face lies inside region or remeshing failed.
+    ///
+    pub fn remesh_region<T: Mesh<ScalarType = f32>>(&mut self, mesh: &T, region: &RegionOfInterest) -> Option<T> {
+        let mut vertex_map = HashMap::new();
+        let mut positions = Vec::new();
+
+        for vertex in mesh.vertices() {
+            vertex_map.insert(vertex, positions.len());
+            positions.push(*mesh.vertex_position(&vertex));
+        }
+
+        let mut kept = Vec::new();
+        let mut removed = Vec::new();
+
+        for face in mesh.faces() {
+            let (v1, v2, v3) = mesh.face_vertices(&face);
+            let triangle = [vertex_map[&v1], vertex_map[&v2], vertex_map[&v3]];
+            let center = (positions[triangle[0]] + positions[triangle[1]] + positions[triangle[2]]) / 3.0;
+
+            if region.contains(&center) {
+                removed.extend_from_slice(&triangle);
+            } else {
+                kept.extend_from_slice(&triangle);
+            }
+        }
+
+        if removed.is_empty() {
+            return None;
+        }
+
+        // Voxelize neighborhood of region, so surface of patch is not cut by volume bounds
+        let margin = Vec3f::repeat(4.0 * self.voxel_size);
+        let region_bbox = region.bbox();
+        let neighborhood = Box3::new(region_bbox.get_min() - margin, region_bbox.get_max() + margin);
+
+        let mut triangles = Vec::new();
+        for face in mesh.faces() {
+            let triangle = mesh.face_positions(&face);
+
+            if triangle.bbox().intersects_box3(&neighborhood) {
+                triangles.extend_from_slice(&[*triangle.p1(), *triangle.p2(), *triangle.p3()]);
+            }
+        }
+
+        let volume = self.mesh_to_sdf.convert(&PolygonSoup::from(triangles))?;
+        let patch_faces: Vec<_> = mesh_volume(&volume, self.meshing_method, self.voxel_size, self.gpu)?
+            .chunks_exact(3)
+            .filter(|face| region.contains(&((face[0] + face[1] + face[2]) / 3.0)))
+            .flatten()
+            .copied()
+            .collect();
+
+        if patch_faces.is_empty() {
+            return None;
+        }
+
+        let patch = merge_points(&patch_faces);
+
+        // Compact vertices of untouched part, patch vertices are appended after them
+        let mut old_to_new = vec![usize::MAX; positions.len()];
+        let mut vertices = Vec::new();
+
+        for &vertex in &kept {
+            if old_to_new[vertex] == usize::MAX {
+                old_to_new[vertex] = vertices.len();
+                vertices.push(positions[vertex]);
+            }
+        }
+
+        let mut indices: Vec<_> = kept.iter().map(|&v| old_to_new[v]).collect();
+        let patch_offset = vertices.len();
+        vertices.extend_from_slice(&patch.points);
+        indices.extend(patch.indices.iter().map(|&v| v + patch_offset));
+
+        // Stitch only holes left by removed faces, boundaries of input mesh stay open
+        let removed_edges = directed_edges(&removed);
+        let old_loops = boundary_loops(&kept, |from, to| removed_edges.contains(&(to, from)))
+            .into_iter()
+            .map(|l| l.into_iter().map(|v| old_to_new[v]).collect::<Vec<_>>());
+        let mut new_loops: Vec<_> = boundary_loops(&patch.indices, |_, _| true)
+            .into_iter()
+            .map(|l| l.into_iter().map(|v| v + patch_offset).collect::<Vec<_>>())
+            .collect();
+
+        for old_loop in old_loops {
+            let old_center = loop_center(&old_loop, &vertices);
+            let closest = new_loops
+                .iter()
+                .enumerate()
+                .map(|(i, l)| (i, (loop_center(l, &vertices) - old_center).norm_squared()))
+                .min_by(|a, b| a.1.total_cmp(&b.1))
+                .map(|(i, _)| i);
+
+            if let Some(closest) = closest {
+                let new_loop = new_loops.swap_remove(closest);
+                stitch_loops(&old_loop, &new_loop, &vertices, &mut indices);
+            }
+        }
+
+        Some(T::from_vertices_and_indices(&vertices, &indices))
+    }
 }
 
 impl Default for VoxelRemesher {
@@ -85,16 +270,309 @@ impl Default for VoxelRemesher {
             mesh_to_sdf: MeshToVolume::default().with_narrow_band_width(0),
             voxel_size: 1.0,
             meshing_method: MeshingMethod::Manifold,
+            gpu: false,
+            manifold_post_pass: false,
+            manifold_report: None,
+        }
+    }
+}
+
+fn mesh_volume(volume: &Volume, method: MeshingMethod, voxel_size: f32, gpu: bool) -> Option<Vec<Vec3f>> {
+    match method {
+        MeshingMethod::FeaturePreserving => {
+            let mut dc = DualContouringMesher::default().with_voxel_size(voxel_size);
+            dc.mesh_triangles(volume)
+        }
+        MeshingMethod::Manifold => {
+            let mut mc = MarchingCubesMesher::default().with_voxel_size(voxel_size).with_gpu(gpu);
+            Some(mc.mesh_triangles(volume))
+        }
+        MeshingMethod::Adaptive => {
+            let mut amc = AdaptiveMarchingCubesMesher::default().with_voxel_size(voxel_size);
+            Some(amc.mesh_triangles(volume))
+        }
+    }
+}
+
+fn directed_edges(indices: &[usize]) -> HashSet<(usize, usize)> {
+    indices
+        .chunks_exact(3)
+        .flat_map(|face| [(face[0], face[1]), (face[1], face[2]), (face[2], face[0])])
+        .collect()
+}
+
+///
+/// Returns closed loops of directed boundary edges (edges without reversed twin) which pass given filter.
+/// Loops passing through non-manifold vertices may not close, such loops are skipped.
+///
+fn boundary_loops(indices: &[usize], filter: impl Fn(usize, usize) -> bool) -> Vec<Vec<usize>> {
+    let edges = directed_edges(indices);
+    let mut next: BTreeMap<_, _> = edges
+        .iter()
+        .filter(|(from, to)| !edges.contains(&(*to, *from)) && filter(*from, *to))
+        .copied()
+        .collect();
+
+    let mut loops = Vec::new();
+
+    while let Some((start, mut current)) = next.pop_first() {
+        let mut boundary_loop = vec![start];
+
+        while current != start {
+            boundary_loop.push(current);
+
+            match next.remove(&current) {
+                Some(to) => current = to,
+                None => break,
+            }
+        }
+
+        if current == start && boundary_loop.len() >= 3 {
+            loops.push(boundary_loop);
+        }
+    }
+
+    loops
+}
+
+fn loop_center(boundary_loop: &[usize], vertices: &[Vec3f]) -> Vec3f {
+    boundary_loop.iter().map(|&v| vertices[v]).sum::<Vec3f>() / boundary_loop.len() as f32
+}
+
+///
+/// Closes gap between two boundary loops running around the same hole in opposite directions.
+/// Loops are zipped together advancing the one which gives shorter diagonal.
+///
+fn stitch_loops(old_loop: &[usize], new_loop: &[usize], vertices: &[Vec3f], indices: &mut Vec<usize>) {
+    // Walk new loop backwards, so both loops advance in the same direction
+    let a = old_loop;
+    let b: Vec<_> = new_loop.iter().rev().copied().collect();
+    let (n, m) = (a.len(), b.len());
+
+    let distance = |v1: usize, v2: usize| (vertices[v1] - vertices[v2]).norm_squared();
+    let start = (0..m)
+        .min_by(|&k1, &k2| distance(a[0], b[k1]).total_cmp(&distance(a[0], b[k2])))
+        .unwrap_or(0);
+
+    let (mut i, mut k) = (0, 0);
+
+    while i < n || k < m {
+        let a_current = a[i % n];
+        let a_next = a[(i + 1) % n];
+        let b_current = b[(start + k) % m];
+        let b_next = b[(start + k + 1) % m];
+
+        let advance_old = k == m || (i < n && distance(a_next, b_current) <= distance(a_current, b_next));
+
+        if advance_old {
+            indices.extend_from_slice(&[a_next, a_current, b_current]);
+            i += 1;
+        } else {
+            indices.extend_from_slice(&[b_current, b_next, a_current]);
+            k += 1;
+        }
+    }
+}
+
+/// Rough estimate of memory used by one voxel of distance field and its share of input triangles
+const BYTES_PER_VOXEL: usize = 32;
+
+///
+/// Out-of-core voxel remeshing for meshes that don't fit in memory.
+/// Bounding box of input is split into cubic chunks sized to fit given memory budget.
+/// Each chunk is voxelized together with overlapping neighborhood and meshed separately,
+/// then faces from all chunks are stitched into single mesh. Only triangles of current chunk
+/// (and its overlap) are kept in memory, input is streamed once per chunk.
+///
+/// Inside/outside is decided using triangles within overlap only, so overlap should be several
+/// voxels wide for correct signs. Output mesh is built in memory.
+///
+/// ## Example
+/// ```ignore
+/// let mut remesher = ChunkedVoxelRemesher::default()
+///     .with_voxel_size(0.5)
+///     .with_memory_budget(512 * 1024 * 1024);
+/// let remeshed: Option<CornerTableF> = remesher.remesh_stl_file(Path::new("scan.stl"))?;
+/// ```
+///
+pub struct ChunkedVoxelRemesher {
+    mesh_to_sdf: MeshToVolume,
+    meshing_method: MeshingMethod,
+    voxel_size: f32,
+    memory_budget: usize,
+    overlap: usize,
+}
+
+impl ChunkedVoxelRemesher {
+    #[inline]
+    pub fn with_voxel_size(mut self, size: f32) -> Self {
+        self.mesh_to_sdf.set_voxel_size(size);
+        self.voxel_size = size;
+        self
+    }
+
+    #[inline]
+    pub fn with_meshing_method(mut self, method: MeshingMethod) -> Self {
+        self.meshing_method = method;
+        self
+    }
+
+    ///
+    /// Set approximate memory (in bytes) available for processing of one chunk.
+    /// Default is 1 GiB.
+    ///
+    #[inline]
+    pub fn with_memory_budget(mut self, bytes: usize) -> Self {
+        self.memory_budget = bytes;
+        self
+    }
+
+    ///
+    /// Set width of chunk neighborhood (in voxels) which is voxelized together with chunk.
+    /// Default is 4.
+    ///
+    #[inline]
+    pub fn with_overlap(mut self, voxels: usize) -> Self {
+        self.overlap = voxels.max(2);
+        self
+    }
+
+    /// Remeshes mesh chunk by chunk
+    pub fn remesh<T: Mesh<ScalarType = f32>>(&mut self, mesh: &T) -> Option<T> {
+        let mut bbox = Box3::empty();
+        for face in mesh.faces() {
+            bbox.union_box(&mesh.face_positions(&face).bbox());
+        }
+
+        let result: Result<_, ()> = self.remesh_chunks(&bbox, |visit| {
+            mesh.faces().for_each(|face| visit(&mesh.face_positions(&face)));
+            Ok(())
+        });
+
+        result.ok().flatten()
+    }
+
+    ///
+    /// Remeshes binary STL file chunk by chunk. File is streamed once to find bounds
+    /// and then once per chunk, so whole input is never loaded at once.
+    ///
+    #[cfg(feature = "io")]
+    pub fn remesh_stl_file<T: Mesh<ScalarType = f32>>(&mut self, path: &Path) -> io::Result<Option<T>> {
+        let mut reader = StlReader::new();
+        let mut bbox = Box3::empty();
+        reader.visit_triangles_from_file(path, |triangle| {
+            bbox.union_box(&triangle.bbox());
+        })?;
+
+        self.remesh_chunks(&bbox, |visit| {
+            reader.visit_triangles_from_file(path, |triangle| visit(&triangle))
+        })
+    }
+
+    fn remesh_chunks<T, TError, TStream>(&mut self, bbox: &Box3<f32>, mut stream: TStream) -> Result<Option<T>, TError>
+    where
+        T: Mesh<ScalarType = f32>,
+        TStream: FnMut(&mut dyn FnMut(&Triangle3<f32>)) -> Result<(), TError>,
+    {
+        if !bbox.is_valid() {
+            return Ok(None);
+        }
+
+        let chunk_resolution = self.chunk_resolution() as isize;
+        let overlap = self.overlap as f32 * self.voxel_size;
+
+        // Grid is extended by one voxel so surface near bounds is not cut
+        let grid_min = (bbox.get_min() / self.voxel_size).map(|x| x.floor() as isize).add_scalar(-1);
+        let grid_max = (bbox.get_max() / self.voxel_size).map(|x| x.ceil() as isize).add_scalar(1);
+        let chunks_count = (grid_max - grid_min).map(|x| x / chunk_resolution + 1);
+
+        let mut faces = Vec::new();
+
+        for x in 0..chunks_count.x {
+            for y in 0..chunks_count.y {
+                for z in 0..chunks_count.z {
+                    let core_min = grid_min + Vec3i::new(x, y, z) * chunk_resolution;
+                    let core_max = core_min.add_scalar(chunk_resolution);
+
+                    let chunk_bbox = Box3::new(
+                        core_min.cast() * self.voxel_size - Vec3f::repeat(overlap),
+                        core_max.cast() * self.voxel_size + Vec3f::repeat(overlap),
+                    );
+
+                    let mut chunk_triangles = Vec::new();
+                    stream(&mut |triangle| {
+                        if triangle.bbox().intersects_box3(&chunk_bbox) {
+                            chunk_triangles.extend_from_slice(&[*triangle.p1(), *triangle.p2(), *triangle.p3()]);
+                        }
+                    })?;
+
+                    if chunk_triangles.is_empty() {
+                        continue;
+                    }
+
+                    let chunk_mesh = PolygonSoup::from(chunk_triangles);
+                    let chunk_faces = self
+                        .mesh_to_sdf
+                        .convert(&chunk_mesh)
+                        .and_then(|volume| mesh_volume(&volume, self.meshing_method, self.voxel_size, false));
+
+                    // Keep faces which belong to chunk core, faces in overlap are produced by neighboring chunks
+                    for face in chunk_faces.iter().flat_map(|f| f.chunks_exact(3)) {
+                        let center = (face[0] + face[1] + face[2]) / (3.0 * self.voxel_size);
+                        let cell = center.map(|x| x.floor() as isize);
+
+                        let in_core = (0..3).all(|i| cell[i] >= core_min[i] && cell[i] < core_max[i]);
+
+                        if in_core {
+                            faces.extend_from_slice(face);
+                        }
+                    }
+                }
+            }
+        }
+
+        if faces.is_empty() {
+            return Ok(None);
+        }
+
+        let indexed_faces = merge_points(&faces);
+        let mesh = T::from_vertices_and_indices(&indexed_faces.points, &indexed_faces.indices);
+
+        Ok(Some(mesh))
+    }
+
+    /// Returns size of chunk side in voxels, it is multiple of 8
+    fn chunk_resolution(&self) -> usize {
+        let voxels = (self.memory_budget / BYTES_PER_VOXEL) as f64;
+        let resolution = voxels.cbrt() as usize;
+
+        (resolution / 8 * 8).max(8)
+    }
+}
+
+impl Default for ChunkedVoxelRemesher {
+    fn default() -> Self {
+        Self {
+            mesh_to_sdf: MeshToVolume::default().with_narrow_band_width(0),
+            voxel_size: 1.0,
+            meshing_method: MeshingMethod::Manifold,
+            memory_budget: 1024 * 1024 * 1024,
+            overlap: 4,
         }
     }
 }
 
 #[cfg(test)]
 mod tests {
-    use super::VoxelRemesher;
+    use super::{ChunkedVoxelRemesher, RegionOfInterest, VoxelRemesher};
     use crate::{
-        helpers::aliases::Vec3,
-        mesh::{builder, polygon_soup::data_structure::PolygonSoup, traits::Mesh},
+        decimation::{edge_decimation::ConstantErrorDecimationCriteria, prelude::EdgeDecimator},
+        geometry::primitives::box3::Box3,
+        helpers::aliases::{Vec3, Vec3f},
+        mesh::{
+            builder, corner_table::prelude::CornerTableF, polygon_soup::data_structure::PolygonSoup, primitives,
+            traits::{Mesh, TopologicalMesh},
+        },
     };
 
     #[test]
@@ -105,4 +583,68 @@ mod tests {
 
         assert!(remeshed.faces().count() > 0);
     }
+
+    #[test]
+    fn test_manifold_post_pass() {
+        let sphere: CornerTableF = primitives::uv_sphere(1.0, 32, 16);
+        let mut remesher = VoxelRemesher::default()
+            .with_voxel_size(0.1)
+            .with_manifold_post_pass(true);
+        let mut remeshed: CornerTableF = remesher.remesh(&sphere).unwrap();
+
+        let report = remesher.manifold_report().unwrap();
+        assert!(report.is_closed());
+        assert_eq!(remeshed.edges().filter(|e| remeshed.is_edge_on_boundary(e)).count(), 0);
+
+        let faces_count = remeshed.faces().count();
+        EdgeDecimator::new()
+            .decimation_criteria(ConstantErrorDecimationCriteria::new(0.01))
+            .decimate(&mut remeshed);
+        assert!(remeshed.faces().count() < faces_count);
+    }
+
+    #[test]
+    fn test_chunked_voxel_remeshing() {
+        let sphere: CornerTableF = primitives::uv_sphere(1.0, 32, 16);
+
+        // Budget for 8^3 voxels per chunk, so sphere is split into many chunks
+        let mut chunked = ChunkedVoxelRemesher::default()
+            .with_voxel_size(0.2)
+            .with_memory_budget(8 * 8 * 8 * 32);
+        let remeshed: CornerTableF = chunked.remesh(&sphere).unwrap();
+
+        let mut remesher = VoxelRemesher::default().with_voxel_size(0.2);
+        let expected = remesher.remesh(&sphere).unwrap();
+
+        // Chunks are stitched without seams
+        let boundary_edges = |mesh: &CornerTableF| mesh.edges().filter(|e| mesh.is_edge_on_boundary(e)).count();
+        assert_eq!(remeshed.faces().count(), expected.faces().count());
+        assert_eq!(boundary_edges(&remeshed), boundary_edges(&expected));
+    }
+
+    #[test]
+    fn test_region_voxel_remeshing() {
+        let sphere: CornerTableF = primitives::uv_sphere(1.0, 32, 16);
+        let regions = [
+            RegionOfInterest::Sphere { center: Vec3f::new(1.0, 0.0, 0.0), radius: 0.5 },
+            RegionOfInterest::Box(Box3::new(Vec3f::new(-0.3, -0.3, 0.5), Vec3f::new(0.3, 0.3, 1.5))),
+        ];
+
+        for region in &regions {
+            let mut remesher = VoxelRemesher::default().with_voxel_size(0.05);
+            let remeshed: CornerTableF = remesher.remesh_region(&sphere, region).unwrap();
+
+            // Patch is stitched to untouched part without holes
+            assert_eq!(remeshed.edges().filter(|e| remeshed.is_edge_on_boundary(e)).count(), 0);
+            assert!(remeshed.faces().count() > sphere.faces().count());
+
+            for vertex in remeshed.vertices() {
+                assert!((remeshed.vertex_position(&vertex).norm() - 1.0).abs() < 0.05);
+            }
+        }
+
+        // Nothing to remesh
+        let far = RegionOfInterest::Sphere { center: Vec3f::new(5.0, 0.0, 0.0), radius: 0.5 };
+        assert!(VoxelRemesher::default().remesh_region(&sphere, &far).is_none());
+    }
 }
@@ -0,0 +1,299 @@
+//!
+//! Container for multiple named meshes placed in world with transforms.
+//! Used in multi-part workflows: loading several objects from one file, baking transforms
+//! and feeding all objects into volume CSG.
+//!
+
+use std::collections::HashMap;
+#[cfg(feature = "io")]
+use std::{io, path::Path};
+
+use nalgebra::{Matrix4, Point3};
+#[cfg(feature = "io")]
+use simba::scalar::SupersetOf;
+
+#[cfg(feature = "io")]
+use crate::io::obj::{ObjReader, ObjWriter};
+#[cfg(feature = "voxel")]
+use crate::voxel::{mesh_to_volume::MeshToVolume, prelude::Volume};
+use crate::{helpers::aliases::Vec3, mesh::traits::Mesh};
+
+///
+/// Named mesh with affine transform from local space of mesh to world space.
+///
+pub struct SceneObject<TMesh: Mesh> {
+    name: String,
+    mesh: TMesh,
+    transform: Matrix4<TMesh::ScalarType>,
+}
+
+impl<TMesh: Mesh> SceneObject<TMesh> {
+    /// Creates object with identity transform
+    pub fn new(name: impl Into<String>, mesh: TMesh) -> Self {
+        Self {
+            name: name.into(),
+            mesh,
+            transform: Matrix4::identity(),
+        }
+    }
+
+    /// Set transform from local space of mesh to world space
+    #[inline]
+    pub fn with_transform(mut self, transform: Matrix4<TMesh::ScalarType>) -> Self {
+        self.transform = transform;
+        self
+    }
+
+    #[inline]
+    pub fn set_transform(&mut self, transform: Matrix4<TMesh::ScalarType>) {
+        self.transform = transform;
+    }
+
+    #[inline]
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    #[inline]
+    pub fn mesh(&self) -> &TMesh {
+        &self.mesh
+    }
+
+    #[inline]
+    pub fn mesh_mut(&mut self) -> &mut TMesh {
+        &mut self.mesh
+    }
+
+    #[inline]
+    pub fn transform(&self) -> &Matrix4<TMesh::ScalarType> {
+        &self.transform
+    }
+
+    /// Returns copy of mesh with transform applied to its vertices
+    pub fn baked_mesh(&self) -> TMesh {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+        self.append_baked(&mut vertices, &mut indices);
+
+        TMesh::from_vertices_and_indices(&vertices, &indices)
+    }
+
+    /// Appends transformed faces of mesh to indexed face set
+    fn append_baked(&self, vertices: &mut Vec<Vec3<TMesh::ScalarType>>, indices: &mut Vec<usize>) {
+        let mut vertex_index = HashMap::new();
+
+        for face in self.mesh.faces() {
+            let (v1, v2, v3) = self.mesh.face_vertices(&face);
+
+            for vertex in [v1, v2, v3] {
+                let index = *vertex_index.entry(vertex).or_insert_with(|| {
+                    let position = Point3::from(*self.mesh.vertex_position(&vertex));
+                    vertices.push(self.transform.transform_point(&position).coords);
+                    vertices.len() - 1
+                });
+                indices.push(index);
+            }
+        }
+    }
+}
+
+///
+/// Scene of named meshes with transforms.
+///
+/// ## Example
+/// ```ignore
+/// let mut scene = Scene::load_obj(Path::new("parts.obj"))?;
+/// scene.get_mut("lid").unwrap().set_transform(Matrix4::new_translation(&Vec3f::new(0.0, 0.0, 1.0)));
+///
+/// let mut mesh_to_volume = MeshToVolume::default().with_voxel_size(0.01);
+/// let volume = scene.union_volume(&mut mesh_to_volume);
+/// ```
+///
+pub struct Scene<TMesh: Mesh> {
+    objects: Vec<SceneObject<TMesh>>,
+}
+
+impl<TMesh: Mesh> Scene<TMesh> {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds object to scene and returns reference to it
+    pub fn add(&mut self, object: SceneObject<TMesh>) -> &mut SceneObject<TMesh> {
+        self.objects.push(object);
+        self.objects.last_mut().unwrap()
+    }
+
+    /// Removes first object with given name
+    pub fn remove(&mut self, name: &str) -> Option<SceneObject<TMesh>> {
+        let index = self.objects.iter().position(|object| object.name == name)?;
+        Some(self.objects.remove(index))
+    }
+
+    /// Returns first object with given name
+    #[inline]
+    pub fn get(&self, name: &str) -> Option<&SceneObject<TMesh>> {
+        self.objects.iter().find(|object| object.name == name)
+    }
+
+    /// Returns first object with given name
+    #[inline]
+    pub fn get_mut(&mut self, name: &str) -> Option<&mut SceneObject<TMesh>> {
+        self.objects.iter_mut().find(|object| object.name == name)
+    }
+
+    #[inline]
+    pub fn objects(&self) -> &[SceneObject<TMesh>] {
+        &self.objects
+    }
+
+    #[inline]
+    pub fn objects_mut(&mut self) -> &mut [SceneObject<TMesh>] {
+        &mut self.objects
+    }
+
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.objects.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.objects.is_empty()
+    }
+
+    /// Merges all objects into single mesh in world space
+    pub fn bake(&self) -> TMesh {
+        let mut vertices = Vec::new();
+        let mut indices = Vec::new();
+
+        for object in &self.objects {
+            object.append_baked(&mut vertices, &mut indices);
+        }
+
+        TMesh::from_vertices_and_indices(&vertices, &indices)
+    }
+
+    ///
+    /// Loads scene from Wavefront OBJ file. Each object/group becomes separate scene object with identity transform.
+    ///
+    #[cfg(feature = "io")]
+    pub fn load_obj(path: &Path) -> io::Result<Self>
+    where
+        TMesh::ScalarType: SupersetOf<f64>
+    {
+        let objects = ObjReader::new()
+            .read_obj_from_file(path)?
+            .into_iter()
+            .map(|(name, mesh)| SceneObject::new(name, mesh))
+            .collect();
+
+        Ok(Self { objects })
+    }
+
+    ///
+    /// Saves scene to Wavefront OBJ file, one object per scene object.
+    /// OBJ has no transforms, so they are baked into vertices.
+    ///
+    #[cfg(feature = "io")]
+    pub fn save_obj(&self, path: &Path) -> io::Result<()> {
+        let baked: Vec<_> = self.objects.iter().map(|object| object.baked_mesh()).collect();
+        let named = self.objects.iter().map(|object| object.name()).zip(baked.iter());
+
+        ObjWriter::new().write_obj_to_file(named, path)
+    }
+}
+
+#[cfg(feature = "voxel")]
+impl<TMesh: Mesh<ScalarType = f32>> Scene<TMesh> {
+    /// Converts each object (with transform applied) to volume. Objects that can't be converted are skipped.
+    pub fn to_volumes(&self, mesh_to_volume: &mut MeshToVolume) -> Vec<(&str, Volume)> {
+        self.objects
+            .iter()
+            .filter_map(|object| {
+                let volume = mesh_to_volume.convert(&object.baked_mesh())?;
+                Some((object.name(), volume))
+            })
+            .collect()
+    }
+
+    /// Returns union of volumes of all objects
+    pub fn union_volume(&self, mesh_to_volume: &mut MeshToVolume) -> Option<Volume> {
+        self.to_volumes(mesh_to_volume)
+            .into_iter()
+            .map(|(_, volume)| volume)
+            .reduce(|union, volume| union.union(volume))
+    }
+}
+
+impl<TMesh: Mesh> Default for Scene<TMesh> {
+    fn default() -> Self {
+        Self { objects: Vec::new() }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::Matrix4;
+
+    use super::{Scene, SceneObject};
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+    };
+
+    fn two_cubes() -> Scene<CornerTableF> {
+        let mut scene = Scene::new();
+        scene.add(SceneObject::new("a", primitives::cuboid(Vec3f::new(1.0, 1.0, 1.0), 1)));
+        scene.add(
+            SceneObject::new("b", primitives::cuboid(Vec3f::new(1.0, 1.0, 1.0), 1))
+                .with_transform(Matrix4::new_translation(&Vec3f::new(0.5, 0.0, 0.0))),
+        );
+
+        scene
+    }
+
+    #[test]
+    fn test_bake_scene() {
+        let scene = two_cubes();
+        assert_eq!(scene.len(), 2);
+
+        let baked = scene.get("b").unwrap().baked_mesh();
+        for vertex in baked.vertices() {
+            let x = baked.vertex_position(&vertex).x;
+            assert!((x - 0.0).abs() < 1e-6 || (x - 1.0).abs() < 1e-6);
+        }
+
+        let merged = scene.bake();
+        assert_eq!(merged.faces().count(), 24);
+        assert_eq!(merged.vertices().count(), 16);
+    }
+
+    #[cfg(feature = "io")]
+    #[test]
+    fn test_save_load_scene() {
+        let scene = two_cubes();
+        let path = std::env::temp_dir().join("baby_shark_test_scene.obj");
+        scene.save_obj(&path).unwrap();
+
+        let loaded: Scene<CornerTableF> = Scene::load_obj(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.len(), 2);
+        assert_eq!(loaded.objects()[0].name(), "a");
+        assert_eq!(loaded.objects()[1].name(), "b");
+        assert_eq!(loaded.bake().vertices().count(), scene.bake().vertices().count());
+    }
+
+    #[cfg(feature = "voxel")]
+    #[test]
+    fn test_scene_union_volume() {
+        use crate::voxel::mesh_to_volume::MeshToVolume;
+
+        let scene = two_cubes();
+        let mut mesh_to_volume = MeshToVolume::default().with_voxel_size(0.1);
+
+        assert_eq!(scene.to_volumes(&mut mesh_to_volume).len(), 2);
+        assert!(scene.union_volume(&mut mesh_to_volume).is_some());
+    }
+}
@@ -1,9 +1,13 @@
+use std::collections::HashSet;
+
 use nalgebra::Vector3;
 use num_traits::*;
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 use crate::{
     geometry::{
-        primitives::{box3::Box3, plane3::Plane3, triangle3::Triangle3},
+        primitives::{box3::Box3, line_segment3::LineSegment3, plane3::Plane3, sphere3::Sphere3, triangle3::Triangle3},
         traits::{ClosestPoint3, HasBBox3, RealNumber},
     },
     helpers::aliases::Vec3,
@@ -51,8 +55,12 @@ where
     TObject: HasBBox3,
     TObject::ScalarType: RealNumber,
 {
-    nodes: Vec<BinaryNode<TObject::ScalarType>>, // root is last element
+    nodes: Vec<BinaryNode<TObject::ScalarType>>, // root is last element, children are stored before parents
+    parents: Vec<usize>,                         // parent of each node, `usize::MAX` for root
     objects: Vec<(TObject, Box3<TObject::ScalarType>)>,
+    object_indices: Vec<usize>,                  // index of stored object in original objects vector
+    object_positions: Vec<usize>,                // inverse of `object_indices`
+    object_leaves: Vec<usize>,                   // leaf node containing stored object
     min_objects_per_leaf: usize,
     max_depth: usize,
 }
@@ -69,6 +77,10 @@ where
     pub fn new(objects: Vec<TObject>) -> Self {
         Self {
             nodes: Vec::new(),
+            parents: Vec::new(),
+            object_indices: (0..objects.len()).collect(),
+            object_positions: Vec::new(),
+            object_leaves: Vec::new(),
             min_objects_per_leaf: 10,
             max_depth: 40,
             objects: objects
@@ -84,6 +96,10 @@ where
     pub fn empty() -> Self {
         Self {
             nodes: Vec::new(),
+            parents: Vec::new(),
+            object_indices: Vec::new(),
+            object_positions: Vec::new(),
+            object_leaves: Vec::new(),
             min_objects_per_leaf: 10,
             max_depth: 40,
             objects: Vec::new(),
@@ -124,9 +140,142 @@ where
             self.top_down_build_node(0, self.objects.len(), 1, &mut TPartition::default());
         }
 
+        self.build_links();
+
+        self
+    }
+
+    ///
+    /// Constructs linear BVH: objects are sorted along Morton (Z-order) curve of their centers
+    /// and split by bits of their Morton codes. Codes are computed in parallel and sorted by radix sort,
+    /// so construction is order of magnitude faster than [top_down](AABBTree::top_down) on large sets of objects,
+    /// at cost of a bit worse query performance.
+    ///
+    pub fn linear_fast(mut self) -> Self {
+        self.nodes.clear();
+
+        if !self.objects.is_empty() {
+            let codes = self.morton_codes();
+            let order = radix_sort(&codes);
+            let sorted_codes: Vec<_> = order.iter().map(|&i| codes[i]).collect();
+
+            reorder_objects(&mut self.objects, &mut self.object_indices, &order);
+            self.linear_build_node(&sorted_codes, 0, self.objects.len(), 1);
+        }
+
+        self.build_links();
+
         self
     }
 
+    /// Returns object by its index in vector the tree was created from
+    #[inline]
+    pub fn object(&self, index: usize) -> Option<&TObject> {
+        let position = *self.object_positions.get(index)?;
+        Some(&self.objects[position].0)
+    }
+
+    ///
+    /// Returns mutable reference to object by its index in vector the tree was created from.
+    /// Bounding boxes are not updated, call [refit](AABBTree::refit) or [update_object](AABBTree::update_object) after modification.
+    ///
+    #[inline]
+    pub fn object_mut(&mut self, index: usize) -> Option<&mut TObject> {
+        let position = *self.object_positions.get(index)?;
+        Some(&mut self.objects[position].0)
+    }
+
+    ///
+    /// Recomputes bounding boxes of all objects and nodes bottom-up, structure of tree is kept.
+    /// Much faster than rebuilding, but quality of tree degrades when objects move far from their initial positions.
+    ///
+    pub fn refit(&mut self) {
+        for (object, bbox) in &mut self.objects {
+            *bbox = object.bbox();
+        }
+
+        // Children are stored before parents, so one pass is enough
+        for node_index in 0..self.nodes.len() {
+            self.nodes[node_index].bbox = self.node_bbox(node_index);
+        }
+    }
+
+    ///
+    /// Sets bounding box of object with given index (in vector the tree was created from)
+    /// and updates boxes of nodes on path from its leaf to root.
+    ///
+    pub fn update_object(&mut self, index: usize, bbox: Box3<TObject::ScalarType>) {
+        let position = self.object_positions[index];
+        self.objects[position].1 = bbox;
+
+        let mut node_index = self.object_leaves[position];
+
+        while node_index != usize::MAX {
+            let bbox = self.node_bbox(node_index);
+
+            // Ancestors are not affected when box didn't change
+            if self.nodes[node_index].bbox == bbox {
+                break;
+            }
+
+            self.nodes[node_index].bbox = bbox;
+            node_index = self.parents[node_index];
+        }
+    }
+
+    ///
+    /// Returns indices (in vector the tree was created from) of objects whose bounding boxes intersect `bbox`.
+    /// Result is a superset of objects intersecting box, see [triangles_in_box](AABBTree::triangles_in_box) for exact test.
+    ///
+    pub fn objects_in_box(&self, bbox: &Box3<TObject::ScalarType>) -> Vec<usize> {
+        self.collect_objects(|node_bbox| node_bbox.intersects_box3(bbox), |_| true)
+    }
+
+    ///
+    /// Returns indices (in vector the tree was created from) of objects whose bounding boxes intersect `sphere`.
+    /// Result is a superset of objects intersecting sphere, see [triangles_in_sphere](AABBTree::triangles_in_sphere) for exact test.
+    ///
+    pub fn objects_in_sphere(&self, sphere: &Sphere3<TObject::ScalarType>) -> Vec<usize> {
+        self.collect_objects(|node_bbox| sphere.intersects_box3(node_bbox), |_| true)
+    }
+
+    /// Collects indices of objects passing both tests, nodes are skipped when their boxes fail `box_test`
+    fn collect_objects<TBoxTest, TObjectTest>(&self, box_test: TBoxTest, object_test: TObjectTest) -> Vec<usize>
+    where
+        TBoxTest: Fn(&Box3<TObject::ScalarType>) -> bool,
+        TObjectTest: Fn(&TObject) -> bool,
+    {
+        let mut found = Vec::new();
+
+        let Some(root) = self.nodes.last() else {
+            return found;
+        };
+
+        let mut stack = Vec::with_capacity(self.max_depth);
+        stack.push(root);
+
+        while let Some(top) = stack.pop() {
+            if !box_test(&top.bbox) {
+                continue;
+            }
+
+            if top.is_leaf() {
+                for position in top.left..top.right {
+                    let (object, bbox) = &self.objects[position];
+
+                    if box_test(bbox) && object_test(object) {
+                        found.push(self.object_indices[position]);
+                    }
+                }
+            } else {
+                stack.push(&self.nodes[top.left]);
+                stack.push(&self.nodes[top.right]);
+            }
+        }
+
+        found
+    }
+
     /// Traverse leaf node of tree
     #[inline]
     pub fn traverse<TFunc>(&self, visit: &mut TFunc)
@@ -180,8 +329,9 @@ where
         } else {
             // Split set of objects
             let subset = &mut self.objects[first..last];
-            let split_at_result =
-                Self::split(subset, partition_strategy).map(|split_at| split_at + first);
+            let subset_indices = &mut self.object_indices[first..last];
+            let split_at_result = Self::split(subset, subset_indices, partition_strategy)
+                .map(|split_at| split_at + first);
 
             match split_at_result {
                 Some(split_at) => {
@@ -222,6 +372,7 @@ where
 
     fn split<TPartition: PartitionStrategy<TObject>>(
         objects: &mut [(TObject, Box3<TObject::ScalarType>)],
+        indices: &mut [usize],
         partition_strategy: &mut TPartition,
     ) -> Option<usize> {
         // Split by biggest dimension first
@@ -237,10 +388,11 @@ where
 
         // Sort by bbox size along split axis
         split_axises.sort_by(|(size1, _), (size2, _)| size2.partial_cmp(size1).unwrap());
-        Self::sort_along_axis_and_try_split(objects, split_axises[0].1, partition_strategy, &bbox)
+        Self::sort_along_axis_and_try_split(objects, indices, split_axises[0].1, partition_strategy, &bbox)
             .or_else(|| {
                 Self::sort_along_axis_and_try_split(
                     objects,
+                    indices,
                     split_axises[1].1,
                     partition_strategy,
                     &bbox,
@@ -249,6 +401,7 @@ where
             .or_else(|| {
                 Self::sort_along_axis_and_try_split(
                     objects,
+                    indices,
                     split_axises[2].1,
                     partition_strategy,
                     &bbox,
@@ -258,20 +411,75 @@ where
 
     fn sort_along_axis_and_try_split<TPartition: PartitionStrategy<TObject>>(
         objects: &mut [(TObject, Box3<TObject::ScalarType>)],
+        indices: &mut [usize],
         axis: SplitAxis,
         partition_strategy: &mut TPartition,
         objects_bbox: &Box3<TObject::ScalarType>,
     ) -> Option<usize> {
         let axis_idx = axis.as_usize();
-        objects.sort_by(|(_, bbox1), (_, bbox2)| {
-            bbox1.get_center()[axis_idx]
-                .partial_cmp(&bbox2.get_center()[axis_idx])
+        let mut order: Vec<usize> = (0..objects.len()).collect();
+        order.sort_by(|&i, &j| {
+            objects[i].1.get_center()[axis_idx]
+                .partial_cmp(&objects[j].1.get_center()[axis_idx])
                 .unwrap()
         });
 
+        reorder_objects(objects, indices, &order);
+
         partition_strategy.split(objects, axis, objects_bbox)
     }
 
+    /// Computes 30-bit Morton codes of objects centers
+    fn morton_codes(&self) -> Vec<u32> {
+        let centers: Vec<_> = self.objects.iter().map(|(_, bbox)| bbox.get_center()).collect();
+        let bounds = centers.iter().fold(Box3::empty(), |acc, center| acc + center);
+        let scale = TObject::ScalarType::from_u32(MORTON_AXIS_MAX).unwrap();
+
+        #[cfg(feature = "rayon")]
+        let centers_iter = centers.par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let centers_iter = centers.iter();
+
+        centers_iter
+            .map(|center| {
+                let cell = bounds.offset(center).map(|t| (t * scale).to_u32().unwrap_or(0).min(MORTON_AXIS_MAX));
+                (expand_bits(cell.x) << 2) | (expand_bits(cell.y) << 1) | expand_bits(cell.z)
+            })
+            .collect()
+    }
+
+    /// Build tree node from objects sorted by Morton codes
+    fn linear_build_node(&mut self, codes: &[u32], first: usize, last: usize, depth: usize) -> usize {
+        if depth >= self.max_depth || last - first <= self.min_objects_per_leaf {
+            return self.leaf_node_from_objects(first, last);
+        }
+
+        let first_code = codes[first];
+        let last_code = codes[last - 1];
+
+        // Split at highest bit that differs inside range, objects with equal codes are split in halves
+        let split_at = if first_code == last_code {
+            (first + last) / 2
+        } else {
+            let mask = 1 << (31 - (first_code ^ last_code).leading_zeros());
+            first + codes[first..last].partition_point(|code| code & mask == 0)
+        };
+
+        let left = self.linear_build_node(codes, first, split_at, depth + 1);
+        let right = self.linear_build_node(codes, split_at, last, depth + 1);
+
+        let node = BinaryNode {
+            bbox: self.nodes[left].bbox + &self.nodes[right].bbox,
+            node_type: NodeType::Branch,
+            left,
+            right,
+        };
+
+        self.nodes.push(node);
+
+        self.nodes.len() - 1
+    }
+
     /// Create leaf node from set of objects
     fn leaf_node_from_objects(&mut self, first: usize, last: usize) -> usize {
         // Compute bounding box of set of objects
@@ -292,6 +500,39 @@ where
         self.nodes.len() - 1
     }
 
+    /// Computes bounding box of node from its objects or children
+    fn node_bbox(&self, node_index: usize) -> Box3<TObject::ScalarType> {
+        let node = &self.nodes[node_index];
+
+        match node.node_type {
+            NodeType::Leaf => self.objects[node.left + 1..node.right]
+                .iter()
+                .fold(self.objects[node.left].1, |acc, (_, bbox)| acc + bbox),
+            NodeType::Branch => self.nodes[node.left].bbox + &self.nodes[node.right].bbox,
+        }
+    }
+
+    /// Computes links used for updates: parents of nodes, positions of objects and their leaves
+    fn build_links(&mut self) {
+        self.parents = vec![usize::MAX; self.nodes.len()];
+        self.object_leaves = vec![usize::MAX; self.objects.len()];
+        self.object_positions = vec![usize::MAX; self.objects.len()];
+
+        for (node_index, node) in self.nodes.iter().enumerate() {
+            match node.node_type {
+                NodeType::Leaf => self.object_leaves[node.left..node.right].fill(node_index),
+                NodeType::Branch => {
+                    self.parents[node.left] = node_index;
+                    self.parents[node.right] = node_index;
+                }
+            }
+        }
+
+        for (position, index) in self.object_indices.iter().enumerate() {
+            self.object_positions[*index] = position;
+        }
+    }
+
     fn node_depth(&self, idx: usize) -> usize {
         let node = &self.nodes[idx];
 
@@ -315,6 +556,121 @@ impl<TScalar: RealNumber> AABBTree<Triangle3<TScalar>> {
 
         Self::new(faces)
     }
+
+    ///
+    /// Updates triangles from deformed mesh and refits tree (see [refit](AABBTree::refit)).
+    /// Mesh should have the same faces as the one tree was created from, only vertex positions may change.
+    ///
+    pub fn refit_from_mesh<TMesh: Mesh<ScalarType = TScalar>>(&mut self, mesh: &TMesh) {
+        for (index, face) in mesh.faces().enumerate() {
+            let position = self.object_positions[index];
+            self.objects[position].0 = mesh.face_positions(&face);
+        }
+
+        self.refit();
+    }
+
+    ///
+    /// Updates triangles of given faces (e.g. faces around vertices moved by [CornerTable::set_positions](crate::mesh::corner_table::table::CornerTable::set_positions))
+    /// and boxes of nodes above them. Cheaper than [refit_from_mesh](AABBTree::refit_from_mesh) when few faces changed.
+    /// Mesh should have the same faces as the one tree was created from.
+    ///
+    pub fn refit_faces<TMesh: Mesh<ScalarType = TScalar>>(&mut self, mesh: &TMesh, faces: &HashSet<TMesh::FaceDescriptor>) {
+        if faces.is_empty() {
+            return;
+        }
+
+        for (index, face) in mesh.faces().enumerate().filter(|(_, face)| faces.contains(face)) {
+            let triangle = mesh.face_positions(&face);
+            let bbox = triangle.bbox();
+            let position = self.object_positions[index];
+            self.objects[position].0 = triangle;
+            self.update_object(index, bbox);
+        }
+    }
+
+    ///
+    /// Returns `true` when line segment intersects any triangle (face culling off).
+    /// Search stops at first found intersection, so it is suitable for visibility queries.
+    ///
+    pub fn intersects_line_segment(&self, segment: &LineSegment3<TScalar>) -> bool {
+        let Some(root) = self.nodes.last() else {
+            return false;
+        };
+
+        let mut stack = Vec::with_capacity(self.max_depth);
+        stack.push(root);
+
+        while let Some(top) = stack.pop() {
+            // Segment starting inside of box is not reported by segment-box test
+            if !top.bbox.contains_point(segment.get_start()) && !segment.intersects_box3(&top.bbox) {
+                continue;
+            }
+
+            if top.is_leaf() {
+                let objects = &self.objects[top.left..top.right];
+
+                if objects.iter().any(|(triangle, _)| triangle.intersects_line_segment3(segment)) {
+                    return true;
+                }
+            } else {
+                stack.push(&self.nodes[top.left]);
+                stack.push(&self.nodes[top.right]);
+            }
+        }
+
+        false
+    }
+
+    ///
+    /// Returns index (in vector the tree was created from) of first triangle hit by line segment and distance to hit from start of segment
+    /// (face culling off). Returns `None` when segment does not intersect any triangle.
+    ///
+    pub fn first_line_segment_hit(&self, segment: &LineSegment3<TScalar>) -> Option<(usize, TScalar)> {
+        let root = self.nodes.last()?;
+        let mut closest: Option<(usize, TScalar)> = None;
+
+        let mut stack = Vec::with_capacity(self.max_depth);
+        stack.push(root);
+
+        while let Some(top) = stack.pop() {
+            if !top.bbox.contains_point(segment.get_start()) && !segment.intersects_box3(&top.bbox) {
+                continue;
+            }
+
+            if top.is_leaf() {
+                for position in top.left..top.right {
+                    let Some((_, t)) = self.objects[position].0.intersects_line_segment3_at(segment) else {
+                        continue;
+                    };
+
+                    if closest.is_none_or(|(_, closest)| t < closest) {
+                        closest = Some((self.object_indices[position], t));
+                    }
+                }
+            } else {
+                stack.push(&self.nodes[top.left]);
+                stack.push(&self.nodes[top.right]);
+            }
+        }
+
+        closest
+    }
+
+    /// Returns indices (in vector the tree was created from) of triangles intersecting `bbox`
+    pub fn triangles_in_box(&self, bbox: &Box3<TScalar>) -> Vec<usize> {
+        self.collect_objects(|node_bbox| node_bbox.intersects_box3(bbox), |triangle| triangle.intersects_box3(bbox))
+    }
+
+    /// Returns indices (in vector the tree was created from) of triangles intersecting `sphere`
+    pub fn triangles_in_sphere(&self, sphere: &Sphere3<TScalar>) -> Vec<usize> {
+        let radius_squared = sphere.get_radius() * sphere.get_radius();
+
+        self.collect_objects(
+            |node_bbox| sphere.intersects_box3(node_bbox),
+            |triangle| (triangle.closest_point(sphere.get_center()) - sphere.get_center()).norm_squared() <= radius_squared,
+        )
+    }
 }
 
 impl<TObject> AABBTree<TObject>
@@ -327,25 +683,37 @@ where
         point: &Vec3<TObject::ScalarType>,
         max_distance: TObject::ScalarType,
     ) -> Option<Vec3<TObject::ScalarType>> {
+        self.closest_object(point, max_distance).map(|(_, closest_point)| closest_point)
+    }
+
+    ///
+    /// Returns index of object (in original objects vector) closest to `point` and closest point on it.
+    /// Objects further than `max_distance` are ignored.
+    ///
+    pub fn closest_object(
+        &self,
+        point: &Vec3<TObject::ScalarType>,
+        max_distance: TObject::ScalarType,
+    ) -> Option<(usize, Vec3<TObject::ScalarType>)> {
         let max_distance_square = max_distance * max_distance;
 
         let mut stack = Vec::with_capacity(self.max_depth);
         stack.push(self.nodes.last().unwrap());
 
         let mut closest_point = Vec3::zeros();
+        let mut closest_object = 0;
         let mut distance_squared = Float::infinity();
 
         while let Some(top) = stack.pop() {
-            
-
             if top.is_leaf() {
-                for (obj, _) in &self.objects[top.left..top.right + 1] {
+                for (position, (obj, _)) in self.objects.iter().enumerate().take(top.right + 1).skip(top.left) {
                     let new_closest = obj.closest_point(point);
                     let new_distance = (new_closest - point).norm_squared();
 
                     if new_distance < distance_squared {
                         distance_squared = new_distance;
                         closest_point = new_closest;
+                        closest_object = self.object_indices[position];
                     }
                 }
             } else {
@@ -370,7 +738,71 @@ where
             return None;
         }
 
-        Some(closest_point)
+        Some((closest_object, closest_point))
+    }
+}
+
+/// Max coordinate of cell along one axis for 30-bit Morton codes
+const MORTON_AXIS_MAX: u32 = (1 << 10) - 1;
+
+/// Inserts two zero bits after each of 10 lower bits
+#[inline]
+fn expand_bits(value: u32) -> u32 {
+    let mut v = value & 0x3ff;
+    v = (v | (v << 16)) & 0x030000ff;
+    v = (v | (v << 8)) & 0x0300f00f;
+    v = (v | (v << 4)) & 0x030c30c3;
+    v = (v | (v << 2)) & 0x09249249;
+    v
+}
+
+/// Returns indices of `keys` in ascending order of keys. LSD radix sort, stable.
+fn radix_sort(keys: &[u32]) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..keys.len()).collect();
+    let mut buffer = vec![0; keys.len()];
+
+    for shift in (0..32).step_by(8) {
+        let mut offsets = [0usize; 257];
+
+        for &key in keys {
+            offsets[((key >> shift) & 0xff) as usize + 1] += 1;
+        }
+
+        for digit in 0..256 {
+            offsets[digit + 1] += offsets[digit];
+        }
+
+        for &index in &order {
+            let digit = ((keys[index] >> shift) & 0xff) as usize;
+            buffer[offsets[digit]] = index;
+            offsets[digit] += 1;
+        }
+
+        std::mem::swap(&mut order, &mut buffer);
+    }
+
+    order
+}
+
+/// Reorders objects and their indices so that `objects[i]` becomes old `objects[order[i]]`
+fn reorder_objects<TObject, TBox>(objects: &mut [(TObject, TBox)], indices: &mut [usize], order: &[usize]) {
+    let mut visited = vec![false; order.len()];
+
+    for start in 0..order.len() {
+        let mut current = start;
+
+        while !visited[current] {
+            visited[current] = true;
+            let source = order[current];
+
+            if source == start {
+                break;
+            }
+
+            objects.swap(current, source);
+            indices.swap(current, source);
+            current = source;
+        }
     }
 }
 
@@ -599,6 +1031,147 @@ where
     true
 }
 
+#[cfg(test)]
+mod tests {
+    use super::{expand_bits, radix_sort, AABBTree, MedianCut, NodeType};
+    use crate::{
+        geometry::{
+            primitives::{box3::Box3, sphere3::Sphere3, triangle3::Triangle3},
+            traits::{ClosestPoint3, HasBBox3},
+        },
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::{EditableMesh, Mesh}},
+    };
+
+    fn assert_boxes_valid(tree: &AABBTree<Triangle3<f32>>) {
+        for node in &tree.nodes {
+            let expected = match node.node_type {
+                NodeType::Leaf => tree.objects[node.left..node.right]
+                    .iter()
+                    .fold(Box3::empty(), |acc, (_, bbox)| acc + bbox),
+                NodeType::Branch => tree.nodes[node.left].bbox + &tree.nodes[node.right].bbox,
+            };
+
+            assert_eq!(node.bbox, expected);
+        }
+    }
+
+    #[test]
+    fn test_refit_from_mesh() {
+        let mut mesh: CornerTableF = primitives::plane(1.0, 1.0, 8, 8);
+        let mut tree = AABBTree::from_mesh(&mesh)
+            .with_min_objects_per_leaf(2)
+            .top_down::<MedianCut>();
+        assert!(tree.depth() > 1);
+
+        // Bend plane
+        let vertices: Vec<_> = mesh.vertices().collect();
+        for vertex in vertices {
+            let position = *mesh.vertex_position(&vertex);
+            mesh.shift_vertex(&vertex, &Vec3f::new(position.x, position.y, position.x * position.x));
+        }
+
+        tree.refit_from_mesh(&mesh);
+        assert_boxes_valid(&tree);
+
+        let root = tree.nodes.last().unwrap().bbox;
+        assert!((root.get_max().z - 0.25).abs() < 1e-6);
+
+        for (index, face) in mesh.faces().enumerate() {
+            let (stored, expected) = (tree.object(index).unwrap(), mesh.face_positions(&face));
+            assert_eq!((stored.p1(), stored.p2(), stored.p3()), (expected.p1(), expected.p2(), expected.p3()));
+        }
+    }
+
+    #[test]
+    fn test_objects_in_box_and_sphere() {
+        let mesh: CornerTableF = primitives::uv_sphere(1.0, 32, 16);
+        let triangles: Vec<_> = mesh.faces().map(|face| mesh.face_positions(&face)).collect();
+        let tree = AABBTree::from_mesh(&mesh)
+            .with_min_objects_per_leaf(4)
+            .top_down::<MedianCut>();
+
+        let bbox = Box3::new(Vec3f::new(0.2, -0.3, -0.1), Vec3f::new(1.5, 0.3, 0.4));
+        let mut exact = tree.triangles_in_box(&bbox);
+        exact.sort();
+        let expected: Vec<_> = (0..triangles.len()).filter(|i| triangles[*i].intersects_box3(&bbox)).collect();
+        assert!(!expected.is_empty());
+        assert_eq!(exact, expected);
+
+        let mut candidates = tree.objects_in_box(&bbox);
+        candidates.sort();
+        let expected: Vec<_> = (0..triangles.len()).filter(|i| triangles[*i].bbox().intersects_box3(&bbox)).collect();
+        assert_eq!(candidates, expected);
+        assert!(exact.iter().all(|i| candidates.contains(i)));
+
+        let sphere = Sphere3::new(Vec3f::new(0.0, 0.0, 1.0), 0.3);
+        let mut exact = tree.triangles_in_sphere(&sphere);
+        exact.sort();
+        let expected: Vec<_> = (0..triangles.len())
+            .filter(|i| (triangles[*i].closest_point(sphere.get_center()) - sphere.get_center()).norm() <= 0.3)
+            .collect();
+        assert!(!expected.is_empty());
+        assert_eq!(exact, expected);
+        assert!(exact.iter().all(|i| tree.objects_in_sphere(&sphere).contains(i)));
+
+        let far = Box3::new(Vec3f::new(5.0, 5.0, 5.0), Vec3f::new(6.0, 6.0, 6.0));
+        assert!(tree.objects_in_box(&far).is_empty());
+    }
+
+    #[test]
+    fn test_linear_fast() {
+        let mesh: CornerTableF = primitives::uv_sphere(1.0, 32, 16);
+        let faces_count = mesh.faces().count();
+        let tree = AABBTree::from_mesh(&mesh)
+            .with_min_objects_per_leaf(4)
+            .linear_fast();
+
+        assert_boxes_valid(&tree);
+        assert!(tree.depth() > 5 && tree.depth() < 20);
+
+        let mut objects_count = 0;
+        tree.traverse(&mut |(objects, _)| {
+            assert!(!objects.is_empty() && objects.len() <= 4);
+            objects_count += objects.len();
+        });
+        assert_eq!(objects_count, faces_count);
+
+        for (index, face) in mesh.faces().enumerate() {
+            let (stored, expected) = (tree.object(index).unwrap(), mesh.face_positions(&face));
+            assert_eq!(stored.p1(), expected.p1());
+        }
+    }
+
+    #[test]
+    fn test_radix_sort() {
+        let keys = [7, 0x0100_0000, 3, 0xffff_ffff, 3, 0x0001_0000, 0];
+        let order = radix_sort(&keys);
+
+        assert_eq!(order, vec![6, 2, 4, 0, 5, 1, 3]);
+        assert_eq!(expand_bits(0b111), 0b001001001);
+    }
+
+    #[test]
+    fn test_update_object() {
+        let mesh: CornerTableF = primitives::plane(1.0, 1.0, 8, 8);
+        let mut tree = AABBTree::from_mesh(&mesh)
+            .with_min_objects_per_leaf(2)
+            .top_down::<MedianCut>();
+
+        let face = mesh.faces().nth(5).unwrap();
+        let moved = mesh.face_positions(&face);
+        let offset = Vec3f::new(0.0, 0.0, 2.0);
+        *tree.object_mut(5).unwrap() = Triangle3::new(moved.p1() + offset, moved.p2() + offset, moved.p3() + offset);
+
+        let bbox = tree.object(5).unwrap().bbox();
+        tree.update_object(5, bbox);
+        assert_boxes_valid(&tree);
+
+        let root = tree.nodes.last().unwrap().bbox;
+        assert_eq!(root.get_max().z, 2.0);
+    }
+}
+
 pub mod winding_numbers {
     use std::f32::consts::PI;
 
@@ -0,0 +1,313 @@
+use nalgebra::{Isometry3, Point3};
+
+use crate::{
+    algo::merge_points::merge_points,
+    geometry::{
+        polygon2::Polygon2,
+        primitives::{box3::Box3, line_segment3::LineSegment3},
+    },
+    helpers::aliases::{Vec2, Vec3f},
+    mesh::traits::Mesh,
+    spatial_partitioning::aabb_tree::{AABBTree, MedianCut},
+};
+
+use super::{
+    meshing::{MarchingCubesMesher, Mesher},
+    volume::Volume,
+};
+
+/// Width (in voxels) of band around stamp where distances are evaluated
+const STAMP_BAND: usize = 2;
+
+/// Whether [Emboss] adds or removes material
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+pub enum EmbossMode {
+    /// Shape is raised above surface
+    Emboss,
+    /// Shape is cut into surface
+    Engrave,
+}
+
+///
+/// Embosses or engraves planar shape (e.g. text outlines converted to polygons) onto mesh surface using distance field CSG.
+///
+/// Shape is given in XY plane of placement and projected onto surface along negative Z axis of placement,
+/// so placement Z axis should point away from surface. Shape follows surface: every point of shape is raised
+/// (or sunk) by [depth](Emboss::with_depth) from first surface point hit by projection.
+/// Walls of stamp are tilted by [draft angle](Emboss::with_draft_angle), so shape narrows away from its base,
+/// which helps printing and molding.
+///
+/// Result is remeshed by marching cubes, so features smaller than voxel size are lost.
+///
+/// ## Example
+/// ```ignore
+/// let label = Polygon2::new(glyph_outlines, FillRule::NonZero);
+/// let placement = Isometry3::face_towards(&eye, &target, &Vec3f::z());
+/// let part: CornerTableF = Emboss::default()
+///     .with_voxel_size(0.05)
+///     .with_depth(0.5)
+///     .with_mode(EmbossMode::Engrave)
+///     .apply(&part, &label, &placement)
+///     .unwrap();
+/// ```
+///
+pub struct Emboss {
+    voxel_size: f32,
+    depth: f32,
+    draft_angle: f32,
+    mode: EmbossMode,
+}
+
+impl Emboss {
+    #[inline]
+    pub fn with_voxel_size(mut self, voxel_size: f32) -> Self {
+        self.voxel_size = voxel_size;
+        self
+    }
+
+    /// Set height of embossed shape or depth of engraving. Default is `1.0`
+    #[inline]
+    pub fn with_depth(mut self, depth: f32) -> Self {
+        self.depth = depth;
+        self
+    }
+
+    /// Set angle (in radians) between walls of stamp and projection direction. Default is `0.0`, walls are parallel to projection.
+    #[inline]
+    pub fn with_draft_angle(mut self, angle: f32) -> Self {
+        self.draft_angle = angle;
+        self
+    }
+
+    /// Set whether shape is raised or cut into surface. Default is [EmbossMode::Emboss]
+    #[inline]
+    pub fn with_mode(mut self, mode: EmbossMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    ///
+    /// Applies `shape` to closed `mesh` at `placement`. Parts of shape which don't project onto mesh are skipped.
+    /// Returns `None` when mesh can't be converted to volume or result has no surface.
+    ///
+    pub fn apply<T: Mesh<ScalarType = f32>>(&self, mesh: &T, shape: &Polygon2<f32>, placement: &Isometry3<f32>) -> Option<T> {
+        let body = Volume::from_mesh(mesh, self.voxel_size)?;
+
+        let result = match self.stamp(mesh, shape, placement) {
+            Some(stamp) => match self.mode {
+                EmbossMode::Emboss => body.union(stamp),
+                EmbossMode::Engrave => body.subtract(stamp),
+            },
+            None => body,
+        };
+
+        let faces = MarchingCubesMesher::default().with_voxel_size(self.voxel_size).mesh_triangles(&result);
+
+        if faces.is_empty() {
+            return None;
+        }
+
+        let indexed_faces = merge_points(&faces);
+        Some(T::from_vertices_and_indices(&indexed_faces.points, &indexed_faces.indices))
+    }
+
+    ///
+    /// Returns distance field of shape extruded between surface and surface shifted by depth.
+    /// Stamp overlaps surface by margin, so it is fused with body without gaps.
+    ///
+    fn stamp<T: Mesh<ScalarType = f32>>(&self, mesh: &T, shape: &Polygon2<f32>, placement: &Isometry3<f32>) -> Option<Volume> {
+        let margin = (STAMP_BAND + 1) as f32 * self.voxel_size;
+        let heights = HeightMap::new(mesh, shape, placement, self.voxel_size, margin)?;
+        let slope = self.draft_angle.tan();
+        let depth = self.depth;
+        let direction = match self.mode {
+            EmbossMode::Emboss => 1.0,
+            EmbossMode::Engrave => -1.0,
+        };
+
+        // Stamp is stored completely, so CSG sees its interior
+        let band_width = ((depth + margin) / self.voxel_size).ceil() as usize + STAMP_BAND;
+        let far = (band_width + 2) as f32 * self.voxel_size;
+
+        let (min_height, max_height) = heights.range()?;
+        let (min, max) = (heights.min, heights.max());
+        let mut bbox = Box3::empty();
+
+        for corner in 0..8 {
+            let local = Vec3f::new(
+                if corner & 1 == 0 { min.x } else { max.x },
+                if corner & 2 == 0 { min.y } else { max.y },
+                if corner & 4 == 0 { min_height - depth - margin } else { max_height + depth + margin },
+            );
+            bbox.union_point(&placement.transform_point(&Point3::from(local)).coords);
+        }
+
+        let inverse = placement.inverse();
+
+        Some(Volume::from_fn(self.voxel_size, *bbox.get_min(), *bbox.get_max(), band_width, |point| {
+            let local = inverse.transform_point(&Point3::from(*point)).coords;
+            let Some(surface) = heights.sample(local.x, local.y) else {
+                return far;
+            };
+
+            // Distance from base of stamp along projection, negative below base
+            let height = (local.z - surface) * direction;
+            let slab = (-height - margin).max(height - depth);
+            let taper = height.clamp(0.0, depth) * slope;
+
+            (shape.signed_distance(&Vec2::new(local.x, local.y)) + taper).max(slab)
+        }))
+    }
+}
+
+impl Default for Emboss {
+    fn default() -> Self {
+        Self {
+            voxel_size: 0.1,
+            depth: 1.0,
+            draft_angle: 0.0,
+            mode: EmbossMode::Emboss,
+        }
+    }
+}
+
+/// Heights of first surface points hit by projection along negative Z axis of placement, sampled on regular grid
+struct HeightMap {
+    min: Vec2<f32>,
+    step: f32,
+    columns: usize,
+    rows: usize,
+    heights: Vec<Option<f32>>,
+}
+
+impl HeightMap {
+    fn new<T: Mesh<ScalarType = f32>>(mesh: &T, shape: &Polygon2<f32>, placement: &Isometry3<f32>, step: f32, margin: f32) -> Option<Self> {
+        let inverse = placement.inverse();
+        let (mut bottom, mut top) = (f32::MAX, f32::MIN);
+
+        for vertex in mesh.vertices() {
+            let local = inverse.transform_point(&Point3::from(*mesh.vertex_position(&vertex)));
+            bottom = bottom.min(local.z);
+            top = top.max(local.z);
+        }
+
+        let points = shape.contours().iter().flatten();
+        let min = points.clone().fold(Vec2::repeat(f32::MAX), |min, point| min.inf(point)).add_scalar(-margin);
+        let max = points.fold(Vec2::repeat(f32::MIN), |max, point| max.sup(point)).add_scalar(margin);
+
+        if bottom > top || min.x > max.x {
+            return None;
+        }
+
+        let columns = ((max.x - min.x) / step).ceil() as usize + 1;
+        let rows = ((max.y - min.y) / step).ceil() as usize + 1;
+        let tree = AABBTree::from_mesh(mesh).top_down::<MedianCut>();
+
+        let heights = (0..rows)
+            .flat_map(|row| (0..columns).map(move |column| (row, column)))
+            .map(|(row, column)| {
+                let (x, y) = (min.x + column as f32 * step, min.y + row as f32 * step);
+                let start = placement.transform_point(&Point3::new(x, y, top + step)).coords;
+                let end = placement.transform_point(&Point3::new(x, y, bottom - step)).coords;
+
+                tree.first_line_segment_hit(&LineSegment3::new(&start, &end))
+                    .map(|(_, distance)| top + step - distance)
+            })
+            .collect();
+
+        Some(Self {
+            min,
+            step,
+            columns,
+            rows,
+            heights,
+        })
+    }
+
+    #[inline]
+    fn max(&self) -> Vec2<f32> {
+        self.min + Vec2::new((self.columns - 1) as f32, (self.rows - 1) as f32) * self.step
+    }
+
+    /// Returns lowest and highest hit, `None` when projection misses mesh
+    fn range(&self) -> Option<(f32, f32)> {
+        let mut hits = self.heights.iter().flatten().copied();
+        let first = hits.next()?;
+
+        Some(hits.fold((first, first), |(min, max), height| (min.min(height), max.max(height))))
+    }
+
+    /// Bilinear interpolation of heights, `None` outside of grid or near silhouette of mesh
+    fn sample(&self, x: f32, y: f32) -> Option<f32> {
+        let u = (x - self.min.x) / self.step;
+        let v = (y - self.min.y) / self.step;
+
+        if u < 0.0 || v < 0.0 || u > (self.columns - 1) as f32 || v > (self.rows - 1) as f32 {
+            return None;
+        }
+
+        // Grid has at least two samples along each axis, since it is expanded by margin
+        let (column, row) = ((u.floor() as usize).min(self.columns - 2), (v.floor() as usize).min(self.rows - 2));
+
+        let height = |column: usize, row: usize| self.heights[row * self.columns + column];
+        let (fu, fv) = (u - column as f32, v - row as f32);
+        let bottom = height(column, row)? * (1.0 - fu) + height(column + 1, row)? * fu;
+        let top = height(column, row + 1)? * (1.0 - fu) + height(column + 1, row + 1)? * fu;
+
+        Some(bottom * (1.0 - fv) + top * fv)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use nalgebra::{Isometry3, Translation3, UnitQuaternion};
+
+    use super::{Emboss, EmbossMode};
+    use crate::{
+        algo::mass_properties::mass_properties,
+        geometry::polygon2::{FillRule, Polygon2},
+        helpers::aliases::{Vec2, Vec3f},
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+    };
+
+    #[test]
+    fn test_emboss() {
+        let cube: CornerTableF = primitives::cuboid(Vec3f::new(2.0, 2.0, 2.0), 1);
+        let square = |size: f32| vec![Vec2::new(-size, -size), Vec2::new(size, -size), Vec2::new(size, size), Vec2::new(-size, size)];
+        let shape = Polygon2::new(vec![square(0.6)], FillRule::NonZero);
+
+        // Stamp on top face
+        let top = Isometry3::from_parts(Translation3::new(0.0, 0.0, 1.0), UnitQuaternion::identity());
+        let emboss = Emboss::default().with_voxel_size(0.1).with_depth(0.4);
+        let volume = |mesh: &CornerTableF| mass_properties(mesh, 1.0).volume;
+
+        let embossed: CornerTableF = emboss.apply(&cube, &shape, &top).unwrap();
+        let engraved: CornerTableF = emboss.with_mode(EmbossMode::Engrave).apply(&cube, &shape, &top).unwrap();
+        let stamp = 1.2 * 1.2 * 0.4;
+
+        assert!((volume(&embossed) - (8.0 + stamp)).abs() < 0.05);
+        assert!((volume(&engraved) - (8.0 - stamp)).abs() < 0.05);
+
+        // Only front face is stamped
+        let max_z = embossed.vertices().map(|vertex| embossed.vertex_position(&vertex).z).fold(f32::MIN, f32::max);
+        let min_z = embossed.vertices().map(|vertex| embossed.vertex_position(&vertex).z).fold(f32::MAX, f32::min);
+        assert!((max_z - 1.4).abs() < 0.03);
+        assert!((min_z + 1.0).abs() < 0.03);
+
+        // Draft narrows top of stamp
+        let drafted: CornerTableF = Emboss::default()
+            .with_voxel_size(0.1)
+            .with_depth(0.4)
+            .with_draft_angle(0.5)
+            .apply(&cube, &shape, &top)
+            .unwrap();
+        assert!(volume(&drafted) < volume(&embossed) - 0.1);
+
+        // Shape projected past mesh leaves it unchanged
+        let aside = Isometry3::from_parts(Translation3::new(5.0, 0.0, 1.0), UnitQuaternion::identity());
+        let unchanged: CornerTableF = Emboss::default().with_voxel_size(0.1).apply(&cube, &shape, &aside).unwrap();
+        assert!((volume(&unchanged) - 8.0).abs() < 0.05);
+    }
+}
@@ -0,0 +1,456 @@
+//!
+//! Exchange of volumes with NumPy. Volume is sampled on dense regular grid and stored
+//! in `.npy` arrays, or `.npz` archive holding values, gradients and placement of grid.
+//! Arrays are in C order and indexed as `values[x, y, z]`.
+//!
+
+use std::io::{self, Error, ErrorKind};
+
+use crate::{
+    geometry::primitives::box3::Box3,
+    helpers::aliases::{Vec3f, Vec3i},
+};
+
+use super::{volume::Volume, FloodFill, Sign, TreeNode};
+
+const NPY_MAGIC: &[u8] = b"\x93NUMPY";
+/// Header of `.npy` file including magic and length is padded to multiple of this value
+const NPY_ALIGNMENT: usize = 64;
+const ZIP_LOCAL_HEADER_SIGNATURE: u32 = 0x04034b50;
+const ZIP_CENTRAL_HEADER_SIGNATURE: u32 = 0x02014b50;
+const ZIP_END_SIGNATURE: u32 = 0x06054b50;
+
+///
+/// Volume sampled on dense grid. Grid point `(x, y, z)` is at `origin + (x, y, z) * spacing`.
+///
+#[derive(Debug, Clone)]
+pub struct DenseGrid {
+    pub origin: Vec3f,
+    pub spacing: f32,
+    /// Number of grid points along each axis
+    pub shape: [usize; 3],
+    /// Signed distances, truncated to max distance stored in narrow band of volume
+    pub values: Vec<f32>,
+    /// Gradients of distance, 3 values per grid point, zero outside of narrow band
+    pub gradients: Option<Vec<f32>>,
+}
+
+impl DenseGrid {
+    ///
+    /// Samples `volume` at grid points inside `bounds`. Grid is aligned to multiples of `spacing`,
+    /// so dense grids of the same volume with the same spacing match. Values are trilinearly interpolated,
+    /// outside of narrow band they are set to max distance stored in narrow band with sign of region.
+    ///
+    pub fn sample(volume: &Volume, bounds: &Box3<f32>, spacing: f32, gradients: bool) -> Self {
+        let min = (bounds.get_min() / spacing).map(|c| c.ceil());
+        let max = (bounds.get_max() / spacing).map(|c| c.floor());
+        let shape = [0, 1, 2].map(|axis| (max[axis] - min[axis] + 1.0).max(0.0) as usize);
+        let origin = min * spacing;
+
+        // Sign of regions outside of narrow band
+        let mut filled = volume.clone().unfold();
+        filled.grid_mut().flood_fill();
+        let band = volume
+            .active_values()
+            .iter()
+            .fold(volume.voxel_size(), |band, (_, value)| band.max(value.abs()));
+
+        let count = shape.iter().product();
+        let mut values = Vec::with_capacity(count);
+        let mut gradient_values = Vec::with_capacity(if gradients { count * 3 } else { 0 });
+
+        for x in 0..shape[0] {
+            for y in 0..shape[1] {
+                for z in 0..shape[2] {
+                    let point = origin + Vec3f::new(x as f32, y as f32, z as f32) * spacing;
+
+                    let value = match volume.sample(&point) {
+                        Some(value) => value.clamp(-band, band),
+                        None => {
+                            let index = volume.world_to_index(&point);
+
+                            match filled.grid().sign_at(&index) {
+                                Sign::Negative => -band,
+                                Sign::Positive => band,
+                            }
+                        }
+                    };
+                    values.push(value);
+
+                    if gradients {
+                        let gradient = volume.gradient(&point).unwrap_or_else(Vec3f::zeros);
+                        gradient_values.extend_from_slice(gradient.as_slice());
+                    }
+                }
+            }
+        }
+
+        Self {
+            origin,
+            spacing,
+            shape,
+            values,
+            gradients: gradients.then_some(gradient_values),
+        }
+    }
+
+    ///
+    /// Creates volume with voxel size equal to spacing. Only values within `narrow_band_width` voxels
+    /// from surface are stored. Origin is snapped to nearest multiple of spacing.
+    ///
+    pub fn to_volume(&self, narrow_band_width: usize) -> Volume {
+        let offset = (self.origin / self.spacing).map(|c| c.round() as isize);
+        let narrow_band_width = (narrow_band_width + 1) as f32 * self.spacing;
+        let mut volume = Volume::with_voxel_size(self.spacing);
+        let grid = volume.grid_mut();
+
+        for x in 0..self.shape[0] {
+            for y in 0..self.shape[1] {
+                for z in 0..self.shape[2] {
+                    let value = self.values[(x * self.shape[1] + y) * self.shape[2] + z];
+
+                    if !value.is_finite() || value.abs() > narrow_band_width {
+                        continue;
+                    }
+
+                    grid.insert(&(offset + Vec3i::new(x as isize, y as isize, z as isize)), value);
+                }
+            }
+        }
+
+        volume
+    }
+
+    /// Writes `.npz` archive with `values`, `origin`, `spacing` and optionally `gradients` arrays
+    pub fn to_npz(&self) -> Vec<u8> {
+        let mut entries = vec![
+            ("values.npy", write_npy(&self.values, &self.shape)),
+            ("origin.npy", write_npy(self.origin.as_slice(), &[3])),
+            ("spacing.npy", write_npy(&[self.spacing], &[])),
+        ];
+
+        if let Some(gradients) = &self.gradients {
+            let [x, y, z] = self.shape;
+            entries.push(("gradients.npy", write_npy(gradients, &[x, y, z, 3])));
+        }
+
+        write_zip(&entries)
+    }
+
+    ///
+    /// Reads `.npz` archive written by [DenseGrid::to_npz] or `numpy.savez`.
+    /// Compressed archives (`numpy.savez_compressed`) are not supported.
+    ///
+    pub fn from_npz(bytes: &[u8]) -> io::Result<Self> {
+        let entries = read_zip(bytes)?;
+        let entry = |name: &str| {
+            entries
+                .iter()
+                .find(|(entry_name, _)| entry_name == name)
+                .map(|(_, data)| read_npy(data))
+                .ok_or_else(|| invalid_data(&format!("Missing {} array", name)))?
+        };
+
+        let (values, shape) = entry("values.npy")?;
+        let (origin, _) = entry("origin.npy")?;
+        let (spacing, _) = entry("spacing.npy")?;
+
+        let shape: [usize; 3] = shape.try_into().map_err(|_| invalid_data("Values should be 3D array"))?;
+        if origin.len() != 3 || spacing.len() != 1 {
+            return Err(invalid_data("Invalid origin or spacing"));
+        }
+
+        let gradients = match entries.iter().any(|(name, _)| name == "gradients.npy") {
+            true => Some(entry("gradients.npy")?.0),
+            false => None,
+        };
+
+        Ok(Self {
+            origin: Vec3f::from_column_slice(&origin),
+            spacing: spacing[0],
+            shape,
+            values,
+            gradients,
+        })
+    }
+}
+
+///
+/// Samples signed distances of `volume` on dense grid inside `bounds` (see [DenseGrid::sample])
+/// and returns them as `.npy` file. Use [DenseGrid] to export gradients and placement of grid too.
+///
+/// ## Example
+/// ```ignore
+/// let npy = to_npy(&volume, &Box3::new(Vec3f::repeat(-1.0), Vec3f::repeat(1.0)), 0.05);
+/// std::fs::write("sdf.npy", npy)?;
+/// ```
+///
+pub fn to_npy(volume: &Volume, bounds: &Box3<f32>, spacing: f32) -> Vec<u8> {
+    let grid = DenseGrid::sample(volume, bounds, spacing, false);
+    write_npy(&grid.values, &grid.shape)
+}
+
+///
+/// Creates volume from `.npy` file holding 3D array of signed distances at `origin + index * spacing`,
+/// see [DenseGrid::to_volume].
+///
+pub fn from_npy(bytes: &[u8], origin: Vec3f, spacing: f32, narrow_band_width: usize) -> io::Result<Volume> {
+    let (values, shape) = read_npy(bytes)?;
+    let shape: [usize; 3] = shape.try_into().map_err(|_| invalid_data("Values should be 3D array"))?;
+
+    let grid = DenseGrid {
+        origin,
+        spacing,
+        shape,
+        values,
+        gradients: None,
+    };
+
+    Ok(grid.to_volume(narrow_band_width))
+}
+
+/// Writes little endian `f32` array in `.npy` format version 1.0
+fn write_npy(values: &[f32], shape: &[usize]) -> Vec<u8> {
+    let shape = match shape {
+        [size] => format!("({},)", size),
+        _ => format!("({})", shape.iter().map(|s| s.to_string()).collect::<Vec<_>>().join(", ")),
+    };
+    let mut header = format!("{{'descr': '<f4', 'fortran_order': False, 'shape': {}, }}", shape);
+
+    // Magic, version and header length take 10 bytes, header ends with new line
+    let unpadded = NPY_MAGIC.len() + 4 + header.len() + 1;
+    header.extend(std::iter::repeat_n(' ', unpadded.next_multiple_of(NPY_ALIGNMENT) - unpadded));
+    header.push('\n');
+
+    let mut bytes = Vec::with_capacity(NPY_MAGIC.len() + 4 + header.len() + values.len() * 4);
+    bytes.extend_from_slice(NPY_MAGIC);
+    bytes.extend_from_slice(&[1, 0]);
+    bytes.extend_from_slice(&(header.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(header.as_bytes());
+    values.iter().for_each(|value| bytes.extend_from_slice(&value.to_le_bytes()));
+
+    bytes
+}
+
+/// Reads C ordered array of little endian `f32` or `f64` from `.npy` file. Returns values and shape.
+fn read_npy(bytes: &[u8]) -> io::Result<(Vec<f32>, Vec<usize>)> {
+    if !bytes.starts_with(NPY_MAGIC) || bytes.len() < 10 {
+        return Err(invalid_data("Not a .npy file"));
+    }
+
+    let (header_start, header_len) = match bytes[6] {
+        1 => (10, u16::from_le_bytes([bytes[8], bytes[9]]) as usize),
+        2 | 3 if bytes.len() >= 12 => (12, u32::from_le_bytes([bytes[8], bytes[9], bytes[10], bytes[11]]) as usize),
+        _ => return Err(invalid_data("Unsupported .npy version")),
+    };
+
+    let header = bytes
+        .get(header_start..header_start + header_len)
+        .and_then(|header| std::str::from_utf8(header).ok())
+        .ok_or_else(|| invalid_data("Invalid .npy header"))?;
+
+    let field = |name: &str| {
+        let start = header.find(&format!("'{}'", name))? + name.len() + 2;
+        let value = header[start..].trim_start().strip_prefix(':')?.trim_start();
+        Some(value)
+    };
+
+    if field("fortran_order").is_some_and(|value| value.starts_with("True")) {
+        return Err(invalid_data("Fortran ordered arrays are not supported"));
+    }
+
+    let shape = field("shape")
+        .and_then(|value| value.strip_prefix('('))
+        .and_then(|value| value.split(')').next())
+        .ok_or_else(|| invalid_data("Missing shape of array"))?
+        .split(',')
+        .map(str::trim)
+        .filter(|size| !size.is_empty())
+        .map(|size| size.parse::<usize>().map_err(|_| invalid_data("Invalid shape of array")))
+        .collect::<io::Result<Vec<_>>>()?;
+
+    let descr = field("descr").ok_or_else(|| invalid_data("Missing type of array"))?;
+    let count = shape.iter().product::<usize>();
+    let data = &bytes[header_start + header_len..];
+
+    let values = if descr.starts_with("'<f4'") && data.len() >= count * 4 {
+        data.chunks_exact(4)
+            .take(count)
+            .map(|chunk| f32::from_le_bytes(chunk.try_into().unwrap()))
+            .collect()
+    } else if descr.starts_with("'<f8'") && data.len() >= count * 8 {
+        data.chunks_exact(8)
+            .take(count)
+            .map(|chunk| f64::from_le_bytes(chunk.try_into().unwrap()) as f32)
+            .collect()
+    } else {
+        return Err(invalid_data("Expected little endian float array"));
+    };
+
+    Ok((values, shape))
+}
+
+/// Writes uncompressed zip archive
+fn write_zip(entries: &[(&str, Vec<u8>)]) -> Vec<u8> {
+    let mut bytes = Vec::new();
+    let mut central_directory = Vec::new();
+
+    for (name, data) in entries {
+        let offset = bytes.len() as u32;
+        let crc = crc32(data);
+
+        // Version, flags, method (stored), time and date
+        let common = |bytes: &mut Vec<u8>| {
+            bytes.extend_from_slice(&20u16.to_le_bytes());
+            bytes.extend_from_slice(&[0; 8]);
+            bytes.extend_from_slice(&crc.to_le_bytes());
+            bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(&(data.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(&(name.len() as u16).to_le_bytes());
+            bytes.extend_from_slice(&0u16.to_le_bytes());
+        };
+
+        bytes.extend_from_slice(&ZIP_LOCAL_HEADER_SIGNATURE.to_le_bytes());
+        common(&mut bytes);
+        bytes.extend_from_slice(name.as_bytes());
+        bytes.extend_from_slice(data);
+
+        central_directory.extend_from_slice(&ZIP_CENTRAL_HEADER_SIGNATURE.to_le_bytes());
+        central_directory.extend_from_slice(&20u16.to_le_bytes());
+        common(&mut central_directory);
+        // Comment length, disk number, internal and external attributes
+        central_directory.extend_from_slice(&[0; 10]);
+        central_directory.extend_from_slice(&offset.to_le_bytes());
+        central_directory.extend_from_slice(name.as_bytes());
+    }
+
+    let directory_offset = bytes.len() as u32;
+    bytes.extend_from_slice(&central_directory);
+    bytes.extend_from_slice(&ZIP_END_SIGNATURE.to_le_bytes());
+    bytes.extend_from_slice(&[0; 4]);
+    bytes.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(&(entries.len() as u16).to_le_bytes());
+    bytes.extend_from_slice(&(central_directory.len() as u32).to_le_bytes());
+    bytes.extend_from_slice(&directory_offset.to_le_bytes());
+    bytes.extend_from_slice(&0u16.to_le_bytes());
+
+    bytes
+}
+
+/// Reads entries of uncompressed zip archive by its central directory
+fn read_zip(bytes: &[u8]) -> io::Result<Vec<(String, &[u8])>> {
+    let u16_at = |offset: usize| bytes.get(offset..offset + 2).map(|b| u16::from_le_bytes([b[0], b[1]]) as usize);
+    let u32_at = |offset: usize| bytes.get(offset..offset + 4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]]));
+    let truncated = || invalid_data("Truncated zip archive");
+
+    // End of central directory record is last, it may be followed by comment
+    let end = (0..bytes.len().saturating_sub(21))
+        .rev()
+        .find(|&offset| u32_at(offset) == Some(ZIP_END_SIGNATURE))
+        .ok_or_else(|| invalid_data("Not a zip archive"))?;
+
+    let entries_count = u16_at(end + 10).ok_or_else(truncated)?;
+    let mut offset = u32_at(end + 16).ok_or_else(truncated)? as usize;
+    let mut entries = Vec::with_capacity(entries_count);
+
+    for _ in 0..entries_count {
+        if u32_at(offset) != Some(ZIP_CENTRAL_HEADER_SIGNATURE) {
+            return Err(invalid_data("Invalid zip central directory"));
+        }
+
+        let method = u16_at(offset + 10).ok_or_else(truncated)?;
+        let size = u32_at(offset + 20).ok_or_else(truncated)? as usize;
+        let name_len = u16_at(offset + 28).ok_or_else(truncated)?;
+        let extra_len = u16_at(offset + 30).ok_or_else(truncated)?;
+        let comment_len = u16_at(offset + 32).ok_or_else(truncated)?;
+        let local_offset = u32_at(offset + 42).ok_or_else(truncated)? as usize;
+        let name = bytes.get(offset + 46..offset + 46 + name_len).ok_or_else(truncated)?;
+
+        if method != 0 {
+            return Err(Error::new(ErrorKind::Unsupported, "Compressed zip archives are not supported"));
+        }
+
+        let local_name_len = u16_at(local_offset + 26).ok_or_else(truncated)?;
+        let local_extra_len = u16_at(local_offset + 28).ok_or_else(truncated)?;
+        let data_start = local_offset + 30 + local_name_len + local_extra_len;
+        let data = bytes.get(data_start..data_start + size).ok_or_else(truncated)?;
+
+        entries.push((String::from_utf8_lossy(name).into_owned(), data));
+        offset += 46 + name_len + extra_len + comment_len;
+    }
+
+    Ok(entries)
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = !0u32;
+
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            let mask = (crc & 1).wrapping_neg();
+            crc = (crc >> 1) ^ (0xEDB88320 & mask);
+        }
+    }
+
+    !crc
+}
+
+#[inline]
+fn invalid_data(message: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, message)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{crc32, from_npy, read_npy, to_npy, DenseGrid};
+    use crate::{
+        geometry::primitives::box3::Box3,
+        helpers::aliases::Vec3f,
+        voxel::prelude::VolumeBuilder,
+    };
+
+    #[test]
+    fn test_npy_round_trip() {
+        assert_eq!(crc32(b"123456789"), 0xCBF43926);
+
+        let sphere = VolumeBuilder::default().with_voxel_size(0.1).sphere(1.0, Vec3f::zeros());
+        let bounds = Box3::new(Vec3f::repeat(-1.52), Vec3f::repeat(1.52));
+
+        let npy = to_npy(&sphere, &bounds, 0.1);
+        assert_eq!((npy.len() - 31 * 31 * 31 * 4) % 64, 0);
+
+        let (values, shape) = read_npy(&npy).unwrap();
+        assert_eq!(shape, vec![31, 31, 31]);
+        assert!(values[(15 * 31 + 15) * 31 + 15] < 0.0);
+        assert!(values[0] > 0.0);
+
+        let volume = from_npy(&npy, Vec3f::repeat(-1.5), 0.1, 2).unwrap();
+        for point in [Vec3f::new(1.0, 0.0, 0.0), Vec3f::new(0.0, -0.95, 0.0), Vec3f::new(0.6, 0.6, 0.5)] {
+            let expected = sphere.sample(&point).unwrap();
+            assert!((volume.sample(&point).unwrap() - expected).abs() < 1e-5);
+        }
+    }
+
+    #[test]
+    fn test_npz_round_trip() {
+        let sphere = VolumeBuilder::default().with_voxel_size(0.1).sphere(1.0, Vec3f::zeros());
+        let bounds = Box3::new(Vec3f::new(-1.23, -1.22, 0.0), Vec3f::new(1.21, 1.27, 1.21));
+
+        let grid = DenseGrid::sample(&sphere, &bounds, 0.05, true);
+        assert_eq!(grid.shape, [49, 50, 25]);
+        assert!((grid.origin - Vec3f::new(-1.2, -1.2, 0.0)).norm() < 1e-5);
+
+        let read = DenseGrid::from_npz(&grid.to_npz()).unwrap();
+        assert_eq!(read.shape, grid.shape);
+        assert_eq!(read.values, grid.values);
+        assert_eq!(read.origin, grid.origin);
+        assert_eq!(read.spacing, 0.05);
+
+        // Gradient points outwards on surface
+        let gradients = read.gradients.unwrap();
+        let index = (44 * 50 + 24) * 25;
+        let gradient = Vec3f::from_column_slice(&gradients[index * 3..index * 3 + 3]);
+        assert!((gradient - Vec3f::x()).norm() < 0.05);
+    }
+}
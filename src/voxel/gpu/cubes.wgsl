@@ -0,0 +1,95 @@
+// Classifies cubes of marching cubes and computes intersections of iso-surface with grid edges.
+// Input is a set of blocks, each is a leaf node of grid (8^3 grid points) with one layer of neighbors.
+
+struct Params {
+    iso_value: f32,
+    blocks_count: u32,
+    _pad0: u32,
+    _pad1: u32,
+}
+
+const LEAF_SIZE: u32 = 8u;
+const BLOCK_SIZE: u32 = 9u;
+const MIN_ABS_VERTEX_VALUE: f32 = 1e-6;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> values: array<f32>;
+// 1 where grid point is in narrow band, 0 otherwise
+@group(0) @binding(2) var<storage, read> present: array<u32>;
+// 1 for cubes crossed by iso-surface
+@group(0) @binding(3) var<storage, read_write> crossed: array<u32>;
+// Parameter of intersection along X, Y and Z edges starting at grid point, -1 when there is no intersection
+@group(0) @binding(4) var<storage, read_write> intersections: array<f32>;
+
+fn block_index(block: u32, p: vec3<u32>) -> u32 {
+    return block * BLOCK_SIZE * BLOCK_SIZE * BLOCK_SIZE + (p.x * BLOCK_SIZE + p.y) * BLOCK_SIZE + p.z;
+}
+
+fn is_negative(value: f32) -> bool {
+    return (bitcast<u32>(value) >> 31u) == 1u;
+}
+
+fn intersection(block: u32, v1: vec3<u32>, v2: vec3<u32>) -> f32 {
+    let i1 = block_index(block, v1);
+    let i2 = block_index(block, v2);
+
+    if present[i1] == 0u || present[i2] == 0u {
+        return -1.0;
+    }
+
+    let v1_val = values[i1] - params.iso_value;
+    let v2_val = values[i2] - params.iso_value;
+
+    if is_negative(v1_val) == is_negative(v2_val) {
+        return -1.0;
+    }
+
+    let a = max(abs(v1_val), MIN_ABS_VERTEX_VALUE);
+    let b = max(abs(v2_val), MIN_ABS_VERTEX_VALUE);
+
+    return a / (a + b);
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    let cubes_per_block = LEAF_SIZE * LEAF_SIZE * LEAF_SIZE;
+    let block = id.x / cubes_per_block;
+
+    if block >= params.blocks_count {
+        return;
+    }
+
+    let local = id.x % cubes_per_block;
+    let p = vec3<u32>(local / (LEAF_SIZE * LEAF_SIZE), (local / LEAF_SIZE) % LEAF_SIZE, local % LEAF_SIZE);
+
+    intersections[id.x * 3u] = intersection(block, p, p + vec3<u32>(1u, 0u, 0u));
+    intersections[id.x * 3u + 1u] = intersection(block, p, p + vec3<u32>(0u, 1u, 0u));
+    intersections[id.x * 3u + 2u] = intersection(block, p, p + vec3<u32>(0u, 0u, 1u));
+
+    var inside = 0u;
+    var outside = 0u;
+
+    for (var corner = 0u; corner < 8u; corner++) {
+        let offset = vec3<u32>(corner & 1u, (corner >> 1u) & 1u, (corner >> 2u) & 1u);
+        let index = block_index(block, p + offset);
+
+        if present[index] == 0u {
+            crossed[id.x] = 0u;
+            return;
+        }
+
+        var value = values[index] - params.iso_value;
+
+        if abs(value) < MIN_ABS_VERTEX_VALUE {
+            value = select(MIN_ABS_VERTEX_VALUE, -MIN_ABS_VERTEX_VALUE, is_negative(value));
+        }
+
+        if value < 0.0 {
+            inside++;
+        } else {
+            outside++;
+        }
+    }
+
+    crossed[id.x] = select(0u, 1u, inside > 0u && outside > 0u);
+}
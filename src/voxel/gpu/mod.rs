@@ -0,0 +1,362 @@
+use std::{collections::HashMap, sync::{mpsc, OnceLock}};
+
+use wgpu::util::DeviceExt;
+
+use crate::{geometry::primitives::triangle3::Triangle3, helpers::aliases::Vec3i};
+
+/// Number of grid points along one axis of tile distances are splatted into, should match `splat.wgsl`
+const TILE_SIZE: isize = 32;
+/// Number of grid points in tile
+const TILE_POINTS: usize = (TILE_SIZE * TILE_SIZE * TILE_SIZE) as usize;
+/// Size of triangle item in `splat.wgsl`
+const ITEM_SIZE: usize = 96;
+/// Number of grid points along one axis of block classified by `cubes.wgsl`, leaf node with one layer of neighbors
+pub(super) const BLOCK_SIZE: usize = 9;
+/// Number of cubes in block
+pub(super) const BLOCK_CUBES: usize = 512;
+/// Upper limit of single buffer size
+const MAX_BUFFER_SIZE: u64 = 64 << 20;
+const WORKGROUP_SIZE: u32 = 64;
+
+///
+/// Device and compute pipelines used to accelerate mesh to volume conversion and marching cubes.
+/// Created once on first use and shared, `None` when there is no suitable adapter.
+///
+pub(super) struct GpuContext {
+    device: wgpu::Device,
+    queue: wgpu::Queue,
+    splat: wgpu::ComputePipeline,
+    cubes: wgpu::ComputePipeline,
+    max_buffer_size: u64,
+    max_workgroups: u32,
+}
+
+impl GpuContext {
+    pub(super) fn get() -> Option<&'static Self> {
+        static CONTEXT: OnceLock<Option<GpuContext>> = OnceLock::new();
+        CONTEXT.get_or_init(|| pollster::block_on(Self::new())).as_ref()
+    }
+
+    async fn new() -> Option<Self> {
+        let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
+            backends: wgpu::Backends::all(),
+            ..Default::default()
+        });
+        let adapter = instance
+            .request_adapter(&wgpu::RequestAdapterOptions {
+                power_preference: wgpu::PowerPreference::HighPerformance,
+                ..Default::default()
+            })
+            .await?;
+
+        let limits = adapter.limits();
+        if limits.max_storage_buffers_per_shader_stage < 4 || limits.max_compute_invocations_per_workgroup < WORKGROUP_SIZE {
+            return None;
+        }
+
+        let (device, queue) = adapter
+            .request_device(
+                &wgpu::DeviceDescriptor {
+                    label: Some("baby_shark"),
+                    required_limits: limits.clone(),
+                    ..Default::default()
+                },
+                None,
+            )
+            .await
+            .ok()?;
+
+        let pipeline = |shader: wgpu::ShaderModuleDescriptor| {
+            let module = device.create_shader_module(shader);
+            device.create_compute_pipeline(&wgpu::ComputePipelineDescriptor {
+                label: None,
+                layout: None,
+                module: &module,
+                entry_point: Some("main"),
+                compilation_options: Default::default(),
+                cache: None,
+            })
+        };
+
+        let splat = pipeline(wgpu::include_wgsl!("splat.wgsl"));
+        let cubes = pipeline(wgpu::include_wgsl!("cubes.wgsl"));
+
+        Some(Self {
+            splat,
+            cubes,
+            max_buffer_size: MAX_BUFFER_SIZE
+                .min(limits.max_buffer_size)
+                .min(limits.max_storage_buffer_binding_size as u64),
+            max_workgroups: limits.max_compute_workgroups_per_dimension,
+            device,
+            queue,
+        })
+    }
+
+    ///
+    /// Computes unsigned distances from triangles to grid points in their ranges (inclusive).
+    /// Distance of each grid point (min over triangles) is passed to `visit` once.
+    /// Returns `false` when computation failed.
+    ///
+    pub(super) fn unsigned_distances<TVisit>(&self, triangles: &[(Triangle3<f32>, Vec3i, Vec3i)], voxel_size: f32, mut visit: TVisit) -> bool
+    where
+        TVisit: FnMut(Vec3i, f32),
+    {
+        // Triangles are binned into tiles, so every tile is computed in single batch
+        let mut tiles: HashMap<Vec3i, Vec<usize>> = HashMap::new();
+        for (index, (_, min, max)) in triangles.iter().enumerate() {
+            let (tile_min, tile_max) = (min.map(|c| c.div_euclid(TILE_SIZE)), max.map(|c| c.div_euclid(TILE_SIZE)));
+
+            for x in tile_min.x..=tile_max.x {
+                for y in tile_min.y..=tile_max.y {
+                    for z in tile_min.z..=tile_max.z {
+                        tiles.entry(Vec3i::new(x, y, z)).or_default().push(index);
+                    }
+                }
+            }
+        }
+
+        let mut tiles: Vec<_> = tiles.into_iter().collect();
+        tiles.sort_unstable_by_key(|(tile, _)| (tile.x, tile.y, tile.z));
+
+        let tiles_per_batch = (self.max_buffer_size as usize / (TILE_POINTS * 4)).max(1);
+        let items_per_dispatch = (self.max_buffer_size as usize / ITEM_SIZE).min((self.max_workgroups * WORKGROUP_SIZE) as usize);
+        let distances_size = (tiles.len().min(tiles_per_batch) * TILE_POINTS * 4) as u64;
+        let distances = self.storage_buffer(distances_size);
+
+        for batch in tiles.chunks(tiles_per_batch) {
+            let mut items = Vec::new();
+
+            for (slot, (tile, tile_triangles)) in batch.iter().enumerate() {
+                let origin = tile * TILE_SIZE;
+                let end = origin.add_scalar(TILE_SIZE - 1);
+
+                for &index in tile_triangles {
+                    let (triangle, min, max) = &triangles[index];
+                    items.push((triangle, min.sup(&origin), max.inf(&end), origin, slot));
+                }
+            }
+
+            let mut encoder = self.device.create_command_encoder(&Default::default());
+            encoder.clear_buffer(&distances, 0, None);
+
+            for chunk in items.chunks(items_per_dispatch) {
+                let mut bytes = Vec::with_capacity(chunk.len() * ITEM_SIZE);
+
+                for (triangle, min, max, origin, slot) in chunk {
+                    for p in [triangle.p1(), triangle.p2(), triangle.p3()] {
+                        bytes.extend([p.x, p.y, p.z, 0.0].iter().flat_map(|c| c.to_le_bytes()));
+                    }
+
+                    for (v, w) in [(min, 0), (max, 0), (origin, *slot as i32)] {
+                        bytes.extend([v.x as i32, v.y as i32, v.z as i32, w].iter().flat_map(|c| c.to_le_bytes()));
+                    }
+                }
+
+                let params = self.uniform_buffer(voxel_size.to_bits(), chunk.len() as u32);
+                let items_buffer = self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+                    label: None,
+                    contents: &bytes,
+                    usage: wgpu::BufferUsages::STORAGE,
+                });
+
+                self.dispatch(&mut encoder, &self.splat, &[&params, &items_buffer, &distances], chunk.len() as u32);
+            }
+
+            let Some(bytes) = self.read_buffer(encoder, &distances, (batch.len() * TILE_POINTS * 4) as u64) else {
+                return false;
+            };
+
+            for ((tile, _), tile_bytes) in batch.iter().zip(bytes.chunks_exact(TILE_POINTS * 4)) {
+                let origin = tile * TILE_SIZE;
+
+                for (index, value) in tile_bytes.chunks_exact(4).enumerate() {
+                    let bits = u32::from_le_bytes([value[0], value[1], value[2], value[3]]);
+
+                    if bits == 0 {
+                        continue;
+                    }
+
+                    let index = index as isize;
+                    let local = Vec3i::new(index / (TILE_SIZE * TILE_SIZE), (index / TILE_SIZE) % TILE_SIZE, index % TILE_SIZE);
+                    visit(origin + local, f32::from_bits(!bits));
+                }
+            }
+        }
+
+        true
+    }
+
+    ///
+    /// Classifies cubes of blocks of [BLOCK_SIZE]^3 grid points, `present` marks grid points in narrow band.
+    /// Returns flags of cubes crossed by iso-surface and parameters of intersections of iso-surface
+    /// with X, Y and Z edges starting at cube origins (negative when edge is not crossed).
+    ///
+    pub(super) fn classify_cubes(&self, values: &[f32], present: &[u32], iso_value: f32) -> Option<(Vec<u32>, Vec<f32>)> {
+        let block_points = BLOCK_SIZE * BLOCK_SIZE * BLOCK_SIZE;
+        let blocks_count = values.len() / block_points;
+        let blocks_per_batch = (self.max_buffer_size as usize / (BLOCK_CUBES * 3 * 4))
+            .min((self.max_workgroups * WORKGROUP_SIZE) as usize / BLOCK_CUBES)
+            .max(1);
+
+        let mut active = Vec::with_capacity(blocks_count * BLOCK_CUBES);
+        let mut intersections = Vec::with_capacity(blocks_count * BLOCK_CUBES * 3);
+
+        for first in (0..blocks_count).step_by(blocks_per_batch) {
+            let count = blocks_per_batch.min(blocks_count - first);
+            let range = first * block_points..(first + count) * block_points;
+
+            let params = self.uniform_buffer(iso_value.to_bits(), count as u32);
+            let values_buffer = self.storage_buffer_init(values[range.clone()].iter().flat_map(|v| v.to_le_bytes()).collect());
+            let present_buffer = self.storage_buffer_init(present[range].iter().flat_map(|v| v.to_le_bytes()).collect());
+            let active_buffer = self.storage_buffer((count * BLOCK_CUBES * 4) as u64);
+            let intersections_buffer = self.storage_buffer((count * BLOCK_CUBES * 3 * 4) as u64);
+
+            let mut encoder = self.device.create_command_encoder(&Default::default());
+            self.dispatch(
+                &mut encoder,
+                &self.cubes,
+                &[&params, &values_buffer, &present_buffer, &active_buffer, &intersections_buffer],
+                (count * BLOCK_CUBES) as u32,
+            );
+
+            let bytes = self.read_buffer(encoder, &active_buffer, active_buffer.size())?;
+            active.extend(bytes.chunks_exact(4).map(|b| u32::from_le_bytes([b[0], b[1], b[2], b[3]])));
+
+            let encoder = self.device.create_command_encoder(&Default::default());
+            let bytes = self.read_buffer(encoder, &intersections_buffer, intersections_buffer.size())?;
+            intersections.extend(bytes.chunks_exact(4).map(|b| f32::from_le_bytes([b[0], b[1], b[2], b[3]])));
+        }
+
+        Some((active, intersections))
+    }
+
+    fn dispatch(&self, encoder: &mut wgpu::CommandEncoder, pipeline: &wgpu::ComputePipeline, buffers: &[&wgpu::Buffer], invocations: u32) {
+        let entries: Vec<_> = buffers
+            .iter()
+            .enumerate()
+            .map(|(binding, buffer)| wgpu::BindGroupEntry {
+                binding: binding as u32,
+                resource: buffer.as_entire_binding(),
+            })
+            .collect();
+
+        let bind_group = self.device.create_bind_group(&wgpu::BindGroupDescriptor {
+            label: None,
+            layout: &pipeline.get_bind_group_layout(0),
+            entries: &entries,
+        });
+
+        let mut pass = encoder.begin_compute_pass(&Default::default());
+        pass.set_pipeline(pipeline);
+        pass.set_bind_group(0, &bind_group, &[]);
+        pass.dispatch_workgroups(invocations.div_ceil(WORKGROUP_SIZE), 1, 1);
+    }
+
+    /// Submits `encoder` with copy of `buffer` to host memory and waits for result
+    fn read_buffer(&self, mut encoder: wgpu::CommandEncoder, buffer: &wgpu::Buffer, size: u64) -> Option<Vec<u8>> {
+        let staging = self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size,
+            usage: wgpu::BufferUsages::MAP_READ | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        });
+
+        encoder.copy_buffer_to_buffer(buffer, 0, &staging, 0, size);
+        self.queue.submit([encoder.finish()]);
+
+        let (sender, receiver) = mpsc::channel();
+        let slice = staging.slice(..);
+        slice.map_async(wgpu::MapMode::Read, move |result| {
+            let _ = sender.send(result);
+        });
+        self.device.poll(wgpu::Maintain::Wait);
+        receiver.recv().ok()?.ok()?;
+
+        let bytes = slice.get_mapped_range().to_vec();
+        staging.unmap();
+
+        Some(bytes)
+    }
+
+    /// Uniform buffer of `Params` struct, shared by both shaders: 32-bit parameter and number of items
+    fn uniform_buffer(&self, parameter: u32, count: u32) -> wgpu::Buffer {
+        let contents: Vec<u8> = [parameter, count, 0, 0].iter().flat_map(|v| v.to_le_bytes()).collect();
+        self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: None,
+            contents: &contents,
+            usage: wgpu::BufferUsages::UNIFORM,
+        })
+    }
+
+    fn storage_buffer(&self, size: u64) -> wgpu::Buffer {
+        self.device.create_buffer(&wgpu::BufferDescriptor {
+            label: None,
+            size: size.max(4),
+            usage: wgpu::BufferUsages::STORAGE | wgpu::BufferUsages::COPY_SRC | wgpu::BufferUsages::COPY_DST,
+            mapped_at_creation: false,
+        })
+    }
+
+    fn storage_buffer_init(&self, contents: Vec<u8>) -> wgpu::Buffer {
+        self.device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: None,
+            contents: &contents,
+            usage: wgpu::BufferUsages::STORAGE,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::GpuContext;
+    use crate::{
+        geometry::{primitives::triangle3::Triangle3, traits::ClosestPoint3},
+        helpers::aliases::{Vec3f, Vec3i},
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+        remeshing::voxel::VoxelRemesher,
+    };
+
+    #[test]
+    fn test_unsigned_distances() {
+        let Some(gpu) = GpuContext::get() else {
+            return;
+        };
+
+        // Triangle spanning several tiles
+        let triangle = Triangle3::new(Vec3f::new(-1.0, -0.5, 0.2), Vec3f::new(1.5, -0.3, -0.4), Vec3f::new(0.1, 1.2, 0.3));
+        let (min, max) = (Vec3i::new(-24, -14, -12), Vec3i::new(34, 28, 10));
+
+        let mut visited = 0;
+        let computed = gpu.unsigned_distances(&[(triangle, min, max)], 0.05, |index, distance| {
+            let point = index.cast::<f32>() * 0.05;
+            assert!((distance - (triangle.closest_point(&point) - point).norm()).abs() < 1e-5);
+            visited += 1;
+        });
+
+        assert!(computed);
+        assert_eq!(visited, 59 * 43 * 23);
+    }
+
+    #[test]
+    fn test_gpu_remeshing_matches_cpu() {
+        if GpuContext::get().is_none() {
+            return;
+        }
+
+        let mesh: CornerTableF = primitives::icosphere(1.0, 3);
+        let cpu = VoxelRemesher::default().with_voxel_size(0.1).remesh(&mesh).unwrap();
+        let gpu = VoxelRemesher::default().with_voxel_size(0.1).with_gpu(true).remesh(&mesh).unwrap();
+
+        assert_eq!(cpu.faces().count(), gpu.faces().count());
+        assert_eq!(cpu.vertices().count(), gpu.vertices().count());
+
+        // Distances computed on GPU may differ in last bits
+        let cpu_vertices: Vec<_> = cpu.vertices().map(|v| *cpu.vertex_position(&v)).collect();
+        for vertex in gpu.vertices() {
+            let position = gpu.vertex_position(&vertex);
+            let closest = cpu_vertices.iter().map(|p| (p - position).norm()).fold(f32::INFINITY, f32::min);
+            assert!(closest < 1e-4);
+        }
+    }
+}
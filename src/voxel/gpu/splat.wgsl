@@ -0,0 +1,101 @@
+// Splats unsigned distances to triangles into dense tiles of grid points.
+// Every item is a triangle clipped to one tile. Distances are merged by atomic max of inverted float bits:
+// order of bits of non-negative floats matches order of floats, and zero (cleared buffer) means no distance.
+
+struct Params {
+    voxel_size: f32,
+    items_count: u32,
+    _pad0: u32,
+    _pad1: u32,
+}
+
+struct Item {
+    p1: vec4<f32>,
+    p2: vec4<f32>,
+    p3: vec4<f32>,
+    // Grid points range, inclusive
+    min: vec4<i32>,
+    max: vec4<i32>,
+    // Tile origin, `w` is index of tile in distances buffer
+    tile: vec4<i32>,
+}
+
+const TILE_SIZE: i32 = 32;
+
+@group(0) @binding(0) var<uniform> params: Params;
+@group(0) @binding(1) var<storage, read> items: array<Item>;
+@group(0) @binding(2) var<storage, read_write> distances: array<atomic<u32>>;
+
+// Closest point on triangle, "Real-Time Collision Detection" by Christer Ericson
+fn closest_point(p: vec3<f32>, a: vec3<f32>, b: vec3<f32>, c: vec3<f32>) -> vec3<f32> {
+    let ab = b - a;
+    let ac = c - a;
+    let ap = p - a;
+    let d1 = dot(ab, ap);
+    let d2 = dot(ac, ap);
+
+    if d1 <= 0.0 && d2 <= 0.0 {
+        return a;
+    }
+
+    let bp = p - b;
+    let d3 = dot(ab, bp);
+    let d4 = dot(ac, bp);
+
+    if d3 >= 0.0 && d4 <= d3 {
+        return b;
+    }
+
+    let vc = d1 * d4 - d3 * d2;
+
+    if vc <= 0.0 && d1 >= 0.0 && d3 <= 0.0 {
+        return a + ab * (d1 / (d1 - d3));
+    }
+
+    let cp = p - c;
+    let d5 = dot(ab, cp);
+    let d6 = dot(ac, cp);
+
+    if d6 >= 0.0 && d5 <= d6 {
+        return c;
+    }
+
+    let vb = d5 * d2 - d1 * d6;
+
+    if vb <= 0.0 && d2 >= 0.0 && d6 <= 0.0 {
+        return a + ac * (d2 / (d2 - d6));
+    }
+
+    let va = d3 * d6 - d5 * d4;
+
+    if va <= 0.0 && (d4 - d3) >= 0.0 && (d5 - d6) >= 0.0 {
+        return b + (c - b) * ((d4 - d3) / ((d4 - d3) + (d5 - d6)));
+    }
+
+    let denom = 1.0 / (va + vb + vc);
+    return a + ab * (vb * denom) + ac * (vc * denom);
+}
+
+@compute @workgroup_size(64)
+fn main(@builtin(global_invocation_id) id: vec3<u32>) {
+    if id.x >= params.items_count {
+        return;
+    }
+
+    let item = items[id.x];
+    let tile_offset = u32(item.tile.w) * u32(TILE_SIZE * TILE_SIZE * TILE_SIZE);
+
+    for (var x = item.min.x; x <= item.max.x; x++) {
+        for (var y = item.min.y; y <= item.max.y; y++) {
+            for (var z = item.min.z; z <= item.max.z; z++) {
+                let grid_point = vec3<f32>(f32(x), f32(y), f32(z)) * params.voxel_size;
+                let closest = closest_point(grid_point, item.p1.xyz, item.p2.xyz, item.p3.xyz);
+                let distance = length(closest - grid_point);
+
+                let local = vec3<i32>(x, y, z) - item.tile.xyz;
+                let index = tile_offset + u32((local.x * TILE_SIZE + local.y) * TILE_SIZE + local.z);
+                atomicMax(&distances[index], ~bitcast<u32>(distance));
+            }
+        }
+    }
+}
@@ -42,9 +42,9 @@ where
     fn make_child_inside(&mut self, offset: usize) {
         self.remove_child(offset);
         self.value_mask.on(offset);
-        self.childs[offset] = ChildUnion {
-            tile: TChild::Value::far(),
-        };
+        let mut tile = TChild::Value::far();
+        tile.set_sign(Sign::Negative);
+        self.childs[offset] = ChildUnion { tile };
     }
 }
 
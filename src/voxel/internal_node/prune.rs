@@ -0,0 +1,86 @@
+use super::*;
+use crate::{data_structures::bitset::BitSet, voxel::utils::uniform_value};
+
+impl<
+        TChild,
+        const BRANCHING: usize,
+        const BRANCHING_TOTAL: usize,
+        const SIZE: usize,
+        const BIT_SIZE: usize,
+        const PARALLEL: bool,
+    > Prune
+    for InternalNode<TChild::Value, TChild, BRANCHING, BRANCHING_TOTAL, SIZE, BIT_SIZE, PARALLEL>
+where
+    TChild: Prune,
+    TChild::Value: Signed,
+{
+    fn prune(&mut self, tolerance: Self::Value) {
+        for offset in 0..SIZE {
+            let value = match self.child_mut(offset) {
+                Some(OneOf::T1(branch)) => {
+                    branch.prune(tolerance);
+                    branch.uniform_value(tolerance)
+                }
+                _ => continue,
+            };
+
+            if let Some(value) = value {
+                self.remove_branch(offset);
+                self.value_mask.on(offset);
+                self.childs[offset] = ChildUnion { tile: value };
+            }
+        }
+    }
+
+    fn uniform_value(&self, tolerance: Self::Value) -> Option<Self::Value> {
+        // Full value mask means that all childs are tiles
+        if !self.value_mask.is_full() {
+            return None;
+        }
+
+        uniform_value((0..SIZE).map(|offset| unsafe { self.childs[offset].tile }), tolerance)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        data_structures::bitset::BitSet,
+        helpers::aliases::Vec3i,
+        static_vdb,
+        voxel::{utils::box_indices, *},
+    };
+
+    #[test]
+    fn test_prune() {
+        type Internal = static_vdb!(f32, 2, 1);
+        type Leaf = <Internal as TreeNode>::Child;
+
+        let mut node = Internal::empty(Vec3i::zeros());
+        box_indices(0, Internal::resolution() as isize).for_each(|i| node.insert(&i, -1.0));
+        node.insert(&Vec3i::new(0, 0, 0), -1.1);
+        node.insert(&Vec3i::new(7, 7, 7), 1.0);
+
+        node.prune(0.0);
+        assert!(node.child_mask.is_on(0));
+        assert!(node.child_mask.is_on(Internal::SIZE - 1));
+        assert_eq!(node.value_mask.iter().filter(|on| *on).count(), Internal::SIZE - 2);
+        assert_eq!(node.uniform_value(0.0), None);
+
+        // Values are kept
+        box_indices(Leaf::resolution() as isize, Internal::resolution() as isize - 1)
+            .for_each(|i| assert_eq!(node.at(&i), Some(&-1.0)));
+        assert_eq!(node.at(&Vec3i::new(0, 0, 0)), Some(&-1.1));
+
+        // Leaf with values of different signs is never pruned
+        node.prune(5.0);
+        assert!(node.child_mask.is_on(Internal::SIZE - 1));
+        assert!(node.child_mask.is_off(0));
+        assert_eq!(node.uniform_value(5.0), None);
+
+        node.insert(&Vec3i::new(7, 7, 7), -1.0);
+        node.prune(0.5);
+        assert!(node.child_mask.is_empty());
+        assert_eq!(node.uniform_value(0.5), Some(-1.1));
+    }
+}
@@ -184,10 +184,28 @@ where
         clone
     }
 
-    fn visit_leafs_par<T: ParVisitor<Self::Leaf>>(&self, visitor: &T) {
-        use rayon::prelude::*;
+    fn memory_usage(&self) -> usize {
+        let branches: usize = self
+            .childs()
+            .map(|(_, child)| match child {
+                OneOf::T1(branch) => branch.memory_usage(),
+                OneOf::T2(_) => 0,
+            })
+            .sum();
+
+        std::mem::size_of::<Self>() + branches
+    }
+
+    #[inline]
+    fn depth() -> usize {
+        TChild::depth() + 1
+    }
 
+    fn visit_leafs_par<T: ParVisitor<Self::Leaf>>(&self, visitor: &T) {
+        #[cfg(feature = "rayon")]
         if PARALLEL {
+            use rayon::prelude::*;
+
             (0..SIZE)
                 .filter_map(|offset| match self.child(offset) {
                     Some(OneOf::T1(branch)) => Some(branch),
@@ -211,17 +229,19 @@ where
 
                     visitor.tile(tile);
                 });
-        } else {
-            for (offset, child) in self.childs() {
-                match child {
-                    OneOf::T1(branch) => branch.visit_leafs_par(visitor),
-                    OneOf::T2(tile) => visitor.tile(Tile {
-                        origin: self.offset_to_global_index(offset),
-                        size: TChild::resolution(),
-                        value: *tile,
-                    }),
-                };
-            }
+
+            return;
+        }
+
+        for (offset, child) in self.childs() {
+            match child {
+                OneOf::T1(branch) => branch.visit_leafs_par(visitor),
+                OneOf::T2(tile) => visitor.tile(Tile {
+                    origin: self.offset_to_global_index(offset),
+                    size: TChild::resolution(),
+                    value: *tile,
+                }),
+            };
         }
     }
 
@@ -1,10 +1,15 @@
 use super::*;
-use crate::voxel::utils::{partial_max, partial_min};
+use crate::{
+    data_structures::bitset::BitSet,
+    voxel::utils::{partial_max, partial_min},
+};
 use std::ops::Neg;
 
 ///
-/// CSG operations for leaf nodes
-/// This implementation assuming that nodes are flood filler prior to CSG operations
+/// CSG operations for leaf nodes.
+/// Nodes don't have to be flood filled prior to CSG operations: signs of inactive voxels are resolved from active ones,
+/// empty nodes keep signs of their values (outside unless filled with sign, see [FloodFill::fill_with_sign]).
+/// Voxel of result is active only when its value is taken from active voxel of one of operands.
 ///
 impl<
         TValue: Signed + Neg<Output = Self::Value>,
@@ -14,33 +19,69 @@ impl<
         const BIT_SIZE: usize,
     > Csg for LeafNode<TValue, BRANCHING, BRANCHING_TOTAL, SIZE, BIT_SIZE>
 {
-    fn union(&mut self, other: Box<Self>) {
-        for i in 0..SIZE {
-            self.values[i] = partial_min(self.values[i], other.values[i]);
-        }
+    fn union(&mut self, mut other: Box<Self>) {
+        self.combine(&mut other, partial_min);
+    }
 
-        self.value_mask |= other.value_mask;
+    fn subtract(&mut self, mut other: Box<Self>) {
+        other.flip_signs();
+        self.combine(&mut other, partial_max);
     }
 
-    fn subtract(&mut self, other: Box<Self>) {
+    fn intersect(&mut self, mut other: Box<Self>) {
+        self.combine(&mut other, partial_max);
+    }
+
+    fn flip_signs(&mut self) {
+        self.resolve_inactive_values();
+
         for i in 0..SIZE {
-            self.values[i] = partial_max(self.values[i], -other.values[i]);
+            self.values[i] = -self.values[i];
         }
-
-        self.value_mask |= other.value_mask;
     }
+}
+
+impl<
+        TValue: Signed + Neg<Output = TValue>,
+        const BRANCHING: usize,
+        const BRANCHING_TOTAL: usize,
+        const SIZE: usize,
+        const BIT_SIZE: usize,
+    > LeafNode<TValue, BRANCHING, BRANCHING_TOTAL, SIZE, BIT_SIZE>
+{
+    /// Resolves signs of inactive voxels from active ones and sets their values to far, so they can be compared with active values
+    fn resolve_inactive_values(&mut self) {
+        self.flood_fill();
 
-    fn intersect(&mut self, other: Box<Self>) {
         for i in 0..SIZE {
-            self.values[i] = partial_max(self.values[i], other.values[i]);
+            if self.value_mask.is_off(i) {
+                let sign = self.values[i].sign();
+                self.values[i] = TValue::far();
+                self.values[i].set_sign(sign);
+            }
         }
-
-        self.value_mask |= other.value_mask;
     }
 
-    fn flip_signs(&mut self) {
+    /// Replaces every value by `select(value, other_value)`, which returns one of its arguments
+    fn combine<TSelect>(&mut self, other: &mut Self, select: TSelect)
+    where
+        TSelect: Fn(TValue, TValue) -> TValue,
+    {
+        self.resolve_inactive_values();
+        other.resolve_inactive_values();
+
         for i in 0..SIZE {
-            self.values[i] = -self.values[i];
+            let value = select(self.values[i], other.values[i]);
+            let is_active = (self.value_mask.is_on(i) && value == self.values[i])
+                || (other.value_mask.is_on(i) && value == other.values[i]);
+
+            self.values[i] = value;
+
+            if is_active {
+                self.value_mask.on(i);
+            } else {
+                self.value_mask.off(i);
+            }
         }
     }
 }
@@ -130,4 +171,111 @@ mod tests {
         );
         assert_eq!(node1.value_mask, BitArray::ones());
     }
+
+    #[test]
+    fn test_csg_of_all_masks() {
+        type Leaf = static_vdb!(f32, 1);
+        // Operation, selection of value from operands and whether second operand is negated
+        type Operation = (fn(&mut Leaf, Box<Leaf>), fn(f32, f32) -> f32, bool);
+
+        // Node with uniform sign and given active voxels, inactive voxels keep sign of node
+        let node = |mask: usize, sign: Sign, scale: f32| {
+            let mut node = Leaf::empty(Vec3i::zeros());
+            node.fill_with_sign(sign);
+
+            for i in (0..8).filter(|i| mask & (1 << i) != 0) {
+                let mut value = scale * (i + 1) as f32;
+                value.set_sign(sign);
+                node.values[i] = value;
+                node.value_mask.on(i);
+            }
+
+            node
+        };
+        let far = |sign: Sign| {
+            let mut value = f32::far();
+            value.set_sign(sign);
+            value
+        };
+
+        let operations: [Operation; 3] = [
+            (|a, b| a.union(b), f32::min, false),
+            (|a, b| a.subtract(b), f32::max, true),
+            (|a, b| a.intersect(b), f32::max, false),
+        ];
+
+        let signs = [Sign::Positive, Sign::Negative];
+
+        for (operation, select, negate) in operations {
+            for (sign_a, sign_b) in signs.into_iter().flat_map(|a| signs.map(|b| (a, b))) {
+                for mask_a in 0..256 {
+                    for mask_b in 0..256 {
+                        let mut a = node(mask_a, sign_a, 1.0);
+                        let b = node(mask_b, sign_b, 1.5);
+
+                        let mut expected = Vec::new();
+                        for i in 0..8 {
+                            let (a_on, b_on) = (a.value_mask.is_on(i), b.value_mask.is_on(i));
+                            let value_a = if a_on { a.values[i] } else { far(sign_a) };
+                            let value_b = if b_on { b.values[i] } else { far(sign_b) };
+                            let value_b = if negate { -value_b } else { value_b };
+                            let value = select(value_a, value_b);
+                            expected.push((value, (a_on && value == value_a) || (b_on && value == value_b)));
+                        }
+
+                        operation(&mut a, b);
+
+                        for (i, (value, active)) in expected.into_iter().enumerate() {
+                            assert_eq!(a.values[i], value, "Voxel {} of masks {:08b}, {:08b}", i, mask_a, mask_b);
+                            assert_eq!(a.value_mask.is_on(i), active, "Voxel {} of masks {:08b}, {:08b}", i, mask_a, mask_b);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_csg_of_sparse_nodes() {
+        type Leaf = static_vdb!(f32, 2);
+
+        // Narrow band of plane `x = 1.5` without flood fill, inactive voxels have default values
+        let plane = || {
+            let mut node = Leaf::empty(Vec3i::zeros());
+            for y in 0..4 {
+                for z in 0..4 {
+                    node.insert(&Vec3i::new(1, y, z), -0.5);
+                    node.insert(&Vec3i::new(2, y, z), 0.5);
+                }
+            }
+            node
+        };
+
+        // Union with empty node keeps surface and resolves signs of inside voxels
+        let mut union = plane();
+        union.union(Leaf::empty(Vec3i::zeros()));
+        assert_eq!(union.value_mask, plane().value_mask);
+        assert!(union.values[0..16].iter().all(|v| *v == -f32::far()));
+        assert!(union.values[48..64].iter().all(|v| *v == f32::far()));
+
+        // Inside of other node hides surface without making far values active
+        let mut inside = Leaf::empty(Vec3i::zeros());
+        inside.fill_with_sign(Sign::Negative);
+        let mut union = plane();
+        union.union(inside);
+        assert!(union.is_empty());
+        assert!(union.values.iter().all(|v| v.is_sign_negative()));
+
+        // Subtracting plane from itself leaves nothing inside
+        let mut difference = plane();
+        difference.subtract(plane());
+        assert!(difference.values.iter().all(|v| v.is_sign_positive()));
+        assert_eq!(difference.value_mask, plane().value_mask);
+
+        // Flipped sparse node is inside where plane is outside
+        let mut flipped = plane();
+        flipped.flip_signs();
+        assert!(flipped.values[0..16].iter().all(|v| v.is_sign_positive()));
+        assert!(flipped.values[48..64].iter().all(|v| v.is_sign_negative()));
+    }
 }
@@ -0,0 +1,24 @@
+use super::*;
+use crate::{data_structures::bitset::BitSet, voxel::utils::uniform_value};
+
+impl<
+        TValue: Signed,
+        const BRANCHING: usize,
+        const BRANCHING_TOTAL: usize,
+        const SIZE: usize,
+        const BIT_SIZE: usize,
+    > Prune for LeafNode<TValue, BRANCHING, BRANCHING_TOTAL, SIZE, BIT_SIZE>
+{
+    #[inline]
+    fn prune(&mut self, _: Self::Value) {
+        // Do nothing for leaf nodes
+    }
+
+    fn uniform_value(&self, tolerance: Self::Value) -> Option<Self::Value> {
+        if !self.value_mask.is_full() {
+            return None;
+        }
+
+        uniform_value(self.values.iter().copied(), tolerance)
+    }
+}
@@ -113,6 +113,16 @@ impl<
         })
     }
 
+    #[inline]
+    fn memory_usage(&self) -> usize {
+        std::mem::size_of::<Self>()
+    }
+
+    #[inline]
+    fn depth() -> usize {
+        1
+    }
+
     fn visit_leafs_par<T: ParVisitor<Self::Leaf>>(&self, visitor: &T) {
         visitor.dense(self);
     }
@@ -1,19 +1,29 @@
-use self::volume::{Volume, VolumeGrid};
+use self::volume::{Symmetry, Volume, VolumeGrid};
 
 use super::*;
 use crate::{
+    algo::watertight::{watertight_report, WatertightReport},
     geometry::{
-        primitives::{box3::Box3, triangle3::Triangle3},
+        primitives::triangle3::Triangle3,
         traits::{ClosestPoint3, HasBBox3},
     },
-    helpers::aliases::{Vec3f, Vec3i, Vec3u},
+    helpers::{
+        aliases::{Vec3f, Vec3i, Vec3u},
+        trace::{trace_counters, trace_span},
+    },
     mesh::traits::Mesh,
     spatial_partitioning::aabb_tree::winding_numbers::WindingNumbers,
     voxel::{ParVisitor, Tile, TreeNode, Visitor},
 };
+#[cfg(feature = "gpu")]
+use super::gpu::GpuContext;
+#[cfg(feature = "rayon")]
 use rayon::prelude::*;
 use std::sync::Mutex;
 
+/// Number of subdivided triangles rasterized into one grid before grids are merged
+const TRIANGLES_BATCH_SIZE: usize = 4096;
+
 pub struct MeshToVolume {
     band_width: isize,
     voxel_size: f32,
@@ -21,6 +31,10 @@ pub struct MeshToVolume {
     distance_field: Box<VolumeGrid>,
     subdivided_mesh: Vec<Triangle3<f32>>,
     winding_numbers: WindingNumbers,
+    gpu: bool,
+    symmetry: Symmetry,
+    leak_check: bool,
+    leaks: Option<WatertightReport<f32>>,
 }
 
 impl MeshToVolume {
@@ -36,6 +50,72 @@ impl MeshToVolume {
         self
     }
 
+    ///
+    /// Set whether distances should be computed on GPU. Has effect only when `gpu` feature is enabled,
+    /// falls back to CPU when no GPU is available. Default is `false`.
+    ///
+    #[inline]
+    pub fn with_gpu(mut self, gpu: bool) -> Self {
+        self.set_gpu(gpu);
+        self
+    }
+
+    #[inline]
+    pub fn set_gpu(&mut self, gpu: bool) -> &mut Self {
+        self.gpu = gpu;
+        self
+    }
+
+    ///
+    /// Set planes of mirror symmetry of converted mesh. Distances are computed only for grid points
+    /// with non-negative coordinates along mirrored axes, so conversion of mesh symmetric about one plane
+    /// takes about half of time and memory. Mesh should be symmetric about declared planes, other parts of
+    /// mesh only affect distances near planes. Default is [Symmetry::NONE].
+    ///
+    /// ## Example
+    /// ```ignore
+    /// // Left and right halves of part are mirror images
+    /// let volume = MeshToVolume::default()
+    ///     .with_voxel_size(0.05)
+    ///     .with_symmetry(Symmetry { x: true, ..Symmetry::NONE })
+    ///     .convert(&mesh)?;
+    /// ```
+    ///
+    #[inline]
+    pub fn with_symmetry(mut self, symmetry: Symmetry) -> Self {
+        self.set_symmetry(symmetry);
+        self
+    }
+
+    #[inline]
+    pub fn set_symmetry(&mut self, symmetry: Symmetry) -> &mut Self {
+        self.symmetry = symmetry;
+        self
+    }
+
+    ///
+    /// Set whether mesh should be checked for leaks (holes and non-manifold edges) before conversion.
+    /// Signs of distances near leaks are unreliable, so with check enabled leaking mesh is not converted
+    /// and found leaks are available from [leaks](MeshToVolume::leaks). Default is `false`.
+    ///
+    #[inline]
+    pub fn with_leak_check(mut self, leak_check: bool) -> Self {
+        self.set_leak_check(leak_check);
+        self
+    }
+
+    #[inline]
+    pub fn set_leak_check(&mut self, leak_check: bool) -> &mut Self {
+        self.leak_check = leak_check;
+        self
+    }
+
+    /// Returns leaks of mesh rejected by last [convert](MeshToVolume::convert) call, see [with_leak_check](MeshToVolume::with_leak_check)
+    #[inline]
+    pub fn leaks(&self) -> Option<&WatertightReport<f32>> {
+        self.leaks.as_ref()
+    }
+
     #[inline]
     pub fn with_voxel_size(mut self, size: f32) -> Self {
         self.set_voxel_size(size);
@@ -50,11 +130,26 @@ impl MeshToVolume {
     }
 
     pub fn convert<T: Mesh<ScalarType = f32>>(&mut self, mesh: &T) -> Option<Volume> {
-        if mesh.faces().count() == 0 {
+        let faces_count = mesh.faces().count();
+        if faces_count == 0 {
             return None;
         }
 
+        trace_span!("mesh_to_volume", voxel_size = self.voxel_size, gpu = self.gpu);
         self.clear();
+
+        if self.leak_check {
+            let report = watertight_report(mesh);
+
+            if !report.is_watertight() {
+                trace_counters!(
+                    boundary_loops = report.boundary_loops.len(),
+                    non_manifold_edges = report.non_manifold_edges.len(),
+                );
+                self.leaks = Some(report);
+                return None;
+            }
+        }
         for tri in mesh.faces().map(|f| mesh.face_positions(&f)) {
             self.subdivide_triangle(&tri);
         }
@@ -69,7 +164,16 @@ impl MeshToVolume {
         let mut sdf = VolumeGrid::empty(Vec3i::zeros());
         std::mem::swap(&mut sdf, &mut self.distance_field);
 
-        Some(Volume::new(sdf, self.voxel_size))
+        let volume = Volume::new_symmetric(sdf, self.voxel_size, self.symmetry);
+
+        trace_counters!(
+            faces_processed = faces_count,
+            triangles_processed = self.subdivided_mesh.len(),
+            voxels_activated = volume.active_voxels_count(),
+            leaf_nodes_allocated = volume.leafs_count(),
+        );
+
+        Some(volume)
     }
 
     fn subdivide_triangle(&mut self, tri: &Triangle3<f32>) {
@@ -115,84 +219,134 @@ impl MeshToVolume {
         }
     }
 
-    fn compute_unsigned_distance_field(&mut self) {
-        let neighbors: Vec<_> = self
-            .subdivided_mesh
-            .par_iter()
-            .map(|tri| {
-                // Compute distance for voxels intersecting triangle and its `band_width` neighborhood
-                let bbox = tri.bbox();
-                let mut min = Vec3i::new(
-                    (bbox.get_min().x * self.inverse_voxel_size).floor() as isize - self.band_width,
-                    (bbox.get_min().y * self.inverse_voxel_size).floor() as isize - self.band_width,
-                    (bbox.get_min().z * self.inverse_voxel_size).floor() as isize - self.band_width,
-                );
-                let mut max = Vec3i::new(
-                    (bbox.get_max().x * self.inverse_voxel_size).ceil() as isize + self.band_width,
-                    (bbox.get_max().y * self.inverse_voxel_size).ceil() as isize + self.band_width,
-                    (bbox.get_max().z * self.inverse_voxel_size).ceil() as isize + self.band_width,
-                );
+    ///
+    /// Returns range of grid points (inclusive) intersecting triangle and its `band_width` neighborhood.
+    /// Range is clipped to stored part of symmetric volume, `None` is returned when it is empty.
+    ///
+    fn grid_points_range(&self, tri: &Triangle3<f32>) -> Option<(Vec3i, Vec3i)> {
+        let bbox = tri.bbox();
+        let mut min = Vec3i::new(
+            (bbox.get_min().x * self.inverse_voxel_size).floor() as isize - self.band_width,
+            (bbox.get_min().y * self.inverse_voxel_size).floor() as isize - self.band_width,
+            (bbox.get_min().z * self.inverse_voxel_size).floor() as isize - self.band_width,
+        );
+        let mut max = Vec3i::new(
+            (bbox.get_max().x * self.inverse_voxel_size).ceil() as isize + self.band_width,
+            (bbox.get_max().y * self.inverse_voxel_size).ceil() as isize + self.band_width,
+            (bbox.get_max().z * self.inverse_voxel_size).ceil() as isize + self.band_width,
+        );
+
+        // Triangle intersecting voxel along the voxel side?
+        if max.x == min.x || max.y == min.y || max.z == min.z {
+            // Extend box so it is not 0-volume
+            min.add_scalar_mut(-1);
+            max.add_scalar_mut(1);
+        }
 
-                // Triangle intersecting voxel along the voxel side?
-                if max.x == min.x || max.y == min.y || max.z == min.z {
-                    // Extend box so it is not 0-volume
-                    min.add_scalar_mut(-1);
-                    max.add_scalar_mut(1);
-                }
+        for (axis, mirrored) in self.symmetry.axes().into_iter().enumerate() {
+            if mirrored {
+                min[axis] = min[axis].max(0);
 
-                let neighbors_box = Box3::new(min, max);
-
-                let mut distances = Vec::with_capacity(neighbors_box.volume() as usize);
-
-                for x in min.x..=max.x {
-                    let x_world = x as f32 * self.voxel_size;
-                    for y in min.y..=max.y {
-                        let y_world = y as f32 * self.voxel_size;
-                        for z in min.z..=max.z {
-                            let z_world = z as f32 * self.voxel_size;
-                            let grid_point = Vec3f::new(x_world, y_world, z_world);
-                            let closest = tri.closest_point(&grid_point);
-                            let dist = (closest - grid_point).norm();
-                            distances.push(dist);
-
-                            debug_assert!(
-                                dist.is_finite(),
-                                "Mesh to SDF: distance from grid point to mesh is not finite"
-                            );
-                        }
-                    }
+                if max[axis] < min[axis] {
+                    return None;
                 }
+            }
+        }
 
-                (neighbors_box, distances)
-            })
-            .collect();
+        Some((min, max))
+    }
+
+    ///
+    /// Computes distances from subdivided triangles to grid points in their narrow bands.
+    /// Triangles are split into batches, each batch is rasterized into its own grid without contention,
+    /// then grids are merged pairwise (in parallel when `rayon` feature is enabled).
+    ///
+    fn compute_unsigned_distance_field(&mut self) {
+        #[cfg(feature = "gpu")]
+        if self.gpu && self.compute_unsigned_distance_field_gpu() {
+            return;
+        }
+
+        #[cfg(feature = "rayon")]
+        let distance_field = self
+            .subdivided_mesh
+            .par_chunks(TRIANGLES_BATCH_SIZE)
+            .map(|batch| self.batch_distance_field(batch))
+            .reduce_with(merge_distance_fields);
+        #[cfg(not(feature = "rayon"))]
+        let distance_field = self
+            .subdivided_mesh
+            .chunks(TRIANGLES_BATCH_SIZE)
+            .map(|batch| self.batch_distance_field(batch))
+            .reduce(merge_distance_fields);
+
+        if let Some(distance_field) = distance_field {
+            self.distance_field = distance_field;
+        }
+    }
 
-        for (bbox, dist) in neighbors {
-            let min = bbox.get_min();
-            let max = bbox.get_max();
+    /// Returns grid with minimal distances from grid points to given triangles
+    fn batch_distance_field(&self, triangles: &[Triangle3<f32>]) -> Box<VolumeGrid> {
+        let mut distance_field = VolumeGrid::empty(Vec3i::zeros());
 
-            let mut i = 0;
+        for tri in triangles {
+            let Some((min, max)) = self.grid_points_range(tri) else {
+                continue;
+            };
 
             for x in min.x..=max.x {
+                let x_world = x as f32 * self.voxel_size;
                 for y in min.y..=max.y {
+                    let y_world = y as f32 * self.voxel_size;
                     for z in min.z..=max.z {
-                        let idx = Vec3i::new(x, y, z);
+                        let z_world = z as f32 * self.voxel_size;
+                        let grid_point = Vec3f::new(x_world, y_world, z_world);
+                        let closest = tri.closest_point(&grid_point);
+                        let dist = (closest - grid_point).norm();
 
-                        let cur_dist = self
-                            .distance_field
-                            .at(&idx)
-                            .copied()
-                            .unwrap_or(f32::INFINITY);
+                        debug_assert!(
+                            dist.is_finite(),
+                            "Mesh to SDF: distance from grid point to mesh is not finite"
+                        );
 
-                        if dist[i] < cur_dist {
-                            self.distance_field.insert(&idx, dist[i]);
-                        }
+                        let idx = Vec3i::new(x, y, z);
 
-                        i += 1;
+                        match distance_field.at_mut(&idx) {
+                            Some(cur_dist) => *cur_dist = cur_dist.min(dist),
+                            None => distance_field.insert(&idx, dist),
+                        }
                     }
                 }
             }
         }
+
+        distance_field
+    }
+
+    /// Computes same distances as [Self::compute_unsigned_distance_field] on GPU, returns `false` when GPU is not available
+    #[cfg(feature = "gpu")]
+    fn compute_unsigned_distance_field_gpu(&mut self) -> bool {
+        let Some(gpu) = GpuContext::get() else {
+            return false;
+        };
+
+        let triangles: Vec<_> = self
+            .subdivided_mesh
+            .iter()
+            .filter_map(|tri| {
+                let (min, max) = self.grid_points_range(tri)?;
+                Some((*tri, min, max))
+            })
+            .collect();
+
+        let distance_field = &mut self.distance_field;
+        let computed = gpu.unsigned_distances(&triangles, self.voxel_size, |idx, dist| distance_field.insert(&idx, dist));
+
+        if !computed {
+            distance_field.clear();
+        }
+
+        computed
     }
 
     fn compute_sings(&mut self) -> bool {
@@ -217,6 +371,7 @@ impl MeshToVolume {
     fn clear(&mut self) {
         self.subdivided_mesh.clear();
         self.distance_field.clear();
+        self.leaks = None;
     }
 }
 
@@ -231,7 +386,79 @@ impl Default for MeshToVolume {
             subdivided_mesh: Vec::new(),
             inverse_voxel_size: 1.0 / voxel_size,
             winding_numbers: WindingNumbers::from_triangles(vec![]),
+            gpu: false,
+            symmetry: Symmetry::NONE,
+            leak_check: false,
+            leaks: None,
+        }
+    }
+}
+
+///
+/// Merges two unsigned distance fields keeping smaller distance at every grid point.
+/// Leafs present in one field only are moved without copying values, so merging of disjoint narrow bands is cheap.
+///
+fn merge_distance_fields(mut first: Box<VolumeGrid>, mut second: Box<VolumeGrid>) -> Box<VolumeGrid> {
+    let mut origins = LeafOriginsVisitor { origins: Vec::new() };
+    second.visit_leafs(&mut origins);
+
+    let mut first_origins = LeafOriginsVisitor { origins: Vec::new() };
+    first.visit_leafs(&mut first_origins);
+
+    // Move leafs of smaller field into larger one
+    if first_origins.origins.len() < origins.origins.len() {
+        std::mem::swap(&mut first, &mut second);
+        origins = first_origins;
+    }
+
+    let size = <VolumeGrid as TreeNode>::Leaf::resolution() as isize;
+
+    for origin in origins.origins {
+        let Some(leaf) = second.take_leaf_at(&origin) else {
+            continue;
+        };
+
+        let mut merged = match first.take_leaf_at(&origin) {
+            Some(merged) => merged,
+            None => {
+                first.insert_leaf_at(leaf);
+                continue;
+            }
+        };
+
+        for x in origin.x..origin.x + size {
+            for y in origin.y..origin.y + size {
+                for z in origin.z..origin.z + size {
+                    let idx = Vec3i::new(x, y, z);
+                    let Some(dist) = leaf.at(&idx).copied() else {
+                        continue;
+                    };
+
+                    match merged.at_mut(&idx) {
+                        Some(cur_dist) => *cur_dist = cur_dist.min(dist),
+                        None => merged.insert(&idx, dist),
+                    }
+                }
+            }
         }
+
+        first.insert_leaf_at(merged);
+    }
+
+    first
+}
+
+/// Collects origins of leafs
+struct LeafOriginsVisitor {
+    origins: Vec<Vec3i>,
+}
+
+impl<TLeaf: TreeNode> Visitor<TLeaf> for LeafOriginsVisitor {
+    fn tile(&mut self, _tile: Tile<TLeaf::Value>) {}
+
+    #[inline]
+    fn dense(&mut self, dense: &TLeaf) {
+        self.origins.push(dense.origin());
     }
 }
 
@@ -300,3 +527,52 @@ impl<'a, TGrid: TreeNode<Value = f32>> Visitor<TGrid::Leaf> for ComputeSignsVisi
         self.compute_sings_in_node(n);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{LeafOriginsVisitor, MeshToVolume, TRIANGLES_BATCH_SIZE};
+    use crate::{
+        helpers::aliases::Vec3i,
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+        voxel::{TreeNode, VolumeGrid},
+    };
+
+    #[test]
+    fn test_batched_distance_field() {
+        let mesh: CornerTableF = primitives::uv_sphere(1.0, 64, 32);
+        let mut mesh_to_volume = MeshToVolume::default().with_voxel_size(0.05).with_narrow_band_width(1);
+
+        for tri in mesh.faces().map(|face| mesh.face_positions(&face)) {
+            mesh_to_volume.subdivide_triangle(&tri);
+        }
+
+        assert!(mesh_to_volume.subdivided_mesh.len() > 2 * TRIANGLES_BATCH_SIZE);
+
+        // Merged batches give the same distances as single batch
+        let expected = mesh_to_volume.batch_distance_field(&mesh_to_volume.subdivided_mesh);
+        mesh_to_volume.compute_unsigned_distance_field();
+        let merged = &mesh_to_volume.distance_field;
+
+        let origins = |grid: &VolumeGrid| {
+            let mut visitor = LeafOriginsVisitor { origins: Vec::new() };
+            grid.visit_leafs(&mut visitor);
+            visitor.origins.sort_by_key(|origin| (origin.x, origin.y, origin.z));
+            visitor.origins
+        };
+
+        let leafs = origins(&expected);
+        assert_eq!(origins(merged), leafs);
+
+        let size = <VolumeGrid as TreeNode>::Leaf::resolution() as isize;
+        for origin in leafs {
+            for x in origin.x..origin.x + size {
+                for y in origin.y..origin.y + size {
+                    for z in origin.z..origin.z + size {
+                        let idx = Vec3i::new(x, y, z);
+                        assert_eq!(merged.at(&idx), expected.at(&idx));
+                    }
+                }
+            }
+        }
+    }
+}
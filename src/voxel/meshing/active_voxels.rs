@@ -1,25 +1,42 @@
-use crate::voxel::{utils::CUBE_OFFSETS, Tile, TreeNode, Visitor};
+use crate::voxel::{utils::CUBE_OFFSETS, volume::Volume, Tile, TreeNode, Visitor};
 use nalgebra::Vector3;
 
+use super::{grid_to_world, iso_surface, MeshOutput, Mesher, MesherOptions};
+
+///
+/// Meshes boundary faces of active voxels, e.g. to visualize structure of grid.
+/// [Mesher::mesh] meshes only active voxels inside of level set selected by [iso value](MesherOptions::iso_value).
+///
 pub struct ActiveVoxelsMesher {
     vertices: Vec<Vector3<isize>>,
     box_vertices: [Vector3<isize>; 8],
+    options: MesherOptions,
 }
 
 impl ActiveVoxelsMesher {
     /// Returns a list where each tree consecutive vertices form a triangle
-    #[allow(dead_code)]
-    pub fn mesh(&mut self, grid: &impl TreeNode) -> Vec<Vector3<isize>> {
+    pub fn mesh_voxels(&mut self, grid: &impl TreeNode) -> Vec<Vector3<isize>> {
+        self.mesh_voxels_where(grid, |_| true)
+    }
+
+    /// Meshes active voxels which values satisfy `is_meshed`
+    fn mesh_voxels_where<T: TreeNode>(&mut self, grid: &T, is_meshed: impl Fn(&T::Value) -> bool) -> Vec<Vector3<isize>> {
         self.vertices.clear();
 
-        let mut visitor = ActiveVoxelsVisitor { grid, mesher: self };
+        let mut visitor = ActiveVoxelsVisitor {
+            grid,
+            is_meshed: &is_meshed,
+            mesher: self,
+        };
 
         grid.visit_leafs(&mut visitor);
         std::mem::take(&mut self.vertices)
     }
 
-    fn test_voxel(&mut self, voxel: Vector3<isize>, grid: &impl TreeNode) {
-        if grid.at(&voxel).is_none() {
+    fn test_voxel<T: TreeNode>(&mut self, voxel: Vector3<isize>, grid: &T, is_meshed: &impl Fn(&T::Value) -> bool) {
+        let is_meshed = |voxel: &Vector3<isize>| grid.at(voxel).is_some_and(is_meshed);
+
+        if !is_meshed(&voxel) {
             return;
         }
 
@@ -30,12 +47,12 @@ impl ActiveVoxelsMesher {
         let front_index = voxel + Vector3::new(0, 1, 0);
         let back_index = voxel + Vector3::new(0, -1, 0);
 
-        let top = grid.at(&top_index).is_some();
-        let bottom = grid.at(&bottom_index).is_some();
-        let left = grid.at(&left_index).is_some();
-        let right = grid.at(&right_index).is_some();
-        let front = grid.at(&front_index).is_some();
-        let back = grid.at(&back_index).is_some();
+        let top = is_meshed(&top_index);
+        let bottom = is_meshed(&bottom_index);
+        let left = is_meshed(&left_index);
+        let right = is_meshed(&right_index);
+        let front = is_meshed(&front_index);
+        let back = is_meshed(&back_index);
 
         if !top {
             let faces = [
@@ -123,16 +140,41 @@ impl Default for ActiveVoxelsMesher {
         Self {
             vertices: Vec::new(),
             box_vertices: CUBE_OFFSETS,
+            options: MesherOptions::default(),
         }
     }
 }
 
-struct ActiveVoxelsVisitor<'a, T: TreeNode> {
+impl Mesher for ActiveVoxelsMesher {
+    #[inline]
+    fn options(&self) -> &MesherOptions {
+        &self.options
+    }
+
+    #[inline]
+    fn options_mut(&mut self) -> &mut MesherOptions {
+        &mut self.options
+    }
+
+    fn mesh(&mut self, volume: &Volume) -> MeshOutput {
+        let shifted = iso_surface(volume, self.options.iso_value);
+        let vertices = self
+            .mesh_voxels_where(shifted.grid(), |value| *value < 0.0)
+            .into_iter()
+            .map(|vertex| grid_to_world(volume, self.options.voxel_size, &vertex.cast()))
+            .collect();
+
+        MeshOutput::new(volume, &self.options, vertices)
+    }
+}
+
+struct ActiveVoxelsVisitor<'a, T: TreeNode, F: Fn(&T::Value) -> bool> {
     grid: &'a T,
+    is_meshed: &'a F,
     mesher: &'a mut ActiveVoxelsMesher,
 }
 
-impl<T: TreeNode> Visitor<T::Leaf> for ActiveVoxelsVisitor<'_, T> {
+impl<T: TreeNode, F: Fn(&T::Value) -> bool> Visitor<T::Leaf> for ActiveVoxelsVisitor<'_, T, F> {
     fn tile(&mut self, tile: Tile<<T>::Value>) {
         // Test only boundary voxels
         for i in 0..tile.size {
@@ -146,12 +188,12 @@ impl<T: TreeNode> Visitor<T::Leaf> for ActiveVoxelsVisitor<'_, T> {
                 let front = tile.origin + Vector3::new(i, tile.size - 1, j).cast();
                 let back = tile.origin + Vector3::new(i, 0, j).cast();
 
-                self.mesher.test_voxel(left, self.grid);
-                self.mesher.test_voxel(right, self.grid);
-                self.mesher.test_voxel(top, self.grid);
-                self.mesher.test_voxel(bottom, self.grid);
-                self.mesher.test_voxel(front, self.grid);
-                self.mesher.test_voxel(back, self.grid);
+                self.mesher.test_voxel(left, self.grid, self.is_meshed);
+                self.mesher.test_voxel(right, self.grid, self.is_meshed);
+                self.mesher.test_voxel(top, self.grid, self.is_meshed);
+                self.mesher.test_voxel(bottom, self.grid, self.is_meshed);
+                self.mesher.test_voxel(front, self.grid, self.is_meshed);
+                self.mesher.test_voxel(back, self.grid, self.is_meshed);
             }
         }
     }
@@ -166,7 +208,7 @@ impl<T: TreeNode> Visitor<T::Leaf> for ActiveVoxelsVisitor<'_, T> {
                 for z in 0..size {
                     let voxel = origin + Vector3::new(x, y, z).cast();
 
-                    self.mesher.test_voxel(voxel, self.grid);
+                    self.mesher.test_voxel(voxel, self.grid, self.is_meshed);
                 }
             }
         }
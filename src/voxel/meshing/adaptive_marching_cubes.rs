@@ -0,0 +1,239 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::{
+    algo::merge_points::merge_points,
+    helpers::{
+        aliases::{Vec3f, Vec3i},
+        trace::{trace_counters, trace_span},
+    },
+    voxel::volume::Volume,
+};
+
+use super::{grid_to_world, MarchingCubesMesher, MeshOutput, Mesher, MesherOptions};
+
+///
+/// Adaptive marching cubes. Surface is meshed by [MarchingCubesMesher], then octree is built over its vertices:
+/// cells where surface is nearly planar are kept coarse (up to [max cell size](AdaptiveMarchingCubesMesher::with_max_cell_size)),
+/// cells near high curvature are refined down to voxels. Vertices of every coarse cell are merged into their centroid,
+/// so flat regions get large triangles. Cells of different levels share vertices
+/// of the same mesh, so there are no cracks between levels.
+///
+/// ## Example
+/// ```ignore
+/// let faces = AdaptiveMarchingCubesMesher::default()
+///     .with_max_error(0.05)
+///     .mesh_triangles(&volume);
+/// ```
+///
+pub struct AdaptiveMarchingCubesMesher {
+    options: MesherOptions,
+    max_cell_size: usize,
+    max_error: f32,
+    min_normal_cos: f32,
+}
+
+impl AdaptiveMarchingCubesMesher {
+    ///
+    /// Set size (in voxels) of coarsest cells, it is rounded down to power of two. Default is 8.
+    ///
+    #[inline]
+    pub fn with_max_cell_size(mut self, voxels: usize) -> Self {
+        self.max_cell_size = voxels.max(1);
+        self
+    }
+
+    ///
+    /// Set max distance (in voxels) of surface from plane of coarse cell. Default is `0.1`.
+    ///
+    #[inline]
+    pub fn with_max_error(mut self, error: f32) -> Self {
+        self.max_error = error;
+        self
+    }
+
+    ///
+    /// Set max angle (in radians) between normals of surface and plane of coarse cell. Default is 15 degrees.
+    ///
+    #[inline]
+    pub fn with_max_normal_angle(mut self, angle: f32) -> Self {
+        self.min_normal_cos = angle.cos();
+        self
+    }
+
+    /// Returns triangles of level set of `volume`, every 3 consecutive vertices form triangle
+    pub fn mesh_triangles(&mut self, volume: &Volume) -> Vec<Vec3f> {
+        trace_span!("adaptive_marching_cubes", leaf_nodes = volume.leafs_count());
+
+        // Octree is built in grid coordinates, so sizes of cells and errors are in voxels
+        let faces = MarchingCubesMesher::default()
+            .with_voxel_size(1.0)
+            .with_iso_value(self.options.iso_value)
+            .mesh_triangles(volume);
+        let indexed = merge_points(&faces);
+        let surface = Surface::new(indexed.points, indexed.indices);
+
+        let levels = self.max_cell_size.ilog2();
+        let root_size = (1 << levels) as f32;
+
+        let mut roots: HashMap<Vec3i, Vec<usize>> = HashMap::new();
+        for (vertex, point) in surface.points.iter().enumerate() {
+            roots.entry(cell(point, root_size)).or_default().push(vertex);
+        }
+
+        let mut clusters = vec![0; surface.points.len()];
+        let mut positions = Vec::new();
+
+        for vertices in roots.into_values() {
+            self.refine(levels, vertices, &surface, &mut clusters, &mut positions);
+        }
+
+        // Faces collapsed inside coarse cells or repeated by merging are removed
+        let mut emitted = HashSet::new();
+        let mut adaptive = Vec::with_capacity(faces.len());
+
+        for face in surface.indices.chunks_exact(3) {
+            let face = [clusters[face[0]], clusters[face[1]], clusters[face[2]]];
+
+            if face[0] == face[1] || face[1] == face[2] || face[2] == face[0] {
+                continue;
+            }
+
+            let mut key = face;
+            key.sort_unstable();
+
+            if emitted.insert(key) {
+                adaptive.extend(face.map(|vertex| grid_to_world(volume, self.options.voxel_size, &positions[vertex])));
+            }
+        }
+
+        trace_counters!(faces_produced = adaptive.len() / 3, uniform_faces = faces.len() / 3);
+
+        adaptive
+    }
+
+    /// Merges vertices of planar cell into one, otherwise splits cell into 8 children
+    fn refine(
+        &self,
+        level: u32,
+        vertices: Vec<usize>,
+        surface: &Surface,
+        clusters: &mut [usize],
+        positions: &mut Vec<Vec3f>,
+    ) {
+        if level == 0 {
+            for vertex in vertices {
+                clusters[vertex] = positions.len();
+                positions.push(surface.points[vertex]);
+            }
+
+            return;
+        }
+
+        if let Some(position) = self.planar_position(&vertices, surface) {
+            for &vertex in &vertices {
+                clusters[vertex] = positions.len();
+            }
+
+            positions.push(position);
+            return;
+        }
+
+        let child_size = (1 << (level - 1)) as f32;
+        let mut children: HashMap<Vec3i, Vec<usize>> = HashMap::new();
+
+        for vertex in vertices {
+            children.entry(cell(&surface.points[vertex], child_size)).or_default().push(vertex);
+        }
+
+        for child in children.into_values() {
+            self.refine(level - 1, child, surface, clusters, positions);
+        }
+    }
+
+    ///
+    /// Returns centroid of vertices when faces around them lie on plane within tolerances.
+    /// Whole faces are checked, so vertices along sharp edge are not treated as planar.
+    ///
+    fn planar_position(&self, vertices: &[usize], surface: &Surface) -> Option<Vec3f> {
+        let faces: HashSet<_> = vertices.iter().flat_map(|vertex| surface.vertex_faces[*vertex].iter().copied()).collect();
+        let normal = faces
+            .iter()
+            .map(|face| surface.face_normals[*face])
+            .sum::<Vec3f>()
+            .try_normalize(0.0)?;
+
+        let centroid = vertices.iter().map(|vertex| surface.points[*vertex]).sum::<Vec3f>() / vertices.len() as f32;
+        let planar = faces.iter().all(|face| {
+            let face_normal = surface.face_normals[*face].try_normalize(0.0).unwrap_or(normal);
+            let corners = &surface.indices[face * 3..face * 3 + 3];
+
+            face_normal.dot(&normal) >= self.min_normal_cos
+                && corners.iter().all(|corner| normal.dot(&(surface.points[*corner] - centroid)).abs() <= self.max_error)
+        });
+
+        planar.then_some(centroid)
+    }
+}
+
+impl Default for AdaptiveMarchingCubesMesher {
+    fn default() -> Self {
+        Self {
+            options: MesherOptions::default(),
+            max_cell_size: 8,
+            max_error: 0.1,
+            min_normal_cos: 15f32.to_radians().cos(),
+        }
+    }
+}
+
+impl Mesher for AdaptiveMarchingCubesMesher {
+    #[inline]
+    fn options(&self) -> &MesherOptions {
+        &self.options
+    }
+
+    #[inline]
+    fn options_mut(&mut self) -> &mut MesherOptions {
+        &mut self.options
+    }
+
+    fn mesh(&mut self, volume: &Volume) -> MeshOutput {
+        let vertices = self.mesh_triangles(volume);
+        MeshOutput::new(volume, &self.options, vertices)
+    }
+}
+
+#[inline]
+fn cell(point: &Vec3f, size: f32) -> Vec3i {
+    (point / size).map(|x| x.floor() as isize)
+}
+
+/// Indexed mesh produced by marching cubes with adjacency used by planarity test
+struct Surface {
+    points: Vec<Vec3f>,
+    indices: Vec<usize>,
+    /// Normals scaled by doubled area of faces
+    face_normals: Vec<Vec3f>,
+    vertex_faces: Vec<Vec<usize>>,
+}
+
+impl Surface {
+    fn new(points: Vec<Vec3f>, indices: Vec<usize>) -> Self {
+        let mut vertex_faces = vec![Vec::new(); points.len()];
+        let face_normals = indices
+            .chunks_exact(3)
+            .enumerate()
+            .map(|(face, corners)| {
+                corners.iter().for_each(|corner| vertex_faces[*corner].push(face));
+                (points[corners[1]] - points[corners[0]]).cross(&(points[corners[2]] - points[corners[0]]))
+            })
+            .collect();
+
+        Self {
+            points,
+            indices,
+            face_normals,
+            vertex_faces,
+        }
+    }
+}
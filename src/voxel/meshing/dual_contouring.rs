@@ -1,30 +1,36 @@
 use self::{
-    utils::CUBE_OFFSETS,
+    utils::{region_boundary, CUBE_OFFSETS},
     volume::{Volume, VolumeGrid},
 };
-use super::lookup_table::EdgeDir;
-use crate::{geometry::primitives::triangle3::Triangle3, helpers::aliases::Vec3f, voxel::*};
+use super::{grid_to_world, iso_surface, lookup_table::EdgeDir, MeshOutput, Mesher, MesherOptions};
+use crate::{
+    geometry::primitives::triangle3::Triangle3,
+    helpers::{
+        aliases::Vec3f,
+        trace::{trace_counters, trace_span},
+    },
+    voxel::*,
+};
 use std::sync::Mutex;
 
 ///
 /// https://www.cs.rice.edu/~jwarren/papers/dualcontour.pdf
 ///
 pub struct DualContouringMesher {
-    voxel_size: f32,
+    options: MesherOptions,
 }
 
 impl DualContouringMesher {
-    #[inline]
-    pub fn with_voxel_size(mut self, voxel_size: f32) -> Self {
-        self.voxel_size = voxel_size;
-        self
-    }
+    /// Returns triangles of level set of `volume`, every 3 consecutive vertices form triangle
+    pub fn mesh_triangles(&mut self, volume: &Volume) -> Option<Vec<Vec3f>> {
+        trace_span!("dual_contouring", leaf_nodes = volume.leafs_count());
 
-    pub fn mesh(&mut self, volume: &Volume) -> Option<Vec<Vec3f>> {
+        let volume = &*iso_surface(volume, self.options.iso_value);
         let grid = volume.grid();
 
         let compute_intersections = ComputeEdgeIntersectionsVisitor {
             grid,
+            volume,
             x_int: Mutex::new(<VolumeGrid as TreeNode>::As::<IntPoint>::empty(
                 Vec3i::zeros(),
             )),
@@ -63,9 +69,9 @@ impl DualContouringMesher {
             let mut triangles = Vec::with_capacity(vertices.len());
 
             for i in (0..vertices.len()).step_by(3) {
-                let v0 = vertices[i] * self.voxel_size;
-                let v1 = vertices[i + 1] * self.voxel_size;
-                let v2 = vertices[i + 2] * self.voxel_size;
+                let v0 = grid_to_world(volume, self.options.voxel_size, &vertices[i]);
+                let v1 = grid_to_world(volume, self.options.voxel_size, &vertices[i + 1]);
+                let v2 = grid_to_world(volume, self.options.voxel_size, &vertices[i + 2]);
 
                 if Triangle3::is_degenerate(&v0, &v1, &v2) {
                     continue;
@@ -76,6 +82,8 @@ impl DualContouringMesher {
                 triangles.push(v2);
             }
 
+            trace_counters!(faces_produced = triangles.len() / 3);
+
             return Some(triangles);
         }
 
@@ -86,7 +94,26 @@ impl DualContouringMesher {
 impl Default for DualContouringMesher {
     #[inline]
     fn default() -> Self {
-        Self { voxel_size: 1.0 }
+        Self {
+            options: MesherOptions::default(),
+        }
+    }
+}
+
+impl Mesher for DualContouringMesher {
+    #[inline]
+    fn options(&self) -> &MesherOptions {
+        &self.options
+    }
+
+    #[inline]
+    fn options_mut(&mut self) -> &mut MesherOptions {
+        &mut self.options
+    }
+
+    fn mesh(&mut self, volume: &Volume) -> MeshOutput {
+        let vertices = self.mesh_triangles(volume).unwrap_or_default();
+        MeshOutput::new(volume, &self.options, vertices)
     }
 }
 
@@ -135,8 +162,24 @@ impl<'a, T: TreeNode<Value = f32>> TriangulateVisitor<'a, T> {
 }
 
 impl<'a, T: TreeNode<Value = f32>> ParVisitor<T::Leaf> for TriangulateVisitor<'a, T> {
-    fn tile(&self, _tile: Tile<<T as TreeNode>::Value>) {
-        todo!("Dual contouring, tile support")
+    fn tile(&self, tile: Tile<<T as TreeNode>::Value>) {
+        if self.faces.is_poisoned() {
+            return;
+        }
+
+        // Values inside tile are the same, so only edges leaving tile can be crossed by surface
+        let mut faces = Vec::new();
+        let max = tile.origin.add_scalar(tile.size as isize);
+
+        for v in region_boundary(tile.origin, max) {
+            self.handle_edge(tile.value, &v, EdgeDir::X, &mut faces);
+            self.handle_edge(tile.value, &v, EdgeDir::Y, &mut faces);
+            self.handle_edge(tile.value, &v, EdgeDir::Z, &mut faces);
+        }
+
+        if let Ok(mut f) = self.faces.lock() {
+            f.extend(faces);
+        };
     }
 
     fn dense(&self, dense: &T::Leaf) {
@@ -181,9 +224,64 @@ struct ComputeCellPointsVisitor<'a, T: TreeNode<Value = f32>> {
     grid: &'a T,
 }
 
+impl<'a, T: TreeNode<Value = f32>> ComputeCellPointsVisitor<'a, T> {
+    /// Returns feature point of cell with min corner at `o` if cell is crossed by surface
+    fn cell_point(&self, o: &Vec3i, intersections: &mut Vec<IntPoint>) -> Option<Vec3f> {
+        let mut values = [0.0; 8];
+
+        for (i, offset) in CUBE_OFFSETS.iter().enumerate() {
+            let p = o + offset;
+            values[i] = *self.grid.at(&p)?;
+        }
+
+        let first_sign = values[0].sign();
+        let all_has_same_sign = values.iter().skip(1).all(|v| v.sign() == first_sign);
+
+        if all_has_same_sign {
+            return None;
+        }
+
+        intersections.clear();
+
+        for (offset, dir) in &EDGE_OFFSETS {
+            let p = o + offset;
+            let point = match dir {
+                EdgeDir::X => self.x_int.at(&p),
+                EdgeDir::Y => self.y_int.at(&p),
+                EdgeDir::Z => self.z_int.at(&p),
+            };
+
+            if let Some(point) = point {
+                intersections.push(*point);
+            }
+        }
+
+        Some(find_feature_point(intersections))
+    }
+
+    fn insert_cells(&self, cell_points: Vec<(Vec3i, Vec3f)>) {
+        if let Ok(mut cells) = self.cells.lock() {
+            for (o, point) in cell_points {
+                cells.insert(&o, point);
+            }
+        };
+    }
+}
+
 impl<'a, T: TreeNode<Value = f32>> ParVisitor<T::Leaf> for ComputeCellPointsVisitor<'a, T> {
-    fn tile(&self, _tile: Tile<T::Value>) {
-        todo!("Dual contouring, tile support")
+    fn tile(&self, tile: Tile<T::Value>) {
+        if self.cells.is_poisoned() {
+            return;
+        }
+
+        // Only cells on tile boundary have corners outside of tile
+        let mut intersections = Vec::new();
+        let max = tile.origin.add_scalar(tile.size as isize);
+        let cell_points = region_boundary(tile.origin, max)
+            .filter_map(|o| self.cell_point(&o, &mut intersections).map(|point| (o, point)))
+            .collect();
+
+        self.insert_cells(cell_points);
     }
 
     fn dense(&self, dense: &T::Leaf) {
@@ -194,60 +292,28 @@ impl<'a, T: TreeNode<Value = f32>> ParVisitor<T::Leaf> for ComputeCellPointsVisi
         let min = dense.origin();
         let size = T::Leaf::resolution() as isize;
         let max = Vec3i::new(min.x + size, min.y + size, min.z + size);
-        let mut values = [0.0; 8];
         let mut intersections = Vec::new();
+        let mut cell_points = Vec::new();
 
         for x in min.x..max.x {
             for y in min.y..max.y {
-                'next_z: for z in min.z..max.z {
+                for z in min.z..max.z {
                     let o = Vec3i::new(x, y, z);
 
-                    for (i, offset) in CUBE_OFFSETS.iter().enumerate() {
-                        let p = o + offset;
-                        values[i] = match self.grid.at(&p) {
-                            // This can be dense
-                            Some(v) => *v,
-                            None => continue 'next_z,
-                        };
-                    }
-
-                    let first_sign = values[0].sign();
-                    let all_has_same_sign = values.iter().skip(1).all(|v| v.sign() == first_sign);
-
-                    if all_has_same_sign {
-                        continue;
+                    if let Some(point) = self.cell_point(&o, &mut intersections) {
+                        cell_points.push((o, point));
                     }
-
-                    intersections.clear();
-
-                    for (offset, dir) in &EDGE_OFFSETS {
-                        let p = o + offset;
-                        let point = match dir {
-                            EdgeDir::X => self.x_int.at(&p),
-                            EdgeDir::Y => self.y_int.at(&p),
-                            EdgeDir::Z => self.z_int.at(&p),
-                        };
-
-                        if let Some(point) = point {
-                            intersections.push(*point);
-                        }
-                    }
-
-                    match self.cells.lock() {
-                        Ok(mut cells) => {
-                            let feature_point = find_feature_point(&intersections);
-                            cells.insert(&o, feature_point);
-                        }
-                        Err(_) => return,
-                    };
                 }
             }
         }
+
+        self.insert_cells(cell_points);
     }
 }
 
 struct ComputeEdgeIntersectionsVisitor<'a, T: TreeNode<Value = f32>> {
     grid: &'a T,
+    volume: &'a Volume,
     x_int: Mutex<Box<T::As<IntPoint>>>,
     y_int: Mutex<Box<T::As<IntPoint>>>,
     z_int: Mutex<Box<T::As<IntPoint>>>,
@@ -288,13 +354,40 @@ impl<'a, T: TreeNode<Value = f32>> ComputeEdgeIntersectionsVisitor<'a, T> {
             EdgeDir::Y => Vec3f::new(v1.x as f32, v1.y as f32 + t, v1.z as f32),
             EdgeDir::Z => Vec3f::new(v1.x as f32, v1.y as f32, v1.z as f32 + t),
         };
-        let normal = self.normal(&v1, &v2, t);
+        let normal = self
+            .volume
+            .normal(&self.volume.grid_to_world(&point))
+            .unwrap_or_else(|| self.normal(&v1, &v2, t));
 
         let intersection = IntPoint { point, normal };
 
         intersections.push((v1, intersection));
     }
 
+    fn insert_intersections(
+        &self,
+        x_inters: Vec<(Vec3i, IntPoint)>,
+        y_inters: Vec<(Vec3i, IntPoint)>,
+        z_inters: Vec<(Vec3i, IntPoint)>,
+    ) {
+        let intersection_grids = (self.x_int.lock(), self.y_int.lock(), self.z_int.lock());
+
+        if let (Ok(mut x), Ok(mut y), Ok(mut z)) = intersection_grids {
+            for (idx, point) in x_inters {
+                x.insert(&idx, point);
+            }
+
+            for (idx, point) in y_inters {
+                y.insert(&idx, point);
+            }
+
+            for (idx, point) in z_inters {
+                z.insert(&idx, point);
+            }
+        };
+    }
+
+    /// Interpolates gradients at grid points, used near boundary of narrow band where volume can't be sampled
     fn normal(&self, v1: &Vec3i, v2: &Vec3i, t: f32) -> Vec3f {
         let x = (1.0 - t) * self.x_grad(v1) + t * self.x_grad(v2);
         let y = (1.0 - t) * self.y_grad(v1) + t * self.y_grad(v2);
@@ -343,8 +436,20 @@ impl<'a, T: TreeNode<Value = f32>> ComputeEdgeIntersectionsVisitor<'a, T> {
 }
 
 impl<'a, T: TreeNode<Value = f32>> ParVisitor<T::Leaf> for ComputeEdgeIntersectionsVisitor<'a, T> {
-    fn tile(&self, _tile: Tile<T::Value>) {
-        todo!("Dual contouring, tile support")
+    fn tile(&self, tile: Tile<T::Value>) {
+        // Values inside tile are the same, so only edges leaving tile can be crossed by surface
+        let mut x_inters = Vec::new();
+        let mut y_inters = Vec::new();
+        let mut z_inters = Vec::new();
+        let max = tile.origin.add_scalar(tile.size as isize);
+
+        for v in region_boundary(tile.origin, max) {
+            self.intersection(v, EdgeDir::X, &mut x_inters);
+            self.intersection(v, EdgeDir::Y, &mut y_inters);
+            self.intersection(v, EdgeDir::Z, &mut z_inters);
+        }
+
+        self.insert_intersections(x_inters, y_inters, z_inters);
     }
 
     fn dense(&self, dense: &T::Leaf) {
@@ -368,25 +473,12 @@ impl<'a, T: TreeNode<Value = f32>> ParVisitor<T::Leaf> for ComputeEdgeIntersecti
             }
         }
 
-        let intersection_grids = (self.x_int.lock(), self.y_int.lock(), self.z_int.lock());
-
-        if let (Ok(mut x), Ok(mut y), Ok(mut z)) = intersection_grids {
-            for (idx, point) in x_inters {
-                x.insert(&idx, point);
-            }
-
-            for (idx, point) in y_inters {
-                y.insert(&idx, point);
-            }
-
-            for (idx, point) in z_inters {
-                z.insert(&idx, point);
-            }
-        };
+        self.insert_intersections(x_inters, y_inters, z_inters);
     }
 }
 
-const CELL_OFFSETS: [[Vec3i; 4]; 3] = [
+/// Offsets of 4 cells around grid edge (relative to edge start) along X, Y and Z axes in order forming quad
+pub(super) const CELL_OFFSETS: [[Vec3i; 4]; 3] = [
     [
         Vec3i::new(0, 0, 0),
         Vec3i::new(0, 0, -1),
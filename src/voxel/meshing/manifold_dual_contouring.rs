@@ -0,0 +1,365 @@
+use std::collections::{HashMap, HashSet};
+
+use nalgebra::{Matrix3, Vector3};
+
+use self::{
+    utils::{region_boundary, CUBE_OFFSETS},
+    volume::Volume,
+};
+use super::{dual_contouring::CELL_OFFSETS, grid_to_world, iso_surface, MeshOutput, Mesher, MesherOptions};
+use crate::{
+    helpers::{
+        aliases::Vec3f,
+        trace::{trace_counters, trace_span},
+    },
+    voxel::*,
+};
+
+///
+/// Manifold dual contouring. Like [DualContouringMesher](super::DualContouringMesher) places vertices by minimizing
+/// quadratic error function (QEF) of edge intersections, so sharp features of CSG results are preserved,
+/// but output is guaranteed to be 2-manifold: every cell gets separate vertex for each surface patch crossing it.
+/// Patches are separated on ambiguous cell faces by asymptotic decider, which depends only on values of face,
+/// so neighboring cells agree on connectivity and every vertex is surrounded by a single fan of faces.
+///
+/// Based on article: ["Manifold Dual Contouring"](https://people.engr.tamu.edu/schaefer/research/dualsimp_tvcg.pdf),
+/// patches are computed on uniform grid without octree simplification.
+///
+/// ## Example
+/// ```ignore
+/// let part = body.union(lid).subtract(hole);
+/// let faces = ManifoldDualContouringMesher::default().mesh_triangles(&part);
+/// ```
+///
+pub struct ManifoldDualContouringMesher {
+    options: MesherOptions,
+    singular_value_threshold: f32,
+}
+
+impl ManifoldDualContouringMesher {
+    ///
+    /// Set threshold of singular values of QEF relative to the largest one. Directions with smaller singular values
+    /// are considered flat and vertex is kept at mass point of intersections along them.
+    /// Larger threshold gives smoother mesh with fewer sharp features. Default is `0.1`.
+    ///
+    #[inline]
+    pub fn with_singular_value_threshold(mut self, threshold: f32) -> Self {
+        self.singular_value_threshold = threshold;
+        self
+    }
+
+    /// Returns triangles of level set of `volume`, every 3 consecutive vertices form triangle
+    pub fn mesh_triangles(&mut self, volume: &Volume) -> Vec<Vec3f> {
+        trace_span!("manifold_dual_contouring", leaf_nodes = volume.leafs_count());
+
+        let volume = &*iso_surface(volume, self.options.iso_value);
+        let grid = volume.grid();
+        let mut candidates = CellsVisitor { cells: Vec::new() };
+        grid.visit_leafs(&mut candidates);
+
+        let mut intersections = HashMap::new();
+        let mut cells = HashMap::new();
+        let mut vertices = Vec::new();
+        let mut crossed_edges = HashSet::new();
+
+        for origin in candidates.cells {
+            if cells.contains_key(&origin) {
+                continue;
+            }
+
+            let Some(values) = cell_values(grid, &origin) else {
+                continue;
+            };
+
+            let patches = cell_patches(&values);
+            if patches.is_empty() {
+                continue;
+            }
+
+            let points: Vec<Vec<_>> = patches
+                .iter()
+                .map(|patch| {
+                    patch
+                        .iter()
+                        .map(|edge| {
+                            let (start, axis) = cell_edge(&origin, *edge);
+                            crossed_edges.insert((start, axis));
+                            *intersections.entry((start, axis)).or_insert_with(|| {
+                                intersection(volume, &start, axis, values[EDGES[*edge].0], values[EDGES[*edge].1])
+                            })
+                        })
+                        .collect()
+                })
+                .collect();
+
+            let mut cell_vertices: Vec<_> = points.iter().map(|points| self.solve_qef(points, &origin)).collect();
+
+            // Patches meeting at sharp feature (e.g. boxes touching along edge) may have the same QEF minimizer,
+            // mass points are used instead to keep them apart
+            let is_coincident = |v1: &Vec3f, v2: &Vec3f| (v1 - v2).norm_squared() < COINCIDENT_DISTANCE_SQUARED;
+            if cell_vertices
+                .iter()
+                .enumerate()
+                .any(|(i, v1)| cell_vertices[i + 1..].iter().any(|v2| is_coincident(v1, v2)))
+            {
+                cell_vertices = points.iter().map(|points| mass_point(points)).collect();
+            }
+
+            let mut edge_vertices = [usize::MAX; 12];
+
+            for (patch, vertex) in patches.iter().zip(cell_vertices) {
+                patch.iter().for_each(|edge| edge_vertices[*edge] = vertices.len());
+                vertices.push(vertex);
+            }
+
+            cells.insert(origin, edge_vertices);
+        }
+
+        // Every crossed edge is dual to quad connecting patch vertices of 4 cells around it
+        let mut crossed_edges: Vec<_> = crossed_edges.into_iter().collect();
+        crossed_edges.sort_by_key(|(start, axis)| (start.x, start.y, start.z, *axis));
+
+        let mut triangles = Vec::with_capacity(crossed_edges.len() * 6);
+
+        for (start, axis) in crossed_edges {
+            let quad = CELL_OFFSETS[axis].map(|offset| {
+                let cell = start + offset;
+                let local = local_edge(&(start - cell), axis)?;
+                cells
+                    .get(&cell)
+                    .map(|edge_vertices| edge_vertices[local])
+                    .filter(|vertex| *vertex != usize::MAX)
+            });
+
+            let [Some(v0), Some(v1), Some(v2), Some(v3)] = quad else {
+                continue;
+            };
+
+            let mut faces = [v0, v1, v2, v2, v3, v0];
+
+            if grid.at(&start).is_some_and(|value| value.sign() == Sign::Negative) {
+                faces.swap(1, 2);
+                faces.swap(4, 5);
+            }
+
+            // Degenerate triangles are kept, removing them would open holes in mesh
+            triangles.extend(faces.map(|v| grid_to_world(volume, self.options.voxel_size, &vertices[v])));
+        }
+
+        trace_counters!(faces_produced = triangles.len() / 3);
+
+        triangles
+    }
+
+    ///
+    /// Returns point minimizing sum of squared distances to tangent planes at intersections.
+    /// Flat directions are resolved by mass point of intersections, result is clamped to cell.
+    ///
+    fn solve_qef(&self, intersections: &[(Vec3f, Vec3f)], origin: &Vec3i) -> Vec3f {
+        let mass_point = mass_point(intersections);
+        let mut ata = Matrix3::zeros();
+        let mut atb = Vector3::zeros();
+
+        for (point, normal) in intersections {
+            ata += normal * normal.transpose();
+            atb += normal * normal.dot(&(point - mass_point));
+        }
+
+        let svd = ata.svd(true, true);
+        let eps = svd.singular_values.max() * self.singular_value_threshold;
+        let offset = if eps > 0.0 { svd.solve(&atb, eps).unwrap_or_else(|_| Vector3::zeros()) } else { Vector3::zeros() };
+
+        let min = origin.cast::<f32>();
+        let max = min.add_scalar(1.0);
+        (mass_point + offset).zip_zip_map(&min, &max, |v, min, max| v.clamp(min, max))
+    }
+}
+
+impl Default for ManifoldDualContouringMesher {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            options: MesherOptions::default(),
+            singular_value_threshold: 0.1,
+        }
+    }
+}
+
+impl Mesher for ManifoldDualContouringMesher {
+    #[inline]
+    fn options(&self) -> &MesherOptions {
+        &self.options
+    }
+
+    #[inline]
+    fn options_mut(&mut self) -> &mut MesherOptions {
+        &mut self.options
+    }
+
+    fn mesh(&mut self, volume: &Volume) -> MeshOutput {
+        let vertices = self.mesh_triangles(volume);
+        MeshOutput::new(volume, &self.options, vertices)
+    }
+}
+
+/// Squared distance (in grid units) below which vertices of different patches are considered coincident
+const COINCIDENT_DISTANCE_SQUARED: f32 = 1e-4;
+
+/// Corners of cube edges (indices into [CUBE_OFFSETS]), first corner has smaller coordinates
+const EDGES: [(usize, usize); 12] = [
+    (0, 1),
+    (1, 2),
+    (3, 2),
+    (0, 3),
+    (4, 5),
+    (5, 6),
+    (7, 6),
+    (4, 7),
+    (0, 4),
+    (1, 5),
+    (2, 6),
+    (3, 7),
+];
+
+/// Corners of cube faces in cyclic order and edges between consecutive corners
+const FACES: [([usize; 4], [usize; 4]); 6] = [
+    ([0, 1, 2, 3], [0, 1, 2, 3]),
+    ([4, 5, 6, 7], [4, 5, 6, 7]),
+    ([0, 1, 5, 4], [0, 9, 4, 8]),
+    ([3, 2, 6, 7], [2, 10, 6, 11]),
+    ([0, 3, 7, 4], [3, 11, 7, 8]),
+    ([1, 2, 6, 5], [1, 10, 5, 9]),
+];
+
+#[inline]
+fn mass_point(intersections: &[(Vec3f, Vec3f)]) -> Vec3f {
+    intersections.iter().map(|(point, _)| point).sum::<Vec3f>() / intersections.len() as f32
+}
+
+/// Returns values at corners of cell, `None` when some of them are outside of narrow band
+fn cell_values(grid: &VolumeGrid, origin: &Vec3i) -> Option<[f32; 8]> {
+    let mut values = [0.0; 8];
+
+    for (value, offset) in values.iter_mut().zip(CUBE_OFFSETS.iter()) {
+        *value = *grid.at(&(origin + offset))?;
+    }
+
+    Some(values)
+}
+
+///
+/// Splits edges of cell crossed by surface into patches. Crossed edges of face are paired, ambiguous faces
+/// (with 4 crossed edges) are resolved by asymptotic decider. Returns crossed edges of every patch.
+///
+fn cell_patches(values: &[f32; 8]) -> Vec<Vec<usize>> {
+    let is_crossed = |edge: usize| values[EDGES[edge].0].sign() != values[EDGES[edge].1].sign();
+    let mut parent: [usize; 12] = std::array::from_fn(|edge| edge);
+
+    fn find(parent: &mut [usize; 12], mut edge: usize) -> usize {
+        while parent[edge] != edge {
+            parent[edge] = parent[parent[edge]];
+            edge = parent[edge];
+        }
+        edge
+    }
+
+    let mut join = |e1: usize, e2: usize| {
+        let (r1, r2) = (find(&mut parent, e1), find(&mut parent, e2));
+        parent[r1] = r2;
+    };
+
+    for (corners, edges) in FACES {
+        let crossed: Vec<_> = edges.iter().copied().filter(|edge| is_crossed(*edge)).collect();
+
+        match crossed.as_slice() {
+            [e1, e2] => join(*e1, *e2),
+            [_, _, _, _] => {
+                let [a, b, c, d] = corners.map(|corner| values[corner]);
+                let denominator = a + c - b - d;
+                let saddle = if denominator == 0.0 { -1.0 } else { (a * c - b * d) / denominator };
+
+                if saddle.sign() == a.sign() {
+                    // `a` and `c` are connected, separate `b` and `d`
+                    join(edges[0], edges[1]);
+                    join(edges[2], edges[3]);
+                } else {
+                    join(edges[3], edges[0]);
+                    join(edges[1], edges[2]);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    let mut patches: Vec<(usize, Vec<usize>)> = Vec::new();
+
+    for edge in (0..12).filter(|edge| is_crossed(*edge)) {
+        let root = find(&mut parent, edge);
+
+        match patches.iter_mut().find(|(r, _)| *r == root) {
+            Some((_, patch)) => patch.push(edge),
+            None => patches.push((root, vec![edge])),
+        }
+    }
+
+    patches.into_iter().map(|(_, patch)| patch).collect()
+}
+
+/// Returns start grid point and axis of cell edge
+#[inline]
+fn cell_edge(origin: &Vec3i, edge: usize) -> (Vec3i, usize) {
+    let (start, end) = EDGES[edge];
+    let axis = (CUBE_OFFSETS[end] - CUBE_OFFSETS[start]).iamax();
+    (origin + CUBE_OFFSETS[start], axis)
+}
+
+/// Returns index of cell edge starting at `offset` from cell origin along `axis`
+#[inline]
+fn local_edge(offset: &Vec3i, axis: usize) -> Option<usize> {
+    (0..12).find(|edge| {
+        let (start, end) = EDGES[*edge];
+        CUBE_OFFSETS[start] == *offset && (CUBE_OFFSETS[end] - CUBE_OFFSETS[start]).iamax() == axis
+    })
+}
+
+/// Returns intersection of surface with grid edge and normal at it in grid coordinates
+fn intersection(volume: &Volume, start: &Vec3i, axis: usize, v1: f32, v2: f32) -> (Vec3f, Vec3f) {
+    let t = if v1 == v2 { 0.5 } else { v1 / (v1 - v2) };
+    let mut point = start.cast::<f32>();
+    point[axis] += t;
+
+    let normal = volume.normal(&volume.grid_to_world(&point)).unwrap_or_else(|| {
+        // Near boundary of narrow band normal can't be sampled, edge direction points from inside to outside
+        let mut normal = Vec3f::zeros();
+        normal[axis] = if v2 > v1 { 1.0 } else { -1.0 };
+        normal
+    });
+
+    (point, normal)
+}
+
+/// Collects origins of cells which may be crossed by surface
+struct CellsVisitor {
+    cells: Vec<Vec3i>,
+}
+
+impl<T: TreeNode<Value = f32>> Visitor<T> for CellsVisitor {
+    fn tile(&mut self, tile: Tile<f32>) {
+        // Values inside tile are the same, so only cells on tile boundary can be crossed
+        let max = tile.origin.add_scalar(tile.size as isize);
+        self.cells.extend(region_boundary(tile.origin, max));
+    }
+
+    fn dense(&mut self, dense: &T) {
+        let min = dense.origin();
+        let size = T::resolution() as isize;
+
+        for x in min.x..min.x + size {
+            for y in min.y..min.y + size {
+                for z in min.z..min.z + size {
+                    self.cells.push(Vec3i::new(x, y, z));
+                }
+            }
+        }
+    }
+}
@@ -2,21 +2,29 @@ use std::{fmt::Debug, ops::Index};
 
 use crate::{
     geometry::primitives::triangle3::Triangle3,
-    helpers::aliases::{Vec3, Vec3f, Vec3i},
+    helpers::{
+        aliases::{Vec3, Vec3f, Vec3i},
+        trace::{trace_counters, trace_span},
+    },
     voxel::*,
 };
 use self::utils::CUBE_OFFSETS;
 
-use super::lookup_table::*;
+use super::{grid_to_world, lookup_table::*, mirror_symmetric, MeshOutput, Mesher, MesherOptions};
+#[cfg(feature = "gpu")]
+use crate::voxel::gpu::{GpuContext, BLOCK_CUBES, BLOCK_SIZE};
 
 ///
 /// Corrected marching cubes 33.
 ///
 /// Based on article: ["Practical considerations on Marching Cubes 33 topological correctness"](https://www.sci.utah.edu/~etiene/pdf/mc33.pdf)
 ///
+/// Vertices are placed using voxel size of meshed volume unless it is overridden by [Mesher::with_voxel_size],
+/// so by default they are in world coordinates. Before 0.4.0 default voxel size was `1.0` (grid coordinates).
+///
 pub struct MarchingCubesMesher {
     vertices: Vec<Vec3f>,
-    voxel_size: f32,
+    options: MesherOptions,
     v12: Vec3f,
     cube: Cube,
     case: i8,
@@ -24,27 +32,53 @@ pub struct MarchingCubesMesher {
     x_int: Box<VolumeGrid>,
     y_int: Box<VolumeGrid>,
     z_int: Box<VolumeGrid>,
+    gpu: bool,
 }
 
 #[allow(clippy::manual_range_contains)]
 impl MarchingCubesMesher {
+    ///
+    /// Sets voxel size used to place vertices, overrides voxel size of meshed volume (see [Mesher::with_voxel_size]).
+    ///
     #[inline]
-    pub fn with_voxel_size(mut self, size: f32) -> Self {
-        self.voxel_size = size;
+    pub fn set_voxel_size(&mut self, size: f32) -> &mut Self {
+        self.options.voxel_size = Some(size);
         self
     }
 
+    ///
+    /// Sets value of the level set to extract. Default is `0.0` (surface of the volume).
+    /// Positive values extract shells outside of the surface, negative - inside. Note that volume stores distances only within its narrow band, so iso-value should not exceed it.
+    ///
     #[inline]
-    pub fn set_voxel_size(&mut self, size: f32) -> &mut Self {
-        self.voxel_size = size;
+    pub fn set_iso_value(&mut self, iso_value: f32) -> &mut Self {
+        self.options.iso_value = iso_value;
+        self
+    }
+
+    ///
+    /// Set whether cubes should be classified on GPU. Has effect only when `gpu` feature is enabled,
+    /// falls back to CPU when no GPU is available. Default is `false`.
+    ///
+    #[inline]
+    pub fn with_gpu(mut self, gpu: bool) -> Self {
+        self.gpu = gpu;
         self
     }
 
-    pub fn mesh(&mut self, sdf: &Volume) -> Vec<Vec3f> {
+    /// Returns triangles of level set of `sdf`, every 3 consecutive vertices form triangle
+    pub fn mesh_triangles(&mut self, sdf: &Volume) -> Vec<Vec3f> {
+        trace_span!("marching_cubes", leaf_nodes = sdf.leafs_count(), gpu = self.gpu);
         self.clear();
 
+        #[cfg(feature = "gpu")]
+        if self.gpu && self.mesh_gpu(sdf) {
+            return self.to_world(sdf);
+        }
+
         let mut compute_intersections = ComputeEdgeIntersections {
             grid: sdf.grid(),
+            iso_value: self.options.iso_value,
             x_int: self.x_int.as_mut(),
             y_int: self.y_int.as_mut(),
             z_int: self.z_int.as_mut(),
@@ -59,7 +93,117 @@ impl MarchingCubesMesher {
 
         sdf.grid().visit_leafs(&mut cubes_visitor);
 
-        self.vertices.clone()
+        self.to_world(sdf)
+    }
+
+    /// Returns triangles converted from grid to world coordinates and mirrored across symmetry planes of `sdf`,
+    /// triangles degenerated by conversion are skipped
+    fn to_world(&self, sdf: &Volume) -> Vec<Vec3f> {
+        let faces: Vec<_> = self
+            .vertices
+            .chunks_exact(3)
+            .map(|triangle| [&triangle[0], &triangle[1], &triangle[2]].map(|vertex| grid_to_world(sdf, self.options.voxel_size, vertex)))
+            .filter(|[v1, v2, v3]| !Triangle3::is_degenerate(v1, v2, v3))
+            .flatten()
+            .collect();
+        let faces = mirror_symmetric(sdf, faces);
+
+        trace_counters!(faces_produced = faces.len() / 3);
+
+        faces
+    }
+
+    ///
+    /// Same as [Self::mesh_triangles], but edge intersections and cubes crossed by surface are found on GPU for dense leafs,
+    /// so only triangulation of crossed cubes is left for CPU. Returns `false` when GPU is not available.
+    ///
+    #[cfg(feature = "gpu")]
+    fn mesh_gpu(&mut self, sdf: &Volume) -> bool {
+        let Some(gpu) = GpuContext::get() else {
+            return false;
+        };
+
+        let mut blocks = CollectBlocksVisitor {
+            grid: sdf.grid(),
+            origins: Vec::new(),
+            values: Vec::new(),
+            present: Vec::new(),
+            tiles: Vec::new(),
+        };
+        sdf.grid().visit_leafs(&mut blocks);
+
+        let Some((active, intersections)) = gpu.classify_cubes(&blocks.values, &blocks.present, self.options.iso_value) else {
+            return false;
+        };
+
+        // Boundaries of tiles are handled on CPU
+        let mut compute_intersections = ComputeEdgeIntersections {
+            grid: sdf.grid(),
+            iso_value: self.options.iso_value,
+            x_int: self.x_int.as_mut(),
+            y_int: self.y_int.as_mut(),
+            z_int: self.z_int.as_mut(),
+        };
+
+        for &(origin, size, value) in &blocks.tiles {
+            Visitor::<<VolumeGrid as TreeNode>::Leaf>::tile(&mut compute_intersections, Tile { origin, size, value });
+        }
+
+        let cube_offset = |cube: usize| Vec3::new(cube / 64, (cube / 8) % 8, cube % 8).cast::<isize>();
+
+        for (block, origin) in blocks.origins.iter().enumerate() {
+            for cube in 0..BLOCK_CUBES {
+                let index = origin + cube_offset(cube);
+                let first = (block * BLOCK_CUBES + cube) * 3;
+                let [x, y, z] = [intersections[first], intersections[first + 1], intersections[first + 2]];
+
+                if x >= 0.0 {
+                    self.x_int.insert(&index, index.x as f32 + x);
+                }
+
+                if y >= 0.0 {
+                    self.y_int.insert(&index, index.y as f32 + y);
+                }
+
+                if z >= 0.0 {
+                    self.z_int.insert(&index, index.z as f32 + z);
+                }
+            }
+        }
+
+        let mut cubes_visitor = CubesVisitor { grid: sdf.grid(), mc: self };
+
+        for &(origin, size, value) in &blocks.tiles {
+            Visitor::<<VolumeGrid as TreeNode>::Leaf>::tile(&mut cubes_visitor, Tile { origin, size, value });
+        }
+
+        for (block, origin) in blocks.origins.iter().enumerate() {
+            for cube in (0..BLOCK_CUBES).filter(|cube| active[block * BLOCK_CUBES + cube] != 0) {
+                let cube = Cube::from_voxel(origin + cube_offset(cube), sdf.grid(), self.options.iso_value);
+                self.handle_cube(cube);
+            }
+        }
+
+        true
+    }
+
+    ///
+    /// Extracts one mesh per each of given iso-values. Returned meshes are in the same order as `iso_values`.
+    ///
+    pub fn mesh_iso_values(&mut self, sdf: &Volume, iso_values: &[f32]) -> Vec<Vec<Vec3f>> {
+        let iso_value = self.options.iso_value;
+
+        let meshes = iso_values
+            .iter()
+            .map(|&value| {
+                self.options.iso_value = value;
+                self.mesh_triangles(sdf)
+            })
+            .collect();
+
+        self.options.iso_value = iso_value;
+
+        meshes
     }
 
     fn clear(&mut self) {
@@ -302,10 +446,6 @@ impl MarchingCubesMesher {
                 }
             };
 
-            let v1 = v1 * self.voxel_size;
-            let v2 = v2 * self.voxel_size;
-            let v3 = v3 * self.voxel_size;
-
             if Triangle3::is_degenerate(&v1, &v2, &v3) {
                 continue;
             }
@@ -946,14 +1086,32 @@ impl Default for MarchingCubesMesher {
             cube: Default::default(),
             case: 0,
             config: 0,
-            voxel_size: 1.0,
+            options: MesherOptions::default(),
             x_int: VolumeGrid::empty(Vec3::zeros()),
             y_int: VolumeGrid::empty(Vec3::zeros()),
             z_int: VolumeGrid::empty(Vec3::zeros()),
+            gpu: false,
         }
     }
 }
 
+impl Mesher for MarchingCubesMesher {
+    #[inline]
+    fn options(&self) -> &MesherOptions {
+        &self.options
+    }
+
+    #[inline]
+    fn options_mut(&mut self) -> &mut MesherOptions {
+        &mut self.options
+    }
+
+    fn mesh(&mut self, volume: &Volume) -> MeshOutput {
+        let vertices = self.mesh_triangles(volume);
+        MeshOutput::new(volume, &self.options, vertices)
+    }
+}
+
 struct CubesVisitor<'a> {
     grid: &'a VolumeGrid,
     mc: &'a mut MarchingCubesMesher,
@@ -962,7 +1120,7 @@ struct CubesVisitor<'a> {
 impl<'a> CubesVisitor<'a> {
     #[inline]
     fn cube(&self, voxel: Vec3i) -> Option<Cube> {
-        Cube::from_voxel(voxel, self.grid)
+        Cube::from_voxel(voxel, self.grid, self.mc.options.iso_value)
     }
 }
 
@@ -1012,6 +1170,7 @@ impl<T: TreeNode<Value = f32>> Visitor<T> for CubesVisitor<'_> {
 
 struct ComputeEdgeIntersections<'a, T: TreeNode<Value = f32>> {
     grid: &'a T,
+    iso_value: f32,
     x_int: &'a mut T,
     y_int: &'a mut T,
     z_int: &'a mut T,
@@ -1020,7 +1179,7 @@ struct ComputeEdgeIntersections<'a, T: TreeNode<Value = f32>> {
 impl<'a, T: TreeNode<Value = f32>> ComputeEdgeIntersections<'a, T> {
     fn intersection(&mut self, v1: &Vec3i, v2: &Vec3i, dir: EdgeDir) {
         let (v1_val, v2_val) = match (self.grid.at(v1), self.grid.at(v2)) {
-            (Some(v1), Some(v2)) => (*v1, *v2),
+            (Some(v1), Some(v2)) => (*v1 - self.iso_value, *v2 - self.iso_value),
             _ => return,
         };
 
@@ -1029,11 +1188,11 @@ impl<'a, T: TreeNode<Value = f32>> ComputeEdgeIntersections<'a, T> {
 
     fn intersection_tile(&mut self, v1: &Vec3i, v2: &Vec3i, v1_val: f32, dir: EdgeDir) {
         let v2_val = match self.grid.at(v2) {
-            Some(v2) => *v2,
+            Some(v2) => *v2 - self.iso_value,
             _ => return,
         };
 
-        self.compute_intersection(v1, v1_val, v2_val, dir);
+        self.compute_intersection(v1, v1_val - self.iso_value, v2_val, dir);
     }
 
     fn compute_intersection(&mut self, v1: &Vec3i, v1_val: f32, v2_val: f32, dir: EdgeDir) {
@@ -1120,6 +1279,43 @@ impl<'a, T: TreeNode<Value = f32>> Visitor<T::Leaf> for ComputeEdgeIntersections
     }
 }
 
+///
+/// Gathers values of dense leafs with one layer of neighbors into blocks of [BLOCK_SIZE]^3 grid points for GPU.
+/// Tiles are collected separately.
+///
+#[cfg(feature = "gpu")]
+struct CollectBlocksVisitor<'a> {
+    grid: &'a VolumeGrid,
+    origins: Vec<Vec3i>,
+    values: Vec<f32>,
+    present: Vec<u32>,
+    tiles: Vec<(Vec3i, usize, f32)>,
+}
+
+#[cfg(feature = "gpu")]
+impl<T: TreeNode<Value = f32>> Visitor<T> for CollectBlocksVisitor<'_> {
+    fn tile(&mut self, tile: Tile<T::Value>) {
+        self.tiles.push((tile.origin, tile.size, tile.value));
+    }
+
+    fn dense(&mut self, dense: &T) {
+        debug_assert_eq!(T::resolution() + 1, BLOCK_SIZE);
+
+        let min = dense.origin();
+        self.origins.push(min);
+
+        for x in 0..BLOCK_SIZE {
+            for y in 0..BLOCK_SIZE {
+                for z in 0..BLOCK_SIZE {
+                    let value = self.grid.at(&(min + Vec3::new(x, y, z).cast()));
+                    self.values.push(value.copied().unwrap_or(0.0));
+                    self.present.push(value.is_some() as u32);
+                }
+            }
+        }
+    }
+}
+
 const MIN_ABS_VERTEX_VALUE: f32 = 1e-6;
 
 #[derive(Debug, Clone, Copy)]
@@ -1158,13 +1354,13 @@ impl Index<usize> for Cube {
 }
 
 impl Cube {
-    fn from_voxel(voxel: Vec3i, grid: &VolumeGrid) -> Option<Self> {
+    fn from_voxel(voxel: Vec3i, grid: &VolumeGrid, iso_value: f32) -> Option<Self> {
         let mut cube: Self = Default::default();
 
         let vertex_indices = CUBE_OFFSETS.map(|off| voxel + off);
 
         for (i, index) in vertex_indices.into_iter().enumerate() {
-            let mut value: f32 = grid.at(&index).copied()?;
+            let mut value: f32 = grid.at(&index).copied()? - iso_value;
 
             if value.abs() < MIN_ABS_VERTEX_VALUE {
                 value = MIN_ABS_VERTEX_VALUE.copysign(value);
@@ -1180,3 +1376,57 @@ impl Cube {
         Some(cube)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{MarchingCubesMesher, Mesher};
+    use crate::{helpers::aliases::Vec3f, voxel::prelude::Volume};
+
+    fn sphere(radius: f32, voxel_size: f32) -> Volume {
+        let extent = Vec3f::new(radius, radius, radius) * 1.5;
+        Volume::from_fn(voxel_size, -extent, extent, 5, |p| p.norm() - radius)
+    }
+
+    fn assert_on_sphere(vertices: &[Vec3f], radius: f32, tolerance: f32) {
+        assert!(!vertices.is_empty());
+
+        for v in vertices {
+            assert!(
+                (v.norm() - radius).abs() < tolerance,
+                "Vertex {:?} is not on sphere of radius {}",
+                v,
+                radius
+            );
+        }
+    }
+
+    #[test]
+    fn test_mesh_iso_value() {
+        let voxel_size = 0.1;
+        let volume = sphere(1.0, voxel_size);
+        let mut mesher = MarchingCubesMesher::default()
+            .with_voxel_size(voxel_size)
+            .with_iso_value(0.2);
+
+        assert_on_sphere(&mesher.mesh_triangles(&volume), 1.2, voxel_size);
+    }
+
+    #[test]
+    fn test_mesh_iso_values() {
+        let voxel_size = 0.1;
+        let volume = sphere(1.0, voxel_size);
+        let mut mesher = MarchingCubesMesher::default().with_voxel_size(voxel_size);
+
+        let iso_values = [-0.3, 0.0, 0.3];
+        let meshes = mesher.mesh_iso_values(&volume, &iso_values);
+
+        assert_eq!(meshes.len(), iso_values.len());
+
+        for (mesh, iso_value) in meshes.iter().zip(iso_values) {
+            assert_on_sphere(mesh, 1.0 + iso_value, voxel_size);
+        }
+
+        // Iso-value of mesher is not affected
+        assert_on_sphere(&mesher.mesh_triangles(&volume), 1.0, voxel_size);
+    }
+}
@@ -2,7 +2,207 @@ mod active_voxels;
 mod marching_cubes;
 mod lookup_table;
 mod dual_contouring;
+mod adaptive_marching_cubes;
+mod manifold_dual_contouring;
 
 pub use marching_cubes::MarchingCubesMesher;
 pub use dual_contouring::DualContouringMesher;
 pub use active_voxels::ActiveVoxelsMesher;
+pub use adaptive_marching_cubes::AdaptiveMarchingCubesMesher;
+pub use manifold_dual_contouring::ManifoldDualContouringMesher;
+
+use std::borrow::Cow;
+
+use crate::helpers::aliases::Vec3f;
+
+use super::volume::Volume;
+
+/// Options shared by all meshers, see [Mesher]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MesherOptions {
+    /// Voxel size used to place vertices, `None` (default) means voxel size of meshed volume.
+    /// Before 0.4.0 meshers defaulted to `1.0`, i.e. vertices in grid coordinates
+    pub voxel_size: Option<f32>,
+    /// Value of the level set to extract
+    pub iso_value: f32,
+    /// Whether per-vertex normals should be computed
+    pub normals: bool,
+}
+
+impl Default for MesherOptions {
+    #[inline]
+    fn default() -> Self {
+        Self {
+            voxel_size: None,
+            iso_value: 0.0,
+            normals: false,
+        }
+    }
+}
+
+/// Triangles extracted by [Mesher]
+#[derive(Debug, Clone, Default)]
+pub struct MeshOutput {
+    /// Every 3 consecutive vertices form triangle
+    pub vertices: Vec<Vec3f>,
+    /// Normals of vertices, `None` unless enabled by [Mesher::with_normals]
+    pub normals: Option<Vec<Vec3f>>,
+}
+
+///
+/// Common interface of meshers extracting level set of volume, allows to swap meshing algorithms.
+/// Mesher specific settings are configured on concrete type, common ones - by builder methods of this trait.
+///
+/// ## Example
+/// ```ignore
+/// fn extract(mesher: impl Mesher, volume: &Volume) -> MeshOutput {
+///     mesher.with_iso_value(0.1).with_normals(true).mesh(volume)
+/// }
+///
+/// let output = extract(DualContouringMesher::default(), &volume);
+/// ```
+///
+pub trait Mesher {
+    fn options(&self) -> &MesherOptions;
+    fn options_mut(&mut self) -> &mut MesherOptions;
+
+    /// Extracts level set of `volume` selected by [MesherOptions::iso_value]
+    fn mesh(&mut self, volume: &Volume) -> MeshOutput;
+
+    #[inline]
+    fn with_options(mut self, options: MesherOptions) -> Self
+    where
+        Self: Sized,
+    {
+        *self.options_mut() = options;
+        self
+    }
+
+    ///
+    /// Set voxel size used to place vertices. Default is `None`, voxel size of meshed volume is used.
+    ///
+    #[inline]
+    fn with_voxel_size(mut self, voxel_size: f32) -> Self
+    where
+        Self: Sized,
+    {
+        self.options_mut().voxel_size = Some(voxel_size);
+        self
+    }
+
+    ///
+    /// Sets value of the level set to extract. Default is `0.0` (surface of the volume).
+    /// Positive values extract shells outside of the surface, negative - inside. Note that volume stores distances only within its narrow band, so iso-value should not exceed it.
+    ///
+    #[inline]
+    fn with_iso_value(mut self, iso_value: f32) -> Self
+    where
+        Self: Sized,
+    {
+        self.options_mut().iso_value = iso_value;
+        self
+    }
+
+    ///
+    /// Set whether normals of vertices should be computed. Normals are sampled from gradient of volume,
+    /// normal of face is used where gradient can't be computed. Default is `false`.
+    ///
+    #[inline]
+    fn with_normals(mut self, normals: bool) -> Self
+    where
+        Self: Sized,
+    {
+        self.options_mut().normals = normals;
+        self
+    }
+}
+
+impl MeshOutput {
+    /// Creates output from triangles of `volume`, normals are computed when enabled in `options`
+    fn new(volume: &Volume, options: &MesherOptions, vertices: Vec<Vec3f>) -> Self {
+        let normals = options.normals.then(|| vertex_normals(volume, options.voxel_size, &vertices));
+        Self { vertices, normals }
+    }
+}
+
+/// Converts vertex from grid to world coordinates, voxel size set on mesher overrides voxel size of volume
+#[inline]
+fn grid_to_world(volume: &Volume, voxel_size: Option<f32>, point: &Vec3f) -> Vec3f {
+    match voxel_size {
+        Some(voxel_size) => point * voxel_size,
+        None => volume.grid_to_world(point),
+    }
+}
+
+///
+/// Returns volume which zero level set is `iso_value` level set of `volume`.
+/// Values are shifted within narrow band, so like for marching cubes iso-value should not exceed it.
+/// Symmetric volume is unfolded, cells crossing symmetry planes need grid points on both sides.
+///
+fn iso_surface(volume: &Volume, iso_value: f32) -> Cow<'_, Volume> {
+    if iso_value == 0.0 && volume.symmetry().is_none() {
+        return Cow::Borrowed(volume);
+    }
+
+    let mut shifted = volume.clone().unfold();
+
+    if iso_value != 0.0 {
+        shifted.map_active_values(|_, value| value - iso_value);
+    }
+
+    Cow::Owned(shifted)
+}
+
+///
+/// Appends mirror images of triangles across symmetry planes of `volume`, so triangles extracted
+/// from stored part of symmetric volume cover whole surface. Cells of mirrored part are mirror images of stored ones.
+///
+fn mirror_symmetric(volume: &Volume, mut faces: Vec<Vec3f>) -> Vec<Vec3f> {
+    for (axis, mirrored) in volume.symmetry().axes().into_iter().enumerate() {
+        if !mirrored {
+            continue;
+        }
+
+        let images: Vec<_> = faces
+            .chunks_exact(3)
+            .flat_map(|triangle| {
+                // Reflection flips orientation, so winding is reversed
+                [triangle[0], triangle[2], triangle[1]].map(|mut vertex| {
+                    // Adding zero turns `-0.0` into `0.0`, so vertices on plane are merged with their images
+                    vertex[axis] = -vertex[axis] + 0.0;
+                    vertex
+                })
+            })
+            .collect();
+
+        faces.extend(images);
+    }
+
+    faces
+}
+
+/// Samples normals of vertices from gradient of volume, falls back to normal of triangle
+fn vertex_normals(volume: &Volume, voxel_size: Option<f32>, vertices: &[Vec3f]) -> Vec<Vec3f> {
+    vertices
+        .chunks_exact(3)
+        .flat_map(|triangle| {
+            // Triangles may be degenerate, e.g. in output of manifold dual contouring
+            let face_normal = (triangle[1] - triangle[0])
+                .cross(&(triangle[2] - triangle[0]))
+                .try_normalize(0.0)
+                .unwrap_or_else(Vec3f::zeros);
+
+            triangle.iter().map(move |vertex| {
+                let point = match voxel_size {
+                    Some(voxel_size) => volume.grid_to_world(&(vertex / voxel_size)),
+                    None => *vertex,
+                };
+
+                volume
+                    .normal(&point)
+                    .filter(|normal| normal.iter().all(|c| c.is_finite()))
+                    .unwrap_or(face_normal)
+            })
+        })
+        .collect()
+}
@@ -1,9 +1,16 @@
+pub mod emboss;
+pub mod export;
 pub mod mesh_to_volume;
 pub mod meshing;
+pub mod morph;
+pub mod offset;
 pub mod prelude;
+pub mod render;
 pub mod volume;
 
 mod fast_sweep;
+#[cfg(feature = "gpu")]
+mod gpu;
 mod init;
 mod internal_node;
 mod leaf_node;
@@ -112,6 +119,12 @@ trait TreeNode: Send + Sync + Sized {
 
     fn clone(&self) -> Box<Self>;
 
+    /// Approximate number of bytes allocated by node and its descendants
+    fn memory_usage(&self) -> usize;
+
+    /// Number of tree levels from this node down to leafs, leaf node has depth `1`
+    fn depth() -> usize;
+
     /// Number of voxels in one dimension
     #[inline]
     fn resolution() -> usize {
@@ -148,6 +161,20 @@ where
     fn flip_signs(&mut self);
 }
 
+trait Prune
+where
+    Self: TreeNode,
+    Self::Value: Signed,
+{
+    /// Replaces uniform childs by tiles, see [Prune::uniform_value]
+    fn prune(&mut self, tolerance: Self::Value);
+    ///
+    /// Returns value of tile which can replace the node. Node is uniform when all its values are active,
+    /// have same sign and differ from returned value by no more than `tolerance`.
+    ///
+    fn uniform_value(&self, tolerance: Self::Value) -> Option<Self::Value>;
+}
+
 #[derive(Debug)]
 struct Tile<T> {
     pub origin: Vec3i,
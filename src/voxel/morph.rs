@@ -0,0 +1,41 @@
+use crate::{algo::merge_points::merge_points, mesh::traits::Mesh};
+
+use super::{
+    mesh_to_volume::MeshToVolume,
+    meshing::{MarchingCubesMesher, Mesher},
+};
+
+/// Width of narrow band (in voxels) used for morphing, distances outside of it are clamped
+const MORPH_BAND_WIDTH: isize = 8;
+
+///
+/// Interpolates shape between meshes `a` (`t = 0`) and `b` (`t = 1`) by blending their distance fields.
+/// Meshes should be closed and overlap, otherwise intermediate shapes may be clipped.
+/// Can be called for sequence of `t` to generate morphing animation.
+///
+/// ## Example
+/// ```ignore
+/// let frames: Vec<CornerTableF> = (0..=10)
+///     .filter_map(|i| morph_meshes(&sphere, &cube, i as f32 / 10.0, 0.05))
+///     .collect();
+/// ```
+///
+pub fn morph_meshes<T: Mesh<ScalarType = f32>>(a: &T, b: &T, t: f32, voxel_size: f32) -> Option<T> {
+    let mut mesh_to_volume = MeshToVolume::default()
+        .with_voxel_size(voxel_size)
+        .with_narrow_band_width(MORPH_BAND_WIDTH);
+
+    let a = mesh_to_volume.convert(a)?;
+    let b = mesh_to_volume.convert(b)?;
+    let morph = a.lerp(b, t);
+
+    let mut mesher = MarchingCubesMesher::default().with_voxel_size(voxel_size);
+    let faces = mesher.mesh_triangles(&morph);
+
+    if faces.is_empty() {
+        return None;
+    }
+
+    let indexed_faces = merge_points(&faces);
+    Some(T::from_vertices_and_indices(&indexed_faces.points, &indexed_faces.indices))
+}
@@ -0,0 +1,238 @@
+use std::collections::HashMap;
+
+use crate::{
+    algo::merge_points::merge_points,
+    helpers::aliases::{Vec3f, Vec3i},
+    mesh::traits::Mesh,
+};
+
+use super::{
+    mesh_to_volume::MeshToVolume,
+    meshing::{MarchingCubesMesher, Mesher},
+    volume::Volume,
+    TreeNode,
+};
+
+///
+/// Offsets mesh surface by given distance using distance field.
+///
+/// Plain offsetting welds together parts closer than `2 * distance`, e.g. parts of mechanical assembly.
+/// When [separation](MeshOffset::with_separation) is set, every connected component is offset separately
+/// and overlapping offsets are split by bisector between components, so they stay at least `gap` apart.
+/// Components should be closed and should not intersect each other.
+/// Parts of single component touching after offset are still merged.
+///
+/// ## Example
+/// ```ignore
+/// let offset: CornerTableF = MeshOffset::default()
+///     .with_voxel_size(0.05)
+///     .with_separation(Some(0.05))
+///     .offset(&assembly, 0.5)
+///     .unwrap();
+/// ```
+///
+pub struct MeshOffset {
+    voxel_size: f32,
+    separation: Option<f32>,
+}
+
+impl MeshOffset {
+    #[inline]
+    pub fn with_voxel_size(mut self, voxel_size: f32) -> Self {
+        self.voxel_size = voxel_size;
+        self
+    }
+
+    /// Set min gap between offset components. `None` (default) merges overlapping offsets.
+    #[inline]
+    pub fn with_separation(mut self, gap: Option<f32>) -> Self {
+        self.separation = gap;
+        self
+    }
+
+    ///
+    /// Offsets `mesh` by `distance`, positive distance grows the mesh.
+    /// Returns `None` when mesh can't be converted to volume or offset surface vanishes.
+    ///
+    pub fn offset<T: Mesh<ScalarType = f32>>(&self, mesh: &T, distance: f32) -> Option<T> {
+        let mut mesh_to_volume = MeshToVolume::default().with_voxel_size(self.voxel_size);
+
+        // Shrinking can't make components collide
+        let faces = match self.separation.filter(|_| distance > 0.0) {
+            Some(gap) => {
+                let components = split_components::<T>(mesh);
+                let volumes = components
+                    .iter()
+                    .map(|(component, _)| mesh_to_volume.convert(component).map(|v| v.offset(distance)))
+                    .collect::<Option<Vec<_>>>()?;
+
+                let mut faces = Vec::new();
+                let mut mesher = MarchingCubesMesher::default().with_voxel_size(self.voxel_size);
+                let margin = Vec3f::repeat(distance + 2.0 * self.voxel_size);
+                let bboxes: Vec<_> = components.iter().map(|(_, (min, max))| (min - margin, max + margin)).collect();
+
+                for (i, (min, max)) in bboxes.iter().enumerate() {
+                    // Only components with overlapping offsets can collide
+                    let neighbors: Vec<_> = bboxes
+                        .iter()
+                        .enumerate()
+                        .filter(|(j, (other_min, other_max))| {
+                            *j != i && (0..3).all(|axis| min[axis] <= other_max[axis] && other_min[axis] <= max[axis])
+                        })
+                        .map(|(j, _)| &volumes[j])
+                        .collect();
+
+                    if neighbors.is_empty() {
+                        faces.extend(mesher.mesh_triangles(&volumes[i]));
+                        continue;
+                    }
+
+                    // Offset distances differ from distances to components by the same value,
+                    // so their difference is distance to bisector between components
+                    let mut separated = volumes[i].clone();
+                    separated.map_active_values(|index, own| {
+                        neighbors
+                            .iter()
+                            .filter_map(|volume| value_at(volume, index))
+                            .fold(own, |value, other| value.max(0.5 * (own - other + gap)))
+                    });
+
+                    faces.extend(mesher.mesh_triangles(&separated));
+                }
+
+                faces
+            }
+            None => {
+                let volume = mesh_to_volume.convert(mesh)?.offset(distance);
+                MarchingCubesMesher::default()
+                    .with_voxel_size(self.voxel_size)
+                    .mesh_triangles(&volume)
+            }
+        };
+
+        if faces.is_empty() {
+            return None;
+        }
+
+        let indexed_faces = merge_points(&faces);
+        Some(T::from_vertices_and_indices(&indexed_faces.points, &indexed_faces.indices))
+    }
+}
+
+impl Default for MeshOffset {
+    fn default() -> Self {
+        Self {
+            voxel_size: 1.0,
+            separation: None,
+        }
+    }
+}
+
+/// Returns value at grid point. Fast sweeping may leave gaps in narrow band, they are filled by average of adjacent values.
+fn value_at(volume: &Volume, index: &Vec3i) -> Option<f32> {
+    if let Some(value) = volume.grid().at(index) {
+        return Some(*value);
+    }
+
+    let (sum, count) = (0..3)
+        .flat_map(|axis| [-1, 1].map(|step| index + Vec3i::ith(axis, step)))
+        .filter_map(|adjacent| volume.grid().at(&adjacent).copied())
+        .fold((0.0, 0), |(sum, count), value| (sum + value, count + 1));
+
+    (count > 0).then(|| sum / count as f32)
+}
+
+/// Splits mesh into connected components (by shared vertex positions) and computes their bounding boxes
+fn split_components<T: Mesh<ScalarType = f32>>(mesh: &T) -> Vec<(T, (Vec3f, Vec3f))> {
+    fn find(parent: &mut [usize], mut v: usize) -> usize {
+        while parent[v] != v {
+            parent[v] = parent[parent[v]];
+            v = parent[v];
+        }
+
+        v
+    }
+
+    let positions: Vec<_> = mesh
+        .faces()
+        .flat_map(|face| {
+            let triangle = mesh.face_positions(&face);
+            [*triangle.p1(), *triangle.p2(), *triangle.p3()]
+        })
+        .collect();
+    let indexed = merge_points(&positions);
+
+    let mut parent: Vec<_> = (0..indexed.points.len()).collect();
+    for face in indexed.indices.chunks(3) {
+        let root = find(&mut parent, face[0]);
+        for &vertex in &face[1..] {
+            let other = find(&mut parent, vertex);
+            parent[other] = root;
+        }
+    }
+
+    let mut component_index = HashMap::new();
+    let mut components: Vec<(Vec<Vec3f>, Vec<usize>)> = Vec::new();
+
+    for face in indexed.indices.chunks(3) {
+        let root = find(&mut parent, face[0]);
+        let index = *component_index.entry(root).or_insert_with(|| {
+            components.push((Vec::new(), Vec::new()));
+            components.len() - 1
+        });
+
+        let (vertices, indices) = &mut components[index];
+        indices.extend((0..3).map(|i| vertices.len() + i));
+        vertices.extend(face.iter().map(|&v| indexed.points[v]));
+    }
+
+    components
+        .into_iter()
+        .map(|(vertices, indices)| {
+            let min = vertices.iter().fold(Vec3f::repeat(f32::MAX), |min, p| min.inf(p));
+            let max = vertices.iter().fold(Vec3f::repeat(f32::MIN), |max, p| max.sup(p));
+            (T::from_vertices_and_indices(&vertices, &indices), (min, max))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{split_components, MeshOffset};
+    use crate::{
+        helpers::aliases::Vec3f,
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+    };
+
+    #[test]
+    fn test_offset_keeps_components_separated() {
+        // Two unit cubes 0.2 apart
+        let cube: CornerTableF = primitives::cuboid(Vec3f::new(1.0, 1.0, 1.0), 1);
+        let mut vertices = Vec::new();
+        for shift in [-0.6, 0.6] {
+            for face in cube.faces() {
+                let triangle = cube.face_positions(&face);
+                vertices.extend([*triangle.p1(), *triangle.p2(), *triangle.p3()].map(|p| p + Vec3f::new(shift, 0.0, 0.0)));
+            }
+        }
+        let indices: Vec<_> = (0..vertices.len()).collect();
+        let assembly = CornerTableF::from_vertices_and_indices(&vertices, &indices);
+
+        let plain = MeshOffset::default().with_voxel_size(0.05).offset(&assembly, 0.25).unwrap();
+        assert_eq!(split_components(&plain).len(), 1);
+
+        let separated = MeshOffset::default()
+            .with_voxel_size(0.05)
+            .with_separation(Some(0.05))
+            .offset(&assembly, 0.25)
+            .unwrap();
+        let components = split_components(&separated);
+        assert_eq!(components.len(), 2);
+
+        for (_, (min, max)) in components {
+            // Offset is kept away from the other cube
+            assert!(max.x < -0.01 || min.x > 0.01);
+            assert!((max.y - 0.75).abs() < 0.05);
+        }
+    }
+}
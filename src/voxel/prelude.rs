@@ -1,4 +1,6 @@
 pub use super::mesh_to_volume::MeshToVolume;
-pub use super::meshing::{DualContouringMesher, MarchingCubesMesher};
+pub use super::meshing::{AdaptiveMarchingCubesMesher, DualContouringMesher, ManifoldDualContouringMesher, MarchingCubesMesher, MeshOutput, Mesher, MesherOptions};
 pub use super::volume::builder::VolumeBuilder;
-pub use super::volume::Volume;
+pub use super::volume::{MesherKind, Symmetry, Volume, VolumePyramid, VolumeStats};
+pub use super::offset::MeshOffset;
+pub use super::emboss::{Emboss, EmbossMode};
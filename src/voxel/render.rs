@@ -0,0 +1,261 @@
+//!
+//! Sphere tracing of volumes. Renders depth and normal images of SDF
+//! to preview results of CSG and offsetting without meshing.
+//!
+
+use crate::{
+    geometry::primitives::box3::Box3,
+    helpers::aliases::{Vec3f, Vec3i},
+};
+
+use super::{volume::Volume, Tile, TreeNode, Visitor};
+
+/// Max number of steps along one ray
+const MAX_STEPS: usize = 1024;
+
+///
+/// Pinhole camera looking from `position` to `target`.
+///
+/// ## Example
+/// ```ignore
+/// let camera = Camera::look_at(Vec3f::new(0.0, 0.0, 5.0), Vec3f::zeros(), Vec3f::y())
+///     .with_fov(45.0_f32.to_radians());
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct Camera {
+    position: Vec3f,
+    forward: Vec3f,
+    right: Vec3f,
+    up: Vec3f,
+    fov: f32,
+}
+
+impl Camera {
+    /// Creates camera with vertical field of view of 60 degrees
+    pub fn look_at(position: Vec3f, target: Vec3f, up: Vec3f) -> Self {
+        let forward = (target - position).normalize();
+        let right = forward.cross(&up).normalize();
+        let up = right.cross(&forward);
+
+        Self {
+            position,
+            forward,
+            right,
+            up,
+            fov: 60.0_f32.to_radians(),
+        }
+    }
+
+    /// Sets vertical field of view in radians
+    #[inline]
+    pub fn with_fov(mut self, fov: f32) -> Self {
+        self.fov = fov;
+        self
+    }
+
+    #[inline]
+    pub fn position(&self) -> &Vec3f {
+        &self.position
+    }
+
+    /// Returns normalized direction of ray going through center of pixel `(x, y)`
+    fn ray_direction(&self, x: usize, y: usize, width: usize, height: usize) -> Vec3f {
+        let half_height = (self.fov * 0.5).tan();
+        let half_width = half_height * width as f32 / height as f32;
+
+        // Pixel center in [-1, 1], y goes down
+        let u = (2.0 * (x as f32 + 0.5) / width as f32) - 1.0;
+        let v = 1.0 - (2.0 * (y as f32 + 0.5) / height as f32);
+
+        (self.forward + self.right * (u * half_width) + self.up * (v * half_height)).normalize()
+    }
+}
+
+///
+/// Result of ray marching. Pixels are stored row by row starting from top left corner.
+/// Pixels where ray missed surface are `None`.
+///
+#[derive(Debug, Clone)]
+pub struct RenderBuffer {
+    width: usize,
+    height: usize,
+    depth: Vec<Option<f32>>,
+    normals: Vec<Option<Vec3f>>,
+}
+
+impl RenderBuffer {
+    #[inline]
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    #[inline]
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns distance from camera to surface along ray of pixel `(x, y)`
+    #[inline]
+    pub fn depth(&self, x: usize, y: usize) -> Option<f32> {
+        self.depth[y * self.width + x]
+    }
+
+    /// Returns unit normal of surface at pixel `(x, y)`
+    #[inline]
+    pub fn normal(&self, x: usize, y: usize) -> Option<Vec3f> {
+        self.normals[y * self.width + x]
+    }
+
+    #[inline]
+    pub fn depth_buffer(&self) -> &[Option<f32>] {
+        &self.depth
+    }
+
+    #[inline]
+    pub fn normal_buffer(&self) -> &[Option<Vec3f>] {
+        &self.normals
+    }
+}
+
+///
+/// Renders `volume` by sphere tracing its SDF from `camera`.
+/// `resolution` is `(width, height)` of resulting image in pixels.
+/// Outside of narrow band rays advance by one voxel, so surface is never skipped.
+///
+pub fn raymarch(volume: &Volume, camera: &Camera, resolution: (usize, usize)) -> RenderBuffer {
+    let (width, height) = resolution;
+    let pixels = width * height;
+
+    let mut buffer = RenderBuffer {
+        width,
+        height,
+        depth: vec![None; pixels],
+        normals: vec![None; pixels],
+    };
+
+    let bbox = match volume_bbox(volume) {
+        Some(bbox) => bbox,
+        None => return buffer,
+    };
+
+    for y in 0..height {
+        for x in 0..width {
+            let direction = camera.ray_direction(x, y, width, height);
+
+            if let Some(t) = trace(volume, &bbox, camera.position(), &direction) {
+                let hit = camera.position() + direction * t;
+                let pixel = y * width + x;
+
+                buffer.depth[pixel] = Some(t);
+                buffer.normals[pixel] = volume.normal(&hit);
+            }
+        }
+    }
+
+    buffer
+}
+
+/// Returns distance along ray to first intersection with zero level set
+fn trace(volume: &Volume, bbox: &Box3<f32>, origin: &Vec3f, direction: &Vec3f) -> Option<f32> {
+    let (t_enter, t_exit) = intersect_box(bbox, origin, direction)?;
+    let voxel_size = volume.voxel_size();
+    let epsilon = voxel_size * 1e-3;
+    let min_step = voxel_size * 0.1;
+
+    let mut t = t_enter;
+    let mut prev: Option<(f32, f32)> = None;
+
+    for _ in 0..MAX_STEPS {
+        if t > t_exit {
+            return None;
+        }
+
+        let point = origin + direction * t;
+
+        match volume.sample(&point) {
+            Some(distance) if distance <= epsilon => {
+                // Refine hit position between previous outside sample and current one
+                return match prev {
+                    Some((prev_t, prev_distance)) if distance < 0.0 => {
+                        Some(prev_t + (t - prev_t) * prev_distance / (prev_distance - distance))
+                    }
+                    _ => Some(t),
+                };
+            }
+            Some(distance) => {
+                prev = Some((t, distance));
+                t += distance.max(min_step);
+            }
+            None => {
+                prev = None;
+                t += voxel_size;
+            }
+        }
+    }
+
+    None
+}
+
+/// Returns parameters of entry and exit points of ray into box
+fn intersect_box(bbox: &Box3<f32>, origin: &Vec3f, direction: &Vec3f) -> Option<(f32, f32)> {
+    let mut t_min = 0.0_f32;
+    let mut t_max = f32::INFINITY;
+
+    for i in 0..3 {
+        let inv = 1.0 / direction[i];
+        let t1 = (bbox.get_min()[i] - origin[i]) * inv;
+        let t2 = (bbox.get_max()[i] - origin[i]) * inv;
+
+        t_min = t_min.max(t1.min(t2));
+        t_max = t_max.min(t1.max(t2));
+    }
+
+    if t_min <= t_max {
+        Some((t_min, t_max))
+    } else {
+        None
+    }
+}
+
+/// Returns bounding box of all defined grid points in world space
+fn volume_bbox(volume: &Volume) -> Option<Box3<f32>> {
+    let mut visitor = BBoxVisitor { bbox: None };
+    volume.grid().visit_leafs(&mut visitor);
+
+    visitor.bbox.map(|(mut min, max)| {
+        // Grid stores non-negative part of mirrored axes
+        for (axis, mirrored) in volume.symmetry().axes().into_iter().enumerate() {
+            if mirrored {
+                min[axis] = -max[axis];
+            }
+        }
+
+        let voxel_size = volume.voxel_size();
+        Box3::new(min.cast() * voxel_size, max.cast() * voxel_size)
+    })
+}
+
+struct BBoxVisitor {
+    bbox: Option<(Vec3i, Vec3i)>,
+}
+
+impl BBoxVisitor {
+    fn add(&mut self, origin: Vec3i, size: usize) {
+        let max = origin.add_scalar(size as isize - 1);
+        self.bbox = match self.bbox {
+            Some((bbox_min, bbox_max)) => Some((bbox_min.inf(&origin), bbox_max.sup(&max))),
+            None => Some((origin, max)),
+        };
+    }
+}
+
+impl<T: TreeNode> Visitor<T> for BBoxVisitor {
+    fn tile(&mut self, tile: Tile<T::Value>) {
+        self.add(tile.origin, tile.size);
+    }
+
+    fn dense(&mut self, dense: &T) {
+        self.add(dense.origin(), T::resolution());
+    }
+}
@@ -1,5 +1,6 @@
 mod csg;
 mod flood_fill;
+mod prune;
 mod tree_node;
 
 use super::*;
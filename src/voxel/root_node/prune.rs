@@ -0,0 +1,17 @@
+use super::*;
+
+impl<TChild: TreeNode> Prune for RootNode<TChild>
+where
+    TChild: Prune,
+    TChild::Value: Signed,
+{
+    fn prune(&mut self, tolerance: Self::Value) {
+        self.root.values_mut().for_each(|node| node.prune(tolerance));
+    }
+
+    #[inline]
+    fn uniform_value(&self, _: Self::Value) -> Option<Self::Value> {
+        // Root node can't be replaced by tile
+        None
+    }
+}
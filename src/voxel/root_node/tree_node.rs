@@ -1,4 +1,5 @@
 use super::*;
+#[cfg(feature = "rayon")]
 use rayon::prelude::*;
 
 impl<TChild> TreeNode for RootNode<TChild>
@@ -88,6 +89,22 @@ where
         Box::new(RootNode { root })
     }
 
+    fn memory_usage(&self) -> usize {
+        let entry_size = std::mem::size_of::<(RootKey, Box<TChild>)>();
+
+        std::mem::size_of::<Self>()
+            + self
+                .root
+                .values()
+                .map(|child| entry_size + child.memory_usage())
+                .sum::<usize>()
+    }
+
+    #[inline]
+    fn depth() -> usize {
+        TChild::depth() + 1
+    }
+
     fn clone(&self) -> Box<Self> {
         let root = self
             .root
@@ -98,6 +115,7 @@ where
         Box::new(RootNode { root })
     }
 
+    #[cfg(feature = "rayon")]
     fn visit_leafs_par<T: ParVisitor<Self::Leaf>>(&self, visitor: &T) {
         self.root
             .values()
@@ -106,6 +124,13 @@ where
             .for_each(|node| node.visit_leafs_par(visitor));
     }
 
+    #[cfg(not(feature = "rayon"))]
+    fn visit_leafs_par<T: ParVisitor<Self::Leaf>>(&self, visitor: &T) {
+        self.root
+            .values()
+            .for_each(|node| node.visit_leafs_par(visitor));
+    }
+
     fn visit_leafs<T: Visitor<Self::Leaf>>(&self, visitor: &mut T) {
         self.root
             .values()
@@ -3,6 +3,9 @@ use crate::dynamic_vdb;
 use crate::static_vdb;
 use crate::voxel::utils::box_indices;
 use crate::voxel::*;
+use crate::helpers::aliases::Vec3f;
+use crate::voxel::prelude::{AdaptiveMarchingCubesMesher, DualContouringMesher, MarchingCubesMesher, Volume};
+use crate::voxel::render::{raymarch, Camera};
 
 type StaticTree = static_vdb!(Empty, 4, 3, 2);
 type DynamicTree = dynamic_vdb!(Empty, 4, 3, 2);
@@ -93,3 +96,601 @@ fn test_dynamic_tree_insert_remove() {
 
     assert!(tree.is_empty());
 }
+
+#[test]
+fn test_smooth_union() {
+    let sphere = |x: f32| {
+        let center = Vec3f::new(x, 0.0, 0.0);
+        let offset = Vec3f::new(1.5, 1.5, 1.5);
+        Volume::from_fn(0.05, center - offset, center + offset, 5, move |p| (p - center).norm() - 1.0)
+    };
+
+    let sharp = sphere(-0.9).union(sphere(0.9));
+    let smooth = sphere(-0.9).smooth_union(sphere(0.9), 0.2);
+
+    // Distances to both spheres are equal at origin, so blending is maximal
+    let origin = Vec3i::zeros();
+    let sharp_value = *sharp.grid().at(&origin).unwrap();
+    let smooth_value = *smooth.grid().at(&origin).unwrap();
+    assert!((sharp_value - smooth_value - 0.05).abs() < 1e-5);
+
+    // Far from intersection volumes are the same
+    let far = Vec3i::new(-38, 0, 0);
+    assert!(sharp.grid().at(&far).is_some());
+    assert_eq!(sharp.grid().at(&far), smooth.grid().at(&far));
+}
+
+#[test]
+fn test_raymarch_sphere() {
+    let offset = Vec3f::new(1.5, 1.5, 1.5);
+    let sphere = Volume::from_fn(0.05, -offset, offset, 5, |p| p.norm() - 1.0);
+    let camera = Camera::look_at(Vec3f::new(0.0, 0.0, 5.0), Vec3f::zeros(), Vec3f::y());
+
+    let image = raymarch(&sphere, &camera, (33, 33));
+
+    // Ray through center hits sphere pole facing camera
+    let depth = image.depth(16, 16).unwrap();
+    let normal = image.normal(16, 16).unwrap();
+    assert!((depth - 4.0).abs() < 0.01);
+    assert!((normal - Vec3f::z()).norm() < 0.05);
+
+    // Corner rays miss
+    assert!(image.depth(0, 0).is_none());
+    assert!(image.normal(32, 32).is_none());
+}
+
+#[test]
+fn test_lerp() {
+    let sphere = |radius: f32, voxel_size: f32| {
+        let offset = Vec3f::new(2.5, 2.5, 2.5);
+        Volume::from_fn(voxel_size, -offset, offset, 8, move |p| p.norm() - radius)
+    };
+
+    // Halfway between spheres of radius 1 and 2 is sphere of radius 1.5
+    let half = sphere(1.0, 0.1).lerp(sphere(2.0, 0.1), 0.5);
+    assert!(half.grid().at(&Vec3i::new(15, 0, 0)).unwrap().abs() < 1e-5);
+    assert!((half.grid().at(&Vec3i::new(0, 12, 0)).unwrap() + 0.3).abs() < 1e-5);
+
+    // Other volume is resampled to voxel size of first one
+    let resampled = sphere(1.0, 0.1).lerp(sphere(1.2, 0.05), 0.5);
+    assert_eq!(resampled.voxel_size(), 0.1);
+    assert!(resampled.grid().at(&Vec3i::new(0, 0, -11)).unwrap().abs() < 0.01);
+}
+
+#[test]
+fn test_morph_meshes() {
+    use crate::{
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+        voxel::morph::morph_meshes,
+    };
+
+    let small: CornerTableF = primitives::icosphere(1.0, 2);
+    let large: CornerTableF = primitives::icosphere(1.5, 2);
+    let morph = morph_meshes(&small, &large, 0.5, 0.25).unwrap();
+
+    for vertex in morph.vertices() {
+        let radius = morph.vertex_position(&vertex).norm();
+        assert!((radius - 1.25).abs() < 0.1);
+    }
+}
+
+#[test]
+fn test_gradient() {
+    let offset = Vec3f::new(1.5, 1.5, 1.5);
+    let sphere = Volume::from_fn(0.05, -offset, offset, 5, |p| p.norm() - 1.0);
+
+    // Gradient of exact SDF has unit length and points away from center
+    let point = Vec3f::new(0.6, 0.5, 0.6);
+    let gradient = sphere.gradient(&point).unwrap();
+    assert!((gradient.norm() - 1.0).abs() < 0.01);
+    assert!((sphere.normal(&point).unwrap() - point.normalize()).norm() < 0.01);
+
+    // Outside of narrow band
+    assert!(sphere.gradient(&Vec3f::zeros()).is_none());
+}
+
+#[test]
+fn test_query_points() {
+    let offset = Vec3f::new(1.5, 1.5, 1.5);
+    let sphere = Volume::from_fn(0.05, -offset, offset, 5, |p| p.norm() - 1.0);
+
+    let points: Vec<_> = (0..100).map(|i| Vec3f::new(0.6, 0.5, 0.3 + i as f32 * 0.005)).collect();
+    let results = sphere.query_points(&points);
+    assert_eq!(results.len(), points.len());
+
+    for (point, (distance, gradient)) in points.iter().zip(&results) {
+        assert_eq!(Some(*distance), sphere.sample(point));
+        assert_eq!(*gradient, sphere.gradient(point).unwrap());
+    }
+
+    assert_eq!(sphere.query_points(&[Vec3f::zeros()]), vec![(f32::MAX, Vec3f::zeros())]);
+}
+
+#[test]
+fn test_smooth() {
+    let offset = Vec3f::new(1.5, 1.5, 1.5);
+    let bumpy = |p: &Vec3f| p.norm() - 1.0 + 0.03 * (p.x * 20.0).sin() * (p.y * 20.0).sin();
+    let volume = Volume::from_fn(0.05, -offset, offset, 4, bumpy);
+
+    // Difference between max and min radius of surface, smoothed sphere shrinks but stays round
+    let deviation = |volume: &Volume| {
+        let radii: Vec<_> = (0..100)
+            .filter_map(|i| {
+                let angle = i as f32 * 0.0628;
+                let direction = Vec3f::new(angle.cos(), angle.sin(), 0.3).normalize();
+                let distance = volume.sample(&direction)?;
+                let gradient = volume.gradient(&direction)?;
+                Some(1.0 - distance / gradient.norm())
+            })
+            .collect();
+        assert_eq!(radii.len(), 100);
+
+        radii.iter().fold(f32::MIN, |max, r| max.max(*r)) - radii.iter().fold(f32::MAX, |min, r| min.min(*r))
+    };
+
+    let smoothed = volume.clone().smooth(10, 0.5);
+    assert!(deviation(&smoothed) < deviation(&volume) * 0.5);
+
+    // Narrow band is rebuilt
+    let gradient = smoothed.gradient(&Vec3f::new(0.0, 0.0, 1.1)).unwrap();
+    assert!((gradient.norm() - 1.0).abs() < 0.1);
+
+    // Zero strength only redistances
+    let point = Vec3f::new(0.3, 0.2, 0.9);
+    assert_eq!(volume.clone().smooth(10, 0.0).sample(&point), volume.sample(&point));
+}
+
+#[test]
+fn test_transformed() {
+    use std::f32::consts::FRAC_PI_4;
+
+    use nalgebra::{Isometry3, Vector3};
+
+    let box_sdf = |p: &Vec3f| {
+        let q = p.abs() - Vec3f::new(1.0, 0.5, 0.5);
+        q.map(|c| c.max(0.0)).norm() + q.max().min(0.0)
+    };
+
+    let offset = Vec3f::new(2.0, 2.0, 2.0);
+    let volume = Volume::from_fn(0.05, -offset, offset, 4, box_sdf);
+
+    let iso = Isometry3::new(Vector3::new(1.0, 0.0, 0.5), Vector3::z() * FRAC_PI_4);
+    let moved = volume.transformed(iso);
+    assert_eq!(moved.voxel_size(), volume.voxel_size());
+
+    // Distances at transformed points are preserved
+    for point in [Vec3f::new(1.0, 0.0, 0.0), Vec3f::new(0.5, 0.55, 0.1), Vec3f::new(-0.3, 0.2, -0.5)] {
+        let expected = box_sdf(&point);
+        let world = iso.transform_point(&point.into()).coords;
+        assert!((moved.sample(&world).unwrap() - expected).abs() < 0.01);
+    }
+
+    // Far from surface of moved volume
+    assert!(moved.sample(&Vec3f::new(-1.5, 0.0, 0.0)).is_none());
+}
+
+#[test]
+fn test_from_mesh_to_mesh() {
+    use crate::{
+        mesh::{corner_table::prelude::CornerTableF, primitives, traits::Mesh},
+        voxel::prelude::MesherKind,
+    };
+
+    let sphere: CornerTableF = primitives::icosphere(1.0, 3);
+    let volume = Volume::from_mesh(&sphere, 0.1).unwrap();
+
+    for kind in [MesherKind::MarchingCubes, MesherKind::DualContouring] {
+        let mesh = volume.to_mesh(kind).unwrap();
+        assert!(mesh.faces().count() > 100);
+
+        for vertex in mesh.vertices() {
+            let radius = mesh.vertex_position(&vertex).norm();
+            assert!((radius - 1.0).abs() < 0.1);
+        }
+    }
+
+    assert!(Volume::with_voxel_size(0.1).to_mesh(MesherKind::MarchingCubes).is_none());
+}
+
+#[test]
+fn test_adaptive_marching_cubes() {
+    use crate::{
+        algo::mass_properties::mass_properties,
+        mesh::traits::{Mesh, TopologicalMesh},
+        voxel::prelude::{MesherKind, VolumeBuilder},
+    };
+
+    let builder = VolumeBuilder::default().with_voxel_size(0.05);
+    let cuboid = builder.cuboid(Vec3f::new(-0.5, -0.4, -0.3), Vec3f::new(0.5, 0.4, 0.3));
+    let sphere = builder.sphere(0.5, Vec3f::zeros());
+
+    // Flat faces of box are meshed by coarse cells, sphere is curved everywhere
+    for (volume, min_reduction) in [(cuboid, 2.0), (sphere, 1.0)] {
+        let uniform = volume.to_mesh(MesherKind::MarchingCubes).unwrap();
+        let adaptive = volume.to_mesh(MesherKind::AdaptiveMarchingCubes).unwrap();
+        assert!(adaptive.faces().count() as f32 * min_reduction < uniform.faces().count() as f32);
+
+        // Levels are stitched without cracks
+        assert_eq!(adaptive.edges().filter(|edge| adaptive.is_edge_on_boundary(edge)).count(), 0);
+
+        let expected = mass_properties(&uniform, 1.0).volume;
+        assert!((mass_properties(&adaptive, 1.0).volume - expected).abs() / expected < 0.01);
+    }
+}
+
+#[test]
+fn test_manifold_dual_contouring() {
+    use std::collections::HashMap;
+
+    use crate::{
+        algo::merge_points::merge_points,
+        voxel::prelude::{ManifoldDualContouringMesher, MarchingCubesMesher, VolumeBuilder},
+    };
+
+    // Every edge has 2 faces and faces around every vertex form single fan
+    let is_manifold = |faces: &Vec<Vec3f>| {
+        let indexed = merge_points(faces);
+        let mut edges = HashMap::new();
+        let mut links: HashMap<usize, Vec<(usize, usize)>> = HashMap::new();
+
+        for face in indexed.indices.chunks_exact(3) {
+            for i in 0..3 {
+                let (v0, v1, v2) = (face[i], face[(i + 1) % 3], face[(i + 2) % 3]);
+                *edges.entry((v0.min(v1), v0.max(v1))).or_insert(0) += 1;
+                links.entry(v0).or_default().push((v1, v2));
+            }
+        }
+
+        // Walk around vertex, it should return to start only after visiting all faces
+        let is_fan = |link: &Vec<(usize, usize)>| {
+            let next: HashMap<_, _> = link.iter().copied().collect();
+            let start = link[0].0;
+            let mut current = start;
+
+            for step in 1..=link.len() {
+                let Some(v) = next.get(&current) else {
+                    return false;
+                };
+
+                current = *v;
+
+                if (current == start) != (step == link.len()) {
+                    return false;
+                }
+            }
+
+            next.len() == link.len()
+        };
+
+        edges.values().all(|count| *count == 2) && links.values().all(is_fan)
+    };
+
+    let builder = VolumeBuilder::default().with_voxel_size(0.05);
+    let min = Vec3f::new(-0.52, -0.41, -0.33);
+    let max = Vec3f::new(0.47, 0.38, 0.29);
+    let cuboid = builder.cuboid(min, max);
+
+    // Corners of box are reconstructed better than by marching cubes
+    let faces = ManifoldDualContouringMesher::default().mesh_triangles(&cuboid);
+    let mc_faces = MarchingCubesMesher::default().mesh_triangles(&cuboid);
+    assert!(is_manifold(&faces));
+
+    for corner in 0..8 {
+        let corner = Vec3f::new(
+            if corner & 1 == 0 { min.x } else { max.x },
+            if corner & 2 == 0 { min.y } else { max.y },
+            if corner & 4 == 0 { min.z } else { max.z },
+        );
+        let closest = |faces: &Vec<Vec3f>| faces.iter().map(|v| (v - corner).norm()).fold(f32::MAX, f32::min);
+        assert!(closest(&faces) < closest(&mc_faces));
+    }
+
+    // Boxes touching along edge are meshed without non-manifold edges
+    let touching = builder
+        .cuboid(Vec3f::new(-0.48, -0.47, -0.46), Vec3f::new(0.01, 0.02, 0.44))
+        .union(builder.cuboid(Vec3f::new(0.01, 0.02, -0.46), Vec3f::new(0.48, 0.47, 0.44)));
+    assert!(is_manifold(&ManifoldDualContouringMesher::default().mesh_triangles(&touching)));
+}
+
+#[test]
+fn test_mesh_to_volume_leak_check() {
+    use crate::{
+        mesh::{corner_table::prelude::CornerTableF, primitives},
+        voxel::prelude::MeshToVolume,
+    };
+
+    let mut mesh_to_volume = MeshToVolume::default().with_voxel_size(0.1).with_leak_check(true);
+
+    let plane: CornerTableF = primitives::plane(1.0, 1.0, 4, 4);
+    assert!(mesh_to_volume.convert(&plane).is_none());
+    assert_eq!(mesh_to_volume.leaks().unwrap().boundary_loops.len(), 1);
+
+    let sphere: CornerTableF = primitives::icosphere(1.0, 2);
+    assert!(mesh_to_volume.convert(&sphere).is_some());
+    assert!(mesh_to_volume.leaks().is_none());
+}
+
+#[test]
+fn test_surface_integrals() {
+    use std::f32::consts::PI;
+
+    use crate::mesh::{corner_table::prelude::CornerTableF, primitives};
+
+    let offset = Vec3f::new(1.5, 1.5, 1.5);
+    let sphere = Volume::from_fn(0.05, -offset, offset, 2, |p| p.norm() - 1.0);
+
+    let area = sphere.surface_area_estimate();
+    let volume = sphere.enclosed_volume_estimate();
+    assert!((area - 4.0 * PI).abs() < 0.02 * 4.0 * PI, "Area: {}", area);
+    assert!((volume - 4.0 / 3.0 * PI).abs() < 0.02 * 4.0 / 3.0 * PI, "Volume: {}", volume);
+
+    // Volume does not depend on position
+    let center = Vec3f::new(3.0, -2.0, 1.0);
+    let moved = Volume::from_fn(0.05, center - offset, center + offset, 2, |p| (p - center).norm() - 1.0);
+    assert!((moved.enclosed_volume_estimate() - volume).abs() < 0.01 * volume);
+
+    // Sharp edges are smoothed by narrow band
+    let cube: CornerTableF = primitives::cuboid(Vec3f::new(1.0, 1.0, 1.0), 4);
+    let cube = Volume::from_mesh(&cube, 0.05).unwrap();
+    assert!((cube.surface_area_estimate() - 6.0).abs() < 0.3);
+    assert!((cube.enclosed_volume_estimate() - 1.0).abs() < 0.05);
+}
+
+#[test]
+fn test_prune() {
+    use crate::{mesh::traits::Mesh, voxel::prelude::MesherKind};
+
+    // Truncated distance field has large uniform regions inside and outside of sphere
+    let truncated_sphere = |center: Vec3f| {
+        let offset = Vec3f::new(2.0, 2.0, 2.0);
+        Volume::from_fn(0.05, center - offset, center + offset, 100, move |p| {
+            ((p - center).norm() - 1.2).clamp(-0.15, 0.15)
+        })
+    };
+
+    let volume = truncated_sphere(Vec3f::zeros());
+    let pruned = volume.clone().prune(0.0);
+    assert!(pruned.active_values().len() < volume.active_values().len() / 2);
+    assert_eq!(pruned.sample(&Vec3f::zeros()), Some(-0.15));
+    assert_eq!(pruned.sample(&Vec3f::new(1.9, 1.9, 1.9)), Some(0.15));
+
+    for kind in [MesherKind::MarchingCubes, MesherKind::DualContouring] {
+        let expected = volume.to_mesh(kind).unwrap();
+        let mesh = pruned.to_mesh(kind).unwrap();
+        assert_eq!(mesh.faces().count(), expected.faces().count());
+    }
+
+    // CSG results are pruned when auto prune is set
+    let a = truncated_sphere(Vec3f::new(-0.5, 0.0, 0.0));
+    let b = truncated_sphere(Vec3f::new(0.5, 0.0, 0.0));
+    let union = a.clone().union(b.clone());
+    let pruned_union = a.with_auto_prune(Some(0.0)).union(b);
+    assert_eq!(pruned_union.auto_prune(), Some(0.0));
+    assert!(pruned_union.active_values().len() < union.active_values().len() / 2);
+
+    let expected = union.to_mesh(MesherKind::MarchingCubes).unwrap();
+    let mesh = pruned_union.to_mesh(MesherKind::MarchingCubes).unwrap();
+    assert_eq!(mesh.faces().count(), expected.faces().count());
+}
+
+#[test]
+fn test_stats() {
+    let volume = Volume::from_fn(0.05, Vec3f::new(-2.0, -2.0, -2.0), Vec3f::new(2.0, 2.0, 2.0), 100, |p| {
+        (p.norm() - 1.2).clamp(-0.15, 0.15)
+    });
+
+    let stats = volume.stats();
+    assert_eq!(stats.depth, 4);
+    assert_eq!(stats.tiles, 0);
+    assert_eq!(stats.leaf_nodes, volume.leafs_count());
+    assert_eq!(stats.active_voxels, volume.active_values().len());
+    assert!(stats.memory_bytes > stats.leaf_nodes * 8 * 8 * 8 * std::mem::size_of::<f32>());
+
+    // Uniform leafs are replaced by tiles
+    let pruned = volume.prune(0.0).stats();
+    assert!(pruned.tiles > 0);
+    assert!(pruned.leaf_nodes < stats.leaf_nodes);
+    assert!(pruned.memory_bytes < stats.memory_bytes);
+    assert_eq!(Volume::with_voxel_size(0.1).stats().memory_bytes, std::mem::size_of::<volume::VolumeGrid>());
+}
+
+#[test]
+fn test_symmetric_volume() {
+    use crate::{
+        algo::merge_points::merge_points,
+        mesh::{
+            corner_table::prelude::CornerTableF,
+            primitives,
+            traits::{Mesh, TopologicalMesh},
+        },
+        voxel::prelude::{ManifoldDualContouringMesher, MeshToVolume, Mesher, MesherKind, Symmetry},
+    };
+
+    let sphere: CornerTableF = primitives::icosphere(1.0, 3);
+    let convert = |symmetry| {
+        MeshToVolume::default()
+            .with_voxel_size(0.1)
+            .with_narrow_band_width(1)
+            .with_symmetry(symmetry)
+            .convert(&sphere)
+            .unwrap()
+    };
+
+    let full = convert(Symmetry::NONE);
+    let half = convert(Symmetry { x: true, ..Symmetry::NONE });
+    let quarter = convert(Symmetry { x: true, y: true, z: false });
+    let stored = |volume: &Volume| Volume::new(volume.grid().clone(), volume.voxel_size()).active_values().len();
+
+    assert!(stored(&half) < stored(&full) * 6 / 10);
+    assert!(stored(&quarter) < stored(&full) * 3 / 10);
+    assert_eq!(quarter.active_values().len(), full.active_values().len());
+
+    for point in [Vec3f::new(-0.95, 0.1, 0.2), Vec3f::new(0.3, -0.9, -0.3), Vec3f::new(-0.5, -0.5, 0.7)] {
+        let expected = full.sample(&point).unwrap();
+        assert!((half.sample(&point).unwrap() - expected).abs() < 1e-4);
+        assert!((quarter.sample(&point).unwrap() - expected).abs() < 1e-4);
+    }
+
+    let expected = full.to_mesh(MesherKind::MarchingCubes).unwrap().faces().count();
+
+    for kind in [MesherKind::MarchingCubes, MesherKind::DualContouring] {
+        let mesh = quarter.to_mesh(kind).unwrap();
+        assert!(mesh.faces().count().abs_diff(expected) < expected / 50);
+
+        for vertex in mesh.vertices() {
+            assert!((mesh.vertex_position(&vertex).norm() - 1.0).abs() < 0.1);
+        }
+    }
+
+    // Meshers called directly extract whole surface of symmetric volume too
+    fn extract(mut mesher: impl Mesher, volume: &Volume) -> Vec<Vec3f> {
+        mesher.mesh(volume).vertices
+    }
+
+    let meshers: [fn(&Volume) -> Vec<Vec3f>; 4] = [
+        |volume| extract(MarchingCubesMesher::default(), volume),
+        |volume| extract(DualContouringMesher::default(), volume),
+        |volume| extract(AdaptiveMarchingCubesMesher::default(), volume),
+        |volume| extract(ManifoldDualContouringMesher::default(), volume),
+    ];
+
+    let boundary_edges = |faces: &Vec<Vec3f>| {
+        let indexed = merge_points(faces);
+        let mesh = CornerTableF::from_vertices_and_indices(&indexed.points, &indexed.indices);
+        mesh.edges().filter(|edge| mesh.is_edge_on_boundary(edge)).count()
+    };
+
+    for mesh in meshers {
+        let expected = mesh(&full);
+        let faces = mesh(&quarter);
+        assert!((faces.len() / 3).abs_diff(expected.len() / 3) < expected.len() / 150);
+        assert!(faces.iter().all(|vertex| (vertex.norm() - 1.0).abs() < 0.1));
+
+        // Triangles along symmetry planes are connected to their images, so no holes are left
+        assert_eq!(boundary_edges(&faces), boundary_edges(&expected));
+    }
+
+    assert!((quarter.surface_area_estimate() - full.surface_area_estimate()).abs() < 0.01);
+
+    // Volumes with same symmetry are combined without unfolding
+    assert_eq!(half.clone().union(half.clone()).symmetry(), half.symmetry());
+    assert!(half.clone().union(full.clone()).symmetry().is_none());
+    assert_eq!(stored(&half.unfold()), stored(&full));
+}
+
+#[test]
+fn test_coordinate_conversions() {
+    let volume = Volume::from_fn(0.1, Vec3f::repeat(-1.5), Vec3f::repeat(1.5), 2, |p| p.norm() - 1.0);
+
+    let index = Vec3i::new(3, -7, 10);
+    assert!((volume.index_to_world(&index) - Vec3f::new(0.3, -0.7, 1.0)).norm() < 1e-6);
+    assert_eq!(volume.world_to_index(&volume.index_to_world(&index)), index);
+    assert_eq!(volume.world_to_index(&Vec3f::new(0.34, -0.66, 0.96)), index);
+    assert!((volume.world_to_grid(&Vec3f::new(0.35, 0.0, 0.0)).x - 3.5).abs() < 1e-5);
+
+    // Values are sampled at grid points
+    for (index, value) in volume.active_values().into_iter().take(100) {
+        assert!((volume.sample(&volume.index_to_world(&index)).unwrap() - value).abs() < 1e-5);
+    }
+
+    let (min, max) = volume.index_bounds().unwrap();
+    assert_eq!(min, -max);
+    let bounds = volume.bounds().unwrap();
+    assert!(bounds.get_max().x > 1.0 && bounds.get_max().x < 1.5);
+    assert!(Volume::with_voxel_size(0.1).bounds().is_none());
+}
+
+#[test]
+fn test_meshers_default_voxel_size() {
+    use crate::voxel::prelude::{ManifoldDualContouringMesher, Mesher};
+
+    let volume = Volume::from_fn(0.1, Vec3f::repeat(-1.5), Vec3f::repeat(1.5), 2, |p| p.norm() - 1.0);
+
+    // Meshers use voxel size of volume by default, so vertices are in world coordinates
+    for faces in [
+        MarchingCubesMesher::default().mesh_triangles(&volume),
+        DualContouringMesher::default().mesh_triangles(&volume).unwrap(),
+        AdaptiveMarchingCubesMesher::default().mesh_triangles(&volume),
+        ManifoldDualContouringMesher::default().mesh_triangles(&volume),
+    ] {
+        assert!(!faces.is_empty());
+        assert!(faces.iter().all(|vertex| (vertex.norm() - 1.0).abs() < 0.05));
+    }
+
+    // Voxel size of 1 (default before 0.4.0) gives vertices in grid coordinates
+    let faces = MarchingCubesMesher::default().with_voxel_size(1.0).mesh_triangles(&volume);
+    assert!(faces.iter().all(|vertex| (vertex.norm() - 10.0).abs() < 0.5));
+}
+
+#[test]
+fn test_mesher_trait() {
+    use crate::voxel::{
+        meshing::ActiveVoxelsMesher,
+        prelude::{ManifoldDualContouringMesher, MeshOutput, Mesher},
+    };
+
+    let volume = Volume::from_fn(0.05, Vec3f::new(-1.5, -1.5, -1.5), Vec3f::new(1.5, 1.5, 1.5), 6, |p| p.norm() - 1.0);
+
+    fn extract(mesher: impl Mesher, volume: &Volume) -> MeshOutput {
+        mesher.with_iso_value(0.1).with_normals(true).mesh(volume)
+    }
+
+    for output in [
+        extract(MarchingCubesMesher::default(), &volume),
+        extract(DualContouringMesher::default(), &volume),
+        extract(AdaptiveMarchingCubesMesher::default(), &volume),
+        extract(ManifoldDualContouringMesher::default(), &volume),
+    ] {
+        let normals = output.normals.unwrap();
+        assert!(!output.vertices.is_empty());
+        assert_eq!(normals.len(), output.vertices.len());
+
+        for (vertex, normal) in output.vertices.iter().zip(normals) {
+            assert!((vertex.norm() - 1.1).abs() < 0.05);
+            assert!(normal.dot(&vertex.normalize()) > 0.9);
+        }
+    }
+
+    // Voxel size set on mesher is used to place vertices, normals are off by default
+    let output = MarchingCubesMesher::default().with_voxel_size(0.1).mesh(&volume);
+    assert!(output.normals.is_none());
+    assert!(output.vertices.iter().all(|vertex| (vertex.norm() - 2.0).abs() < 0.1));
+
+    // Active voxels inside of level set are meshed, so blocky surface grows with iso value
+    let radius = |output: MeshOutput| output.vertices.iter().map(|vertex| vertex.norm()).fold(0.0, f32::max);
+    let surface = radius(ActiveVoxelsMesher::default().mesh(&volume));
+    let shell = radius(extract(ActiveVoxelsMesher::default(), &volume));
+    assert!((surface - 1.0).abs() < 0.1);
+    assert!((shell - 1.1).abs() < 0.1);
+}
+
+#[test]
+fn test_build_pyramid() {
+    use crate::{
+        mesh::traits::{Mesh, TopologicalMesh},
+        voxel::prelude::MesherKind,
+    };
+
+    // Narrow band of one voxel is too thin for coarse levels without estimating missing distances
+    let radius = 1.0;
+    let extent = Vec3f::new(1.5, 1.5, 1.5);
+    let volume = Volume::from_fn(0.025, -extent, extent, 1, |p| p.norm() - radius);
+
+    let pyramid = volume.build_pyramid(4);
+    assert_eq!(pyramid.len(), 4);
+    assert!(volume.build_pyramid(0).is_empty());
+
+    let mut faces_count = usize::MAX;
+
+    for (level, lod) in pyramid.levels().iter().enumerate() {
+        assert_eq!(lod.voxel_size(), volume.voxel_size() * (1 << level) as f32);
+
+        let mesh = pyramid.to_mesh(level, MesherKind::MarchingCubes).unwrap();
+        assert!(mesh.vertices().all(|v| (mesh.vertex_position(&v).norm() - radius).abs() < lod.voxel_size() * 0.25));
+        assert!(mesh.edges().all(|edge| !mesh.is_edge_on_boundary(&edge)));
+        assert!(mesh.faces().count() < faces_count);
+        faces_count = mesh.faces().count();
+    }
+
+    assert!(pyramid.to_mesh(4, MesherKind::MarchingCubes).is_none());
+}
@@ -1,3 +1,4 @@
+use super::Signed;
 use crate::helpers::aliases::Vec3i;
 use std::cmp::Ordering;
 
@@ -7,14 +8,13 @@ pub fn box_indices(start: isize, end: isize) -> impl Iterator<Item = Vec3i> {
     region(Vec3i::new(start, start, start), Vec3i::new(end, end, end))
 }
 
-#[cfg(test)]
 pub fn region(start: Vec3i, end: Vec3i) -> impl Iterator<Item = Vec3i> {
     (start.x..end.x).flat_map(move |x| {
         (start.y..end.y).flat_map(move |y| (start.z..end.z).map(move |z| Vec3i::new(x, y, z)))
     })
 }
 
-#[cfg(test)]
+/// Returns grid points on faces of box `[start, end)`
 pub fn region_boundary(start: Vec3i, end: Vec3i) -> impl Iterator<Item = Vec3i> {
     region(start, Vec3i::new(start.x + 1, end.y, end.z))
         .chain(region(Vec3i::new(end.x - 1, start.y, start.z), end))
@@ -36,6 +36,22 @@ pub fn region_boundary(start: Vec3i, end: Vec3i) -> impl Iterator<Item = Vec3i>
         ))
 }
 
+///
+/// Returns first value if all values have its sign and differ from it by no more than `tolerance`
+///
+pub fn uniform_value<T: Signed>(mut values: impl Iterator<Item = T>, tolerance: T) -> Option<T> {
+    let first = values.next()?;
+    let sign = first.sign();
+
+    for value in values {
+        if value.sign() != sign || value - first > tolerance || first - value > tolerance {
+            return None;
+        }
+    }
+
+    Some(first)
+}
+
 #[inline]
 pub fn partial_min<T: PartialOrd>(a: T, b: T) -> T {
     match a.partial_cmp(&b) {
@@ -60,6 +76,25 @@ pub fn partial_max<T: PartialOrd>(a: T, b: T) -> T {
     }
 }
 
+///
+/// Polynomial smooth minimum. Differs from `min` only when `|a - b| < k`.
+///
+#[inline]
+pub fn smooth_min(a: f32, b: f32, k: f32) -> f32 {
+    if k <= 0.0 {
+        return a.min(b);
+    }
+
+    let h = (k - (a - b).abs()).max(0.0) / k;
+    a.min(b) - h * h * k * 0.25
+}
+
+/// Polynomial smooth maximum. See [smooth_min]
+#[inline]
+pub fn smooth_max(a: f32, b: f32, k: f32) -> f32 {
+    -smooth_min(-a, -b, k)
+}
+
 #[inline]
 pub fn option_min_by<T>(a: Option<T>, b: Option<T>, cmp: impl Fn(&T, &T) -> Ordering) -> Option<T> {
     match (a, b) {
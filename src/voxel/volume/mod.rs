@@ -1,16 +1,167 @@
 pub mod builder;
+mod pyramid;
+
+pub use pyramid::VolumePyramid;
+
+use std::{collections::HashSet, fmt::Display};
+
+use nalgebra::{Isometry3, Point3};
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
 
 use self::fast_sweep::FastSweeping;
+use self::utils::{region, smooth_max, smooth_min};
 use self::visitors::ValueMutVisitor;
 use crate::voxel::*;
-use crate::{dynamic_vdb, helpers::aliases::Vec3f};
+use crate::{
+    algo::merge_points::merge_points,
+    dynamic_vdb,
+    geometry::primitives::box3::Box3,
+    helpers::{
+        aliases::Vec3f,
+        trace::{trace_counters, trace_enabled, trace_span},
+    },
+    mesh::{corner_table::prelude::CornerTableF, traits::Mesh},
+};
+
+use super::{
+    mesh_to_volume::MeshToVolume,
+    meshing::{AdaptiveMarchingCubesMesher, DualContouringMesher, ManifoldDualContouringMesher, MarchingCubesMesher},
+};
 
 pub(super) type VolumeGrid = dynamic_vdb!(f32, par 5, 4, 3);
 
+/// Narrow band width (in voxels) of volumes created by [Volume::from_mesh]
+const FROM_MESH_BAND_WIDTH: isize = 1;
+/// Half width (in voxels) of smoothed Dirac delta used for surface integrals
+const DIRAC_HALF_WIDTH: f32 = 1.5;
+
+///
+/// Statistics of volume grid returned by [Volume::stats]. Useful to choose voxel size and to find
+/// operations blowing up memory, CSG operations log them when `tracing` feature is enabled.
+///
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct VolumeStats {
+    /// Number of allocated leaf nodes, dense blocks of grid points
+    pub leaf_nodes: usize,
+    /// Number of tiles, regions of grid storing single value
+    pub tiles: usize,
+    /// Number of grid points stored in leaf nodes
+    pub active_voxels: usize,
+    /// Approximate number of bytes allocated by grid
+    pub memory_bytes: usize,
+    /// Number of tree levels from root down to leaf nodes
+    pub depth: usize,
+}
+
+impl Display for VolumeStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "leaf nodes: {}, tiles: {}, active voxels: {}, memory: {:.2} MiB, depth: {}",
+            self.leaf_nodes,
+            self.tiles,
+            self.active_voxels,
+            self.memory_bytes as f64 / (1024.0 * 1024.0),
+            self.depth
+        )
+    }
+}
+
+/// Meshing algorithm used by [Volume::to_mesh]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MesherKind {
+    /// Manifold mesh which may smooth sharp features, see [MarchingCubesMesher]
+    MarchingCubes,
+    /// Feature preserving mesh which may be non-manifold, see [DualContouringMesher]
+    DualContouring,
+    /// Marching cubes with larger triangles in flat regions, see [AdaptiveMarchingCubesMesher]
+    AdaptiveMarchingCubes,
+    /// Feature preserving manifold mesh, see [ManifoldDualContouringMesher]
+    ManifoldDualContouring,
+}
+
+///
+/// Planes of mirror symmetry of volume. Planes pass through origin and are orthogonal to coordinate axes,
+/// e.g. `x` is plane `x = 0`. Symmetric volume stores only grid points with non-negative coordinates along
+/// mirrored axes, other grid points are mirrored on access and meshing. See [MeshToVolume::with_symmetry].
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Symmetry {
+    pub x: bool,
+    pub y: bool,
+    pub z: bool,
+}
+
+impl Symmetry {
+    /// No symmetry planes
+    pub const NONE: Self = Self { x: false, y: false, z: false };
+
+    /// Returns whether volume is mirrored along each axis
+    #[inline]
+    pub fn axes(&self) -> [bool; 3] {
+        [self.x, self.y, self.z]
+    }
+
+    #[inline]
+    pub fn is_none(&self) -> bool {
+        *self == Self::NONE
+    }
+
+    /// Returns index of stored grid point which is mirror image of `index`
+    #[inline]
+    fn stored_index(&self, index: &Vec3i) -> Vec3i {
+        Vec3i::new(
+            if self.x { index.x.abs() } else { index.x },
+            if self.y { index.y.abs() } else { index.y },
+            if self.z { index.z.abs() } else { index.z },
+        )
+    }
+
+    /// Returns all distinct mirror images of stored grid point `index` including itself
+    fn images(&self, index: &Vec3i) -> Vec<Vec3i> {
+        let mut images = vec![*index];
+
+        for (axis, mirrored) in self.axes().into_iter().enumerate() {
+            if !mirrored || index[axis] == 0 {
+                continue;
+            }
+
+            for i in 0..images.len() {
+                let mut image = images[i];
+                image[axis] = -image[axis];
+                images.push(image);
+            }
+        }
+
+        images
+    }
+}
+
+///
+/// Signed distance field stored in sparse grid, inside is negative.
+///
+/// Grid points far from surface can be merged into tiles by [Volume::prune]: parts of grid with uniform values
+/// (e.g. interior of large solids after CSG) are then stored as single value instead of dense nodes.
+/// Pruned volumes are supported by all operations and meshers. Results of CSG are pruned automatically
+/// when tolerance is set by [Volume::with_auto_prune].
+///
+/// ## Example
+/// ```ignore
+/// let part = body.with_auto_prune(Some(0.0)).union(lid).subtract(hole);
+/// let mesh = part.to_mesh(MesherKind::DualContouring)?;
+/// ```
+///
+/// Volume may store only part of grid when it has [Symmetry], operations which can't work on stored part
+/// convert volume to full grid first, see [Volume::unfold].
+///
 #[derive(Debug)]
 pub struct Volume {
     grid: Box<VolumeGrid>,
     voxel_size: f32,
+    auto_prune: Option<f32>,
+    symmetry: Symmetry,
 }
 
 impl Volume {
@@ -20,12 +171,123 @@ impl Volume {
         Self {
             voxel_size,
             grid: VolumeGrid::empty(Vec3i::zeros()),
+            auto_prune: None,
+            symmetry: Symmetry::NONE,
         }
     }
 
+    ///
+    /// Set tolerance used to prune results of CSG operations, see [Volume::prune].
+    /// Default is `None`, results are not pruned.
+    ///
+    #[inline]
+    pub fn with_auto_prune(mut self, tolerance: Option<f32>) -> Self {
+        self.set_auto_prune(tolerance);
+        self
+    }
+
+    #[inline]
+    pub fn set_auto_prune(&mut self, tolerance: Option<f32>) {
+        self.auto_prune = tolerance;
+    }
+
+    #[inline]
+    pub fn auto_prune(&self) -> Option<f32> {
+        self.auto_prune
+    }
+
+    ///
+    /// Converts mesh to signed distance field with narrow band of one voxel, see [MeshToVolume] for more control.
+    /// Returns `None` when mesh is empty or signs can't be computed.
+    ///
+    /// ## Example
+    /// ```ignore
+    /// let volume = Volume::from_mesh(&mesh, 0.05)?.offset(0.1);
+    /// let offset: CornerTableF = volume.to_mesh(MesherKind::MarchingCubes)?;
+    /// ```
+    ///
+    pub fn from_mesh<T: Mesh<ScalarType = f32>>(mesh: &T, voxel_size: f32) -> Option<Self> {
+        let mut volume = MeshToVolume::default()
+            .with_voxel_size(voxel_size)
+            .with_narrow_band_width(FROM_MESH_BAND_WIDTH)
+            .convert(mesh)?;
+        volume.grid.flood_fill();
+
+        Some(volume)
+    }
+
+    ///
+    /// Extracts zero level set of volume as indexed mesh with merged vertices.
+    /// Returns `None` when volume has no surface.
+    ///
+    pub fn to_mesh(&self, kind: MesherKind) -> Option<CornerTableF> {
+        let faces = match kind {
+            MesherKind::MarchingCubes => MarchingCubesMesher::default().mesh_triangles(self),
+            MesherKind::DualContouring => DualContouringMesher::default().mesh_triangles(self)?,
+            MesherKind::AdaptiveMarchingCubes => AdaptiveMarchingCubesMesher::default().mesh_triangles(self),
+            MesherKind::ManifoldDualContouring => ManifoldDualContouringMesher::default().mesh_triangles(self),
+        };
+
+        if faces.is_empty() {
+            return None;
+        }
+
+        let indexed_faces = merge_points(&faces);
+        Some(CornerTableF::from_vertices_and_indices(&indexed_faces.points, &indexed_faces.indices))
+    }
+
     #[inline]
     pub(super) fn new(grid: Box<VolumeGrid>, voxel_size: f32) -> Self {
-        Self { grid, voxel_size }
+        Self::new_symmetric(grid, voxel_size, Symmetry::NONE)
+    }
+
+    /// Creates volume from grid storing grid points with non-negative coordinates along mirrored axes
+    #[inline]
+    pub(super) fn new_symmetric(grid: Box<VolumeGrid>, voxel_size: f32, symmetry: Symmetry) -> Self {
+        Self {
+            grid,
+            voxel_size,
+            auto_prune: None,
+            symmetry,
+        }
+    }
+
+    #[inline]
+    pub fn symmetry(&self) -> Symmetry {
+        self.symmetry
+    }
+
+    ///
+    /// Returns volume storing all grid points, mirror images of stored grid points are inserted into grid.
+    /// Returns volume unchanged when it has no symmetry.
+    ///
+    pub fn unfold(mut self) -> Self {
+        if self.symmetry.is_none() {
+            return self;
+        }
+
+        for (index, value) in self.active_values() {
+            self.grid.insert(&index, value);
+        }
+
+        // Mirror images of tiles are not aligned to nodes, so they are inserted voxel by voxel and pruned back
+        let mut tiles = TilesVisitor { tiles: Vec::new() };
+        self.grid.visit_leafs(&mut tiles);
+
+        for (origin, size, value) in &tiles.tiles {
+            for index in region(*origin, origin.add_scalar(*size as isize)) {
+                for image in self.symmetry.images(&index).into_iter().skip(1) {
+                    self.grid.insert(&image, *value);
+                }
+            }
+        }
+
+        if !tiles.tiles.is_empty() {
+            self.grid.prune(0.0);
+        }
+
+        self.symmetry = Symmetry::NONE;
+        self
     }
 
     #[inline]
@@ -33,6 +295,50 @@ impl Volume {
         self.voxel_size
     }
 
+    ///
+    /// Returns world position of grid point. Values of volume are distances sampled at grid points
+    /// (not at centers of voxels), grid point `index` is located at `index * voxel_size`.
+    ///
+    #[inline]
+    pub fn index_to_world(&self, index: &Vec3i) -> Vec3f {
+        index.cast() * self.voxel_size
+    }
+
+    /// Returns index of grid point closest to `point`
+    #[inline]
+    pub fn world_to_index(&self, point: &Vec3f) -> Vec3i {
+        self.world_to_grid(point).map(|c| c.round() as isize)
+    }
+
+    /// Converts point from grid coordinates (in voxels, grid points have integer coordinates) to world coordinates
+    #[inline]
+    pub fn grid_to_world(&self, point: &Vec3f) -> Vec3f {
+        point * self.voxel_size
+    }
+
+    /// Converts point from world coordinates to grid coordinates, see [Volume::grid_to_world]
+    #[inline]
+    pub fn world_to_grid(&self, point: &Vec3f) -> Vec3f {
+        point / self.voxel_size
+    }
+
+    ///
+    /// Returns min and max indices (inclusive) of grid points in narrow band, including mirror images for volumes with [Symmetry].
+    /// Returns `None` for empty volume.
+    ///
+    pub fn index_bounds(&self) -> Option<(Vec3i, Vec3i)> {
+        self.active_values().into_iter().fold(None, |bounds, (index, _)| match bounds {
+            Some((min, max)) => Some((index.inf(&min), index.sup(&max))),
+            None => Some((index, index)),
+        })
+    }
+
+    /// Returns bounding box of grid points in narrow band in world coordinates, `None` for empty volume
+    pub fn bounds(&self) -> Option<Box3<f32>> {
+        let (min, max) = self.index_bounds()?;
+        Some(Box3::new(self.index_to_world(&min), self.index_to_world(&max)))
+    }
+
     ///
     /// Creates new SDF grid by evaluating given function on each grid point.
     /// Inside is negative.
@@ -66,51 +372,701 @@ impl Volume {
             }
         }
 
-        // TODO: prune
-
-        Self { grid, voxel_size }
+        Self::new(grid, voxel_size)
     }
 
-    pub fn union(mut self, mut other: Self) -> Self {
-        self.grid.flood_fill();
-        other.grid.flood_fill();
-        self.grid.union(other.grid);
+    ///
+    /// Replaces parts of grid where all grid points are in narrow band, have same sign and differ by no more than
+    /// `tolerance` by tiles storing single value. Saves memory when volume has large uniform regions,
+    /// e.g. interior of solids after CSG or truncated distance fields. Zero tolerance keeps values unchanged,
+    /// with positive tolerance values of merged grid points are replaced by one of them.
+    /// Tolerance should be much less than voxel size, otherwise meshing with non-zero iso value may lose details.
+    ///
+    pub fn prune(mut self, tolerance: f32) -> Self {
+        self.grid.prune(tolerance);
         self
     }
 
-    pub fn intersect(mut self, mut other: Self) -> Self {
-        self.grid.flood_fill();
-        other.grid.flood_fill();
-        self.grid.intersect(other.grid);
-        self
+    /// CSG union, result is pruned when [Volume::auto_prune] tolerance of `self` is set
+    pub fn union(self, other: Self) -> Self {
+        self.sharp_csg(other, VolumeGrid::union)
     }
 
-    pub fn subtract(mut self, mut other: Self) -> Self {
-        self.grid.flood_fill();
+    /// CSG intersection, result is pruned when [Volume::auto_prune] tolerance of `self` is set
+    pub fn intersect(self, other: Self) -> Self {
+        self.sharp_csg(other, VolumeGrid::intersect)
+    }
+
+    /// CSG subtraction, result is pruned when [Volume::auto_prune] tolerance of `self` is set
+    pub fn subtract(self, other: Self) -> Self {
+        self.sharp_csg(other, VolumeGrid::subtract)
+    }
+
+    fn sharp_csg(self, other: Self, csg: fn(&mut VolumeGrid, Box<VolumeGrid>)) -> Self {
+        trace_span!("csg");
+
+        // Volumes with same symmetry are combined without unfolding
+        let (mut result, mut other) = self.with_common_symmetry(other);
+
+        // Inputs are consumed by operation, so their stats can't be computed lazily
+        let inputs = trace_enabled!().then(|| (result.stats(), other.stats()));
+
+        result.grid.flood_fill();
         other.grid.flood_fill();
-        self.grid.subtract(other.grid);
-        self
+        csg(&mut result.grid, other.grid);
+
+        let result = match result.auto_prune {
+            Some(tolerance) => result.prune(tolerance),
+            None => result,
+        };
+
+        if let Some((a, b)) = inputs {
+            let stats = result.stats();
+            trace_counters!(
+                input_leaf_nodes = a.leaf_nodes + b.leaf_nodes,
+                input_tiles = a.tiles + b.tiles,
+                input_memory_bytes = a.memory_bytes + b.memory_bytes,
+                leaf_nodes = stats.leaf_nodes,
+                tiles = stats.tiles,
+                active_voxels = stats.active_voxels,
+                memory_bytes = stats.memory_bytes,
+            );
+        }
+
+        result
+    }
+
+    /// Returns volumes with same symmetry, volumes are unfolded when their symmetries are different
+    fn with_common_symmetry(self, other: Self) -> (Self, Self) {
+        if self.symmetry == other.symmetry {
+            (self, other)
+        } else {
+            (self.unfold(), other.unfold())
+        }
+    }
+
+    ///
+    /// Union with smooth blending of surfaces where they meet.
+    /// `k` is blending radius in world units, it should not exceed narrow band width of volumes.
+    ///
+    pub fn smooth_union(self, other: Self, k: f32) -> Self {
+        self.smooth_csg(other, k, Self::union, smooth_min)
+    }
+
+    ///
+    /// Intersection with smooth blending of surfaces where they meet.
+    /// `k` is blending radius in world units, it should not exceed narrow band width of volumes.
+    ///
+    pub fn smooth_intersect(self, other: Self, k: f32) -> Self {
+        self.smooth_csg(other, k, Self::intersect, smooth_max)
+    }
+
+    ///
+    /// Subtraction with smooth blending of surfaces where they meet.
+    /// `k` is blending radius in world units, it should not exceed narrow band width of volumes.
+    ///
+    pub fn smooth_subtract(self, other: Self, k: f32) -> Self {
+        self.smooth_csg(other, k, Self::subtract, |a, b, k| smooth_max(a, -b, k))
+    }
+
+    ///
+    /// Performs sharp CSG and then replaces values in overlapping narrow bands by smooth blend of inputs
+    ///
+    fn smooth_csg(
+        self,
+        other: Self,
+        k: f32,
+        csg: fn(Self, Self) -> Self,
+        blend: fn(f32, f32, f32) -> f32,
+    ) -> Self {
+        let (volume, other) = self.with_common_symmetry(other);
+        let a = volume.grid.clone();
+        let b = other.grid.clone();
+        let mut result = csg(volume, other);
+
+        let mut smooth_blend = SmoothBlendVisitor {
+            a: &a,
+            b: &b,
+            k,
+            blend,
+            values: Vec::new(),
+        };
+        result.grid.visit_leafs(&mut smooth_blend);
+
+        for (index, value) in smooth_blend.values {
+            if let Some(v) = result.grid.at_mut(&index) {
+                *v = value;
+            }
+        }
+
+        result
     }
 
-    pub fn offset(mut self, distance: f32) -> Self {
-        self.grid.remove_if(|val| val.abs() > self.voxel_size);
+    pub fn offset(self, distance: f32) -> Self {
+        trace_span!("offset", distance = distance);
+
+        // Distances are propagated across symmetry planes
+        let mut volume = self.unfold();
+        let voxel_size = volume.voxel_size;
+        volume.grid.remove_if(|val| val.abs() > voxel_size);
 
-        let mut extension_distance = distance.abs() + self.voxel_size + self.voxel_size;
+        let mut extension_distance = distance.abs() + voxel_size + voxel_size;
         extension_distance.set_sign(distance.sign());
 
-        let mut sweep = FastSweeping::new(self.voxel_size, extension_distance);
-        sweep.fast_sweep(self.grid.as_mut());
+        let mut sweep = FastSweeping::new(voxel_size, extension_distance);
+        sweep.fast_sweep(volume.grid.as_mut());
 
         let mut offset = ValueMutVisitor::<VolumeGrid, _>::from_fn(|v| *v -= distance);
-        self.grid.visit_values_mut(&mut offset);
+        volume.grid.visit_values_mut(&mut offset);
 
-        self
+        trace_counters!(
+            voxels_activated = volume.active_voxels_count(),
+            leaf_nodes_allocated = volume.leafs_count(),
+        );
+
+        volume
+    }
+
+    ///
+    /// Smooths surface by mean curvature flow, e.g. to reduce voxelization artifacts before meshing.
+    /// Every iteration moves distances in narrow band towards average of their 6 neighbors by `strength`
+    /// (in range `[0, 1]`), then narrow band is redistanced around new zero level set, so volume stays distance field.
+    /// Laplacian of distance field is proportional to mean curvature, so bumps shrink faster than smooth regions.
+    ///
+    /// ## Example
+    /// ```ignore
+    /// let volume = Volume::from_mesh(&scan, 0.01)?.smooth(5, 0.5);
+    /// let mesh = volume.to_mesh(MesherKind::MarchingCubes);
+    /// ```
+    ///
+    pub fn smooth(self, iterations: usize, strength: f32) -> Self {
+        trace_span!("smooth", iterations = iterations, strength = strength);
+
+        let strength = strength.clamp(0.0, 1.0);
+        let mut volume = self.unfold();
+        let band = volume
+            .stored_values()
+            .iter()
+            .fold(volume.voxel_size, |band, (_, v)| band.max(v.abs()));
+
+        for _ in 0..iterations {
+            let values = volume.stored_values();
+
+            #[cfg(feature = "rayon")]
+            let values_iter = values.par_iter();
+            #[cfg(not(feature = "rayon"))]
+            let values_iter = values.iter();
+
+            // Grid points at border of narrow band have no average and keep their values
+            let smoothed: Vec<_> = values_iter
+                .map(|(index, value)| {
+                    let mut sum = 0.0;
+
+                    for axis in 0..3 {
+                        let mut offset = Vec3i::zeros();
+                        offset[axis] = 1;
+
+                        match (volume.grid.at(&(index + offset)), volume.grid.at(&(index - offset))) {
+                            (Some(next), Some(prev)) => sum += next + prev,
+                            _ => return *value,
+                        }
+                    }
+
+                    value + strength * (sum / 6.0 - value)
+                })
+                .collect();
+
+            for ((index, _), value) in values.iter().zip(smoothed) {
+                if let Some(v) = volume.grid.at_mut(index) {
+                    *v = value;
+                }
+            }
+        }
+
+        volume.redistance(band);
+
+        trace_counters!(voxels_active = volume.active_voxels_count());
+
+        volume
+    }
+
+    /// Rebuilds narrow band of given width on both sides of zero level set by fast sweeping
+    fn redistance(&mut self, band: f32) {
+        let voxel_size = self.voxel_size;
+        self.grid.remove_if(|val| val.abs() > voxel_size);
+
+        FastSweeping::new(voxel_size, band).fast_sweep(self.grid.as_mut());
+        FastSweeping::new(voxel_size, -band).fast_sweep(self.grid.as_mut());
+    }
+
+    ///
+    /// Trilinear interpolation of distance at `point`.
+    /// Returns `None` when any of surrounding grid points is outside of narrow band.
+    ///
+    pub fn sample(&self, point: &Vec3f) -> Option<f32> {
+        let grid_point = self.world_to_grid(point);
+        let floor = grid_point.map(|c| c.floor());
+        let frac = grid_point - floor;
+        let base = floor.map(|c| c as isize);
+
+        let mut value = 0.0;
+
+        for i in 0..8 {
+            let offset = Vec3i::new(i & 1, (i >> 1) & 1, (i >> 2) & 1);
+            let corner = self.value_at(&(base + offset))?;
+            let weight = (0..3)
+                .map(|axis| if offset[axis] == 1 { frac[axis] } else { 1.0 - frac[axis] })
+                .product::<f32>();
+
+            value += weight * corner;
+        }
+
+        Some(value)
+    }
+
+    ///
+    /// Gradient of distance field at `point` computed by central differences of trilinearly interpolated distances.
+    /// Returns `None` outside of narrow band or where gradient vanishes.
+    ///
+    pub fn gradient(&self, point: &Vec3f) -> Option<Vec3f> {
+        let h = self.voxel_size * 0.5;
+        let inv_2h = 1.0 / (2.0 * h);
+        let mut gradient = Vec3f::zeros();
+
+        for axis in 0..3 {
+            let mut offset = Vec3f::zeros();
+            offset[axis] = h;
+            gradient[axis] = (self.sample(&(point + offset))? - self.sample(&(point - offset))?) * inv_2h;
+        }
+
+        if gradient.norm_squared() > 0.0 {
+            Some(gradient)
+        } else {
+            None
+        }
+    }
+
+    /// Unit normal of surface (normalized gradient) at `point`, points outside
+    #[inline]
+    pub fn normal(&self, point: &Vec3f) -> Option<Vec3f> {
+        self.gradient(point).map(|g| g.normalize())
+    }
+
+    ///
+    /// Batched [Volume::sample] and [Volume::gradient], points are processed in parallel when `rayon` feature is enabled.
+    /// Returns distance and gradient for every point. Points outside of narrow band get `f32::MAX` distance,
+    /// zero gradient is returned where it can't be computed (e.g. near boundary of narrow band).
+    ///
+    /// ## Example
+    /// ```ignore
+    /// let collisions = volume
+    ///     .query_points(&robot_points)
+    ///     .into_iter()
+    ///     .filter(|(distance, _)| *distance < margin);
+    /// ```
+    ///
+    pub fn query_points(&self, points: &[Vec3f]) -> Vec<(f32, Vec3f)> {
+        #[cfg(feature = "rayon")]
+        let points_iter = points.par_iter();
+        #[cfg(not(feature = "rayon"))]
+        let points_iter = points.iter();
+
+        points_iter
+            .map(|point| match self.sample(point) {
+                Some(distance) => (distance, self.gradient(point).unwrap_or_else(Vec3f::zeros)),
+                None => (f32::far(), Vec3f::zeros()),
+            })
+            .collect()
+    }
+
+    ///
+    /// Returns copy of volume sampled on grid with given voxel size.
+    /// Values are trilinearly interpolated, grid points where interpolation is not possible are skipped.
+    ///
+    pub fn resample(&self, voxel_size: f32) -> Self {
+        let mut grid = VolumeGrid::empty(Vec3i::zeros());
+        let mut visited = HashSet::new();
+
+        for (index, _) in self.active_values() {
+            let point = self.index_to_world(&index);
+            let min = ((point.add_scalar(-self.voxel_size)) / voxel_size).map(|x| x.ceil() as isize);
+            let max = ((point.add_scalar(self.voxel_size)) / voxel_size).map(|x| x.floor() as isize);
+
+            for x in min.x..=max.x {
+                for y in min.y..=max.y {
+                    for z in min.z..=max.z {
+                        let new_index = Vec3i::new(x, y, z);
+
+                        if !visited.insert(new_index) {
+                            continue;
+                        }
+
+                        if let Some(value) = self.sample(&(new_index.cast() * voxel_size)) {
+                            grid.insert(&new_index, value);
+                        }
+                    }
+                }
+            }
+        }
+
+        Self::new(grid, voxel_size).with_auto_prune(self.auto_prune)
+    }
+
+    ///
+    /// Returns copy of volume moved by rigid transform `iso`, e.g. to position part before CSG
+    /// without re-voxelizing its mesh. Values are sampled from this volume at inverse transformed grid points
+    /// and divided by gradient length to restore distance property lost by interpolation.
+    ///
+    /// ## Example
+    /// ```ignore
+    /// let rotation = UnitQuaternion::from_axis_angle(&Vector3::z_axis(), FRAC_PI_4);
+    /// let lid = lid.transformed(Isometry3::from_parts(Translation3::new(0.0, 0.0, 1.0), rotation));
+    /// let part = body.union(lid);
+    /// ```
+    ///
+    pub fn transformed(&self, iso: Isometry3<f32>) -> Self {
+        let mut grid = VolumeGrid::empty(Vec3i::zeros());
+        let mut visited = HashSet::new();
+
+        for (index, _) in self.active_values() {
+            let point = iso.transform_point(&Point3::from(self.index_to_world(&index))).coords;
+            let min = self.world_to_grid(&point.add_scalar(-self.voxel_size)).map(|x| x.ceil() as isize);
+            let max = self.world_to_grid(&point.add_scalar(self.voxel_size)).map(|x| x.floor() as isize);
+
+            for x in min.x..=max.x {
+                for y in min.y..=max.y {
+                    for z in min.z..=max.z {
+                        let new_index = Vec3i::new(x, y, z);
+
+                        if !visited.insert(new_index) {
+                            continue;
+                        }
+
+                        let source = iso.inverse_transform_point(&Point3::from(self.index_to_world(&new_index))).coords;
+
+                        let Some(value) = self.sample(&source) else {
+                            continue;
+                        };
+
+                        let value = match self.gradient(&source) {
+                            Some(gradient) => value / gradient.norm(),
+                            None => value,
+                        };
+
+                        grid.insert(&new_index, value);
+                    }
+                }
+            }
+        }
+
+        Self::new(grid, self.voxel_size).with_auto_prune(self.auto_prune)
+    }
+
+    ///
+    /// Interpolates distances of two volumes: `(1 - t) * self + t * other`. Useful for shape morphing.
+    /// Result is defined on union of narrow bands of both volumes. Outside of its narrow band distance of volume
+    /// is approximated by its band width, so shapes should overlap or have wide enough narrow bands.
+    /// `other` is resampled when voxel sizes are different.
+    ///
+    pub fn lerp(self, other: Self, t: f32) -> Self {
+        let mut volume = self.unfold();
+        let mut other = if (other.voxel_size - volume.voxel_size).abs() > f32::EPSILON * volume.voxel_size {
+            other.resample(volume.voxel_size)
+        } else {
+            other.unfold()
+        };
+
+        volume.grid.flood_fill();
+        other.grid.flood_fill();
+
+        let self_values = volume.active_values();
+        let other_values = other.active_values();
+
+        // Max distance stored in narrow band
+        let band = |values: &[(Vec3i, f32)]| values.iter().fold(volume.voxel_size, |band, (_, v)| band.max(v.abs()));
+        let self_band = band(&self_values);
+        let other_band = band(&other_values);
+
+        let value_at = |volume: &Self, band: f32, index: &Vec3i| match volume.grid.at(index) {
+            Some(value) => *value,
+            None if volume.grid.sign_at(index) == Sign::Negative => -band,
+            None => band,
+        };
+
+        let mut grid = VolumeGrid::empty(Vec3i::zeros());
+
+        for (index, _) in self_values.iter().chain(other_values.iter()) {
+            if grid.at(index).is_some() {
+                continue;
+            }
+
+            let a = value_at(&volume, self_band, index);
+            let b = value_at(&other, other_band, index);
+            grid.insert(index, a + (b - a) * t);
+        }
+
+        Self::new(grid, volume.voxel_size).with_auto_prune(volume.auto_prune)
+    }
+
+    /// Returns indices and values of all grid points in narrow band, including mirror images of stored ones
+    pub(in crate::voxel) fn active_values(&self) -> Vec<(Vec3i, f32)> {
+        let values = self.stored_values();
+
+        if self.symmetry.is_none() {
+            return values;
+        }
+
+        values
+            .into_iter()
+            .flat_map(|(index, value)| self.symmetry.images(&index).into_iter().map(move |image| (image, value)))
+            .collect()
+    }
+
+    /// Returns indices and values of grid points in narrow band stored in grid
+    fn stored_values(&self) -> Vec<(Vec3i, f32)> {
+        let mut visitor = ActiveValuesVisitor { values: Vec::new() };
+        self.grid.visit_leafs(&mut visitor);
+        visitor.values
+    }
+
+    /// Returns number of leaf nodes allocated in grid
+    pub(in crate::voxel) fn leafs_count(&self) -> usize {
+        let mut visitor = CountVisitor::default();
+        self.grid.visit_leafs(&mut visitor);
+        visitor.leafs
+    }
+
+    /// Returns statistics of stored grid, only stored part is accounted for volumes with [Symmetry]
+    pub fn stats(&self) -> VolumeStats {
+        let mut visitor = CountVisitor::default();
+        self.grid.visit_leafs(&mut visitor);
+
+        VolumeStats {
+            leaf_nodes: visitor.leafs,
+            tiles: visitor.tiles,
+            active_voxels: visitor.voxels,
+            memory_bytes: self.grid.memory_usage(),
+            depth: VolumeGrid::depth(),
+        }
+    }
+
+    /// Returns number of grid points in narrow band stored in grid
+    pub(in crate::voxel) fn active_voxels_count(&self) -> usize {
+        let mut visitor = CountVisitor::default();
+        self.grid.visit_leafs(&mut visitor);
+        visitor.voxels
+    }
+
+    /// Returns value at grid point `index`, mirrored when volume has symmetry
+    #[inline]
+    fn value_at(&self, index: &Vec3i) -> Option<f32> {
+        self.grid.at(&self.symmetry.stored_index(index)).copied()
+    }
+
+    ///
+    /// Estimates area of zero level set without meshing. Area is integral of smoothed Dirac delta of distance
+    /// over narrow band, so narrow band should contain grid points within 1.5 voxels from surface.
+    /// Cheap enough to be used as progress metric in optimization loops, error is usually within few percents.
+    ///
+    pub fn surface_area_estimate(&self) -> f32 {
+        self.surface_integrals().0
+    }
+
+    ///
+    /// Estimates volume enclosed by zero level set without meshing. Volume is computed by divergence theorem
+    /// as surface integral of `x · n / 3` where `n` is normalized gradient of distance,
+    /// see [Volume::surface_area_estimate] for requirements to narrow band.
+    ///
+    pub fn enclosed_volume_estimate(&self) -> f32 {
+        self.surface_integrals().1
+    }
+
+    /// Returns area and enclosed volume of zero level set
+    fn surface_integrals(&self) -> (f32, f32) {
+        let epsilon = DIRAC_HALF_WIDTH * self.voxel_size;
+        let voxel_volume = (self.voxel_size as f64).powi(3);
+        let mut area = 0.0;
+        let mut volume = 0.0;
+
+        for (index, value) in self.active_values() {
+            if value.abs() >= epsilon {
+                continue;
+            }
+
+            let dirac = (1.0 + (std::f32::consts::PI * value / epsilon).cos()) / (2.0 * epsilon);
+            let weight = dirac as f64 * voxel_volume;
+            area += weight;
+
+            if let Some(normal) = self.grid_gradient(&index).and_then(|g| g.try_normalize(0.0)) {
+                let position = self.index_to_world(&index);
+                volume += weight * position.dot(&normal) as f64 / 3.0;
+            }
+        }
+
+        (area as f32, volume as f32)
+    }
+
+    /// Gradient at grid point by central differences, one-sided differences are used at border of narrow band
+    fn grid_gradient(&self, index: &Vec3i) -> Option<Vec3f> {
+        let value = self.value_at(index)?;
+        let mut gradient = Vec3f::zeros();
+
+        for axis in 0..3 {
+            let mut offset = Vec3i::zeros();
+            offset[axis] = 1;
+
+            gradient[axis] = match (self.value_at(&(index + offset)), self.value_at(&(index - offset))) {
+                (Some(next), Some(prev)) => (next - prev) * 0.5,
+                (Some(next), None) => next - value,
+                (None, Some(prev)) => value - prev,
+                (None, None) => return None,
+            };
+        }
+
+        Some(gradient / self.voxel_size)
+    }
+
+    /// Replaces values of all grid points in narrow band by `func(index, value)`
+    pub(in crate::voxel) fn map_active_values<TFn: Fn(&Vec3i, f32) -> f32>(&mut self, func: TFn) {
+        for (index, value) in self.stored_values() {
+            if let Some(v) = self.grid.at_mut(&index) {
+                *v = func(&index, value);
+            }
+        }
     }
 
     pub(in crate::voxel) fn grid(&self) -> &VolumeGrid {
         // HIDE
         &self.grid
     }
+
+    pub(in crate::voxel) fn grid_mut(&mut self) -> &mut VolumeGrid {
+        &mut self.grid
+    }
+}
+
+struct SmoothBlendVisitor<'a> {
+    a: &'a VolumeGrid,
+    b: &'a VolumeGrid,
+    k: f32,
+    blend: fn(f32, f32, f32) -> f32,
+    values: Vec<(Vec3i, f32)>,
+}
+
+impl<T: TreeNode<Value = f32>> Visitor<T> for SmoothBlendVisitor<'_> {
+    fn tile(&mut self, _: Tile<T::Value>) {
+        // Tiles are far from surface
+    }
+
+    fn dense(&mut self, dense: &T) {
+        let min = dense.origin();
+        let size = T::resolution() as isize;
+
+        for x in min.x..min.x + size {
+            for y in min.y..min.y + size {
+                for z in min.z..min.z + size {
+                    let index = Vec3i::new(x, y, z);
+
+                    if dense.at(&index).is_none() {
+                        continue;
+                    }
+
+                    // Blend only where both distances are known
+                    if let (Some(&a), Some(&b)) = (self.a.at(&index), self.b.at(&index)) {
+                        self.values.push((index, (self.blend)(a, b, self.k)));
+                    }
+                }
+            }
+        }
+    }
+}
+
+struct ActiveValuesVisitor {
+    values: Vec<(Vec3i, f32)>,
+}
+
+impl<T: TreeNode<Value = f32>> Visitor<T> for ActiveValuesVisitor {
+    fn tile(&mut self, _: Tile<T::Value>) {
+        // Tiles are outside of narrow band
+    }
+
+    fn dense(&mut self, dense: &T) {
+        let min = dense.origin();
+        let size = T::resolution() as isize;
+
+        for x in min.x..min.x + size {
+            for y in min.y..min.y + size {
+                for z in min.z..min.z + size {
+                    let index = Vec3i::new(x, y, z);
+
+                    if let Some(value) = dense.at(&index) {
+                        self.values.push((index, *value));
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[derive(Default)]
+struct CountVisitor {
+    leafs: usize,
+    tiles: usize,
+    voxels: usize,
+}
+
+impl<T: TreeNode<Value = f32>> Visitor<T> for CountVisitor {
+    fn tile(&mut self, _: Tile<T::Value>) {
+        self.tiles += 1;
+    }
+
+    fn dense(&mut self, dense: &T) {
+        let min = dense.origin();
+        let size = T::resolution() as isize;
+        self.leafs += 1;
+
+        for x in min.x..min.x + size {
+            for y in min.y..min.y + size {
+                for z in min.z..min.z + size {
+                    if dense.at(&Vec3i::new(x, y, z)).is_some() {
+                        self.voxels += 1;
+                    }
+                }
+            }
+        }
+    }
+}
+
+struct TilesVisitor {
+    tiles: Vec<(Vec3i, usize, f32)>,
+}
+
+impl<T: TreeNode<Value = f32>> Visitor<T> for TilesVisitor {
+    fn tile(&mut self, tile: Tile<T::Value>) {
+        self.tiles.push((tile.origin, tile.size, tile.value));
+    }
+
+    fn dense(&mut self, _: &T) {}
+}
+
+impl Display for Volume {
+    /// Summary of grid: voxel size, allocated leaf nodes, active voxels and symmetry planes
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "voxel size: {}, leaf nodes: {}, active voxels: {}",
+            self.voxel_size,
+            self.leafs_count(),
+            self.active_voxels_count()
+        )?;
+
+        if !self.symmetry.is_none() {
+            let [x, y, z] = self.symmetry.axes();
+            write!(f, ", symmetry: x={} y={} z={}", x, y, z)?;
+        }
+
+        Ok(())
+    }
 }
 
 impl Clone for Volume {
@@ -118,6 +1074,8 @@ impl Clone for Volume {
         Self {
             grid: self.grid.clone(),
             voxel_size: self.voxel_size,
+            auto_prune: self.auto_prune,
+            symmetry: self.symmetry,
         }
     }
 }
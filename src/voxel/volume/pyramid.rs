@@ -0,0 +1,127 @@
+use std::collections::HashMap;
+
+use super::{MesherKind, Volume};
+use crate::{
+    helpers::{aliases::Vec3i, trace::trace_span},
+    mesh::corner_table::prelude::CornerTableF,
+    voxel::*,
+};
+
+///
+/// Levels of detail of volume built by [Volume::build_pyramid]. Level `0` is the original volume,
+/// voxel size of every next level is twice larger, e.g. for quick previews or meshes of distant objects.
+///
+/// ## Example
+/// ```ignore
+/// let pyramid = volume.build_pyramid(4);
+/// let preview = pyramid.to_mesh(3, MesherKind::MarchingCubes)?;
+/// ```
+///
+#[derive(Debug, Clone)]
+pub struct VolumePyramid {
+    levels: Vec<Volume>,
+}
+
+impl VolumePyramid {
+    /// Returns number of levels
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.levels.len()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.levels.is_empty()
+    }
+
+    /// Returns volume of given level, `0` is the finest one
+    #[inline]
+    pub fn level(&self, level: usize) -> Option<&Volume> {
+        self.levels.get(level)
+    }
+
+    /// Returns volumes of all levels from finest to coarsest
+    #[inline]
+    pub fn levels(&self) -> &[Volume] {
+        &self.levels
+    }
+
+    /// Extracts zero level set of given level, see [Volume::to_mesh]. Returns `None` when there is no such level.
+    #[inline]
+    pub fn to_mesh(&self, level: usize, kind: MesherKind) -> Option<CornerTableF> {
+        self.level(level)?.to_mesh(kind)
+    }
+}
+
+impl Volume {
+    ///
+    /// Builds pyramid of `levels` progressively coarser volumes, see [VolumePyramid].
+    /// Grid points of coarse level coincide with every second grid point of finer one and keep their distances.
+    /// Coarse level needs wider narrow band (in world units), so missing distances are estimated from fine grid points
+    /// around: distance is the smallest of `|distance| + offset` over them, sign is taken from fine grid.
+    ///
+    pub fn build_pyramid(&self, levels: usize) -> VolumePyramid {
+        trace_span!("build_pyramid", levels = levels);
+
+        let mut pyramid = Vec::with_capacity(levels);
+
+        if levels == 0 {
+            return VolumePyramid { levels: pyramid };
+        }
+
+        pyramid.push(self.clone());
+
+        // Coarse grid points may lie across symmetry planes or outside of narrow band, where sign is needed
+        let mut finest = self.clone().unfold();
+        finest.grid.flood_fill();
+
+        for level in 1..levels {
+            let fine = if level == 1 { &finest } else { &pyramid[level - 1] };
+            let coarse = fine.downsample();
+            pyramid.push(coarse);
+        }
+
+        VolumePyramid { levels: pyramid }
+    }
+
+    /// Returns flood filled volume with twice larger voxel size, signs of inactive grid points of this volume should be resolved
+    fn downsample(&self) -> Self {
+        let mut distances: HashMap<Vec3i, f32> = HashMap::new();
+
+        for (index, value) in self.stored_values() {
+            // Coarse grid points within one fine voxel along every axis
+            let min = index.map(|i| i.div_euclid(2));
+            let max = index.map(|i| (i + 1).div_euclid(2));
+
+            for x in min.x..=max.x {
+                for y in min.y..=max.y {
+                    for z in min.z..=max.z {
+                        let coarse = Vec3i::new(x, y, z);
+                        let offset = (coarse * 2 - index).cast::<f32>().norm() * self.voxel_size;
+                        let distance = distances.entry(coarse).or_insert(f32::MAX);
+                        *distance = distance.min(value.abs() + offset);
+                    }
+                }
+            }
+        }
+
+        let mut grid = VolumeGrid::empty(Vec3i::zeros());
+
+        for (coarse, distance) in distances {
+            let fine = coarse * 2;
+            let value = match self.grid.at(&fine) {
+                Some(value) => *value,
+                None => match self.grid.sign_at(&fine) {
+                    Sign::Negative => -distance,
+                    _ => distance,
+                },
+            };
+
+            grid.insert(&coarse, value);
+        }
+
+        grid.flood_fill();
+
+        Self::new(grid, self.voxel_size * 2.0).with_auto_prune(self.auto_prune)
+    }
+}